@@ -0,0 +1,267 @@
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+use tauri::{async_runtime::spawn_blocking, AppHandle, Manager};
+use url::Url;
+
+use crate::annotations::csv_field;
+use crate::app_error::{AppError, AppResult};
+use crate::read_only;
+
+const DB_FILENAME: &str = "access_log.sqlite3";
+const DEFAULT_QUERY_LIMIT: u32 = 1_000;
+const MAX_QUERY_LIMIT: u32 = 100_000;
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+/// Opened on the first request recorded or query made.
+static DB: Mutex<Option<Connection>> = Mutex::new(None);
+/// Requests waiting for the writer thread, so recording never blocks the request.
+static QUEUE: OnceLock<Mutex<Sender<Request>>> = OnceLock::new();
+
+struct Request {
+    at: i64,
+    scheme: String,
+    host: String,
+    path: String,
+    status: Option<u16>,
+    bytes: Option<i64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLogEntry {
+    id: i64,
+    /// Seconds since the Unix epoch.
+    at: i64,
+    scheme: String,
+    /// Host, with the port when the URL named one.
+    host: String,
+    path: String,
+    /// HTTP status; `None` for SFTP and WebDAV transfers.
+    status: Option<u16>,
+    /// Body length the server declared, or bytes transferred for SFTP and WebDAV.
+    bytes: Option<i64>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessLogExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLogExportReport {
+    path: String,
+    count: u32,
+}
+
+/// Keeps the handle used to find the app data dir and starts the writer thread. Called once at
+/// startup.
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+    let (tx, rx) = mpsc::channel::<Request>();
+    if QUEUE.set(Mutex::new(tx)).is_err() {
+        return;
+    }
+    std::thread::spawn(move || {
+        for r in rx {
+            // A failed write must not take the app down; the next one retries opening the log.
+            let _ = with_conn(|conn| {
+                conn.execute(
+                    "INSERT INTO requests (at, scheme, host, path, status, bytes)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![r.at, r.scheme, r.host, r.path, r.status, r.bytes],
+                )
+            });
+        }
+    });
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn db_error(e: rusqlite::Error) -> AppError {
+    AppError::Task(format!("access log database error: {e}"))
+}
+
+fn open_db(app: &AppHandle) -> AppResult<Connection> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Task(format!("app data dir unavailable: {e}")))?;
+    std::fs::create_dir_all(&dir)?;
+    let conn = Connection::open(dir.join(DB_FILENAME)).map_err(db_error)?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS requests (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            at INTEGER NOT NULL,
+            scheme TEXT NOT NULL,
+            host TEXT NOT NULL,
+            path TEXT NOT NULL,
+            status INTEGER,
+            bytes INTEGER
+        );
+        CREATE INDEX IF NOT EXISTS requests_by_time ON requests(at);
+        CREATE INDEX IF NOT EXISTS requests_by_host ON requests(host, at);",
+    )
+    .map_err(db_error)?;
+    Ok(conn)
+}
+
+fn with_conn<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> AppResult<T> {
+    let app = APP
+        .get()
+        .ok_or_else(|| AppError::Task("access log is not initialized".into()))?;
+    let mut guard = DB
+        .lock()
+        .map_err(|_| AppError::Task("access log lock poisoned".into()))?;
+    if guard.is_none() {
+        *guard = Some(open_db(app)?);
+    }
+    let conn = guard
+        .as_ref()
+        .ok_or_else(|| AppError::Task("access log database unavailable".into()))?;
+    f(conn).map_err(db_error)
+}
+
+/// Adds a request to `url` to the access log. The query string is left out: it can carry
+/// signatures and tokens.
+pub fn record(url: &Url, status: Option<u16>, bytes: Option<u64>) {
+    let Some(queue) = QUEUE.get() else {
+        return;
+    };
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    let request = Request {
+        at: now_secs(),
+        scheme: url.scheme().to_string(),
+        host: match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host,
+        },
+        path: url.path().to_string(),
+        status,
+        bytes: bytes.map(|b| b.min(i64::MAX as u64) as i64),
+    };
+    if let Ok(queue) = queue.lock() {
+        let _ = queue.send(request);
+    }
+}
+
+fn row_to_entry(row: &Row) -> rusqlite::Result<AccessLogEntry> {
+    Ok(AccessLogEntry {
+        id: row.get(0)?,
+        at: row.get(1)?,
+        scheme: row.get(2)?,
+        host: row.get(3)?,
+        path: row.get(4)?,
+        status: row.get(5)?,
+        bytes: row.get(6)?,
+    })
+}
+
+/// The newest `limit` entries (all with `None`) matching the filters, oldest first. `until` is
+/// exclusive.
+fn query_entries(
+    conn: &Connection,
+    host: Option<&str>,
+    since: Option<i64>,
+    until: Option<i64>,
+    limit: Option<u32>,
+) -> rusqlite::Result<Vec<AccessLogEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, at, scheme, host, path, status, bytes FROM (
+            SELECT * FROM requests
+            WHERE (?1 IS NULL OR host = ?1)
+              AND (?2 IS NULL OR at >= ?2)
+              AND (?3 IS NULL OR at < ?3)
+            ORDER BY at DESC, id DESC
+            LIMIT ?4
+         )
+         ORDER BY at, id",
+    )?;
+    let limit = limit.map_or(-1, i64::from);
+    let rows = stmt.query_map(params![host, since, until, limit], row_to_entry)?;
+    rows.collect()
+}
+
+fn host_filter(host: Option<String>) -> Option<String> {
+    host.map(|h| h.trim().to_ascii_lowercase())
+        .filter(|h| !h.is_empty())
+}
+
+/// Remote requests made by the app, optionally only those to `host` between `since` and
+/// `until` (seconds since the Unix epoch). Returns the newest `limit` entries, oldest first.
+#[tauri::command]
+pub async fn query_access_log(
+    host: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    limit: Option<u32>,
+) -> AppResult<Vec<AccessLogEntry>> {
+    let host = host_filter(host);
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT);
+    spawn_blocking(move || {
+        with_conn(|conn| query_entries(conn, host.as_deref(), since, until, Some(limit)))
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn entries_csv(entries: &[AccessLogEntry]) -> String {
+    let mut out = String::from("id,at,scheme,host,path,status,bytes\n");
+    for e in entries {
+        let fields = [
+            e.id.to_string(),
+            e.at.to_string(),
+            csv_field(&e.scheme),
+            csv_field(&e.host),
+            csv_field(&e.path),
+            e.status.map(|s| s.to_string()).unwrap_or_default(),
+            e.bytes.map(|b| b.to_string()).unwrap_or_default(),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Writes every access log entry matching the filters of `query_access_log` to `dest_path`.
+#[tauri::command]
+pub async fn export_access_log(
+    host: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    format: AccessLogExportFormat,
+    dest_path: String,
+) -> AppResult<AccessLogExportReport> {
+    let dest = dest_path.trim();
+    if dest.is_empty() {
+        return Err(AppError::Invalid("destination path is empty".into()));
+    }
+    let dest = PathBuf::from(dest);
+    read_only::ensure_writable(&dest, "access log export")?;
+    let host = host_filter(host);
+    spawn_blocking(move || {
+        let entries = with_conn(|conn| query_entries(conn, host.as_deref(), since, until, None))?;
+        let body = match format {
+            AccessLogExportFormat::Csv => entries_csv(&entries),
+            AccessLogExportFormat::Json => serde_json::to_string_pretty(&entries)
+                .map_err(|e| AppError::Task(format!("serializing access log failed: {e}")))?,
+        };
+        std::fs::write(&dest, body)?;
+        Ok(AccessLogExportReport {
+            path: dest.display().to_string(),
+            count: entries.len().min(u32::MAX as usize) as u32,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
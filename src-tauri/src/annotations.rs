@@ -0,0 +1,278 @@
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{async_runtime::spawn_blocking, AppHandle, Manager, State};
+
+use crate::app_error::{AppError, AppResult};
+use crate::read_only;
+
+const DB_FILENAME: &str = "annotations.sqlite3";
+
+/// Lazily opened connection to the annotations database in the app data dir.
+#[derive(Clone, Default)]
+pub struct AnnotationStore(Arc<Mutex<Option<Connection>>>);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    id: i64,
+    source: String,
+    shard: String,
+    sample: String,
+    tag: String,
+    note: Option<String>,
+    created_at: i64,
+    updated_at: i64,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationExportReport {
+    path: String,
+    count: u32,
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn db_error(e: rusqlite::Error) -> AppError {
+    AppError::Task(format!("annotations database error: {e}"))
+}
+
+fn open_db(app: &AppHandle) -> AppResult<Connection> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Task(format!("app data dir unavailable: {e}")))?;
+    std::fs::create_dir_all(&dir)?;
+    let conn = Connection::open(dir.join(DB_FILENAME)).map_err(db_error)?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS annotations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source TEXT NOT NULL,
+            shard TEXT NOT NULL,
+            sample TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            note TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            UNIQUE(source, shard, sample, tag)
+        );
+        CREATE INDEX IF NOT EXISTS annotations_by_sample ON annotations(source, shard, sample);",
+    )
+    .map_err(db_error)?;
+    Ok(conn)
+}
+
+impl AnnotationStore {
+    fn with_conn<T>(
+        &self,
+        app: &AppHandle,
+        f: impl FnOnce(&Connection) -> rusqlite::Result<T>,
+    ) -> AppResult<T> {
+        let mut guard = self
+            .0
+            .lock()
+            .map_err(|_| AppError::Task("annotations lock poisoned".into()))?;
+        if guard.is_none() {
+            *guard = Some(open_db(app)?);
+        }
+        let conn = guard
+            .as_ref()
+            .ok_or_else(|| AppError::Task("annotations database unavailable".into()))?;
+        f(conn).map_err(db_error)
+    }
+}
+
+fn row_to_annotation(row: &Row) -> rusqlite::Result<Annotation> {
+    Ok(Annotation {
+        id: row.get(0)?,
+        source: row.get(1)?,
+        shard: row.get(2)?,
+        sample: row.get(3)?,
+        tag: row.get(4)?,
+        note: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, source, shard, sample, tag, note, created_at, updated_at FROM annotations";
+
+fn required(value: String, what: &str) -> AppResult<String> {
+    let trimmed = value.trim().to_string();
+    if trimmed.is_empty() {
+        return Err(AppError::Invalid(format!("{what} is empty")));
+    }
+    Ok(trimmed)
+}
+
+fn query_annotations(
+    conn: &Connection,
+    source: Option<&str>,
+    shard: Option<&str>,
+    sample: Option<&str>,
+) -> rusqlite::Result<Vec<Annotation>> {
+    let mut stmt = conn.prepare(&format!(
+        "{SELECT_COLUMNS}
+         WHERE (?1 IS NULL OR source = ?1)
+           AND (?2 IS NULL OR shard = ?2)
+           AND (?3 IS NULL OR sample = ?3)
+         ORDER BY source, shard, sample, tag"
+    ))?;
+    let rows = stmt.query_map(params![source, shard, sample], row_to_annotation)?;
+    rows.collect()
+}
+
+/// Tags a sample, identified by source (dataset path or URL), shard and sample key. Re-tagging
+/// updates the note instead of adding a duplicate.
+#[tauri::command]
+pub async fn annotate_sample(
+    app: AppHandle,
+    store: State<'_, AnnotationStore>,
+    source: String,
+    shard: String,
+    sample: String,
+    tag: String,
+    note: Option<String>,
+) -> AppResult<Annotation> {
+    let source = required(source, "source")?;
+    let sample = required(sample, "sample")?;
+    let tag = required(tag, "tag")?;
+    let shard = shard.trim().to_string();
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let store = (*store).clone();
+    spawn_blocking(move || {
+        store.with_conn(&app, |conn| {
+            let now = now_secs();
+            conn.execute(
+                "INSERT INTO annotations (source, shard, sample, tag, note, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+                 ON CONFLICT(source, shard, sample, tag)
+                 DO UPDATE SET note = excluded.note, updated_at = excluded.updated_at",
+                params![source, shard, sample, tag, note, now],
+            )?;
+            conn.query_row(
+                &format!(
+                    "{SELECT_COLUMNS} WHERE source = ?1 AND shard = ?2 AND sample = ?3 AND tag = ?4"
+                ),
+                params![source, shard, sample, tag],
+                row_to_annotation,
+            )
+        })
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn remove_annotation(
+    app: AppHandle,
+    store: State<'_, AnnotationStore>,
+    id: i64,
+) -> AppResult<bool> {
+    let store = (*store).clone();
+    spawn_blocking(move || {
+        store.with_conn(&app, |conn| {
+            conn.execute("DELETE FROM annotations WHERE id = ?1", params![id])
+                .map(|removed| removed > 0)
+        })
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn list_annotations(
+    app: AppHandle,
+    store: State<'_, AnnotationStore>,
+    source: Option<String>,
+    shard: Option<String>,
+    sample: Option<String>,
+) -> AppResult<Vec<Annotation>> {
+    let store = (*store).clone();
+    spawn_blocking(move || {
+        store.with_conn(&app, |conn| {
+            query_annotations(
+                conn,
+                source.as_deref().map(str::trim),
+                shard.as_deref().map(str::trim),
+                sample.as_deref().map(str::trim),
+            )
+        })
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn annotations_csv(annotations: &[Annotation]) -> String {
+    let mut out = String::from("id,source,shard,sample,tag,note,created_at,updated_at\n");
+    for a in annotations {
+        let fields = [
+            a.id.to_string(),
+            csv_field(&a.source),
+            csv_field(&a.shard),
+            csv_field(&a.sample),
+            csv_field(&a.tag),
+            csv_field(a.note.as_deref().unwrap_or("")),
+            a.created_at.to_string(),
+            a.updated_at.to_string(),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Writes annotations (optionally only those for `source`) to `dest_path` for sharing with the
+/// dataset team.
+#[tauri::command]
+pub async fn export_annotations(
+    app: AppHandle,
+    store: State<'_, AnnotationStore>,
+    source: Option<String>,
+    format: AnnotationExportFormat,
+    dest_path: String,
+) -> AppResult<AnnotationExportReport> {
+    let dest = PathBuf::from(required(dest_path, "destination path")?);
+    read_only::ensure_writable(&dest, "annotation export")?;
+    let store = (*store).clone();
+    spawn_blocking(move || {
+        let annotations = store.with_conn(&app, |conn| {
+            query_annotations(conn, source.as_deref().map(str::trim), None, None)
+        })?;
+        let body = match format {
+            AnnotationExportFormat::Csv => annotations_csv(&annotations),
+            AnnotationExportFormat::Json => serde_json::to_string_pretty(&annotations)
+                .map_err(|e| AppError::Task(format!("serializing annotations failed: {e}")))?,
+        };
+        std::fs::write(&dest, body)?;
+        Ok(AnnotationExportReport {
+            path: dest.display().to_string(),
+            count: annotations.len().min(u32::MAX as usize) as u32,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
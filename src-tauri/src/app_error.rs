@@ -22,6 +22,8 @@ pub enum AppError {
     Task(String),
     #[error("open error: {0}")]
     Open(String),
+    #[error("interrupted: {0}")]
+    Interrupted(String),
 }
 
 impl From<std::io::Error> for AppError {
@@ -0,0 +1,88 @@
+use tauri::menu::{Menu, MenuBuilder, MenuEvent, MenuItemBuilder, SubmenuBuilder};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Menu item ids and the event each sends to the main window.
+const MENU_EVENTS: &[(&str, &str)] = &[
+    ("check_updates", "app://check-updates"),
+    ("open", "app://open"),
+    ("reload_source", "app://reload-source"),
+    ("copy_preview", "app://copy-preview"),
+];
+
+/// The application menu: the usual macOS app menu there, and File / Edit / Help on Windows and
+/// Linux, with the same items and shortcuts on all three.
+pub fn build<R: Runtime>(handle: &AppHandle<R>) -> tauri::Result<Menu<R>> {
+    let open = MenuItemBuilder::with_id("open", "Open…")
+        .accelerator("CmdOrCtrl+O")
+        .build(handle)?;
+    let reload_source = MenuItemBuilder::with_id("reload_source", "Reload Source")
+        .accelerator("CmdOrCtrl+R")
+        .build(handle)?;
+    let copy_preview = MenuItemBuilder::with_id("copy_preview", "Copy Preview")
+        .accelerator("CmdOrCtrl+Shift+C")
+        .build(handle)?;
+    let check_updates =
+        MenuItemBuilder::with_id("check_updates", "Check for Updates…").build(handle)?;
+
+    let file_menu = SubmenuBuilder::new(handle, "File")
+        .item(&open)
+        .item(&reload_source);
+    #[cfg(not(target_os = "macos"))]
+    let file_menu = file_menu.separator().quit();
+    let file_menu = file_menu.build()?;
+
+    let edit_menu = SubmenuBuilder::new(handle, "Edit")
+        .undo()
+        .redo()
+        .separator()
+        .cut()
+        .copy()
+        .paste()
+        .select_all()
+        .separator()
+        .item(&copy_preview)
+        .build()?;
+
+    #[cfg(target_os = "macos")]
+    {
+        let app_menu = SubmenuBuilder::new(handle, handle.package_info().name.clone())
+            .about(None)
+            .separator()
+            .item(&check_updates)
+            .separator()
+            .services()
+            .separator()
+            .hide()
+            .hide_others()
+            .show_all()
+            .separator()
+            .quit()
+            .build()?;
+        MenuBuilder::new(handle)
+            .item(&app_menu)
+            .item(&file_menu)
+            .item(&edit_menu)
+            .build()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let help_menu = SubmenuBuilder::new(handle, "Help")
+            .item(&check_updates)
+            .separator()
+            .about(None)
+            .build()?;
+        MenuBuilder::new(handle)
+            .item(&file_menu)
+            .item(&edit_menu)
+            .item(&help_menu)
+            .build()
+    }
+}
+
+/// Forwards the menu's own items to the UI, which owns what they do.
+pub fn handle_event<R: Runtime>(app: &AppHandle<R>, event: MenuEvent) {
+    if let Some((_, name)) = MENU_EVENTS.iter().find(|(id, _)| event.id().0 == *id) {
+        let _ = app.emit_to("main", name, ());
+    }
+}
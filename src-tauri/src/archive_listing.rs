@@ -0,0 +1,420 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// What the listing helpers need from a ZIP or TAR entry.
+pub trait ArchiveEntry {
+    fn name(&self) -> &str;
+    /// Uncompressed size.
+    fn size(&self) -> u64;
+    /// Bytes the entry occupies in the archive; equals `size` unless compressed per entry.
+    fn stored_size(&self) -> u64 {
+        self.size()
+    }
+    fn is_dir(&self) -> bool;
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveSort {
+    Name,
+    Size,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveListQuery {
+    prefix: Option<String>,
+    /// `*` and `?` stay within a path segment, `**` crosses them. A pattern without `/` is matched
+    /// against the file name only.
+    glob: Option<String>,
+    extensions: Option<Vec<String>>,
+    /// Tree mode: list only the immediate children of this directory ("" for the root), with
+    /// subdirectories folded into single rows.
+    directory: Option<String>,
+    sort: Option<ArchiveSort>,
+    descending: Option<bool>,
+}
+
+/// One listing row: an archive entry (`source` is its index) or a folded directory.
+pub struct ListingItem {
+    pub name: String,
+    pub size: u64,
+    pub stored_size: u64,
+    pub is_dir: bool,
+    pub source: Option<usize>,
+}
+
+pub fn extension_of(name: &str) -> Option<String> {
+    let base = name.rsplit('/').next().unwrap_or(name);
+    let (stem, ext) = base.rsplit_once('.')?;
+    if stem.is_empty() || ext.is_empty() {
+        return None;
+    }
+    Some(ext.to_ascii_lowercase())
+}
+
+fn glob_match_bytes(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            let rest = pattern[2..].strip_prefix(b"/").unwrap_or(&pattern[2..]);
+            (0..=text.len()).any(|i| glob_match_bytes(rest, &text[i..]))
+                || glob_match_bytes(&pattern[2..], text)
+        }
+        Some(b'*') => {
+            let rest = &pattern[1..];
+            for i in 0..=text.len() {
+                if glob_match_bytes(rest, &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&b'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some(b'?') => {
+            !text.is_empty() && text[0] != b'/' && glob_match_bytes(&pattern[1..], &text[1..])
+        }
+        Some(&c) => {
+            !text.is_empty()
+                && text[0].eq_ignore_ascii_case(&c)
+                && glob_match_bytes(&pattern[1..], &text[1..])
+        }
+    }
+}
+
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let target = if pattern.contains('/') {
+        path
+    } else {
+        path.rsplit('/').next().unwrap_or(path)
+    };
+    glob_match_bytes(pattern.as_bytes(), target.as_bytes())
+}
+
+struct Filter {
+    prefix: Option<String>,
+    glob: Option<String>,
+    extensions: Option<HashSet<String>>,
+}
+
+impl Filter {
+    fn new(query: &ArchiveListQuery) -> Self {
+        let non_empty = |v: &Option<String>| {
+            v.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        let extensions = query
+            .extensions
+            .as_ref()
+            .map(|exts| {
+                exts.iter()
+                    .map(|e| e.trim().trim_start_matches('.').to_ascii_lowercase())
+                    .filter(|e| !e.is_empty())
+                    .collect::<HashSet<_>>()
+            })
+            .filter(|set| !set.is_empty());
+        Self {
+            prefix: non_empty(&query.prefix).map(|p| p.trim_start_matches('/').to_string()),
+            glob: non_empty(&query.glob),
+            extensions,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.prefix.is_some() || self.glob.is_some() || self.extensions.is_some()
+    }
+
+    fn accepts_file(&self, name: &str) -> bool {
+        if let Some(prefix) = &self.prefix {
+            if !name.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if let Some(glob) = &self.glob {
+            if !glob_match(glob, name) {
+                return false;
+            }
+        }
+        if let Some(exts) = &self.extensions {
+            if !extension_of(name).is_some_and(|e| exts.contains(&e)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn tree_children<E: ArchiveEntry>(entries: &[E], dir: &str, filter: &Filter) -> Vec<ListingItem> {
+    let dir = dir.trim().trim_matches('/');
+    let prefix = if dir.is_empty() {
+        String::new()
+    } else {
+        format!("{dir}/")
+    };
+    let mut folded: BTreeMap<String, (u64, u64, u64)> = BTreeMap::new();
+    let mut files = Vec::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        let Some(rest) = entry.name().strip_prefix(prefix.as_str()) else {
+            continue;
+        };
+        let rest = rest.trim_end_matches('/');
+        if rest.is_empty() {
+            continue;
+        }
+        match rest.split_once('/') {
+            Some((child, _)) => {
+                if entry.is_dir() {
+                    continue;
+                }
+                if filter.accepts_file(entry.name()) {
+                    let totals = folded.entry(child.to_string()).or_default();
+                    totals.0 += entry.size();
+                    totals.1 += entry.stored_size();
+                    totals.2 += 1;
+                }
+            }
+            None if entry.is_dir() => {
+                folded.entry(rest.to_string()).or_default();
+            }
+            None => {
+                if filter.accepts_file(entry.name()) {
+                    files.push(ListingItem {
+                        name: entry.name().to_string(),
+                        size: entry.size(),
+                        stored_size: entry.stored_size(),
+                        is_dir: false,
+                        source: Some(idx),
+                    });
+                }
+            }
+        }
+    }
+    // With a filter on, only directories that still contain matches are worth expanding.
+    let mut out: Vec<ListingItem> = folded
+        .into_iter()
+        .filter(|(_, (_, _, matches))| !filter.is_active() || *matches > 0)
+        .map(|(child, (size, stored_size, _))| ListingItem {
+            name: format!("{prefix}{child}/"),
+            size,
+            stored_size,
+            is_dir: true,
+            source: None,
+        })
+        .collect();
+    out.extend(files);
+    out
+}
+
+/// Applies a listing query to a flat entry list. Unsorted results keep archive order (in tree
+/// mode, folders first).
+pub fn list<E: ArchiveEntry>(entries: &[E], query: &ArchiveListQuery) -> Vec<ListingItem> {
+    let filter = Filter::new(query);
+    let mut items = match query.directory.as_deref() {
+        Some(dir) => tree_children(entries, dir, &filter),
+        None => entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                if e.is_dir() {
+                    !filter.is_active()
+                } else {
+                    filter.accepts_file(e.name())
+                }
+            })
+            .map(|(idx, e)| ListingItem {
+                name: e.name().to_string(),
+                size: e.size(),
+                stored_size: e.stored_size(),
+                is_dir: e.is_dir(),
+                source: Some(idx),
+            })
+            .collect(),
+    };
+    match query.sort {
+        Some(ArchiveSort::Name) => items.sort_by(|a, b| a.name.cmp(&b.name)),
+        Some(ArchiveSort::Size) => {
+            items.sort_by(|a, b| a.size.cmp(&b.size).then(a.name.cmp(&b.name)))
+        }
+        None => {}
+    }
+    if query.descending.unwrap_or(false) {
+        items.reverse();
+    }
+    items
+}
+
+/// A directory with totals over everything below it. Files are not included; tree-mode listings
+/// fetch them per directory.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveTreeNode {
+    name: String,
+    /// Full path with a trailing slash, "" for the archive root.
+    path: String,
+    num_files: u64,
+    num_dirs: u64,
+    size: u64,
+    stored_size: u64,
+    children: Vec<ArchiveTreeNode>,
+    /// Subdirectories exist but were left out by the depth limit.
+    collapsed: bool,
+}
+
+#[derive(Default)]
+struct DirTotals {
+    files: u64,
+    dirs: u64,
+    size: u64,
+    stored_size: u64,
+}
+
+fn parent_dir(path: &str) -> Option<&str> {
+    if path.is_empty() {
+        return None;
+    }
+    Some(path.rsplit_once('/').map_or("", |(parent, _)| parent))
+}
+
+fn add_dir(totals: &mut BTreeMap<String, DirTotals>, path: &str) {
+    if totals.contains_key(path) {
+        return;
+    }
+    totals.insert(path.to_string(), DirTotals::default());
+    let Some(parent) = parent_dir(path) else {
+        return;
+    };
+    add_dir(totals, parent);
+    let mut dir = Some(parent);
+    while let Some(d) = dir {
+        if let Some(t) = totals.get_mut(d) {
+            t.dirs += 1;
+        }
+        dir = parent_dir(d);
+    }
+}
+
+fn tree_node(
+    path: &str,
+    totals: &BTreeMap<String, DirTotals>,
+    children: &HashMap<&str, Vec<&str>>,
+    depth: u32,
+) -> ArchiveTreeNode {
+    let t = &totals[path];
+    let kids = children.get(path).map(Vec::as_slice).unwrap_or_default();
+    let expand = depth > 0;
+    ArchiveTreeNode {
+        name: path.rsplit('/').next().unwrap_or(path).to_string(),
+        path: if path.is_empty() {
+            String::new()
+        } else {
+            format!("{path}/")
+        },
+        num_files: t.files,
+        num_dirs: t.dirs,
+        size: t.size,
+        stored_size: t.stored_size,
+        children: if expand {
+            kids.iter()
+                .map(|kid| tree_node(kid, totals, children, depth - 1))
+                .collect()
+        } else {
+            Vec::new()
+        },
+        collapsed: !expand && !kids.is_empty(),
+    }
+}
+
+/// Folds a flat entry list into the directory tree under `root`, expanding `depth` levels.
+/// Directories only implied by file paths are included. Returns `None` if `root` does not exist.
+pub fn build_tree<E: ArchiveEntry>(
+    entries: &[E],
+    root: &str,
+    depth: u32,
+) -> Option<ArchiveTreeNode> {
+    let mut totals: BTreeMap<String, DirTotals> = BTreeMap::new();
+    totals.insert(String::new(), DirTotals::default());
+    for entry in entries {
+        let name = entry.name().trim_matches('/');
+        if name.is_empty() {
+            continue;
+        }
+        if entry.is_dir() {
+            add_dir(&mut totals, name);
+            continue;
+        }
+        let parent = parent_dir(name).unwrap_or_default();
+        add_dir(&mut totals, parent);
+        let mut dir = Some(parent);
+        while let Some(d) = dir {
+            if let Some(t) = totals.get_mut(d) {
+                t.files += 1;
+                t.size += entry.size();
+                t.stored_size += entry.stored_size();
+            }
+            dir = parent_dir(d);
+        }
+    }
+
+    let root = root.trim().trim_matches('/');
+    if !totals.contains_key(root) {
+        return None;
+    }
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for path in totals.keys() {
+        if let Some(parent) = parent_dir(path) {
+            children.entry(parent).or_default().push(path);
+        }
+    }
+    Some(tree_node(root, &totals, &children, depth))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveTypeCount {
+    /// Lowercased extension without the dot; `None` for files without one.
+    extension: Option<String>,
+    count: u64,
+    size: u64,
+    stored_size: u64,
+}
+
+impl ArchiveTypeCount {
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// File counts and byte totals grouped by extension, largest total first.
+pub fn type_summary<E: ArchiveEntry>(entries: &[E]) -> Vec<ArchiveTypeCount> {
+    let mut groups: HashMap<Option<String>, ArchiveTypeCount> = HashMap::new();
+    for entry in entries.iter().filter(|e| !e.is_dir()) {
+        let extension = extension_of(entry.name());
+        let group = groups
+            .entry(extension.clone())
+            .or_insert_with(|| ArchiveTypeCount {
+                extension,
+                count: 0,
+                size: 0,
+                stored_size: 0,
+            });
+        group.count += 1;
+        group.size += entry.size();
+        group.stored_size += entry.stored_size();
+    }
+    let mut out: Vec<ArchiveTypeCount> = groups.into_values().collect();
+    out.sort_by(|a, b| {
+        b.size
+            .cmp(&a.size)
+            .then(b.count.cmp(&a.count))
+            .then(a.extension.cmp(&b.extension))
+    });
+    out
+}
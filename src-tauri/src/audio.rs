@@ -31,6 +31,7 @@ struct SphereHeader {
     sample_n_bytes: u16,
     sample_byte_format: Option<String>,
     sample_coding: Option<String>,
+    sample_count: Option<u64>,
 }
 
 fn parse_sphere_header(data: &[u8]) -> Result<(SphereHeader, usize), String> {
@@ -100,6 +101,7 @@ fn parse_sphere_header(data: &[u8]) -> Result<(SphereHeader, usize), String> {
 
     let sample_byte_format = map.get("sample_byte_format").cloned();
     let sample_coding = map.get("sample_coding").cloned();
+    let sample_count = map.get("sample_count").and_then(|v| v.parse::<u64>().ok());
 
     Ok((
         SphereHeader {
@@ -108,11 +110,91 @@ fn parse_sphere_header(data: &[u8]) -> Result<(SphereHeader, usize), String> {
             sample_n_bytes,
             sample_byte_format,
             sample_coding,
+            sample_count,
         },
         header_bytes,
     ))
 }
 
+/// Sample rate and, when the header states a length, duration of an audio payload.
+pub struct StreamInfo {
+    pub sample_rate: u32,
+    pub duration_secs: Option<f64>,
+}
+
+/// Reads the stream parameters from a WAV, FLAC or SPHERE header at the start of `data`; other
+/// containers return `None`.
+pub fn stream_info(data: &[u8]) -> Option<StreamInfo> {
+    if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE".as_slice()) {
+        wav_stream_info(data)
+    } else if data.starts_with(b"fLaC") {
+        flac_stream_info(data)
+    } else if is_sphere_file(data) {
+        let (header, _) = parse_sphere_header(data).ok()?;
+        let duration_secs = header
+            .sample_count
+            .filter(|_| header.sample_rate > 0)
+            .map(|count| count as f64 / header.sample_rate as f64);
+        Some(StreamInfo {
+            sample_rate: header.sample_rate,
+            duration_secs,
+        })
+    } else {
+        None
+    }
+}
+
+fn wav_stream_info(data: &[u8]) -> Option<StreamInfo> {
+    let mut pos = 12;
+    let mut format = None::<(u32, u32)>;
+    while let Some(chunk) = data.get(pos..pos + 8) {
+        let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+        let body = pos + 8;
+        match &chunk[..4] {
+            b"fmt " => {
+                let fmt = data.get(body..body + 12)?;
+                let sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+                let byte_rate = u32::from_le_bytes([fmt[8], fmt[9], fmt[10], fmt[11]]);
+                format = Some((sample_rate, byte_rate));
+            }
+            b"data" => {
+                let (sample_rate, byte_rate) = format?;
+                // Streamed WAVs leave the data size at 0 or 0xFFFFFFFF.
+                let duration_secs = (byte_rate > 0 && size != 0 && size != u32::MAX)
+                    .then_some(size as f64 / byte_rate as f64);
+                return Some(StreamInfo {
+                    sample_rate,
+                    duration_secs,
+                });
+            }
+            _ => {}
+        }
+        pos = body + size as usize + (size as usize & 1);
+    }
+    format.map(|(sample_rate, _)| StreamInfo {
+        sample_rate,
+        duration_secs: None,
+    })
+}
+
+fn flac_stream_info(data: &[u8]) -> Option<StreamInfo> {
+    // The first metadata block is always STREAMINFO (type 0).
+    if data.get(4)? & 0x7F != 0 {
+        return None;
+    }
+    let info = data.get(8..26)?;
+    let sample_rate =
+        ((info[10] as u32) << 12) | ((info[11] as u32) << 4) | ((info[12] as u32) >> 4);
+    let total_samples = (((info[13] & 0x0F) as u64) << 32)
+        | u32::from_be_bytes([info[14], info[15], info[16], info[17]]) as u64;
+    let duration_secs =
+        (sample_rate > 0 && total_samples > 0).then_some(total_samples as f64 / sample_rate as f64);
+    Some(StreamInfo {
+        sample_rate,
+        duration_secs,
+    })
+}
+
 fn mu_law_to_i16(byte: u8) -> i16 {
     // ITU-T G.711 mu-law decoding.
     let byte = !byte;
@@ -0,0 +1,442 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::access_log;
+use crate::app_error::{AppError, AppResult};
+use crate::croissant;
+use crate::dataset_docs;
+use crate::dataset_profile;
+use crate::dataset_tree;
+use crate::huggingface;
+use crate::litdata;
+use crate::mosaicml;
+use crate::report;
+use crate::settings;
+use crate::shard_balance;
+use crate::webdataset;
+use crate::zenodo;
+
+const AUTOMATION_KEY: &str = "automation";
+const DEFAULT_PORT: u16 = 47_615;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+const MAX_HEADER_LINES: usize = 64;
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+/// Binding the port a just-stopped listener held can briefly fail; tried this many times.
+const BIND_ATTEMPTS: u32 = 10;
+const BIND_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Commands scripts can run, under the names and with the camelCase arguments the UI passes to
+/// `invoke`. Only ones that inspect; nothing that opens windows or apps.
+const COMMANDS: &[&str] = &[
+    "detect_local_dataset",
+    "load_index",
+    "validate_index",
+    "mosaicml_load_index",
+    "mosaicml_validate",
+    "wds_load_dir",
+    "wds_lint",
+    "shard_balance",
+    "scan_tree",
+    "get_dataset_profile",
+    "local_dataset_docs",
+    "read_declared_metadata",
+    "zenodo_record_summary",
+    "hf_dataset_preview",
+    "generate_report",
+    "query_access_log",
+];
+
+/// Bumped whenever the endpoint stops or restarts; listeners of older generations exit.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Port the endpoint is listening on, when it is.
+static RUNNING: Mutex<Option<u16>> = Mutex::new(None);
+/// Token requests must carry. Changing it does not need a restart.
+static TOKEN: Mutex<String> = Mutex::new(String::new());
+
+#[derive(Serialize, Deserialize, Default)]
+struct AutomationSettings {
+    enabled: bool,
+    port: Option<u16>,
+    token: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationEndpoint {
+    enabled: bool,
+    /// `http://127.0.0.1:<port>` while the endpoint is listening.
+    url: Option<String>,
+    /// Sent by scripts as `Authorization: Bearer <token>`.
+    token: Option<String>,
+    commands: Vec<String>,
+}
+
+fn read_automation_settings(app: &AppHandle) -> AutomationSettings {
+    settings::read_setting(app, AUTOMATION_KEY)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// 256 bits from the operating system's random source.
+fn new_token() -> AppResult<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| AppError::Task(format!("generating a token failed: {e}")))?;
+    Ok(hex::encode(bytes))
+}
+
+fn running_port() -> Option<u16> {
+    *RUNNING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn endpoint(settings: &AutomationSettings) -> AutomationEndpoint {
+    AutomationEndpoint {
+        enabled: settings.enabled,
+        url: running_port().map(|port| format!("http://{}:{port}", Ipv4Addr::LOCALHOST)),
+        token: settings.token.clone(),
+        commands: COMMANDS.iter().map(|c| c.to_string()).collect(),
+    }
+}
+
+fn stop() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    if let Some(port) = RUNNING.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        // Wakes the listener blocked in `accept` so it sees the new generation and exits.
+        let _ = TcpStream::connect((Ipv4Addr::LOCALHOST, port));
+    }
+}
+
+fn bind(port: u16) -> AppResult<TcpListener> {
+    let mut attempt = 1;
+    loop {
+        match TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+            Ok(listener) => return Ok(listener),
+            Err(e) if e.kind() == ErrorKind::AddrInUse && attempt < BIND_ATTEMPTS => {
+                attempt += 1;
+                std::thread::sleep(BIND_RETRY_DELAY);
+            }
+            Err(e) => {
+                return Err(AppError::Invalid(format!(
+                    "cannot listen on 127.0.0.1:{port}: {e}"
+                )))
+            }
+        }
+    }
+}
+
+/// Listens on loopback only; other machines never reach the endpoint. Keeps a listener already
+/// on `port`. Port 0 lets the system pick one, and the port actually bound is what gets reported.
+fn start(app: &AppHandle, port: u16, token: String) -> AppResult<()> {
+    *TOKEN.lock().unwrap_or_else(|e| e.into_inner()) = token;
+    if running_port() == Some(port) {
+        return Ok(());
+    }
+    stop();
+    let listener = bind(port)?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| AppError::Task(format!("reading the listening address failed: {e}")))?
+        .port();
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    *RUNNING.lock().unwrap_or_else(|e| e.into_inner()) = Some(port);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            if GENERATION.load(Ordering::SeqCst) != generation {
+                break;
+            }
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let app = app.clone();
+            std::thread::spawn(move || serve(&app, stream));
+        }
+    });
+    Ok(())
+}
+
+/// Starts the endpoint if it was left on. Called once at startup.
+pub fn load_settings(app: &AppHandle) {
+    let settings = read_automation_settings(app);
+    if let (true, Some(token)) = (settings.enabled, settings.token) {
+        let _ = start(app, settings.port.unwrap_or(DEFAULT_PORT), token);
+    }
+}
+
+fn arg<T: DeserializeOwned>(args: &Value, name: &str) -> AppResult<T> {
+    serde_json::from_value(args.get(name).cloned().unwrap_or(Value::Null))
+        .map_err(|e| AppError::Invalid(format!("argument {name}: {e}")))
+}
+
+fn to_json<T: Serialize>(result: AppResult<T>) -> AppResult<Value> {
+    serde_json::to_value(result?)
+        .map_err(|e| AppError::Task(format!("serializing the result failed: {e}")))
+}
+
+async fn run(app: &AppHandle, command: &str, args: &Value) -> AppResult<Value> {
+    match command {
+        "detect_local_dataset" => {
+            to_json(webdataset::detect_local_dataset(arg(args, "path")?).await)
+        }
+        "load_index" => to_json(litdata::load_index(arg(args, "indexPath")?).await),
+        "validate_index" => {
+            to_json(litdata::validate_index(app.clone(), arg(args, "indexPath")?).await)
+        }
+        "mosaicml_load_index" => {
+            to_json(mosaicml::mosaicml_load_index(arg(args, "indexPath")?).await)
+        }
+        "mosaicml_validate" => to_json(
+            mosaicml::mosaicml_validate(
+                app.clone(),
+                arg(args, "indexPath")?,
+                arg(args, "verifyHashes")?,
+            )
+            .await,
+        ),
+        "wds_load_dir" => to_json(webdataset::wds_load_dir(arg(args, "dirPath")?).await),
+        "wds_lint" => to_json(
+            webdataset::wds_lint(
+                arg(args, "dirPath")?,
+                arg(args, "shardFilename")?,
+                arg(args, "maxMembers")?,
+            )
+            .await,
+        ),
+        "shard_balance" => to_json(
+            shard_balance::shard_balance(arg(args, "path")?, arg(args, "thresholdPct")?).await,
+        ),
+        "scan_tree" => {
+            to_json(dataset_tree::scan_tree(arg(args, "path")?, arg(args, "maxDepth")?).await)
+        }
+        "get_dataset_profile" => {
+            to_json(dataset_profile::get_dataset_profile(arg(args, "path")?).await)
+        }
+        "local_dataset_docs" => to_json(dataset_docs::local_dataset_docs(arg(args, "path")?).await),
+        "read_declared_metadata" => {
+            to_json(croissant::read_declared_metadata(arg(args, "path")?).await)
+        }
+        "zenodo_record_summary" => to_json(
+            zenodo::zenodo_record_summary(
+                app.state(),
+                arg(args, "input")?,
+                arg(args, "forceRefresh")?,
+            )
+            .await,
+        ),
+        "hf_dataset_preview" => to_json(
+            huggingface::hf_dataset_preview(
+                app.state(),
+                arg(args, "input")?,
+                arg(args, "config")?,
+                arg(args, "split")?,
+                arg(args, "offset")?,
+                arg(args, "length")?,
+                arg(args, "token")?,
+            )
+            .await,
+        ),
+        "generate_report" => to_json(
+            report::generate_report(
+                app.clone(),
+                app.state(),
+                app.state(),
+                arg(args, "path")?,
+                arg(args, "format")?,
+                arg(args, "destPath")?,
+                arg(args, "options")?,
+            )
+            .await,
+        ),
+        "query_access_log" => to_json(
+            access_log::query_access_log(
+                arg(args, "host")?,
+                arg(args, "since")?,
+                arg(args, "until")?,
+                arg(args, "limit")?,
+            )
+            .await,
+        ),
+        _ => Err(AppError::Missing(format!("command {command}"))),
+    }
+}
+
+/// Compares without stopping at the first differing byte, so timing does not leak the token.
+fn token_matches(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+fn read_request(stream: &TcpStream) -> AppResult<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut authorization = None;
+    let mut content_length = 0usize;
+    for _ in 0..MAX_HEADER_LINES {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            authorization = value.strip_prefix("Bearer ").map(str::to_string);
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| AppError::Invalid("bad Content-Length".into()))?;
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(AppError::Invalid(format!(
+            "request body over {MAX_BODY_BYTES} bytes"
+        )));
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request {
+        method,
+        path,
+        authorization,
+        body,
+    })
+}
+
+fn respond(stream: &mut TcpStream, status: u16, body: &Value) {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    let head = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(body.as_bytes());
+}
+
+fn error_status(err: &AppError) -> u16 {
+    match err {
+        AppError::Invalid(_) => 400,
+        AppError::Missing(_) => 404,
+        _ => 500,
+    }
+}
+
+fn error_body(err: &AppError) -> Value {
+    json!({ "error": err })
+}
+
+/// One request per connection:
+///
+/// - `GET /commands` lists the commands;
+/// - `POST /invoke/<command>` runs one, the body being its arguments as a JSON object.
+///
+/// Results come back as JSON; failures as `{"error": {"code", "message"}}`, the error the UI
+/// would get. Every request needs the token.
+fn serve(app: &AppHandle, mut stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let request = match read_request(&stream) {
+        Ok(request) => request,
+        Err(err) => return respond(&mut stream, 400, &error_body(&err)),
+    };
+    let token = TOKEN.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    if !request
+        .authorization
+        .as_deref()
+        .is_some_and(|given| !token.is_empty() && token_matches(given, &token))
+    {
+        let err = AppError::Invalid("missing or wrong automation token".into());
+        return respond(&mut stream, 401, &error_body(&err));
+    }
+
+    let path = request.path.split('?').next().unwrap_or_default();
+    if path == "/commands" {
+        return respond(&mut stream, 200, &json!(COMMANDS));
+    }
+    let Some(command) = path.strip_prefix("/invoke/") else {
+        let err = AppError::Missing(format!("endpoint {path}"));
+        return respond(&mut stream, 404, &error_body(&err));
+    };
+    if request.method != "POST" {
+        let err = AppError::Invalid("use POST to invoke a command".into());
+        return respond(&mut stream, 405, &error_body(&err));
+    }
+    let args = if request.body.iter().all(u8::is_ascii_whitespace) {
+        json!({})
+    } else {
+        match serde_json::from_slice::<Value>(&request.body) {
+            Ok(args) if args.is_object() => args,
+            _ => {
+                let err = AppError::Invalid("arguments must be a JSON object".into());
+                return respond(&mut stream, 400, &error_body(&err));
+            }
+        }
+    };
+    match tauri::async_runtime::block_on(run(app, command, &args)) {
+        Ok(result) => respond(&mut stream, 200, &result),
+        Err(err) => respond(&mut stream, error_status(&err), &error_body(&err)),
+    }
+}
+
+#[tauri::command]
+pub async fn get_automation_endpoint(app: AppHandle) -> AppResult<AutomationEndpoint> {
+    Ok(endpoint(&read_automation_settings(&app)))
+}
+
+/// Turns the local automation endpoint on or off. `port` defaults to the last one used; a new
+/// token is made the first time and when `rotate_token` is set.
+#[tauri::command]
+pub async fn set_automation_endpoint(
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+    rotate_token: Option<bool>,
+) -> AppResult<AutomationEndpoint> {
+    let mut settings = read_automation_settings(&app);
+    if let Some(port) = port {
+        settings.port = Some(port);
+    }
+    if settings.token.is_none() || rotate_token.unwrap_or(false) {
+        settings.token = Some(new_token()?);
+    }
+    if enabled {
+        let token = settings.token.clone().unwrap_or_default();
+        start(&app, settings.port.unwrap_or(DEFAULT_PORT), token)?;
+    } else {
+        stop();
+    }
+    settings.enabled = enabled;
+    settings::write_setting(&app, AUTOMATION_KEY, &settings)?;
+    Ok(endpoint(&settings))
+}
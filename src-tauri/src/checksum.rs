@@ -0,0 +1,92 @@
+use sha2::Digest;
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use crate::app_error::AppResult;
+
+/// Streaming digest over the algorithms used by MDS indexes and Zenodo records.
+pub enum StreamingHasher {
+    Md5(md5::Md5),
+    Sha1(sha1::Sha1),
+    Sha224(sha2::Sha224),
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+    Xxh64(xxhash_rust::xxh64::Xxh64),
+    Xxh3_64(Box<xxhash_rust::xxh3::Xxh3>),
+}
+
+impl StreamingHasher {
+    pub fn new(algorithm: &str) -> Option<Self> {
+        let algo = algorithm.trim().to_ascii_lowercase().replace('-', "");
+        Some(match algo.as_str() {
+            "md5" => Self::Md5(md5::Md5::new()),
+            "sha1" => Self::Sha1(sha1::Sha1::new()),
+            "sha224" => Self::Sha224(sha2::Sha224::new()),
+            "sha256" => Self::Sha256(sha2::Sha256::new()),
+            "sha384" => Self::Sha384(sha2::Sha384::new()),
+            "sha512" => Self::Sha512(sha2::Sha512::new()),
+            "xxh64" => Self::Xxh64(xxhash_rust::xxh64::Xxh64::new(0)),
+            "xxh3_64" | "xxh364" => Self::Xxh3_64(Box::new(xxhash_rust::xxh3::Xxh3::new())),
+            _ => return None,
+        })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Md5(h) => h.update(data),
+            Self::Sha1(h) => h.update(data),
+            Self::Sha224(h) => h.update(data),
+            Self::Sha256(h) => h.update(data),
+            Self::Sha384(h) => h.update(data),
+            Self::Sha512(h) => h.update(data),
+            Self::Xxh64(h) => h.update(data),
+            Self::Xxh3_64(h) => h.update(data),
+        }
+    }
+
+    pub fn finalize_hex(self) -> String {
+        match self {
+            Self::Md5(h) => hex::encode(h.finalize()),
+            Self::Sha1(h) => hex::encode(h.finalize()),
+            Self::Sha224(h) => hex::encode(h.finalize()),
+            Self::Sha256(h) => hex::encode(h.finalize()),
+            Self::Sha384(h) => hex::encode(h.finalize()),
+            Self::Sha512(h) => hex::encode(h.finalize()),
+            Self::Xxh64(h) => format!("{:016x}", h.digest()),
+            Self::Xxh3_64(h) => format!("{:016x}", h.digest()),
+        }
+    }
+}
+
+/// Hashes a file with the given algorithm; `None` when the algorithm is unsupported.
+pub fn hash_file(path: &Path, algorithm: &str) -> AppResult<Option<String>> {
+    let Some(mut hasher) = StreamingHasher::new(algorithm) else {
+        return Ok(None);
+    };
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(Some(hasher.finalize_hex()))
+}
+
+/// Splits an `algorithm:hex` checksum string as reported by Zenodo; a bare digest is taken as md5.
+pub fn split_checksum(value: &str) -> Option<(String, String)> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    match trimmed.split_once(':') {
+        Some((algo, digest)) => Some((algo.trim().to_ascii_lowercase(), digest.trim().to_string())),
+        None => Some(("md5".into(), trimmed.to_string())),
+    }
+}
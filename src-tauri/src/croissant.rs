@@ -0,0 +1,450 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::schema::{self, SchemaDiffResponse};
+
+/// Declared-metadata files are small JSON documents; anything bigger is not worth parsing inline.
+pub const MAX_DECLARED_METADATA_BYTES: u64 = 8 * 1024 * 1024;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredField {
+    name: String,
+    data_type: Option<String>,
+    description: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredSplit {
+    name: String,
+    num_records: Option<u64>,
+    num_bytes: Option<u64>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredRecordSet {
+    name: String,
+    num_records: Option<u64>,
+    splits: Vec<DeclaredSplit>,
+    fields: Vec<DeclaredField>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredFile {
+    name: String,
+    content_url: Option<String>,
+    content_size: Option<String>,
+    encoding_format: Option<String>,
+}
+
+/// What a `croissant.json` or Hugging Face `dataset_infos.json` claims about a dataset.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredMetadata {
+    source: String,
+    /// "croissant" or "dataset_infos".
+    format: String,
+    name: Option<String>,
+    description: Option<String>,
+    licenses: Vec<String>,
+    record_sets: Vec<DeclaredRecordSet>,
+    files: Vec<DeclaredFile>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredSchemaComparison {
+    declared: DeclaredMetadata,
+    record_set: String,
+    /// `left` is the declared schema, `right` the one observed on disk. Declared types use the
+    /// metadata vocabulary, so only field presence is compared.
+    diff: SchemaDiffResponse,
+}
+
+pub fn is_declared_metadata_file(filename: &str) -> bool {
+    let base = filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(filename)
+        .to_ascii_lowercase();
+    base == "croissant.json"
+        || base.ends_with(".croissant.json")
+        || base.ends_with("_croissant.json")
+        || base == "dataset_infos.json"
+        || base == "dataset_info.json"
+}
+
+fn str_of(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Array(items) => items.iter().find_map(|v| str_of(Some(v))),
+        Value::Object(obj) => str_of(obj.get("@id"))
+            .or_else(|| str_of(obj.get("url")))
+            .or_else(|| str_of(obj.get("name"))),
+        _ => None,
+    }
+}
+
+fn u64_of(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn list_of(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(other) => vec![other],
+    }
+}
+
+fn licenses_of(value: Option<&Value>) -> Vec<String> {
+    let mut out: Vec<String> = list_of(value)
+        .into_iter()
+        .filter_map(|v| str_of(Some(v)))
+        .collect();
+    out.dedup();
+    out
+}
+
+/// Croissant field names may be qualified with their record set ("default/image").
+fn short_field_name(obj: &Map<String, Value>) -> Option<String> {
+    let name = str_of(obj.get("name")).or_else(|| str_of(obj.get("@id")))?;
+    Some(name.rsplit('/').next().unwrap_or(&name).to_string())
+}
+
+fn croissant_fields(prefix: &str, value: Option<&Value>, out: &mut Vec<DeclaredField>) {
+    for field in list_of(value) {
+        let Some(obj) = field.as_object() else {
+            continue;
+        };
+        let Some(name) = short_field_name(obj) else {
+            continue;
+        };
+        let name = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}.{name}")
+        };
+        let sub_fields = obj.get("subField").or_else(|| obj.get("cr:subField"));
+        if sub_fields.is_some() {
+            croissant_fields(&name, sub_fields, out);
+            continue;
+        }
+        out.push(DeclaredField {
+            name,
+            data_type: str_of(obj.get("dataType").or_else(|| obj.get("cr:dataType"))),
+            description: str_of(obj.get("description")),
+        });
+    }
+}
+
+fn parse_croissant(source: &str, root: &Map<String, Value>) -> DeclaredMetadata {
+    let record_sets = list_of(root.get("recordSet").or_else(|| root.get("cr:recordSet")))
+        .into_iter()
+        .filter_map(Value::as_object)
+        .map(|rs| {
+            let mut fields = Vec::new();
+            croissant_fields(
+                "",
+                rs.get("field").or_else(|| rs.get("cr:field")),
+                &mut fields,
+            );
+            DeclaredRecordSet {
+                name: short_field_name(rs).unwrap_or_else(|| "recordSet".into()),
+                num_records: u64_of(rs.get("numRecords").or_else(|| rs.get("cr:numRecords"))),
+                splits: Vec::new(),
+                fields,
+            }
+        })
+        .collect();
+    let files = list_of(root.get("distribution"))
+        .into_iter()
+        .filter_map(Value::as_object)
+        .map(|f| DeclaredFile {
+            name: str_of(f.get("name"))
+                .or_else(|| str_of(f.get("@id")))
+                .unwrap_or_default(),
+            content_url: str_of(f.get("contentUrl")),
+            content_size: str_of(f.get("contentSize")),
+            encoding_format: str_of(f.get("encodingFormat")),
+        })
+        .collect();
+    DeclaredMetadata {
+        source: source.to_string(),
+        format: "croissant".into(),
+        name: str_of(root.get("name")),
+        description: str_of(root.get("description")),
+        licenses: licenses_of(root.get("license")),
+        record_sets,
+        files,
+    }
+}
+
+/// Renders a `datasets` feature spec as a short type label, e.g. `list<string>` or
+/// `ClassLabel(10)`.
+fn hf_feature_type(value: &Value) -> String {
+    match value {
+        Value::Array(items) => format!(
+            "list<{}>",
+            items.first().map(hf_feature_type).unwrap_or_default()
+        ),
+        Value::Object(obj) => {
+            let kind = obj.get("_type").and_then(Value::as_str).unwrap_or("");
+            match kind {
+                "Sequence" | "LargeList" | "List" => format!(
+                    "list<{}>",
+                    obj.get("feature").map(hf_feature_type).unwrap_or_default()
+                ),
+                "ClassLabel" => match obj.get("names").and_then(Value::as_array) {
+                    Some(names) => format!("ClassLabel({})", names.len()),
+                    None => "ClassLabel".into(),
+                },
+                _ => str_of(obj.get("dtype")).unwrap_or_else(|| {
+                    if kind.is_empty() {
+                        "struct".into()
+                    } else {
+                        kind.to_string()
+                    }
+                }),
+            }
+        }
+        other => str_of(Some(other)).unwrap_or_default(),
+    }
+}
+
+fn hf_fields(prefix: &str, features: &Map<String, Value>, out: &mut Vec<DeclaredField>) {
+    for (name, spec) in features {
+        let name = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        // A plain mapping without `_type`/`dtype` is a nested struct of features.
+        if let Value::Object(obj) = spec {
+            if !obj.contains_key("_type") && !obj.contains_key("dtype") {
+                hf_fields(&name, obj, out);
+                continue;
+            }
+        }
+        out.push(DeclaredField {
+            name,
+            data_type: Some(hf_feature_type(spec)),
+            description: None,
+        });
+    }
+}
+
+fn hf_record_set(config: &str, info: &Map<String, Value>) -> DeclaredRecordSet {
+    let mut fields = Vec::new();
+    if let Some(Value::Object(features)) = info.get("features") {
+        hf_fields("", features, &mut fields);
+    }
+    let splits: Vec<DeclaredSplit> = match info.get("splits") {
+        Some(Value::Object(map)) => map
+            .iter()
+            .map(|(name, split)| DeclaredSplit {
+                name: name.clone(),
+                num_records: u64_of(split.get("num_examples")),
+                num_bytes: u64_of(split.get("num_bytes")),
+            })
+            .collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|split| DeclaredSplit {
+                name: str_of(split.get("name")).unwrap_or_default(),
+                num_records: u64_of(split.get("num_examples")),
+                num_bytes: u64_of(split.get("num_bytes")),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let num_records = if splits.is_empty() || splits.iter().any(|s| s.num_records.is_none()) {
+        None
+    } else {
+        Some(splits.iter().filter_map(|s| s.num_records).sum())
+    };
+    DeclaredRecordSet {
+        name: str_of(info.get("config_name")).unwrap_or_else(|| config.to_string()),
+        num_records,
+        splits,
+        fields,
+    }
+}
+
+fn parse_dataset_infos(source: &str, root: &Map<String, Value>) -> DeclaredMetadata {
+    // `dataset_info.json` holds one config; `dataset_infos.json` maps config names to infos.
+    let configs: Vec<(String, &Map<String, Value>)> =
+        if root.contains_key("features") || root.contains_key("splits") {
+            vec![("default".to_string(), root)]
+        } else {
+            root.iter()
+                .filter_map(|(name, info)| Some((name.clone(), info.as_object()?)))
+                .collect()
+        };
+    let mut licenses = Vec::new();
+    let mut description = None;
+    let mut name = None;
+    let mut record_sets = Vec::new();
+    for (config, info) in configs {
+        for license in licenses_of(info.get("license")) {
+            if !licenses.contains(&license) {
+                licenses.push(license);
+            }
+        }
+        description = description.or_else(|| str_of(info.get("description")));
+        name = name.or_else(|| str_of(info.get("dataset_name")));
+        record_sets.push(hf_record_set(&config, info));
+    }
+    DeclaredMetadata {
+        source: source.to_string(),
+        format: "dataset_infos".into(),
+        name,
+        description,
+        licenses,
+        record_sets,
+        files: Vec::new(),
+    }
+}
+
+/// Parses a declared-metadata document, telling Croissant JSON-LD apart from `datasets` infos by
+/// content rather than filename.
+pub fn parse_declared_metadata(source: &str, bytes: &[u8]) -> AppResult<DeclaredMetadata> {
+    let value: Value = serde_json::from_slice(bytes)
+        .map_err(|e| AppError::Invalid(format!("invalid JSON in {source}: {e}")))?;
+    let Value::Object(root) = value else {
+        return Err(AppError::Invalid(format!(
+            "expected a JSON object in {source}"
+        )));
+    };
+    let is_croissant = root.contains_key("@context")
+        || root.contains_key("recordSet")
+        || root.contains_key("cr:recordSet");
+    if is_croissant {
+        Ok(parse_croissant(source, &root))
+    } else {
+        Ok(parse_dataset_infos(source, &root))
+    }
+}
+
+fn declared_files_in(path: &Path) -> AppResult<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        return Err(AppError::Missing(format!(
+            "path does not exist: {}",
+            path.display()
+        )));
+    }
+    let mut found: Vec<PathBuf> = std::fs::read_dir(path)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file()
+                && p.file_name()
+                    .and_then(|f| f.to_str())
+                    .is_some_and(is_declared_metadata_file)
+        })
+        .collect();
+    found.sort();
+    Ok(found)
+}
+
+fn read_declared_file(path: &Path) -> AppResult<DeclaredMetadata> {
+    let len = std::fs::metadata(path)?.len();
+    if len > MAX_DECLARED_METADATA_BYTES {
+        return Err(AppError::Invalid(format!(
+            "{} is too large to parse ({len} bytes)",
+            path.display()
+        )));
+    }
+    let bytes = std::fs::read(path)?;
+    parse_declared_metadata(&path.display().to_string(), &bytes)
+}
+
+fn read_declared_metadata_sync(path: PathBuf) -> AppResult<Vec<DeclaredMetadata>> {
+    declared_files_in(&path)?
+        .into_iter()
+        .map(|p| read_declared_file(&p))
+        .collect()
+}
+
+/// Reads `croissant.json` / `dataset_infos.json` from a dataset directory (or a single such file).
+#[tauri::command]
+pub async fn read_declared_metadata(path: String) -> AppResult<Vec<DeclaredMetadata>> {
+    let path = PathBuf::from(path.trim());
+    spawn_blocking(move || read_declared_metadata_sync(path))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+/// Compares the fields a dataset declares against the schema observed in the same directory.
+/// `record_set` picks which declared record set (or HF config) to use; by default the one with the
+/// most fields.
+#[tauri::command]
+pub async fn compare_declared_schema(
+    path: String,
+    record_set: Option<String>,
+) -> AppResult<DeclaredSchemaComparison> {
+    let path = PathBuf::from(path.trim());
+    spawn_blocking(move || {
+        let declared = read_declared_metadata_sync(path.clone())?
+            .into_iter()
+            .find(|d| d.record_sets.iter().any(|rs| !rs.fields.is_empty()))
+            .ok_or_else(|| {
+                AppError::Missing(format!("no declared fields found in {}", path.display()))
+            })?;
+        let wanted = record_set
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+        let chosen = match wanted {
+            Some(name) => declared
+                .record_sets
+                .iter()
+                .find(|rs| rs.name == name)
+                .ok_or_else(|| AppError::Missing(format!("record set not declared: {name}")))?,
+            None => declared
+                .record_sets
+                .iter()
+                .max_by_key(|rs| rs.fields.len())
+                .ok_or_else(|| AppError::Missing("no record sets declared".into()))?,
+        };
+        let observed_dir = if path.is_file() {
+            path.parent()
+                .unwrap_or_else(|| Path::new("."))
+                .to_path_buf()
+        } else {
+            path.clone()
+        };
+        let fields = chosen
+            .fields
+            .iter()
+            .map(|f| (f.name.clone(), f.data_type.clone()))
+            .collect();
+        let diff = schema::diff_declared_fields(
+            &declared.format,
+            &declared.source,
+            fields,
+            &observed_dir,
+        )?;
+        let record_set = chosen.name.clone();
+        Ok(DeclaredSchemaComparison {
+            declared,
+            record_set,
+            diff,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
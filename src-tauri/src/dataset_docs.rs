@@ -0,0 +1,145 @@
+use serde::Serialize;
+use std::io::Read;
+use std::path::PathBuf;
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+
+/// Documentation files are shown inline; longer ones are cut off and flagged as truncated.
+pub const MAX_DOC_BYTES: u64 = 512 * 1024;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetDocument {
+    /// "license", "readme" or "datasheet".
+    kind: String,
+    name: String,
+    size: u64,
+    text: String,
+    truncated: bool,
+    url: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetDocsResponse {
+    source: String,
+    /// License identifier declared in the source's own metadata, when it has one.
+    declared_license: Option<String>,
+    documents: Vec<DatasetDocument>,
+}
+
+impl DatasetDocsResponse {
+    pub fn new(
+        source: String,
+        declared_license: Option<String>,
+        mut documents: Vec<DatasetDocument>,
+    ) -> Self {
+        documents.sort_by_key(|d| (kind_rank(&d.kind), d.name.clone()));
+        Self {
+            source,
+            declared_license,
+            documents,
+        }
+    }
+}
+
+fn kind_rank(kind: &str) -> u8 {
+    match kind {
+        "license" => 0,
+        "readme" => 1,
+        _ => 2,
+    }
+}
+
+/// Classifies a file as LICENSE / README / DATASHEET by its name, ignoring case and any
+/// `.md`/`.txt`/`.rst` style extension.
+pub fn doc_kind(filename: &str) -> Option<&'static str> {
+    let base = filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(filename)
+        .to_ascii_lowercase();
+    let (stem, ext) = match base.split_once('.') {
+        Some((stem, ext)) => (stem.to_string(), ext.to_string()),
+        None => (base, String::new()),
+    };
+    if !matches!(
+        ext.as_str(),
+        "" | "md" | "markdown" | "txt" | "rst" | "html" | "htm"
+    ) {
+        return None;
+    }
+    match stem.as_str() {
+        "license" | "licence" | "copying" | "license-data" | "data_license" => Some("license"),
+        "readme" => Some("readme"),
+        "datasheet" | "data_sheet" | "dataset_card" | "datacard" | "data_card" => Some("datasheet"),
+        _ => None,
+    }
+}
+
+/// Builds a document from the first bytes of a file; `size` is the full file size.
+pub fn document_from_bytes(
+    kind: &str,
+    name: String,
+    size: u64,
+    bytes: &[u8],
+    url: Option<String>,
+) -> DatasetDocument {
+    let limit = (MAX_DOC_BYTES as usize).min(bytes.len());
+    DatasetDocument {
+        kind: kind.to_string(),
+        name,
+        size,
+        text: String::from_utf8_lossy(&bytes[..limit]).to_string(),
+        truncated: size > limit as u64,
+        url,
+    }
+}
+
+fn local_dataset_docs_sync(path: PathBuf) -> AppResult<DatasetDocsResponse> {
+    let root = if path.is_file() {
+        path.parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+    } else if path.is_dir() {
+        path
+    } else {
+        return Err(AppError::Missing(format!(
+            "path does not exist: {}",
+            path.display()
+        )));
+    };
+    let mut documents = Vec::new();
+    for entry in std::fs::read_dir(&root)?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(kind) = doc_kind(&name) else {
+            continue;
+        };
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        std::fs::File::open(entry.path())?
+            .take(MAX_DOC_BYTES)
+            .read_to_end(&mut bytes)?;
+        documents.push(document_from_bytes(kind, name, meta.len(), &bytes, None));
+    }
+    Ok(DatasetDocsResponse::new(
+        root.display().to_string(),
+        None,
+        documents,
+    ))
+}
+
+/// Returns LICENSE, README and DATASHEET files found next to a local dataset.
+#[tauri::command]
+pub async fn local_dataset_docs(path: String) -> AppResult<DatasetDocsResponse> {
+    let path = PathBuf::from(path.trim());
+    spawn_blocking(move || local_dataset_docs_sync(path))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::decode_profile::{self, DecodeProfile};
+use crate::path_glob;
+
+/// Looked for in a dataset's root directory.
+const PROFILE_FILE: &str = ".dataset-inspector.toml";
+
+/// Preview settings kept next to a dataset, so everyone opening it sees the same field names
+/// and decodes:
+///
+/// ```toml
+/// default_shard = "shard-000000.tar"
+/// hidden_fields = ["__key__", "json"]
+///
+/// [rename]
+/// "seg.png" = "segmentation"
+///
+/// [decode.depth]
+/// decode_as = "float32"
+/// shape = [480, 640]
+/// ```
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all(serialize = "camelCase"), deny_unknown_fields)]
+pub struct DatasetProfile {
+    /// The profile file this was read from.
+    #[serde(skip_deserializing)]
+    path: String,
+    /// Display names keyed by field index, column name, or WebDataset field suffix.
+    #[serde(default)]
+    rename: BTreeMap<String, String>,
+    /// Decode overrides keyed like a decode profile. Overrides the user set in the app win.
+    #[serde(default)]
+    decode: DecodeProfile,
+    /// Chunk or shard file to select when the dataset is opened.
+    #[serde(default)]
+    default_shard: Option<String>,
+    /// Fields left out of previews, keyed like `rename`.
+    #[serde(default)]
+    hidden_fields: Vec<String>,
+}
+
+/// The directory a dataset path's profile file sits in: the directory itself, the folder
+/// holding an index file, or the fixed part of a shard glob.
+fn profile_root(path: &str) -> PathBuf {
+    if path_glob::is_glob(path) {
+        return path_glob::base_dir(path);
+    }
+    let path = Path::new(path);
+    if path.is_dir() {
+        return path.to_path_buf();
+    }
+    path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// Reads the profile file for `dataset` (an index path, directory, or shard glob) and makes
+/// its decode overrides apply to the dataset. `None` when it has no profile file.
+pub fn load(dataset: &str) -> AppResult<Option<DatasetProfile>> {
+    let dataset = dataset.trim();
+    let file = profile_root(dataset).join(PROFILE_FILE);
+    let text = match fs::read_to_string(&file) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            decode_profile::set_file_profile(dataset, DecodeProfile::new())?;
+            return Ok(None);
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let mut profile: DatasetProfile = toml::from_str(&text)
+        .map_err(|e| AppError::Invalid(format!("invalid {}: {e}", file.display())))?;
+    profile.path = file.display().to_string();
+    profile.rename = profile
+        .rename
+        .into_iter()
+        .map(|(field, name)| (field.trim().to_string(), name.trim().to_string()))
+        .filter(|(field, name)| !field.is_empty() && !name.is_empty())
+        .collect();
+    profile.default_shard = profile
+        .default_shard
+        .map(|shard| shard.trim().to_string())
+        .filter(|shard| !shard.is_empty());
+    profile.hidden_fields = profile
+        .hidden_fields
+        .into_iter()
+        .map(|field| field.trim().to_string())
+        .filter(|field| !field.is_empty())
+        .collect();
+    profile.decode = decode_profile::set_file_profile(dataset, std::mem::take(&mut profile.decode))
+        .map_err(|e| AppError::Invalid(format!("{}: {e}", file.display())))?;
+    Ok(Some(profile))
+}
+
+/// The profile file of the dataset at `path`, reloaded from disk. Loading a dataset reads it
+/// too; this is where a broken file's error shows up.
+#[tauri::command]
+pub async fn get_dataset_profile(path: String) -> AppResult<Option<DatasetProfile>> {
+    spawn_blocking(move || load(&path))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,259 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::mosaicml;
+use crate::network_fs;
+use crate::parquet::looks_like_parquet;
+use crate::webdataset::{litdata_index_name, looks_like_mds_shard, looks_like_wds_shard};
+
+const DEFAULT_DEPTH: u32 = 4;
+const MAX_DEPTH: u32 = 12;
+/// Directories read per scan; the result says when the tree had more.
+const MAX_DIRS: usize = 50_000;
+const SCAN_BUDGET: Duration = Duration::from_secs(60);
+/// Unreadable directories reported by path; more are only counted.
+const MAX_SKIPPED: usize = 100;
+/// Never datasets themselves, and often huge. Hidden directories are skipped as well.
+const SKIPPED_DIR_NAMES: &[&str] = &["node_modules", "__pycache__", "site-packages"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RootKind {
+    Litdata,
+    Mds,
+    Webdataset,
+    Parquet,
+}
+
+impl RootKind {
+    const ALL: [RootKind; 4] = [
+        RootKind::Litdata,
+        RootKind::Mds,
+        RootKind::Webdataset,
+        RootKind::Parquet,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            RootKind::Litdata => "litdata",
+            RootKind::Mds => "mds",
+            RootKind::Webdataset => "webdataset",
+            RootKind::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetRoot {
+    /// What to open: the `index.json` for LitData and MDS, the directory otherwise.
+    path: String,
+    dir: String,
+    /// `dir` relative to the scanned folder (`.` for the folder itself).
+    relative_dir: String,
+    depth: usize,
+    /// Chunk, shard or Parquet files directly in `dir` (for Parquet, across its partitions).
+    files: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetRootGroup {
+    /// `litdata`, `mds`, `webdataset` or `parquet`.
+    kind: &'static str,
+    roots: Vec<DatasetRoot>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetTreeScan {
+    root: String,
+    max_depth: u32,
+    /// Kinds with at least one root, each sorted by relative path.
+    groups: Vec<DatasetRootGroup>,
+    dirs_scanned: usize,
+    /// Directories that could not be read (or did not answer on a network share).
+    skipped: Vec<String>,
+    skipped_count: usize,
+    /// Whether the walk stopped at `MAX_DIRS` or the time budget before covering the tree.
+    truncated: bool,
+}
+
+/// File and subdirectory names of `dir`. Symlinks count as files, so linked directories are
+/// not followed (and cannot loop).
+fn read_names(dir: &Path) -> AppResult<(Vec<String>, Vec<String>)> {
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            dirs.push(name);
+        } else {
+            files.push(name);
+        }
+    }
+    files.sort();
+    dirs.sort();
+    Ok((files, dirs))
+}
+
+/// `key=value`, as in Hive-style partition directories.
+fn is_partition(name: &str) -> bool {
+    name.split_once('=').is_some_and(|(key, _)| !key.is_empty())
+}
+
+/// The directory a Parquet dataset starts at: `dir`, or the parent of its partition directories.
+fn partition_root<'a>(dir: &'a Path, root: &Path) -> &'a Path {
+    let mut current = dir;
+    while current != root
+        && current
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(is_partition)
+    {
+        match current.parent() {
+            Some(parent) => current = parent,
+            None => break,
+        }
+    }
+    current
+}
+
+fn scan(root: &Path, max_depth: u32) -> AppResult<DatasetTreeScan> {
+    if !root.is_dir() {
+        return Err(AppError::Invalid(format!(
+            "{} is not a directory",
+            root.display()
+        )));
+    }
+    // On network shares each directory read gets its own timeout, so one hung subtree is
+    // skipped instead of stalling the scan.
+    let slow = network_fs::is_slow(root);
+    let started = Instant::now();
+    let mut found: Vec<(RootKind, PathBuf, String, usize)> = Vec::new();
+    let mut parquet: BTreeMap<PathBuf, usize> = BTreeMap::new();
+    let mut skipped = Vec::new();
+    let mut skipped_count = 0;
+    let mut dirs_scanned = 0;
+    let mut truncated = false;
+    let mut queue = VecDeque::from([(root.to_path_buf(), 0u32)]);
+
+    while let Some((dir, depth)) = queue.pop_front() {
+        if dirs_scanned == MAX_DIRS || started.elapsed() > SCAN_BUDGET {
+            truncated = true;
+            break;
+        }
+        dirs_scanned += 1;
+        let listing = if slow {
+            let target = dir.clone();
+            let what = format!("reading {}", dir.display());
+            network_fs::with_timeout(&what, network_fs::PROBE_TIMEOUT, move || {
+                read_names(&target)
+            })
+        } else {
+            read_names(&dir)
+        };
+        let Ok((files, subdirs)) = listing else {
+            skipped_count += 1;
+            if skipped.len() < MAX_SKIPPED {
+                skipped.push(dir.display().to_string());
+            }
+            continue;
+        };
+
+        // Dataset roots are not descended into: their subfolders belong to them.
+        if let Some(index) = litdata_index_name(&files) {
+            let index_path = dir.join(index);
+            let (kind, path) = match mosaicml::detect_mds_index_path(&index_path) {
+                Some(path) => (RootKind::Mds, path),
+                None => (RootKind::Litdata, index_path.display().to_string()),
+            };
+            let chunks = files
+                .iter()
+                .filter(|n| looks_like_mds_shard(n) || n.ends_with(".bin") || n.contains(".bin."))
+                .count();
+            found.push((kind, dir, path, chunks));
+            continue;
+        }
+        let shards = files.iter().filter(|n| looks_like_wds_shard(n)).count();
+        if shards > 0 {
+            let path = dir.display().to_string();
+            found.push((RootKind::Webdataset, dir, path, shards));
+            continue;
+        }
+        let parquet_files = files.iter().filter(|n| looks_like_parquet(n)).count();
+        if parquet_files > 0 {
+            *parquet
+                .entry(partition_root(&dir, root).to_path_buf())
+                .or_default() += parquet_files;
+        }
+
+        if depth < max_depth {
+            let children = subdirs
+                .into_iter()
+                .filter(|n| !n.starts_with('.') && !SKIPPED_DIR_NAMES.contains(&n.as_str()))
+                .map(|n| (dir.join(n), depth + 1));
+            queue.extend(children);
+        }
+    }
+    found.extend(parquet.into_iter().map(|(dir, files)| {
+        let path = dir.display().to_string();
+        (RootKind::Parquet, dir, path, files)
+    }));
+
+    let groups = RootKind::ALL
+        .iter()
+        .filter_map(|kind| {
+            let mut roots = found
+                .iter()
+                .filter(|(k, ..)| k == kind)
+                .map(|(_, dir, path, files)| {
+                    let relative = dir.strip_prefix(root).unwrap_or(dir);
+                    DatasetRoot {
+                        path: path.clone(),
+                        dir: dir.display().to_string(),
+                        relative_dir: if relative.as_os_str().is_empty() {
+                            ".".into()
+                        } else {
+                            relative.display().to_string()
+                        },
+                        depth: relative.components().count(),
+                        files: *files,
+                    }
+                })
+                .collect::<Vec<_>>();
+            roots.sort_by(|a, b| a.relative_dir.cmp(&b.relative_dir));
+            (!roots.is_empty()).then(|| DatasetRootGroup {
+                kind: kind.name(),
+                roots,
+            })
+        })
+        .collect();
+
+    Ok(DatasetTreeScan {
+        root: root.display().to_string(),
+        max_depth,
+        groups,
+        dirs_scanned,
+        skipped,
+        skipped_count,
+        truncated,
+    })
+}
+
+/// Walks a folder up to `max_depth` levels down and lists every LitData, MDS, WebDataset and
+/// Parquet dataset in it, grouped by kind, so the right one can be opened from a folder of
+/// experiments.
+#[tauri::command]
+pub async fn scan_tree(path: String, max_depth: Option<u32>) -> AppResult<DatasetTreeScan> {
+    let max_depth = max_depth.unwrap_or(DEFAULT_DEPTH).min(MAX_DEPTH);
+    spawn_blocking(move || scan(Path::new(path.trim()), max_depth))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
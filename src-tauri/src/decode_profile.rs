@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use tauri::AppHandle;
+
+use crate::app_error::{AppError, AppResult};
+use crate::ipc_types::FieldPreview;
+use crate::settings;
+
+const DECODE_PROFILES_KEY: &str = "decode_profiles";
+const PREVIEW_TEXT_CHARS: usize = 8 * 1024;
+
+/// How to decode a field instead of the guessed type.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodeOverride {
+    /// `text`, `json`, a little-endian dtype (`int8` … `uint64`, `float32`, `float64`), or any
+    /// other extension to preview and open the bytes as.
+    #[serde(alias = "decode_as")]
+    decode_as: String,
+    /// Array shape for dtypes. The last dimension sets how many values go on each line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shape: Option<Vec<u64>>,
+}
+
+/// Overrides for one dataset, keyed by field index, column name, format, or extension.
+pub type DecodeProfile = BTreeMap<String, DecodeOverride>;
+
+/// Profiles keyed by the dataset's index path, directory, or content URL.
+static PROFILES: RwLock<BTreeMap<String, DecodeProfile>> = RwLock::new(BTreeMap::new());
+/// Overrides from the profile files shipped with datasets, keyed the same way. The user's own
+/// profile wins where both name a key.
+static FILE_PROFILES: RwLock<BTreeMap<String, DecodeProfile>> = RwLock::new(BTreeMap::new());
+
+fn normalize_key(key: &str) -> String {
+    key.trim().trim_start_matches('.').to_ascii_lowercase()
+}
+
+fn normalize_profile(profile: DecodeProfile) -> AppResult<DecodeProfile> {
+    let mut out = DecodeProfile::new();
+    for (key, mut rule) in profile {
+        let key = normalize_key(&key);
+        if key.is_empty() {
+            continue;
+        }
+        rule.decode_as = normalize_key(&rule.decode_as);
+        if rule.decode_as.is_empty() {
+            return Err(AppError::Invalid(format!("Missing decode type for {key}.")));
+        }
+        if let Some(shape) = rule.shape.as_ref() {
+            if dtype_width(&rule.decode_as).is_none() {
+                return Err(AppError::Invalid(format!(
+                    "A shape needs a numeric decode type ({key})."
+                )));
+            }
+            if shape.is_empty() || shape.contains(&0) {
+                return Err(AppError::Invalid(format!("Invalid shape for {key}.")));
+            }
+        }
+        out.insert(key, rule);
+    }
+    Ok(out)
+}
+
+/// The override for the first of `keys` that the dataset's profile, or its profile file,
+/// mentions.
+pub fn lookup(dataset: &str, keys: &[&str]) -> Option<DecodeOverride> {
+    let profiles = PROFILES.read().ok()?;
+    let files = FILE_PROFILES.read().ok()?;
+    let user = profiles.get(dataset.trim());
+    let file = files.get(dataset.trim());
+    keys.iter()
+        .find_map(|key| {
+            let key = normalize_key(key);
+            user.and_then(|p| p.get(&key))
+                .or_else(|| file.and_then(|p| p.get(&key)))
+        })
+        .cloned()
+}
+
+/// Replaces the overrides a dataset's profile file sets, returning them normalized. An empty
+/// profile removes them.
+pub fn set_file_profile(dataset: &str, profile: DecodeProfile) -> AppResult<DecodeProfile> {
+    let profile = normalize_profile(profile)?;
+    if let Ok(mut files) = FILE_PROFILES.write() {
+        if profile.is_empty() {
+            files.remove(dataset.trim());
+        } else {
+            files.insert(dataset.trim().to_string(), profile.clone());
+        }
+    }
+    Ok(profile)
+}
+
+/// Keys for a member or file name: the WebDataset field suffix (`seg.png` in `000.seg.png`) and
+/// the plain extension.
+pub fn name_keys(name: &str) -> Vec<&str> {
+    let base = name.trim().rsplit('/').next().unwrap_or("");
+    let mut keys = Vec::new();
+    if let Some((_, suffix)) = base.split_once('.') {
+        keys.push(suffix);
+    }
+    if let Some((_, ext)) = base.rsplit_once('.') {
+        if keys.last() != Some(&ext) {
+            keys.push(ext);
+        }
+    }
+    keys
+}
+
+fn dtype_width(dtype: &str) -> Option<usize> {
+    match dtype {
+        "int8" | "uint8" => Some(1),
+        "int16" | "uint16" => Some(2),
+        "int32" | "uint32" | "float32" => Some(4),
+        "int64" | "uint64" | "float64" => Some(8),
+        _ => None,
+    }
+}
+
+fn format_value(dtype: &str, b: &[u8]) -> String {
+    match dtype {
+        "int8" => (b[0] as i8).to_string(),
+        "uint8" => b[0].to_string(),
+        "int16" => i16::from_le_bytes([b[0], b[1]]).to_string(),
+        "uint16" => u16::from_le_bytes([b[0], b[1]]).to_string(),
+        "int32" => i32::from_le_bytes(b.try_into().unwrap_or([0; 4])).to_string(),
+        "uint32" => u32::from_le_bytes(b.try_into().unwrap_or([0; 4])).to_string(),
+        "float32" => f32::from_le_bytes(b.try_into().unwrap_or([0; 4])).to_string(),
+        "int64" => i64::from_le_bytes(b.try_into().unwrap_or([0; 8])).to_string(),
+        "uint64" => u64::from_le_bytes(b.try_into().unwrap_or([0; 8])).to_string(),
+        _ => f64::from_le_bytes(b.try_into().unwrap_or([0; 8])).to_string(),
+    }
+}
+
+impl DecodeOverride {
+    /// Values in `data` as text, one row of the shape per line. `total_size` is the size of the
+    /// whole field, checked against the shape.
+    fn array_text(&self, width: usize, data: &[u8], total_size: u64) -> String {
+        let mut out = String::new();
+        let mut row_len = usize::MAX;
+        if let Some(shape) = self.shape.as_deref() {
+            let expected = shape
+                .iter()
+                .try_fold(width as u64, |acc, dim| acc.checked_mul(*dim));
+            if expected != Some(total_size) {
+                out.push_str(&format!(
+                    "# shape {shape:?} does not match the field size ({total_size} bytes)\n"
+                ));
+            }
+            if shape.len() > 1 {
+                row_len = shape[shape.len() - 1] as usize;
+            }
+        }
+        for (i, chunk) in data.chunks_exact(width).enumerate() {
+            if i > 0 {
+                out.push(if i % row_len == 0 { '\n' } else { ' ' });
+            }
+            out.push_str(&format_value(&self.decode_as, chunk));
+        }
+        out
+    }
+
+    /// Rewrites a preview of `data` (a window of a field `preview.size` bytes long).
+    pub fn apply_to_preview(&self, data: &[u8], preview: &mut FieldPreview) {
+        let (text, ext) = match self.decode_as.as_str() {
+            "text" => (String::from_utf8_lossy(data).to_string(), "txt"),
+            "json" => (String::from_utf8_lossy(data).to_string(), "json"),
+            dtype => match dtype_width(dtype) {
+                Some(width) => (self.array_text(width, data, preview.size), "txt"),
+                None => {
+                    preview.guessed_ext = Some(dtype.to_string());
+                    return;
+                }
+            },
+        };
+        preview.preview_text = Some(text.chars().take(PREVIEW_TEXT_CHARS).collect());
+        preview.is_binary = false;
+        preview.guessed_ext = Some(ext.into());
+    }
+
+    /// Bytes and extension to write out when opening the whole field.
+    pub fn decode_for_open(&self, data: Vec<u8>) -> (Vec<u8>, String) {
+        match self.decode_as.as_str() {
+            "text" => (data, "txt".into()),
+            "json" => (data, "json".into()),
+            dtype => match dtype_width(dtype) {
+                Some(width) => {
+                    let text = self.array_text(width, &data, data.len() as u64);
+                    (text.into_bytes(), "txt".into())
+                }
+                None => (data, dtype.to_string()),
+            },
+        }
+    }
+}
+
+fn store(profiles: BTreeMap<String, DecodeProfile>) {
+    if let Ok(mut current) = PROFILES.write() {
+        *current = profiles;
+    }
+}
+
+/// Loads the persisted profiles. Called once at startup.
+pub fn load_settings(app: &AppHandle) {
+    if let Ok(Some(profiles)) =
+        settings::read_setting::<BTreeMap<String, DecodeProfile>>(app, DECODE_PROFILES_KEY)
+    {
+        let profiles = profiles
+            .into_iter()
+            .filter_map(|(dataset, profile)| Some((dataset, normalize_profile(profile).ok()?)))
+            .collect();
+        store(profiles);
+    }
+}
+
+#[tauri::command]
+pub async fn get_decode_profile(dataset: String) -> AppResult<DecodeProfile> {
+    Ok(PROFILES
+        .read()
+        .ok()
+        .and_then(|p| p.get(dataset.trim()).cloned())
+        .unwrap_or_default())
+}
+
+/// Replaces the dataset's overrides and returns them normalized. An empty profile removes it.
+#[tauri::command]
+pub async fn set_decode_profile(
+    app: AppHandle,
+    dataset: String,
+    profile: DecodeProfile,
+) -> AppResult<DecodeProfile> {
+    let dataset = dataset.trim().to_string();
+    if dataset.is_empty() {
+        return Err(AppError::Invalid("Missing dataset.".into()));
+    }
+    let profile = normalize_profile(profile)?;
+    let mut profiles = PROFILES.read().map(|p| p.clone()).unwrap_or_default();
+    if profile.is_empty() {
+        profiles.remove(&dataset);
+    } else {
+        profiles.insert(dataset, profile.clone());
+    }
+    settings::write_setting(&app, DECODE_PROFILES_KEY, &profiles)?;
+    store(profiles);
+    Ok(profile)
+}
@@ -0,0 +1,344 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tauri::{AppHandle, Manager};
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::app_error::{AppError, AppResult};
+use crate::ipc_types::FieldPreview;
+use crate::preview_kind::PreviewKind;
+
+const PLUGIN_DIR: &str = "decoder-plugins";
+/// Instructions one decode may run before it is stopped.
+const PLUGIN_FUEL: u64 = 2_000_000_000;
+const PLUGIN_MAX_MEMORY_BYTES: usize = 256 * 1024 * 1024;
+const PLUGIN_MAX_OUTPUT_BYTES: usize = 8 * 1024 * 1024;
+const PREVIEW_TEXT_CHARS: usize = 8 * 1024;
+
+/// Optional `<name>.json` next to `<name>.wasm`. Without it the plugin handles the `.<name>`
+/// extension.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct PluginManifest {
+    #[serde(default)]
+    extensions: Vec<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+struct LoadedPlugin {
+    name: String,
+    extensions: Vec<String>,
+    module: Module,
+}
+
+struct Registry {
+    engine: Engine,
+    plugins: Vec<LoadedPlugin>,
+    /// What the last scan of the plugin directory found.
+    listing: DecoderPluginList,
+}
+
+static REGISTRY: RwLock<Option<Registry>> = RwLock::new(None);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecoderPluginInfo {
+    name: String,
+    path: String,
+    extensions: Vec<String>,
+    description: Option<String>,
+    /// Why the module could not be loaded; such plugins are skipped.
+    error: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecoderPluginList {
+    dir: String,
+    plugins: Vec<DecoderPluginInfo>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginImage {
+    mime: String,
+    base64: String,
+}
+
+/// What a plugin returned for the previewed bytes.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPreview {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<PluginImage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// Kind of the text the plugin supplied, which replaces the raw bytes' own classification.
+    #[serde(skip)]
+    text_kind: Option<PreviewKind>,
+}
+
+impl PluginPreview {
+    pub fn text_kind(&self) -> Option<PreviewKind> {
+        self.text_kind.clone()
+    }
+}
+
+/// Output of `decode`, as JSON.
+#[derive(Deserialize)]
+struct PluginOutput {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    json: Option<serde_json::Value>,
+    #[serde(default)]
+    image: Option<PluginImage>,
+}
+
+fn plugin_dir(app: &AppHandle) -> AppResult<PathBuf> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Task(format!("app data dir unavailable: {e}")))?;
+    Ok(dir.join(PLUGIN_DIR))
+}
+
+fn normalize_ext(ext: &str) -> String {
+    ext.trim().trim_start_matches('.').to_ascii_lowercase()
+}
+
+fn read_manifest(wasm_path: &Path) -> AppResult<PluginManifest> {
+    match fs::read(wasm_path.with_extension("json")) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| AppError::Invalid(format!("plugin manifest is malformed: {e}"))),
+        Err(_) => Ok(PluginManifest::default()),
+    }
+}
+
+fn load_plugin(
+    engine: &Engine,
+    path: &Path,
+    name: &str,
+) -> (Option<LoadedPlugin>, DecoderPluginInfo) {
+    let mut info = DecoderPluginInfo {
+        name: name.to_string(),
+        path: path.display().to_string(),
+        extensions: Vec::new(),
+        description: None,
+        error: None,
+    };
+    let loaded = read_manifest(path).and_then(|manifest| {
+        let bytes = fs::read(path)?;
+        let module = Module::new(engine, &bytes[..])
+            .map_err(|e| AppError::Invalid(format!("invalid WASM module: {e}")))?;
+        Ok((manifest, module))
+    });
+    match loaded {
+        Ok((manifest, module)) => {
+            let mut extensions = manifest
+                .extensions
+                .iter()
+                .map(|e| normalize_ext(e))
+                .filter(|e| !e.is_empty())
+                .collect::<Vec<_>>();
+            if extensions.is_empty() {
+                extensions.push(normalize_ext(name));
+            }
+            info.extensions = extensions.clone();
+            info.description = manifest.description;
+            let plugin = LoadedPlugin {
+                name: name.to_string(),
+                extensions,
+                module,
+            };
+            (Some(plugin), info)
+        }
+        Err(err) => {
+            info.error = Some(err.to_string());
+            (None, info)
+        }
+    }
+}
+
+/// Compiles every `*.wasm` in the plugin directory, replacing the loaded set.
+fn reload(app: &AppHandle) -> AppResult<DecoderPluginList> {
+    let dir = plugin_dir(app)?;
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+
+    let mut paths = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|x| x.to_str()) == Some("wasm"))
+            .collect::<Vec<_>>(),
+        Err(_) => Vec::new(),
+    };
+    paths.sort();
+
+    let mut plugins = Vec::new();
+    let mut infos = Vec::new();
+    for path in paths {
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let (plugin, info) = load_plugin(&engine, &path, &name);
+        plugins.extend(plugin);
+        infos.push(info);
+    }
+    let listing = DecoderPluginList {
+        dir: dir.display().to_string(),
+        plugins: infos,
+    };
+    if let Ok(mut registry) = REGISTRY.write() {
+        *registry = Some(Registry {
+            engine,
+            plugins,
+            listing: listing.clone(),
+        });
+    }
+    Ok(listing)
+}
+
+/// Loads the installed plugins. Called once at startup.
+pub fn load_plugins(app: &AppHandle) {
+    let _ = reload(app);
+}
+
+/// Runs the module's `decode` export on `data`.
+///
+/// ABI: the module exports `memory`, `alloc(len: i32) -> i32`, and
+/// `decode(ptr: i32, len: i32) -> i64`, which returns `(out_ptr << 32) | out_len` pointing at UTF-8
+/// JSON `{"text"?: string, "json"?: any, "image"?: {"mime": string, "base64": string}}`.
+fn run_plugin(engine: &Engine, module: &Module, data: &[u8]) -> Result<PluginOutput, String> {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(PLUGIN_MAX_MEMORY_BYTES)
+        .build();
+    let mut store = Store::new(engine, limits);
+    store.limiter(|limits| limits);
+    store.set_fuel(PLUGIN_FUEL).map_err(|e| e.to_string())?;
+
+    let linker = Linker::<StoreLimits>::new(engine);
+    let instance = linker
+        .instantiate(&mut store, module)
+        .and_then(|pre| pre.start(&mut store))
+        .map_err(|e| format!("instantiation failed: {e}"))?;
+    let memory = instance
+        .get_memory(&store, "memory")
+        .ok_or("module does not export memory")?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&store, "alloc")
+        .map_err(|e| format!("missing alloc export: {e}"))?;
+    let decode = instance
+        .get_typed_func::<(i32, i32), i64>(&store, "decode")
+        .map_err(|e| format!("missing decode export: {e}"))?;
+
+    let len = i32::try_from(data.len()).map_err(|_| "input too large".to_string())?;
+    let ptr = alloc.call(&mut store, len).map_err(|e| e.to_string())?;
+    memory
+        .write(&mut store, ptr as u32 as usize, data)
+        .map_err(|e| format!("alloc returned an invalid buffer: {e}"))?;
+    let packed = decode
+        .call(&mut store, (ptr, len))
+        .map_err(|e| format!("decode trapped: {e}"))? as u64;
+
+    let out_ptr = (packed >> 32) as usize;
+    let out_len = (packed & 0xFFFF_FFFF) as usize;
+    if out_len > PLUGIN_MAX_OUTPUT_BYTES {
+        return Err(format!("output too large ({out_len} bytes)"));
+    }
+    let mut out = vec![0u8; out_len];
+    memory
+        .read(&store, out_ptr, &mut out)
+        .map_err(|e| format!("decode returned an invalid buffer: {e}"))?;
+    serde_json::from_slice(&out).map_err(|e| format!("output is not valid JSON: {e}"))
+}
+
+/// True if a loaded plugin is registered for `ext`.
+pub fn handles(ext: &str) -> bool {
+    let ext = normalize_ext(ext);
+    REGISTRY.read().is_ok_and(|registry| {
+        registry
+            .as_ref()
+            .is_some_and(|r| r.plugins.iter().any(|p| p.extensions.contains(&ext)))
+    })
+}
+
+/// Lets the plugin registered for the preview's guessed extension decode the previewed bytes.
+pub fn apply(data: &[u8], preview: &mut FieldPreview) {
+    let Some(ext) = preview.guessed_ext.as_deref().map(normalize_ext) else {
+        return;
+    };
+    let Ok(registry) = REGISTRY.read() else {
+        return;
+    };
+    let Some(registry) = registry.as_ref() else {
+        return;
+    };
+    let Some(plugin) = registry
+        .plugins
+        .iter()
+        .find(|p| p.extensions.contains(&ext))
+    else {
+        return;
+    };
+
+    let mut result = PluginPreview {
+        name: plugin.name.clone(),
+        image: None,
+        error: None,
+        text_kind: None,
+    };
+    match run_plugin(&registry.engine, &plugin.module, data) {
+        Ok(output) => {
+            let text = match output.text {
+                Some(text) => Some((text, PreviewKind::Text)),
+                None => output
+                    .json
+                    .as_ref()
+                    .and_then(|v| serde_json::to_string_pretty(v).ok())
+                    .map(|text| (text, PreviewKind::Json)),
+            };
+            if let Some((text, kind)) = text {
+                preview.preview_text = Some(text.chars().take(PREVIEW_TEXT_CHARS).collect());
+                preview.text_length = Some(text.chars().count());
+                preview.is_binary = false;
+                preview.kind = kind.clone();
+                result.text_kind = Some(kind);
+            }
+            result.image = output.image;
+        }
+        Err(err) => result.error = Some(err),
+    }
+    preview.plugin = Some(result);
+}
+
+#[tauri::command]
+pub async fn list_decoder_plugins(app: AppHandle) -> AppResult<DecoderPluginList> {
+    let registry = REGISTRY
+        .read()
+        .map_err(|_| AppError::Task("decoder plugin lock poisoned".into()))?;
+    match registry.as_ref() {
+        Some(registry) => Ok(registry.listing.clone()),
+        None => Ok(DecoderPluginList {
+            dir: plugin_dir(&app)?.display().to_string(),
+            plugins: Vec::new(),
+        }),
+    }
+}
+
+/// Rescans the plugin directory, creating it if needed, and reports every module found including
+/// ones that failed to load.
+#[tauri::command]
+pub async fn reload_decoder_plugins(app: AppHandle) -> AppResult<DecoderPluginList> {
+    fs::create_dir_all(plugin_dir(&app)?)?;
+    tauri::async_runtime::spawn_blocking(move || reload(&app))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,90 @@
+use std::io::Read;
+
+/// Decompressed bytes kept for one preview, whatever window the caller asks for.
+const MAX_INFLATED_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Clone, Copy)]
+enum Codec {
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl Codec {
+    fn sniff(data: &[u8]) -> Option<Self> {
+        if data.starts_with(&[0x1F, 0x8B]) {
+            Some(Codec::Gzip)
+        } else if data.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            Some(Codec::Zstd)
+        } else if data.starts_with(b"BZh") && data.get(3).is_some_and(|b| (b'1'..=b'9').contains(b))
+        {
+            Some(Codec::Bzip2)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Codec::Gzip => "gzip",
+            Codec::Zstd => "zstd",
+            Codec::Bzip2 => "bzip2",
+        }
+    }
+
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Codec::Gzip => &[".gz", ".gzip"],
+            Codec::Zstd => &[".zst", ".zstd"],
+            Codec::Bzip2 => &[".bz2", ".bzip2"],
+        }
+    }
+}
+
+/// A member's preview window with its single compression layer removed.
+pub struct Unwrapped {
+    /// `gzip`, `zstd` or `bzip2`.
+    pub codec: &'static str,
+    /// The member name without its compression extension, for guessing what is inside.
+    pub inner_name: String,
+    pub data: Vec<u8>,
+}
+
+/// Decompresses a window that starts with a gzip, zstd or bzip2 header, keeping at most `limit`
+/// bytes. The window may end mid-stream; everything decoded before the cut is kept. Returns
+/// `None` for uncompressed data or when nothing decodes.
+pub fn unwrap(name: &str, data: &[u8], limit: usize) -> Option<Unwrapped> {
+    let codec = Codec::sniff(data)?;
+    let reader: Box<dyn Read + '_> = match codec {
+        Codec::Gzip => Box::new(flate2::read::GzDecoder::new(data)),
+        Codec::Zstd => Box::new(zstd::stream::read::Decoder::new(data).ok()?),
+        Codec::Bzip2 => Box::new(bzip2::read::BzDecoder::new(data)),
+    };
+    let inflated = read_prefix(reader, limit.min(MAX_INFLATED_BYTES));
+    if inflated.is_empty() {
+        return None;
+    }
+    let lower = name.to_ascii_lowercase();
+    let inner_name = codec
+        .extensions()
+        .iter()
+        .find(|ext| lower.ends_with(*ext))
+        .map_or(name, |ext| &name[..name.len() - ext.len()]);
+    Some(Unwrapped {
+        codec: codec.name(),
+        inner_name: inner_name.to_string(),
+        data: inflated,
+    })
+}
+
+fn read_prefix(mut reader: impl Read, limit: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut buf = [0u8; 16 * 1024];
+    while out.len() < limit {
+        match reader.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => out.extend_from_slice(&buf[..n.min(limit - out.len())]),
+        }
+    }
+    out
+}
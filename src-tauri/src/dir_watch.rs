@@ -0,0 +1,158 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::app_error::{AppError, AppResult};
+use crate::webdataset::{looks_like_litdata_file, looks_like_mds_shard, looks_like_wds_shard};
+
+pub const DIR_CHANGED_EVENT: &str = "watch://dir-changed";
+/// Polling instead of OS notifications keeps this working on network mounts, where dataset
+/// builds often write.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Default)]
+pub struct DirWatchRegistry(Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>);
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedFileInfo {
+    filename: String,
+    bytes: u64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DirChangedEvent {
+    dir_path: String,
+    added: Vec<WatchedFileInfo>,
+    changed: Vec<WatchedFileInfo>,
+    removed: Vec<String>,
+}
+
+type Snapshot = BTreeMap<String, (u64, Option<SystemTime>)>;
+
+fn is_dataset_file(filename: &str) -> bool {
+    looks_like_wds_shard(filename)
+        || looks_like_mds_shard(filename)
+        || looks_like_litdata_file(filename)
+}
+
+fn snapshot(dir: &Path) -> Snapshot {
+    let mut out = Snapshot::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return out;
+    };
+    for entry in entries.flatten() {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        if !is_dataset_file(&filename) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.is_file() {
+            out.insert(filename, (meta.len(), meta.modified().ok()));
+        }
+    }
+    out
+}
+
+fn diff(dir_path: &str, before: &Snapshot, after: &Snapshot) -> Option<DirChangedEvent> {
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (filename, (bytes, modified)) in after {
+        match before.get(filename) {
+            None => added.push(WatchedFileInfo {
+                filename: filename.clone(),
+                bytes: *bytes,
+            }),
+            Some((old_bytes, old_modified)) if old_bytes != bytes || old_modified != modified => {
+                changed.push(WatchedFileInfo {
+                    filename: filename.clone(),
+                    bytes: *bytes,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    let removed = before
+        .keys()
+        .filter(|filename| !after.contains_key(*filename))
+        .cloned()
+        .collect::<Vec<_>>();
+    if added.is_empty() && changed.is_empty() && removed.is_empty() {
+        return None;
+    }
+    Some(DirChangedEvent {
+        dir_path: dir_path.to_string(),
+        added,
+        changed,
+        removed,
+    })
+}
+
+fn watch_loop(app: AppHandle, dir: PathBuf, dir_path: String, stop: Arc<AtomicBool>) {
+    let mut previous = snapshot(&dir);
+    while !stop.load(Ordering::Relaxed) {
+        std::thread::sleep(POLL_INTERVAL);
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        let current = snapshot(&dir);
+        if let Some(event) = diff(&dir_path, &previous, &current) {
+            let _ = app.emit(DIR_CHANGED_EVENT, event);
+        }
+        previous = current;
+    }
+}
+
+/// Starts polling a dataset directory and emits `watch://dir-changed` whenever shards or chunks
+/// appear, disappear, or change size. Returns the watched path, which identifies the watch.
+#[tauri::command]
+pub async fn watch_dataset_dir(
+    app: AppHandle,
+    registry: State<'_, DirWatchRegistry>,
+    dir_path: String,
+) -> AppResult<String> {
+    let dir = PathBuf::from(dir_path.trim());
+    if !dir.is_dir() {
+        return Err(AppError::Invalid(format!(
+            "not a directory: {}",
+            dir.display()
+        )));
+    }
+    let dir_path = dir.display().to_string();
+    let mut guard = registry
+        .0
+        .lock()
+        .map_err(|_| AppError::Task("dir watch registry lock poisoned".into()))?;
+    if guard.contains_key(&dir_path) {
+        return Ok(dir_path);
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    guard.insert(dir_path.clone(), stop.clone());
+    let watched = dir_path.clone();
+    std::thread::spawn(move || watch_loop(app, dir, watched, stop));
+    Ok(dir_path)
+}
+
+#[tauri::command]
+pub async fn unwatch_dataset_dir(
+    registry: State<'_, DirWatchRegistry>,
+    dir_path: String,
+) -> AppResult<bool> {
+    let dir_path = PathBuf::from(dir_path.trim()).display().to_string();
+    let mut guard = registry
+        .0
+        .lock()
+        .map_err(|_| AppError::Task("dir watch registry lock poisoned".into()))?;
+    let Some(stop) = guard.remove(&dir_path) else {
+        return Ok(false);
+    };
+    stop.store(true, Ordering::Relaxed);
+    Ok(true)
+}
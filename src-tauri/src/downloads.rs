@@ -0,0 +1,168 @@
+use serde::Serialize;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tauri::{async_runtime::spawn_blocking, AppHandle, Emitter, Manager};
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::checksum;
+use crate::http_limit;
+use crate::jobs::JobRegistry;
+use crate::offline;
+use crate::throttle;
+
+const PROGRESS_STEP_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    job_id: String,
+    file: String,
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+pub fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+pub fn emit_progress(
+    app: &AppHandle,
+    job_id: &str,
+    file: &str,
+    downloaded: u64,
+    total: Option<u64>,
+) {
+    let _ = app.emit(
+        "download://progress",
+        DownloadProgress {
+            job_id: job_id.to_string(),
+            file: file.to_string(),
+            downloaded,
+            total,
+        },
+    );
+}
+
+/// Streams `url` into `dest` through a sibling `.part` file, resuming a previous
+/// partial transfer with a Range request. Returns whether the transfer was resumed.
+/// Closing the app stops the transfer and removes the `.part` file.
+pub async fn download_to_file(
+    http: &reqwest::Client,
+    app: &AppHandle,
+    job_id: &str,
+    url: Url,
+    dest: &Path,
+    expected_size: Option<u64>,
+) -> AppResult<bool> {
+    let part = part_path(dest);
+    let label = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let job = app.state::<JobRegistry>().start(Some(part.clone()));
+    let mut offset = fs::metadata(&part).map(|m| m.len()).unwrap_or(0);
+    if expected_size.is_some_and(|size| offset > size) {
+        fs::remove_file(&part)?;
+        offset = 0;
+    }
+
+    offline::ensure_online(&url)?;
+    let permit = http_limit::acquire(&url).await?;
+    let build = || {
+        let req = http.get(url.clone());
+        if offset > 0 {
+            req.header(reqwest::header::RANGE, format!("bytes={offset}-"))
+        } else {
+            req
+        }
+    };
+    let mut res = throttle::send(&url, "download", build).await?;
+    drop(permit);
+    let status = res.status();
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE
+        && offset > 0
+        && expected_size == Some(offset)
+    {
+        fs::rename(&part, dest)?;
+        return Ok(true);
+    }
+    if !status.is_success() {
+        return Err(AppError::Remote(format!(
+            "download HTTP {status} from {url}"
+        )));
+    }
+
+    let resumed = offset > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+    if resumed {
+        let range_start = res
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("bytes "))
+            .and_then(|v| v.split_once('-'))
+            .and_then(|(start, _)| start.trim().parse::<u64>().ok());
+        if range_start != Some(offset) {
+            fs::remove_file(&part)?;
+            return Err(AppError::Remote(format!(
+                "server answered the resume of {label} at an unexpected offset; retry to restart it"
+            )));
+        }
+    }
+    let mut file = if resumed {
+        OpenOptions::new().append(true).open(&part)?
+    } else {
+        offset = 0;
+        File::create(&part)?
+    };
+    let total = expected_size.or_else(|| res.content_length().map(|len| len + offset));
+
+    let mut downloaded = offset;
+    let mut next_report = downloaded;
+    while let Some(chunk) = res
+        .chunk()
+        .await
+        .map_err(|e| AppError::Remote(format!("download read failed: {e}")))?
+    {
+        job.ensure_running()?;
+        file.write_all(&chunk)?;
+        downloaded += chunk.len() as u64;
+        if downloaded >= next_report {
+            emit_progress(app, job_id, &label, downloaded, total);
+            next_report = downloaded + PROGRESS_STEP_BYTES;
+        }
+    }
+    file.flush()?;
+    drop(file);
+
+    if let Some(expected) = expected_size {
+        if downloaded != expected {
+            return Err(AppError::Remote(format!(
+                "incomplete download of {label}: got {downloaded} of {expected} bytes"
+            )));
+        }
+    }
+    emit_progress(app, job_id, &label, downloaded, total);
+    fs::rename(&part, dest)?;
+    Ok(resumed)
+}
+
+/// Compares a file against an `algorithm:hex` checksum; `None` when the algorithm is unsupported.
+pub async fn verify_checksum(path: PathBuf, expected: String) -> AppResult<Option<bool>> {
+    let Some((algo, digest)) = checksum::split_checksum(&expected) else {
+        return Ok(None);
+    };
+    spawn_blocking(move || {
+        Ok(checksum::hash_file(&path, &algo)?.map(|actual| actual.eq_ignore_ascii_case(&digest)))
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
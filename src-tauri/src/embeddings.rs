@@ -0,0 +1,478 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::app_error::{AppError, AppResult};
+
+pub const DEFAULT_PROJECTION_SAMPLES: usize = 2000;
+pub const MAX_PROJECTION_SAMPLES: usize = 20_000;
+/// UMAP compares every pair of vectors to find neighbours, so it runs on fewer samples.
+pub const MAX_UMAP_SAMPLES: usize = 5_000;
+const POWER_ITERATIONS: usize = 200;
+const POWER_TOLERANCE: f64 = 1e-9;
+const UMAP_NEIGHBORS: usize = 15;
+const UMAP_EPOCHS: usize = 500;
+/// Low-dimensional similarity curve `1 / (1 + a·d^2b)` for umap-learn's defaults
+/// `min_dist = 0.1`, `spread = 1.0`.
+const UMAP_A: f64 = 1.577;
+const UMAP_B: f64 = 0.8951;
+const UMAP_NEGATIVE_RATE: f64 = 5.0;
+const UMAP_LEARNING_RATE: f64 = 1.0;
+const UMAP_GRADIENT_CLIP: f64 = 4.0;
+/// The PCA layout UMAP starts from is scaled to span this far from the origin.
+const UMAP_INIT_EXTENT: f64 = 10.0;
+const UMAP_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+const SIGMA_SEARCH_STEPS: usize = 64;
+const SIGMA_TOLERANCE: f64 = 1e-5;
+/// Floor on a point's bandwidth, relative to its mean neighbour distance.
+const MIN_SIGMA_SCALE: f64 = 1e-3;
+
+/// How vectors are reduced to two dimensions.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectionMethod {
+    /// The first two principal components; linear, and the axes keep their meaning.
+    #[default]
+    Pca,
+    /// Keeps each sample near its nearest neighbours, so clusters separate even when they
+    /// overlap along every linear direction.
+    Umap,
+}
+
+impl ProjectionMethod {
+    pub fn max_samples(self) -> usize {
+        match self {
+            ProjectionMethod::Pca => MAX_PROJECTION_SAMPLES,
+            ProjectionMethod::Umap => MAX_UMAP_SAMPLES,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingPoint {
+    /// Sample key (WebDataset) or `shard:item` (MDS), for jumping back to the sample.
+    id: String,
+    x: f64,
+    y: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingProjection {
+    /// Length of each vector.
+    dims: usize,
+    method: ProjectionMethod,
+    /// Share of the total variance along each of the two axes; PCA only.
+    explained_variance: Option<[f64; 2]>,
+    points: Vec<EmbeddingPoint>,
+    /// Sampled fields that did not decode to a vector of `dims` floats.
+    skipped: usize,
+}
+
+fn dtype_values(dtype: &str, data: &[u8]) -> Option<Vec<f64>> {
+    let values = match dtype {
+        "float16" | "f2" => data
+            .chunks_exact(2)
+            .map(|b| f16_to_f64(u16::from_le_bytes([b[0], b[1]])))
+            .collect(),
+        "float32" | "f4" => data
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64)
+            .collect(),
+        "float64" | "f8" => data
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap_or([0; 8])))
+            .collect(),
+        _ => return None,
+    };
+    Some(values)
+}
+
+pub fn f16_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = ((bits >> 10) & 0x1f) as i32;
+    let frac = (bits & 0x3ff) as f64;
+    match exp {
+        0 => sign * frac * 2f64.powi(-24),
+        0x1f if frac == 0.0 => sign * f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => sign * (1.0 + frac / 1024.0) * 2f64.powi(exp - 15),
+    }
+}
+
+/// The header dict of a `.npy` payload and the offset its data starts at.
+fn npy_header(data: &[u8]) -> Option<(&str, usize)> {
+    let rest = data.strip_prefix(b"\x93NUMPY")?;
+    let (header_len, header_start) = match rest.first()? {
+        1 => (
+            u16::from_le_bytes([*rest.get(2)?, *rest.get(3)?]) as usize,
+            10,
+        ),
+        _ => (
+            u32::from_le_bytes(rest.get(2..6)?.try_into().ok()?) as usize,
+            12,
+        ),
+    };
+    let header = std::str::from_utf8(data.get(header_start..header_start + header_len)?).ok()?;
+    Some((header, header_start + header_len))
+}
+
+fn npy_descr(header: &str) -> Option<&str> {
+    Some(
+        header
+            .split("'descr':")
+            .nth(1)?
+            .split('\'')
+            .nth(1)?
+            .trim_start_matches(['<', '|']),
+    )
+}
+
+/// Dtype (the `descr` minus a `<` or `|` prefix) and shape declared in a `.npy` header.
+pub fn npy_shape(data: &[u8]) -> Option<(String, Vec<usize>)> {
+    let (header, _) = npy_header(data)?;
+    let dims = header
+        .split("'shape':")
+        .nth(1)?
+        .split('(')
+        .nth(1)?
+        .split(')')
+        .next()?;
+    let shape = dims
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.trim_end_matches('L').parse().ok())
+        .collect::<Option<Vec<usize>>>()?;
+    Some((npy_descr(header)?.to_string(), shape))
+}
+
+/// Values of a little-endian float `.npy` array, flattened. Other dtypes and byte orders are
+/// rejected.
+pub fn npy_values(data: &[u8]) -> Option<Vec<f64>> {
+    let (header, data_start) = npy_header(data)?;
+    if header.contains("'fortran_order': True") {
+        return None;
+    }
+    dtype_values(npy_descr(header)?, &data[data_start..])
+}
+
+/// Values of an MDS field with a fixed float `ndarray:<dtype>:<shape>` encoding (stored as raw
+/// little-endian values) or of an `.npy` payload in a bytes column.
+pub fn mds_values(encoding: &str, data: &[u8]) -> Option<Vec<f64>> {
+    let encoding = encoding.trim().to_lowercase();
+    let mut parts = encoding.split(':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("ndarray"), Some(dtype), Some(_shape)) => dtype_values(dtype, data),
+        _ => npy_values(data),
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalize(v: &mut [f64]) -> f64 {
+    let norm = dot(v, v).sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+    norm
+}
+
+/// Leading eigenvector and eigenvalue of the covariance of `rows` (already centered), orthogonal to
+/// `exclude`, by power iteration on `XᵀX v` so the d×d matrix is never built.
+fn principal_axis(rows: &[Vec<f64>], dims: usize, exclude: Option<&[f64]>) -> (Vec<f64>, f64) {
+    let mut v = (0..dims)
+        .map(|i| 1.0 / (1.0 + i as f64))
+        .collect::<Vec<_>>();
+    let mut eigenvalue = 0.0;
+    for _ in 0..POWER_ITERATIONS {
+        if let Some(e) = exclude {
+            let proj = dot(&v, e);
+            v.iter_mut().zip(e).for_each(|(x, y)| *x -= proj * y);
+        }
+        normalize(&mut v);
+        let mut next = vec![0.0; dims];
+        for row in rows {
+            let score = dot(row, &v);
+            next.iter_mut().zip(row).for_each(|(n, r)| *n += score * r);
+        }
+        if let Some(e) = exclude {
+            let proj = dot(&next, e);
+            next.iter_mut().zip(e).for_each(|(x, y)| *x -= proj * y);
+        }
+        let value = normalize(&mut next) / rows.len().max(1) as f64;
+        let converged = (value - eigenvalue).abs() <= POWER_TOLERANCE * value.max(1.0);
+        eigenvalue = value;
+        v = next;
+        if converged {
+            break;
+        }
+    }
+    (v, eigenvalue)
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// Each row's `k` nearest other rows with their distances, closest first, by brute force.
+fn nearest_neighbors(rows: &[Vec<f64>], k: usize) -> Vec<Vec<(usize, f64)>> {
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut dists = rows
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(j, other)| (j, squared_distance(row, other).sqrt()))
+                .collect::<Vec<_>>();
+            if k < dists.len() {
+                dists.select_nth_unstable_by(k, |a, b| a.1.total_cmp(&b.1));
+                dists.truncate(k);
+            }
+            dists.sort_by(|a, b| a.1.total_cmp(&b.1));
+            dists
+        })
+        .collect()
+}
+
+/// Bandwidth that makes a point's neighbour memberships sum to `target`, by bisection.
+fn smooth_sigma(neighbors: &[(usize, f64)], rho: f64, target: f64) -> f64 {
+    let (mut lo, mut hi, mut sigma) = (0.0, f64::INFINITY, 1.0);
+    for _ in 0..SIGMA_SEARCH_STEPS {
+        let sum = neighbors
+            .iter()
+            .map(|(_, d)| {
+                let gap = d - rho;
+                if gap > 0.0 {
+                    (-gap / sigma).exp()
+                } else {
+                    1.0
+                }
+            })
+            .sum::<f64>();
+        if (sum - target).abs() < SIGMA_TOLERANCE {
+            break;
+        }
+        if sum > target {
+            hi = sigma;
+            sigma = (lo + hi) / 2.0;
+        } else {
+            lo = sigma;
+            sigma = if hi.is_finite() {
+                (lo + hi) / 2.0
+            } else {
+                sigma * 2.0
+            };
+        }
+    }
+    let mean = neighbors.iter().map(|(_, d)| d).sum::<f64>() / neighbors.len() as f64;
+    sigma.max(MIN_SIGMA_SCALE * mean).max(f64::MIN_POSITIVE)
+}
+
+/// UMAP's fuzzy neighbour graph: memberships are 1 at a point's nearest neighbour and decay with
+/// distance beyond it, and the two directions of an edge are merged as `a + b - ab`.
+fn fuzzy_graph(neighbors: &[Vec<(usize, f64)>]) -> Vec<(usize, usize, f64)> {
+    let mut weights: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+    for (i, row) in neighbors.iter().enumerate() {
+        if row.is_empty() {
+            continue;
+        }
+        let rho = row
+            .iter()
+            .map(|(_, d)| *d)
+            .find(|d| *d > 0.0)
+            .unwrap_or(0.0);
+        let sigma = smooth_sigma(row, rho, (row.len() as f64).log2());
+        for &(j, d) in row {
+            let w = if d <= rho {
+                1.0
+            } else {
+                (-(d - rho) / sigma).exp()
+            };
+            let merged = weights.entry((i.min(j), i.max(j))).or_insert(0.0);
+            *merged = *merged + w - *merged * w;
+        }
+    }
+    weights
+        .into_iter()
+        .filter(|(_, w)| *w > 0.0)
+        .map(|((i, j), w)| (i, j, w))
+        .collect()
+}
+
+/// xorshift64*, for picking negative samples; fixed-seeded so a field always gives the same map.
+struct XorShift(u64);
+
+impl XorShift {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_F491_4F6C_DD1D) % n as u64) as usize
+    }
+}
+
+fn clip(value: f64) -> f64 {
+    value.clamp(-UMAP_GRADIENT_CLIP, UMAP_GRADIENT_CLIP)
+}
+
+/// Gradient pulling `from` towards a neighbour at `to`.
+fn attraction(from: [f64; 2], to: [f64; 2]) -> [f64; 2] {
+    let delta = [from[0] - to[0], from[1] - to[1]];
+    let dist2 = delta[0] * delta[0] + delta[1] * delta[1];
+    if dist2 <= 0.0 {
+        return [0.0; 2];
+    }
+    let coeff =
+        -2.0 * UMAP_A * UMAP_B * dist2.powf(UMAP_B - 1.0) / (UMAP_A * dist2.powf(UMAP_B) + 1.0);
+    [clip(coeff * delta[0]), clip(coeff * delta[1])]
+}
+
+/// Gradient pushing `from` away from a random non-neighbour at `to`.
+fn repulsion(from: [f64; 2], to: [f64; 2]) -> [f64; 2] {
+    let delta = [from[0] - to[0], from[1] - to[1]];
+    let dist2 = delta[0] * delta[0] + delta[1] * delta[1];
+    if dist2 <= 0.0 {
+        return [UMAP_GRADIENT_CLIP; 2];
+    }
+    let coeff = 2.0 * UMAP_B / ((0.001 + dist2) * (UMAP_A * dist2.powf(UMAP_B) + 1.0));
+    [clip(coeff * delta[0]), clip(coeff * delta[1])]
+}
+
+/// Moves `coords` by stochastic gradient descent so that graph neighbours sit close together and
+/// random pairs apart. Strong edges are sampled every epoch, weak ones proportionally less often.
+fn optimize_layout(coords: &mut [[f64; 2]], edges: &[(usize, usize, f64)]) {
+    let max_weight = edges.iter().map(|e| e.2).fold(0.0, f64::max);
+    let edges = edges
+        .iter()
+        .filter(|e| e.2 >= max_weight / UMAP_EPOCHS as f64)
+        .collect::<Vec<_>>();
+    let epochs_per_sample = edges.iter().map(|e| max_weight / e.2).collect::<Vec<_>>();
+    let epochs_per_negative = epochs_per_sample
+        .iter()
+        .map(|e| e / UMAP_NEGATIVE_RATE)
+        .collect::<Vec<_>>();
+    let mut next_sample = epochs_per_sample.clone();
+    let mut next_negative = epochs_per_negative.clone();
+    let mut rng = XorShift(UMAP_SEED);
+    let n = coords.len();
+    for epoch in 0..UMAP_EPOCHS {
+        let now = epoch as f64;
+        let alpha = UMAP_LEARNING_RATE * (1.0 - now / UMAP_EPOCHS as f64);
+        for (e, &&(i, j, _)) in edges.iter().enumerate() {
+            if next_sample[e] > now {
+                continue;
+            }
+            let grad = attraction(coords[i], coords[j]);
+            for d in 0..2 {
+                coords[i][d] += grad[d] * alpha;
+                coords[j][d] -= grad[d] * alpha;
+            }
+            next_sample[e] += epochs_per_sample[e];
+
+            let negatives = ((now - next_negative[e]) / epochs_per_negative[e]).max(0.0) as usize;
+            for _ in 0..negatives {
+                let k = rng.below(n);
+                if k == i {
+                    continue;
+                }
+                let grad = repulsion(coords[i], coords[k]);
+                for d in 0..2 {
+                    coords[i][d] += grad[d] * alpha;
+                }
+            }
+            next_negative[e] += negatives as f64 * epochs_per_negative[e];
+        }
+    }
+}
+
+/// UMAP layout of `rows`, starting from their PCA coordinates.
+fn umap_layout(rows: &[Vec<f64>], mut coords: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    let extent = coords
+        .iter()
+        .flat_map(|c| c.iter().map(|v| v.abs()))
+        .fold(0.0, f64::max);
+    if extent > 0.0 {
+        let scale = UMAP_INIT_EXTENT / extent;
+        coords
+            .iter_mut()
+            .for_each(|c| c.iter_mut().for_each(|v| *v *= scale));
+    }
+    let graph = fuzzy_graph(&nearest_neighbors(rows, UMAP_NEIGHBORS));
+    optimize_layout(&mut coords, &graph);
+    coords
+}
+
+/// Projects the vectors to two dimensions with `method`. Vectors whose length differs from the
+/// first one are dropped and counted as skipped.
+pub fn project_2d(
+    vectors: Vec<(String, Vec<f64>)>,
+    mut skipped: usize,
+    method: ProjectionMethod,
+) -> AppResult<EmbeddingProjection> {
+    let dims = vectors
+        .first()
+        .map(|(_, v)| v.len())
+        .ok_or_else(|| AppError::Invalid("no float vectors found for this field".into()))?;
+    if dims < 2 {
+        return Err(AppError::Invalid("field holds scalars, not vectors".into()));
+    }
+    let (ids, mut rows): (Vec<String>, Vec<Vec<f64>>) = vectors
+        .into_iter()
+        .filter(|(_, v)| {
+            let keep = v.len() == dims && v.iter().all(|x| x.is_finite());
+            if !keep {
+                skipped += 1;
+            }
+            keep
+        })
+        .unzip();
+    if rows.is_empty() {
+        return Err(AppError::Invalid(
+            "no finite float vectors found for this field".into(),
+        ));
+    }
+
+    let mut mean = vec![0.0; dims];
+    for row in &rows {
+        mean.iter_mut().zip(row).for_each(|(m, x)| *m += x);
+    }
+    mean.iter_mut().for_each(|m| *m /= rows.len() as f64);
+    for row in &mut rows {
+        row.iter_mut().zip(&mean).for_each(|(x, m)| *x -= m);
+    }
+    let total_variance = rows.iter().map(|r| dot(r, r)).sum::<f64>() / rows.len() as f64;
+
+    let (first, first_value) = principal_axis(&rows, dims, None);
+    let (second, second_value) = principal_axis(&rows, dims, Some(&first));
+    let share = |value: f64| {
+        if total_variance > 0.0 {
+            value / total_variance
+        } else {
+            0.0
+        }
+    };
+    let coords = rows
+        .iter()
+        .map(|row| [dot(row, &first), dot(row, &second)])
+        .collect::<Vec<_>>();
+    let (coords, explained_variance) = match method {
+        ProjectionMethod::Pca => (coords, Some([share(first_value), share(second_value)])),
+        ProjectionMethod::Umap => (umap_layout(&rows, coords), None),
+    };
+    let points = ids
+        .into_iter()
+        .zip(coords)
+        .map(|(id, [x, y])| EmbeddingPoint { id, x, y })
+        .collect();
+    Ok(EmbeddingProjection {
+        dims,
+        method,
+        explained_variance,
+        points,
+        skipped,
+    })
+}
@@ -0,0 +1,366 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::messages::{self, Message};
+
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+/// Array items kept per metadata value; the rest are skipped (tokenizer vocabularies run to
+/// hundreds of thousands of entries).
+const MAX_ARRAY_ITEMS: u64 = 16;
+/// Strings longer than this are replaced by their length.
+const MAX_STRING_BYTES: u64 = 64 * 1024;
+const MAX_KV_COUNT: u64 = 1_000_000;
+const MAX_TENSOR_COUNT: u64 = 1_000_000;
+const MAX_DIMS: u32 = 8;
+/// Arrays of arrays nested deeper than this are rejected rather than recursed into.
+const MAX_ARRAY_NESTING: u32 = 8;
+const DEFAULT_ALIGNMENT: u64 = 32;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GgufMetadataEntry {
+    key: String,
+    /// `uint32`, `string`, `array[string]`, ...
+    value_type: String,
+    /// Arrays are cut to their first `MAX_ARRAY_ITEMS` items.
+    value: Value,
+    /// Item count of an array value.
+    length: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GgufTensor {
+    name: String,
+    /// Innermost dimension first, as GGUF stores them.
+    dims: Vec<u64>,
+    /// ggml type name (`F16`, `Q4_K`, ...).
+    ggml_type: String,
+    /// Offset from the start of the tensor data section.
+    offset: u64,
+    /// `None` for types whose block layout is unknown.
+    byte_size: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GgufSummary {
+    path: String,
+    version: u32,
+    /// `general.architecture`, `general.name` and friends are in `metadata`; these two are
+    /// surfaced for convenience.
+    architecture: Option<String>,
+    name: Option<String>,
+    metadata: Vec<GgufMetadataEntry>,
+    tensors: Vec<GgufTensor>,
+    parameter_count: u64,
+    /// Where tensor data starts in the file.
+    data_offset: u64,
+    alignment: u64,
+}
+
+struct GgufReader {
+    reader: BufReader<File>,
+    pos: u64,
+    /// Version 1 used 32-bit lengths and counts.
+    wide: bool,
+}
+
+impl GgufReader {
+    fn bytes<const N: usize>(&mut self) -> AppResult<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.reader.read_exact(&mut buf)?;
+        self.pos += N as u64;
+        Ok(buf)
+    }
+
+    fn u32(&mut self) -> AppResult<u32> {
+        Ok(u32::from_le_bytes(self.bytes()?))
+    }
+
+    fn u64(&mut self) -> AppResult<u64> {
+        Ok(u64::from_le_bytes(self.bytes()?))
+    }
+
+    /// A length or count: `u64` since version 2, `u32` before.
+    fn size(&mut self) -> AppResult<u64> {
+        if self.wide {
+            self.u64()
+        } else {
+            self.u32().map(u64::from)
+        }
+    }
+
+    fn skip(&mut self, len: u64) -> AppResult<()> {
+        let delta = i64::try_from(len)
+            .map_err(|_| AppError::Invalid(messages::text(Message::GgufLengthOverflow, &[])))?;
+        self.reader.seek_relative(delta)?;
+        self.pos += len;
+        Ok(())
+    }
+
+    /// A string, or `None` (skipped) when longer than `MAX_STRING_BYTES`.
+    fn string(&mut self) -> AppResult<(Option<String>, u64)> {
+        let len = self.size()?;
+        if len > MAX_STRING_BYTES {
+            self.skip(len)?;
+            return Ok((None, len));
+        }
+        let mut buf = vec![0u8; len as usize];
+        self.reader.read_exact(&mut buf)?;
+        self.pos += len;
+        Ok((Some(String::from_utf8_lossy(&buf).into_owned()), len))
+    }
+
+    fn key(&mut self) -> AppResult<String> {
+        let (key, len) = self.string()?;
+        key.ok_or_else(|| {
+            AppError::Invalid(messages::text(Message::GgufKeyTooLong, &[("len", &len)]))
+        })
+    }
+
+    /// Reads a value of GGUF type `ty`, `depth` arrays down.
+    fn value(&mut self, ty: u32, depth: u32) -> AppResult<Value> {
+        let value = match ty {
+            0 => Value::from(self.bytes::<1>()?[0]),
+            1 => Value::from(i8::from_le_bytes(self.bytes()?)),
+            2 => Value::from(u16::from_le_bytes(self.bytes()?)),
+            3 => Value::from(i16::from_le_bytes(self.bytes()?)),
+            4 => Value::from(self.u32()?),
+            5 => Value::from(i32::from_le_bytes(self.bytes()?)),
+            6 => Value::from(f32::from_le_bytes(self.bytes()?)),
+            7 => Value::from(self.bytes::<1>()?[0] != 0),
+            8 => match self.string()? {
+                (Some(s), _) => Value::from(s),
+                (None, len) => Value::from(format!("<{len} bytes>")),
+            },
+            9 => self.array(depth + 1)?.0,
+            10 => Value::from(self.u64()?),
+            11 => Value::from(i64::from_le_bytes(self.bytes()?)),
+            12 => Value::from(f64::from_le_bytes(self.bytes()?)),
+            other => {
+                return Err(AppError::Invalid(messages::text(
+                    Message::GgufUnknownValueType,
+                    &[("type", &other), ("offset", &self.pos)],
+                )))
+            }
+        };
+        Ok(value)
+    }
+
+    /// An array value cut to its first `MAX_ARRAY_ITEMS` items, with its item type and length.
+    fn array(&mut self, depth: u32) -> AppResult<(Value, u32, u64)> {
+        if depth > MAX_ARRAY_NESTING {
+            return Err(AppError::Invalid(messages::text(
+                Message::GgufNestedTooDeep,
+                &[("max", &MAX_ARRAY_NESTING), ("offset", &self.pos)],
+            )));
+        }
+        let item_ty = self.u32()?;
+        let len = self.size()?;
+        let mut items = Vec::new();
+        for idx in 0..len {
+            if idx < MAX_ARRAY_ITEMS {
+                items.push(self.value(item_ty, depth)?);
+            } else if let Some(width) = fixed_width(item_ty) {
+                self.skip(width.saturating_mul(len - idx))?;
+                break;
+            } else {
+                self.value(item_ty, depth)?;
+            }
+        }
+        Ok((Value::Array(items), item_ty, len))
+    }
+}
+
+/// Byte width of a fixed-size GGUF value type.
+fn fixed_width(ty: u32) -> Option<u64> {
+    match ty {
+        0 | 1 | 7 => Some(1),
+        2 | 3 => Some(2),
+        4 | 5 | 6 => Some(4),
+        10..=12 => Some(8),
+        _ => None,
+    }
+}
+
+fn value_type_name(ty: u32) -> &'static str {
+    match ty {
+        0 => "uint8",
+        1 => "int8",
+        2 => "uint16",
+        3 => "int16",
+        4 => "uint32",
+        5 => "int32",
+        6 => "float32",
+        7 => "bool",
+        8 => "string",
+        9 => "array",
+        10 => "uint64",
+        11 => "int64",
+        12 => "float64",
+        _ => "unknown",
+    }
+}
+
+/// ggml type name and block layout `(elements per block, bytes per block)`.
+fn ggml_type(code: u32) -> (String, Option<(u64, u64)>) {
+    let (name, layout) = match code {
+        0 => ("F32", (1, 4)),
+        1 => ("F16", (1, 2)),
+        2 => ("Q4_0", (32, 18)),
+        3 => ("Q4_1", (32, 20)),
+        6 => ("Q5_0", (32, 22)),
+        7 => ("Q5_1", (32, 24)),
+        8 => ("Q8_0", (32, 34)),
+        9 => ("Q8_1", (32, 36)),
+        10 => ("Q2_K", (256, 84)),
+        11 => ("Q3_K", (256, 110)),
+        12 => ("Q4_K", (256, 144)),
+        13 => ("Q5_K", (256, 176)),
+        14 => ("Q6_K", (256, 210)),
+        15 => ("Q8_K", (256, 292)),
+        16 => ("IQ2_XXS", (256, 66)),
+        17 => ("IQ2_XS", (256, 74)),
+        18 => ("IQ3_XXS", (256, 98)),
+        19 => ("IQ1_S", (256, 50)),
+        20 => ("IQ4_NL", (32, 18)),
+        21 => ("IQ3_S", (256, 110)),
+        22 => ("IQ2_S", (256, 82)),
+        23 => ("IQ4_XS", (256, 136)),
+        24 => ("I8", (1, 1)),
+        25 => ("I16", (1, 2)),
+        26 => ("I32", (1, 4)),
+        27 => ("I64", (1, 8)),
+        28 => ("F64", (1, 8)),
+        29 => ("IQ1_M", (256, 56)),
+        30 => ("BF16", (1, 2)),
+        34 => ("TQ1_0", (256, 54)),
+        35 => ("TQ2_0", (256, 66)),
+        other => return (format!("type {other}"), None),
+    };
+    (name.to_string(), Some(layout))
+}
+
+fn inspect(path: &Path) -> AppResult<GgufSummary> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != GGUF_MAGIC {
+        return Err(AppError::Invalid(messages::text(Message::NotGguf, &[])));
+    }
+    let mut r = GgufReader {
+        reader,
+        pos: 4,
+        wide: true,
+    };
+    let version = r.u32()?;
+    if version == 0 || version > 0xFFFF {
+        return Err(AppError::Invalid(messages::text(
+            Message::GgufUnsupportedVersion,
+            &[("version", &version)],
+        )));
+    }
+    r.wide = version >= 2;
+    let tensor_count = r.size()?;
+    let kv_count = r.size()?;
+    if tensor_count > MAX_TENSOR_COUNT || kv_count > MAX_KV_COUNT {
+        return Err(AppError::Invalid(messages::text(
+            Message::GgufImplausibleHeader,
+            &[("tensors", &tensor_count), ("entries", &kv_count)],
+        )));
+    }
+
+    // Grown as entries are read: the counts come from the file and are not trusted up front.
+    let mut metadata = Vec::new();
+    for _ in 0..kv_count {
+        let key = r.key()?;
+        let ty = r.u32()?;
+        let (value_type, value, length) = if ty == 9 {
+            let (value, item_ty, len) = r.array(1)?;
+            (
+                format!("array[{}]", value_type_name(item_ty)),
+                value,
+                Some(len),
+            )
+        } else {
+            (value_type_name(ty).to_string(), r.value(ty, 0)?, None)
+        };
+        metadata.push(GgufMetadataEntry {
+            key,
+            value_type,
+            value,
+            length,
+        });
+    }
+
+    let mut tensors = Vec::new();
+    let mut parameter_count = 0u64;
+    for _ in 0..tensor_count {
+        let name = r.key()?;
+        let n_dims = r.u32()?;
+        if n_dims > MAX_DIMS {
+            return Err(AppError::Invalid(messages::text(
+                Message::GgufTooManyDims,
+                &[("name", &name), ("dims", &n_dims)],
+            )));
+        }
+        let dims = (0..n_dims)
+            .map(|_| r.size())
+            .collect::<AppResult<Vec<_>>>()?;
+        let (ggml_type, layout) = ggml_type(r.u32()?);
+        let offset = r.u64()?;
+        let numel = dims.iter().try_fold(1u64, |acc, d| acc.checked_mul(*d));
+        parameter_count = parameter_count.saturating_add(numel.unwrap_or(0));
+        tensors.push(GgufTensor {
+            name,
+            dims,
+            ggml_type,
+            offset,
+            byte_size: numel
+                .zip(layout)
+                .and_then(|(numel, (block, bytes))| numel.div_ceil(block).checked_mul(bytes)),
+        });
+    }
+
+    let string_of = |key: &str| {
+        metadata
+            .iter()
+            .find(|e| e.key == key)
+            .and_then(|e| e.value.as_str())
+            .map(str::to_string)
+    };
+    let alignment = metadata
+        .iter()
+        .find(|e| e.key == "general.alignment")
+        .and_then(|e| e.value.as_u64())
+        .filter(|a| *a > 0)
+        .unwrap_or(DEFAULT_ALIGNMENT);
+    Ok(GgufSummary {
+        path: path.display().to_string(),
+        version,
+        architecture: string_of("general.architecture"),
+        name: string_of("general.name"),
+        data_offset: r.pos.div_ceil(alignment) * alignment,
+        alignment,
+        metadata,
+        tensors,
+        parameter_count,
+    })
+}
+
+/// Reads a `.gguf` file's header: metadata key/values and the tensor table (names, dims,
+/// quantization types), without touching tensor data.
+#[tauri::command]
+pub async fn inspect_gguf_file(path: String) -> AppResult<GgufSummary> {
+    spawn_blocking(move || inspect(Path::new(path.trim())))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use miniz_oxide::inflate::stream::{inflate, InflateState};
+use miniz_oxide::{DataFormat, MZError, MZFlush, MZStatus};
+
+use crate::app_error::AppResult;
+use crate::scan_cache::SourceFingerprint;
+
+/// Shards whose index is kept; the oldest registration is dropped beyond this.
+const MAX_INDEXED_SHARDS: usize = 16;
+/// Decompressed bytes between window checkpoints inside one gzip member. Each checkpoint holds
+/// the inflate state with its 32 KiB window, about 48 KiB in all.
+const CHECKPOINT_SPACING: u64 = 16 * 1024 * 1024;
+
+/// Everything needed to carry on inflating a member from the middle: the decompressor with its
+/// bit buffer and 32 KiB window, plus the running checksum and length for the trailer check.
+#[derive(Clone)]
+struct InflateResume {
+    state: Box<InflateState>,
+    crc: crc32fast::Hasher,
+    member_len: u32,
+}
+
+/// A place decompression can restart from: the start of a gzip member (`resume` is `None`) or
+/// a window checkpoint inside one.
+#[derive(Clone)]
+struct Checkpoint {
+    compressed: u64,
+    decompressed: u64,
+    resume: Option<Arc<InflateResume>>,
+}
+
+/// Restart points of a `.tar.gz` shard and the decompressed offset of each member's first header
+/// block, filled in by the first scan.
+///
+/// Like zlib's `zran`, the scan saves the inflate state every [`CHECKPOINT_SPACING`] decompressed
+/// bytes, so a plain `tar | gzip` shard can be entered mid-stream; gzip member boundaries
+/// (bgzip, concatenated gzip files) are restart points as well.
+pub struct GzipIndex {
+    checkpoints: Vec<Checkpoint>,
+    headers: HashMap<String, u64>,
+}
+
+impl GzipIndex {
+    pub fn record_header(&mut self, member_path: String, offset: u64) {
+        self.headers.insert(member_path, offset);
+    }
+}
+
+type SharedIndex = Arc<Mutex<GzipIndex>>;
+
+struct Registered {
+    fingerprint: SourceFingerprint,
+    index: SharedIndex,
+    order: u64,
+}
+
+static INDEXES: Mutex<BTreeMap<PathBuf, Registered>> = Mutex::new(BTreeMap::new());
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Consumes a gzip member header and returns its length in bytes.
+fn read_member_header(input: &mut impl BufRead) -> io::Result<u64> {
+    let mut fixed = [0u8; 10];
+    input.read_exact(&mut fixed)?;
+    if fixed[..3] != [0x1f, 0x8b, 8] {
+        return Err(invalid("not a gzip member"));
+    }
+    let flags = fixed[3];
+    let mut len = fixed.len() as u64;
+    if flags & 0x04 != 0 {
+        let mut xlen = [0u8; 2];
+        input.read_exact(&mut xlen)?;
+        let xlen = u16::from_le_bytes(xlen) as u64;
+        if io::copy(&mut input.take(xlen), &mut io::sink())? != xlen {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        len += 2 + xlen;
+    }
+    for flag in [0x08, 0x10] {
+        if flags & flag != 0 {
+            let mut field = Vec::new();
+            input.read_until(0, &mut field)?;
+            if field.last() != Some(&0) {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            len += field.len() as u64;
+        }
+    }
+    if flags & 0x02 != 0 {
+        input.read_exact(&mut [0u8; 2])?;
+        len += 2;
+    }
+    Ok(len)
+}
+
+/// Multi-member gzip decoder that knows its compressed offset, so it can be started from a
+/// checkpoint and, while scanning, record new ones into `index`.
+struct GzipStream {
+    input: BufReader<File>,
+    compressed: u64,
+    decompressed: u64,
+    /// `None` between members, where the next read parses a header.
+    inflate: Option<InflateResume>,
+    index: Option<SharedIndex>,
+    next_checkpoint: u64,
+}
+
+impl GzipStream {
+    fn checkpoint(&mut self, resume: Option<Arc<InflateResume>>) {
+        let Some(index) = self.index.as_ref() else {
+            return;
+        };
+        if let Ok(mut index) = index.lock() {
+            index.checkpoints.push(Checkpoint {
+                compressed: self.compressed,
+                decompressed: self.decompressed,
+                resume,
+            });
+        }
+        self.next_checkpoint = self.decompressed + CHECKPOINT_SPACING;
+    }
+
+    fn finish_member(&mut self, resume: InflateResume) -> io::Result<()> {
+        let mut trailer = [0u8; 8];
+        self.input.read_exact(&mut trailer)?;
+        self.compressed += trailer.len() as u64;
+        let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+        let len = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+        if crc != resume.crc.finalize() || len != resume.member_len {
+            return Err(invalid("gzip member checksum mismatch"));
+        }
+        Ok(())
+    }
+}
+
+impl Read for GzipStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let Some(mut resume) = self.inflate.take() else {
+                if self.input.fill_buf()?.is_empty() {
+                    return Ok(0);
+                }
+                if self.compressed > 0 {
+                    self.checkpoint(None);
+                }
+                self.compressed += read_member_header(&mut self.input)?;
+                self.inflate = Some(InflateResume {
+                    state: InflateState::new_boxed(DataFormat::Raw),
+                    crc: crc32fast::Hasher::new(),
+                    member_len: 0,
+                });
+                continue;
+            };
+            let input = self.input.fill_buf()?;
+            let at_eof = input.is_empty();
+            let result = inflate(&mut resume.state, input, buf, MZFlush::None);
+            self.input.consume(result.bytes_consumed);
+            self.compressed += result.bytes_consumed as u64;
+            let written = result.bytes_written;
+            resume.crc.update(&buf[..written]);
+            resume.member_len = resume.member_len.wrapping_add(written as u32);
+            self.decompressed += written as u64;
+            match result.status {
+                Ok(MZStatus::StreamEnd) => self.finish_member(resume)?,
+                Ok(_) => {
+                    if self.index.is_some() && self.decompressed >= self.next_checkpoint {
+                        self.checkpoint(Some(Arc::new(resume.clone())));
+                    }
+                    self.inflate = Some(resume);
+                }
+                Err(MZError::Buf) if at_eof => {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                Err(_) => return Err(invalid("corrupt deflate stream")),
+            }
+            if written > 0 {
+                return Ok(written);
+            }
+        }
+    }
+}
+
+/// Opens `shard_path` for a full scan, registering a fresh index that the returned reader fills
+/// with restart points. The caller records member headers into the same index.
+pub fn indexing_reader(shard_path: &Path) -> AppResult<(Box<dyn Read + Send>, SharedIndex)> {
+    let fingerprint = SourceFingerprint::of(shard_path)?;
+    let file = File::open(shard_path)?;
+    let index = Arc::new(Mutex::new(GzipIndex {
+        checkpoints: vec![Checkpoint {
+            compressed: 0,
+            decompressed: 0,
+            resume: None,
+        }],
+        headers: HashMap::new(),
+    }));
+    if let Ok(mut indexes) = INDEXES.lock() {
+        let order = indexes.values().map(|r| r.order + 1).max().unwrap_or(0);
+        indexes.insert(
+            shard_path.to_path_buf(),
+            Registered {
+                fingerprint,
+                index: index.clone(),
+                order,
+            },
+        );
+        while indexes.len() > MAX_INDEXED_SHARDS {
+            let Some(oldest) = indexes
+                .iter()
+                .min_by_key(|(_, r)| r.order)
+                .map(|(path, _)| path.clone())
+            else {
+                break;
+            };
+            indexes.remove(&oldest);
+        }
+    }
+    let reader = GzipStream {
+        input: BufReader::new(file),
+        compressed: 0,
+        decompressed: 0,
+        inflate: None,
+        index: Some(index.clone()),
+        next_checkpoint: CHECKPOINT_SPACING,
+    };
+    Ok((Box::new(reader), index))
+}
+
+/// A decompressed reader positioned at the earliest header of `members`, started from the nearest
+/// restart point. `None` when the shard has no usable index, a member was not scanned yet, or the
+/// nearest restart point is the start of the file anyway.
+pub fn open_near(shard_path: &Path, members: &[&str]) -> AppResult<Option<Box<dyn Read + Send>>> {
+    let index = {
+        let Ok(mut indexes) = INDEXES.lock() else {
+            return Ok(None);
+        };
+        let Some(registered) = indexes.get(shard_path) else {
+            return Ok(None);
+        };
+        if SourceFingerprint::of(shard_path).ok() != Some(registered.fingerprint) {
+            indexes.remove(shard_path);
+            return Ok(None);
+        }
+        registered.index.clone()
+    };
+    let (checkpoint, target) = {
+        // A scan still recording into the index holds it briefly; read from the start instead.
+        let Ok(index) = index.try_lock() else {
+            return Ok(None);
+        };
+        let mut target = u64::MAX;
+        for member in members {
+            match index.headers.get(*member) {
+                Some(offset) => target = target.min(*offset),
+                None => return Ok(None),
+            }
+        }
+        let Some(checkpoint) = index
+            .checkpoints
+            .iter()
+            .take_while(|c| c.decompressed <= target)
+            .last()
+            .cloned()
+        else {
+            return Ok(None);
+        };
+        (checkpoint, target)
+    };
+    if checkpoint.compressed == 0 {
+        return Ok(None);
+    }
+
+    let mut file = File::open(shard_path)?;
+    file.seek(SeekFrom::Start(checkpoint.compressed))?;
+    let mut reader = GzipStream {
+        input: BufReader::new(file),
+        compressed: checkpoint.compressed,
+        decompressed: checkpoint.decompressed,
+        inflate: checkpoint.resume.as_deref().cloned(),
+        index: None,
+        next_checkpoint: u64::MAX,
+    };
+    let skip = target - checkpoint.decompressed;
+    let skipped = io::copy(&mut (&mut reader).take(skip), &mut io::sink())?;
+    if skipped != skip {
+        return Ok(None);
+    }
+    Ok(Some(Box::new(reader)))
+}
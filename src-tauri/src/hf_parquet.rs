@@ -0,0 +1,387 @@
+use ::parquet::basic::{ConvertedType, LogicalType};
+use ::parquet::errors::ParquetError;
+use ::parquet::file::metadata::ParquetMetaData;
+use ::parquet::file::metadata::ParquetMetaDataReader;
+use ::parquet::file::reader::{ChunkReader, FileReader, Length, SerializedFileReader};
+use ::parquet::record::{Field, Row};
+use bytes::{Buf, Bytes};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::http_cache;
+use crate::parquet::{looks_like_parquet, parse_footer_tail};
+
+/// Bytes fetched from the end of each file in the first request; enough for most footers.
+const TAIL_BYTES: u64 = 64 * 1024;
+/// Splits stored in more files than this are not read directly.
+const MAX_FILES_PER_SPLIT: usize = 256;
+/// Row groups are downloaded whole, so larger ones are refused.
+const MAX_ROW_GROUP_BYTES: u64 = 256 * 1024 * 1024;
+/// Binary cells (image/audio bytes) longer than this are replaced by their length.
+const MAX_INLINE_BINARY: usize = 256;
+
+/// Parquet files of a repo by config and split, inferred from their paths: the split from a
+/// `train-00000-of-00002.parquet` style name or else the parent directory, the config from the
+/// nearest directory other than `data` (`default` if none).
+pub fn group_files(
+    paths: impl IntoIterator<Item = String>,
+) -> BTreeMap<String, BTreeMap<String, Vec<String>>> {
+    let mut groups = BTreeMap::<String, BTreeMap<String, Vec<String>>>::new();
+    for path in paths.into_iter().filter(|p| looks_like_parquet(p)) {
+        let mut segments = path.split('/').collect::<Vec<_>>();
+        let file = segments.pop().unwrap_or_default();
+        let named_split = file
+            .split(['-', '_', '.'])
+            .next()
+            .filter(|s| !s.is_empty() && !s.chars().all(|c| c.is_ascii_digit()));
+        let split = match named_split {
+            Some(split) => split,
+            None => segments.pop().unwrap_or("train"),
+        };
+        let config = segments
+            .iter()
+            .rev()
+            .find(|s| **s != "data")
+            .copied()
+            .unwrap_or("default");
+        groups
+            .entry(config.to_string())
+            .or_default()
+            .entry(split.to_string())
+            .or_default()
+            .push(path.clone());
+    }
+    for files in groups.values_mut().flat_map(|splits| splits.values_mut()) {
+        files.sort();
+    }
+    groups
+}
+
+/// Rows `offset..offset + length` of a split stored as `files`, with the split's total row count
+/// and its columns as `(name, datasets feature type)`.
+pub struct ParquetPage {
+    pub features: Vec<(String, Value)>,
+    pub rows: Vec<Value>,
+    pub num_rows_total: usize,
+}
+
+/// Reads a page of rows by fetching each file's footer and then only the row groups the page
+/// overlaps, with ranged requests.
+pub async fn read_page(
+    http: &reqwest::Client,
+    files: Vec<Url>,
+    offset: usize,
+    length: usize,
+    token: Option<&str>,
+) -> AppResult<ParquetPage> {
+    if files.len() > MAX_FILES_PER_SPLIT {
+        return Err(AppError::Invalid(format!(
+            "Split is stored in {} Parquet files; at most {MAX_FILES_PER_SPLIT} can be read directly.",
+            files.len()
+        )));
+    }
+    let mut remotes = Vec::with_capacity(files.len());
+    for url in files {
+        remotes.push(open_remote(http, url, token).await?);
+    }
+    let features = remotes
+        .first()
+        .map(|remote| column_features(&remote.meta))
+        .unwrap_or_default();
+    let num_rows_total = remotes
+        .iter()
+        .map(|remote| remote.meta.file_metadata().num_rows().max(0) as usize)
+        .sum();
+
+    let mut rows = Vec::new();
+    let mut first_row = 0usize;
+    for remote in &remotes {
+        if rows.len() >= length {
+            break;
+        }
+        let file_rows = remote.meta.file_metadata().num_rows().max(0) as usize;
+        if first_row + file_rows > offset {
+            let skip = offset.saturating_sub(first_row);
+            let take = length - rows.len();
+            rows.extend(read_rows(http, remote, skip, take, token).await?);
+        }
+        first_row += file_rows;
+    }
+    Ok(ParquetPage {
+        features,
+        rows,
+        num_rows_total,
+    })
+}
+
+/// A remote Parquet file's size, decoded footer and the tail bytes holding it.
+struct RemoteParquet {
+    url: Url,
+    size: u64,
+    tail_start: u64,
+    tail: Bytes,
+    meta: ParquetMetaData,
+}
+
+async fn open_remote(
+    http: &reqwest::Client,
+    url: Url,
+    token: Option<&str>,
+) -> AppResult<RemoteParquet> {
+    let (mut tail_start, size, mut tail) =
+        fetch_range(http, &url, &format!("bytes=-{TAIL_BYTES}"), token).await?;
+    let footer_len = parse_footer_tail(&tail)?;
+    if footer_len + 8 > size {
+        return Err(AppError::Invalid(format!(
+            "Parquet footer length is out of bounds in {url}."
+        )));
+    }
+    if footer_len + 8 > tail.len() as u64 {
+        (tail_start, _, tail) =
+            fetch_range(http, &url, &format!("bytes=-{}", footer_len + 8), token).await?;
+        if footer_len + 8 > tail.len() as u64 {
+            return Err(AppError::Remote(format!(
+                "Short read of the Parquet footer from {url}."
+            )));
+        }
+    }
+    let footer_end = tail.len() - 8;
+    let footer = &tail[footer_end - footer_len as usize..footer_end];
+    let meta = ParquetMetaDataReader::decode_metadata(footer)
+        .map_err(|e| AppError::Invalid(format!("Parquet footer decode failed for {url}: {e}")))?;
+    Ok(RemoteParquet {
+        url,
+        size,
+        tail_start,
+        tail: Bytes::from(tail),
+        meta,
+    })
+}
+
+/// Sends a ranged GET and returns where the body starts in the file, the file size and the body.
+/// Servers that ignore `Range` send the whole file, which is handled the same way.
+async fn fetch_range(
+    http: &reqwest::Client,
+    url: &Url,
+    range: &str,
+    token: Option<&str>,
+) -> AppResult<(u64, u64, Vec<u8>)> {
+    let res = http_cache::get(http, url, Some(range), token).await?;
+    match res.status {
+        StatusCode::PARTIAL_CONTENT => {
+            // `bytes <start>-<end>/<size>`
+            let parsed = res.content_range.as_deref().and_then(|v| {
+                let (span, size) = v.strip_prefix("bytes ")?.split_once('/')?;
+                let start = span.split_once('-')?.0.parse::<u64>().ok()?;
+                Some((start, size.parse::<u64>().ok()?))
+            });
+            let (start, size) = parsed.ok_or_else(|| {
+                AppError::Remote(format!("Missing or invalid Content-Range from {url}."))
+            })?;
+            Ok((start, size, res.body))
+        }
+        status if status.is_success() => Ok((0, res.body.len() as u64, res.body)),
+        status => Err(AppError::Remote(format!("HTTP {status} from {url}"))),
+    }
+}
+
+async fn read_rows(
+    http: &reqwest::Client,
+    remote: &RemoteParquet,
+    skip: usize,
+    take: usize,
+    token: Option<&str>,
+) -> AppResult<Vec<Value>> {
+    let mut rows = Vec::new();
+    let mut first_row = 0usize;
+    for (idx, group) in remote.meta.row_groups().iter().enumerate() {
+        if rows.len() >= take {
+            break;
+        }
+        let group_rows = group.num_rows().max(0) as usize;
+        let group_end = first_row + group_rows;
+        if group_end <= skip {
+            first_row = group_end;
+            continue;
+        }
+        let (start, end) = group
+            .columns()
+            .iter()
+            .map(|column| column.byte_range())
+            .fold((u64::MAX, 0), |(lo, hi), (offset, len)| {
+                (lo.min(offset), hi.max(offset + len))
+            });
+        if end <= start {
+            first_row = group_end;
+            continue;
+        }
+        if end - start > MAX_ROW_GROUP_BYTES {
+            return Err(AppError::Invalid(format!(
+                "Row group {idx} of {} is too large to preview ({} bytes, max {MAX_ROW_GROUP_BYTES}).",
+                remote.url,
+                end - start
+            )));
+        }
+        let (body_start, _, body) = fetch_range(
+            http,
+            &remote.url,
+            &format!("bytes={start}-{}", end - 1),
+            token,
+        )
+        .await?;
+        let file = SparseFile {
+            len: remote.size,
+            ranges: vec![
+                (body_start, Bytes::from(body)),
+                (remote.tail_start, remote.tail.clone()),
+            ],
+        };
+        let group_skip = skip.saturating_sub(first_row);
+        let group_take = take - rows.len();
+        let decoded = tauri::async_runtime::spawn_blocking(move || {
+            decode_rows(file, idx, group_skip, group_take)
+        })
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))??;
+        rows.extend(decoded);
+        first_row = group_end;
+    }
+    Ok(rows)
+}
+
+fn decode_rows(
+    file: SparseFile,
+    row_group: usize,
+    skip: usize,
+    take: usize,
+) -> AppResult<Vec<Value>> {
+    let reader = SerializedFileReader::new(file).map_err(read_error)?;
+    let group = reader.get_row_group(row_group).map_err(read_error)?;
+    let mut rows = Vec::with_capacity(take);
+    for row in group
+        .get_row_iter(None)
+        .map_err(read_error)?
+        .skip(skip)
+        .take(take)
+    {
+        rows.push(row_json(&row.map_err(read_error)?));
+    }
+    Ok(rows)
+}
+
+fn read_error(err: ParquetError) -> AppError {
+    AppError::Invalid(format!("Parquet read failed: {err}"))
+}
+
+pub fn row_json(row: &Row) -> Value {
+    Value::Object(
+        row.get_column_iter()
+            .map(|(name, field)| (name.clone(), field_json(field)))
+            .collect(),
+    )
+}
+
+fn field_json(field: &Field) -> Value {
+    match field {
+        Field::Bytes(bytes) if bytes.len() > MAX_INLINE_BINARY => {
+            Value::String(format!("<{} bytes>", bytes.len()))
+        }
+        Field::Group(row) => row_json(row),
+        Field::ListInternal(list) => Value::Array(list.elements().iter().map(field_json).collect()),
+        Field::MapInternal(map) => Value::Array(
+            map.entries()
+                .iter()
+                .map(|(key, value)| json!([field_json(key), field_json(value)]))
+                .collect(),
+        ),
+        other => other.to_json_value(),
+    }
+}
+
+/// Top-level columns with the `datasets` feature type stored in the `huggingface` key-value
+/// metadata, or a type derived from the Parquet schema when that is missing.
+fn column_features(meta: &ParquetMetaData) -> Vec<(String, Value)> {
+    let declared = meta
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|kv| kv.iter().find(|kv| kv.key == "huggingface"))
+        .and_then(|kv| kv.value.as_deref())
+        .and_then(|raw| serde_json::from_str::<Value>(raw).ok())
+        .and_then(|v| v.get("info")?.get("features")?.as_object().cloned())
+        .unwrap_or_default();
+    meta.file_metadata()
+        .schema_descr()
+        .root_schema()
+        .get_fields()
+        .iter()
+        .map(|field| {
+            let name = field.name().to_string();
+            if let Some(ty) = declared.get(&name) {
+                return (name, ty.clone());
+            }
+            let info = field.get_basic_info();
+            let ty = if field.is_primitive() {
+                let is_string = info.converted_type() == ConvertedType::UTF8
+                    || matches!(info.logical_type(), Some(LogicalType::String));
+                let dtype = if is_string {
+                    "string".to_string()
+                } else {
+                    field.get_physical_type().to_string().to_lowercase()
+                };
+                json!({ "dtype": dtype, "_type": "Value" })
+            } else if info.converted_type() == ConvertedType::LIST
+                || matches!(info.logical_type(), Some(LogicalType::List))
+            {
+                json!({ "_type": "Sequence" })
+            } else {
+                json!({ "_type": "Struct" })
+            };
+            (name, ty)
+        })
+        .collect()
+}
+
+/// A remote file of which only some byte ranges (a row group and the footer) were downloaded.
+struct SparseFile {
+    len: u64,
+    ranges: Vec<(u64, Bytes)>,
+}
+
+impl SparseFile {
+    fn slice(&self, start: u64, length: Option<usize>) -> ::parquet::errors::Result<Bytes> {
+        for (offset, bytes) in &self.ranges {
+            if start < *offset || start > offset + bytes.len() as u64 {
+                continue;
+            }
+            let from = (start - offset) as usize;
+            let to = length.map_or(bytes.len(), |len| from + len);
+            if to <= bytes.len() {
+                return Ok(bytes.slice(from..to));
+            }
+        }
+        Err(ParquetError::General(format!(
+            "bytes at offset {start} were not downloaded"
+        )))
+    }
+}
+
+impl Length for SparseFile {
+    fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+impl ChunkReader for SparseFile {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64) -> ::parquet::errors::Result<Self::T> {
+        Ok(self.slice(start, None)?.reader())
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> ::parquet::errors::Result<Bytes> {
+        self.slice(start, Some(length))
+    }
+}
@@ -0,0 +1,208 @@
+use reqwest::header::{
+    HeaderMap, AUTHORIZATION, CONTENT_RANGE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+    RANGE,
+};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::http_limit;
+use crate::offline;
+use crate::throttle;
+
+/// Bodies larger than this are never written to disk.
+const MAX_ENTRY_BYTES: usize = 32 * 1024 * 1024;
+/// Oldest entries are evicted once the cache grows past this size.
+const MAX_CACHE_BYTES: u64 = 512 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EntryMeta {
+    url: String,
+    range: Option<String>,
+    status: u16,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_range: Option<String>,
+}
+
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub content_range: Option<String>,
+    pub body: Vec<u8>,
+}
+
+fn cache_dir() -> PathBuf {
+    std::env::temp_dir()
+        .join("dataset-inspector")
+        .join("http-cache")
+}
+
+fn entry_key(url: &Url, range: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_str().as_bytes());
+    hasher.update([0u8]);
+    hasher.update(range.unwrap_or_default().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+fn load_entry(key: &str) -> Option<(EntryMeta, Vec<u8>)> {
+    let dir = cache_dir();
+    let meta = fs::read(dir.join(format!("{key}.json"))).ok()?;
+    let meta: EntryMeta = serde_json::from_slice(&meta).ok()?;
+    let body = fs::read(dir.join(format!("{key}.bin"))).ok()?;
+    Some((meta, body))
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)
+}
+
+fn store_entry(key: &str, meta: &EntryMeta, body: &[u8]) {
+    if body.len() > MAX_ENTRY_BYTES {
+        return;
+    }
+    let dir = cache_dir();
+    let Ok(json) = serde_json::to_vec(meta) else {
+        return;
+    };
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    // Body first: metadata without a body is treated as a miss, the reverse would serve stale bytes.
+    if write_atomic(&dir.join(format!("{key}.bin")), body).is_err() {
+        return;
+    }
+    let _ = write_atomic(&dir.join(format!("{key}.json")), &json);
+    prune(&dir);
+}
+
+fn prune(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut bodies = Vec::new();
+    let mut total = 0u64;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("bin") {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        total += meta.len();
+        bodies.push((meta.modified().ok(), meta.len(), path));
+    }
+    if total <= MAX_CACHE_BYTES {
+        return;
+    }
+    bodies.sort_by_key(|(modified, _, _)| *modified);
+    for (_, len, path) in bodies {
+        if total <= MAX_CACHE_BYTES / 4 * 3 {
+            break;
+        }
+        let _ = fs::remove_file(path.with_extension("json"));
+        let _ = fs::remove_file(&path);
+        total = total.saturating_sub(len);
+    }
+}
+
+fn header_string(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Sends a GET (optionally with a `Range` header) through the per-host limiter and the disk cache,
+/// retrying when the server throttles it.
+/// Cached bodies are revalidated with `If-None-Match`/`If-Modified-Since`, so a `304` replays them
+/// instead of downloading the same bytes again. Authenticated requests bypass the cache so private
+/// content is never persisted. In offline mode only cached bodies are returned.
+pub async fn get(
+    client: &reqwest::Client,
+    url: &Url,
+    range: Option<&str>,
+    bearer: Option<&str>,
+) -> AppResult<CachedResponse> {
+    let key = entry_key(url, range);
+    let cached = if bearer.is_none() {
+        load_entry(&key)
+    } else {
+        None
+    };
+    if offline::is_enabled() {
+        let (meta, body) = cached.ok_or_else(|| offline::not_cached(url))?;
+        return Ok(CachedResponse {
+            status: StatusCode::from_u16(meta.status).unwrap_or(StatusCode::OK),
+            content_range: meta.content_range,
+            body,
+        });
+    }
+
+    let permit = http_limit::acquire(url).await?;
+
+    let build = || {
+        let mut req = client.get(url.clone());
+        if let Some(range) = range {
+            req = req.header(RANGE, range);
+        }
+        if let Some(token) = bearer {
+            req = req.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        if let Some((meta, _)) = &cached {
+            if let Some(etag) = &meta.etag {
+                req = req.header(IF_NONE_MATCH, etag);
+            } else if let Some(last_modified) = &meta.last_modified {
+                req = req.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        req
+    };
+    let res = throttle::send(url, "request", build).await?;
+    drop(permit);
+    let status = res.status();
+    if status == StatusCode::NOT_MODIFIED {
+        if let Some((meta, body)) = cached {
+            return Ok(CachedResponse {
+                status: StatusCode::from_u16(meta.status).unwrap_or(StatusCode::OK),
+                content_range: meta.content_range,
+                body,
+            });
+        }
+    }
+
+    let etag = header_string(res.headers(), ETAG);
+    let last_modified = header_string(res.headers(), LAST_MODIFIED);
+    let content_range = header_string(res.headers(), CONTENT_RANGE);
+    let body = res
+        .bytes()
+        .await
+        .map_err(|e| AppError::Remote(format!("read response failed: {e}")))?
+        .to_vec();
+
+    if bearer.is_none() && status.is_success() && (etag.is_some() || last_modified.is_some()) {
+        let meta = EntryMeta {
+            url: url.to_string(),
+            range: range.map(str::to_string),
+            status: status.as_u16(),
+            etag,
+            last_modified,
+            content_range: content_range.clone(),
+        };
+        store_entry(&key, &meta, &body);
+    }
+
+    Ok(CachedResponse {
+        status,
+        content_range,
+        body,
+    })
+}
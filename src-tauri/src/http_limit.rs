@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::AppHandle;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::settings;
+
+const MAX_REQUESTS_PER_HOST_KEY: &str = "max_requests_per_host";
+const DEFAULT_MAX_REQUESTS_PER_HOST: usize = 4;
+const MAX_REQUESTS_PER_HOST_CAP: usize = 64;
+
+static MAX_PER_HOST: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_REQUESTS_PER_HOST);
+static HOSTS: OnceLock<Mutex<HashMap<String, Arc<Semaphore>>>> = OnceLock::new();
+
+/// Keeps one request slot for a host until dropped. Requests beyond the limit queue in FIFO order.
+///
+/// Callers drop it once the response headers arrive: the limit paces requests, and a body streamed
+/// for a whole TAR scan would otherwise hold the slot for minutes.
+pub struct HostPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+fn semaphore_for(url: &Url) -> Arc<Semaphore> {
+    let host = match (url.host_str(), url.port_or_known_default()) {
+        (Some(host), Some(port)) => format!("{host}:{port}"),
+        (Some(host), None) => host.to_string(),
+        (None, _) => String::new(),
+    };
+    let hosts = HOSTS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut hosts = hosts.lock().unwrap_or_else(|e| e.into_inner());
+    hosts
+        .entry(host)
+        .or_insert_with(|| Arc::new(Semaphore::new(MAX_PER_HOST.load(Ordering::Relaxed))))
+        .clone()
+}
+
+pub async fn acquire(url: &Url) -> AppResult<HostPermit> {
+    let permit = semaphore_for(url)
+        .acquire_owned()
+        .await
+        .map_err(|e| AppError::Task(format!("request limiter closed: {e}")))?;
+    Ok(HostPermit { _permit: permit })
+}
+
+/// Blocking variant for callers running on a `spawn_blocking` thread.
+pub fn acquire_blocking(url: &Url) -> AppResult<HostPermit> {
+    tauri::async_runtime::block_on(acquire(url))
+}
+
+fn apply_limit(limit: usize) {
+    MAX_PER_HOST.store(limit, Ordering::Relaxed);
+    // Drop existing semaphores; requests already holding permits finish on the old ones.
+    if let Some(hosts) = HOSTS.get() {
+        hosts.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// Loads the persisted per-host limit. Called once at startup.
+pub fn load_settings(app: &AppHandle) {
+    if let Ok(Some(limit)) = settings::read_setting::<usize>(app, MAX_REQUESTS_PER_HOST_KEY) {
+        apply_limit(limit.clamp(1, MAX_REQUESTS_PER_HOST_CAP));
+    }
+}
+
+#[tauri::command]
+pub async fn get_max_requests_per_host() -> AppResult<usize> {
+    Ok(MAX_PER_HOST.load(Ordering::Relaxed))
+}
+
+#[tauri::command]
+pub async fn set_max_requests_per_host(app: AppHandle, limit: usize) -> AppResult<()> {
+    if !(1..=MAX_REQUESTS_PER_HOST_CAP).contains(&limit) {
+        return Err(AppError::Invalid(format!(
+            "max requests per host must be between 1 and {MAX_REQUESTS_PER_HOST_CAP}"
+        )));
+    }
+    settings::write_setting(&app, MAX_REQUESTS_PER_HOST_KEY, &limit)?;
+    apply_limit(limit);
+    Ok(())
+}
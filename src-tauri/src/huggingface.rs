@@ -1,17 +1,28 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 use std::{fs, path::PathBuf};
-use tauri::State;
+use tauri::{AppHandle, State};
 use url::Url;
 
 use crate::app_error::{AppError, AppResult};
-use crate::ipc_types::OpenLeafResponse;
+use crate::dataset_docs::{self, DatasetDocsResponse};
+use crate::hf_parquet;
+use crate::http_cache;
+use crate::ipc_types::{OpenLeafResponse, SavedLeafResponse};
+use crate::magic;
+use crate::messages::{self, Message};
 use crate::open_with;
+use crate::save_leaf;
+use crate::temp_names::{self, sanitize};
 
 const DATASETS_SERVER_BASE: &str = "https://datasets-server.huggingface.co/";
+const HUB_BASE: &str = "https://huggingface.co/";
 const DEFAULT_ROWS: usize = 25;
 const MAX_ROWS: usize = 100;
 const MAX_INLINE_TEXT: usize = 10 * 1024 * 1024;
+/// Arrays in preview rows longer than this are cut to their first items; `hf_field_value` returns
+/// the whole value.
+const MAX_INLINE_ITEMS: usize = 16;
 
 #[derive(Clone)]
 pub struct HfClient {
@@ -61,6 +72,20 @@ struct RowEntry {
     row: serde_json::Value,
 }
 
+#[derive(Deserialize)]
+struct HubDatasetInfo {
+    #[serde(default)]
+    siblings: Vec<HubSibling>,
+    #[serde(rename = "cardData")]
+    card_data: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct HubSibling {
+    rfilename: String,
+    size: Option<u64>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HfConfigSummary {
@@ -84,11 +109,35 @@ pub struct HfDatasetPreview {
     split: String,
     configs: Vec<HfConfigSummary>,
     offset: usize,
+    /// Rows per page actually used, after clamping the requested length to `max_length`.
     length: usize,
+    /// Largest page the datasets server is asked for.
+    max_length: usize,
+    /// `datasets-server`, or `parquet` when the rows were read from the repo's Parquet files
+    /// because the datasets server could not serve the dataset.
+    source: &'static str,
     num_rows_total: usize,
     partial: bool,
     features: Vec<HfFeature>,
     rows: Vec<serde_json::Value>,
+    /// Arrays in `rows` that were cut to `MAX_INLINE_ITEMS` items.
+    truncated: Vec<HfTruncatedValue>,
+}
+
+/// A large list (Sequence, Array2D, ...) in a preview row, summarized instead of sent whole.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HfTruncatedValue {
+    /// Index into `rows`.
+    row: usize,
+    field: String,
+    /// JSON pointer from the field's value to the cut array; empty for the value itself.
+    pointer: String,
+    length: usize,
+    /// Length of each nesting level when the array is rectangular, e.g. `[128, 80]`.
+    shape: Option<Vec<usize>>,
+    /// `int`, `float`, `string`, `bool`, `object` or `mixed`, from the array's leaves.
+    dtype: String,
 }
 
 fn validate_repo_segment(segment: &str) -> bool {
@@ -139,7 +188,7 @@ fn extract_repo_id_from_url(url: &Url) -> Option<String> {
     None
 }
 
-fn extract_repo_id(input: &str) -> AppResult<String> {
+pub fn extract_repo_id(input: &str) -> AppResult<String> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         return Err(AppError::Invalid(
@@ -193,21 +242,11 @@ async fn get_json<T: DeserializeOwned>(
     url: Url,
     token: Option<&str>,
 ) -> AppResult<T> {
-    let mut req = client.get(url.clone());
-    if let Some(t) = token.map(|s| s.trim()).filter(|s| !s.is_empty()) {
-        req = req.header(reqwest::header::AUTHORIZATION, format!("Bearer {t}"));
-    }
-    let res = req
-        .send()
-        .await
-        .map_err(|e| AppError::Remote(format!("request failed: {e}")))?;
-    let status = res.status();
-    let text = res
-        .text()
-        .await
-        .map_err(|e| AppError::Remote(format!("read response failed: {e}")))?;
-
-    let value: serde_json::Value = serde_json::from_str(&text)
+    let token = token.map(|s| s.trim()).filter(|s| !s.is_empty());
+    let res = http_cache::get(client, &url, None, token).await?;
+    let status = res.status;
+
+    let value: serde_json::Value = serde_json::from_slice(&res.body)
         .map_err(|e| AppError::Remote(format!("invalid JSON from {url}: {e}")))?;
 
     if let Some(err) = value.get("error").and_then(|v| v.as_str()) {
@@ -220,19 +259,6 @@ async fn get_json<T: DeserializeOwned>(
     serde_json::from_value(value).map_err(|e| AppError::Remote(format!("parse failed: {e}")))
 }
 
-fn sanitize(value: &str) -> String {
-    value
-        .chars()
-        .map(|c| {
-            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
-                c
-            } else {
-                '_'
-            }
-        })
-        .collect()
-}
-
 fn allowed_asset_url(url: &Url) -> bool {
     if url.scheme() != "https" && url.scheme() != "http" {
         return false;
@@ -315,22 +341,13 @@ async fn download_bytes(
     if !allowed_asset_url(&url) {
         return Err(AppError::Invalid("Blocked asset URL host/scheme.".into()));
     }
-    let mut req = client.get(url.clone());
-    if let Some(t) = token.map(|s| s.trim()).filter(|s| !s.is_empty()) {
-        req = req.header(reqwest::header::AUTHORIZATION, format!("Bearer {t}"));
-    }
-    let res = req
-        .send()
-        .await
-        .map_err(|e| AppError::Remote(format!("asset request failed: {e}")))?;
-    let status = res.status();
+    let token = token.map(|s| s.trim()).filter(|s| !s.is_empty());
+    let res = http_cache::get(client, &url, None, token).await?;
+    let status = res.status;
     if !status.is_success() {
         return Err(AppError::Remote(format!("asset HTTP {status} from {url}")));
     }
-    res.bytes()
-        .await
-        .map(|b| b.to_vec())
-        .map_err(|e| AppError::Remote(format!("asset read failed: {e}")))
+    Ok(res.body)
 }
 
 #[tauri::command]
@@ -354,7 +371,22 @@ pub async fn hf_dataset_preview(
     splits_url
         .query_pairs_mut()
         .append_pair("dataset", &dataset);
-    let splits_resp: SplitsResponse = get_json(&client.http, splits_url, token).await?;
+    let splits_resp: SplitsResponse = match get_json(&client.http, splits_url, token).await {
+        Ok(resp) => resp,
+        Err(err) => {
+            let fallback = parquet_preview(
+                &client.http,
+                &dataset,
+                config.as_deref(),
+                split.as_deref(),
+                offset,
+                length,
+                token,
+            )
+            .await?;
+            return fallback.ok_or(err);
+        }
+    };
 
     let mut configs_map: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
     for entry in splits_resp.splits {
@@ -389,18 +421,16 @@ pub async fn hf_dataset_preview(
         )));
     }
 
-    let mut rows_url = Url::parse(DATASETS_SERVER_BASE)
-        .map_err(|e| AppError::Remote(format!("invalid datasets-server base url: {e}")))?;
-    rows_url.set_path("rows");
-    {
-        let mut qp = rows_url.query_pairs_mut();
-        qp.append_pair("dataset", &dataset);
-        qp.append_pair("config", &selected_config);
-        qp.append_pair("split", &selected_split);
-        qp.append_pair("offset", &offset.to_string());
-        qp.append_pair("length", &length.to_string());
-    }
-    let rows_resp: RowsResponse = get_json(&client.http, rows_url, token).await?;
+    let rows_resp = fetch_rows(
+        &client.http,
+        &dataset,
+        &selected_config,
+        &selected_split,
+        offset,
+        length,
+        token,
+    )
+    .await?;
 
     let mut configs: Vec<HfConfigSummary> = Vec::with_capacity(configs_map.len());
     for (config_name, splits) in configs_map {
@@ -419,7 +449,7 @@ pub async fn hf_dataset_preview(
             raw_type: f.ty,
         })
         .collect::<Vec<_>>();
-    let rows = rows_resp.rows.into_iter().map(|r| r.row).collect();
+    let (rows, truncated) = summarize_rows(rows_resp.rows.into_iter().map(|entry| entry.row));
 
     Ok(HfDatasetPreview {
         dataset,
@@ -428,29 +458,254 @@ pub async fn hf_dataset_preview(
         configs,
         offset,
         length,
+        max_length: MAX_ROWS,
+        source: "datasets-server",
         num_rows_total: rows_resp.num_rows_total,
         partial: rows_resp.partial,
         features,
         rows,
+        truncated,
     })
 }
 
-#[tauri::command]
-pub async fn hf_open_field(
-    client: State<'_, HfClient>,
-    input: String,
-    config: String,
-    split: String,
-    row_index: usize,
-    field_name: String,
-    opener_app_path: Option<String>,
-    token: Option<String>,
-) -> AppResult<OpenLeafResponse> {
-    let dataset = extract_repo_id(&input)?;
+/// Previews a dataset the datasets server cannot serve (typically private or gated) by reading
+/// the Parquet files in the repo directly. Returns `None` when the repo has no Parquet files.
+async fn parquet_preview(
+    http: &reqwest::Client,
+    dataset: &str,
+    config: Option<&str>,
+    split: Option<&str>,
+    offset: usize,
+    length: usize,
+    token: Option<&str>,
+) -> AppResult<Option<HfDatasetPreview>> {
+    let token = token.map(|s| s.trim()).filter(|s| !s.is_empty());
+    let mut info_url =
+        Url::parse(HUB_BASE).map_err(|e| AppError::Remote(format!("invalid Hub base url: {e}")))?;
+    info_url.set_path(&format!("api/datasets/{dataset}"));
+    let info: HubDatasetInfo = get_json(http, info_url, token).await?;
+    let groups = hf_parquet::group_files(info.siblings.into_iter().map(|s| s.rfilename));
+    if groups.is_empty() {
+        return Ok(None);
+    }
+
+    let selected_config = config
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| groups.keys().next().cloned().unwrap_or_default());
+    let splits_for_config = groups.get(&selected_config).ok_or_else(|| {
+        AppError::Invalid(format!(
+            "Unknown config '{selected_config}' for dataset {dataset}."
+        ))
+    })?;
+    let selected_split = split
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| pick_default_split(&splits_for_config.keys().cloned().collect()));
+    let paths = splits_for_config.get(&selected_split).ok_or_else(|| {
+        AppError::Invalid(format!(
+            "Unknown split '{selected_split}' for config '{selected_config}'."
+        ))
+    })?;
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let mut url = Url::parse(HUB_BASE)
+            .map_err(|e| AppError::Remote(format!("invalid Hub base url: {e}")))?;
+        url.set_path(&format!("datasets/{dataset}/resolve/main/{path}"));
+        files.push(url);
+    }
+    let page = hf_parquet::read_page(http, files, offset, length, token).await?;
+
+    let configs = groups
+        .iter()
+        .map(|(config_name, splits)| HfConfigSummary {
+            config: config_name.clone(),
+            splits: splits.keys().cloned().collect(),
+        })
+        .collect();
+    let features = page
+        .features
+        .into_iter()
+        .map(|(name, ty)| HfFeature {
+            name,
+            dtype: feature_dtype_label(&ty),
+            raw_type: ty,
+        })
+        .collect();
+    let (rows, truncated) = summarize_rows(page.rows);
+
+    Ok(Some(HfDatasetPreview {
+        dataset: dataset.to_string(),
+        config: selected_config,
+        split: selected_split,
+        configs,
+        offset,
+        length,
+        max_length: MAX_ROWS,
+        source: "parquet",
+        num_rows_total: page.num_rows_total,
+        partial: false,
+        features,
+        rows,
+        truncated,
+    }))
+}
+
+/// One page of rows from the datasets-server `/rows` endpoint.
+async fn fetch_rows(
+    http: &reqwest::Client,
+    dataset: &str,
+    config: &str,
+    split: &str,
+    offset: usize,
+    length: usize,
+    token: Option<&str>,
+) -> AppResult<RowsResponse> {
+    let mut rows_url = Url::parse(DATASETS_SERVER_BASE)
+        .map_err(|e| AppError::Remote(format!("invalid datasets-server base url: {e}")))?;
+    rows_url.set_path("rows");
+    {
+        let mut qp = rows_url.query_pairs_mut();
+        qp.append_pair("dataset", dataset);
+        qp.append_pair("config", config);
+        qp.append_pair("split", split);
+        qp.append_pair("offset", &offset.to_string());
+        qp.append_pair("length", &length.to_string());
+    }
+    get_json(http, rows_url, token).await
+}
+
+/// The rows' values with large arrays cut, and a summary of each cut.
+fn summarize_rows(
+    values: impl IntoIterator<Item = serde_json::Value>,
+) -> (Vec<serde_json::Value>, Vec<HfTruncatedValue>) {
+    let mut truncated = Vec::new();
+    let rows = values
+        .into_iter()
+        .enumerate()
+        .map(|(row, mut value)| {
+            if let Some(fields) = value.as_object_mut() {
+                for (field, field_value) in fields.iter_mut() {
+                    truncate_large_arrays(field_value, row, field, String::new(), &mut truncated);
+                }
+            }
+            value
+        })
+        .collect();
+    (rows, truncated)
+}
+
+/// Cuts every array under `value` (at `pointer` in a row's field) longer than
+/// `MAX_INLINE_ITEMS`, summarizing the outermost cut arrays into `out`.
+fn truncate_large_arrays(
+    value: &mut serde_json::Value,
+    row: usize,
+    field: &str,
+    pointer: String,
+    out: &mut Vec<HfTruncatedValue>,
+) {
+    match value {
+        serde_json::Value::Array(items) if items.len() > MAX_INLINE_ITEMS => {
+            out.push(HfTruncatedValue {
+                row,
+                field: field.to_string(),
+                length: items.len(),
+                shape: array_shape(value),
+                dtype: leaf_dtype(value),
+                pointer,
+            });
+            cut_arrays(value);
+        }
+        serde_json::Value::Array(items) => {
+            for (idx, item) in items.iter_mut().enumerate() {
+                truncate_large_arrays(item, row, field, format!("{pointer}/{idx}"), out);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for (key, item) in fields.iter_mut() {
+                let escaped = key.replace('~', "~0").replace('/', "~1");
+                truncate_large_arrays(item, row, field, format!("{pointer}/{escaped}"), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The JSON type shared by the leaves of `value`, or `mixed`.
+fn leaf_dtype(value: &serde_json::Value) -> String {
+    let mut types = BTreeSet::new();
+    leaf_types(value, &mut types);
+    let mut types = types.into_iter();
+    match (types.next(), types.next()) {
+        (Some(dtype), None) => dtype.to_string(),
+        _ => "mixed".to_string(),
+    }
+}
+
+/// Cuts `value` and every array nested in it to `MAX_INLINE_ITEMS` items.
+fn cut_arrays(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Array(items) => {
+            items.truncate(MAX_INLINE_ITEMS);
+            items.iter_mut().for_each(cut_arrays);
+        }
+        serde_json::Value::Object(fields) => fields.values_mut().for_each(cut_arrays),
+        _ => {}
+    }
+}
+
+/// Lengths of each nesting level of a rectangular array; `None` when rows differ in length.
+fn array_shape(value: &serde_json::Value) -> Option<Vec<usize>> {
+    let Some(items) = value.as_array() else {
+        return Some(Vec::new());
+    };
+    let mut inner = items.iter().map(array_shape);
+    let first = inner.next().unwrap_or(Some(Vec::new()))?;
+    for shape in inner {
+        if shape? != first {
+            return None;
+        }
+    }
+    let mut shape = vec![items.len()];
+    shape.extend(first);
+    Some(shape)
+}
+
+/// Adds the JSON type of every non-null leaf under `value` to `types`.
+fn leaf_types(value: &serde_json::Value, types: &mut BTreeSet<&'static str>) {
+    match value {
+        serde_json::Value::Array(items) => items.iter().for_each(|item| leaf_types(item, types)),
+        serde_json::Value::Number(n) if n.is_f64() => {
+            types.insert("float");
+        }
+        serde_json::Value::Number(_) => {
+            types.insert("int");
+        }
+        serde_json::Value::String(_) => {
+            types.insert("string");
+        }
+        serde_json::Value::Bool(_) => {
+            types.insert("bool");
+        }
+        serde_json::Value::Object(_) => {
+            types.insert("object");
+        }
+        serde_json::Value::Null => {}
+    }
+}
+
+/// Validated request parameters for a single field of a single row.
+fn field_request(
+    input: &str,
+    config: &str,
+    split: &str,
+    field_name: &str,
+) -> AppResult<(String, String, String, String)> {
+    let dataset = extract_repo_id(input)?;
     let config = config.trim().to_string();
     let split = split.trim().to_string();
     let field_name = field_name.trim().to_string();
-    let token = token.as_deref();
     if config.is_empty() {
         return Err(AppError::Invalid("Missing config.".into()));
     }
@@ -460,20 +715,20 @@ pub async fn hf_open_field(
     if field_name.is_empty() {
         return Err(AppError::Invalid("Missing field name.".into()));
     }
+    Ok((dataset, config, split, field_name))
+}
 
-    let mut rows_url = Url::parse(DATASETS_SERVER_BASE)
-        .map_err(|e| AppError::Remote(format!("invalid datasets-server base url: {e}")))?;
-    rows_url.set_path("rows");
-    {
-        let mut qp = rows_url.query_pairs_mut();
-        qp.append_pair("dataset", &dataset);
-        qp.append_pair("config", &config);
-        qp.append_pair("split", &split);
-        qp.append_pair("offset", &row_index.to_string());
-        qp.append_pair("length", "1");
-    }
-
-    let rows_resp: RowsResponse = get_json(&client.http, rows_url, token).await?;
+/// The raw JSON value of one field of one row.
+async fn field_value(
+    http: &reqwest::Client,
+    dataset: &str,
+    config: &str,
+    split: &str,
+    row_index: usize,
+    field_name: &str,
+    token: Option<&str>,
+) -> AppResult<serde_json::Value> {
+    let rows_resp = fetch_rows(http, dataset, config, split, row_index, 1, token).await?;
     let row = rows_resp
         .rows
         .into_iter()
@@ -483,96 +738,179 @@ pub async fn hf_open_field(
     let row_obj = row
         .as_object()
         .ok_or_else(|| AppError::Invalid("Row is not a JSON object.".into()))?;
-    let value = row_obj.get(&field_name).cloned().ok_or_else(|| {
+    row_obj.get(field_name).cloned().ok_or_else(|| {
         AppError::Missing(format!(
             "Field '{field_name}' not found in the requested row."
         ))
-    })?;
+    })
+}
+
+/// The field's bytes (the downloaded asset, text, or pretty JSON) and the extension to write it as.
+async fn field_bytes(
+    http: &reqwest::Client,
+    dataset: &str,
+    config: &str,
+    split: &str,
+    row_index: usize,
+    field_name: &str,
+    token: Option<&str>,
+) -> AppResult<(Vec<u8>, String)> {
+    let value = field_value(http, dataset, config, split, row_index, field_name, token).await?;
 
     if let Some((asset_url, mime)) = extract_asset(&value) {
-        let bytes = download_bytes(&client.http, asset_url.clone(), token).await?;
+        let bytes = download_bytes(http, asset_url.clone(), token).await?;
         let ext = ext_from_url(&asset_url)
             .or_else(|| {
                 mime.as_deref()
                     .and_then(ext_from_mime)
                     .map(|s| s.to_string())
             })
-            .or_else(|| infer::get(&bytes).map(|t| t.extension().to_string()))
+            .or_else(|| magic::sniff_ext(&bytes))
             .unwrap_or_else(|| "bin".into());
-        let size = bytes.len().min(u32::MAX as usize) as u32;
-        let temp_dir = std::env::temp_dir()
-            .join("dataset-inspector")
-            .join("huggingface");
-        fs::create_dir_all(&temp_dir)?;
-        let base_name = format!(
-            "{}-{}-{}-r{}-{}",
-            sanitize(&dataset),
-            sanitize(&config),
-            sanitize(&split),
-            row_index,
-            sanitize(&field_name)
-        );
-        let out: PathBuf = temp_dir.join(format!("{base_name}.{ext}"));
-        fs::write(&out, &bytes)?;
-
-        let mut opened = false;
-        let mut open_error = None::<String>;
-        if let Some(app_path) = opener_app_path.as_deref() {
-            match open_with::open_with_app_detached(&out, app_path) {
-                Ok(()) => opened = true,
-                Err(err) => open_error = Some(err),
-            }
-        }
-        if !opened {
-            if let Err(err) = open::that_detached(&out) {
-                open_error = Some(err.to_string());
-            } else {
-                opened = true;
-            }
-        }
-
-        let base = format!("{} ({} bytes)", out.display(), size);
-        let mut message = base;
-        let needs_opener = !opened && open_error.is_some();
-        if needs_opener {
-            message.push_str(" · no default app found, choose an app to open it");
-        }
-
-        return Ok(OpenLeafResponse {
-            path: out.display().to_string(),
-            size,
-            ext,
-            opened,
-            needs_opener,
-            message,
-        });
+        return Ok((bytes, ext));
     }
 
-    let (bytes, ext): (Vec<u8>, String) = match value {
+    match value {
         serde_json::Value::String(s) => {
             if s.len() > MAX_INLINE_TEXT {
                 return Err(AppError::Invalid("Text field is too large to open.".into()));
             }
-            (s.into_bytes(), "txt".into())
-        }
-        serde_json::Value::Object(map) => {
-            let bytes = serde_json::to_vec_pretty(&serde_json::Value::Object(map))
-                .map_err(|e| AppError::Invalid(format!("JSON serialize failed: {e}")))?;
-            (bytes, "json".into())
-        }
-        serde_json::Value::Array(_) => {
-            let bytes = serde_json::to_vec_pretty(&value)
-                .map_err(|e| AppError::Invalid(format!("JSON serialize failed: {e}")))?;
-            (bytes, "json".into())
+            Ok((s.into_bytes(), "txt".into()))
         }
         other => {
             let bytes = serde_json::to_vec_pretty(&other)
                 .map_err(|e| AppError::Invalid(format!("JSON serialize failed: {e}")))?;
-            (bytes, "json".into())
+            Ok((bytes, "json".into()))
         }
-    };
+    }
+}
+
+/// A single row, for jumping to a position without loading the pages around it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HfRow {
+    row_index: usize,
+    num_rows_total: usize,
+    row: serde_json::Value,
+    /// Arrays in `row` that were cut; `row` is always 0.
+    truncated: Vec<HfTruncatedValue>,
+}
 
-    let size = bytes.len().min(u32::MAX as usize) as u32;
+#[tauri::command]
+pub async fn hf_get_row(
+    client: State<'_, HfClient>,
+    input: String,
+    config: String,
+    split: String,
+    row_index: usize,
+    token: Option<String>,
+) -> AppResult<HfRow> {
+    let dataset = extract_repo_id(&input)?;
+    let config = config.trim();
+    let split = split.trim();
+    if config.is_empty() {
+        return Err(AppError::Invalid("Missing config.".into()));
+    }
+    if split.is_empty() {
+        return Err(AppError::Invalid("Missing split.".into()));
+    }
+    let rows_resp = fetch_rows(
+        &client.http,
+        &dataset,
+        config,
+        split,
+        row_index,
+        1,
+        token.as_deref(),
+    )
+    .await?;
+    let num_rows_total = rows_resp.num_rows_total;
+    let (rows, truncated) = summarize_rows(rows_resp.rows.into_iter().map(|entry| entry.row));
+    let row = rows.into_iter().next().ok_or_else(|| {
+        AppError::Missing(format!(
+            "Row {row_index} is past the end of the split ({num_rows_total} rows)."
+        ))
+    })?;
+    Ok(HfRow {
+        row_index,
+        num_rows_total,
+        row,
+        truncated,
+    })
+}
+
+/// The whole value of a field that `hf_dataset_preview` cut short.
+#[tauri::command]
+pub async fn hf_field_value(
+    client: State<'_, HfClient>,
+    input: String,
+    config: String,
+    split: String,
+    row_index: usize,
+    field_name: String,
+    token: Option<String>,
+) -> AppResult<serde_json::Value> {
+    let (dataset, config, split, field_name) = field_request(&input, &config, &split, &field_name)?;
+    field_value(
+        &client.http,
+        &dataset,
+        &config,
+        &split,
+        row_index,
+        &field_name,
+        token.as_deref(),
+    )
+    .await
+}
+
+/// A field's bytes and the extension to write them as, for opening several fields at once.
+pub async fn fetch_field(
+    client: &HfClient,
+    input: &str,
+    config: &str,
+    split: &str,
+    row_index: usize,
+    field_name: &str,
+    token: Option<&str>,
+) -> AppResult<(Vec<u8>, String)> {
+    let (dataset, config, split, field_name) = field_request(input, config, split, field_name)?;
+    field_bytes(
+        &client.http,
+        &dataset,
+        &config,
+        &split,
+        row_index,
+        &field_name,
+        token,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn hf_open_field(
+    app: AppHandle,
+    client: State<'_, HfClient>,
+    input: String,
+    config: String,
+    split: String,
+    row_index: usize,
+    field_name: String,
+    opener_app_path: Option<String>,
+    token: Option<String>,
+) -> AppResult<OpenLeafResponse> {
+    let (dataset, config, split, field_name) = field_request(&input, &config, &split, &field_name)?;
+    let (bytes, ext) = field_bytes(
+        &client.http,
+        &dataset,
+        &config,
+        &split,
+        row_index,
+        &field_name,
+        token.as_deref(),
+    )
+    .await?;
+
+    let size = bytes.len() as u64;
     let temp_dir = std::env::temp_dir()
         .join("dataset-inspector")
         .join("huggingface");
@@ -585,30 +923,18 @@ pub async fn hf_open_field(
         row_index,
         sanitize(&field_name)
     );
-    let out: PathBuf = temp_dir.join(format!("{base_name}.{ext}"));
-    fs::write(&out, &bytes)?;
-
-    let mut opened = false;
-    let mut open_error = None::<String>;
-    if let Some(app_path) = opener_app_path.as_deref() {
-        match open_with::open_with_app_detached(&out, app_path) {
-            Ok(()) => opened = true,
-            Err(err) => open_error = Some(err),
-        }
-    }
-    if !opened {
-        if let Err(err) = open::that_detached(&out) {
-            open_error = Some(err.to_string());
-        } else {
-            opened = true;
-        }
-    }
+    let out: PathBuf = temp_names::write(&temp_dir, &base_name, &ext, &bytes)?;
+
+    let opened = open_with::open_extracted(&app, &out, opener_app_path.as_deref()).is_ok();
 
-    let base = format!("{} ({} bytes)", out.display(), size);
+    let base = messages::text(
+        Message::FileWithSize,
+        &[("path", &out.display()), ("size", &size)],
+    );
     let mut message = base;
-    let needs_opener = !opened && open_error.is_some();
+    let needs_opener = !opened;
     if needs_opener {
-        message.push_str(" · no default app found, choose an app to open it");
+        message.push_str(&messages::text(Message::NoDefaultApp, &[]));
     }
 
     Ok(OpenLeafResponse {
@@ -620,3 +946,100 @@ pub async fn hf_open_field(
         message,
     })
 }
+
+/// Writes a field to `destination_path` instead of opening it from the temp dir.
+#[tauri::command]
+pub async fn hf_save_field_as(
+    client: State<'_, HfClient>,
+    input: String,
+    config: String,
+    split: String,
+    row_index: usize,
+    field_name: String,
+    destination_path: String,
+    token: Option<String>,
+) -> AppResult<SavedLeafResponse> {
+    let (dataset, config, split, field_name) = field_request(&input, &config, &split, &field_name)?;
+    let (bytes, ext) = field_bytes(
+        &client.http,
+        &dataset,
+        &config,
+        &split,
+        row_index,
+        &field_name,
+        token.as_deref(),
+    )
+    .await?;
+    save_leaf::write_to(&destination_path, &bytes, ext)
+}
+
+fn card_license(card: &serde_json::Value) -> Option<String> {
+    match card.get("license")? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Array(items) => {
+            let names: Vec<&str> = items.iter().filter_map(|v| v.as_str()).collect();
+            (!names.is_empty()).then(|| names.join(", "))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the README (dataset card) and any LICENSE/DATASHEET files at the root of a dataset
+/// repo, plus the license declared in the card metadata.
+#[tauri::command]
+pub async fn hf_dataset_docs(
+    client: State<'_, HfClient>,
+    input: String,
+    token: Option<String>,
+) -> AppResult<DatasetDocsResponse> {
+    let dataset = extract_repo_id(&input)?;
+    let token = token.as_deref().map(|s| s.trim()).filter(|s| !s.is_empty());
+
+    let mut info_url =
+        Url::parse(HUB_BASE).map_err(|e| AppError::Remote(format!("invalid Hub base url: {e}")))?;
+    info_url.set_path(&format!("api/datasets/{dataset}"));
+    info_url.query_pairs_mut().append_pair("blobs", "true");
+    let info: HubDatasetInfo = get_json(&client.http, info_url, token).await?;
+    let declared_license = info.card_data.as_ref().and_then(card_license);
+
+    let mut documents = Vec::new();
+    for sibling in info.siblings {
+        if sibling.rfilename.contains('/') {
+            continue;
+        }
+        let Some(kind) = dataset_docs::doc_kind(&sibling.rfilename) else {
+            continue;
+        };
+        let mut url = Url::parse(HUB_BASE)
+            .map_err(|e| AppError::Remote(format!("invalid Hub base url: {e}")))?;
+        url.set_path(&format!(
+            "datasets/{dataset}/resolve/main/{}",
+            sibling.rfilename
+        ));
+        let range = format!("bytes=0-{}", dataset_docs::MAX_DOC_BYTES - 1);
+        let res = http_cache::get(&client.http, &url, Some(&range), token).await?;
+        if !(res.status.is_success() || res.status == reqwest::StatusCode::PARTIAL_CONTENT) {
+            return Err(AppError::Remote(format!("HTTP {} from {url}", res.status)));
+        }
+        let size = sibling
+            .size
+            .or_else(|| {
+                res.content_range
+                    .as_deref()
+                    .and_then(|v| v.rsplit('/').next()?.parse().ok())
+            })
+            .unwrap_or(res.body.len() as u64);
+        documents.push(dataset_docs::document_from_bytes(
+            kind,
+            sibling.rfilename,
+            size,
+            &res.body,
+            Some(url.to_string()),
+        ));
+    }
+    Ok(DatasetDocsResponse::new(
+        dataset,
+        declared_license,
+        documents,
+    ))
+}
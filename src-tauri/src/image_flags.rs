@@ -0,0 +1,95 @@
+use image::GenericImageView;
+
+/// Images whose shorter side is below this many pixels are flagged as tiny.
+pub const DEFAULT_MIN_EDGE: u32 = 32;
+/// Images whose longer side exceeds the shorter one by more than this factor are flagged.
+pub const DEFAULT_MAX_ASPECT_RATIO: f64 = 8.0;
+/// Edge of the downscaled copy the blank check looks at.
+const BLANK_SAMPLE_EDGE: u32 = 64;
+/// Luma standard deviation below which an image counts as one flat color.
+const BLANK_MAX_STDDEV: f64 = 2.0;
+const BLACK_MAX_MEAN: f64 = 8.0;
+const WHITE_MIN_MEAN: f64 = 247.0;
+
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif", "bmp", "tif", "tiff"];
+
+/// True if a member or file name has an extension the image checks can decode.
+pub fn is_image_name(name: &str) -> bool {
+    name.rsplit_once('.')
+        .is_some_and(|(_, ext)| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+pub struct Thresholds {
+    pub min_edge: u32,
+    pub max_aspect_ratio: f64,
+}
+
+/// One problem found in an image: a kind (`undecodable`, `tiny`, `extreme-aspect-ratio`,
+/// `all-black`, `all-white`, `solid-color`) and a human-readable reason.
+pub struct Finding {
+    pub kind: &'static str,
+    pub message: String,
+}
+
+/// Decodes `bytes` and returns its dimensions (if it decoded) and everything that looks wrong.
+pub fn inspect(bytes: &[u8], thresholds: &Thresholds) -> (Option<(u32, u32)>, Vec<Finding>) {
+    let img = match image::load_from_memory(bytes) {
+        Ok(img) => img,
+        Err(e) => {
+            return (
+                None,
+                vec![Finding {
+                    kind: "undecodable",
+                    message: format!("decode failed: {e}"),
+                }],
+            )
+        }
+    };
+    let (width, height) = img.dimensions();
+    let mut findings = Vec::new();
+    let short = width.min(height);
+    let long = width.max(height);
+    if short < thresholds.min_edge {
+        findings.push(Finding {
+            kind: "tiny",
+            message: format!(
+                "{width}x{height} is below the {}px minimum edge",
+                thresholds.min_edge
+            ),
+        });
+    }
+    if short > 0 && long as f64 / short as f64 > thresholds.max_aspect_ratio {
+        findings.push(Finding {
+            kind: "extreme-aspect-ratio",
+            message: format!(
+                "{width}x{height} has an aspect ratio of {:.1}:1",
+                long as f64 / short as f64
+            ),
+        });
+    }
+
+    let luma = img
+        .thumbnail(BLANK_SAMPLE_EDGE, BLANK_SAMPLE_EDGE)
+        .to_luma8();
+    let count = (luma.width() as f64 * luma.height() as f64).max(1.0);
+    let mean = luma.pixels().map(|p| p.0[0] as f64).sum::<f64>() / count;
+    let variance = luma
+        .pixels()
+        .map(|p| (p.0[0] as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    if variance.sqrt() < BLANK_MAX_STDDEV {
+        let (kind, what) = if mean <= BLACK_MAX_MEAN {
+            ("all-black", "black")
+        } else if mean >= WHITE_MIN_MEAN {
+            ("all-white", "white")
+        } else {
+            ("solid-color", "a single color")
+        };
+        findings.push(Finding {
+            kind,
+            message: format!("image is {what} (mean luma {mean:.0})"),
+        });
+    }
+    (Some((width, height)), findings)
+}
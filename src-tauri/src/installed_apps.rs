@@ -0,0 +1,504 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledApp {
+    name: String,
+    /// Value to pass back as `openerAppPath`: an .app bundle, a .desktop file or an executable.
+    path: String,
+    /// Icon file path, or a theme icon name on Linux.
+    icon: Option<String>,
+    is_default: bool,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::InstalledApp;
+    use core_foundation::{
+        array::{CFArray, CFArrayRef},
+        base::TCFType,
+        error::CFErrorRef,
+        url::{CFURLRef, CFURL},
+    };
+    use std::path::{Path, PathBuf};
+
+    const LS_ROLES_ALL: u32 = 0xFFFF_FFFF;
+
+    #[link(name = "CoreServices", kind = "framework")]
+    extern "C" {
+        fn LSCopyApplicationURLsForURL(url: CFURLRef, roles: u32) -> CFArrayRef;
+        fn LSCopyDefaultApplicationURLForURL(
+            url: CFURLRef,
+            roles: u32,
+            error: *mut CFErrorRef,
+        ) -> CFURLRef;
+    }
+
+    /// Asks LaunchServices for every registered handler of `*.ext` and the default one, using
+    /// an empty probe file so the lookup goes through the same path as a double click.
+    fn launch_services_apps(ext: &str) -> Option<(Vec<PathBuf>, Option<PathBuf>)> {
+        let probe = std::env::temp_dir().join(format!("dataset-inspector-open-with.{ext}"));
+        std::fs::write(&probe, b"").ok()?;
+        let url = CFURL::from_path(&probe, false);
+        let result = url.map(|url| unsafe {
+            let list = LSCopyApplicationURLsForURL(url.as_concrete_TypeRef(), LS_ROLES_ALL);
+            let apps = if list.is_null() {
+                Vec::new()
+            } else {
+                CFArray::<CFURL>::wrap_under_create_rule(list)
+                    .iter()
+                    .filter_map(|app| app.to_path())
+                    .collect()
+            };
+            let default = LSCopyDefaultApplicationURLForURL(
+                url.as_concrete_TypeRef(),
+                LS_ROLES_ALL,
+                std::ptr::null_mut(),
+            );
+            let default = (!default.is_null())
+                .then(|| CFURL::wrap_under_create_rule(default).to_path())
+                .flatten();
+            (apps, default)
+        });
+        let _ = std::fs::remove_file(&probe);
+        result
+    }
+
+    /// A few common extension → UTI mappings, since most bundles declare content types
+    /// rather than extensions.
+    fn uti_candidates(ext: &str) -> &'static [&'static str] {
+        match ext {
+            "jpg" | "jpeg" => &["public.jpeg", "public.image"],
+            "png" => &["public.png", "public.image"],
+            "gif" | "webp" | "bmp" | "tif" | "tiff" | "heic" => &["public.image"],
+            "wav" | "flac" | "mp3" | "ogg" | "m4a" | "aac" | "opus" => {
+                &["public.audio", "public.audiovisual-content"]
+            }
+            "mp4" | "mov" | "mkv" | "webm" | "avi" => {
+                &["public.movie", "public.audiovisual-content"]
+            }
+            "txt" | "log" | "csv" | "tsv" | "md" => &["public.plain-text", "public.text"],
+            "json" | "jsonl" => &["public.json", "public.text"],
+            "pdf" => &["com.adobe.pdf"],
+            "html" | "htm" => &["public.html"],
+            _ => &[],
+        }
+    }
+
+    fn bundle_handles(dict: &plist::Dictionary, ext: &str, utis: &[&str]) -> bool {
+        let Some(doc_types) = dict.get("CFBundleDocumentTypes").and_then(|v| v.as_array()) else {
+            return false;
+        };
+        doc_types.iter().filter_map(|t| t.as_dictionary()).any(|t| {
+            let by_ext = t
+                .get("CFBundleTypeExtensions")
+                .and_then(|v| v.as_array())
+                .is_some_and(|a| {
+                    a.iter()
+                        .filter_map(|v| v.as_string())
+                        .any(|e| e == "*" || e.eq_ignore_ascii_case(ext))
+                });
+            let by_uti = t
+                .get("LSItemContentTypes")
+                .and_then(|v| v.as_array())
+                .is_some_and(|a| {
+                    a.iter()
+                        .filter_map(|v| v.as_string())
+                        .any(|u| utis.contains(&u))
+                });
+            by_ext || by_uti
+        })
+    }
+
+    /// Builds the entry for `bundle`; with `filter` set, only when its Info.plist declares the
+    /// extension or one of the content types.
+    fn app_for_bundle(bundle: &Path, filter: Option<(&str, &[&str])>) -> Option<InstalledApp> {
+        let info = plist::Value::from_file(bundle.join("Contents/Info.plist")).ok()?;
+        let dict = info.as_dictionary()?;
+        if let Some((ext, utis)) = filter {
+            if !bundle_handles(dict, ext, utis) {
+                return None;
+            }
+        }
+        let name = ["CFBundleDisplayName", "CFBundleName"]
+            .iter()
+            .find_map(|k| dict.get(k).and_then(|v| v.as_string()))
+            .map(str::to_string)
+            .or_else(|| bundle.file_stem().map(|s| s.to_string_lossy().to_string()))?;
+        let icon = dict
+            .get("CFBundleIconFile")
+            .and_then(|v| v.as_string())
+            .map(|file| {
+                let mut path = bundle.join("Contents/Resources").join(file);
+                if path.extension().is_none() {
+                    path.set_extension("icns");
+                }
+                path
+            })
+            .filter(|p| p.exists())
+            .map(|p| p.display().to_string());
+        Some(InstalledApp {
+            name,
+            path: bundle.display().to_string(),
+            icon,
+            is_default: false,
+        })
+    }
+
+    pub fn apps_for_ext(ext: &str) -> Vec<InstalledApp> {
+        if let Some((bundles, default)) = launch_services_apps(ext) {
+            if !bundles.is_empty() {
+                return bundles
+                    .iter()
+                    .filter_map(|bundle| {
+                        let mut app = app_for_bundle(bundle, None)?;
+                        app.is_default = default.as_deref() == Some(bundle.as_path());
+                        Some(app)
+                    })
+                    .collect();
+            }
+        }
+        scan_applications(ext)
+    }
+
+    /// Fallback when LaunchServices has nothing: scan the usual Applications folders and match
+    /// each bundle's declared document types.
+    fn scan_applications(ext: &str) -> Vec<InstalledApp> {
+        let mut roots = vec![
+            PathBuf::from("/Applications"),
+            PathBuf::from("/Applications/Utilities"),
+            PathBuf::from("/System/Applications"),
+            PathBuf::from("/System/Applications/Utilities"),
+        ];
+        if let Some(home) = std::env::var_os("HOME") {
+            roots.push(PathBuf::from(home).join("Applications"));
+        }
+        let utis = uti_candidates(ext);
+        let mut apps = Vec::new();
+        for root in roots {
+            let Ok(entries) = std::fs::read_dir(&root) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("app") {
+                    continue;
+                }
+                if let Some(app) = app_for_bundle(&path, Some((ext, utis))) {
+                    apps.push(app);
+                }
+            }
+        }
+        apps
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::InstalledApp;
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        process::Command,
+    };
+
+    fn data_dirs() -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        match std::env::var_os("XDG_DATA_HOME") {
+            Some(dir) if !dir.is_empty() => dirs.push(PathBuf::from(dir)),
+            _ => {
+                if let Some(home) = std::env::var_os("HOME") {
+                    dirs.push(PathBuf::from(home).join(".local/share"));
+                }
+            }
+        }
+        let system = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+        dirs.extend(
+            system
+                .split(':')
+                .filter(|d| !d.is_empty())
+                .map(PathBuf::from),
+        );
+        dirs
+    }
+
+    /// Mime types registered for `*.ext` in the shared-mime-info glob files.
+    fn mime_types_for_ext(ext: &str, dirs: &[PathBuf]) -> Vec<String> {
+        let pattern = format!("*.{ext}");
+        let mut mimes = Vec::new();
+        for dir in dirs {
+            let Ok(text) = fs::read_to_string(dir.join("mime/globs2")) else {
+                continue;
+            };
+            for line in text.lines().filter(|l| !l.starts_with('#')) {
+                let mut parts = line.split(':');
+                let (_weight, Some(mime), Some(glob)) = (parts.next(), parts.next(), parts.next())
+                else {
+                    continue;
+                };
+                if glob.eq_ignore_ascii_case(&pattern) && !mimes.iter().any(|m| m == mime) {
+                    mimes.push(mime.to_string());
+                }
+            }
+        }
+        mimes
+    }
+
+    fn desktop_ids_for_mime(mime: &str, dirs: &[PathBuf]) -> Vec<String> {
+        let prefix = format!("{mime}=");
+        let mut ids = Vec::new();
+        for dir in dirs {
+            let Ok(text) = fs::read_to_string(dir.join("applications/mimeinfo.cache")) else {
+                continue;
+            };
+            for line in text.lines() {
+                if let Some(list) = line.strip_prefix(&prefix) {
+                    ids.extend(
+                        list.split(';')
+                            .filter(|id| !id.is_empty())
+                            .map(str::to_string),
+                    );
+                }
+            }
+        }
+        ids
+    }
+
+    fn default_desktop_id(mime: &str) -> Option<String> {
+        let out = Command::new("xdg-mime")
+            .args(["query", "default", mime])
+            .output()
+            .ok()?;
+        let id = String::from_utf8_lossy(&out.stdout).trim().to_string();
+        (!id.is_empty()).then_some(id)
+    }
+
+    fn find_desktop_file(id: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+        dirs.iter()
+            .map(|dir| dir.join("applications").join(id))
+            .find(|p| p.is_file())
+    }
+
+    fn parse_desktop_entry(path: &Path) -> Option<(String, Option<String>)> {
+        let text = fs::read_to_string(path).ok()?;
+        let mut in_entry = false;
+        let mut name = None;
+        let mut icon = None;
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_entry = line == "[Desktop Entry]";
+                continue;
+            }
+            if !in_entry {
+                continue;
+            }
+            if let Some(v) = line.strip_prefix("Name=") {
+                name = Some(v.to_string());
+            } else if let Some(v) = line.strip_prefix("Icon=") {
+                icon = Some(v.to_string());
+            } else if line == "NoDisplay=true" || line == "Hidden=true" {
+                return None;
+            }
+        }
+        Some((name?, icon))
+    }
+
+    pub fn apps_for_ext(ext: &str) -> Vec<InstalledApp> {
+        let dirs = data_dirs();
+        let mut apps = Vec::new();
+        for mime in mime_types_for_ext(ext, &dirs) {
+            let default_id = default_desktop_id(&mime);
+            let mut ids = desktop_ids_for_mime(&mime, &dirs);
+            if let Some(id) = default_id.as_ref() {
+                ids.insert(0, id.clone());
+            }
+            for id in ids {
+                let Some(path) = find_desktop_file(&id, &dirs) else {
+                    continue;
+                };
+                let Some((name, icon)) = parse_desktop_entry(&path) else {
+                    continue;
+                };
+                apps.push(InstalledApp {
+                    name,
+                    path: path.display().to_string(),
+                    icon,
+                    is_default: default_id.as_deref() == Some(id.as_str()),
+                });
+            }
+        }
+        apps
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::InstalledApp;
+    use std::path::Path;
+    use winreg::{enums::HKEY_CLASSES_ROOT, enums::HKEY_CURRENT_USER, RegKey};
+
+    const FILE_EXTS_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\FileExts";
+
+    fn expand_env(value: &str) -> String {
+        let mut out = String::new();
+        let mut rest = value;
+        while let Some(start) = rest.find('%') {
+            let Some(len) = rest[start + 1..].find('%') else {
+                break;
+            };
+            let var = &rest[start + 1..start + 1 + len];
+            out.push_str(&rest[..start]);
+            match std::env::var(var) {
+                Ok(v) => out.push_str(&v),
+                Err(_) => out.push_str(&rest[start..start + len + 2]),
+            }
+            rest = &rest[start + len + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Executable path from a `shell\open\command` string such as `"C:\x\app.exe" "%1"`.
+    fn exe_from_command(command: &str) -> Option<String> {
+        let command = command.trim();
+        let exe = if let Some(quoted) = command.strip_prefix('"') {
+            quoted.split('"').next()?.to_string()
+        } else {
+            let lower = command.to_ascii_lowercase();
+            match lower.find(".exe") {
+                Some(idx) => command[..idx + 4].to_string(),
+                None => command.split_whitespace().next()?.to_string(),
+            }
+        };
+        let exe = expand_env(&exe);
+        (!exe.is_empty()).then_some(exe)
+    }
+
+    fn readable_name(value: Option<String>, exe: &str) -> String {
+        value
+            .filter(|v| !v.trim().is_empty() && !v.starts_with('@'))
+            .unwrap_or_else(|| {
+                Path::new(exe)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| exe.to_string())
+            })
+    }
+
+    fn app_from_key(key: &RegKey, name_value: &str) -> Option<InstalledApp> {
+        if key.get_raw_value("NoOpenWith").is_ok() {
+            return None;
+        }
+        let command: String = key
+            .open_subkey(r"shell\open\command")
+            .ok()?
+            .get_value("")
+            .ok()?;
+        let exe = exe_from_command(&command)?;
+        let icon = key
+            .open_subkey("DefaultIcon")
+            .ok()
+            .and_then(|k| k.get_value::<String, _>("").ok())
+            .map(|v| expand_env(&v));
+        Some(InstalledApp {
+            name: readable_name(key.get_value(name_value).ok(), &exe),
+            path: exe,
+            icon,
+            is_default: false,
+        })
+    }
+
+    pub fn apps_for_ext(ext: &str) -> Vec<InstalledApp> {
+        let hkcr = RegKey::predef(HKEY_CLASSES_ROOT);
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let dot = format!(".{ext}");
+
+        let class_default: Option<String> = hkcr
+            .open_subkey(&dot)
+            .ok()
+            .and_then(|k| k.get_value("").ok());
+        let user_choice: Option<String> = hkcu
+            .open_subkey(format!(r"{FILE_EXTS_KEY}\{dot}\UserChoice"))
+            .ok()
+            .and_then(|k| k.get_value("ProgId").ok());
+        let default_progid = user_choice.or(class_default);
+
+        let mut progids: Vec<String> = default_progid.iter().cloned().collect();
+        if let Ok(key) = hkcr.open_subkey(format!(r"{dot}\OpenWithProgids")) {
+            progids.extend(key.enum_values().flatten().map(|(name, _)| name));
+        }
+        let mut apps = Vec::new();
+        for progid in progids {
+            if let Some(mut app) = hkcr
+                .open_subkey(&progid)
+                .ok()
+                .and_then(|key| app_from_key(&key, ""))
+            {
+                app.is_default = default_progid.as_deref() == Some(progid.as_str());
+                apps.push(app);
+            }
+        }
+
+        let mut exe_names: Vec<String> = hkcr
+            .open_subkey(format!(r"{dot}\OpenWithList"))
+            .map(|k| k.enum_keys().flatten().collect())
+            .unwrap_or_default();
+        if let Ok(key) = hkcu.open_subkey(format!(r"{FILE_EXTS_KEY}\{dot}\OpenWithList")) {
+            exe_names.extend(
+                key.enum_values()
+                    .flatten()
+                    .filter(|(name, _)| name != "MRUList")
+                    .map(|(_, value)| value.to_string().trim_matches('"').to_string()),
+            );
+        }
+        for exe in exe_names {
+            if let Some(app) = hkcr
+                .open_subkey(format!(r"Applications\{exe}"))
+                .ok()
+                .and_then(|key| app_from_key(&key, "FriendlyAppName"))
+            {
+                apps.push(app);
+            }
+        }
+        apps
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+mod platform {
+    use super::InstalledApp;
+
+    pub fn apps_for_ext(_ext: &str) -> Vec<InstalledApp> {
+        Vec::new()
+    }
+}
+
+#[tauri::command]
+pub async fn list_installed_apps(ext: String) -> AppResult<Vec<InstalledApp>> {
+    spawn_blocking(move || {
+        let ext = ext.trim().trim_start_matches('.').to_ascii_lowercase();
+        if ext.is_empty() {
+            return Err(AppError::Invalid("extension is empty".into()));
+        }
+        let mut seen = HashSet::new();
+        let mut apps: Vec<InstalledApp> = platform::apps_for_ext(&ext)
+            .into_iter()
+            .filter(|app| seen.insert(app.path.to_lowercase()))
+            .collect();
+        apps.sort_by(|a, b| {
+            b.is_default
+                .cmp(&a.is_default)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        Ok(apps)
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -1,5 +1,7 @@
 use serde::Serialize;
 
+use crate::{decoder_plugins::PluginPreview, preview_kind::PreviewKind};
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ChunkSummary {
@@ -43,28 +45,58 @@ pub struct ItemMeta {
 #[serde(rename_all = "camelCase")]
 pub struct FieldPreview {
     pub preview_text: Option<String>,
+    /// Characters of text in the previewed window before `preview_text` was cut to length.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_length: Option<usize>,
     pub hex_snippet: String,
     pub guessed_ext: Option<String>,
     pub is_binary: bool,
-    pub size: u32,
+    pub size: u64,
+    /// How to render the preview; `previewText`/`hexSnippet` stay filled in for every kind.
+    pub kind: PreviewKind,
+    /// Compression layer (`gzip`, `zstd`, `bzip2`) removed before the rest was previewed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<&'static str>,
+    /// Embedded EXIF/XMP/ID3/Vorbis tags found in the previewed bytes.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub metadata: Vec<MetadataTag>,
+    /// Set when a decoder plugin handled the guessed extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<PluginPreview>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataTag {
+    pub key: String,
+    pub value: String,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenLeafResponse {
     pub path: String,
-    pub size: u32,
+    pub size: u64,
     pub ext: String,
     pub opened: bool,
     pub needs_opener: bool,
     pub message: String,
 }
 
+/// A leaf written to a location the user picked instead of a temp file.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedLeafResponse {
+    pub path: String,
+    pub size: u64,
+    pub ext: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PreparedFileResponse {
     pub path: String,
-    pub size: u32,
+    pub size: u64,
     pub ext: String,
 }
 
@@ -73,6 +105,27 @@ pub struct PreparedFileResponse {
 pub struct InlineMediaResponse {
     pub base64: String,
     pub mime: String,
-    pub size: u32,
+    pub size: u64,
+    pub ext: String,
+}
+
+/// Media registered with the `dsi-media` protocol; the webview loads it by `token`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamedMediaResponse {
+    pub token: String,
+    pub mime: String,
+    pub size: u64,
     pub ext: String,
 }
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Thumbnail {
+    pub name: String,
+    /// Base64 JPEG, absent when the source could not be read or decoded.
+    pub base64: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub error: Option<String>,
+}
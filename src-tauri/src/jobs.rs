@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tauri::{Manager, Runtime, Window, WindowEvent};
+
+use crate::app_error::{AppError, AppResult};
+
+/// How long closing the window waits for running jobs to notice they were cancelled.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+const SHUTDOWN_POLL: Duration = Duration::from_millis(50);
+
+/// Scans and downloads running right now, so closing the app can stop them instead of leaving
+/// their threads running and their files half-written.
+#[derive(Clone, Default)]
+pub struct JobRegistry {
+    next_id: Arc<AtomicU64>,
+    closing: Arc<AtomicBool>,
+    active: Arc<Mutex<HashMap<u64, ActiveJob>>>,
+}
+
+struct ActiveJob {
+    cancel: Arc<AtomicBool>,
+    /// The file the job writes before renaming it into place.
+    partial: Option<PathBuf>,
+}
+
+/// A registered job; dropping it, however the job ends, unregisters it.
+pub struct JobGuard {
+    registry: JobRegistry,
+    id: u64,
+    cancel: Arc<AtomicBool>,
+}
+
+pub fn cancelled() -> AppError {
+    AppError::Interrupted("cancelled because the app is closing".into())
+}
+
+/// Fails once `cancel` is set, for loops that only get the flag.
+pub fn ensure_running(cancel: &AtomicBool) -> AppResult<()> {
+    if cancel.load(Ordering::Relaxed) {
+        return Err(cancelled());
+    }
+    Ok(())
+}
+
+impl JobRegistry {
+    /// Registers a job writing `partial`, which is removed if the app closes before it finishes.
+    pub fn start(&self, partial: Option<PathBuf>) -> JobGuard {
+        self.register(Arc::new(AtomicBool::new(false)), partial)
+    }
+
+    /// Registers a job that already stops when `cancel` is set.
+    pub fn track(&self, cancel: Arc<AtomicBool>) -> JobGuard {
+        self.register(cancel, None)
+    }
+
+    fn register(&self, cancel: Arc<AtomicBool>, partial: Option<PathBuf>) -> JobGuard {
+        if self.closing.load(Ordering::Relaxed) {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.active
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                id,
+                ActiveJob {
+                    cancel: cancel.clone(),
+                    partial,
+                },
+            );
+        JobGuard {
+            registry: self.clone(),
+            id,
+            cancel,
+        }
+    }
+
+    /// Cancels every job, gives them `SHUTDOWN_GRACE` to wind down, then removes the partial
+    /// files they were writing. Jobs started afterwards are cancelled right away.
+    pub fn shutdown(&self) {
+        self.closing.store(true, Ordering::Relaxed);
+        let partials = {
+            let active = self.active.lock().unwrap_or_else(PoisonError::into_inner);
+            for job in active.values() {
+                job.cancel.store(true, Ordering::Relaxed);
+            }
+            active
+                .values()
+                .filter_map(|job| job.partial.clone())
+                .collect::<Vec<_>>()
+        };
+        let deadline = Instant::now() + SHUTDOWN_GRACE;
+        while Instant::now() < deadline && !self.is_idle() {
+            std::thread::sleep(SHUTDOWN_POLL);
+        }
+        for partial in partials {
+            let _ = fs::remove_file(partial);
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.active
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+}
+
+impl JobGuard {
+    pub fn cancel_flag(&self) -> &AtomicBool {
+        &self.cancel
+    }
+
+    /// Fails once the app started closing, so the job can stop at a safe point.
+    pub fn ensure_running(&self) -> AppResult<()> {
+        ensure_running(&self.cancel)
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        self.registry
+            .active
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.id);
+    }
+}
+
+/// Stops every job when the main window is closed, which ends the app.
+pub fn handle_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if window.label() == "main" && matches!(event, WindowEvent::CloseRequested { .. }) {
+        window.state::<JobRegistry>().shutdown();
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use tauri::async_runtime::spawn_blocking;
+
+use crate::annotations::csv_field;
+use crate::app_error::{AppError, AppResult};
+use crate::read_only;
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ListingExportFormat {
+    Csv,
+    Jsonl,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingField {
+    name: String,
+    size: u64,
+}
+
+/// One row of the sample table as the UI shows it.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingRow {
+    key: String,
+    #[serde(default)]
+    fields: Vec<ListingField>,
+    /// Derived values (duration, dimensions, annotations, …) keyed by column name.
+    #[serde(default)]
+    metadata: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingExportReport {
+    path: String,
+    count: u32,
+}
+
+fn metadata_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => csv_field(s),
+        other => csv_field(&other.to_string()),
+    }
+}
+
+/// One column per field name (its size) and per metadata key, in sorted order.
+fn listing_csv(rows: &[ListingRow]) -> String {
+    let field_names = rows
+        .iter()
+        .flat_map(|r| r.fields.iter().map(|f| f.name.as_str()))
+        .collect::<BTreeSet<_>>();
+    let metadata_keys = rows
+        .iter()
+        .flat_map(|r| r.metadata.keys().map(String::as_str))
+        .collect::<BTreeSet<_>>();
+
+    let mut header = vec!["key".to_string(), "fields".into(), "total_size".into()];
+    header.extend(field_names.iter().map(|n| csv_field(&format!("size_{n}"))));
+    header.extend(metadata_keys.iter().map(|k| csv_field(k)));
+    let mut out = header.join(",");
+    out.push('\n');
+
+    for row in rows {
+        let mut cells = vec![
+            csv_field(&row.key),
+            csv_field(
+                &row.fields
+                    .iter()
+                    .map(|f| f.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(";"),
+            ),
+            row.fields.iter().map(|f| f.size).sum::<u64>().to_string(),
+        ];
+        cells.extend(field_names.iter().map(|name| {
+            row.fields
+                .iter()
+                .find(|f| f.name == *name)
+                .map(|f| f.size.to_string())
+                .unwrap_or_default()
+        }));
+        cells.extend(metadata_keys.iter().map(|key| {
+            row.metadata
+                .get(*key)
+                .map(metadata_cell)
+                .unwrap_or_default()
+        }));
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn listing_jsonl(rows: &[ListingRow]) -> AppResult<String> {
+    let mut out = String::new();
+    for row in rows {
+        let line = serde_json::to_string(row)
+            .map_err(|e| AppError::Task(format!("serializing listing failed: {e}")))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Writes the sample table (already filtered by the UI) to `dest_path` for analysis elsewhere.
+#[tauri::command]
+pub async fn export_listing(
+    rows: Vec<ListingRow>,
+    format: ListingExportFormat,
+    dest_path: String,
+) -> AppResult<ListingExportReport> {
+    let dest = dest_path.trim();
+    if dest.is_empty() {
+        return Err(AppError::Invalid("destination path is empty".into()));
+    }
+    let dest = PathBuf::from(dest);
+    read_only::ensure_writable(&dest, "listing export")?;
+    spawn_blocking(move || {
+        let body = match format {
+            ListingExportFormat::Csv => listing_csv(&rows),
+            ListingExportFormat::Jsonl => listing_jsonl(&rows)?,
+        };
+        std::fs::write(&dest, body)?;
+        Ok(ListingExportReport {
+            path: dest.display().to_string(),
+            count: rows.len().min(u32::MAX as usize) as u32,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
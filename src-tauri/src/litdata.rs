@@ -1,4 +1,3 @@
-use hex::encode as hex_encode;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
@@ -7,31 +6,33 @@ use std::{
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
-use tauri::async_runtime::spawn_blocking;
+use tauri::{async_runtime::spawn_blocking, AppHandle, Emitter};
 
 use crate::audio;
+use crate::dataset_profile;
+use crate::decode_profile::{self, DecodeOverride};
+use crate::decoder_plugins;
 
 use crate::app_error::{AppError, AppResult};
 use crate::ipc_types::{
     ChunkSummary, FieldMeta, FieldPreview, IndexSummary, ItemMeta, OpenLeafResponse,
-    PreparedFileResponse,
+    PreparedFileResponse, SavedLeafResponse,
 };
+use crate::magic;
+use crate::messages::{self, Message};
+use crate::metadata;
 use crate::open_with;
+use crate::preview_kind::PreviewKind;
+use crate::preview_limits::PreviewLimits;
+use crate::resource_monitor::MemoryCache;
+use crate::save_leaf;
+use crate::temp_names::{self, sanitize};
 
 const PREVIEW_BYTES: usize = 16 * 1024;
-const PREVIEW_TEXT_CHARS: usize = 8 * 1024;
 const MAX_CACHE_BYTES: usize = 128 * 1024 * 1024;
-
-fn preview_utf8_text(data: &[u8]) -> Option<String> {
-    let raw = match std::str::from_utf8(data) {
-        Ok(text) => text,
-        Err(err) if err.error_len().is_none() => {
-            std::str::from_utf8(&data[..err.valid_up_to()]).ok()?
-        }
-        Err(_) => return None,
-    };
-    Some(raw.chars().take(PREVIEW_TEXT_CHARS).collect())
-}
+const MAX_VALIDATION_ISSUES: usize = 50;
+/// Largest string field `get_item_json` decodes.
+const MAX_OPEN_BYTES: usize = 256 * 1024 * 1024;
 
 #[derive(Clone, Default)]
 pub struct ChunkCache {
@@ -52,6 +53,30 @@ impl ChunkCache {
     }
 }
 
+impl MemoryCache for ChunkCache {
+    fn usage(&self) -> (usize, usize) {
+        self.inner
+            .lock()
+            .map(|guard| (guard.values().map(Vec::len).sum(), guard.len()))
+            .unwrap_or_default()
+    }
+
+    /// Chunks are not ordered by use, so any are dropped; each is read again on demand.
+    fn shrink_by(&self, bytes: usize) -> usize {
+        let Ok(mut guard) = self.inner.lock() else {
+            return 0;
+        };
+        let mut freed = 0usize;
+        while freed < bytes {
+            let Some(key) = guard.keys().next().cloned() else {
+                break;
+            };
+            freed += guard.remove(&key).map_or(0, |data| data.len());
+        }
+        freed
+    }
+}
+
 fn read_le_u32(bytes: &[u8]) -> AppResult<u32> {
     let buf: [u8; 4] = bytes.try_into().map_err(|_| AppError::MalformedChunk)?;
     Ok(u32::from_le_bytes(buf))
@@ -318,15 +343,54 @@ fn parse_index_file(path: &Path) -> AppResult<ParsedIndex> {
     })
 }
 
+/// Resolved index path and per-field `data_format` for an index, chunk file, or dataset root.
+pub fn litdata_data_format(path: &Path) -> AppResult<(String, Vec<String>)> {
+    let parsed = parse_index(path)?;
+    Ok((
+        parsed.source.display().to_string(),
+        parsed.config.data_format.unwrap_or_default(),
+    ))
+}
+
+/// Dataset directory, the item's index across all chunks (its index in a `StreamingDataset` over
+/// the same directory), and the per-field `data_format`.
+pub fn streaming_position(
+    index_path: &Path,
+    chunk_filename: &str,
+    item_index: u32,
+) -> AppResult<(PathBuf, u64, Vec<String>)> {
+    let parsed = parse_index(index_path)?;
+    let trimmed = chunk_filename.trim();
+    let mut before = 0u64;
+    for chunk in &parsed.chunks {
+        if chunk.filename == trimmed {
+            if item_index >= chunk.chunk_size {
+                return Err(AppError::Invalid("item index out of range".into()));
+            }
+            return Ok((
+                parsed.root_dir,
+                before + item_index as u64,
+                parsed.config.data_format.unwrap_or_default(),
+            ));
+        }
+        before += chunk.chunk_size as u64;
+    }
+    Err(AppError::Missing(format!("unknown chunk: {trimmed}")))
+}
+
 #[tauri::command]
 pub async fn load_index(index_path: String) -> AppResult<IndexSummary> {
-    let path = PathBuf::from(index_path);
-    spawn_blocking(move || load_index_sync(path))
-        .await
-        .map_err(|e| AppError::Task(e.to_string()))?
+    spawn_blocking(move || {
+        // A broken profile file must not keep the dataset from opening; its error is shown
+        // through `get_dataset_profile`.
+        let _ = dataset_profile::load(&index_path);
+        load_index_sync(PathBuf::from(index_path))
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn load_index_sync(index_path: PathBuf) -> AppResult<IndexSummary> {
+pub fn load_index_sync(index_path: PathBuf) -> AppResult<IndexSummary> {
     parse_index(&index_path).and_then(
         |ParsedIndex {
              root_dir,
@@ -533,7 +597,7 @@ pub async fn list_chunk_items(
         .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn list_chunk_items_sync(
+pub fn list_chunk_items_sync(
     index_path: PathBuf,
     chunk_filename: String,
     cache: &ChunkCache,
@@ -585,15 +649,21 @@ pub async fn peek_field(
     chunk_filename: String,
     item_index: u32,
     field_index: usize,
+    offset: Option<u64>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
     cache: tauri::State<'_, ChunkCache>,
 ) -> AppResult<FieldPreview> {
     let cache_handle = (*cache).clone();
+    let limits = PreviewLimits::new(text_chars, hex_bytes);
     spawn_blocking(move || {
         preview_field(
             &index_path,
             &chunk_filename,
             item_index,
             field_index,
+            offset.unwrap_or(0),
+            limits,
             &cache_handle,
         )
     })
@@ -601,11 +671,15 @@ pub async fn peek_field(
     .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn preview_field(
+/// Previews `offset..offset + PREVIEW_BYTES` of a field, or a longer window when `limits` ask for
+/// more text.
+pub fn preview_field(
     index_path: &str,
     chunk_filename: &str,
     item_index: u32,
     field_index: usize,
+    offset: u64,
+    limits: PreviewLimits,
     cache: &ChunkCache,
 ) -> AppResult<FieldPreview> {
     let parsed = parse_index(Path::new(index_path))?;
@@ -616,23 +690,191 @@ fn preview_field(
         item_index,
         field_index,
         fmt.len(),
-        Some(PREVIEW_BYTES),
+        offset,
+        Some(limits.window(PREVIEW_BYTES)),
     )?;
-    let preview_text = preview_utf8_text(&data);
+    let (preview_text, text_length) = limits.text(&data).unzip();
     let is_binary = preview_text.is_none();
     let guessed_ext = guess_ext(fmt.get(field_index), &data);
-    let hex_snippet = hex_encode(data.iter().take(48).copied().collect::<Vec<u8>>());
-    Ok(FieldPreview {
+    let mut preview = FieldPreview {
         preview_text,
-        hex_snippet,
+        text_length,
+        hex_snippet: limits.hex(&data),
         guessed_ext,
         is_binary,
-        size,
+        size: u64::from(size),
+        metadata: metadata::extract(&data),
+        plugin: None,
+        kind: PreviewKind::Binary,
+        compression: None,
+    };
+    if let Some(rule) = decode_override(index_path, &fmt, field_index) {
+        rule.apply_to_preview(&data, &mut preview);
+    }
+    decoder_plugins::apply(&data, &mut preview);
+    preview.kind = PreviewKind::detect(&data, &preview);
+    Ok(preview)
+}
+
+/// The user's decode override for a field, keyed by field index or data format.
+fn decode_override(index_path: &str, fmt: &[String], field_index: usize) -> Option<DecodeOverride> {
+    let format = fmt.get(field_index).map_or("", String::as_str);
+    decode_profile::lookup(index_path, &[&field_index.to_string(), format])
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LitdataItemJson {
+    pub item_index: u32,
+    /// The decoded item, nested per `data_spec` when it describes a dict or list; binary fields
+    /// hold a `{fieldIndex, dataFormat, size, ext}` summary.
+    pub item: serde_json::Value,
+    /// Fields that were summarized rather than decoded.
+    pub binary_fields: Vec<usize>,
+}
+
+/// A field decoded the way litdata's serializer for `format` yields it, or `None` for formats
+/// that deserialize to binary objects (images, arrays, tensors, pickles, raw bytes).
+fn decode_json_value(format: &str, data: &[u8]) -> Option<serde_json::Value> {
+    use serde_json::Value;
+    let value = match format {
+        "str" | "string" => Value::String(String::from_utf8_lossy(data).into_owned()),
+        "int" => match data.len() {
+            8 => Value::from(i64::from_le_bytes(data.try_into().ok()?)),
+            4 => Value::from(i32::from_le_bytes(data.try_into().ok()?)),
+            _ => return None,
+        },
+        "float" => match data.len() {
+            8 => Value::from(f64::from_le_bytes(data.try_into().ok()?)),
+            4 => Value::from(f32::from_le_bytes(data.try_into().ok()?) as f64),
+            _ => return None,
+        },
+        "bool" => Value::Bool(*data.first()? != 0),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Rebuilds the container structure of a torch pytree spec (as `treespec_dumps` writes it) around
+/// the decoded leaves. `None` when the spec is not understood or the leaf count does not match.
+fn rebuild_from_spec(
+    spec: &serde_json::Value,
+    leaves: &mut std::vec::IntoIter<serde_json::Value>,
+) -> Option<serde_json::Value> {
+    use serde_json::Value;
+    let Some(kind) = spec.get("type").and_then(Value::as_str) else {
+        return leaves.next();
+    };
+    let children = spec.get("children_spec")?.as_array()?;
+    if kind == "builtins.dict" || kind == "collections.OrderedDict" {
+        let keys: Vec<Value> = serde_json::from_str(spec.get("context")?.as_str()?).ok()?;
+        if keys.len() != children.len() {
+            return None;
+        }
+        let mut map = serde_json::Map::new();
+        for (key, child) in keys.iter().zip(children) {
+            let key = key.as_str().map_or_else(|| key.to_string(), str::to_string);
+            map.insert(key, rebuild_from_spec(child, leaves)?);
+        }
+        return Some(Value::Object(map));
+    }
+    children
+        .iter()
+        .map(|child| rebuild_from_spec(child, leaves))
+        .collect::<Option<Vec<_>>>()
+        .map(Value::Array)
+}
+
+/// Every field of one item decoded into a single JSON value, honoring each field's
+/// `data_format` and, when it describes a dict or list, the index's `data_spec`.
+#[tauri::command]
+pub async fn get_item_json(
+    index_path: String,
+    chunk_filename: String,
+    item_index: u32,
+    cache: tauri::State<'_, ChunkCache>,
+) -> AppResult<LitdataItemJson> {
+    let cache_handle = (*cache).clone();
+    spawn_blocking(move || {
+        get_item_json_sync(
+            Path::new(&index_path),
+            &chunk_filename,
+            item_index,
+            &cache_handle,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn get_item_json_sync(
+    index_path: &Path,
+    chunk_filename: &str,
+    item_index: u32,
+    cache: &ChunkCache,
+) -> AppResult<LitdataItemJson> {
+    let parsed = parse_index(index_path)?;
+    let fmt = parsed.config.data_format.clone().unwrap_or_default();
+    let access = load_chunk_access(&parsed, chunk_filename, cache)?;
+
+    let mut leaves = Vec::with_capacity(fmt.len());
+    let mut binary_fields = Vec::new();
+    for (field_index, format) in fmt.iter().enumerate() {
+        let format_lower = format.trim().to_lowercase();
+        let is_text = matches!(format_lower.as_str(), "str" | "string");
+        // Scalars are small; for binary fields the head is enough to sniff an extension.
+        let limit = match format_lower.as_str() {
+            "str" | "string" => Some(MAX_OPEN_BYTES),
+            "int" | "float" | "bool" => None,
+            _ => Some(64),
+        };
+        let (data, size) = read_field_bytes(&access, item_index, field_index, fmt.len(), 0, limit)?;
+        if is_text && size as usize > MAX_OPEN_BYTES {
+            return Err(AppError::Invalid(format!(
+                "field {field_index} is too large to decode ({size} bytes, max {MAX_OPEN_BYTES})"
+            )));
+        }
+        let value = match decode_json_value(&format_lower, &data) {
+            Some(value) => value,
+            None => {
+                binary_fields.push(field_index);
+                serde_json::json!({
+                    "fieldIndex": field_index,
+                    "dataFormat": format,
+                    "size": size,
+                    "ext": guess_ext(Some(format), &data).unwrap_or_else(|| "bin".into()),
+                })
+            }
+        };
+        leaves.push(value);
+    }
+
+    let spec = parsed
+        .config
+        .data_spec
+        .as_deref()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|v| match v {
+            // `treespec_dumps` writes `[protocol, spec]`.
+            serde_json::Value::Array(mut parts) if parts.len() == 2 => Some(parts.remove(1)),
+            _ => None,
+        });
+    let item = spec
+        .and_then(|spec| {
+            let mut iter = leaves.clone().into_iter();
+            rebuild_from_spec(&spec, &mut iter).filter(|_| iter.next().is_none())
+        })
+        .unwrap_or(serde_json::Value::Array(leaves));
+    Ok(LitdataItemJson {
+        item_index,
+        item,
+        binary_fields,
     })
 }
 
 #[tauri::command]
 pub async fn open_leaf(
+    app: AppHandle,
     index_path: String,
     chunk_filename: String,
     item_index: u32,
@@ -644,6 +886,7 @@ pub async fn open_leaf(
     spawn_blocking(move || {
         let path = PathBuf::from(&index_path);
         open_leaf_inner(
+            &app,
             &path,
             &chunk_filename,
             item_index,
@@ -689,7 +932,7 @@ fn prepare_audio_preview_inner(
     let parsed = parse_index(index_path)?;
     let fmt = parsed.config.data_format.clone().unwrap_or_default();
     let access = load_chunk_access(&parsed, chunk_filename, cache)?;
-    let (data, size) = read_field_bytes(&access, item_index, field_index, fmt.len(), None)?;
+    let (data, size) = read_field_bytes(&access, item_index, field_index, fmt.len(), 0, None)?;
     let ext = guess_ext(fmt.get(field_index), &data).unwrap_or_else(|| "bin".into());
 
     let temp_dir = std::env::temp_dir().join("dataset-inspector");
@@ -701,12 +944,11 @@ fn prepare_audio_preview_inner(
         field_index
     );
 
-    let mut out = temp_dir.join(format!("{base_name}.{ext}"));
-    fs::write(&out, &data)?;
+    let mut out = temp_names::write(&temp_dir, &base_name, &ext, &data)?;
 
     let mut ext = ext;
     if ext == "sph" {
-        let wav_out = temp_dir.join(format!("{base_name}.wav"));
+        let wav_out = out.with_extension("wav");
         audio::write_sph_as_wav_with_fallback(&data, &out, &wav_out)
             .map_err(|e| AppError::Invalid(format!("sph decode failed: {e}")))?;
         out = wav_out;
@@ -715,24 +957,69 @@ fn prepare_audio_preview_inner(
 
     Ok(PreparedFileResponse {
         path: out.display().to_string(),
-        size,
+        size: u64::from(size),
         ext,
     })
 }
 
-fn open_leaf_inner(
+/// A field's bytes after any decode override, its stored size, and the extension to write it as.
+pub fn decoded_leaf(
     index_path: &Path,
     chunk_filename: &str,
     item_index: u32,
     field_index: usize,
-    opener_app_path: Option<&str>,
     cache: &ChunkCache,
-) -> AppResult<OpenLeafResponse> {
+) -> AppResult<(Vec<u8>, u64, String)> {
     let parsed = parse_index(index_path)?;
     let fmt = parsed.config.data_format.clone().unwrap_or_default();
     let access = load_chunk_access(&parsed, chunk_filename, cache)?;
-    let (data, size) = read_field_bytes(&access, item_index, field_index, fmt.len(), None)?;
-    let ext = guess_ext(fmt.get(field_index), &data).unwrap_or_else(|| "bin".into());
+    let (data, size) = read_field_bytes(&access, item_index, field_index, fmt.len(), 0, None)?;
+    let (data, ext) = match decode_override(&index_path.to_string_lossy(), &fmt, field_index) {
+        Some(rule) => rule.decode_for_open(data),
+        None => {
+            let ext = guess_ext(fmt.get(field_index), &data).unwrap_or_else(|| "bin".into());
+            (data, ext)
+        }
+    };
+    Ok((data, u64::from(size), ext))
+}
+
+/// Writes a field to `destination_path` instead of opening it from the temp dir.
+#[tauri::command]
+pub async fn save_leaf_as(
+    index_path: String,
+    chunk_filename: String,
+    item_index: u32,
+    field_index: usize,
+    destination_path: String,
+    cache: tauri::State<'_, ChunkCache>,
+) -> AppResult<SavedLeafResponse> {
+    let cache_handle = (*cache).clone();
+    spawn_blocking(move || {
+        let (data, _size, ext) = decoded_leaf(
+            Path::new(&index_path),
+            &chunk_filename,
+            item_index,
+            field_index,
+            &cache_handle,
+        )?;
+        save_leaf::write_to(&destination_path, &data, ext)
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn open_leaf_inner(
+    app: &AppHandle,
+    index_path: &Path,
+    chunk_filename: &str,
+    item_index: u32,
+    field_index: usize,
+    opener_app_path: Option<&str>,
+    cache: &ChunkCache,
+) -> AppResult<OpenLeafResponse> {
+    let (data, size, ext) =
+        decoded_leaf(index_path, chunk_filename, item_index, field_index, cache)?;
     let temp_dir = std::env::temp_dir().join("dataset-inspector");
     fs::create_dir_all(&temp_dir)?;
     let base_name = format!(
@@ -742,13 +1029,12 @@ fn open_leaf_inner(
         field_index
     );
 
-    let mut out = temp_dir.join(format!("{base_name}.{ext}"));
-    fs::write(&out, &data)?;
+    let mut out = temp_names::write(&temp_dir, &base_name, &ext, &data)?;
 
     // Default `.sph` support: decode to a WAV and open that.
     let mut ext = ext;
     if ext == "sph" {
-        let wav_out = temp_dir.join(format!("{base_name}.wav"));
+        let wav_out = out.with_extension("wav");
         match audio::write_sph_as_wav_with_fallback(&data, &out, &wav_out) {
             Ok(()) => {
                 out = wav_out;
@@ -756,42 +1042,32 @@ fn open_leaf_inner(
             }
             Err(err) => {
                 // Fallback to the raw `.sph` file and let the user pick an opener if desired.
-                let base = format!("{} ({} bytes)", out.display(), size);
+                let base = messages::text(
+                    Message::FileWithSize,
+                    &[("path", &out.display()), ("size", &size)],
+                );
                 return Ok(OpenLeafResponse {
                     path: out.display().to_string(),
                     size,
                     ext,
                     opened: false,
                     needs_opener: true,
-                    message: format!(
-                        "{base} · sph decode failed: {err} · choose an app to open it"
-                    ),
+                    message: base + &messages::text(Message::SphDecodeFailed, &[("error", &err)]),
                 });
             }
         }
     }
 
-    let mut opened = false;
-    let mut open_error = None::<String>;
-    if let Some(app_path) = opener_app_path {
-        match open_with::open_with_app_detached(&out, app_path) {
-            Ok(()) => opened = true,
-            Err(err) => open_error = Some(err),
-        }
-    }
-    if !opened {
-        if let Err(err) = open::that_detached(&out) {
-            open_error = Some(err.to_string());
-        } else {
-            opened = true;
-        }
-    }
+    let opened = open_with::open_extracted(app, &out, opener_app_path).is_ok();
 
-    let base = format!("{} ({} bytes)", out.display(), size);
+    let base = messages::text(
+        Message::FileWithSize,
+        &[("path", &out.display()), ("size", &size)],
+    );
     let mut message = base;
-    let needs_opener = !opened && open_error.is_some();
+    let needs_opener = !opened;
     if needs_opener {
-        message.push_str(" · no default app found, choose an app to open it");
+        message.push_str(&messages::text(Message::NoDefaultApp, &[]));
     }
 
     Ok(OpenLeafResponse {
@@ -809,6 +1085,7 @@ fn read_field_bytes(
     item_index: u32,
     field_index: usize,
     format_len: usize,
+    skip: u64,
     limit: Option<usize>,
 ) -> AppResult<(Vec<u8>, u32)> {
     let header_len = format_len * 4;
@@ -839,8 +1116,10 @@ fn read_field_bytes(
     let mut cursor = start as u64 + header_len as u64;
     for (idx, sz) in sizes.iter().enumerate() {
         if idx == field_index {
-            let desired = limit.map(|l| l.min(*sz as usize)).unwrap_or(*sz as usize);
-            let data = access.read_exact_at(cursor, desired)?;
+            let skip = skip.min(*sz as u64);
+            let available = (*sz as u64 - skip) as usize;
+            let desired = limit.map(|l| l.min(available)).unwrap_or(available);
+            let data = access.read_exact_at(cursor + skip, desired)?;
             return Ok((data, *sz));
         }
         cursor += *sz as u64;
@@ -848,12 +1127,242 @@ fn read_field_bytes(
     Err(AppError::MalformedChunk)
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkValidation {
+    filename: String,
+    exists: bool,
+    expected_items: u32,
+    header_items: Option<u32>,
+    expected_bytes: u64,
+    actual_bytes: Option<u64>,
+    sampled_item: Option<u32>,
+    issues: Vec<String>,
+    ok: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LitdataValidationReport {
+    index_path: String,
+    chunks_total: usize,
+    chunks_ok: usize,
+    chunks: Vec<ChunkValidation>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LitdataValidateProgress {
+    index_path: String,
+    completed: usize,
+    chunks_total: usize,
+    filename: String,
+}
+
+fn push_issue(issues: &mut Vec<String>, issue: String) {
+    if issues.len() < MAX_VALIDATION_ISSUES {
+        issues.push(issue);
+    }
+}
+
+/// Opens a chunk for validation without touching the shared cache, so a full
+/// pass over a large dataset does not evict what the user is browsing.
+fn open_chunk_uncached(parsed: &ParsedIndex, chunk_path: &Path) -> AppResult<(ChunkAccess, u64)> {
+    match parsed.config.compression.as_ref().map(|c| c.to_lowercase()) {
+        Some(ref c) if c == "zstd" => {
+            let file = File::open(chunk_path)?;
+            let mut decoder = zstd::stream::Decoder::new(file)?;
+            let mut buf = Vec::new();
+            decoder
+                .read_to_end(&mut buf)
+                .map_err(|e| AppError::Invalid(format!("decompressing chunk: {e}")))?;
+            let len = buf.len() as u64;
+            Ok((ChunkAccess::Memory(buf), len))
+        }
+        Some(other) => Err(AppError::UnsupportedCompression(other)),
+        None => {
+            let len = fs::metadata(chunk_path)?.len();
+            Ok((ChunkAccess::File(chunk_path.to_path_buf()), len))
+        }
+    }
+}
+
+fn check_chunk_layout(
+    access: &ChunkAccess,
+    data_len: u64,
+    format_len: usize,
+    report: &mut ChunkValidation,
+) -> AppResult<()> {
+    let (num_items, offsets) = parse_offsets(access)?;
+    report.header_items = Some(num_items);
+    if num_items != report.expected_items {
+        push_issue(
+            &mut report.issues,
+            format!(
+                "item count mismatch: index says {}, chunk header says {num_items}",
+                report.expected_items
+            ),
+        );
+    }
+
+    let data_start = 4 + (num_items as u64 + 1) * 4;
+    let first = offsets.first().copied().unwrap_or(0) as u64;
+    if first != data_start {
+        push_issue(
+            &mut report.issues,
+            format!("first item offset {first} does not follow the offset table ({data_start})"),
+        );
+    }
+    let last = offsets.last().copied().unwrap_or(0) as u64;
+    if last != data_len {
+        push_issue(
+            &mut report.issues,
+            format!("last item offset {last} does not match the chunk size {data_len}"),
+        );
+    }
+    for (idx, pair) in offsets.windows(2).enumerate() {
+        if pair[1] < pair[0] {
+            push_issue(
+                &mut report.issues,
+                format!(
+                    "item {idx}: offsets are not monotonic ({} > {})",
+                    pair[0], pair[1]
+                ),
+            );
+        }
+    }
+    if num_items == 0 || last > data_len {
+        return Ok(());
+    }
+
+    let sample = num_items / 2;
+    report.sampled_item = Some(sample);
+    let (start, end) = (offsets[sample as usize], offsets[sample as usize + 1]);
+    if end < start {
+        return Ok(());
+    }
+    let item = access.read_exact_at(start as u64, (end - start) as usize)?;
+    let header_len = format_len * 4;
+    if item.len() < header_len {
+        push_issue(
+            &mut report.issues,
+            format!("item {sample}: shorter than its field-size header"),
+        );
+        return Ok(());
+    }
+    let mut payload = header_len as u64;
+    for j in 0..format_len {
+        payload += read_le_u32(&item[j * 4..j * 4 + 4])? as u64;
+    }
+    if payload != item.len() as u64 {
+        push_issue(
+            &mut report.issues,
+            format!(
+                "item {sample}: field sizes sum to {payload} bytes but the item spans {}",
+                item.len()
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn validate_chunk(parsed: &ParsedIndex, chunk: &RawChunk, format_len: usize) -> ChunkValidation {
+    let mut report = ChunkValidation {
+        filename: chunk.filename.clone(),
+        exists: false,
+        expected_items: chunk.chunk_size,
+        header_items: None,
+        expected_bytes: chunk.chunk_bytes,
+        actual_bytes: None,
+        sampled_item: None,
+        issues: Vec::new(),
+        ok: false,
+    };
+    let chunk_path = parsed.root_dir.join(&chunk.filename);
+    if !chunk_path.exists() {
+        report
+            .issues
+            .push(format!("chunk file not found: {}", chunk_path.display()));
+        return report;
+    }
+    report.exists = true;
+
+    let (access, data_len) = match open_chunk_uncached(parsed, &chunk_path) {
+        Ok(opened) => opened,
+        Err(err) => {
+            report.issues.push(err.to_string());
+            return report;
+        }
+    };
+    report.actual_bytes = Some(data_len);
+    if data_len != chunk.chunk_bytes {
+        push_issue(
+            &mut report.issues,
+            format!(
+                "size mismatch: index says {} bytes, chunk has {data_len}",
+                chunk.chunk_bytes
+            ),
+        );
+    }
+    if let Err(err) = check_chunk_layout(&access, data_len, format_len, &mut report) {
+        push_issue(&mut report.issues, format!("read failed: {err}"));
+    }
+    report.ok = report.issues.is_empty();
+    report
+}
+
+#[tauri::command]
+pub async fn validate_index(
+    app: AppHandle,
+    index_path: String,
+) -> AppResult<LitdataValidationReport> {
+    let path = PathBuf::from(index_path);
+    spawn_blocking(move || validate_index_sync(&app, path))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+pub fn validate_index_sync(
+    app: &AppHandle,
+    index_path: PathBuf,
+) -> AppResult<LitdataValidationReport> {
+    let parsed = parse_index(&index_path)?;
+    let format_len = parsed
+        .config
+        .data_format
+        .as_ref()
+        .map(|v| v.len())
+        .unwrap_or(0);
+    let index_display = parsed.source.display().to_string();
+    let chunks_total = parsed.chunks.len();
+    let mut chunks = Vec::with_capacity(chunks_total);
+    for (idx, chunk) in parsed.chunks.iter().enumerate() {
+        chunks.push(validate_chunk(&parsed, chunk, format_len));
+        let _ = app.emit(
+            "litdata://validate-progress",
+            LitdataValidateProgress {
+                index_path: index_display.clone(),
+                completed: idx + 1,
+                chunks_total,
+                filename: chunk.filename.clone(),
+            },
+        );
+    }
+    let chunks_ok = chunks.iter().filter(|c| c.ok).count();
+    Ok(LitdataValidationReport {
+        index_path: index_display,
+        chunks_total,
+        chunks_ok,
+        chunks,
+    })
+}
+
 fn guess_ext(data_format: Option<&String>, data: &[u8]) -> Option<String> {
     if let Some(fmt) = data_format {
         let fmt_lower = fmt.to_lowercase();
         if fmt_lower == "bytes" || fmt_lower == "bin" {
-            if let Some(magic) = detect_magic_ext(data) {
-                return Some(magic);
+            if let Some(ext) = magic::detect_ext(data) {
+                return Some(ext);
             }
             return Some("bin".into());
         }
@@ -887,8 +1396,8 @@ fn guess_ext(data_format: Option<&String>, data: &[u8]) -> Option<String> {
             return Some((*ext).into());
         }
         if fmt_lower == "audio" {
-            if let Some(magic) = detect_magic_ext(data) {
-                return Some(magic);
+            if let Some(ext) = magic::detect_ext(data) {
+                return Some(ext);
             }
             return Some("wav".into());
         }
@@ -902,8 +1411,8 @@ fn guess_ext(data_format: Option<&String>, data: &[u8]) -> Option<String> {
             return Some("flac".into());
         }
     }
-    if let Some(magic_ext) = detect_magic_ext(data) {
-        return Some(magic_ext);
+    if let Some(ext) = magic::detect_ext(data) {
+        return Some(ext);
     }
     if std::str::from_utf8(data)
         .map(|s| s.trim().len() > 0)
@@ -913,31 +1422,3 @@ fn guess_ext(data_format: Option<&String>, data: &[u8]) -> Option<String> {
     }
     infer::get(data).map(|t| t.extension().to_string())
 }
-
-fn sanitize(input: &str) -> String {
-    input
-        .chars()
-        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
-        .collect()
-}
-
-fn detect_magic_ext(data: &[u8]) -> Option<String> {
-    // NIST SPHERE audio files start with an ASCII "NIST_1A" marker.
-    // Example: "NIST_1A\n   1024\n"
-    if audio::is_sphere_file(data) {
-        return Some("sph".into());
-    }
-    if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE" {
-        return Some("wav".into());
-    }
-    if data.len() >= 3 && &data[0..3] == b"ID3" {
-        return Some("mp3".into());
-    }
-    if data.len() >= 2 && data[0] == 0xFF && (data[1] & 0xE0) == 0xE0 {
-        return Some("mp3".into());
-    }
-    if data.len() >= 4 && &data[0..4] == b"fLaC" {
-        return Some("flac".into());
-    }
-    None
-}
@@ -0,0 +1,117 @@
+use crate::audio;
+
+/// Extension for the container a payload's leading bytes identify, for the formats the
+/// `infer` crate misses or gets too coarse (ISO-BMFF brands, EBML doc types, SPHERE audio).
+pub fn detect_ext(data: &[u8]) -> Option<String> {
+    // NIST SPHERE audio files start with an ASCII "NIST_1A" marker.
+    // Example: "NIST_1A\n   1024\n"
+    if audio::is_sphere_file(data) {
+        return Some("sph".into());
+    }
+    if data.starts_with(b"RIFF") {
+        return match data.get(8..12)? {
+            b"WAVE" => Some("wav".into()),
+            b"WEBP" => Some("webp".into()),
+            b"AVI " => Some("avi".into()),
+            _ => None,
+        };
+    }
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some("png".into());
+    }
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return Some("jpg".into());
+    }
+    if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        return Some("gif".into());
+    }
+    if data.get(4..8) == Some(b"ftyp".as_slice()) {
+        return ftyp_ext(data);
+    }
+    if data.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        // The EBML header names the doc type within its first few dozen bytes.
+        let header = &data[..data.len().min(64)];
+        let is_webm = header.windows(4).any(|w| w == b"webm");
+        return Some(if is_webm { "webm" } else { "mkv" }.into());
+    }
+    if data.starts_with(b"OggS") {
+        return Some("ogg".into());
+    }
+    if data.starts_with(b"ID3") {
+        return Some("mp3".into());
+    }
+    if data.len() >= 2 && data[0] == 0xFF && (data[1] & 0xE0) == 0xE0 {
+        return Some("mp3".into());
+    }
+    if data.starts_with(b"fLaC") {
+        return Some("flac".into());
+    }
+    if data.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+        return Some("zst".into());
+    }
+    if data.starts_with(&[0x1F, 0x8B]) {
+        return Some("gz".into());
+    }
+    None
+}
+
+/// `detect_ext`, falling back to `infer` for everything else it knows.
+pub fn sniff_ext(data: &[u8]) -> Option<String> {
+    detect_ext(data).or_else(|| infer::get(data).map(|t| t.extension().to_string()))
+}
+
+/// Extension for an ISO-BMFF file from the major brand of its `ftyp` box, checking the
+/// compatible brands for HEIF images that declare a generic major brand.
+fn ftyp_ext(data: &[u8]) -> Option<String> {
+    let major = data.get(8..12)?;
+    let ext = match major {
+        b"avif" | b"avis" => "avif",
+        b"heic" | b"heix" | b"heim" | b"heis" | b"hevc" | b"hevx" => "heic",
+        b"mif1" | b"msf1" => {
+            let box_size = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?) as usize;
+            let brands = data
+                .get(16..box_size.min(data.len()))
+                .unwrap_or_default()
+                .chunks_exact(4)
+                .collect::<Vec<_>>();
+            if brands.contains(&b"avif".as_slice()) {
+                "avif"
+            } else if brands
+                .iter()
+                .any(|b| b.starts_with(b"hei") || b.starts_with(b"hev"))
+            {
+                "heic"
+            } else {
+                "heif"
+            }
+        }
+        b"qt  " => "mov",
+        b"M4A " | b"M4B " => "m4a",
+        b"M4V " => "m4v",
+        b if b.starts_with(b"3g2") => "3g2",
+        b if b.starts_with(b"3gp") => "3gp",
+        _ => "mp4",
+    };
+    Some(ext.into())
+}
+
+/// Whether `data` starts like an HTML document, as error and sign-in pages served in place of
+/// the requested file do.
+pub fn looks_like_html(data: &[u8]) -> bool {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    let start = data
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    let head = &data[start..data.len().min(start + 16)];
+    let head = head.to_ascii_lowercase();
+    [
+        b"<!doctype html".as_slice(),
+        b"<html",
+        b"<head",
+        b"<body",
+        b"<title",
+    ]
+    .iter()
+    .any(|tag| head.starts_with(tag))
+}
@@ -1,90 +1,209 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod access_log;
+mod annotations;
 mod app_error;
+mod app_menu;
+mod archive_listing;
 mod audio;
+mod automation;
+mod checksum;
+mod croissant;
+mod dataset_docs;
+mod dataset_profile;
+mod dataset_tree;
+mod decode_profile;
+mod decoder_plugins;
+mod decompress;
+mod dir_watch;
+mod downloads;
+mod embeddings;
+mod gguf;
+mod gzip_index;
+mod hf_parquet;
+mod http_cache;
+mod http_date;
+mod http_limit;
 mod huggingface;
+mod image_flags;
+mod installed_apps;
 mod ipc_types;
+mod jobs;
+mod listing_export;
 mod litdata;
+mod magic;
+mod mcap;
+mod media_stream;
+mod message_archives;
+mod messages;
+mod metadata;
 mod mosaicml;
+mod multipart;
+mod network_fs;
+mod offline;
+mod onnx;
+mod open_many;
 mod open_with;
+mod parquet;
+mod parquet_query;
+mod path_glob;
+mod pickle;
+mod pii_scan;
+mod preview_kind;
+mod preview_limits;
+mod quarantine;
+mod read_only;
+mod remote_fs;
+mod report;
+mod resource_monitor;
+mod robot_log;
+mod robot_log_export;
+mod ros_msg;
+mod rosbag;
+mod sample_location;
+mod save_leaf;
+mod scan_cache;
+mod scan_checkpoint;
+mod schema;
+mod settings;
+mod sftp;
+mod shard_balance;
+mod snippets;
+mod sql_query;
+mod supervisor;
+mod symlinks;
+mod tabular;
+mod tabular_repos;
+mod tail_preview;
+mod temp_names;
+mod throttle;
+mod thumbnails;
+mod torch_archive;
+mod trusted_hosts;
+mod updater;
+mod warehouse;
 mod webdataset;
+mod webdav;
+mod workspaces;
 mod zenodo;
+mod zip_format;
 
-#[cfg(all(desktop, target_os = "macos"))]
-use tauri::menu::{MenuBuilder, SubmenuBuilder};
-#[cfg(desktop)]
-use tauri::Emitter;
+use tauri::Manager;
 
-use huggingface::hf_open_field;
-use huggingface::{hf_dataset_preview, HfClient};
+use access_log::{export_access_log, query_access_log};
+use annotations::{
+    annotate_sample, export_annotations, list_annotations, remove_annotation, AnnotationStore,
+};
+use automation::{get_automation_endpoint, set_automation_endpoint};
+use croissant::{compare_declared_schema, read_declared_metadata};
+use dataset_docs::local_dataset_docs;
+use dataset_profile::get_dataset_profile;
+use dataset_tree::scan_tree;
+use decode_profile::{get_decode_profile, set_decode_profile};
+use decoder_plugins::{list_decoder_plugins, reload_decoder_plugins};
+use dir_watch::{unwatch_dataset_dir, watch_dataset_dir, DirWatchRegistry};
+use gguf::inspect_gguf_file;
+use http_limit::{get_max_requests_per_host, set_max_requests_per_host};
+use huggingface::{hf_dataset_docs, hf_field_value, hf_open_field, hf_save_field_as};
+use huggingface::{hf_dataset_preview, hf_get_row, HfClient};
+use installed_apps::list_installed_apps;
+use jobs::JobRegistry;
+use listing_export::export_listing;
 use litdata::{
-    list_chunk_items, load_chunk_list, load_index, open_leaf, peek_field, prepare_audio_preview,
-    ChunkCache,
+    get_item_json, list_chunk_items, load_chunk_list, load_index, open_leaf, peek_field,
+    prepare_audio_preview, save_leaf_as, validate_index, ChunkCache,
 };
+use media_stream::{MediaStore, MEDIA_SCHEME};
+use message_archives::{mbox_page, warc_page};
+use messages::{get_message_language, list_message_languages, set_message_language};
 use mosaicml::{
-    mosaicml_list_samples, mosaicml_load_index, mosaicml_open_leaf, mosaicml_peek_field,
-    mosaicml_prepare_audio_preview,
+    mosaicml_embedding_projection, mosaicml_get_sample_json, mosaicml_list_samples,
+    mosaicml_load_index, mosaicml_open_leaf, mosaicml_peek_field, mosaicml_peek_page,
+    mosaicml_prepare_audio_preview, mosaicml_save_leaf_as, mosaicml_validate,
+};
+use offline::{get_offline_mode, set_offline_mode};
+use onnx::inspect_onnx_file;
+use open_many::open_many;
+use open_with::{
+    delete_opener_template, get_default_opener, list_registered_openers, open_path_with_app,
+    open_with_system_picker, run_opener_template, save_opener_template, set_default_opener,
+};
+use parquet_query::parquet_query;
+use pii_scan::{get_pii_patterns, set_pii_patterns};
+use quarantine::{get_quarantine_mode, set_quarantine_mode};
+use read_only::{
+    clear_read_only_dry_run, get_read_only_dry_run, get_read_only_mode, set_read_only_mode,
 };
-use open_with::open_path_with_app;
+use remote_fs::{remote_fetch_file, remote_list_dir, remote_peek_file};
+use report::generate_report;
+use resource_monitor::{get_memory_usage, set_memory_cap};
+use robot_log::inspect_robot_log;
+use robot_log_export::export_robot_log_topic;
+use sample_location::{parse_sample_locator, sample_locator};
+use scan_cache::invalidate_scan_cache;
+use schema::diff_schemas;
+use shard_balance::shard_balance;
+use snippets::python_snippet;
+use sql_query::run_query;
+use tabular_repos::{
+    openml_dataset, openml_download, uci_dataset, uci_download, TabularRepoClient,
+};
+use torch_archive::inspect_torch_file;
+use trusted_hosts::{get_trusted_repository_hosts, set_trusted_repository_hosts};
+use updater::{apply_update, check_update, get_update_channel, set_update_channel, PendingUpdate};
+use warehouse::{warehouse_page, warehouse_tables};
 use webdataset::{
-    detect_local_dataset, wds_list_samples, wds_load_dir, wds_open_member, wds_peek_member,
-    wds_prepare_audio_preview, WdsScanCache,
+    detect_local_dataset, get_wds_prefetch_depth, set_wds_prefetch_depth, wds_embedding_projection,
+    wds_image_flags, wds_lint, wds_list_samples, wds_load_dir, wds_open_member, wds_open_sample,
+    wds_peek_member, wds_peek_member_tail, wds_peek_page, wds_pii_scan, wds_prepare_audio_preview,
+    wds_prepare_audio_previews, wds_save_member_as, wds_shard_layout, wds_stream_samples,
+    wds_thumbnails, WdsScanCache,
+};
+use workspaces::{
+    delete_workspace, list_workspaces, open_workspace_root, refresh_workspace, save_workspace,
 };
 use zenodo::{
-    zenodo_open_file, zenodo_peek_file, zenodo_record_summary, zenodo_tar_inline_entry_media,
-    zenodo_tar_list_entries_paged, zenodo_tar_open_entry, zenodo_tar_peek_entry,
-    zenodo_zip_inline_entry_media, zenodo_zip_list_entries, zenodo_zip_open_entry,
-    zenodo_zip_peek_entry, ZenodoClient, ZenodoTarScanCache, ZenodoZipIndexCache,
+    archive_type_summary, zenodo_archive_tree, zenodo_declared_metadata, zenodo_download_record,
+    zenodo_open_file, zenodo_peek_file, zenodo_peek_tail, zenodo_record_docs,
+    zenodo_record_summary, zenodo_record_versions, zenodo_tar_cancel_scan, zenodo_tar_find_entry,
+    zenodo_tar_inline_entry_media, zenodo_tar_list_entries_paged, zenodo_tar_open_entry,
+    zenodo_tar_peek_entry, zenodo_tar_peek_glob, zenodo_tar_save_entry_as, zenodo_tar_scan_to_end,
+    zenodo_tar_stream_entry_media, zenodo_transfer_estimate, zenodo_zip_inline_entry_media,
+    zenodo_zip_list_entries, zenodo_zip_list_entries_paged, zenodo_zip_open_entry,
+    zenodo_zip_peek_entry, zenodo_zip_peek_glob, zenodo_zip_save_entry_as,
+    zenodo_zip_stream_entry_media, ZenodoClient, ZenodoTarScanCache, ZenodoZipIndexCache,
 };
 
 fn main() {
     tauri::Builder::default()
         .setup(|app| {
+            http_limit::load_settings(app.handle());
+            throttle::init(app.handle());
+            access_log::init(app.handle());
+            decode_profile::load_settings(app.handle());
+            decoder_plugins::load_plugins(app.handle());
+            offline::load_settings(app.handle());
+            trusted_hosts::load_settings(app.handle());
+            pii_scan::load_settings(app.handle());
+            read_only::load_settings(app.handle());
+            messages::load_settings(app.handle());
+            automation::load_settings(app.handle());
+            resource_monitor::init(app.handle());
+
             #[cfg(desktop)]
             app.handle()
                 .plugin(tauri_plugin_updater::Builder::new().build())?;
 
-            #[cfg(all(desktop, target_os = "macos"))]
+            #[cfg(desktop)]
             {
-                let handle = app.handle();
-                let app_menu = SubmenuBuilder::new(handle, handle.package_info().name.clone())
-                    .about(None)
-                    .separator()
-                    .text("check_updates", "Check for Updates…")
-                    .separator()
-                    .services()
-                    .separator()
-                    .hide()
-                    .hide_others()
-                    .show_all()
-                    .separator()
-                    .quit()
-                    .build()?;
-
-                let edit_menu = SubmenuBuilder::new(handle, "Edit")
-                    .undo()
-                    .redo()
-                    .separator()
-                    .cut()
-                    .copy()
-                    .paste()
-                    .select_all()
-                    .build()?;
-
-                let menu = MenuBuilder::new(handle)
-                    .item(&app_menu)
-                    .item(&edit_menu)
-                    .build()?;
+                let menu = app_menu::build(app.handle())?;
                 app.set_menu(menu)?;
             }
 
             Ok(())
         })
-        .on_menu_event(|app, event| {
-            if event.id() == "check_updates" {
-                let _ = app.emit_to("main", "app://check-updates", ());
-            }
-        })
+        .on_menu_event(app_menu::handle_event)
+        .on_window_event(jobs::handle_window_event)
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_store::Builder::default().build())
@@ -92,40 +211,178 @@ fn main() {
         .manage(WdsScanCache::default())
         .manage(HfClient::default())
         .manage(ZenodoClient::default())
+        .manage(TabularRepoClient::default())
         .manage(ZenodoZipIndexCache::default())
         .manage(ZenodoTarScanCache::default())
+        .manage(MediaStore::default())
+        .manage(DirWatchRegistry::default())
+        .manage(AnnotationStore::default())
+        .manage(PendingUpdate::default())
+        .manage(JobRegistry::default())
+        .register_asynchronous_uri_scheme_protocol(MEDIA_SCHEME, |ctx, request, responder| {
+            let store = ctx.app_handle().state::<MediaStore>().inner().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                responder.respond(media_stream::handle_request(&store, &request));
+            });
+        })
         .invoke_handler(tauri::generate_handler![
             detect_local_dataset,
             load_index,
             load_chunk_list,
             list_chunk_items,
             peek_field,
+            get_item_json,
             open_leaf,
+            save_leaf_as,
             prepare_audio_preview,
+            validate_index,
             mosaicml_load_index,
             mosaicml_list_samples,
             mosaicml_peek_field,
+            mosaicml_peek_page,
+            mosaicml_get_sample_json,
+            mosaicml_embedding_projection,
             mosaicml_open_leaf,
+            mosaicml_save_leaf_as,
             mosaicml_prepare_audio_preview,
+            mosaicml_validate,
             wds_load_dir,
             wds_list_samples,
             wds_peek_member,
+            wds_peek_member_tail,
+            wds_peek_page,
             wds_open_member,
+            wds_open_sample,
+            wds_save_member_as,
             wds_prepare_audio_preview,
+            wds_prepare_audio_previews,
+            wds_lint,
+            wds_image_flags,
+            wds_embedding_projection,
+            wds_pii_scan,
+            wds_shard_layout,
+            wds_stream_samples,
+            wds_thumbnails,
+            get_wds_prefetch_depth,
+            set_wds_prefetch_depth,
             open_path_with_app,
+            open_with_system_picker,
+            list_registered_openers,
+            save_opener_template,
+            delete_opener_template,
+            run_opener_template,
+            get_default_opener,
+            set_default_opener,
+            get_quarantine_mode,
+            set_quarantine_mode,
+            list_installed_apps,
+            get_max_requests_per_host,
+            set_max_requests_per_host,
+            get_offline_mode,
+            set_offline_mode,
+            get_pii_patterns,
+            set_pii_patterns,
+            get_trusted_repository_hosts,
+            set_trusted_repository_hosts,
+            get_decode_profile,
+            set_decode_profile,
+            list_decoder_plugins,
+            reload_decoder_plugins,
             hf_dataset_preview,
+            hf_get_row,
+            hf_field_value,
             hf_open_field,
+            hf_save_field_as,
+            openml_dataset,
+            openml_download,
+            uci_dataset,
+            uci_download,
             zenodo_record_summary,
+            zenodo_record_versions,
             zenodo_peek_file,
+            zenodo_peek_tail,
             zenodo_open_file,
+            zenodo_archive_tree,
+            archive_type_summary,
             zenodo_zip_list_entries,
+            zenodo_zip_list_entries_paged,
             zenodo_zip_peek_entry,
+            zenodo_zip_peek_glob,
             zenodo_zip_open_entry,
+            zenodo_zip_save_entry_as,
             zenodo_zip_inline_entry_media,
             zenodo_tar_list_entries_paged,
+            zenodo_tar_scan_to_end,
+            zenodo_tar_cancel_scan,
             zenodo_tar_peek_entry,
+            zenodo_tar_find_entry,
+            zenodo_tar_peek_glob,
             zenodo_tar_open_entry,
-            zenodo_tar_inline_entry_media
+            zenodo_tar_save_entry_as,
+            zenodo_tar_inline_entry_media,
+            zenodo_download_record,
+            zenodo_zip_stream_entry_media,
+            zenodo_tar_stream_entry_media,
+            invalidate_scan_cache,
+            watch_dataset_dir,
+            unwatch_dataset_dir,
+            annotate_sample,
+            remove_annotation,
+            list_annotations,
+            export_annotations,
+            export_listing,
+            generate_report,
+            read_declared_metadata,
+            compare_declared_schema,
+            zenodo_declared_metadata,
+            local_dataset_docs,
+            zenodo_record_docs,
+            hf_dataset_docs,
+            python_snippet,
+            sample_locator,
+            parse_sample_locator,
+            diff_schemas,
+            inspect_torch_file,
+            inspect_onnx_file,
+            inspect_gguf_file,
+            inspect_robot_log,
+            export_robot_log_topic,
+            mbox_page,
+            warc_page,
+            parquet_query,
+            run_query,
+            warehouse_tables,
+            warehouse_page,
+            remote_list_dir,
+            remote_peek_file,
+            remote_fetch_file,
+            scan_tree,
+            list_workspaces,
+            save_workspace,
+            delete_workspace,
+            refresh_workspace,
+            open_workspace_root,
+            shard_balance,
+            zenodo_transfer_estimate,
+            open_many,
+            get_dataset_profile,
+            get_read_only_mode,
+            set_read_only_mode,
+            query_access_log,
+            export_access_log,
+            list_message_languages,
+            get_message_language,
+            set_message_language,
+            get_automation_endpoint,
+            set_automation_endpoint,
+            get_update_channel,
+            set_update_channel,
+            check_update,
+            apply_update,
+            get_memory_usage,
+            set_memory_cap,
+            get_read_only_dry_run,
+            clear_read_only_dry_run
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
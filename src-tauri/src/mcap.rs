@@ -0,0 +1,410 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    ops::ControlFlow,
+    path::Path,
+};
+
+use crate::{
+    app_error::{AppError, AppResult},
+    robot_log::{self, LogChannel, LogChunk, LogIndex, LogMessage, MAX_CHUNK_BYTES},
+};
+
+pub const MAGIC: &[u8] = b"\x89MCAP0\r\n";
+
+const OP_HEADER: u8 = 0x01;
+const OP_FOOTER: u8 = 0x02;
+const OP_SCHEMA: u8 = 0x03;
+const OP_CHANNEL: u8 = 0x04;
+const OP_MESSAGE: u8 = 0x05;
+const OP_CHUNK: u8 = 0x06;
+const OP_CHUNK_INDEX: u8 = 0x08;
+const OP_STATISTICS: u8 = 0x0B;
+const OP_DATA_END: u8 = 0x0F;
+/// Opcode and content length.
+const RECORD_PREFIX_LEN: u64 = 9;
+/// Footer record: prefix, summary start, summary offset start, summary CRC.
+const FOOTER_LEN: u64 = RECORD_PREFIX_LEN + 20;
+
+/// Little-endian reader over one record's content.
+struct Fields<'a>(&'a [u8]);
+
+impl<'a> Fields<'a> {
+    fn take(&mut self, len: usize) -> AppResult<&'a [u8]> {
+        if len > self.0.len() {
+            return Err(AppError::Invalid("MCAP record ends early".into()));
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn fixed<const N: usize>(&mut self) -> AppResult<[u8; N]> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u16(&mut self) -> AppResult<u16> {
+        self.fixed().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> AppResult<u32> {
+        self.fixed().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> AppResult<u64> {
+        self.fixed().map(u64::from_le_bytes)
+    }
+
+    /// `uint32`-length-prefixed bytes (strings, byte arrays, maps).
+    fn prefixed(&mut self) -> AppResult<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> AppResult<String> {
+        self.prefixed()
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// Splits the next record off the front of `bytes`.
+fn next_record<'a>(bytes: &mut &'a [u8]) -> AppResult<Option<(u8, &'a [u8])>> {
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    let mut fields = Fields(bytes);
+    let [op] = fields.fixed()?;
+    let len = usize::try_from(fields.u64()?)
+        .map_err(|_| AppError::Invalid("MCAP record length overflow".into()))?;
+    let content = fields.take(len)?;
+    *bytes = fields.0;
+    Ok(Some((op, content)))
+}
+
+/// A chunk record's inflated records.
+fn chunk_records(content: &[u8]) -> AppResult<Cow<'_, [u8]>> {
+    let mut fields = Fields(content);
+    let _message_start = fields.u64()?;
+    let _message_end = fields.u64()?;
+    let uncompressed_size = fields.u64()?;
+    let _crc = fields.u32()?;
+    let compression = fields.string()?;
+    let len = usize::try_from(fields.u64()?)
+        .map_err(|_| AppError::Invalid("MCAP chunk length overflow".into()))?;
+    robot_log::inflate(&compression, fields.take(len)?, uncompressed_size)
+}
+
+fn message(content: &[u8]) -> AppResult<LogMessage<'_>> {
+    let mut fields = Fields(content);
+    let channel = fields.u16()?;
+    let _sequence = fields.u32()?;
+    let log_time_ns = fields.u64()?;
+    let _publish_time = fields.u64()?;
+    Ok(LogMessage {
+        channel: u32::from(channel),
+        log_time_ns,
+        data: fields.0,
+    })
+}
+
+/// Reads every record front to back, expanding chunks, and passes schema, channel, message and
+/// chunk records to `f` until it breaks.
+fn scan(path: &Path, mut f: impl FnMut(u8, &[u8]) -> AppResult<ControlFlow<()>>) -> AppResult<()> {
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+    reader.seek(SeekFrom::Start(MAGIC.len() as u64))?;
+    let mut pos = MAGIC.len() as u64;
+    while pos + RECORD_PREFIX_LEN <= len {
+        let mut prefix = [0u8; RECORD_PREFIX_LEN as usize];
+        reader.read_exact(&mut prefix)?;
+        pos += RECORD_PREFIX_LEN;
+        let op = prefix[0];
+        let mut raw_len = [0u8; 8];
+        raw_len.copy_from_slice(&prefix[1..]);
+        let record_len = u64::from_le_bytes(raw_len);
+        if op == OP_FOOTER || op == OP_DATA_END {
+            break;
+        }
+        if record_len > len - pos {
+            return Err(AppError::Invalid(format!(
+                "MCAP record at byte {} runs past the end of the file",
+                pos - RECORD_PREFIX_LEN
+            )));
+        }
+        if !matches!(op, OP_SCHEMA | OP_CHANNEL | OP_MESSAGE | OP_CHUNK) {
+            let delta = i64::try_from(record_len)
+                .map_err(|_| AppError::Invalid("MCAP record length overflow".into()))?;
+            reader.seek_relative(delta)?;
+            pos += record_len;
+            continue;
+        }
+        if record_len > MAX_CHUNK_BYTES {
+            return Err(AppError::Invalid(format!(
+                "MCAP record of {record_len} bytes is too large to read"
+            )));
+        }
+        let mut content = vec![0u8; record_len as usize];
+        reader.read_exact(&mut content)?;
+        pos += record_len;
+        if f(op, &content)?.is_break() {
+            return Ok(());
+        }
+        if op == OP_CHUNK {
+            let records = chunk_records(&content)?;
+            let mut rest: &[u8] = &records;
+            while let Some((op, content)) = next_record(&mut rest)? {
+                if f(op, content)?.is_break() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Schemas, channels and counts gathered from the summary section or a full scan.
+#[derive(Default)]
+struct Summary {
+    schemas: HashMap<u16, (String, String, Vec<u8>)>,
+    channels: Vec<(u16, u16, String, String)>,
+    counts: HashMap<u16, u64>,
+    /// Whether `counts` came from a statistics record.
+    has_statistics: bool,
+    chunks: Vec<LogChunk>,
+    compression: BTreeSet<String>,
+    start_ns: Option<u64>,
+    end_ns: Option<u64>,
+}
+
+impl Summary {
+    fn widen(&mut self, start_ns: u64, end_ns: u64) {
+        self.start_ns = Some(self.start_ns.map_or(start_ns, |t| t.min(start_ns)));
+        self.end_ns = Some(self.end_ns.map_or(end_ns, |t| t.max(end_ns)));
+    }
+
+    fn record(&mut self, op: u8, content: &[u8]) -> AppResult<()> {
+        let mut fields = Fields(content);
+        match op {
+            OP_SCHEMA => {
+                let id = fields.u16()?;
+                let name = fields.string()?;
+                let encoding = fields.string()?;
+                let data = fields.prefixed()?.to_vec();
+                self.schemas.insert(id, (name, encoding, data));
+            }
+            OP_CHANNEL => {
+                let id = fields.u16()?;
+                let schema_id = fields.u16()?;
+                let topic = fields.string()?;
+                let encoding = fields.string()?;
+                if !self.channels.iter().any(|c| c.0 == id) {
+                    self.channels.push((id, schema_id, topic, encoding));
+                }
+            }
+            OP_MESSAGE => {
+                let channel = fields.u16()?;
+                let _sequence = fields.u32()?;
+                let log_time_ns = fields.u64()?;
+                *self.counts.entry(channel).or_insert(0) += 1;
+                self.widen(log_time_ns, log_time_ns);
+            }
+            OP_CHUNK => {
+                // message start/end times, uncompressed size and CRC
+                fields.take(8 * 3 + 4)?;
+                self.compression.insert(fields.string()?);
+            }
+            OP_CHUNK_INDEX => {
+                let start_ns = fields.u64()?;
+                let end_ns = fields.u64()?;
+                let offset = fields.u64()?;
+                let _length = fields.u64()?;
+                fields.prefixed()?;
+                let _message_index_length = fields.u64()?;
+                self.compression.insert(fields.string()?);
+                self.widen(start_ns, end_ns);
+                self.chunks.push(LogChunk {
+                    offset,
+                    start_ns,
+                    end_ns,
+                });
+            }
+            OP_STATISTICS => {
+                // message, schema, channel, attachment, metadata and chunk counts
+                fields.take(8 + 2 + 4 * 4)?;
+                let start_ns = fields.u64()?;
+                let end_ns = fields.u64()?;
+                let mut counts = Fields(fields.prefixed()?);
+                while !counts.0.is_empty() {
+                    let channel = counts.u16()?;
+                    let count = counts.u64()?;
+                    self.counts.insert(channel, count);
+                }
+                if start_ns != 0 || end_ns != 0 {
+                    self.widen(start_ns, end_ns);
+                }
+                self.has_statistics = true;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn read_at(reader: &mut BufReader<File>, pos: u64, len: u64) -> AppResult<Vec<u8>> {
+    reader.seek(SeekFrom::Start(pos))?;
+    let mut buf = vec![0u8; len as usize];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Reads schemas, channels, statistics and the chunk index from the summary section, falling
+/// back to a full scan when the writer left no summary or no statistics.
+pub fn open(path: &Path) -> AppResult<LogIndex> {
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+    let min_len = 2 * MAGIC.len() as u64 + FOOTER_LEN;
+    if len < min_len || read_at(&mut reader, 0, MAGIC.len() as u64)? != MAGIC {
+        return Err(AppError::Invalid("not an MCAP file".into()));
+    }
+    if read_at(&mut reader, len - MAGIC.len() as u64, MAGIC.len() as u64)? != MAGIC {
+        return Err(AppError::Invalid(
+            "MCAP file is truncated (no trailing magic)".into(),
+        ));
+    }
+
+    let prefix = read_at(&mut reader, MAGIC.len() as u64, RECORD_PREFIX_LEN)?;
+    let profile = if prefix[0] == OP_HEADER {
+        let head = read_at(&mut reader, MAGIC.len() as u64 + RECORD_PREFIX_LEN, 4)?;
+        let profile_len = u32::from_le_bytes([head[0], head[1], head[2], head[3]]);
+        let profile = read_at(
+            &mut reader,
+            MAGIC.len() as u64 + RECORD_PREFIX_LEN + 4,
+            u64::from(profile_len).min(256),
+        )?;
+        Some(String::from_utf8_lossy(&profile).into_owned()).filter(|p| !p.is_empty())
+    } else {
+        None
+    };
+
+    let footer_pos = len - MAGIC.len() as u64 - FOOTER_LEN;
+    let footer = read_at(&mut reader, footer_pos, FOOTER_LEN)?;
+    if footer[0] != OP_FOOTER {
+        return Err(AppError::Invalid("MCAP file has no footer record".into()));
+    }
+    let mut fields = Fields(&footer[RECORD_PREFIX_LEN as usize..]);
+    let summary_start = fields.u64()?;
+    let summary_offset_start = fields.u64()?;
+
+    let mut summary = Summary::default();
+    if summary_start != 0 && summary_start < footer_pos {
+        let summary_end = if summary_offset_start > summary_start {
+            summary_offset_start.min(footer_pos)
+        } else {
+            footer_pos
+        };
+        if summary_end - summary_start > MAX_CHUNK_BYTES {
+            return Err(AppError::Invalid(
+                "MCAP summary section is too large".into(),
+            ));
+        }
+        let section = read_at(&mut reader, summary_start, summary_end - summary_start)?;
+        let mut rest: &[u8] = &section;
+        while let Some((op, content)) = next_record(&mut rest)? {
+            summary.record(op, content)?;
+        }
+    }
+    if !summary.has_statistics {
+        summary.counts.clear();
+        scan(path, |op, content| {
+            summary
+                .record(op, content)
+                .map(|()| ControlFlow::Continue(()))
+        })?;
+    }
+
+    let Summary {
+        schemas,
+        channels,
+        counts,
+        mut chunks,
+        compression,
+        start_ns,
+        end_ns,
+        ..
+    } = summary;
+    let channels = channels
+        .into_iter()
+        .map(|(id, schema_id, topic, message_encoding)| {
+            let schema = schemas.get(&schema_id);
+            LogChannel {
+                id: u32::from(id),
+                topic,
+                message_type: schema.map(|s| s.0.clone()).unwrap_or_default(),
+                message_encoding,
+                schema_encoding: schema.map(|s| s.1.clone()),
+                // Text encodings (`ros1msg`, `ros2msg`, `jsonschema`); protobuf descriptors are
+                // binary and not shown.
+                schema: schema.and_then(|s| std::str::from_utf8(&s.2).ok().map(str::to_string)),
+                message_count: counts.get(&id).copied().unwrap_or(0),
+            }
+        })
+        .collect();
+    chunks.sort_by_key(|c| c.offset);
+    Ok(LogIndex {
+        format: "mcap",
+        profile,
+        channels,
+        chunks,
+        compression: compression
+            .into_iter()
+            .map(|c| if c.is_empty() { "none".into() } else { c })
+            .collect(),
+        start_ns,
+        end_ns,
+    })
+}
+
+/// Reads indexed chunks directly when there is a chunk index, and scans the file otherwise.
+pub fn for_each_message(
+    path: &Path,
+    index: &LogIndex,
+    mut f: impl FnMut(LogMessage) -> ControlFlow<()>,
+) -> AppResult<()> {
+    if index.chunks.is_empty() {
+        return scan(path, |op, content| {
+            Ok(if op == OP_MESSAGE {
+                f(message(content)?)
+            } else {
+                ControlFlow::Continue(())
+            })
+        });
+    }
+    let mut reader = BufReader::new(File::open(path)?);
+    for chunk in &index.chunks {
+        let prefix = read_at(&mut reader, chunk.offset, RECORD_PREFIX_LEN)?;
+        let mut fields = Fields(&prefix);
+        let [op] = fields.fixed()?;
+        let record_len = fields.u64()?;
+        if op != OP_CHUNK || record_len > MAX_CHUNK_BYTES {
+            return Err(AppError::Invalid(format!(
+                "chunk index points at a bad record at byte {}",
+                chunk.offset
+            )));
+        }
+        let content = read_at(&mut reader, chunk.offset + RECORD_PREFIX_LEN, record_len)?;
+        let records = chunk_records(&content)?;
+        let mut rest: &[u8] = &records;
+        while let Some((op, content)) = next_record(&mut rest)? {
+            if op == OP_MESSAGE && f(message(content)?).is_break() {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+use tauri::http::{header, Request, Response, StatusCode};
+
+use crate::app_error::{AppError, AppResult};
+use crate::resource_monitor::MemoryCache;
+
+/// URI scheme the webview uses to fetch registered media (`dsi-media://localhost/<token>`).
+pub const MEDIA_SCHEME: &str = "dsi-media";
+const MEDIA_STORE_MAX_BYTES: u64 = 512 * 1024 * 1024;
+/// Largest body one response copies out of an entry; bigger entries are served as 206 slices
+/// of at most this size, which `<audio>`/`<video>` follow up with further Range requests.
+const MEDIA_RESPONSE_MAX_BYTES: u64 = 8 * 1024 * 1024;
+
+struct MediaEntry {
+    bytes: Arc<Vec<u8>>,
+    mime: String,
+}
+
+#[derive(Default)]
+struct MediaStoreInner {
+    entries: HashMap<String, MediaEntry>,
+    order: VecDeque<String>,
+    total: u64,
+    next_id: u64,
+}
+
+/// In-memory media served over [`MEDIA_SCHEME`], so large previews reach `<audio>`/`<video>`
+/// with Range support instead of as one base64 IPC payload.
+#[derive(Clone, Default)]
+pub struct MediaStore {
+    inner: Arc<Mutex<MediaStoreInner>>,
+}
+
+impl MediaStore {
+    /// Stores `bytes` and returns the token to request them by; evicts the oldest entries
+    /// once the store exceeds its byte budget.
+    pub fn register(&self, bytes: Vec<u8>, mime: String) -> AppResult<String> {
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|_| AppError::Task("media store lock poisoned".into()))?;
+        let size = bytes.len() as u64;
+        while guard.total.saturating_add(size) > MEDIA_STORE_MAX_BYTES {
+            let Some(oldest) = guard.order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = guard.entries.remove(&oldest) {
+                guard.total = guard.total.saturating_sub(evicted.bytes.len() as u64);
+            }
+        }
+        guard.next_id += 1;
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let token = format!("m{:x}-{nanos:08x}", guard.next_id);
+        guard.total = guard.total.saturating_add(size);
+        guard.order.push_back(token.clone());
+        guard.entries.insert(
+            token.clone(),
+            MediaEntry {
+                bytes: Arc::new(bytes),
+                mime,
+            },
+        );
+        Ok(token)
+    }
+
+    fn get(&self, token: &str) -> Option<(Arc<Vec<u8>>, String)> {
+        let guard = self.inner.lock().ok()?;
+        let entry = guard.entries.get(token)?;
+        Some((entry.bytes.clone(), entry.mime.clone()))
+    }
+}
+
+impl MemoryCache for MediaStore {
+    fn usage(&self) -> (usize, usize) {
+        self.inner
+            .lock()
+            .map(|guard| (guard.total as usize, guard.entries.len()))
+            .unwrap_or_default()
+    }
+
+    fn shrink_by(&self, bytes: usize) -> usize {
+        let Ok(mut guard) = self.inner.lock() else {
+            return 0;
+        };
+        let mut freed = 0usize;
+        while freed < bytes {
+            let Some(oldest) = guard.order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = guard.entries.remove(&oldest) {
+                guard.total = guard.total.saturating_sub(evicted.bytes.len() as u64);
+                freed += evicted.bytes.len();
+            }
+        }
+        freed
+    }
+}
+
+/// Parses a single `bytes=` range against a body of `len` bytes into an inclusive span.
+fn parse_range(value: &str, len: u64) -> Option<(u64, u64)> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    let first = spec.split(',').next()?.trim();
+    let (start, end) = first.split_once('-')?;
+    if len == 0 {
+        return None;
+    }
+    if start.is_empty() {
+        let suffix: u64 = end.parse().ok()?;
+        if suffix == 0 {
+            return None;
+        }
+        return Some((len.saturating_sub(suffix), len - 1));
+    }
+    let start: u64 = start.parse().ok()?;
+    let end = if end.is_empty() {
+        len - 1
+    } else {
+        end.parse::<u64>().ok()?.min(len - 1)
+    };
+    (start <= end).then_some((start, end))
+}
+
+fn empty_response(status: StatusCode) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .body(Vec::new())
+        .unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+/// Answers one media request. Runs off the main thread (the scheme is registered as
+/// asynchronous), and never copies more than [`MEDIA_RESPONSE_MAX_BYTES`] of the entry.
+pub fn handle_request(store: &MediaStore, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let token = request.uri().path().trim_start_matches('/');
+    let Some((bytes, mime)) = store.get(token) else {
+        return empty_response(StatusCode::NOT_FOUND);
+    };
+    let len = bytes.len() as u64;
+    let builder = Response::builder()
+        .header(header::CONTENT_TYPE, mime)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+
+    let range = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok());
+    let span = match range {
+        None if len <= MEDIA_RESPONSE_MAX_BYTES => None,
+        None => Some((0, MEDIA_RESPONSE_MAX_BYTES - 1)),
+        Some(value) => match parse_range(value, len) {
+            Some((start, end)) => Some((start, end.min(start + MEDIA_RESPONSE_MAX_BYTES - 1))),
+            None => {
+                return builder
+                    .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                    .header(header::CONTENT_RANGE, format!("bytes */{len}"))
+                    .body(Vec::new())
+                    .unwrap_or_else(|_| empty_response(StatusCode::INTERNAL_SERVER_ERROR));
+            }
+        },
+    };
+    let response = match span {
+        None => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_LENGTH, len)
+            .body(bytes.to_vec()),
+        Some((start, end)) => builder
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_RANGE, format!("bytes {start}-{end}/{len}"))
+            .header(header::CONTENT_LENGTH, end - start + 1)
+            .body(bytes[start as usize..=end as usize].to_vec()),
+    };
+    response.unwrap_or_else(|_| empty_response(StatusCode::INTERNAL_SERVER_ERROR))
+}
@@ -0,0 +1,433 @@
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::decoder_plugins;
+use crate::ipc_types::FieldPreview;
+use crate::magic;
+use crate::metadata;
+use crate::preview_kind::PreviewKind;
+use crate::preview_limits::PreviewLimits;
+
+const PREVIEW_BYTES: usize = 16 * 1024;
+const PEEK_PAGE_DEFAULT_RECORDS: u32 = 20;
+const PEEK_PAGE_MAX_RECORDS: u32 = 100;
+const MAX_HEADERS: usize = 64;
+const MAX_HEADER_VALUE_CHARS: usize = 1024;
+/// Longer lines are read in pieces; only matters for separator detection in broken files.
+const MAX_LINE_BYTES: u64 = 1024 * 1024;
+/// HTTP status line and headers read ahead of the payload in WARC `response` records.
+const MAX_HTTP_HEAD_BYTES: usize = 64 * 1024;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveRecord {
+    index: u32,
+    /// Where the record starts in the decompressed stream.
+    byte_offset: u64,
+    /// Message headers (mbox) or WARC headers, the first `MAX_HEADERS` of them.
+    headers: Vec<RecordHeader>,
+    /// Status line of an HTTP message captured in a WARC record.
+    http_status: Option<String>,
+    http_headers: Vec<RecordHeader>,
+    /// The message body, or the HTTP payload of a WARC record. Bodies are previewed as stored:
+    /// MIME parts and transfer encodings are not decoded.
+    body: FieldPreview,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivePage {
+    /// `mbox` or `warc`.
+    format: &'static str,
+    /// `gzip` or `zstd` when the file is compressed as a whole or per record.
+    compression: Option<&'static str>,
+    offset: u32,
+    records: Vec<ArchiveRecord>,
+    /// Whether the file ended within this page.
+    done: bool,
+}
+
+struct Stream {
+    reader: Box<dyn BufRead>,
+    pos: u64,
+}
+
+impl Stream {
+    fn open(path: &Path) -> AppResult<(Self, Option<&'static str>)> {
+        let mut file = BufReader::new(File::open(path)?);
+        let head = file.fill_buf()?;
+        let (is_gzip, is_zstd) = (
+            head.starts_with(&[0x1F, 0x8B]),
+            head.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]),
+        );
+        let (reader, compression): (Box<dyn BufRead>, _) = if is_gzip {
+            // WARC files are usually one gzip member per record.
+            let decoder = flate2::bufread::MultiGzDecoder::new(file);
+            (Box::new(BufReader::new(decoder)), Some("gzip"))
+        } else if is_zstd {
+            let decoder = zstd::stream::read::Decoder::with_buffer(file)?;
+            (Box::new(BufReader::new(decoder)), Some("zstd"))
+        } else {
+            (Box::new(file), None)
+        };
+        Ok((Self { reader, pos: 0 }, compression))
+    }
+
+    /// Reads the next line, terminator included, into `line`; returns 0 at the end of the file.
+    fn line(&mut self, line: &mut Vec<u8>) -> AppResult<usize> {
+        line.clear();
+        let n = (&mut self.reader)
+            .take(MAX_LINE_BYTES)
+            .read_until(b'\n', line)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn skip(&mut self, len: u64) -> AppResult<()> {
+        let n = io::copy(&mut (&mut self.reader).take(len), &mut io::sink())?;
+        self.pos += n;
+        if n < len {
+            return Err(AppError::Invalid(format!(
+                "file ends inside a record at byte {}",
+                self.pos
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reads a `len`-byte block, keeping its first `keep` bytes.
+    fn block(&mut self, len: u64, keep: usize) -> AppResult<Vec<u8>> {
+        let keep = len.min(keep as u64);
+        let mut buf = Vec::with_capacity(keep as usize);
+        (&mut self.reader).take(keep).read_to_end(&mut buf)?;
+        self.pos += buf.len() as u64;
+        self.skip(len - buf.len() as u64)?;
+        Ok(buf)
+    }
+}
+
+fn is_blank(line: &[u8]) -> bool {
+    line == b"\n" || line == b"\r\n"
+}
+
+/// Adds one `Name: value` line (or a folded continuation of the previous one) to `headers`.
+fn push_header_line(headers: &mut Vec<RecordHeader>, text: &str) {
+    if text.starts_with([' ', '\t']) {
+        if let Some(last) = headers.last_mut() {
+            if last.value.len() < MAX_HEADER_VALUE_CHARS {
+                last.value.push(' ');
+                last.value.push_str(text.trim());
+            }
+        }
+        return;
+    }
+    if headers.len() >= MAX_HEADERS {
+        return;
+    }
+    if let Some((name, value)) = text.split_once(':') {
+        headers.push(RecordHeader {
+            name: name.trim().to_string(),
+            value: value.trim().chars().take(MAX_HEADER_VALUE_CHARS).collect(),
+        });
+    }
+}
+
+/// Reads header lines up to the blank line that ends them.
+fn read_headers(stream: &mut Stream, line: &mut Vec<u8>) -> AppResult<Vec<RecordHeader>> {
+    let mut headers = Vec::new();
+    while stream.line(line)? > 0 && !is_blank(line) {
+        push_header_line(&mut headers, String::from_utf8_lossy(line).trim_end());
+    }
+    Ok(headers)
+}
+
+fn header<'a>(headers: &'a [RecordHeader], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+fn ext_from_content_type(content_type: &str) -> Option<&'static str> {
+    let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+    Some(match mime.as_str() {
+        "text/plain" => "txt",
+        "text/html" | "application/xhtml+xml" => "html",
+        "text/xml" | "application/xml" => "xml",
+        "text/css" => "css",
+        "text/csv" => "csv",
+        "application/json" => "json",
+        "application/javascript" | "text/javascript" => "js",
+        "application/pdf" => "pdf",
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "message/rfc822" => "eml",
+        _ => return None,
+    })
+}
+
+fn body_preview(
+    data: &[u8],
+    size: u64,
+    content_type: Option<&str>,
+    limits: PreviewLimits,
+) -> FieldPreview {
+    let (preview_text, text_length) = limits.text(data).unzip();
+    let is_binary = preview_text.is_none();
+    let guessed_ext = content_type
+        .and_then(ext_from_content_type)
+        .map(str::to_string)
+        .or_else(|| magic::sniff_ext(data));
+    let mut preview = FieldPreview {
+        preview_text,
+        text_length,
+        hex_snippet: limits.hex(data),
+        guessed_ext,
+        is_binary,
+        size,
+        metadata: metadata::extract(data),
+        plugin: None,
+        kind: PreviewKind::Binary,
+        compression: None,
+    };
+    decoder_plugins::apply(data, &mut preview);
+    preview.kind = PreviewKind::detect(data, &preview);
+    preview
+}
+
+/// Undoes mboxrd `>From ` quoting: one `>` is dropped from lines matching `^>+From `.
+fn unquote_from(line: &[u8]) -> &[u8] {
+    let quotes = line.iter().take_while(|b| **b == b'>').count();
+    if quotes > 0 && line[quotes..].starts_with(b"From ") {
+        &line[1..]
+    } else {
+        line
+    }
+}
+
+fn mbox_page_sync(
+    path: &Path,
+    offset: u32,
+    length: u32,
+    limits: PreviewLimits,
+) -> AppResult<ArchivePage> {
+    let (mut stream, compression) = Stream::open(path)?;
+    let mut line = Vec::new();
+    stream.line(&mut line)?;
+    if !line.starts_with(b"From ") {
+        return Err(AppError::Invalid(
+            "not an mbox file (it does not start with a `From ` line)".into(),
+        ));
+    }
+
+    let end = offset.saturating_add(length.clamp(1, PEEK_PAGE_MAX_RECORDS));
+    let window = limits.window(PREVIEW_BYTES);
+    let mut records = Vec::new();
+    let mut index = 0u32;
+    loop {
+        // `line` holds this message's `From ` separator.
+        let byte_offset = stream.pos - line.len() as u64;
+        let keep = index >= offset;
+        let headers = read_headers(&mut stream, &mut line)?;
+        let mut body = Vec::new();
+        let mut size = 0u64;
+        let mut previous_blank = false;
+        let mut more = false;
+        while stream.line(&mut line)? > 0 {
+            if previous_blank && line.starts_with(b"From ") {
+                more = true;
+                break;
+            }
+            previous_blank = is_blank(&line);
+            let text = unquote_from(&line);
+            size += text.len() as u64;
+            if keep && body.len() < window {
+                body.extend_from_slice(&text[..text.len().min(window - body.len())]);
+            }
+        }
+        if keep {
+            let content_type = header(&headers, "Content-Type");
+            records.push(ArchiveRecord {
+                index,
+                byte_offset,
+                body: body_preview(&body, size, content_type, limits),
+                headers,
+                http_status: None,
+                http_headers: Vec::new(),
+            });
+        }
+        index += 1;
+        if !more || index >= end {
+            return Ok(ArchivePage {
+                format: "mbox",
+                compression,
+                offset,
+                records,
+                done: !more,
+            });
+        }
+    }
+}
+
+/// Status line, headers and payload offset of the HTTP message at the start of `block`.
+fn http_head(block: &[u8]) -> Option<(String, Vec<RecordHeader>, usize)> {
+    let end = block.windows(4).position(|w| w == b"\r\n\r\n")? + 4;
+    let head = String::from_utf8_lossy(&block[..end]);
+    let mut lines = head.split("\r\n");
+    // `HTTP/1.1 200 OK` for responses, `GET / HTTP/1.1` for requests.
+    let status = lines.next()?.trim().to_string();
+    if !status.contains("HTTP/") {
+        return None;
+    }
+    let mut headers = Vec::new();
+    for text in lines.filter(|l| !l.is_empty()) {
+        push_header_line(&mut headers, text);
+    }
+    Some((status, headers, end))
+}
+
+fn warc_page_sync(
+    path: &Path,
+    offset: u32,
+    length: u32,
+    limits: PreviewLimits,
+) -> AppResult<ArchivePage> {
+    let (mut stream, compression) = Stream::open(path)?;
+    let end = offset.saturating_add(length.clamp(1, PEEK_PAGE_MAX_RECORDS));
+    let window = limits.window(PREVIEW_BYTES);
+    let mut line = Vec::new();
+    let mut records = Vec::new();
+    let mut index = 0u32;
+    while index < end {
+        // Records are followed by two CRLFs; tolerate any number of blank lines.
+        while stream.line(&mut line)? > 0 && is_blank(&line) {}
+        if line.is_empty() {
+            return Ok(ArchivePage {
+                format: "warc",
+                compression,
+                offset,
+                records,
+                done: true,
+            });
+        }
+        let byte_offset = stream.pos - line.len() as u64;
+        if !line.starts_with(b"WARC/") {
+            return Err(AppError::Invalid(if index == 0 {
+                "not a WARC file (it does not start with a `WARC/` version line)".into()
+            } else {
+                format!("expected a WARC record at byte {byte_offset}")
+            }));
+        }
+        let headers = read_headers(&mut stream, &mut line)?;
+        let len = header(&headers, "Content-Length")
+            .and_then(|v| v.parse::<u64>().ok())
+            .ok_or_else(|| {
+                AppError::Invalid(format!(
+                    "WARC record at byte {byte_offset} has no Content-Length"
+                ))
+            })?;
+        if index < offset {
+            stream.skip(len)?;
+            index += 1;
+            continue;
+        }
+
+        let is_http = header(&headers, "Content-Type")
+            .is_some_and(|ct| ct.trim_start().starts_with("application/http"));
+        let block = stream.block(len, window + if is_http { MAX_HTTP_HEAD_BYTES } else { 0 })?;
+        let head = if is_http { http_head(&block) } else { None };
+        let (http_status, http_headers, payload_start) = match head {
+            Some((status, headers, start)) => (Some(status), headers, start),
+            None => (None, Vec::new(), 0),
+        };
+        let payload = &block[payload_start..];
+        let content_type = if http_status.is_some() {
+            header(&http_headers, "Content-Type")
+        } else {
+            header(&headers, "Content-Type")
+        };
+        let body = body_preview(
+            &payload[..payload.len().min(window)],
+            len - payload_start as u64,
+            content_type,
+            limits,
+        );
+        records.push(ArchiveRecord {
+            index,
+            byte_offset,
+            headers,
+            http_status,
+            http_headers,
+            body,
+        });
+        index += 1;
+    }
+    Ok(ArchivePage {
+        format: "warc",
+        compression,
+        offset,
+        records,
+        done: false,
+    })
+}
+
+/// Pages through the messages of an `.mbox` (optionally gzip/zstd-compressed) file: headers and a
+/// preview of each body. `offset` counts messages; reaching it means reading the file from the
+/// start.
+#[tauri::command]
+pub async fn mbox_page(
+    path: String,
+    offset: Option<u32>,
+    length: Option<u32>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
+) -> AppResult<ArchivePage> {
+    let limits = PreviewLimits::new(text_chars, hex_bytes);
+    spawn_blocking(move || {
+        mbox_page_sync(
+            &PathBuf::from(path.trim()),
+            offset.unwrap_or(0),
+            length.unwrap_or(PEEK_PAGE_DEFAULT_RECORDS),
+            limits,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+/// Pages through the records of a `.warc`/`.warc.gz` file: WARC headers, the HTTP status and
+/// headers of captured responses, and a preview of each payload. Skipped records are read past
+/// without being kept.
+#[tauri::command]
+pub async fn warc_page(
+    path: String,
+    offset: Option<u32>,
+    length: Option<u32>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
+) -> AppResult<ArchivePage> {
+    let limits = PreviewLimits::new(text_chars, hex_bytes);
+    spawn_blocking(move || {
+        warc_page_sync(
+            &PathBuf::from(path.trim()),
+            offset.unwrap_or(0),
+            length.unwrap_or(PEEK_PAGE_DEFAULT_RECORDS),
+            limits,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
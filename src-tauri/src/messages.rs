@@ -0,0 +1,426 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri::AppHandle;
+
+use crate::app_error::{AppError, AppResult};
+use crate::settings;
+
+const LANGUAGE_KEY: &str = "message_language";
+
+/// Languages the catalog has templates for, as BCP 47 tags. The first is the fallback.
+const LANGUAGES: &[&str] = &["en", "zh-CN"];
+
+/// Index into `LANGUAGES`.
+static LANGUAGE: AtomicUsize = AtomicUsize::new(0);
+
+/// Status and error messages the backend hands to the UI to show as-is. Templates name their
+/// arguments in braces; each variant lists the ones it takes.
+///
+/// Covers the open/status messages and the errors a user can act on from Zenodo, SFTP, WebDAV,
+/// the updater, offline and read-only modes, and the GGUF and PyTorch inspectors. Internal
+/// faults (poisoned locks, failed tasks) and low-level archive structure errors stay English.
+#[derive(Clone, Copy)]
+pub enum Message {
+    /// `{path}`, `{size}`: where an opened or saved leaf was written.
+    FileWithSize,
+    /// Appended to `FileWithSize` when nothing could open the file.
+    NoDefaultApp,
+    /// `{error}`: appended to `FileWithSize` when a `.sph` file could not be converted to WAV.
+    SphDecodeFailed,
+    /// `{path}`, `{size}`.
+    OpenedFile,
+    /// `{path}`, `{app}`.
+    OpenedWith,
+    /// `{path}`, `{count}`, `{size}`: a folder of extracted members.
+    OpenedFolder,
+    /// `{path}`, `{error}`.
+    CouldNotOpen,
+    /// `{size}`.
+    OpenedInBrowser,
+    CouldNotOpenUrl,
+    FileMissing,
+    NotAFile,
+    AppPathEmpty,
+    /// `{path}`: the Windows "Open with" chooser was shown.
+    ChooseAppToOpen,
+    OpenWithDialogUnavailable,
+    /// `{id}`: no opener template has this id.
+    UnknownTool,
+    /// `{tool}`, `{path}`: an opener template was started.
+    StartedTool,
+    /// `{program}`, `{error}`.
+    ToolFailedToStart,
+    /// `{url}`: offline mode is on and nothing is cached for it.
+    OfflineNotCached,
+    /// `{what}`, `{path}`: a write stopped by read-only mode.
+    ReadOnlyBlocked,
+    /// `{error}`.
+    UpdateFailed,
+    /// `{error}`.
+    InvalidUpdateEndpoint,
+    NoPendingUpdate,
+    /// `{error}`.
+    SftpFailed,
+    /// `{host}`.
+    NoHostKey,
+    /// `{host}`.
+    HostKeyUnknown,
+    /// `{host}`.
+    HostKeyMismatch,
+    /// `{host}`.
+    HostKeyCheckFailed,
+    /// `{user}`, `{error}`.
+    PasswordLoginFailed,
+    /// `{user}`.
+    KeyLoginFailed,
+    /// `{url}`.
+    UrlHasNoHost,
+    /// `{url}`.
+    UrlHasNoUser,
+    /// `{host}`.
+    CouldNotResolve,
+    /// `{path}`: a remote file whose size the server left out.
+    SizeNotReported,
+    /// `{error}`.
+    WebdavFailed,
+    /// `{path}`.
+    NotOnServer,
+    /// `{path}`.
+    AccessRefused,
+    /// `{status}`, `{path}`.
+    WebdavHttpStatus,
+    GgufLengthOverflow,
+    /// `{len}`.
+    GgufKeyTooLong,
+    /// `{type}`, `{offset}`.
+    GgufUnknownValueType,
+    /// `{max}`, `{offset}`.
+    GgufNestedTooDeep,
+    NotGguf,
+    /// `{version}`.
+    GgufUnsupportedVersion,
+    /// `{tensors}`, `{entries}`.
+    GgufImplausibleHeader,
+    /// `{name}`, `{dims}`.
+    GgufTooManyDims,
+    UnexpectedEndOfFile,
+    NotTorchZip,
+    MissingZip64Locator,
+    MissingZip64Eocd,
+    /// `{size}`.
+    CentralDirTooLarge,
+    /// `{name}`, `{size}`.
+    EntryTooLargeToRead,
+    /// `{name}`.
+    BadLocalHeader,
+    /// `{method}`, `{name}`.
+    UnsupportedZipMethod,
+    LegacyTorchSave,
+    NoDataPkl,
+    MissingContentUrl,
+    MissingFilename,
+    NotTarArchive,
+    NotZipArchive,
+    InvalidContentUrl,
+    BlockedContentUrl,
+    ProvideRecordUrl,
+    UnsupportedRecordInput,
+    UnsupportedRepositoryHost,
+    UnsupportedRecordUrl,
+    /// `{status}`, `{url}`.
+    HttpStatusFrom,
+    /// `{url}`, `{error}`.
+    InvalidJsonFrom,
+    /// `{error}`.
+    InvalidRecordJson,
+    /// `{host}`.
+    HtmlInsteadOfFile,
+    SplitZipNaming,
+    /// `{segment}`, `{error}`.
+    SplitZipSegmentUnavailable,
+    /// `{segment}`.
+    SplitZipSegmentNoSize,
+    MissingZipEntryName,
+    /// `{name}`.
+    ZipEntryNotFound,
+    /// `{key}`.
+    UnsafeFileName,
+    /// `{file}`: the downloaded copy was deleted.
+    ChecksumMismatch,
+    ChooseDestinationFolder,
+    /// `{status}`, `{url}`.
+    DownloadHttpStatus,
+    /// `{error}`.
+    DownloadReadFailed,
+    ZipEntryIsDirectory,
+    EncryptedZipEntry,
+    /// `{method}`.
+    UnsupportedZipCompression,
+    PreviewOffsetTooFar,
+    MissingGlobPattern,
+    ZipEntryTooLargeToExtract,
+    TooManyTarEntries,
+    ZipEntryTooLarge,
+}
+
+fn template(language: &str, message: Message) -> &'static str {
+    use Message::*;
+    match (language, message) {
+        ("zh-CN", FileWithSize) => "{path}（{size} 字节）",
+        ("zh-CN", NoDefaultApp) => " · 没有默认应用，请选择一个应用打开",
+        ("zh-CN", SphDecodeFailed) => " · sph 解码失败：{error} · 请选择一个应用打开",
+        ("zh-CN", OpenedFile) => "已打开 {path}（{size} 字节）",
+        ("zh-CN", OpenedWith) => "已用 {app} 打开 {path}",
+        ("zh-CN", OpenedFolder) => "已打开 {path}（{count} 个文件，{size} 字节）",
+        ("zh-CN", CouldNotOpen) => "无法打开 {path} · {error}",
+        ("zh-CN", OpenedInBrowser) => "已在浏览器中打开下载链接（{size} 字节）。",
+        ("zh-CN", CouldNotOpenUrl) => "无法打开下载链接。",
+        ("zh-CN", FileMissing) => "文件不存在",
+        ("zh-CN", NotAFile) => "路径不是文件",
+        ("zh-CN", AppPathEmpty) => "未指定应用路径",
+        ("zh-CN", ChooseAppToOpen) => "请选择用于打开 {path} 的应用",
+        ("zh-CN", OpenWithDialogUnavailable) => "系统的“打开方式”对话框仅在 Windows 上可用",
+        ("zh-CN", UnknownTool) => "未知工具：{id}",
+        ("zh-CN", StartedTool) => "已启动 {tool} 处理 {path}",
+        ("zh-CN", ToolFailedToStart) => "无法启动 {program}：{error}",
+        ("zh-CN", OfflineNotCached) => "离线模式已开启，{url} 没有缓存。关闭离线模式后才能获取。",
+        ("zh-CN", ReadOnlyBlocked) => "只读模式已开启；本应将{what}写入 {path}",
+        ("zh-CN", UpdateFailed) => "更新失败：{error}",
+        ("zh-CN", InvalidUpdateEndpoint) => "更新地址无效：{error}",
+        ("zh-CN", NoPendingUpdate) => "没有可安装的更新，请先检查更新",
+        ("zh-CN", SftpFailed) => "SFTP 操作失败：{error}",
+        ("zh-CN", NoHostKey) => "{host} 没有发送主机密钥",
+        ("zh-CN", HostKeyUnknown) => "{host} 不在 ~/.ssh/known_hosts 中；请先用 ssh 连接一次以验证并添加其密钥",
+        ("zh-CN", HostKeyMismatch) => "{host} 的主机密钥与 ~/.ssh/known_hosts 不符；已拒绝连接",
+        ("zh-CN", HostKeyCheckFailed) => "无法检查 {host} 的主机密钥",
+        ("zh-CN", PasswordLoginFailed) => "以 {user} 身份密码登录失败：{error}",
+        ("zh-CN", KeyLoginFailed) => "无法通过 SSH agent 或 ~/.ssh 中的密钥以 {user} 身份登录；请提供密码",
+        ("zh-CN", UrlHasNoHost) => "{url} 缺少主机名",
+        ("zh-CN", UrlHasNoUser) => "{url} 缺少用户名",
+        ("zh-CN", CouldNotResolve) => "无法解析 {host}",
+        ("zh-CN", SizeNotReported) => "服务器没有报告 {path} 的大小",
+        ("zh-CN", WebdavFailed) => "WebDAV 请求失败：{error}",
+        ("zh-CN", NotOnServer) => "服务器上没有 {path}",
+        ("zh-CN", AccessRefused) => "服务器拒绝访问 {path}；请检查用户名和密码",
+        ("zh-CN", WebdavHttpStatus) => "{path} 的 WebDAV 请求返回 HTTP {status}",
+        ("zh-CN", GgufLengthOverflow) => "GGUF 长度溢出",
+        ("zh-CN", GgufKeyTooLong) => "GGUF 键长 {len} 字节，过长",
+        ("zh-CN", GgufUnknownValueType) => "第 {offset} 字节处有未知的 GGUF 值类型 {type}",
+        ("zh-CN", GgufNestedTooDeep) => "第 {offset} 字节处的 GGUF 数组嵌套超过 {max} 层",
+        ("zh-CN", NotGguf) => "不是 GGUF 文件",
+        ("zh-CN", GgufUnsupportedVersion) => "不支持的 GGUF 版本 {version}（不支持大端序文件）",
+        ("zh-CN", GgufImplausibleHeader) => "GGUF 文件头不合理（{tensors} 个张量，{entries} 个元数据项）",
+        ("zh-CN", GgufTooManyDims) => "张量 {name} 有 {dims} 个维度",
+        ("zh-CN", UnexpectedEndOfFile) => "文件意外结束",
+        ("zh-CN", NotTorchZip) => "不是基于 ZIP 的 PyTorch 文件",
+        ("zh-CN", MissingZip64Locator) => "缺少 ZIP64 定位记录",
+        ("zh-CN", MissingZip64Eocd) => "缺少 ZIP64 中央目录结束记录",
+        ("zh-CN", CentralDirTooLarge) => "ZIP 中央目录过大（{size} 字节）",
+        ("zh-CN", EntryTooLargeToRead) => "{name} 过大，无法读取（{size} 字节）",
+        ("zh-CN", BadLocalHeader) => "{name} 的本地文件头无效",
+        ("zh-CN", UnsupportedZipMethod) => "{name} 使用了 ZIP 压缩方法 {method}",
+        ("zh-CN", LegacyTorchSave) => "不支持旧版（1.6 之前）torch.save 文件；请用较新的 PyTorch 重新保存",
+        ("zh-CN", NoDataPkl) => "归档中没有 data.pkl",
+        ("zh-CN", MissingContentUrl) => "缺少内容 URL。",
+        ("zh-CN", MissingFilename) => "缺少文件名。",
+        ("zh-CN", NotTarArchive) => "所选文件不是受支持的 TAR 归档。",
+        ("zh-CN", NotZipArchive) => "所选文件不是 ZIP 归档。",
+        ("zh-CN", InvalidContentUrl) => "Zenodo 内容 URL 无效。",
+        ("zh-CN", BlockedContentUrl) => "内容 URL 已被阻止。",
+        ("zh-CN", ProvideRecordUrl) => "请提供 Zenodo 记录 URL，例如 https://zenodo.org/records/<id>。",
+        ("zh-CN", UnsupportedRecordInput) => "不支持的输入。请提供 Zenodo 记录 URL（如 https://zenodo.org/records/<id>）或 DOI（如 10.5281/zenodo.<id>）。",
+        ("zh-CN", UnsupportedRepositoryHost) => "不支持的 Zenodo 主机或协议。自建的 InvenioRDM 实例请添加到受信任的仓库主机。",
+        ("zh-CN", UnsupportedRecordUrl) => "不支持的 Zenodo URL。应为 https://zenodo.org/records/<id>。",
+        ("zh-CN", HttpStatusFrom) => "{url} 返回 HTTP {status}",
+        ("zh-CN", InvalidJsonFrom) => "{url} 返回的 JSON 无效：{error}",
+        ("zh-CN", InvalidRecordJson) => "记录 JSON 无效：{error}",
+        ("zh-CN", HtmlInsteadOfFile) => "{host} 返回的是 HTML 页面而不是文件。请检查网络连接或登录状态。",
+        ("zh-CN", SplitZipNaming) => "分卷 ZIP 的分段必须命名为 <name>.z01 这样的形式。",
+        ("zh-CN", SplitZipSegmentUnavailable) => "分卷 ZIP 的第 {segment} 段不可用：{error}",
+        ("zh-CN", SplitZipSegmentNoSize) => "分卷 ZIP 的第 {segment} 段缺少大小。",
+        ("zh-CN", MissingZipEntryName) => "缺少 ZIP 条目名称。",
+        ("zh-CN", ZipEntryNotFound) => "ZIP 中找不到条目“{name}”。",
+        ("zh-CN", UnsafeFileName) => "记录中的文件名不安全：{key}。",
+        ("zh-CN", ChecksumMismatch) => "{file} 校验和不匹配；文件已删除。",
+        ("zh-CN", ChooseDestinationFolder) => "请选择目标文件夹。",
+        ("zh-CN", DownloadHttpStatus) => "下载 {url} 返回 HTTP {status}",
+        ("zh-CN", DownloadReadFailed) => "读取下载内容失败：{error}",
+        ("zh-CN", ZipEntryIsDirectory) => "该 ZIP 条目是目录。",
+        ("zh-CN", EncryptedZipEntry) => "不支持加密的 ZIP 条目。",
+        ("zh-CN", UnsupportedZipCompression) => "不支持的 ZIP 压缩方法：{method}",
+        ("zh-CN", PreviewOffsetTooFar) => "偏移量在压缩的 ZIP 条目中过深，无法预览。",
+        ("zh-CN", MissingGlobPattern) => "缺少 glob 模式。",
+        ("zh-CN", ZipEntryTooLargeToExtract) => "ZIP 条目过大，无法在本地解压。",
+        ("zh-CN", TooManyTarEntries) => "TAR 条目过多，无法列出。",
+        ("zh-CN", ZipEntryTooLarge) => "ZIP 条目解压后超出限制。",
+        (_, FileWithSize) => "{path} ({size} bytes)",
+        (_, NoDefaultApp) => " · no default app found, choose an app to open it",
+        (_, SphDecodeFailed) => " · sph decode failed: {error} · choose an app to open it",
+        (_, OpenedFile) => "Opened {path} ({size} bytes)",
+        (_, OpenedWith) => "Opened {path} with {app}",
+        (_, OpenedFolder) => "Opened {path} ({count} files, {size} bytes)",
+        (_, CouldNotOpen) => "Could not open {path} · {error}",
+        (_, OpenedInBrowser) => "Opened download URL ({size} bytes) in your browser.",
+        (_, CouldNotOpenUrl) => "Unable to open download URL.",
+        (_, FileMissing) => "file does not exist",
+        (_, NotAFile) => "path is not a file",
+        (_, AppPathEmpty) => "app path is empty",
+        (_, ChooseAppToOpen) => "Choose an app to open {path}",
+        (_, OpenWithDialogUnavailable) => {
+            "the system open-with dialog is only available on Windows"
+        }
+        (_, UnknownTool) => "unknown tool: {id}",
+        (_, StartedTool) => "Started {tool} on {path}",
+        (_, ToolFailedToStart) => "could not start {program}: {error}",
+        (_, OfflineNotCached) => "Offline mode is on and {url} is not cached. Turn offline mode off to fetch it.",
+        (_, ReadOnlyBlocked) => "read-only mode is on; would have written {what} to {path}",
+        (_, UpdateFailed) => "update failed: {error}",
+        (_, InvalidUpdateEndpoint) => "invalid update endpoint: {error}",
+        (_, NoPendingUpdate) => "no update to install; check for updates first",
+        (_, SftpFailed) => "sftp failed: {error}",
+        (_, NoHostKey) => "{host} sent no host key",
+        (_, HostKeyUnknown) => "{host} is not in ~/.ssh/known_hosts; connect once with ssh to verify and add its key",
+        (_, HostKeyMismatch) => "the host key of {host} does not match ~/.ssh/known_hosts; refusing to connect",
+        (_, HostKeyCheckFailed) => "could not check the host key of {host}",
+        (_, PasswordLoginFailed) => "password login as {user} failed: {error}",
+        (_, KeyLoginFailed) => "could not log in as {user} with the SSH agent or a key in ~/.ssh; give a password",
+        (_, UrlHasNoHost) => "{url} has no host",
+        (_, UrlHasNoUser) => "{url} has no user name",
+        (_, CouldNotResolve) => "could not resolve {host}",
+        (_, SizeNotReported) => "the server did not report the size of {path}",
+        (_, WebdavFailed) => "webdav request failed: {error}",
+        (_, NotOnServer) => "{path} is not on the server",
+        (_, AccessRefused) => "the server refused access to {path}; check the user name and password",
+        (_, WebdavHttpStatus) => "webdav HTTP {status} for {path}",
+        (_, GgufLengthOverflow) => "GGUF length overflow",
+        (_, GgufKeyTooLong) => "GGUF key of {len} bytes is too long",
+        (_, GgufUnknownValueType) => "unknown GGUF value type {type} at byte {offset}",
+        (_, GgufNestedTooDeep) => "GGUF arrays nested more than {max} deep at byte {offset}",
+        (_, NotGguf) => "not a GGUF file",
+        (_, GgufUnsupportedVersion) => "unsupported GGUF version {version} (big-endian files are not supported)",
+        (_, GgufImplausibleHeader) => "implausible GGUF header ({tensors} tensors, {entries} metadata entries)",
+        (_, GgufTooManyDims) => "tensor {name} has {dims} dimensions",
+        (_, UnexpectedEndOfFile) => "unexpected end of file",
+        (_, NotTorchZip) => "not a ZIP-based PyTorch file",
+        (_, MissingZip64Locator) => "missing ZIP64 locator",
+        (_, MissingZip64Eocd) => "missing ZIP64 end of central directory",
+        (_, CentralDirTooLarge) => "ZIP central directory is too large ({size} bytes)",
+        (_, EntryTooLargeToRead) => "{name} is too large to read ({size} bytes)",
+        (_, BadLocalHeader) => "bad local header for {name}",
+        (_, UnsupportedZipMethod) => "ZIP method {method} in {name}",
+        (_, LegacyTorchSave) => "legacy (pre-1.6) torch.save files are not supported; re-save with a newer PyTorch",
+        (_, NoDataPkl) => "no data.pkl in the archive",
+        (_, MissingContentUrl) => "Missing content URL.",
+        (_, MissingFilename) => "Missing filename.",
+        (_, NotTarArchive) => "Selected file is not a supported TAR archive.",
+        (_, NotZipArchive) => "Selected file is not a ZIP archive.",
+        (_, InvalidContentUrl) => "Invalid Zenodo content URL.",
+        (_, BlockedContentUrl) => "Blocked content URL.",
+        (_, ProvideRecordUrl) => "Provide a Zenodo record URL like https://zenodo.org/records/<id>.",
+        (_, UnsupportedRecordInput) => "Unsupported input. Provide a Zenodo record URL like https://zenodo.org/records/<id> or a DOI like 10.5281/zenodo.<id>.",
+        (_, UnsupportedRepositoryHost) => "Unsupported Zenodo host or scheme. Add self-hosted InvenioRDM instances to the trusted repository hosts.",
+        (_, UnsupportedRecordUrl) => "Unsupported Zenodo URL. Expected https://zenodo.org/records/<id>.",
+        (_, HttpStatusFrom) => "HTTP {status} from {url}",
+        (_, InvalidJsonFrom) => "invalid JSON from {url}: {error}",
+        (_, InvalidRecordJson) => "invalid record JSON: {error}",
+        (_, HtmlInsteadOfFile) => "Got an HTML page instead of the file from {host}. Check the network connection or sign-in.",
+        (_, SplitZipNaming) => "Split ZIP segments must be named like <name>.z01.",
+        (_, SplitZipSegmentUnavailable) => "Split ZIP segment {segment} is not available: {error}",
+        (_, SplitZipSegmentNoSize) => "Missing size for split ZIP segment {segment}.",
+        (_, MissingZipEntryName) => "Missing ZIP entry name.",
+        (_, ZipEntryNotFound) => "Entry '{name}' not found in ZIP.",
+        (_, UnsafeFileName) => "Unsafe file name in record: {key}.",
+        (_, ChecksumMismatch) => "Checksum mismatch for {file}; the file was removed.",
+        (_, ChooseDestinationFolder) => "Choose a destination folder.",
+        (_, DownloadHttpStatus) => "download HTTP {status} from {url}",
+        (_, DownloadReadFailed) => "download read failed: {error}",
+        (_, ZipEntryIsDirectory) => "ZIP entry is a directory.",
+        (_, EncryptedZipEntry) => "Encrypted ZIP entries are not supported.",
+        (_, UnsupportedZipCompression) => "Unsupported ZIP compression method: {method}",
+        (_, PreviewOffsetTooFar) => "Offset is too far into a compressed ZIP entry to preview.",
+        (_, MissingGlobPattern) => "Missing glob pattern.",
+        (_, ZipEntryTooLargeToExtract) => "ZIP entry is too large to extract locally.",
+        (_, TooManyTarEntries) => "TAR contains too many entries to list.",
+        (_, ZipEntryTooLarge) => "ZIP entry expanded beyond the limit.",
+    }
+}
+
+pub fn language() -> &'static str {
+    LANGUAGES
+        .get(LANGUAGE.load(Ordering::Relaxed))
+        .copied()
+        .unwrap_or(LANGUAGES[0])
+}
+
+/// `message` in the selected language, with each `{name}` replaced by its argument. Names
+/// without an argument are left as they are.
+pub fn text(message: Message, args: &[(&str, &dyn Display)]) -> String {
+    let template = template(language(), message);
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let name = &after[..end];
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => out.push_str(&value.to_string()),
+            None => out.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The supported language `tag` names: an exact match ignoring case, else the first language
+/// with the same primary subtag (`zh-TW` gets `zh-CN`).
+fn resolve(tag: &str) -> Option<usize> {
+    let tag = tag.trim().replace('_', "-");
+    if let Some(i) = LANGUAGES.iter().position(|l| l.eq_ignore_ascii_case(&tag)) {
+        return Some(i);
+    }
+    let primary = tag.split('-').next().unwrap_or_default();
+    LANGUAGES.iter().position(|l| {
+        l.split('-')
+            .next()
+            .is_some_and(|p| !primary.is_empty() && p.eq_ignore_ascii_case(primary))
+    })
+}
+
+/// Loads the persisted language. Called once at startup.
+pub fn load_settings(app: &AppHandle) {
+    if let Ok(Some(tag)) = settings::read_setting::<String>(app, LANGUAGE_KEY) {
+        if let Some(i) = resolve(&tag) {
+            LANGUAGE.store(i, Ordering::Relaxed);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn list_message_languages() -> AppResult<Vec<String>> {
+    Ok(LANGUAGES.iter().map(|l| l.to_string()).collect())
+}
+
+#[tauri::command]
+pub async fn get_message_language() -> AppResult<String> {
+    Ok(language().to_string())
+}
+
+/// Switches backend messages to the supported language closest to `language`, usually the
+/// UI's, and returns the one picked.
+#[tauri::command]
+pub async fn set_message_language(app: AppHandle, language: String) -> AppResult<String> {
+    let i = resolve(&language).ok_or_else(|| {
+        AppError::Invalid(format!(
+            "unsupported language {}; expected one of {}",
+            language.trim(),
+            LANGUAGES.join(", ")
+        ))
+    })?;
+    settings::write_setting(&app, LANGUAGE_KEY, &LANGUAGES[i])?;
+    LANGUAGE.store(i, Ordering::Relaxed);
+    Ok(LANGUAGES[i].to_string())
+}
@@ -0,0 +1,571 @@
+//! Embedded media metadata (EXIF, XMP, ID3, Vorbis comments, FLAC/WAV stream info) extracted from
+//! the leading bytes we already read for previews. Everything here is best effort: truncated or
+//! malformed structures simply yield fewer tags.
+
+use crate::ipc_types::MetadataTag;
+
+const MAX_TAGS: usize = 64;
+const MAX_VALUE_CHARS: usize = 512;
+
+struct Tags(Vec<MetadataTag>);
+
+impl Tags {
+    fn push(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let value: String = value.into();
+        let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        if value.is_empty() || self.0.len() >= MAX_TAGS {
+            return;
+        }
+        self.0.push(MetadataTag {
+            key: key.into(),
+            value: value.chars().take(MAX_VALUE_CHARS).collect(),
+        });
+    }
+}
+
+pub fn extract(data: &[u8]) -> Vec<MetadataTag> {
+    let mut tags = Tags(Vec::new());
+    if data.starts_with(&[0xFF, 0xD8]) {
+        jpeg(data, &mut tags);
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png(data, &mut tags);
+    } else if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+        exif(data, &mut tags);
+    } else if data.starts_with(b"ID3") {
+        id3(data, &mut tags);
+    } else if data.starts_with(b"fLaC") {
+        flac(data, &mut tags);
+    } else if data.starts_with(b"OggS") {
+        ogg(data, &mut tags);
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(&b"WAVE"[..]) {
+        wav(data, &mut tags);
+    }
+    xmp(data, &mut tags);
+    tags.0
+}
+
+fn be16(data: &[u8], off: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(off..off + 2)?.try_into().ok()?))
+}
+
+fn be32(data: &[u8], off: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(off..off + 4)?.try_into().ok()?))
+}
+
+fn le16(data: &[u8], off: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(off..off + 2)?.try_into().ok()?))
+}
+
+fn le32(data: &[u8], off: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(off..off + 4)?.try_into().ok()?))
+}
+
+fn jpeg(data: &[u8], tags: &mut Tags) {
+    let mut pos = 2;
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        // Start of scan: image data follows, no more metadata segments.
+        if marker == 0xDA {
+            break;
+        }
+        let Some(len) = be16(data, pos + 2) else {
+            break;
+        };
+        let start = pos + 4;
+        let end = (pos + 2 + len as usize).min(data.len());
+        if start > end {
+            break;
+        }
+        if marker == 0xE1 && data[start..end].starts_with(b"Exif\0\0") {
+            exif(&data[start + 6..end], tags);
+        }
+        pos += 2 + len as usize;
+    }
+}
+
+fn png(data: &[u8], tags: &mut Tags) {
+    let mut pos = 8;
+    while let (Some(len), Some(kind)) = (be32(data, pos), data.get(pos + 4..pos + 8)) {
+        let start = pos + 8;
+        let end = start.saturating_add(len as usize);
+        if kind == b"eXIf" {
+            exif(&data[start.min(data.len())..end.min(data.len())], tags);
+        }
+        if kind == b"IDAT" || kind == b"IEND" {
+            break;
+        }
+        pos = end.saturating_add(4);
+    }
+}
+
+struct Tiff<'a> {
+    data: &'a [u8],
+    le: bool,
+}
+
+struct IfdEntry {
+    tag: u16,
+    kind: u16,
+    count: u32,
+    value_off: usize,
+}
+
+impl Tiff<'_> {
+    fn u16(&self, off: usize) -> Option<u16> {
+        if self.le {
+            le16(self.data, off)
+        } else {
+            be16(self.data, off)
+        }
+    }
+
+    fn u32(&self, off: usize) -> Option<u32> {
+        if self.le {
+            le32(self.data, off)
+        } else {
+            be32(self.data, off)
+        }
+    }
+
+    fn entries(&self, ifd_off: usize) -> Vec<IfdEntry> {
+        let Some(count) = self.u16(ifd_off) else {
+            return Vec::new();
+        };
+        (0..count as usize)
+            .filter_map(|i| {
+                let off = ifd_off + 2 + i * 12;
+                let tag = self.u16(off)?;
+                let kind = self.u16(off + 2)?;
+                let count = self.u32(off + 4)?;
+                let unit = match kind {
+                    3 => 2,
+                    4 | 9 => 4,
+                    5 | 10 => 8,
+                    _ => 1,
+                };
+                let value_off = if unit * count as usize <= 4 {
+                    off + 8
+                } else {
+                    self.u32(off + 8)? as usize
+                };
+                Some(IfdEntry {
+                    tag,
+                    kind,
+                    count,
+                    value_off,
+                })
+            })
+            .collect()
+    }
+
+    fn ascii(&self, e: &IfdEntry) -> Option<String> {
+        let bytes = self
+            .data
+            .get(e.value_off..e.value_off.checked_add(e.count as usize)?)?;
+        Some(String::from_utf8_lossy(bytes).to_string())
+    }
+
+    fn uint(&self, e: &IfdEntry) -> Option<u32> {
+        match e.kind {
+            3 => self.u16(e.value_off).map(u32::from),
+            4 => self.u32(e.value_off),
+            1 | 7 => self.data.get(e.value_off).map(|b| u32::from(*b)),
+            _ => None,
+        }
+    }
+
+    fn rational(&self, e: &IfdEntry, index: usize) -> Option<f64> {
+        let off = e.value_off + index * 8;
+        match e.kind {
+            5 => {
+                let (num, den) = (self.u32(off)?, self.u32(off + 4)?);
+                (den != 0).then(|| f64::from(num) / f64::from(den))
+            }
+            10 => {
+                let (num, den) = (self.u32(off)? as i32, self.u32(off + 4)? as i32);
+                (den != 0).then(|| f64::from(num) / f64::from(den))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn orientation_label(value: u32) -> &'static str {
+    match value {
+        1 => "normal",
+        2 => "mirrored horizontally",
+        3 => "rotated 180°",
+        4 => "mirrored vertically",
+        5 => "mirrored, rotated 90° CCW",
+        6 => "rotated 90° CW",
+        7 => "mirrored, rotated 90° CW",
+        8 => "rotated 90° CCW",
+        _ => "unknown",
+    }
+}
+
+fn exif(data: &[u8], tags: &mut Tags) {
+    let le = match data.get(0..2) {
+        Some(b"II") => true,
+        Some(b"MM") => false,
+        _ => return,
+    };
+    let tiff = Tiff { data, le };
+    let Some(ifd0) = tiff.u32(4) else {
+        return;
+    };
+    let mut exif_ifd = None;
+    let mut gps_ifd = None;
+    for e in tiff.entries(ifd0 as usize) {
+        match e.tag {
+            0x010F => tags.push("exif.make", tiff.ascii(&e).unwrap_or_default()),
+            0x0110 => tags.push("exif.model", tiff.ascii(&e).unwrap_or_default()),
+            0x0131 => tags.push("exif.software", tiff.ascii(&e).unwrap_or_default()),
+            0x0132 => tags.push("exif.dateTime", tiff.ascii(&e).unwrap_or_default()),
+            0x0112 => {
+                if let Some(v) = tiff.uint(&e) {
+                    tags.push(
+                        "exif.orientation",
+                        format!("{v} ({})", orientation_label(v)),
+                    );
+                }
+            }
+            0x8769 => exif_ifd = tiff.uint(&e),
+            0x8825 => gps_ifd = tiff.uint(&e),
+            _ => {}
+        }
+    }
+    if let Some(off) = exif_ifd {
+        for e in tiff.entries(off as usize) {
+            match e.tag {
+                0x9003 => tags.push("exif.dateTimeOriginal", tiff.ascii(&e).unwrap_or_default()),
+                0x829A => {
+                    if let Some(v) = tiff.rational(&e, 0).filter(|v| *v > 0.0) {
+                        let value = if v < 1.0 {
+                            format!("1/{:.0} s", 1.0 / v)
+                        } else {
+                            format!("{v} s")
+                        };
+                        tags.push("exif.exposureTime", value);
+                    }
+                }
+                0x829D => {
+                    if let Some(v) = tiff.rational(&e, 0) {
+                        tags.push("exif.fNumber", format!("f/{v:.1}"));
+                    }
+                }
+                0x8827 => {
+                    if let Some(v) = tiff.uint(&e) {
+                        tags.push("exif.iso", v.to_string());
+                    }
+                }
+                0x920A => {
+                    if let Some(v) = tiff.rational(&e, 0) {
+                        tags.push("exif.focalLength", format!("{v:.1} mm"));
+                    }
+                }
+                0xA002 => {
+                    if let Some(v) = tiff.uint(&e) {
+                        tags.push("exif.pixelWidth", v.to_string());
+                    }
+                }
+                0xA003 => {
+                    if let Some(v) = tiff.uint(&e) {
+                        tags.push("exif.pixelHeight", v.to_string());
+                    }
+                }
+                0xA434 => tags.push("exif.lensModel", tiff.ascii(&e).unwrap_or_default()),
+                _ => {}
+            }
+        }
+    }
+    if let Some(off) = gps_ifd {
+        gps(&tiff, off as usize, tags);
+    }
+}
+
+fn gps(tiff: &Tiff, off: usize, tags: &mut Tags) {
+    let mut lat_ref = None;
+    let mut lon_ref = None;
+    let mut lat = None;
+    let mut lon = None;
+    let mut alt = None;
+    let mut below_sea = false;
+    let dms = |e: &IfdEntry| -> Option<f64> {
+        Some(tiff.rational(e, 0)? + tiff.rational(e, 1)? / 60.0 + tiff.rational(e, 2)? / 3600.0)
+    };
+    for e in tiff.entries(off) {
+        match e.tag {
+            1 => lat_ref = tiff.ascii(&e),
+            2 => lat = dms(&e),
+            3 => lon_ref = tiff.ascii(&e),
+            4 => lon = dms(&e),
+            5 => below_sea = tiff.uint(&e) == Some(1),
+            6 => alt = tiff.rational(&e, 0),
+            _ => {}
+        }
+    }
+    let signed = |v: f64, r: Option<String>, negative: char| {
+        if r.is_some_and(|r| r.starts_with(negative)) {
+            -v
+        } else {
+            v
+        }
+    };
+    if let Some(v) = lat {
+        tags.push("gps.latitude", format!("{:.6}", signed(v, lat_ref, 'S')));
+    }
+    if let Some(v) = lon {
+        tags.push("gps.longitude", format!("{:.6}", signed(v, lon_ref, 'W')));
+    }
+    if let Some(v) = alt {
+        let v = if below_sea { -v } else { v };
+        tags.push("gps.altitude", format!("{v:.1} m"));
+    }
+}
+
+fn xmp(data: &[u8], tags: &mut Tags) {
+    const OPEN: &[u8] = b"<x:xmpmeta";
+    const CLOSE: &[u8] = b"</x:xmpmeta>";
+    let Some(start) = data.windows(OPEN.len()).position(|w| w == OPEN) else {
+        return;
+    };
+    let rest = &data[start..];
+    let end = rest
+        .windows(CLOSE.len())
+        .position(|w| w == CLOSE)
+        .unwrap_or(rest.len());
+    let packet = String::from_utf8_lossy(&rest[..end]);
+
+    // Simple properties are serialized as attributes on rdf:Description: `ns:Name="value"`.
+    let mut cursor = packet.as_ref();
+    while let Some(eq) = cursor.find("=\"") {
+        let name_start = cursor[..eq]
+            .rfind(|c: char| c.is_ascii_whitespace())
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let name = &cursor[name_start..eq];
+        let value_start = eq + 2;
+        let Some(value_len) = cursor[value_start..].find('"') else {
+            break;
+        };
+        let value = &cursor[value_start..value_start + value_len];
+        if name.contains(':') && !name.starts_with("xmlns") && !name.starts_with("rdf:") {
+            tags.push(format!("xmp.{name}"), value);
+        }
+        cursor = &cursor[value_start + value_len + 1..];
+    }
+}
+
+fn id3_text(frame: &[u8]) -> Option<String> {
+    let (&encoding, body) = frame.split_first()?;
+    let text: String = match encoding {
+        0 => body.iter().map(|&b| b as char).collect(),
+        1 | 2 => {
+            let (le, body) = match body {
+                [0xFF, 0xFE, rest @ ..] => (true, rest),
+                [0xFE, 0xFF, rest @ ..] => (false, rest),
+                _ => (encoding == 1, body),
+            };
+            let units = body
+                .chunks_exact(2)
+                .map(|c| {
+                    if le {
+                        u16::from_le_bytes([c[0], c[1]])
+                    } else {
+                        u16::from_be_bytes([c[0], c[1]])
+                    }
+                })
+                .collect::<Vec<_>>();
+            String::from_utf16_lossy(&units)
+        }
+        3 => String::from_utf8_lossy(body).to_string(),
+        _ => return None,
+    };
+    // Multiple values are NUL separated; show them like a list.
+    Some(
+        text.split('\0')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+fn syncsafe(data: &[u8], off: usize) -> Option<u32> {
+    let b = data.get(off..off + 4)?;
+    Some(
+        (u32::from(b[0] & 0x7F) << 21)
+            | (u32::from(b[1] & 0x7F) << 14)
+            | (u32::from(b[2] & 0x7F) << 7)
+            | u32::from(b[3] & 0x7F),
+    )
+}
+
+fn id3(data: &[u8], tags: &mut Tags) {
+    let Some(&version) = data.get(3) else {
+        return;
+    };
+    let Some(size) = syncsafe(data, 6) else {
+        return;
+    };
+    let end = (10 + size as usize).min(data.len());
+    let (id_len, header_len) = if version == 2 { (3, 6) } else { (4, 10) };
+    let mut pos = 10;
+    while pos + header_len <= end {
+        let id = &data[pos..pos + id_len];
+        if id[0] == 0 {
+            break;
+        }
+        let frame_size = match version {
+            2 => {
+                let b = &data[pos + 3..pos + 6];
+                (usize::from(b[0]) << 16) | (usize::from(b[1]) << 8) | usize::from(b[2])
+            }
+            3 => be32(data, pos + 4).unwrap_or(0) as usize,
+            _ => syncsafe(data, pos + 4).unwrap_or(0) as usize,
+        };
+        let body_start = pos + header_len;
+        let body_end = body_start.saturating_add(frame_size).min(end);
+        let key = match id {
+            b"TIT2" | b"TT2" => Some("id3.title"),
+            b"TPE1" | b"TP1" => Some("id3.artist"),
+            b"TALB" | b"TAL" => Some("id3.album"),
+            b"TYER" | b"TDRC" | b"TYE" => Some("id3.year"),
+            b"TCON" | b"TCO" => Some("id3.genre"),
+            b"TRCK" | b"TRK" => Some("id3.track"),
+            b"TLEN" | b"TLE" => Some("id3.lengthMs"),
+            _ => None,
+        };
+        if let Some(key) = key {
+            if let Some(text) = id3_text(&data[body_start..body_end]) {
+                if key == "id3.lengthMs" {
+                    if let Ok(ms) = text.trim().parse::<f64>() {
+                        tags.push("audio.durationSeconds", format!("{:.3}", ms / 1000.0));
+                    }
+                }
+                tags.push(key, text);
+            }
+        }
+        if frame_size == 0 {
+            break;
+        }
+        pos = body_start + frame_size;
+    }
+}
+
+fn vorbis_comments(data: &[u8], tags: &mut Tags) {
+    let Some(vendor_len) = le32(data, 0) else {
+        return;
+    };
+    let vendor_end = 4 + vendor_len as usize;
+    if let Some(vendor) = data.get(4..vendor_end) {
+        tags.push("vorbis.vendor", String::from_utf8_lossy(vendor));
+    }
+    let Some(count) = le32(data, vendor_end) else {
+        return;
+    };
+    let mut pos = vendor_end + 4;
+    for _ in 0..count {
+        let Some(len) = le32(data, pos) else {
+            break;
+        };
+        let Some(comment) = data.get(pos + 4..pos + 4 + len as usize) else {
+            break;
+        };
+        let comment = String::from_utf8_lossy(comment);
+        if let Some((name, value)) = comment.split_once('=') {
+            // Cover art is base64 and would drown out everything else.
+            if !name.eq_ignore_ascii_case("METADATA_BLOCK_PICTURE") {
+                tags.push(format!("vorbis.{}", name.to_ascii_lowercase()), value);
+            }
+        }
+        pos += 4 + len as usize;
+    }
+}
+
+fn flac(data: &[u8], tags: &mut Tags) {
+    let mut pos = 4;
+    while pos + 4 <= data.len() {
+        let header = data[pos];
+        let is_last = header & 0x80 != 0;
+        let kind = header & 0x7F;
+        let len = (usize::from(data[pos + 1]) << 16)
+            | (usize::from(data[pos + 2]) << 8)
+            | usize::from(data[pos + 3]);
+        let start = pos + 4;
+        let Some(block) = data.get(start..start + len) else {
+            break;
+        };
+        match kind {
+            0 if block.len() >= 18 => {
+                let sample_rate = (u32::from(block[10]) << 12)
+                    | (u32::from(block[11]) << 4)
+                    | (u32::from(block[12]) >> 4);
+                let channels = ((block[12] >> 1) & 0x07) + 1;
+                let bits = (((block[12] & 0x01) << 4) | (block[13] >> 4)) + 1;
+                let total = (u64::from(block[13] & 0x0F) << 32)
+                    | u64::from(u32::from_be_bytes([
+                        block[14], block[15], block[16], block[17],
+                    ]));
+                tags.push("audio.sampleRate", sample_rate.to_string());
+                tags.push("audio.channels", channels.to_string());
+                tags.push("audio.bitsPerSample", bits.to_string());
+                if sample_rate > 0 && total > 0 {
+                    tags.push(
+                        "audio.durationSeconds",
+                        format!("{:.3}", total as f64 / f64::from(sample_rate)),
+                    );
+                }
+            }
+            4 => vorbis_comments(block, tags),
+            _ => {}
+        }
+        if is_last {
+            break;
+        }
+        pos = start + len;
+    }
+}
+
+fn ogg(data: &[u8], tags: &mut Tags) {
+    for (marker, skip) in [(&b"\x03vorbis"[..], 7), (&b"OpusTags"[..], 8)] {
+        if let Some(at) = data.windows(marker.len()).position(|w| w == marker) {
+            vorbis_comments(&data[at + skip..], tags);
+            return;
+        }
+    }
+}
+
+fn wav(data: &[u8], tags: &mut Tags) {
+    let mut pos = 12;
+    let mut byte_rate = None;
+    while let (Some(id), Some(len)) = (data.get(pos..pos + 4), le32(data, pos + 4)) {
+        let start = pos + 8;
+        match id {
+            b"fmt " => {
+                if let (Some(channels), Some(rate), Some(bytes_per_sec), Some(bits)) = (
+                    le16(data, start + 2),
+                    le32(data, start + 4),
+                    le32(data, start + 8),
+                    le16(data, start + 14),
+                ) {
+                    tags.push("audio.sampleRate", rate.to_string());
+                    tags.push("audio.channels", channels.to_string());
+                    tags.push("audio.bitsPerSample", bits.to_string());
+                    byte_rate = Some(bytes_per_sec);
+                }
+            }
+            b"data" => {
+                if let Some(rate) = byte_rate.filter(|r| *r > 0) {
+                    tags.push(
+                        "audio.durationSeconds",
+                        format!("{:.3}", f64::from(len) / f64::from(rate)),
+                    );
+                }
+                break;
+            }
+            _ => {}
+        }
+        pos = start + len as usize + (len as usize & 1);
+    }
+}
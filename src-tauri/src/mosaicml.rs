@@ -1,38 +1,58 @@
-use hex::encode as hex_encode;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     fs::{self, File},
     io::{Read, Seek, SeekFrom},
     path::{Path, PathBuf},
 };
-use tauri::async_runtime::spawn_blocking;
+use tauri::{async_runtime::spawn_blocking, AppHandle, Emitter};
 
 use crate::{
     app_error::{AppError, AppResult},
-    audio,
+    audio, checksum, dataset_profile,
+    decode_profile::{self, DecodeOverride},
+    decoder_plugins,
+    embeddings::{self, EmbeddingProjection, ProjectionMethod},
     ipc_types::{
         ChunkSummary, FieldMeta, FieldPreview, IndexSummary, ItemMeta, OpenLeafResponse,
-        PreparedFileResponse,
+        PreparedFileResponse, SavedLeafResponse,
     },
-    open_with,
+    magic,
+    messages::{self, Message},
+    metadata, open_with,
+    preview_kind::PreviewKind,
+    preview_limits::PreviewLimits,
+    save_leaf,
+    temp_names::{self, sanitize},
 };
 
 const PREVIEW_BYTES: usize = 16 * 1024;
-const PREVIEW_TEXT_CHARS: usize = 8 * 1024;
 const MAX_LISTED_SAMPLES: u32 = 5_000;
+const PEEK_PAGE_DEFAULT_SAMPLES: u32 = 20;
+const PEEK_PAGE_MAX_SAMPLES: u32 = 100;
 const MAX_OPEN_BYTES: u64 = 256 * 1024 * 1024;
-
-fn preview_utf8_text(data: &[u8]) -> Option<String> {
-    let raw = match std::str::from_utf8(data) {
-        Ok(text) => text,
-        Err(err) if err.error_len().is_none() => {
-            std::str::from_utf8(&data[..err.valid_up_to()]).ok()?
-        }
-        Err(_) => return None,
-    };
-    Some(raw.chars().take(PREVIEW_TEXT_CHARS).collect())
-}
+const MAX_VALIDATION_ISSUES: usize = 50;
+/// Encodings that `StreamingDataset` decodes to plain values rather than binary objects (images,
+/// bytes, arrays, pickles).
+const JSON_ENCODINGS: &[&str] = &[
+    "str",
+    "str_int",
+    "str_float",
+    "str_decimal",
+    "json",
+    "int",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "float16",
+    "float32",
+    "float64",
+];
 
 #[derive(Deserialize)]
 struct MdsIndexFile {
@@ -284,10 +304,7 @@ fn field_start_offset(
 fn mds_guess_ext(encoding: Option<&str>, data: &[u8]) -> Option<String> {
     let encoding = encoding.unwrap_or("").trim();
     if encoding.is_empty() {
-        if let Some(magic) = detect_magic_ext(data) {
-            return Some(magic);
-        }
-        return infer::get(data).map(|t| t.extension().to_string());
+        return magic::sniff_ext(data);
     }
     let enc_lower = encoding.to_lowercase();
     let map = [
@@ -318,15 +335,15 @@ fn mds_guess_ext(encoding: Option<&str>, data: &[u8]) -> Option<String> {
     ];
     if let Some((_, ext)) = map.iter().find(|(k, _)| *k == enc_lower) {
         if *ext == "bin" {
-            if let Some(magic) = detect_magic_ext(data) {
-                return Some(magic);
+            if let Some(ext) = magic::detect_ext(data) {
+                return Some(ext);
             }
         }
         return Some((*ext).into());
     }
     if enc_lower == "audio" {
-        if let Some(magic) = detect_magic_ext(data) {
-            return Some(magic);
+        if let Some(ext) = magic::detect_ext(data) {
+            return Some(ext);
         }
         return Some("wav".into());
     }
@@ -336,8 +353,8 @@ fn mds_guess_ext(encoding: Option<&str>, data: &[u8]) -> Option<String> {
             return Some(trimmed.to_string());
         }
     }
-    if let Some(magic) = detect_magic_ext(data) {
-        return Some(magic);
+    if let Some(ext) = magic::detect_ext(data) {
+        return Some(ext);
     }
     if std::str::from_utf8(data)
         .map(|s| s.trim().len() > 0)
@@ -348,32 +365,6 @@ fn mds_guess_ext(encoding: Option<&str>, data: &[u8]) -> Option<String> {
     infer::get(data).map(|t| t.extension().to_string())
 }
 
-fn detect_magic_ext(data: &[u8]) -> Option<String> {
-    if audio::is_sphere_file(data) {
-        return Some("sph".into());
-    }
-    if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE" {
-        return Some("wav".into());
-    }
-    if data.len() >= 3 && &data[0..3] == b"ID3" {
-        return Some("mp3".into());
-    }
-    if data.len() >= 2 && data[0] == 0xFF && (data[1] & 0xE0) == 0xE0 {
-        return Some("mp3".into());
-    }
-    if data.len() >= 4 && &data[0..4] == b"fLaC" {
-        return Some("flac".into());
-    }
-    None
-}
-
-fn sanitize(input: &str) -> String {
-    input
-        .chars()
-        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
-        .collect()
-}
-
 fn decode_scalar_to_text(encoding: &str, data: &[u8]) -> Option<String> {
     let enc = encoding.trim().to_lowercase();
     match enc.as_str() {
@@ -444,12 +435,16 @@ fn decode_scalar_to_text(encoding: &str, data: &[u8]) -> Option<String> {
 
 #[tauri::command]
 pub async fn mosaicml_load_index(index_path: String) -> AppResult<IndexSummary> {
-    spawn_blocking(move || mosaicml_load_index_sync(PathBuf::from(index_path)))
-        .await
-        .map_err(|e| AppError::Task(e.to_string()))?
+    spawn_blocking(move || {
+        // Profile file errors are reported by `get_dataset_profile`, not here.
+        let _ = dataset_profile::load(&index_path);
+        mosaicml_load_index_sync(PathBuf::from(index_path))
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn mosaicml_load_index_sync(index_path: PathBuf) -> AppResult<IndexSummary> {
+pub fn mosaicml_load_index_sync(index_path: PathBuf) -> AppResult<IndexSummary> {
     let (root_dir, resolved, index) = parse_index(&index_path)?;
     let first = index
         .shards
@@ -534,7 +529,7 @@ pub async fn mosaicml_list_samples(
         .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn mosaicml_list_samples_sync(
+pub fn mosaicml_list_samples_sync(
     index_path: PathBuf,
     shard_filename: String,
 ) -> AppResult<Vec<ItemMeta>> {
@@ -578,33 +573,62 @@ pub async fn mosaicml_peek_field(
     shard_filename: String,
     item_index: u32,
     field_index: usize,
+    offset: Option<u64>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
 ) -> AppResult<FieldPreview> {
+    let limits = PreviewLimits::new(text_chars, hex_bytes);
     spawn_blocking(move || {
         mosaicml_peek_field_sync(
             PathBuf::from(index_path),
             shard_filename,
             item_index,
             field_index,
+            offset.unwrap_or(0),
+            limits,
         )
     })
     .await
     .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn mosaicml_peek_field_sync(
+/// Previews `offset..offset + PREVIEW_BYTES` of a field, or a longer window when `limits` ask for
+/// more text. Scalar columns are always decoded whole.
+pub fn mosaicml_peek_field_sync(
     index_path: PathBuf,
     shard_filename: String,
     item_index: u32,
     field_index: usize,
+    offset: u64,
+    limits: PreviewLimits,
 ) -> AppResult<FieldPreview> {
     let (root_dir, _resolved, index) = parse_index(&index_path)?;
     let shard = shard_for_filename(&index, &shard_filename)?;
     let raw_path = resolve_raw_shard_path(&root_dir, shard)?;
-    let encoding = shard.column_encodings.get(field_index).map(|s| s.as_str());
-
     let mut fp = File::open(&raw_path)?;
-    let (begin, end) = read_sample_offsets(&mut fp, item_index)?;
-    let sizes = read_variable_sizes(&mut fp, begin, shard)?;
+    peek_field_in(
+        &index_path,
+        &mut fp,
+        shard,
+        item_index,
+        field_index,
+        offset,
+        limits,
+    )
+}
+
+fn peek_field_in(
+    index_path: &Path,
+    fp: &mut File,
+    shard: &MdsShard,
+    item_index: u32,
+    field_index: usize,
+    offset: u64,
+    limits: PreviewLimits,
+) -> AppResult<FieldPreview> {
+    let encoding = shard.column_encodings.get(field_index).map(|s| s.as_str());
+    let (begin, end) = read_sample_offsets(fp, item_index)?;
+    let sizes = read_variable_sizes(fp, begin, shard)?;
     let (field_start, field_size) = field_start_offset(begin, shard, field_index, &sizes)?;
     let available = (end as u64)
         .checked_sub(field_start)
@@ -629,36 +653,331 @@ fn mosaicml_peek_field_sync(
                 | "float64"
         )
     );
+    let skip = if should_read_full {
+        0
+    } else {
+        offset.min(field_size as u64)
+    };
     let desired = if should_read_full {
         field_size as usize
     } else {
-        PREVIEW_BYTES.min(field_size as usize)
+        limits
+            .window(PREVIEW_BYTES)
+            .min((field_size as u64 - skip) as usize)
     };
 
-    fp.seek(SeekFrom::Start(field_start))?;
+    fp.seek(SeekFrom::Start(field_start + skip))?;
     let mut data = vec![0u8; desired];
     fp.read_exact(&mut data)?;
 
-    let preview_text = if let Some(enc) = encoding {
-        if should_read_full {
-            decode_scalar_to_text(enc, &data).map(|s| s.chars().take(PREVIEW_TEXT_CHARS).collect())
-        } else {
-            preview_utf8_text(&data)
-        }
-    } else {
-        preview_utf8_text(&data)
+    let text = match encoding {
+        Some(enc) if should_read_full => decode_scalar_to_text(enc, &data).map(|s| limits.cut(&s)),
+        _ => limits.text(&data),
     };
+    let (preview_text, text_length) = text.unzip();
 
     let guessed_ext = mds_guess_ext(encoding, &data);
-    let hex_snippet = hex_encode(data.iter().take(48).copied().collect::<Vec<u8>>());
     let is_binary = preview_text.is_none();
-    Ok(FieldPreview {
+    let mut preview = FieldPreview {
         preview_text,
-        hex_snippet,
+        text_length,
+        hex_snippet: limits.hex(&data),
         guessed_ext,
         is_binary,
-        size: field_size,
+        size: u64::from(field_size),
+        metadata: metadata::extract(&data),
+        plugin: None,
+        kind: PreviewKind::Binary,
+        compression: None,
+    };
+    if let Some(rule) = decode_override(index_path, shard, field_index) {
+        rule.apply_to_preview(&data, &mut preview);
+    }
+    decoder_plugins::apply(&data, &mut preview);
+    preview.kind = PreviewKind::detect(&data, &preview);
+    Ok(preview)
+}
+
+/// The user's decode override for a column, keyed by column name or encoding.
+fn decode_override(
+    index_path: &Path,
+    shard: &MdsShard,
+    field_index: usize,
+) -> Option<DecodeOverride> {
+    let name = shard
+        .column_names
+        .get(field_index)
+        .map_or("", String::as_str);
+    let encoding = shard
+        .column_encodings
+        .get(field_index)
+        .map_or("", String::as_str);
+    decode_profile::lookup(&index_path.to_string_lossy(), &[name, encoding])
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MdsSamplePreview {
+    item_index: u32,
+    /// One preview per column, in column order.
+    fields: Vec<FieldPreview>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MdsPeekPageResponse {
+    offset: u32,
+    num_samples_total: u32,
+    samples: Vec<MdsSamplePreview>,
+}
+
+/// Head previews of every column of `offset..offset + length`, read with one open of the shard.
+#[tauri::command]
+pub async fn mosaicml_peek_page(
+    index_path: String,
+    shard_filename: String,
+    offset: Option<u32>,
+    length: Option<u32>,
+) -> AppResult<MdsPeekPageResponse> {
+    spawn_blocking(move || {
+        mosaicml_peek_page_sync(
+            PathBuf::from(index_path),
+            shard_filename,
+            offset.unwrap_or(0),
+            length.unwrap_or(PEEK_PAGE_DEFAULT_SAMPLES),
+        )
     })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn mosaicml_peek_page_sync(
+    index_path: PathBuf,
+    shard_filename: String,
+    offset: u32,
+    length: u32,
+) -> AppResult<MdsPeekPageResponse> {
+    let (root_dir, _resolved, index) = parse_index(&index_path)?;
+    let shard = shard_for_filename(&index, &shard_filename)?;
+    let raw_path = resolve_raw_shard_path(&root_dir, shard)?;
+    let mut fp = File::open(&raw_path)?;
+
+    let mut num_buf = [0u8; 4];
+    fp.read_exact(&mut num_buf)?;
+    let total = shard.samples.min(read_le_u32(&num_buf)?);
+    let end = offset
+        .saturating_add(length.clamp(1, PEEK_PAGE_MAX_SAMPLES))
+        .min(total);
+
+    let mut samples = Vec::new();
+    for item_index in offset..end {
+        let fields = (0..shard.column_names.len())
+            .map(|field_index| {
+                peek_field_in(
+                    &index_path,
+                    &mut fp,
+                    shard,
+                    item_index,
+                    field_index,
+                    0,
+                    PreviewLimits::default(),
+                )
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+        samples.push(MdsSamplePreview { item_index, fields });
+    }
+    Ok(MdsPeekPageResponse {
+        offset,
+        num_samples_total: total,
+        samples,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MdsSampleJson {
+    pub item_index: u32,
+    /// Column name to decoded value; binary columns hold an `{encoding, size, ext}` summary.
+    pub sample: serde_json::Map<String, serde_json::Value>,
+    /// Columns in `sample` that were summarized rather than decoded.
+    pub binary_columns: Vec<String>,
+}
+
+/// A column with one of the `JSON_ENCODINGS` decoded the way `StreamingDataset` yields it.
+fn decode_json_value(encoding: &str, data: &[u8]) -> AppResult<serde_json::Value> {
+    use serde_json::Value;
+    let text = || String::from_utf8_lossy(data).into_owned();
+    let value = match encoding {
+        "str" | "str_decimal" => Value::String(text()),
+        "str_int" => text()
+            .trim()
+            .parse::<i64>()
+            .map_or_else(|_| Value::String(text()), Value::from),
+        "str_float" => text()
+            .trim()
+            .parse::<f64>()
+            .map_or_else(|_| Value::String(text()), Value::from),
+        "json" => serde_json::from_slice(data)
+            .map_err(|e| AppError::Invalid(format!("json column does not parse: {e}")))?,
+        "float16" => {
+            let raw: [u8; 2] = data.try_into().map_err(|_| AppError::MalformedChunk)?;
+            Value::from(embeddings::f16_to_f64(u16::from_le_bytes(raw)))
+        }
+        _ => {
+            let text = decode_scalar_to_text(encoding, data).ok_or(AppError::MalformedChunk)?;
+            if encoding.starts_with("float") {
+                Value::from(text.parse::<f64>().unwrap_or(f64::NAN))
+            } else if encoding.starts_with('u') {
+                Value::from(text.parse::<u64>().unwrap_or_default())
+            } else {
+                Value::from(text.parse::<i64>().unwrap_or_default())
+            }
+        }
+    };
+    Ok(value)
+}
+
+/// Every column of one sample decoded into a single JSON object, as `StreamingDataset` would
+/// yield it.
+#[tauri::command]
+pub async fn mosaicml_get_sample_json(
+    index_path: String,
+    shard_filename: String,
+    item_index: u32,
+) -> AppResult<MdsSampleJson> {
+    spawn_blocking(move || {
+        mosaicml_get_sample_json_sync(PathBuf::from(index_path), shard_filename, item_index)
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn mosaicml_get_sample_json_sync(
+    index_path: PathBuf,
+    shard_filename: String,
+    item_index: u32,
+) -> AppResult<MdsSampleJson> {
+    let (root_dir, _resolved, index) = parse_index(&index_path)?;
+    let shard = shard_for_filename(&index, &shard_filename)?;
+    if item_index >= shard.samples {
+        return Err(AppError::Invalid("item index out of range".into()));
+    }
+    let raw_path = resolve_raw_shard_path(&root_dir, shard)?;
+    let mut fp = File::open(&raw_path)?;
+
+    let (begin, end) = read_sample_offsets(&mut fp, item_index)?;
+    let sizes = read_variable_sizes(&mut fp, begin, shard)?;
+    let mut sample = serde_json::Map::new();
+    let mut binary_columns = Vec::new();
+    for (field_index, name) in shard.column_names.iter().enumerate() {
+        let encoding = shard
+            .column_encodings
+            .get(field_index)
+            .map(|s| s.trim().to_lowercase())
+            .unwrap_or_default();
+        let (field_start, field_size) = field_start_offset(begin, shard, field_index, &sizes)?;
+        if field_start + field_size as u64 > end as u64 {
+            return Err(AppError::MalformedChunk);
+        }
+        let decodes = JSON_ENCODINGS.contains(&encoding.as_str());
+        let read_len = if decodes {
+            if field_size as u64 > MAX_OPEN_BYTES {
+                return Err(AppError::Invalid(format!(
+                    "column {name} is too large to decode ({field_size} bytes, max {MAX_OPEN_BYTES})"
+                )));
+            }
+            field_size as usize
+        } else {
+            // Enough of the head for magic-byte extension sniffing.
+            (field_size as usize).min(64)
+        };
+        fp.seek(SeekFrom::Start(field_start))?;
+        let mut data = vec![0u8; read_len];
+        fp.read_exact(&mut data)?;
+
+        let value = if decodes {
+            decode_json_value(&encoding, &data)?
+        } else {
+            binary_columns.push(name.clone());
+            serde_json::json!({
+                "encoding": encoding,
+                "size": field_size,
+                "ext": mds_guess_ext(Some(&encoding), &data).unwrap_or_else(|| "bin".into()),
+            })
+        };
+        sample.insert(name.clone(), value);
+    }
+    Ok(MdsSampleJson {
+        item_index,
+        sample,
+        binary_columns,
+    })
+}
+
+/// 2D PCA or UMAP projection of a float vector column over an evenly strided subset of the
+/// dataset.
+#[tauri::command]
+pub async fn mosaicml_embedding_projection(
+    index_path: String,
+    field_index: usize,
+    max_samples: Option<usize>,
+    method: Option<ProjectionMethod>,
+) -> AppResult<EmbeddingProjection> {
+    let method = method.unwrap_or_default();
+    spawn_blocking(move || {
+        mosaicml_embedding_projection_sync(
+            PathBuf::from(index_path),
+            field_index,
+            max_samples
+                .unwrap_or(embeddings::DEFAULT_PROJECTION_SAMPLES)
+                .clamp(2, method.max_samples()),
+            method,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn mosaicml_embedding_projection_sync(
+    index_path: PathBuf,
+    field_index: usize,
+    max_samples: usize,
+    method: ProjectionMethod,
+) -> AppResult<EmbeddingProjection> {
+    let (root_dir, _resolved, index) = parse_index(&index_path)?;
+    let total = index.shards.iter().map(|s| s.samples as u64).sum::<u64>();
+    let stride = total.div_ceil(max_samples as u64).max(1);
+
+    let mut vectors = Vec::new();
+    let mut skipped = 0;
+    let mut shard_start = 0u64;
+    for shard in &index.shards {
+        let shard_end = shard_start + shard.samples as u64;
+        // First strided sample at or after this shard's start.
+        let first = shard_start.div_ceil(stride) * stride;
+        if first < shard_end {
+            let encoding = shard
+                .column_encodings
+                .get(field_index)
+                .ok_or_else(|| AppError::Invalid("field index out of range".into()))?;
+            let raw_path = resolve_raw_shard_path(&root_dir, shard)?;
+            let mut fp = File::open(&raw_path)?;
+            for global in (first..shard_end).step_by(stride as usize) {
+                let item_index = (global - shard_start) as u32;
+                let values = read_field_full(&mut fp, shard, item_index, field_index)
+                    .ok()
+                    .and_then(|(data, _)| embeddings::mds_values(encoding, &data));
+                match values {
+                    Some(values) => {
+                        vectors.push((format!("{}:{item_index}", shard.raw_data.basename), values))
+                    }
+                    None => skipped += 1,
+                }
+            }
+        }
+        shard_start = shard_end;
+    }
+    embeddings::project_2d(vectors, skipped, method)
 }
 
 fn read_field_full(
@@ -666,7 +985,7 @@ fn read_field_full(
     shard: &MdsShard,
     item_index: u32,
     field_index: usize,
-) -> AppResult<(Vec<u8>, u32)> {
+) -> AppResult<(Vec<u8>, u64)> {
     let (begin, end) = read_sample_offsets(fp, item_index)?;
     let sizes = read_variable_sizes(fp, begin, shard)?;
     let (field_start, field_size) = field_start_offset(begin, shard, field_index, &sizes)?;
@@ -685,11 +1004,70 @@ fn read_field_full(
     fp.seek(SeekFrom::Start(field_start))?;
     let mut data = vec![0u8; field_size as usize];
     fp.read_exact(&mut data)?;
-    Ok((data, field_size))
+    Ok((data, u64::from(field_size)))
+}
+
+/// A field's bytes (scalars as text) after any decode override, its stored size, and the
+/// extension to write it as.
+pub fn decoded_leaf(
+    index_path: &Path,
+    shard_filename: &str,
+    item_index: u32,
+    field_index: usize,
+) -> AppResult<(Vec<u8>, u64, String)> {
+    let (root_dir, _resolved, index) = parse_index(index_path)?;
+    let shard = shard_for_filename(&index, shard_filename)?;
+    let raw_path = resolve_raw_shard_path(&root_dir, shard)?;
+    let encoding = shard.column_encodings.get(field_index).map(|s| s.as_str());
+
+    let mut fp = File::open(&raw_path)?;
+    let (mut data, size) = read_field_full(&mut fp, shard, item_index, field_index)?;
+    let ext = match decode_override(index_path, shard, field_index) {
+        Some(rule) => {
+            let (decoded, ext) = rule.decode_for_open(data);
+            data = decoded;
+            ext
+        }
+        None => {
+            let ext = mds_guess_ext(encoding, &data).unwrap_or_else(|| "bin".into());
+            if let Some(enc) = encoding {
+                if let Some(text) = decode_scalar_to_text(enc, &data) {
+                    if ext == "txt" || ext == "json" {
+                        data = text.into_bytes();
+                    }
+                }
+            }
+            ext
+        }
+    };
+    Ok((data, size, ext))
+}
+
+/// Writes a field to `destination_path` instead of opening it from the temp dir.
+#[tauri::command]
+pub async fn mosaicml_save_leaf_as(
+    index_path: String,
+    shard_filename: String,
+    item_index: u32,
+    field_index: usize,
+    destination_path: String,
+) -> AppResult<SavedLeafResponse> {
+    spawn_blocking(move || {
+        let (data, _size, ext) = decoded_leaf(
+            Path::new(&index_path),
+            &shard_filename,
+            item_index,
+            field_index,
+        )?;
+        save_leaf::write_to(&destination_path, &data, ext)
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
 }
 
 #[tauri::command]
 pub async fn mosaicml_open_leaf(
+    app: AppHandle,
     index_path: String,
     shard_filename: String,
     item_index: u32,
@@ -698,6 +1076,7 @@ pub async fn mosaicml_open_leaf(
 ) -> AppResult<OpenLeafResponse> {
     spawn_blocking(move || {
         mosaicml_open_leaf_sync(
+            &app,
             PathBuf::from(index_path),
             shard_filename,
             item_index,
@@ -710,28 +1089,14 @@ pub async fn mosaicml_open_leaf(
 }
 
 fn mosaicml_open_leaf_sync(
+    app: &AppHandle,
     index_path: PathBuf,
     shard_filename: String,
     item_index: u32,
     field_index: usize,
     opener_app_path: Option<&str>,
 ) -> AppResult<OpenLeafResponse> {
-    let (root_dir, _resolved, index) = parse_index(&index_path)?;
-    let shard = shard_for_filename(&index, &shard_filename)?;
-    let raw_path = resolve_raw_shard_path(&root_dir, shard)?;
-    let encoding = shard.column_encodings.get(field_index).map(|s| s.as_str());
-
-    let mut fp = File::open(&raw_path)?;
-    let (mut data, size) = read_field_full(&mut fp, shard, item_index, field_index)?;
-    let ext = mds_guess_ext(encoding, &data).unwrap_or_else(|| "bin".into());
-
-    if let Some(enc) = encoding {
-        if let Some(text) = decode_scalar_to_text(enc, &data) {
-            if ext == "txt" || ext == "json" {
-                data = text.into_bytes();
-            }
-        }
-    }
+    let (data, size, ext) = decoded_leaf(&index_path, &shard_filename, item_index, field_index)?;
 
     let temp_dir = std::env::temp_dir().join("dataset-inspector");
     fs::create_dir_all(&temp_dir)?;
@@ -742,12 +1107,11 @@ fn mosaicml_open_leaf_sync(
         field_index
     );
 
-    let mut out = temp_dir.join(format!("{base_name}.{ext}"));
-    fs::write(&out, &data)?;
+    let mut out = temp_names::write(&temp_dir, &base_name, &ext, &data)?;
 
     let mut ext = ext;
     if ext == "sph" {
-        let wav_out = temp_dir.join(format!("{base_name}.wav"));
+        let wav_out = out.with_extension("wav");
         match audio::write_sph_as_wav_with_fallback(&data, &out, &wav_out) {
             Ok(()) => {
                 out = wav_out;
@@ -757,27 +1121,16 @@ fn mosaicml_open_leaf_sync(
         }
     }
 
-    let mut opened = false;
-    let mut open_error: Option<String> = None;
-    if let Some(app_path) = opener_app_path {
-        match open_with::open_with_app_detached(&out, app_path) {
-            Ok(()) => opened = true,
-            Err(err) => open_error = Some(err),
-        }
-    }
-    if !opened {
-        if let Err(err) = open::that_detached(&out) {
-            open_error = Some(err.to_string());
-        } else {
-            opened = true;
-        }
-    }
+    let opened = open_with::open_extracted(app, &out, opener_app_path).is_ok();
 
-    let base = format!("{} ({} bytes)", out.display(), size);
+    let base = messages::text(
+        Message::FileWithSize,
+        &[("path", &out.display()), ("size", &size)],
+    );
     let mut message = base;
-    let needs_opener = !opened && open_error.is_some();
+    let needs_opener = !opened;
     if needs_opener {
-        message.push_str(" · no default app found, choose an app to open it");
+        message.push_str(&messages::text(Message::NoDefaultApp, &[]));
     }
 
     Ok(OpenLeafResponse {
@@ -832,12 +1185,11 @@ fn mosaicml_prepare_audio_preview_sync(
         item_index,
         field_index
     );
-    let mut out = temp_dir.join(format!("{base_name}.{ext}"));
-    fs::write(&out, &data)?;
+    let mut out = temp_names::write(&temp_dir, &base_name, &ext, &data)?;
 
     let mut ext = ext;
     if ext == "sph" {
-        let wav_out = temp_dir.join(format!("{base_name}.wav"));
+        let wav_out = out.with_extension("wav");
         audio::write_sph_as_wav_with_fallback(&data, &out, &wav_out)
             .map_err(|e| AppError::Invalid(format!("sph decode failed: {e}")))?;
         out = wav_out;
@@ -851,6 +1203,330 @@ fn mosaicml_prepare_audio_preview_sync(
     })
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MdsHashCheck {
+    algorithm: String,
+    target: String,
+    expected: String,
+    actual: Option<String>,
+    matches: Option<bool>,
+    note: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MdsShardValidation {
+    filename: String,
+    exists: bool,
+    expected_samples: u32,
+    header_samples: Option<u32>,
+    expected_bytes: u64,
+    actual_bytes: Option<u64>,
+    issues: Vec<String>,
+    hash_checks: Vec<MdsHashCheck>,
+    ok: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MdsValidationReport {
+    index_path: String,
+    shards_total: usize,
+    shards_ok: usize,
+    shards: Vec<MdsShardValidation>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MdsValidateProgress {
+    index_path: String,
+    completed: usize,
+    shards_total: usize,
+    filename: String,
+}
+
+fn push_issue(issues: &mut Vec<String>, issue: String) {
+    if issues.len() < MAX_VALIDATION_ISSUES {
+        issues.push(issue);
+    }
+}
+
+fn check_shard_layout(
+    raw_path: &Path,
+    shard: &MdsShard,
+    report: &mut MdsShardValidation,
+) -> AppResult<()> {
+    let mut fp = File::open(raw_path)?;
+    let file_len = fp.metadata()?.len();
+    report.actual_bytes = Some(file_len);
+    if file_len != shard.raw_data.bytes {
+        push_issue(
+            &mut report.issues,
+            format!(
+                "size mismatch: index says {} bytes, file has {file_len}",
+                shard.raw_data.bytes
+            ),
+        );
+    }
+
+    let mut num_buf = [0u8; 4];
+    fp.read_exact(&mut num_buf)?;
+    let num = read_le_u32(&num_buf)?;
+    report.header_samples = Some(num);
+    if num != shard.samples {
+        push_issue(
+            &mut report.issues,
+            format!(
+                "sample count mismatch: index says {}, shard header says {num}",
+                shard.samples
+            ),
+        );
+    }
+
+    let table_len = (num as u64 + 1) * 4;
+    if 4 + table_len > file_len {
+        push_issue(
+            &mut report.issues,
+            "offset table extends past the end of the file".into(),
+        );
+        return Ok(());
+    }
+    let mut table = vec![0u8; table_len as usize];
+    fp.read_exact(&mut table)?;
+    let offsets: Vec<u32> = table
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+
+    let data_start = 4 + table_len;
+    let first = offsets.first().copied().unwrap_or(0) as u64;
+    if first != data_start {
+        push_issue(
+            &mut report.issues,
+            format!("first sample offset {first} does not follow the offset table ({data_start})"),
+        );
+    }
+    let last = offsets.last().copied().unwrap_or(0) as u64;
+    if last != file_len {
+        push_issue(
+            &mut report.issues,
+            format!("last sample offset {last} does not match the file size {file_len}"),
+        );
+    }
+
+    let var_cols = shard.column_sizes.iter().filter(|s| s.is_none()).count() as u64;
+    for idx in 0..num as usize {
+        if report.issues.len() >= MAX_VALIDATION_ISSUES {
+            break;
+        }
+        let (begin, end) = (offsets[idx], offsets[idx + 1]);
+        if end < begin {
+            push_issue(
+                &mut report.issues,
+                format!("sample {idx}: offsets are not monotonic ({begin} > {end})"),
+            );
+            continue;
+        }
+        if end as u64 > file_len {
+            push_issue(
+                &mut report.issues,
+                format!("sample {idx}: extends past the end of the file"),
+            );
+            continue;
+        }
+        let sizes = read_variable_sizes(&mut fp, begin, shard)?;
+        let payload = sizes.iter().map(|s| *s as u64).sum::<u64>() + var_cols * 4;
+        let span = (end - begin) as u64;
+        if payload != span {
+            push_issue(
+                &mut report.issues,
+                format!(
+                    "sample {idx}: field sizes sum to {payload} bytes but the sample spans {span}"
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn hash_check(path: &Path, algorithm: &str, target: &str, expected: &str) -> MdsHashCheck {
+    let (actual, matches, note) = match checksum::hash_file(path, algorithm) {
+        Ok(Some(actual)) => {
+            let matches = actual.eq_ignore_ascii_case(expected.trim());
+            (Some(actual), Some(matches), None)
+        }
+        Ok(None) => (None, None, Some("unsupported hash algorithm".to_string())),
+        Err(err) => (None, Some(false), Some(err.to_string())),
+    };
+    MdsHashCheck {
+        algorithm: algorithm.to_string(),
+        target: target.to_string(),
+        expected: expected.to_string(),
+        actual,
+        matches,
+        note,
+    }
+}
+
+fn validate_shard(root_dir: &Path, shard: &MdsShard, verify_hashes: bool) -> MdsShardValidation {
+    let mut report = MdsShardValidation {
+        filename: shard.raw_data.basename.clone(),
+        exists: false,
+        expected_samples: shard.samples,
+        header_samples: None,
+        expected_bytes: shard.raw_data.bytes,
+        actual_bytes: None,
+        issues: Vec::new(),
+        hash_checks: Vec::new(),
+        ok: false,
+    };
+    let raw_path = match resolve_raw_shard_path(root_dir, shard) {
+        Ok(path) => path,
+        Err(err) => {
+            report.issues.push(format!("shard data unavailable: {err}"));
+            return report;
+        }
+    };
+    report.exists = true;
+
+    if let Err(err) = check_shard_layout(&raw_path, shard, &mut report) {
+        push_issue(&mut report.issues, format!("read failed: {err}"));
+    }
+
+    if verify_hashes {
+        for algo in &shard.hashes {
+            if let Some(expected) = shard.raw_data.hashes.get(algo) {
+                report
+                    .hash_checks
+                    .push(hash_check(&raw_path, algo, "raw", expected));
+            }
+            if let Some(zip) = shard.zip_data.as_ref() {
+                let zip_path = root_dir.join(&zip.basename);
+                if let (true, Some(expected)) = (zip_path.exists(), zip.hashes.get(algo)) {
+                    report
+                        .hash_checks
+                        .push(hash_check(&zip_path, algo, "zip", expected));
+                }
+            }
+        }
+    }
+
+    report.ok =
+        report.issues.is_empty() && report.hash_checks.iter().all(|c| c.matches != Some(false));
+    report
+}
+
+#[tauri::command]
+pub async fn mosaicml_validate(
+    app: AppHandle,
+    index_path: String,
+    verify_hashes: Option<bool>,
+) -> AppResult<MdsValidationReport> {
+    spawn_blocking(move || {
+        mosaicml_validate_sync(
+            &app,
+            PathBuf::from(index_path),
+            verify_hashes.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+pub fn mosaicml_validate_sync(
+    app: &AppHandle,
+    index_path: PathBuf,
+    verify_hashes: bool,
+) -> AppResult<MdsValidationReport> {
+    let (root_dir, resolved, index) = parse_index(&index_path)?;
+    let index_display = resolved.display().to_string();
+    let shards_total = index.shards.len();
+    let mut shards = Vec::with_capacity(shards_total);
+    for (idx, shard) in index.shards.iter().enumerate() {
+        shards.push(validate_shard(&root_dir, shard, verify_hashes));
+        let _ = app.emit(
+            "mds://validate-progress",
+            MdsValidateProgress {
+                index_path: index_display.clone(),
+                completed: idx + 1,
+                shards_total,
+                filename: shard.raw_data.basename.clone(),
+            },
+        );
+    }
+    let shards_ok = shards.iter().filter(|s| s.ok).count();
+    Ok(MdsValidationReport {
+        index_path: index_display,
+        shards_total,
+        shards_ok,
+        shards,
+    })
+}
+
+fn shard_columns(shard: &MdsShard) -> Vec<(String, String)> {
+    shard
+        .column_names
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            let encoding = shard.column_encodings.get(idx).cloned().unwrap_or_default();
+            (name.clone(), encoding)
+        })
+        .collect()
+}
+
+/// Column names and encodings of one shard.
+pub fn mds_shard_columns(
+    index_path: &Path,
+    shard_filename: &str,
+) -> AppResult<(String, Vec<(String, String)>)> {
+    let (_root_dir, resolved, index) = parse_index(index_path)?;
+    let shard = shard_for_filename(&index, shard_filename)?;
+    Ok((resolved.display().to_string(), shard_columns(shard)))
+}
+
+/// Column names and encodings of every shard in the index, with each shard's file name.
+pub fn mds_columns_by_shard(
+    index_path: &Path,
+) -> AppResult<(String, Vec<(String, Vec<(String, String)>)>)> {
+    let (_root_dir, resolved, index) = parse_index(index_path)?;
+    if index.shards.is_empty() {
+        return Err(AppError::Invalid("index.json contains no shards".into()));
+    }
+    let shards = index
+        .shards
+        .iter()
+        .map(|shard| (shard.raw_data.basename.clone(), shard_columns(shard)))
+        .collect();
+    Ok((resolved.display().to_string(), shards))
+}
+
+/// Dataset directory, the sample's index across all shards (its index in a `StreamingDataset`
+/// over the same directory), and the shard's column names.
+pub fn streaming_position(
+    index_path: &Path,
+    shard_filename: &str,
+    item_index: u32,
+) -> AppResult<(PathBuf, u64, Vec<String>)> {
+    let (root_dir, _resolved, index) = parse_index(index_path)?;
+    let shard = shard_for_filename(&index, shard_filename)?;
+    if item_index >= shard.samples {
+        return Err(AppError::Invalid("item index out of range".into()));
+    }
+    let before = index
+        .shards
+        .iter()
+        .take_while(|s| !std::ptr::eq(*s, shard))
+        .map(|s| s.samples as u64)
+        .sum::<u64>();
+    Ok((
+        root_dir,
+        before + item_index as u64,
+        shard.column_names.clone(),
+    ))
+}
+
 pub fn detect_mds_index_path(path: &Path) -> Option<String> {
     let resolved = resolve_index_path(path).ok()?;
     let bytes = read_index_bytes(&resolved).ok()?;
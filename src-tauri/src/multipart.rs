@@ -0,0 +1,132 @@
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Archive extensions that may be split into volumes.
+const VOLUME_BASE_EXTS: &[&str] = &[".tar", ".tar.gz", ".tgz", ".tar.zst", ".tar.zstd"];
+
+/// One volume of a split archive, e.g. `data.tar.gz` + `.part` + `ab`.
+struct Volume<'a> {
+    base: &'a str,
+    sep: &'a str,
+    suffix: &'a str,
+}
+
+/// Recognizes `split` output (`data.tar.gz.partaa`, `data.tar.aa`) and numbered volumes
+/// (`data.tar.00`, `data.tar.gz.001`).
+fn parse_volume(filename: &str) -> Option<Volume<'_>> {
+    let lower = filename.to_ascii_lowercase();
+    if VOLUME_BASE_EXTS.iter().any(|ext| lower.ends_with(ext)) {
+        return None;
+    }
+    let (head, suffix) = filename.rsplit_once('.')?;
+    let (base, sep, suffix) = match suffix.strip_prefix("part") {
+        Some(rest) if !rest.is_empty() => (head, ".part", rest),
+        _ => (head, ".", suffix),
+    };
+    let numeric = suffix.len() >= 2 && suffix.bytes().all(|b| b.is_ascii_digit());
+    let alpha = suffix.len() >= 2 && suffix.bytes().all(|b| b.is_ascii_lowercase());
+    if !(numeric || alpha) {
+        return None;
+    }
+    let base_lower = base.to_ascii_lowercase();
+    if !VOLUME_BASE_EXTS.iter().any(|ext| base_lower.ends_with(ext)) {
+        return None;
+    }
+    Some(Volume { base, sep, suffix })
+}
+
+fn next_suffix(suffix: &str) -> Option<String> {
+    if suffix.bytes().all(|b| b.is_ascii_digit()) {
+        let next = suffix.parse::<u64>().ok()? + 1;
+        let out = format!("{next:0width$}", width = suffix.len());
+        return (out.len() == suffix.len()).then_some(out);
+    }
+    let mut bytes = suffix.as_bytes().to_vec();
+    for b in bytes.iter_mut().rev() {
+        if *b < b'z' {
+            *b += 1;
+            return String::from_utf8(bytes).ok();
+        }
+        *b = b'a';
+    }
+    None
+}
+
+/// True for the volume a split archive starts at: `aa`, `00`, or `001` for 7-Zip style numbering.
+pub fn is_first_volume(filename: &str) -> bool {
+    parse_volume(filename)
+        .is_some_and(|v| v.suffix.bytes().all(|b| b == b'a' || b == b'0') || v.suffix == "001")
+}
+
+/// Name of the combined archive, which decides how the concatenated bytes are decompressed.
+pub fn volume_base(filename: &str) -> Option<&str> {
+    parse_volume(filename).map(|v| v.base)
+}
+
+pub fn next_volume_name(filename: &str) -> Option<String> {
+    let v = parse_volume(filename)?;
+    Some(format!("{}{}{}", v.base, v.sep, next_suffix(v.suffix)?))
+}
+
+/// The first volume followed by every consecutively numbered sibling that exists on disk.
+pub fn local_volume_paths(first: &Path) -> Vec<PathBuf> {
+    let mut out = vec![first.to_path_buf()];
+    let dir = first.parent().unwrap_or_else(|| Path::new("."));
+    let mut name = first
+        .file_name()
+        .and_then(|f| f.to_str())
+        .map(|s| s.to_string());
+    while let Some(next) = name.as_deref().and_then(next_volume_name) {
+        let path = dir.join(&next);
+        if !path.is_file() {
+            break;
+        }
+        out.push(path);
+        name = Some(next);
+    }
+    out
+}
+
+type OpenVolume = Box<dyn FnMut(&str) -> io::Result<Option<Box<dyn Read + Send>>> + Send>;
+
+/// Reads a split archive as one stream, opening each following volume only when the previous one
+/// is exhausted. `open_next` returns `None` once the next volume does not exist.
+pub struct VolumeReader {
+    current: Option<Box<dyn Read + Send>>,
+    next_name: Option<String>,
+    open_next: OpenVolume,
+}
+
+impl VolumeReader {
+    pub fn new(first: Box<dyn Read + Send>, first_name: &str, open_next: OpenVolume) -> Self {
+        Self {
+            current: Some(first),
+            next_name: next_volume_name(first_name),
+            open_next,
+        }
+    }
+}
+
+impl Read for VolumeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(reader) = self.current.as_mut() {
+                let n = reader.read(buf)?;
+                if n > 0 || buf.is_empty() {
+                    return Ok(n);
+                }
+                self.current = None;
+            }
+            let Some(name) = self.next_name.take() else {
+                return Ok(0);
+            };
+            match (self.open_next)(&name)? {
+                Some(reader) => {
+                    self.next_name = next_volume_name(&name);
+                    self.current = Some(reader);
+                }
+                None => return Ok(0),
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::app_error::{AppError, AppResult};
+
+/// Longest a directory scan may take before the UI gets an error instead.
+pub const LISTING_TIMEOUT: Duration = Duration::from_secs(20);
+/// Longest a few `stat` calls on one path may take.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+/// A `stat` of the directory slower than this marks its filesystem as slow.
+const SLOW_STAT: Duration = Duration::from_millis(50);
+/// Concurrent `stat` calls on slow filesystems, where each one is a network round trip.
+const STAT_THREADS: usize = 16;
+/// Mount types (as in `/proc/self/mounts`) that are served over the network.
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "9p",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "lustre",
+    "beegfs",
+    "gpfs",
+    "davfs",
+    "fuse.sshfs",
+    "fuse.rclone",
+    "fuse.s3fs",
+    "fuse.gcsfuse",
+];
+
+/// Runs `f` on its own thread and gives up waiting after `timeout`. A thread stuck in a hung
+/// `stat` or `readdir` cannot be interrupted; it is left to finish in the background while the
+/// caller gets an error.
+pub fn with_timeout<T: Send + 'static>(
+    what: &str,
+    timeout: Duration,
+    f: impl FnOnce() -> AppResult<T> + Send + 'static,
+) -> AppResult<T> {
+    let (tx, rx) = mpsc::sync_channel(1);
+    std::thread::Builder::new()
+        .name("fs-probe".into())
+        .spawn(move || {
+            let _ = tx.send(f());
+        })?;
+    match rx.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(AppError::Io(format!(
+            "{what} did not finish within {} s; the network share may be unresponsive",
+            timeout.as_secs()
+        ))),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(AppError::Task(format!("{what} stopped unexpectedly")))
+        }
+    }
+}
+
+/// Undoes the octal escapes (`\040` for a space) in `/proc/self/mounts` fields.
+#[cfg(target_os = "linux")]
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            if let Some(Ok(byte)) = field.get(i + 1..i + 4).map(|o| u8::from_str_radix(o, 8)) {
+                out.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Filesystem type of the mount holding `path`, from the longest matching mount point. The path
+/// is not canonicalized, since resolving it could itself hang.
+#[cfg(target_os = "linux")]
+fn mount_type(path: &Path) -> Option<String> {
+    let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(' ');
+            let point = PathBuf::from(unescape_mount_field(fields.nth(1)?));
+            Some((point, fields.next()?))
+        })
+        .filter(|(point, _)| path.starts_with(point))
+        .max_by_key(|(point, _)| point.as_os_str().len())
+        .map(|(_, kind)| kind.to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mount_type(_path: &Path) -> Option<String> {
+    None
+}
+
+fn is_network_mount(path: &Path) -> bool {
+    // UNC paths (`\\server\share`) on Windows.
+    if cfg!(windows) && path.to_string_lossy().starts_with(r"\\") {
+        return true;
+    }
+    mount_type(path).is_some_and(|kind| NETWORK_FS_TYPES.contains(&kind.as_str()))
+}
+
+/// Whether `dir` is on a network mount, or answers `stat` slowly enough to be treated as one.
+pub fn is_slow(dir: &Path) -> bool {
+    if is_network_mount(dir) {
+        return true;
+    }
+    let started = Instant::now();
+    let _ = std::fs::metadata(dir);
+    started.elapsed() > SLOW_STAT
+}
+
+fn stat_pool() -> &'static ThreadPool {
+    static POOL: OnceLock<ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        ThreadPoolBuilder::new()
+            .num_threads(STAT_THREADS)
+            .thread_name(|i| format!("fs-stat-{i}"))
+            .build()
+            .expect("stat thread pool")
+    })
+}
+
+/// `f` applied to every path, in order. With `parallel`, the calls overlap on a dedicated pool
+/// so that on network shares the round trips are paid once per batch rather than per file.
+pub fn stat_all<T: Send>(
+    paths: &[PathBuf],
+    parallel: bool,
+    f: impl Fn(&Path) -> T + Sync + Send,
+) -> Vec<T> {
+    if !parallel {
+        return paths.iter().map(|p| f(p.as_path())).collect();
+    }
+    stat_pool().install(|| paths.par_iter().map(|p| f(p.as_path())).collect())
+}
@@ -0,0 +1,47 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::AppHandle;
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::messages::{self, Message};
+use crate::settings;
+
+const OFFLINE_MODE_KEY: &str = "offline_mode";
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+pub fn is_enabled() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Error returned by remote paths that have nothing cached to serve while offline.
+pub fn not_cached(url: &Url) -> AppError {
+    AppError::Remote(messages::text(Message::OfflineNotCached, &[("url", &url)]))
+}
+
+/// Fails fast for network paths that have no cache to fall back on.
+pub fn ensure_online(url: &Url) -> AppResult<()> {
+    if is_enabled() {
+        return Err(not_cached(url));
+    }
+    Ok(())
+}
+
+/// Loads the persisted toggle. Called once at startup.
+pub fn load_settings(app: &AppHandle) {
+    if let Ok(Some(enabled)) = settings::read_setting::<bool>(app, OFFLINE_MODE_KEY) {
+        OFFLINE.store(enabled, Ordering::Relaxed);
+    }
+}
+
+#[tauri::command]
+pub async fn get_offline_mode() -> AppResult<bool> {
+    Ok(is_enabled())
+}
+
+#[tauri::command]
+pub async fn set_offline_mode(app: AppHandle, enabled: bool) -> AppResult<()> {
+    settings::write_setting(&app, OFFLINE_MODE_KEY, &enabled)?;
+    OFFLINE.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
@@ -0,0 +1,422 @@
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+
+/// Longest string field read (names, doc strings, metadata values).
+const MAX_STRING_BYTES: u64 = 1024 * 1024;
+/// Largest message whose fields are collected; raw tensor data is skipped, never read.
+const MAX_FIELDS: usize = 4_000_000;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnnxOpset {
+    /// Empty for the default `ai.onnx` domain.
+    domain: String,
+    version: i64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnnxValueInfo {
+    name: String,
+    /// Tensor element type (`float32`, `int64`, ...), or the kind of a non-tensor type.
+    elem_type: Option<String>,
+    /// Each dimension's size, symbolic name, or `?`; `None` when the shape is not declared.
+    shape: Option<Vec<String>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnnxOpCount {
+    op_type: String,
+    count: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnnxSummary {
+    path: String,
+    ir_version: Option<i64>,
+    producer_name: Option<String>,
+    producer_version: Option<String>,
+    domain: Option<String>,
+    model_version: Option<i64>,
+    doc_string: Option<String>,
+    opsets: Vec<OnnxOpset>,
+    graph_name: Option<String>,
+    /// Graph inputs that are not initializers (older exporters list weights as inputs too).
+    inputs: Vec<OnnxValueInfo>,
+    outputs: Vec<OnnxValueInfo>,
+    node_count: usize,
+    /// Nodes per operator type, most frequent first.
+    op_counts: Vec<OnnxOpCount>,
+    initializer_count: usize,
+    /// Elements across all initializers.
+    parameter_count: u64,
+    /// Whether initializer data lives in files next to the model.
+    external_data: bool,
+    metadata: BTreeMap<String, String>,
+}
+
+/// Where a length-delimited field's payload sits in the file.
+#[derive(Clone, Copy)]
+struct Span {
+    offset: u64,
+    len: u64,
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Int(u64),
+    Bytes(Span),
+}
+
+/// Protobuf wire-format reader over a file, skipping payloads it is not asked for.
+struct ProtoFile {
+    reader: BufReader<File>,
+    pos: u64,
+    len: u64,
+}
+
+impl ProtoFile {
+    fn seek_to(&mut self, pos: u64) -> AppResult<()> {
+        if pos != self.pos {
+            let delta = i64::try_from(i128::from(pos) - i128::from(self.pos))
+                .map_err(|_| AppError::Invalid("protobuf offset overflow".into()))?;
+            self.reader.seek_relative(delta)?;
+            self.pos = pos;
+        }
+        Ok(())
+    }
+
+    fn byte(&mut self) -> AppResult<u8> {
+        let mut b = [0u8; 1];
+        self.reader.read_exact(&mut b)?;
+        self.pos += 1;
+        Ok(b[0])
+    }
+
+    fn varint(&mut self) -> AppResult<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            value |= u64::from(b & 0x7F) << shift;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(AppError::Invalid("malformed protobuf varint".into()))
+    }
+
+    fn fixed(&mut self, len: usize) -> AppResult<u64> {
+        let mut buf = [0u8; 8];
+        self.reader.read_exact(&mut buf[..len])?;
+        self.pos += len as u64;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// The top-level fields of the message in `span`, in file order.
+    fn fields(&mut self, span: Span) -> AppResult<Vec<(u32, Field)>> {
+        let end = span.offset + span.len;
+        if end > self.len {
+            return Err(AppError::Invalid(
+                "protobuf message runs past end of file".into(),
+            ));
+        }
+        self.seek_to(span.offset)?;
+        let mut out = Vec::new();
+        while self.pos < end {
+            if out.len() >= MAX_FIELDS {
+                return Err(AppError::Invalid(
+                    "protobuf message has too many fields".into(),
+                ));
+            }
+            let key = self.varint()?;
+            let number = u32::try_from(key >> 3)
+                .map_err(|_| AppError::Invalid("malformed protobuf field number".into()))?;
+            let field = match key & 7 {
+                0 => Field::Int(self.varint()?),
+                1 => Field::Int(self.fixed(8)?),
+                5 => Field::Int(self.fixed(4)?),
+                2 => {
+                    let len = self.varint()?;
+                    let span = Span {
+                        offset: self.pos,
+                        len,
+                    };
+                    if span.offset.saturating_add(len) > end {
+                        return Err(AppError::Invalid(
+                            "protobuf field runs past its message".into(),
+                        ));
+                    }
+                    self.seek_to(span.offset + len)?;
+                    Field::Bytes(span)
+                }
+                wire => {
+                    return Err(AppError::Invalid(format!(
+                        "unsupported protobuf wire type {wire}"
+                    )))
+                }
+            };
+            out.push((number, field));
+        }
+        Ok(out)
+    }
+
+    fn string(&mut self, span: Span) -> AppResult<String> {
+        self.seek_to(span.offset)?;
+        let mut buf = Vec::new();
+        (&mut self.reader)
+            .take(span.len.min(MAX_STRING_BYTES))
+            .read_to_end(&mut buf)?;
+        self.pos += buf.len() as u64;
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    /// Packed or unpacked repeated varints: each `Int` is one value, each `Bytes` a packed run.
+    fn ints(&mut self, values: Vec<Field>) -> AppResult<Vec<i64>> {
+        let mut out = Vec::new();
+        for value in values {
+            match value {
+                Field::Int(v) => out.push(v as i64),
+                Field::Bytes(span) => {
+                    self.seek_to(span.offset)?;
+                    while self.pos < span.offset + span.len {
+                        out.push(self.varint()? as i64);
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+fn int_of(fields: &[(u32, Field)], number: u32) -> Option<i64> {
+    fields.iter().rev().find_map(|(n, f)| match f {
+        Field::Int(v) if *n == number => Some(*v as i64),
+        _ => None,
+    })
+}
+
+fn span_of(fields: &[(u32, Field)], number: u32) -> Option<Span> {
+    fields.iter().rev().find_map(|(n, f)| match f {
+        Field::Bytes(span) if *n == number => Some(*span),
+        _ => None,
+    })
+}
+
+fn spans_of(fields: &[(u32, Field)], number: u32) -> impl Iterator<Item = Span> + '_ {
+    fields.iter().filter_map(move |(n, f)| match f {
+        Field::Bytes(span) if *n == number => Some(*span),
+        _ => None,
+    })
+}
+
+fn string_of(
+    proto: &mut ProtoFile,
+    fields: &[(u32, Field)],
+    number: u32,
+) -> AppResult<Option<String>> {
+    span_of(fields, number)
+        .map(|span| proto.string(span))
+        .transpose()
+}
+
+/// `TensorProto.DataType` names.
+fn elem_type_name(code: i64) -> String {
+    let name = match code {
+        1 => "float32",
+        2 => "uint8",
+        3 => "int8",
+        4 => "uint16",
+        5 => "int16",
+        6 => "int32",
+        7 => "int64",
+        8 => "string",
+        9 => "bool",
+        10 => "float16",
+        11 => "float64",
+        12 => "uint32",
+        13 => "uint64",
+        14 => "complex64",
+        15 => "complex128",
+        16 => "bfloat16",
+        17 => "float8e4m3fn",
+        18 => "float8e4m3fnuz",
+        19 => "float8e5m2",
+        20 => "float8e5m2fnuz",
+        21 => "uint4",
+        22 => "int4",
+        23 => "float4e2m1",
+        other => return format!("type {other}"),
+    };
+    name.to_string()
+}
+
+/// A `ValueInfoProto`: name and (for tensors) element type and shape.
+fn value_info(proto: &mut ProtoFile, span: Span) -> AppResult<OnnxValueInfo> {
+    let fields = proto.fields(span)?;
+    let name = string_of(proto, &fields, 1)?.unwrap_or_default();
+    let mut info = OnnxValueInfo {
+        name,
+        elem_type: None,
+        shape: None,
+    };
+    let Some(type_span) = span_of(&fields, 2) else {
+        return Ok(info);
+    };
+    let type_fields = proto.fields(type_span)?;
+    // TypeProto oneof: tensor_type = 1, sparse_tensor_type = 8.
+    let Some(tensor) = span_of(&type_fields, 1).or_else(|| span_of(&type_fields, 8)) else {
+        info.elem_type = type_fields.iter().find_map(|(n, _)| match n {
+            4 => Some("sequence".to_string()),
+            5 => Some("map".to_string()),
+            9 => Some("optional".to_string()),
+            _ => None,
+        });
+        return Ok(info);
+    };
+    let tensor_fields = proto.fields(tensor)?;
+    info.elem_type = int_of(&tensor_fields, 1).map(elem_type_name);
+    if let Some(shape) = span_of(&tensor_fields, 2) {
+        let dims = proto.fields(shape)?;
+        let mut out = Vec::new();
+        for dim in spans_of(&dims, 1) {
+            let dim_fields = proto.fields(dim)?;
+            let label = match int_of(&dim_fields, 1) {
+                Some(size) => size.to_string(),
+                None => string_of(proto, &dim_fields, 2)?.unwrap_or_else(|| "?".into()),
+            };
+            out.push(label);
+        }
+        info.shape = Some(out);
+    }
+    Ok(info)
+}
+
+fn inspect(path: &Path) -> AppResult<OnnxSummary> {
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut proto = ProtoFile {
+        reader: BufReader::new(file),
+        pos: 0,
+        len,
+    };
+    let model = proto
+        .fields(Span { offset: 0, len })
+        .map_err(|e| AppError::Invalid(format!("not an ONNX model: {e}")))?;
+    let graph_span = span_of(&model, 7)
+        .ok_or_else(|| AppError::Invalid("not an ONNX model: no graph".into()))?;
+
+    let mut opsets = Vec::new();
+    for span in spans_of(&model, 8) {
+        let fields = proto.fields(span)?;
+        opsets.push(OnnxOpset {
+            domain: string_of(&mut proto, &fields, 1)?.unwrap_or_default(),
+            version: int_of(&fields, 2).unwrap_or(0),
+        });
+    }
+    let mut metadata = BTreeMap::new();
+    for span in spans_of(&model, 14) {
+        let fields = proto.fields(span)?;
+        let key = string_of(&mut proto, &fields, 1)?.unwrap_or_default();
+        let value = string_of(&mut proto, &fields, 2)?.unwrap_or_default();
+        metadata.insert(key, value);
+    }
+
+    let graph = proto.fields(graph_span)?;
+    let mut op_counts = HashMap::<String, usize>::new();
+    let nodes = spans_of(&graph, 1).collect::<Vec<_>>();
+    for span in &nodes {
+        let fields = proto.fields(*span)?;
+        let op_type = string_of(&mut proto, &fields, 4)?.unwrap_or_default();
+        let op = match string_of(&mut proto, &fields, 7)? {
+            Some(domain) if !domain.is_empty() && domain != "ai.onnx" => {
+                format!("{domain}::{op_type}")
+            }
+            _ => op_type,
+        };
+        *op_counts.entry(op).or_default() += 1;
+    }
+    let mut op_counts = op_counts
+        .into_iter()
+        .map(|(op_type, count)| OnnxOpCount { op_type, count })
+        .collect::<Vec<_>>();
+    op_counts.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.op_type.cmp(&b.op_type))
+    });
+
+    // TensorProto: dims = 1, name = 8, data_location = 14 (1 = EXTERNAL).
+    let mut initializer_names = HashSet::new();
+    let mut parameter_count = 0u64;
+    let mut external_data = false;
+    let initializers = spans_of(&graph, 5).collect::<Vec<_>>();
+    for span in &initializers {
+        let fields = proto.fields(*span)?;
+        let dims = fields
+            .iter()
+            .filter(|(n, _)| *n == 1)
+            .map(|(_, f)| *f)
+            .collect::<Vec<_>>();
+        let numel = proto
+            .ints(dims)?
+            .into_iter()
+            .try_fold(1u64, |acc, d| acc.checked_mul(u64::try_from(d).ok()?))
+            .unwrap_or(0);
+        parameter_count = parameter_count.saturating_add(numel);
+        external_data |= int_of(&fields, 14) == Some(1);
+        if let Some(name) = string_of(&mut proto, &fields, 8)? {
+            initializer_names.insert(name);
+        }
+    }
+
+    let mut inputs = Vec::new();
+    for span in spans_of(&graph, 11) {
+        let info = value_info(&mut proto, span)?;
+        if !initializer_names.contains(&info.name) {
+            inputs.push(info);
+        }
+    }
+    let mut outputs = Vec::new();
+    for span in spans_of(&graph, 12) {
+        outputs.push(value_info(&mut proto, span)?);
+    }
+
+    Ok(OnnxSummary {
+        path: path.display().to_string(),
+        ir_version: int_of(&model, 1),
+        producer_name: string_of(&mut proto, &model, 2)?,
+        producer_version: string_of(&mut proto, &model, 3)?,
+        domain: string_of(&mut proto, &model, 4)?,
+        model_version: int_of(&model, 5),
+        doc_string: string_of(&mut proto, &model, 6)?,
+        opsets,
+        graph_name: string_of(&mut proto, &graph, 2)?,
+        inputs,
+        outputs,
+        node_count: nodes.len(),
+        op_counts,
+        initializer_count: initializers.len(),
+        parameter_count,
+        external_data,
+        metadata,
+    })
+}
+
+/// Reads an `.onnx` model's header and graph outline (opsets, input/output shapes, operator
+/// counts, parameter count) without loading its weights.
+#[tauri::command]
+pub async fn inspect_onnx_file(path: String) -> AppResult<OnnxSummary> {
+    spawn_blocking(move || inspect(Path::new(path.trim())))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,250 @@
+use serde::Serialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::async_runtime::spawn_blocking;
+use tauri::{AppHandle, State};
+
+use crate::app_error::{AppError, AppResult};
+use crate::huggingface::{self, HfClient};
+use crate::litdata::{self, ChunkCache};
+use crate::messages::{self, Message};
+use crate::mosaicml;
+use crate::quarantine;
+use crate::sample_location::SampleLocation;
+use crate::temp_names::{self, sanitize};
+use crate::webdataset;
+
+/// Leaves extracted per session; more than this is not browsable as one folder anyway.
+const MAX_LEAVES: usize = 500;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenManyFailure {
+    /// Position of the leaf in the request.
+    index: usize,
+    error: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenedSession {
+    /// Folder the leaves were extracted into.
+    path: String,
+    /// File names inside `path`, in request order. Leaves that failed are missing.
+    files: Vec<String>,
+    total_bytes: u64,
+    failed: Vec<OpenManyFailure>,
+    opened: bool,
+    message: String,
+}
+
+/// A fresh folder under the temp dir, so sessions never write into each other.
+fn session_dir() -> AppResult<PathBuf> {
+    let root = std::env::temp_dir()
+        .join("dataset-inspector")
+        .join("sessions");
+    fs::create_dir_all(&root)?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let mut attempt = 0u32;
+    loop {
+        let dir = root.join(format!("{stamp}-{attempt}"));
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => attempt += 1,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+fn missing_field() -> AppError {
+    AppError::Invalid("select a field to open, not a whole sample".into())
+}
+
+/// A leaf's decoded bytes, the extension to write it as, and a name to write it under.
+async fn leaf_bytes(
+    leaf: SampleLocation,
+    cache: &ChunkCache,
+    hf: &HfClient,
+    token: Option<&str>,
+) -> AppResult<(Vec<u8>, String, String)> {
+    match leaf {
+        SampleLocation::Litdata {
+            index_path,
+            chunk_filename,
+            item_index,
+            field_index,
+        } => {
+            let field_index = field_index.ok_or_else(missing_field)?;
+            let name = format!("{}-i{item_index}-f{field_index}", sanitize(&chunk_filename));
+            let cache = cache.clone();
+            let (data, _size, ext) = spawn_blocking(move || {
+                litdata::decoded_leaf(
+                    Path::new(&index_path),
+                    &chunk_filename,
+                    item_index,
+                    field_index,
+                    &cache,
+                )
+            })
+            .await
+            .map_err(|e| AppError::Task(e.to_string()))??;
+            Ok((data, ext, name))
+        }
+        SampleLocation::Mds {
+            index_path,
+            shard_filename,
+            item_index,
+            field_index,
+        } => {
+            let field_index = field_index.ok_or_else(missing_field)?;
+            let name = format!("{}-i{item_index}-f{field_index}", sanitize(&shard_filename));
+            let (data, _size, ext) = spawn_blocking(move || {
+                mosaicml::decoded_leaf(
+                    Path::new(&index_path),
+                    &shard_filename,
+                    item_index,
+                    field_index,
+                )
+            })
+            .await
+            .map_err(|e| AppError::Task(e.to_string()))??;
+            Ok((data, ext, name))
+        }
+        SampleLocation::Webdataset {
+            dir_path,
+            shard_filename,
+            sample_key,
+            field,
+        } => {
+            let field = field
+                .map(|f| f.trim().to_string())
+                .filter(|f| !f.is_empty())
+                .ok_or_else(missing_field)?;
+            let member_path = format!("{}.{field}", sample_key.trim());
+            let name = format!("{}-{}", sanitize(&shard_filename), sanitize(&member_path));
+            let (data, _size, ext) = spawn_blocking(move || {
+                webdataset::decoded_member(Path::new(&dir_path), &shard_filename, &member_path)
+            })
+            .await
+            .map_err(|e| AppError::Task(e.to_string()))??;
+            Ok((data, ext, name))
+        }
+        SampleLocation::Huggingface {
+            dataset,
+            config,
+            split,
+            row_index,
+            field_name,
+        } => {
+            let field_name = field_name.ok_or_else(missing_field)?;
+            let name = format!(
+                "{}-r{row_index}-{}",
+                sanitize(&split),
+                sanitize(&field_name)
+            );
+            let (data, ext) = huggingface::fetch_field(
+                hf,
+                &dataset,
+                config.as_deref().unwrap_or("default"),
+                &split,
+                row_index,
+                &field_name,
+                token,
+            )
+            .await?;
+            Ok((data, ext, name))
+        }
+    }
+}
+
+/// Extracts the selected fields or members into one new session folder and opens the folder
+/// once, instead of launching an app per leaf. Leaves are numbered in request order so the
+/// folder sorts the way they were picked. Leaves that fail are reported and skipped.
+#[tauri::command]
+pub async fn open_many(
+    app: AppHandle,
+    cache: State<'_, ChunkCache>,
+    hf: State<'_, HfClient>,
+    leaves: Vec<SampleLocation>,
+    token: Option<String>,
+) -> AppResult<OpenedSession> {
+    if leaves.is_empty() {
+        return Err(AppError::Invalid("no leaves selected".into()));
+    }
+    if leaves.len() > MAX_LEAVES {
+        return Err(AppError::Invalid(format!(
+            "{} leaves selected; open at most {MAX_LEAVES} at a time",
+            leaves.len()
+        )));
+    }
+
+    let dir = session_dir()?;
+    let width = leaves.len().to_string().len();
+    let mut files = Vec::new();
+    let mut failed = Vec::new();
+    let mut total_bytes = 0u64;
+    for (index, leaf) in leaves.into_iter().enumerate() {
+        let written = leaf_bytes(leaf, &cache, &hf, token.as_deref())
+            .await
+            .and_then(|(data, ext, name)| {
+                let stem = format!("{:0width$}-{name}", index + 1);
+                let path = temp_names::temp_file(&dir, &stem, &ext);
+                fs::write(&path, &data)?;
+                Ok((path, data.len() as u64))
+            });
+        match written {
+            Ok((path, size)) => {
+                quarantine::prepare_temp_file(&app, &path);
+                total_bytes += size;
+                if let Some(name) = path.file_name() {
+                    files.push(name.to_string_lossy().into_owned());
+                }
+            }
+            Err(err) => failed.push(OpenManyFailure {
+                index,
+                error: err.to_string(),
+            }),
+        }
+    }
+    if files.is_empty() {
+        let _ = fs::remove_dir(&dir);
+        let first = failed.first().map(|f| f.error.as_str()).unwrap_or_default();
+        return Err(AppError::Invalid(format!(
+            "none of the selected leaves could be extracted: {first}"
+        )));
+    }
+
+    let (opened, message) = match open::that_detached(&dir) {
+        Ok(()) => (
+            true,
+            messages::text(
+                Message::OpenedFolder,
+                &[
+                    ("path", &dir.display()),
+                    ("count", &files.len()),
+                    ("size", &total_bytes),
+                ],
+            ),
+        ),
+        Err(err) => (
+            false,
+            messages::text(
+                Message::CouldNotOpen,
+                &[("path", &dir.display()), ("error", &err)],
+            ),
+        ),
+    };
+    Ok(OpenedSession {
+        path: dir.display().to_string(),
+        files,
+        total_bytes,
+        failed,
+        opened,
+        message,
+    })
+}
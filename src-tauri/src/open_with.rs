@@ -1,28 +1,39 @@
+use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
-    process::Command,
+    process::{Command, Stdio},
 };
 
-use tauri::async_runtime::spawn_blocking;
+use tauri::{async_runtime::spawn_blocking, AppHandle};
 
 use crate::app_error::{AppError, AppResult};
+use crate::messages::{self, Message};
+use crate::quarantine;
+use crate::settings;
 
 #[tauri::command]
 pub async fn open_path_with_app(path: String, app_path: String) -> AppResult<String> {
     spawn_blocking(move || {
         let target = PathBuf::from(path.trim());
         if !target.exists() {
-            return Err(AppError::Missing("file does not exist".into()));
+            return Err(AppError::Missing(messages::text(Message::FileMissing, &[])));
         }
         if !target.is_file() {
-            return Err(AppError::Invalid("path is not a file".into()));
+            return Err(AppError::Invalid(messages::text(Message::NotAFile, &[])));
         }
         let app_path = app_path.trim();
         if app_path.is_empty() {
-            return Err(AppError::Invalid("app path is empty".into()));
+            return Err(AppError::Invalid(messages::text(
+                Message::AppPathEmpty,
+                &[],
+            )));
         }
         open_with_app_detached(&target, app_path).map_err(AppError::Open)?;
-        Ok(format!("Opened {} with {}", target.display(), app_path))
+        Ok(messages::text(
+            Message::OpenedWith,
+            &[("path", &target.display()), ("app", &app_path)],
+        ))
     })
     .await
     .map_err(|e| AppError::Task(e.to_string()))?
@@ -37,9 +48,382 @@ pub fn open_with_app_detached(target: &Path, app_path: &str) -> Result<(), Strin
             return Ok(());
         }
     }
-    Command::new(app_path)
+    #[cfg(target_os = "linux")]
+    {
+        if app_path.ends_with(".desktop") {
+            return launch_desktop_entry(Path::new(app_path), target);
+        }
+    }
+    let direct = Command::new(app_path)
         .arg(target)
         .spawn()
         .map(|_| ())
+        .map_err(|e| e.to_string());
+    #[cfg(target_os = "windows")]
+    {
+        // Shortcuts and other non-executables can't be spawned directly; fall back to
+        // the shell's "Open with" dialog rather than failing outright.
+        if direct.is_err() && show_open_with_dialog(target).is_ok() {
+            return Ok(());
+        }
+    }
+    direct
+}
+
+/// Launches a `.desktop` entry with `gio launch`, then `gtk-launch`, then by expanding its
+/// `Exec=` line ourselves.
+#[cfg(target_os = "linux")]
+fn launch_desktop_entry(desktop: &Path, target: &Path) -> Result<(), String> {
+    let quiet = |mut cmd: Command| {
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        cmd.status().map(|s| s.success()).unwrap_or(false)
+    };
+    let mut gio = Command::new("gio");
+    gio.arg("launch").arg(desktop).arg(target);
+    if quiet(gio) {
+        return Ok(());
+    }
+    if let Some(id) = desktop.file_name() {
+        let mut gtk = Command::new("gtk-launch");
+        gtk.arg(id).arg(target);
+        if quiet(gtk) {
+            return Ok(());
+        }
+    }
+
+    let text = std::fs::read_to_string(desktop).map_err(|e| e.to_string())?;
+    let exec = text
+        .lines()
+        .map(str::trim)
+        .skip_while(|l| *l != "[Desktop Entry]")
+        .take_while(|l| *l == "[Desktop Entry]" || !l.starts_with('['))
+        .find_map(|l| l.strip_prefix("Exec="))
+        .ok_or_else(|| format!("{} has no Exec line", desktop.display()))?;
+    let target_str = target.to_string_lossy();
+    let mut args = Vec::new();
+    let mut has_file_code = false;
+    for arg in split_command(exec).map_err(|e| e.to_string())? {
+        args.extend(expand_exec_arg(&arg, &target_str, &mut has_file_code));
+    }
+    if !has_file_code {
+        args.push(target_str.to_string());
+    }
+    Command::new(&args[0])
+        .args(&args[1..])
+        .spawn()
+        .map(|_| ())
         .map_err(|e| e.to_string())
 }
+
+/// Expands the field codes in one `Exec=` argument, including codes inside a longer argument
+/// such as `--file=%f`. `None` when the argument held only codes that expand to nothing.
+#[cfg(target_os = "linux")]
+fn expand_exec_arg(arg: &str, target: &str, has_file_code: &mut bool) -> Option<String> {
+    let mut out = String::with_capacity(arg.len());
+    let mut dropped = false;
+    let mut chars = arg.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => out.push('%'),
+            Some('f' | 'F' | 'u' | 'U') => {
+                *has_file_code = true;
+                out.push_str(target);
+            }
+            // `%i`, `%c`, `%k` and the deprecated codes have nothing to expand to here.
+            _ => dropped = true,
+        }
+    }
+    (!dropped || !out.is_empty()).then_some(out)
+}
+
+/// The Windows "Open with" chooser (ShellExecute's `openas` verb).
+#[cfg(target_os = "windows")]
+fn show_open_with_dialog(target: &Path) -> Result<(), String> {
+    Command::new("rundll32.exe")
+        .arg("shell32.dll,OpenAs_RunDLL")
+        .arg(target)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn open_with_system_picker(path: String) -> AppResult<String> {
+    spawn_blocking(move || {
+        let target = PathBuf::from(path.trim());
+        if !target.is_file() {
+            return Err(AppError::Missing(messages::text(Message::FileMissing, &[])));
+        }
+        #[cfg(target_os = "windows")]
+        {
+            show_open_with_dialog(&target).map_err(AppError::Open)?;
+            Ok(messages::text(
+                Message::ChooseAppToOpen,
+                &[("path", &target.display())],
+            ))
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(AppError::Open(messages::text(
+                Message::OpenWithDialogUnavailable,
+                &[],
+            )))
+        }
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+const OPENER_TEMPLATES_KEY: &str = "opener_templates";
+/// Shared with the frontend, which used to write this map directly.
+const OPENERS_BY_EXT_KEY: &str = "openers_by_ext";
+
+/// A user-defined external tool, e.g. `sox {path} -n stat` or `python -m http.server -d {dir}`.
+/// Placeholders: `{path}`, `{dir}`, `{name}`, `{stem}`, `{ext}`.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenerTemplate {
+    #[serde(default)]
+    id: String,
+    name: String,
+    command: String,
+    /// Lower-case extensions without the dot; empty means "any file".
+    #[serde(default)]
+    extensions: Vec<String>,
+}
+
+impl OpenerTemplate {
+    fn applies_to(&self, ext: &str) -> bool {
+        self.extensions.is_empty() || self.extensions.iter().any(|e| e == ext)
+    }
+}
+
+fn normalize_ext(ext: &str) -> String {
+    ext.trim().trim_start_matches('.').to_ascii_lowercase()
+}
+
+fn load_default_openers(app: &AppHandle) -> AppResult<HashMap<String, String>> {
+    Ok(settings::read_setting(app, OPENERS_BY_EXT_KEY)?.unwrap_or_default())
+}
+
+/// The explicitly requested opener, or else the remembered default for the target's extension.
+pub fn resolve_opener(app: &AppHandle, explicit: Option<&str>, target: &Path) -> Option<String> {
+    if let Some(explicit) = explicit.map(str::trim).filter(|p| !p.is_empty()) {
+        return Some(explicit.to_string());
+    }
+    let ext = normalize_ext(&target.extension()?.to_string_lossy());
+    load_default_openers(app).ok()?.remove(&ext)
+}
+
+/// Opens an extracted temp file with the explicit or remembered opener, falling back to the
+/// system default when there is none or it fails. Returns the last error when nothing opened it.
+pub fn open_extracted(
+    app: &AppHandle,
+    target: &Path,
+    explicit: Option<&str>,
+) -> Result<(), String> {
+    quarantine::prepare_temp_file(app, target);
+    if let Some(app_path) = resolve_opener(app, explicit, target) {
+        if open_with_app_detached(target, &app_path).is_ok() {
+            return Ok(());
+        }
+    }
+    open::that_detached(target).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_default_opener(app: AppHandle, ext: String) -> AppResult<Option<String>> {
+    let ext = normalize_ext(&ext);
+    if ext.is_empty() {
+        return Ok(None);
+    }
+    Ok(load_default_openers(&app)?.remove(&ext))
+}
+
+/// Remembers `app_path` for `ext`; an empty or missing path forgets it.
+#[tauri::command]
+pub async fn set_default_opener(
+    app: AppHandle,
+    ext: String,
+    app_path: Option<String>,
+) -> AppResult<()> {
+    let ext = normalize_ext(&ext);
+    if ext.is_empty() {
+        return Err(AppError::Invalid("extension is empty".into()));
+    }
+    let mut openers = load_default_openers(&app)?;
+    match app_path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => openers.insert(ext, path.to_string()),
+        None => openers.remove(&ext),
+    };
+    settings::write_setting(&app, OPENERS_BY_EXT_KEY, &openers)
+}
+
+fn load_templates(app: &AppHandle) -> AppResult<Vec<OpenerTemplate>> {
+    Ok(settings::read_setting(app, OPENER_TEMPLATES_KEY)?.unwrap_or_default())
+}
+
+/// Splits a command template into arguments, honouring single and double quotes.
+/// The command is spawned directly, never through a shell.
+fn split_command(template: &str) -> AppResult<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_token = false;
+    for c in template.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_token = true;
+            }
+            None if c.is_whitespace() => {
+                if in_token {
+                    args.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err(AppError::Invalid(
+            "unterminated quote in command template".into(),
+        ));
+    }
+    if in_token {
+        args.push(current);
+    }
+    if args.is_empty() {
+        return Err(AppError::Invalid("command template is empty".into()));
+    }
+    Ok(args)
+}
+
+fn expand_template_arg(arg: &str, target: &Path) -> String {
+    let dir = target.parent().unwrap_or_else(|| Path::new("."));
+    let lossy = |value: Option<&std::ffi::OsStr>| {
+        value
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+    arg.replace("{path}", &target.to_string_lossy())
+        .replace("{dir}", &dir.to_string_lossy())
+        .replace("{name}", &lossy(target.file_name()))
+        .replace("{stem}", &lossy(target.file_stem()))
+        .replace("{ext}", &lossy(target.extension()))
+}
+
+#[tauri::command]
+pub async fn list_registered_openers(
+    app: AppHandle,
+    ext: Option<String>,
+) -> AppResult<Vec<OpenerTemplate>> {
+    let templates = load_templates(&app)?;
+    Ok(match ext.as_deref().map(normalize_ext) {
+        Some(ext) if !ext.is_empty() => templates
+            .into_iter()
+            .filter(|t| t.applies_to(&ext))
+            .collect(),
+        _ => templates,
+    })
+}
+
+#[tauri::command]
+pub async fn save_opener_template(
+    app: AppHandle,
+    template: OpenerTemplate,
+) -> AppResult<Vec<OpenerTemplate>> {
+    let name = template.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Invalid("tool name is empty".into()));
+    }
+    split_command(&template.command)?;
+    let id = match template.id.trim() {
+        "" => format!(
+            "tool-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        ),
+        id => id.to_string(),
+    };
+    let cleaned = OpenerTemplate {
+        id: id.clone(),
+        name,
+        command: template.command.trim().to_string(),
+        extensions: template
+            .extensions
+            .iter()
+            .map(|e| normalize_ext(e))
+            .filter(|e| !e.is_empty())
+            .collect(),
+    };
+
+    let mut templates = load_templates(&app)?;
+    match templates.iter_mut().find(|t| t.id == id) {
+        Some(existing) => *existing = cleaned,
+        None => templates.push(cleaned),
+    }
+    settings::write_setting(&app, OPENER_TEMPLATES_KEY, &templates)?;
+    Ok(templates)
+}
+
+#[tauri::command]
+pub async fn delete_opener_template(app: AppHandle, id: String) -> AppResult<Vec<OpenerTemplate>> {
+    let mut templates = load_templates(&app)?;
+    templates.retain(|t| t.id != id.trim());
+    settings::write_setting(&app, OPENER_TEMPLATES_KEY, &templates)?;
+    Ok(templates)
+}
+
+#[tauri::command]
+pub async fn run_opener_template(app: AppHandle, id: String, path: String) -> AppResult<String> {
+    let template = load_templates(&app)?
+        .into_iter()
+        .find(|t| t.id == id.trim())
+        .ok_or_else(|| {
+            AppError::Missing(messages::text(Message::UnknownTool, &[("id", &id.trim())]))
+        })?;
+    spawn_blocking(move || {
+        let target = PathBuf::from(path.trim());
+        if !target.exists() {
+            return Err(AppError::Missing(messages::text(Message::FileMissing, &[])));
+        }
+        let args: Vec<String> = split_command(&template.command)?
+            .iter()
+            .map(|arg| expand_template_arg(arg, &target))
+            .collect();
+        let mut cmd = Command::new(&args[0]);
+        cmd.args(&args[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(dir) = target.parent().filter(|d| d.is_dir()) {
+            cmd.current_dir(dir);
+        }
+        cmd.spawn().map_err(|e| {
+            AppError::Open(messages::text(
+                Message::ToolFailedToStart,
+                &[("program", &args[0]), ("error", &e)],
+            ))
+        })?;
+        Ok(messages::text(
+            Message::StartedTool,
+            &[("tool", &template.name), ("path", &target.display())],
+        ))
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
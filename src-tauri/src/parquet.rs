@@ -0,0 +1,121 @@
+use ::parquet::basic::{ConvertedType, Repetition};
+use ::parquet::file::metadata::ParquetMetaDataReader;
+use ::parquet::file::reader::{FileReader, SerializedFileReader};
+use serde::Serialize;
+use std::{fs::File, path::Path};
+
+use crate::app_error::{AppError, AppResult};
+
+const FOOTER_TAIL_BYTES: usize = 8;
+const MAX_FOOTER_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ParquetColumn {
+    pub path: String,
+    pub physical_type: String,
+    pub logical_type: Option<String>,
+    pub repetition: String,
+}
+
+impl ParquetColumn {
+    pub fn type_label(&self) -> String {
+        match &self.logical_type {
+            Some(logical) => format!("{} ({logical})", self.physical_type),
+            None => self.physical_type.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ParquetRowGroupSummary {
+    pub num_rows: i64,
+    pub total_byte_size: i64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ParquetFileMeta {
+    pub num_rows: i64,
+    pub created_by: Option<String>,
+    pub columns: Vec<ParquetColumn>,
+    pub row_groups: Vec<ParquetRowGroupSummary>,
+}
+
+/// Parses the trailing `<len:u32 LE>PAR1` of a Parquet file and returns the footer length.
+pub fn parse_footer_tail(tail: &[u8]) -> AppResult<u64> {
+    let not_parquet = || AppError::Invalid("Not a Parquet file (missing PAR1 footer).".into());
+    let end: &[u8; FOOTER_TAIL_BYTES] = tail
+        .len()
+        .checked_sub(FOOTER_TAIL_BYTES)
+        .and_then(|start| tail[start..].try_into().ok())
+        .ok_or_else(not_parquet)?;
+    let footer_len = ParquetMetaDataReader::decode_footer(end).map_err(|_| not_parquet())? as u64;
+    if footer_len > MAX_FOOTER_BYTES {
+        return Err(AppError::Invalid(
+            "Parquet footer is too large to parse.".into(),
+        ));
+    }
+    Ok(footer_len)
+}
+
+fn repetition_name(repetition: Repetition) -> &'static str {
+    match repetition {
+        Repetition::REQUIRED => "required",
+        Repetition::OPTIONAL => "optional",
+        Repetition::REPEATED => "repeated",
+    }
+}
+
+pub fn read_parquet_meta(path: &Path) -> AppResult<ParquetFileMeta> {
+    let reader = SerializedFileReader::new(File::open(path)?).map_err(|e| {
+        AppError::Invalid(format!(
+            "Parquet footer decode failed for {}: {e}",
+            path.display()
+        ))
+    })?;
+    let meta = reader.metadata();
+    let file_meta = meta.file_metadata();
+    let columns = file_meta
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| {
+            let info = column.self_type().get_basic_info();
+            let repetition = if info.has_repetition() {
+                info.repetition()
+            } else {
+                Repetition::REQUIRED
+            };
+            ParquetColumn {
+                path: column.path().string(),
+                physical_type: column.physical_type().to_string(),
+                logical_type: match column.converted_type() {
+                    ConvertedType::NONE => None,
+                    converted => Some(converted.to_string()),
+                },
+                repetition: repetition_name(repetition).to_string(),
+            }
+        })
+        .collect();
+    let row_groups = meta
+        .row_groups()
+        .iter()
+        .map(|rg| ParquetRowGroupSummary {
+            num_rows: rg.num_rows(),
+            total_byte_size: rg.total_byte_size(),
+        })
+        .collect();
+    Ok(ParquetFileMeta {
+        num_rows: file_meta.num_rows(),
+        created_by: file_meta.created_by().map(str::to_string),
+        columns,
+        row_groups,
+    })
+}
+
+pub fn looks_like_parquet(filename: &str) -> bool {
+    let name = filename.trim().to_ascii_lowercase();
+    name.ends_with(".parquet") || name.ends_with(".pq")
+}
@@ -0,0 +1,599 @@
+use ::parquet::basic::{ConvertedType, LogicalType, Type as PhysicalType};
+use ::parquet::errors::ParquetError;
+use ::parquet::file::metadata::{ColumnChunkMetaData, ParquetMetaData};
+use ::parquet::file::page_index::index::{Index, PageIndex};
+use ::parquet::file::reader::{FileReader, SerializedFileReader};
+use ::parquet::file::serialized_reader::ReadOptionsBuilder;
+use ::parquet::file::statistics::Statistics;
+use ::parquet::record::{Field, Row};
+use ::parquet::schema::types::{ColumnDescriptor, SchemaDescriptor, Type};
+use serde::Serialize;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::hf_parquet::row_json;
+use crate::parquet::looks_like_parquet;
+
+const QUERY_PAGE_DEFAULT_ROWS: u32 = 20;
+const QUERY_PAGE_MAX_ROWS: u32 = 100;
+const MAX_QUERY_FILES: usize = 100_000;
+/// A query returns what it has found once it has been scanning this long.
+const MAX_SCAN_TIME: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn holds(self, ord: Ordering) -> bool {
+        match self {
+            Op::Eq => ord == Ordering::Equal,
+            Op::Ne => ord != Ordering::Equal,
+            Op::Lt => ord == Ordering::Less,
+            Op::Le => ord != Ordering::Greater,
+            Op::Gt => ord == Ordering::Greater,
+            Op::Ge => ord != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Scalar {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+fn compare(a: &Scalar, b: &Scalar) -> Option<Ordering> {
+    match (a, b) {
+        (Scalar::Int(x), Scalar::Int(y)) => Some(x.cmp(y)),
+        (Scalar::Int(x), Scalar::Float(y)) => (*x as f64).partial_cmp(y),
+        (Scalar::Float(x), Scalar::Int(y)) => x.partial_cmp(&(*y as f64)),
+        (Scalar::Float(x), Scalar::Float(y)) => x.partial_cmp(y),
+        // Byte order, as Parquet statistics for UTF-8 columns are kept.
+        (Scalar::Str(x), Scalar::Str(y)) => Some(x.as_bytes().cmp(y.as_bytes())),
+        (Scalar::Bool(x), Scalar::Bool(y)) => Some(x.cmp(y)),
+        _ => None,
+    }
+}
+
+/// `column op literal`; `column` may be a dotted path into struct columns.
+struct Predicate {
+    column: String,
+    op: Op,
+    value: Scalar,
+}
+
+impl Predicate {
+    fn matches(&self, row: &Row) -> bool {
+        lookup(row, &self.column)
+            .and_then(scalar)
+            .and_then(|v| compare(&v, &self.value))
+            .is_some_and(|ord| self.op.holds(ord))
+    }
+
+    /// Whether a row group or page whose values lie in `min..=max` can hold a match.
+    fn can_match(&self, min: &Scalar, max: &Scalar) -> bool {
+        let (Some(lo), Some(hi)) = (compare(min, &self.value), compare(max, &self.value)) else {
+            return true;
+        };
+        match self.op {
+            Op::Eq => lo != Ordering::Greater && hi != Ordering::Less,
+            Op::Ne => lo != Ordering::Equal || hi != Ordering::Equal,
+            Op::Lt => lo == Ordering::Less,
+            Op::Le => lo != Ordering::Greater,
+            Op::Gt => hi == Ordering::Greater,
+            Op::Ge => hi != Ordering::Less,
+        }
+    }
+}
+
+const OPS: &[(&str, Op)] = &[
+    ("==", Op::Eq),
+    ("!=", Op::Ne),
+    ("<>", Op::Ne),
+    ("<=", Op::Le),
+    (">=", Op::Ge),
+    ("=", Op::Eq),
+    ("<", Op::Lt),
+    (">", Op::Gt),
+];
+
+/// One `column op literal` term and the text after it. Columns may be quoted with backticks,
+/// strings with single or double quotes; bare literals are booleans, numbers or words.
+fn parse_term(text: &str) -> AppResult<(Predicate, &str)> {
+    let text = text.trim_start();
+    let (column, rest) = match text.strip_prefix('`') {
+        Some(quoted) => quoted
+            .split_once('`')
+            .ok_or_else(|| AppError::Invalid("unclosed ` in column name".into()))?,
+        None => {
+            let end = text
+                .find(|c: char| c.is_whitespace() || "=!<>".contains(c))
+                .unwrap_or(text.len());
+            text.split_at(end)
+        }
+    };
+    if column.is_empty() {
+        return Err(AppError::Invalid(format!(
+            "expected a column name at `{text}`"
+        )));
+    }
+    let rest = rest.trim_start();
+    let (op_text, op) = OPS
+        .iter()
+        .find(|(op, _)| rest.starts_with(op))
+        .copied()
+        .ok_or_else(|| {
+            AppError::Invalid(format!("expected one of = != < <= > >= after {column}"))
+        })?;
+    let rest = rest[op_text.len()..].trim_start();
+    let (value, rest) = match rest.chars().next() {
+        Some(quote @ ('\'' | '"')) => {
+            let (literal, after) = rest[1..]
+                .split_once(quote)
+                .ok_or_else(|| AppError::Invalid(format!("unclosed {quote} in filter")))?;
+            (Scalar::Str(literal.to_string()), after)
+        }
+        Some(_) => {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (word, after) = rest.split_at(end);
+            let value = if word.eq_ignore_ascii_case("true") {
+                Scalar::Bool(true)
+            } else if word.eq_ignore_ascii_case("false") {
+                Scalar::Bool(false)
+            } else if let Ok(int) = word.parse() {
+                Scalar::Int(int)
+            } else if let Ok(float) = word.parse() {
+                Scalar::Float(float)
+            } else {
+                Scalar::Str(word.to_string())
+            };
+            (value, after)
+        }
+        None => {
+            return Err(AppError::Invalid(format!(
+                "expected a value after {column} {op_text}"
+            )))
+        }
+    };
+    Ok((
+        Predicate {
+            column: column.to_string(),
+            op,
+            value,
+        },
+        rest,
+    ))
+}
+
+/// Terms joined by `AND` (or `&&`); an empty filter matches every row.
+fn parse_filter(filter: &str) -> AppResult<Vec<Predicate>> {
+    let mut predicates = Vec::new();
+    let mut rest = filter.trim();
+    while !rest.is_empty() {
+        let (predicate, after) = parse_term(rest)?;
+        predicates.push(predicate);
+        rest = after.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        rest = match rest.get(..3) {
+            Some(word) if word.eq_ignore_ascii_case("and") => &rest[3..],
+            _ => rest
+                .strip_prefix("&&")
+                .ok_or_else(|| AppError::Invalid(format!("expected AND before `{rest}`")))?,
+        };
+    }
+    Ok(predicates)
+}
+
+fn lookup<'r>(row: &'r Row, path: &str) -> Option<&'r Field> {
+    let mut current = row;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        let (_, field) = current.get_column_iter().find(|(name, _)| *name == part)?;
+        if parts.peek().is_none() {
+            return Some(field);
+        }
+        match field {
+            Field::Group(group) => current = group,
+            _ => return None,
+        }
+    }
+    None
+}
+
+fn scalar(field: &Field) -> Option<Scalar> {
+    Some(match field {
+        Field::Bool(v) => Scalar::Bool(*v),
+        Field::Byte(v) => Scalar::Int(i64::from(*v)),
+        Field::Short(v) => Scalar::Int(i64::from(*v)),
+        Field::Int(v) | Field::Date(v) => Scalar::Int(i64::from(*v)),
+        Field::Long(v) | Field::TimestampMillis(v) | Field::TimestampMicros(v) => Scalar::Int(*v),
+        Field::UByte(v) => Scalar::Int(i64::from(*v)),
+        Field::UShort(v) => Scalar::Int(i64::from(*v)),
+        Field::UInt(v) => Scalar::Int(i64::from(*v)),
+        Field::ULong(v) => match i64::try_from(*v) {
+            Ok(v) => Scalar::Int(v),
+            Err(_) => Scalar::Float(*v as f64),
+        },
+        Field::Float(v) => Scalar::Float(f64::from(*v)),
+        Field::Double(v) => Scalar::Float(*v),
+        Field::Str(v) => Scalar::Str(v.clone()),
+        _ => return None,
+    })
+}
+
+/// Whether min/max statistics of this column order the same way as the values rows decode to.
+/// Unsigned and decimal columns are stored in signed physical types and are left unpruned.
+fn prunable(descr: &ColumnDescriptor) -> bool {
+    match descr.physical_type() {
+        PhysicalType::BOOLEAN | PhysicalType::FLOAT | PhysicalType::DOUBLE => true,
+        PhysicalType::INT32 | PhysicalType::INT64 => {
+            !matches!(
+                descr.converted_type(),
+                ConvertedType::UINT_8
+                    | ConvertedType::UINT_16
+                    | ConvertedType::UINT_32
+                    | ConvertedType::UINT_64
+                    | ConvertedType::DECIMAL
+            ) && !matches!(
+                descr.logical_type(),
+                Some(LogicalType::Integer {
+                    is_signed: false,
+                    ..
+                }) | Some(LogicalType::Decimal { .. })
+            )
+        }
+        PhysicalType::BYTE_ARRAY => {
+            descr.converted_type() == ConvertedType::UTF8
+                || matches!(descr.logical_type(), Some(LogicalType::String))
+        }
+        _ => false,
+    }
+}
+
+fn stats_bounds(column: &ColumnChunkMetaData) -> Option<(Scalar, Scalar)> {
+    if !prunable(column.column_descr()) {
+        return None;
+    }
+    Some(match column.statistics()? {
+        Statistics::Boolean(s) => (Scalar::Bool(*s.min_opt()?), Scalar::Bool(*s.max_opt()?)),
+        Statistics::Int32(s) => (
+            Scalar::Int(i64::from(*s.min_opt()?)),
+            Scalar::Int(i64::from(*s.max_opt()?)),
+        ),
+        Statistics::Int64(s) => (Scalar::Int(*s.min_opt()?), Scalar::Int(*s.max_opt()?)),
+        Statistics::Float(s) => (
+            Scalar::Float(f64::from(*s.min_opt()?)),
+            Scalar::Float(f64::from(*s.max_opt()?)),
+        ),
+        Statistics::Double(s) => (Scalar::Float(*s.min_opt()?), Scalar::Float(*s.max_opt()?)),
+        Statistics::ByteArray(s) => (
+            Scalar::Str(String::from_utf8_lossy(s.min_opt()?.data()).into_owned()),
+            Scalar::Str(String::from_utf8_lossy(s.max_opt()?.data()).into_owned()),
+        ),
+        _ => return None,
+    })
+}
+
+fn page_bounds(index: &Index) -> Option<Vec<Option<(Scalar, Scalar)>>> {
+    fn bounds<T>(
+        pages: &[PageIndex<T>],
+        to_scalar: impl Fn(&T) -> Scalar,
+    ) -> Vec<Option<(Scalar, Scalar)>> {
+        pages
+            .iter()
+            .map(|page| Some((to_scalar(page.min()?), to_scalar(page.max()?))))
+            .collect()
+    }
+    Some(match index {
+        Index::BOOLEAN(i) => bounds(&i.indexes, |v| Scalar::Bool(*v)),
+        Index::INT32(i) => bounds(&i.indexes, |v| Scalar::Int(i64::from(*v))),
+        Index::INT64(i) => bounds(&i.indexes, |v| Scalar::Int(*v)),
+        Index::FLOAT(i) => bounds(&i.indexes, |v| Scalar::Float(f64::from(*v))),
+        Index::DOUBLE(i) => bounds(&i.indexes, |v| Scalar::Float(*v)),
+        Index::BYTE_ARRAY(i) => bounds(&i.indexes, |v| {
+            Scalar::Str(String::from_utf8_lossy(v.data()).into_owned())
+        }),
+        _ => return None,
+    })
+}
+
+fn intersect(a: &[Range<usize>], b: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let start = a[i].start.max(b[j].start);
+        let end = a[i].end.min(b[j].end);
+        if start < end {
+            out.push(start..end);
+        }
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    out
+}
+
+/// Row ranges of row group `rg` that page statistics (the column and offset indexes) leave
+/// possible, or `None` when the file has no page index for the filtered columns.
+fn candidate_rows(
+    meta: &ParquetMetaData,
+    rg: usize,
+    predicates: &[Predicate],
+    columns: &[Option<usize>],
+) -> Option<Vec<Range<usize>>> {
+    let column_index = meta.column_index()?.get(rg)?;
+    let offset_index = meta.offset_index()?.get(rg)?;
+    let group = meta.row_group(rg);
+    let group_rows = group.num_rows().max(0) as usize;
+    let mut result: Option<Vec<Range<usize>>> = None;
+    for (predicate, column) in predicates.iter().zip(columns) {
+        let Some(column) = *column else { continue };
+        if !prunable(group.column(column).column_descr()) {
+            continue;
+        }
+        let (Some(index), Some(offsets)) = (column_index.get(column), offset_index.get(column))
+        else {
+            continue;
+        };
+        let Some(bounds) = page_bounds(index) else {
+            continue;
+        };
+        let locations = offsets.page_locations();
+        if locations.len() != bounds.len() {
+            continue;
+        }
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for (page, bound) in bounds.iter().enumerate() {
+            let possible = match bound {
+                Some((min, max)) => predicate.can_match(min, max),
+                None => true,
+            };
+            if !possible {
+                continue;
+            }
+            let start = locations[page].first_row_index.max(0) as usize;
+            let end = locations
+                .get(page + 1)
+                .map_or(group_rows, |next| next.first_row_index.max(0) as usize);
+            match ranges.last_mut() {
+                Some(last) if last.end == start => last.end = end,
+                _ => ranges.push(start..end),
+            }
+        }
+        result = Some(match result {
+            Some(previous) => intersect(&previous, &ranges),
+            None => ranges,
+        });
+    }
+    result
+}
+
+/// The top-level fields the filter reads, in schema order.
+fn projection(schema: &SchemaDescriptor, predicates: &[Predicate]) -> AppResult<Type> {
+    let root = schema.root_schema();
+    for predicate in predicates {
+        let top = predicate.column.split('.').next().unwrap_or_default();
+        if !root.get_fields().iter().any(|f| f.name() == top) {
+            return Err(AppError::Invalid(format!("no column named {top}")));
+        }
+    }
+    let fields = root
+        .get_fields()
+        .iter()
+        .filter(|f| {
+            predicates
+                .iter()
+                .any(|p| p.column.split('.').next() == Some(f.name()))
+        })
+        .cloned()
+        .collect();
+    Type::group_type_builder(root.name())
+        .with_fields(fields)
+        .build()
+        .map_err(parquet_error)
+}
+
+fn parquet_error(err: ParquetError) -> AppError {
+    AppError::Invalid(format!("parquet read failed: {err}"))
+}
+
+/// `path` itself, or every Parquet file under it (hidden and `_`-prefixed entries such as
+/// `_SUCCESS` and `_delta_log` excluded), sorted.
+fn parquet_files(path: &Path) -> AppResult<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') || name.starts_with('_') {
+                continue;
+            }
+            if entry.file_type()?.is_dir() {
+                dirs.push(entry.path());
+            } else if looks_like_parquet(&name) {
+                files.push(entry.path());
+                if files.len() > MAX_QUERY_FILES {
+                    return Err(AppError::Invalid(format!(
+                        "more than {MAX_QUERY_FILES} parquet files under {}",
+                        path.display()
+                    )));
+                }
+            }
+        }
+    }
+    if files.is_empty() {
+        return Err(AppError::Missing(format!(
+            "no parquet files under {}",
+            path.display()
+        )));
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ParquetQueryResult {
+    offset: usize,
+    /// Matching rows `offset..offset + limit`.
+    rows: Vec<Value>,
+    /// Matches seen; the total when `scan_complete`.
+    matched: usize,
+    files: usize,
+    files_scanned: usize,
+    row_groups_scanned: usize,
+    /// Row groups ruled out by their column statistics without being read.
+    row_groups_pruned: usize,
+    /// Rows ruled out by page statistics without being tested.
+    rows_pruned: u64,
+    rows_tested: u64,
+    /// Whether every file was scanned; otherwise the page filled up first.
+    scan_complete: bool,
+    /// Whether the scan stopped at the time budget before filling the page.
+    timed_out: bool,
+}
+
+fn query(path: &Path, filter: &str, offset: usize, limit: usize) -> AppResult<ParquetQueryResult> {
+    let predicates = parse_filter(filter)?;
+    let files = parquet_files(path)?;
+    let started = Instant::now();
+    let mut result = ParquetQueryResult {
+        offset,
+        files: files.len(),
+        ..Default::default()
+    };
+    for file in &files {
+        let options = ReadOptionsBuilder::new().with_page_index().build();
+        let reader = SerializedFileReader::new_with_options(File::open(file)?, options)
+            .map_err(parquet_error)?;
+        let meta = reader.metadata();
+        let schema = meta.file_metadata().schema_descr();
+        let columns = predicates
+            .iter()
+            .map(|p| {
+                (0..schema.num_columns()).find(|i| schema.column(*i).path().string() == p.column)
+            })
+            .collect::<Vec<_>>();
+        let filter_columns = (!predicates.is_empty())
+            .then(|| projection(schema, &predicates))
+            .transpose()?;
+        result.files_scanned += 1;
+
+        for rg in 0..meta.num_row_groups() {
+            let group = meta.row_group(rg);
+            let pruned = predicates.iter().zip(&columns).any(|(p, column)| {
+                column
+                    .and_then(|c| stats_bounds(group.column(c)))
+                    .is_some_and(|(min, max)| !p.can_match(&min, &max))
+            });
+            if pruned {
+                result.row_groups_pruned += 1;
+                continue;
+            }
+            if started.elapsed() > MAX_SCAN_TIME {
+                result.timed_out = true;
+                return Ok(result);
+            }
+            result.row_groups_scanned += 1;
+            let group_rows = group.num_rows().max(0) as usize;
+            let candidates = candidate_rows(meta, rg, &predicates, &columns)
+                .unwrap_or_else(|| vec![0..group_rows]);
+            let candidate_count = candidates.iter().map(ExactSizeIterator::len).sum::<usize>();
+            result.rows_pruned += (group_rows - candidate_count.min(group_rows)) as u64;
+            if candidates.is_empty() {
+                continue;
+            }
+            let row_group = reader.get_row_group(rg).map_err(parquet_error)?;
+
+            // Test the filter on the filtered columns alone, then read whole rows only for the
+            // matches that land on the requested page.
+            let mut hits = Vec::new();
+            match &filter_columns {
+                Some(projection) => {
+                    let mut ranges = candidates.iter().peekable();
+                    let rows = row_group
+                        .get_row_iter(Some(projection.clone()))
+                        .map_err(parquet_error)?;
+                    for (idx, row) in rows.enumerate() {
+                        while ranges.peek().is_some_and(|r| r.end <= idx) {
+                            ranges.next();
+                        }
+                        let Some(range) = ranges.peek() else { break };
+                        if idx < range.start {
+                            continue;
+                        }
+                        let row = row.map_err(parquet_error)?;
+                        result.rows_tested += 1;
+                        if predicates.iter().all(|p| p.matches(&row)) {
+                            hits.push(idx);
+                        }
+                    }
+                }
+                None => hits.extend(0..group_rows),
+            }
+
+            let skip = offset.saturating_sub(result.matched).min(hits.len());
+            let wanted = &hits[skip..hits.len().min(skip + limit - result.rows.len())];
+            result.matched += hits.len();
+            if !wanted.is_empty() {
+                let mut wanted = wanted.iter().peekable();
+                let rows = row_group.get_row_iter(None).map_err(parquet_error)?;
+                for (idx, row) in rows.enumerate() {
+                    let Some(next) = wanted.peek() else { break };
+                    if idx == **next {
+                        result.rows.push(row_json(&row.map_err(parquet_error)?));
+                        wanted.next();
+                    }
+                }
+            }
+            if result.rows.len() >= limit {
+                return Ok(result);
+            }
+        }
+    }
+    result.scan_complete = true;
+    Ok(result)
+}
+
+/// Rows of a local Parquet file, or of every Parquet file under a directory, that match `filter`
+/// (`column op literal` terms joined by `AND`). Row groups and pages whose statistics rule the
+/// filter out are skipped, so selective filters over large datasets answer quickly; `offset`
+/// counts matches, and paging re-scans from the first file.
+#[tauri::command]
+pub async fn parquet_query(
+    path: String,
+    filter: String,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> AppResult<ParquetQueryResult> {
+    let offset = offset.unwrap_or(0) as usize;
+    let limit = limit
+        .unwrap_or(QUERY_PAGE_DEFAULT_ROWS)
+        .clamp(1, QUERY_PAGE_MAX_ROWS) as usize;
+    spawn_blocking(move || query(Path::new(path.trim()), &filter, offset, limit))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,63 @@
+use glob::MatchOptions;
+use std::path::{Component, Path, PathBuf};
+
+use crate::app_error::{AppError, AppResult};
+
+/// Matches returned per pattern; a pattern matching more is rejected rather than truncated.
+const MAX_MATCHES: usize = 100_000;
+
+/// Whether `path` is a glob pattern (`*`, `**`, `?` or `[...]`) rather than a plain path.
+pub fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// The leading directories of `pattern` that hold no wildcard, which every match sits under.
+pub fn base_dir(pattern: &str) -> PathBuf {
+    let mut base = PathBuf::new();
+    for component in Path::new(pattern).components() {
+        if let Component::Normal(part) = component {
+            if part.to_str().is_some_and(is_glob) {
+                break;
+            }
+        }
+        base.push(component);
+    }
+    if base.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        base
+    }
+}
+
+/// Files matching `pattern`, sorted. `**` spans any number of directories; wildcards do not
+/// match `/` or hidden names.
+pub fn expand(pattern: &str) -> AppResult<Vec<PathBuf>> {
+    let options = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: true,
+    };
+    let paths = glob::glob_with(pattern, options)
+        .map_err(|e| AppError::Invalid(format!("invalid glob pattern {pattern}: {e}")))?;
+    let mut matches = Vec::new();
+    for path in paths.filter_map(|p| p.ok()).filter(|p| p.is_file()) {
+        if matches.len() == MAX_MATCHES {
+            return Err(AppError::Invalid(format!(
+                "{pattern} matches more than {MAX_MATCHES} files; narrow the pattern"
+            )));
+        }
+        matches.push(path);
+    }
+    matches.sort();
+    Ok(matches)
+}
+
+/// `path` relative to `base`, with `/` separators, as shard names spanning several directories.
+pub fn relative_name(path: &Path, base: &Path) -> String {
+    let relative = path.strip_prefix(base).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,531 @@
+use std::collections::HashMap;
+
+use crate::app_error::{AppError, AppResult};
+
+/// Nodes a single stream may create; bounds memory for hostile inputs.
+const MAX_NODES: usize = 4_000_000;
+
+/// A value decoded from a pickle stream without importing or calling anything: globals stay
+/// names and calls (`REDUCE`, `NEWOBJ`) stay unevaluated, with whatever `BUILD`, `SETITEM(S)`
+/// and `APPEND(S)` later applied to them. Children are ids into `Pickle::node`.
+pub enum Node {
+    None,
+    Bool(bool),
+    Int(i64),
+    /// An integer wider than 64 bits, kept as its little-endian two's complement bytes.
+    BigInt(Vec<u8>),
+    Float(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+    List(Vec<usize>),
+    Tuple(Vec<usize>),
+    Dict(Vec<(usize, usize)>),
+    Set(Vec<usize>),
+    Global {
+        module: String,
+        name: String,
+    },
+    Call {
+        callable: usize,
+        args: usize,
+        state: Option<usize>,
+        items: Vec<(usize, usize)>,
+        list: Vec<usize>,
+    },
+    PersistentId(usize),
+}
+
+/// A decoded pickle: an arena of nodes, which memo references (`GET`) share, and the root.
+pub struct Pickle {
+    nodes: Vec<Node>,
+    root: usize,
+}
+
+impl Pickle {
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    pub fn node(&self, id: usize) -> &Node {
+        &self.nodes[id]
+    }
+
+    pub fn as_str(&self, id: usize) -> Option<&str> {
+        match self.node(id) {
+            Node::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self, id: usize) -> Option<i64> {
+        match self.node(id) {
+            Node::Int(v) => Some(*v),
+            Node::Bool(v) => Some(i64::from(*v)),
+            _ => None,
+        }
+    }
+
+    /// Items of a tuple or list.
+    pub fn items(&self, id: usize) -> Option<&[usize]> {
+        match self.node(id) {
+            Node::Tuple(items) | Node::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// `(module, name)` of a global.
+    pub fn global(&self, id: usize) -> Option<(&str, &str)> {
+        match self.node(id) {
+            Node::Global { module, name } => Some((module, name)),
+            _ => None,
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> AppResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| AppError::Invalid("pickle ends unexpectedly".into()))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> AppResult<[u8; N]> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> AppResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> AppResult<u16> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> AppResult<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> AppResult<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn len_u64(&mut self) -> AppResult<usize> {
+        usize::try_from(self.u64()?).map_err(|_| AppError::Invalid("pickle length overflow".into()))
+    }
+
+    /// A newline-terminated argument of the text opcodes, without the newline.
+    fn line(&mut self) -> AppResult<String> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|b| *b == b'\n')
+            .ok_or_else(|| AppError::Invalid("pickle ends unexpectedly".into()))?;
+        let line = self.take(len + 1)?;
+        Ok(String::from_utf8_lossy(&line[..len]).into_owned())
+    }
+}
+
+struct Machine {
+    nodes: Vec<Node>,
+    stack: Vec<usize>,
+    /// Stack heights at each `MARK`.
+    marks: Vec<usize>,
+    memo: HashMap<u64, usize>,
+}
+
+impl Machine {
+    fn push(&mut self, node: Node) -> AppResult<()> {
+        if self.nodes.len() >= MAX_NODES {
+            return Err(AppError::Invalid("pickle has too many objects".into()));
+        }
+        self.nodes.push(node);
+        self.stack.push(self.nodes.len() - 1);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> AppResult<usize> {
+        if self.marks.last() == Some(&self.stack.len()) {
+            return Err(AppError::Invalid("pickle pops past a mark".into()));
+        }
+        self.stack
+            .pop()
+            .ok_or_else(|| AppError::Invalid("pickle stack underflow".into()))
+    }
+
+    fn top(&self) -> AppResult<usize> {
+        self.stack
+            .last()
+            .copied()
+            .ok_or_else(|| AppError::Invalid("pickle stack underflow".into()))
+    }
+
+    fn pop_mark(&mut self) -> AppResult<Vec<usize>> {
+        let mark = self
+            .marks
+            .pop()
+            .ok_or_else(|| AppError::Invalid("pickle has no mark to pop".into()))?;
+        Ok(self.stack.split_off(mark.min(self.stack.len())))
+    }
+
+    fn get(&mut self, key: u64) -> AppResult<()> {
+        let id = *self
+            .memo
+            .get(&key)
+            .ok_or_else(|| AppError::Invalid(format!("pickle memo key {key} is unset")))?;
+        self.stack.push(id);
+        Ok(())
+    }
+
+    fn put(&mut self, key: u64) -> AppResult<()> {
+        let id = self.top()?;
+        self.memo.insert(key, id);
+        Ok(())
+    }
+
+    fn append(&mut self, values: Vec<usize>) -> AppResult<()> {
+        let target = self.top()?;
+        match &mut self.nodes[target] {
+            Node::List(items) | Node::Set(items) | Node::Call { list: items, .. } => {
+                items.extend(values);
+                Ok(())
+            }
+            _ => Err(AppError::Invalid("pickle appends to a non-list".into())),
+        }
+    }
+
+    fn set_items(&mut self, values: Vec<usize>) -> AppResult<()> {
+        let target = self.top()?;
+        let pairs = values.chunks_exact(2).map(|kv| (kv[0], kv[1]));
+        match &mut self.nodes[target] {
+            Node::Dict(items) | Node::Call { items, .. } => {
+                items.extend(pairs);
+                Ok(())
+            }
+            _ => Err(AppError::Invalid("pickle sets items on a non-dict".into())),
+        }
+    }
+
+    fn call(&mut self, callable: usize, args: usize) -> AppResult<()> {
+        self.push(Node::Call {
+            callable,
+            args,
+            state: None,
+            items: Vec::new(),
+            list: Vec::new(),
+        })
+    }
+}
+
+/// Decodes a pickle stream (protocols 0-5) into a node tree. Opcodes that would need the
+/// referenced classes at load time (`INST`, `OBJ`, extension registry, out-of-band buffers) are
+/// refused.
+pub fn parse(data: &[u8]) -> AppResult<Pickle> {
+    let mut r = Reader { data, pos: 0 };
+    let mut m = Machine {
+        nodes: Vec::new(),
+        stack: Vec::new(),
+        marks: Vec::new(),
+        memo: HashMap::new(),
+    };
+    loop {
+        let op = r.u8()?;
+        match op {
+            // PROTO
+            0x80 => {
+                r.u8()?;
+            }
+            // FRAME
+            0x95 => {
+                r.u64()?;
+            }
+            // STOP
+            b'.' => {
+                let root = m.pop()?;
+                return Ok(Pickle {
+                    nodes: m.nodes,
+                    root,
+                });
+            }
+            // MARK
+            b'(' => m.marks.push(m.stack.len()),
+            // POP
+            b'0' => {
+                if m.marks.last() == Some(&m.stack.len()) {
+                    m.marks.pop();
+                } else {
+                    m.pop()?;
+                }
+            }
+            // POP_MARK
+            b'1' => {
+                m.pop_mark()?;
+            }
+            // DUP
+            b'2' => {
+                let top = m.top()?;
+                m.stack.push(top);
+            }
+            b'N' => m.push(Node::None)?,
+            0x88 => m.push(Node::Bool(true))?,
+            0x89 => m.push(Node::Bool(false))?,
+            // BININT, BININT1, BININT2
+            b'J' => {
+                let v = i32::from_le_bytes(r.array()?);
+                m.push(Node::Int(i64::from(v)))?;
+            }
+            b'K' => {
+                let v = r.u8()?;
+                m.push(Node::Int(i64::from(v)))?;
+            }
+            b'M' => {
+                let v = r.u16()?;
+                m.push(Node::Int(i64::from(v)))?;
+            }
+            // LONG1, LONG4
+            0x8A | 0x8B => {
+                let len = if op == 0x8A {
+                    usize::from(r.u8()?)
+                } else {
+                    r.u32()? as usize
+                };
+                let bytes = r.take(len)?;
+                m.push(long_node(bytes))?;
+            }
+            // INT, LONG (text)
+            b'I' | b'L' => {
+                let line = r.line()?;
+                let text = line.trim_end_matches('L');
+                let node = match text {
+                    "00" => Node::Bool(false),
+                    "01" => Node::Bool(true),
+                    _ => text
+                        .parse::<i64>()
+                        .map(Node::Int)
+                        .unwrap_or_else(|_| Node::Str(text.to_string())),
+                };
+                m.push(node)?;
+            }
+            // BINFLOAT, FLOAT
+            b'G' => {
+                let v = f64::from_be_bytes(r.array()?);
+                m.push(Node::Float(v))?;
+            }
+            b'F' => {
+                let v = r.line()?.trim().parse::<f64>().unwrap_or(f64::NAN);
+                m.push(Node::Float(v))?;
+            }
+            // BINUNICODE, SHORT_BINUNICODE, BINUNICODE8
+            b'X' | 0x8C | 0x8D => {
+                let len = match op {
+                    b'X' => r.u32()? as usize,
+                    0x8C => usize::from(r.u8()?),
+                    _ => r.len_u64()?,
+                };
+                let text = String::from_utf8_lossy(r.take(len)?).into_owned();
+                m.push(Node::Str(text))?;
+            }
+            // BINSTRING, SHORT_BINSTRING: Python 2 str, decoded as Latin-1
+            b'T' | b'U' => {
+                let len = if op == b'T' {
+                    r.u32()? as usize
+                } else {
+                    usize::from(r.u8()?)
+                };
+                let text = r.take(len)?.iter().map(|b| char::from(*b)).collect();
+                m.push(Node::Str(text))?;
+            }
+            // STRING (quoted repr), UNICODE (raw-unicode-escape); kept as written
+            b'S' | b'V' => {
+                let line = r.line()?;
+                let text = line
+                    .trim()
+                    .trim_matches(|c| c == '\'' || c == '"')
+                    .to_string();
+                m.push(Node::Str(text))?;
+            }
+            // BINBYTES, SHORT_BINBYTES, BINBYTES8, BYTEARRAY8
+            b'B' | b'C' | 0x8E | 0x96 => {
+                let len = match op {
+                    b'B' => r.u32()? as usize,
+                    b'C' => usize::from(r.u8()?),
+                    _ => r.len_u64()?,
+                };
+                let bytes = r.take(len)?.to_vec();
+                m.push(Node::Bytes(bytes))?;
+            }
+            // EMPTY_LIST, EMPTY_TUPLE, EMPTY_DICT, EMPTY_SET
+            b']' => m.push(Node::List(Vec::new()))?,
+            b')' => m.push(Node::Tuple(Vec::new()))?,
+            b'}' => m.push(Node::Dict(Vec::new()))?,
+            0x8F => m.push(Node::Set(Vec::new()))?,
+            // LIST, TUPLE, DICT, FROZENSET
+            b'l' => {
+                let items = m.pop_mark()?;
+                m.push(Node::List(items))?;
+            }
+            b't' => {
+                let items = m.pop_mark()?;
+                m.push(Node::Tuple(items))?;
+            }
+            b'd' => {
+                let items = m.pop_mark()?;
+                let pairs = items.chunks_exact(2).map(|kv| (kv[0], kv[1])).collect();
+                m.push(Node::Dict(pairs))?;
+            }
+            0x91 => {
+                let items = m.pop_mark()?;
+                m.push(Node::Set(items))?;
+            }
+            // TUPLE1, TUPLE2, TUPLE3
+            0x85..=0x87 => {
+                let len = usize::from(op - 0x84);
+                if m.stack.len() < len + m.marks.last().copied().unwrap_or(0) {
+                    return Err(AppError::Invalid("pickle stack underflow".into()));
+                }
+                let items = m.stack.split_off(m.stack.len() - len);
+                m.push(Node::Tuple(items))?;
+            }
+            // APPEND, APPENDS, ADDITEMS
+            b'a' => {
+                let value = m.pop()?;
+                m.append(vec![value])?;
+            }
+            b'e' | 0x90 => {
+                let values = m.pop_mark()?;
+                m.append(values)?;
+            }
+            // SETITEM, SETITEMS
+            b's' => {
+                let value = m.pop()?;
+                let key = m.pop()?;
+                m.set_items(vec![key, value])?;
+            }
+            b'u' => {
+                let values = m.pop_mark()?;
+                m.set_items(values)?;
+            }
+            // GLOBAL, STACK_GLOBAL
+            b'c' => {
+                let module = r.line()?;
+                let name = r.line()?;
+                m.push(Node::Global { module, name })?;
+            }
+            0x93 => {
+                let name = m.pop()?;
+                let module = m.pop()?;
+                let (Node::Str(module), Node::Str(name)) = (&m.nodes[module], &m.nodes[name])
+                else {
+                    return Err(AppError::Invalid(
+                        "pickle STACK_GLOBAL needs strings".into(),
+                    ));
+                };
+                let (module, name) = (module.clone(), name.clone());
+                m.push(Node::Global { module, name })?;
+            }
+            // REDUCE, NEWOBJ
+            b'R' | 0x81 => {
+                let args = m.pop()?;
+                let callable = m.pop()?;
+                m.call(callable, args)?;
+            }
+            // NEWOBJ_EX: keyword arguments are dropped
+            0x92 => {
+                m.pop()?;
+                let args = m.pop()?;
+                let callable = m.pop()?;
+                m.call(callable, args)?;
+            }
+            // BUILD
+            b'b' => {
+                let state = m.pop()?;
+                let target = m.top()?;
+                if let Node::Call { state: slot, .. } = &mut m.nodes[target] {
+                    *slot = Some(state);
+                }
+            }
+            // BINPERSID, PERSID
+            b'Q' => {
+                let pid = m.pop()?;
+                m.push(Node::PersistentId(pid))?;
+            }
+            b'P' => {
+                let line = r.line()?;
+                m.push(Node::Str(line))?;
+                let pid = m.pop()?;
+                m.push(Node::PersistentId(pid))?;
+            }
+            // BINGET, LONG_BINGET, GET
+            b'h' => {
+                let key = r.u8()?;
+                m.get(u64::from(key))?;
+            }
+            b'j' => {
+                let key = r.u32()?;
+                m.get(u64::from(key))?;
+            }
+            b'g' => {
+                let key = memo_key(&r.line()?)?;
+                m.get(key)?;
+            }
+            // BINPUT, LONG_BINPUT, PUT, MEMOIZE
+            b'q' => {
+                let key = r.u8()?;
+                m.put(u64::from(key))?;
+            }
+            b'r' => {
+                let key = r.u32()?;
+                m.put(u64::from(key))?;
+            }
+            b'p' => {
+                let key = memo_key(&r.line()?)?;
+                m.put(key)?;
+            }
+            0x94 => {
+                let key = m.memo.len() as u64;
+                m.put(key)?;
+            }
+            other => {
+                return Err(AppError::Invalid(format!(
+                    "unsupported pickle opcode 0x{other:02x} at byte {}",
+                    r.pos - 1
+                )));
+            }
+        }
+    }
+}
+
+fn memo_key(line: &str) -> AppResult<u64> {
+    line.trim()
+        .parse()
+        .map_err(|_| AppError::Invalid(format!("bad pickle memo key {line:?}")))
+}
+
+/// A `LONG1`/`LONG4` payload: little-endian two's complement of any width.
+fn long_node(bytes: &[u8]) -> Node {
+    if bytes.is_empty() {
+        return Node::Int(0);
+    }
+    if bytes.len() > 8 {
+        return Node::BigInt(bytes.to_vec());
+    }
+    let fill = if bytes[bytes.len() - 1] & 0x80 != 0 {
+        0xFF
+    } else {
+        0
+    };
+    let mut buf = [fill; 8];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    Node::Int(i64::from_le_bytes(buf))
+}
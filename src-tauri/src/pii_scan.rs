@@ -0,0 +1,173 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use tauri::AppHandle;
+
+use crate::app_error::{AppError, AppResult};
+use crate::settings;
+
+const PII_PATTERNS_KEY: &str = "pii_patterns";
+/// Matches kept per scanned text; the rest are only counted.
+const MAX_MATCHES_PER_TEXT: usize = 20;
+
+const EMAIL_PATTERN: &str = r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b";
+const PHONE_PATTERN: &str =
+    r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{2,4}\)[ .-]?|\b\d{2,4}[ .-])\d{3,4}[ .-]\d{3,4}\b";
+/// 13–19 digits with optional single space or dash separators; confirmed with a Luhn check.
+const CARD_PATTERN: &str = r"\b\d(?:[ -]?\d){12,18}\b";
+const PROFANITY_PATTERN: &str = r"(?i)\b(?:fuck\w*|shit\w*|bitch\w*|cunt\w*|asshole\w*|bastard\w*|dick(?:head)?s?|motherfuck\w*)\b";
+
+/// A user-defined pattern scanned for in addition to the built-in ones.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomPattern {
+    name: String,
+    pattern: String,
+}
+
+static CUSTOM: RwLock<Vec<CustomPattern>> = RwLock::new(Vec::new());
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PiiMatch {
+    /// `email`, `phone`, `credit-card`, `profanity`, or a custom pattern's name.
+    pub kind: String,
+    /// The match with its middle masked, enough to judge it without copying it around.
+    pub redacted: String,
+    /// Byte offset of the match in the text.
+    pub offset: usize,
+}
+
+pub struct Scanner {
+    rules: Vec<(String, Regex)>,
+}
+
+fn compile(pattern: &str) -> AppResult<Regex> {
+    Regex::new(pattern).map_err(|e| AppError::Invalid(format!("Invalid pattern: {e}.")))
+}
+
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| match (i % 2, d * 2) {
+            (1, doubled) if doubled > 9 => doubled - 9,
+            (1, doubled) => doubled,
+            _ => *d,
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+fn redact(matched: &str) -> String {
+    let chars = matched.chars().collect::<Vec<_>>();
+    if chars.len() <= 4 {
+        return "*".repeat(chars.len());
+    }
+    let keep = (chars.len() / 5).clamp(1, 4);
+    let mut out = chars[..keep].iter().collect::<String>();
+    out.push_str(&"*".repeat(chars.len() - 2 * keep));
+    out.extend(&chars[chars.len() - keep..]);
+    out
+}
+
+impl Scanner {
+    /// The built-in patterns plus the user's custom ones.
+    pub fn new() -> AppResult<Self> {
+        let mut rules = vec![
+            ("email".to_string(), compile(EMAIL_PATTERN)?),
+            ("phone".to_string(), compile(PHONE_PATTERN)?),
+            ("credit-card".to_string(), compile(CARD_PATTERN)?),
+            ("profanity".to_string(), compile(PROFANITY_PATTERN)?),
+        ];
+        for custom in CUSTOM.read().map(|c| c.clone()).unwrap_or_default() {
+            rules.push((custom.name, compile(&custom.pattern)?));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Matches in `text`, at most `MAX_MATCHES_PER_TEXT`, plus the total number found.
+    pub fn scan(&self, text: &str) -> (Vec<PiiMatch>, usize) {
+        let mut matches = Vec::new();
+        let mut total = 0;
+        for (kind, regex) in &self.rules {
+            for m in regex.find_iter(text) {
+                if kind == "credit-card" {
+                    let digits = m
+                        .as_str()
+                        .chars()
+                        .filter_map(|c| c.to_digit(10))
+                        .collect::<Vec<_>>();
+                    if !luhn_valid(&digits) {
+                        continue;
+                    }
+                }
+                total += 1;
+                if matches.len() < MAX_MATCHES_PER_TEXT {
+                    matches.push(PiiMatch {
+                        kind: kind.clone(),
+                        redacted: redact(m.as_str()),
+                        offset: m.start(),
+                    });
+                }
+            }
+        }
+        matches.sort_by_key(|m| m.offset);
+        (matches, total)
+    }
+}
+
+fn normalize_all(patterns: Vec<CustomPattern>) -> AppResult<Vec<CustomPattern>> {
+    let mut out: Vec<CustomPattern> = Vec::new();
+    for p in patterns {
+        let name = p.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::Invalid("Missing pattern name.".into()));
+        }
+        if out.iter().any(|o| o.name == name) {
+            return Err(AppError::Invalid(format!(
+                "Duplicate pattern name: {name}."
+            )));
+        }
+        compile(&p.pattern)?;
+        out.push(CustomPattern {
+            name,
+            pattern: p.pattern,
+        });
+    }
+    Ok(out)
+}
+
+fn store(patterns: Vec<CustomPattern>) {
+    if let Ok(mut custom) = CUSTOM.write() {
+        *custom = patterns;
+    }
+}
+
+/// Loads the persisted custom patterns. Called once at startup.
+pub fn load_settings(app: &AppHandle) {
+    if let Ok(Some(patterns)) = settings::read_setting::<Vec<CustomPattern>>(app, PII_PATTERNS_KEY)
+    {
+        if let Ok(patterns) = normalize_all(patterns) {
+            store(patterns);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_pii_patterns() -> AppResult<Vec<CustomPattern>> {
+    Ok(CUSTOM.read().map(|c| c.clone()).unwrap_or_default())
+}
+
+/// Replaces the custom patterns after checking that each one compiles.
+#[tauri::command]
+pub async fn set_pii_patterns(
+    app: AppHandle,
+    patterns: Vec<CustomPattern>,
+) -> AppResult<Vec<CustomPattern>> {
+    let patterns = normalize_all(patterns)?;
+    settings::write_setting(&app, PII_PATTERNS_KEY, &patterns)?;
+    store(patterns.clone());
+    Ok(patterns)
+}
@@ -0,0 +1,92 @@
+use std::io::Cursor;
+
+use serde::Serialize;
+
+use crate::{audio, embeddings, ipc_types::FieldPreview};
+
+const JSON_EXTENSIONS: &[&str] = &["json", "jsonl", "geojson"];
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "wav", "flac", "mp3", "ogg", "opus", "m4a", "aac", "sph", "aiff",
+];
+
+/// How the frontend should render a `FieldPreview`, decided from the previewed bytes rather than
+/// the extension alone.
+#[derive(Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PreviewKind {
+    Text,
+    Json,
+    #[serde(rename_all = "camelCase")]
+    Image {
+        width: u32,
+        height: u32,
+    },
+    /// Header fields are only known for WAV, FLAC and SPHERE.
+    #[serde(rename_all = "camelCase")]
+    Audio {
+        duration_secs: Option<f64>,
+        sample_rate: Option<u32>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Tensor {
+        shape: Vec<usize>,
+        dtype: String,
+    },
+    Binary,
+}
+
+impl PreviewKind {
+    /// Classifies `preview`, built from `data` (the start of its window), after decode overrides
+    /// and plugins have had their say on the text and extension.
+    pub fn detect(data: &[u8], preview: &FieldPreview) -> Self {
+        if let Some(kind) = preview.plugin.as_ref().and_then(|p| p.text_kind()) {
+            return kind;
+        }
+        let ext = preview
+            .guessed_ext
+            .as_deref()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if let Some((dtype, shape)) = embeddings::npy_shape(data) {
+            return PreviewKind::Tensor { shape, dtype };
+        }
+        if let Some(info) = audio::stream_info(data) {
+            return PreviewKind::Audio {
+                duration_secs: info.duration_secs,
+                sample_rate: Some(info.sample_rate),
+            };
+        }
+        if AUDIO_EXTENSIONS.contains(&ext.as_str()) || infer::is_audio(data) {
+            return PreviewKind::Audio {
+                duration_secs: None,
+                sample_rate: None,
+            };
+        }
+        if let Some((width, height)) = image_dimensions(data) {
+            return PreviewKind::Image { width, height };
+        }
+        match preview.preview_text.as_deref() {
+            Some(text) if JSON_EXTENSIONS.contains(&ext.as_str()) || is_json(text) => {
+                PreviewKind::Json
+            }
+            Some(_) => PreviewKind::Text,
+            None => PreviewKind::Binary,
+        }
+    }
+}
+
+/// Width and height from an image header; only the first few bytes need to be present.
+fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let reader = image::ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?;
+    reader.format()?;
+    reader.into_dimensions().ok()
+}
+
+/// True for a complete JSON object or array; a window cut mid-document stays plain text.
+fn is_json(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde::de::IgnoredAny>(text).is_ok()
+}
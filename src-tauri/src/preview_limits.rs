@@ -0,0 +1,71 @@
+use hex::encode as hex_encode;
+
+const DEFAULT_TEXT_CHARS: usize = 8 * 1024;
+const DEFAULT_HEX_BYTES: usize = 48;
+const MAX_TEXT_CHARS: usize = 256 * 1024;
+const MAX_HEX_BYTES: usize = 4 * 1024;
+
+/// How much of a peeked window ends up in a `FieldPreview`: the text length and hex snippet size
+/// requested by the caller, clamped to server-side maxima.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PreviewLimits {
+    text_chars: usize,
+    hex_bytes: usize,
+}
+
+impl Default for PreviewLimits {
+    fn default() -> Self {
+        Self {
+            text_chars: DEFAULT_TEXT_CHARS,
+            hex_bytes: DEFAULT_HEX_BYTES,
+        }
+    }
+}
+
+impl PreviewLimits {
+    pub fn new(text_chars: Option<usize>, hex_bytes: Option<usize>) -> Self {
+        Self {
+            text_chars: text_chars
+                .unwrap_or(DEFAULT_TEXT_CHARS)
+                .clamp(1, MAX_TEXT_CHARS),
+            hex_bytes: hex_bytes
+                .unwrap_or(DEFAULT_HEX_BYTES)
+                .clamp(1, MAX_HEX_BYTES),
+        }
+    }
+
+    /// Bytes to read for a preview window of at least `base` bytes, grown so the text and hex
+    /// limits can be filled.
+    pub fn window(&self, base: usize) -> usize {
+        base.max(self.text_chars).max(self.hex_bytes)
+    }
+
+    /// Leading UTF-8 text of `data` cut to the text limit, and how many characters it had before
+    /// the cut. A character split by the window's end is dropped; other invalid UTF-8 means binary.
+    pub fn text(&self, data: &[u8]) -> Option<(String, usize)> {
+        let raw = match std::str::from_utf8(data) {
+            Ok(text) => text,
+            Err(err) if err.error_len().is_none() => {
+                std::str::from_utf8(&data[..err.valid_up_to()]).ok()?
+            }
+            Err(_) => return None,
+        };
+        Some((
+            raw.chars().take(self.text_chars).collect(),
+            raw.chars().count(),
+        ))
+    }
+
+    /// Already-decoded text (such as a scalar rendered as a string) cut to the text limit, with
+    /// its full character count.
+    pub fn cut(&self, text: &str) -> (String, usize) {
+        (
+            text.chars().take(self.text_chars).collect(),
+            text.chars().count(),
+        )
+    }
+
+    pub fn hex(&self, data: &[u8]) -> String {
+        hex_encode(&data[..data.len().min(self.hex_bytes)])
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::app_error::AppResult;
+use crate::settings;
+
+const QUARANTINE_MODE_KEY: &str = "quarantine_mode";
+
+/// What to do with macOS' `com.apple.quarantine` attribute on files we extract to the temp dir.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuarantineMode {
+    /// Leave whatever the OS applied.
+    #[default]
+    Leave,
+    /// Mark extracted files as downloaded, so Gatekeeper treats them like browser downloads.
+    Set,
+    /// Remove the attribute so default apps open extracted files without prompting.
+    Strip,
+}
+
+#[cfg(target_os = "macos")]
+fn apply_mode(mode: QuarantineMode, path: &Path) {
+    use std::process::Command;
+    match mode {
+        QuarantineMode::Leave => {}
+        QuarantineMode::Set => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let value = format!("0081;{now:08x};dataset-inspector;");
+            let _ = Command::new("xattr")
+                .args(["-w", "com.apple.quarantine", &value])
+                .arg(path)
+                .status();
+        }
+        QuarantineMode::Strip => {
+            let _ = Command::new("xattr")
+                .args(["-d", "com.apple.quarantine"])
+                .arg(path)
+                .status();
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn apply_mode(_mode: QuarantineMode, _path: &Path) {}
+
+/// Applies the configured quarantine policy to a file we wrote before handing it to another app.
+pub fn prepare_temp_file(app: &AppHandle, path: &Path) {
+    let mode = settings::read_setting(app, QUARANTINE_MODE_KEY)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    if mode != QuarantineMode::Leave {
+        apply_mode(mode, path);
+    }
+}
+
+#[tauri::command]
+pub async fn get_quarantine_mode(app: AppHandle) -> AppResult<QuarantineMode> {
+    Ok(settings::read_setting(&app, QUARANTINE_MODE_KEY)?.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn set_quarantine_mode(app: AppHandle, mode: QuarantineMode) -> AppResult<()> {
+    settings::write_setting(&app, QUARANTINE_MODE_KEY, &mode)
+}
@@ -0,0 +1,97 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::app_error::{AppError, AppResult};
+use crate::messages::{self, Message};
+use crate::settings;
+
+const READ_ONLY_MODE_KEY: &str = "read_only_mode";
+/// Blocked writes kept for the dry-run report; the oldest are dropped beyond this.
+const MAX_DRY_RUN_ENTRIES: usize = 500;
+
+/// When on, nothing is written outside the app's temp, cache and data dirs: exports, save-as
+/// and downloads to a chosen folder stop before touching the disk and report what they would
+/// have written.
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+static DRY_RUN: Mutex<VecDeque<DryRunEntry>> = Mutex::new(VecDeque::new());
+
+/// A write read-only mode stopped, as listed in the dry-run report.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunEntry {
+    /// Seconds since the Unix epoch.
+    at: i64,
+    /// What the command was about to write (`listing export`, `save as`, ...).
+    what: String,
+    path: String,
+}
+
+pub fn is_enabled() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// Called by every command writing to a user-chosen `dest` before it creates or writes anything.
+/// In read-only mode the write is added to the dry-run report instead and the command fails.
+pub fn ensure_writable(dest: &Path, what: &str) -> AppResult<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    if let Ok(mut entries) = DRY_RUN.lock() {
+        if entries.len() >= MAX_DRY_RUN_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(DryRunEntry {
+            at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0),
+            what: what.to_string(),
+            path: dest.display().to_string(),
+        });
+    }
+    Err(AppError::Invalid(messages::text(
+        Message::ReadOnlyBlocked,
+        &[("what", &what), ("path", &dest.display())],
+    )))
+}
+
+/// Loads the persisted toggle. Called once at startup.
+pub fn load_settings(app: &AppHandle) {
+    if let Ok(Some(enabled)) = settings::read_setting::<bool>(app, READ_ONLY_MODE_KEY) {
+        READ_ONLY.store(enabled, Ordering::Relaxed);
+    }
+}
+
+#[tauri::command]
+pub async fn get_read_only_mode() -> AppResult<bool> {
+    Ok(is_enabled())
+}
+
+#[tauri::command]
+pub async fn set_read_only_mode(app: AppHandle, enabled: bool) -> AppResult<()> {
+    settings::write_setting(&app, READ_ONLY_MODE_KEY, &enabled)?;
+    READ_ONLY.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Writes stopped by read-only mode since startup (or the last clear), oldest first.
+#[tauri::command]
+pub async fn get_read_only_dry_run() -> AppResult<Vec<DryRunEntry>> {
+    let entries = DRY_RUN
+        .lock()
+        .map_err(|_| AppError::Task("dry-run report lock poisoned".into()))?;
+    Ok(entries.iter().cloned().collect())
+}
+
+#[tauri::command]
+pub async fn clear_read_only_dry_run() -> AppResult<()> {
+    DRY_RUN
+        .lock()
+        .map_err(|_| AppError::Task("dry-run report lock poisoned".into()))?
+        .clear();
+    Ok(())
+}
@@ -0,0 +1,315 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use tauri::{async_runtime::spawn_blocking, AppHandle, Manager};
+use url::Url;
+
+use crate::access_log;
+use crate::app_error::{AppError, AppResult};
+use crate::archive_listing::extension_of;
+use crate::decoder_plugins;
+use crate::downloads::{emit_progress, part_path};
+use crate::ipc_types::FieldPreview;
+use crate::jobs::JobRegistry;
+use crate::magic;
+use crate::metadata;
+use crate::offline;
+use crate::preview_kind::PreviewKind;
+use crate::preview_limits::PreviewLimits;
+use crate::sftp::Sftp;
+use crate::webdav::WebDav;
+
+const PEEK_BYTES: usize = 64 * 1024;
+/// Entries returned per directory listing.
+const MAX_LIST_ENTRIES: usize = 20_000;
+const PROGRESS_STEP_BYTES: u64 = 4 * 1024 * 1024;
+const COPY_BUFFER_BYTES: usize = 256 * 1024;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteEntry {
+    pub name: String,
+    /// Absolute path on the server.
+    pub path: String,
+    pub is_dir: bool,
+    pub size: Option<u64>,
+    /// Seconds since the Unix epoch.
+    pub modified: Option<u64>,
+}
+
+/// What the WebDAV and SFTP providers offer. Paths are absolute and unencoded.
+pub trait RemoteFs {
+    fn list(&self, path: &str) -> AppResult<Vec<RemoteEntry>>;
+    fn size(&self, path: &str) -> AppResult<u64>;
+    /// Up to `len` bytes from `start`; fewer at the end of the file.
+    fn read_range(&self, path: &str, start: u64, len: u64) -> AppResult<Vec<u8>>;
+    fn open(&self, path: &str) -> AppResult<Box<dyn Read + '_>>;
+}
+
+/// Parses a remote location: `sftp://` for SFTP, `http(s)://` or `webdav(s)://`/`dav(s)://`
+/// for WebDAV. Returns the URL (WebDAV ones rewritten to `http(s)`) and the path it points at.
+fn parse_location(raw: &str) -> AppResult<(Url, String)> {
+    let raw = raw.trim();
+    let (scheme, rest) = raw
+        .split_once("://")
+        .ok_or_else(|| AppError::Invalid(format!("{raw} is not an sftp:// or WebDAV URL")))?;
+    let normalized = match scheme.to_ascii_lowercase().as_str() {
+        "sftp" => format!("sftp://{rest}"),
+        "http" | "webdav" | "dav" => format!("http://{rest}"),
+        "https" | "webdavs" | "davs" => format!("https://{rest}"),
+        _ => {
+            return Err(AppError::Invalid(format!(
+                "{raw} is not an sftp:// or WebDAV URL"
+            )))
+        }
+    };
+    let url = Url::parse(&normalized)
+        .map_err(|e| AppError::Invalid(format!("invalid remote URL {raw}: {e}")))?;
+    let path = match url.path() {
+        "" => "/".to_string(),
+        path => percent_decode(path),
+    };
+    Ok((url, path))
+}
+
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(Ok(byte)) = text.get(i + 1..i + 3).map(|h| u8::from_str_radix(h, 16)) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn connect(url: &Url, password: Option<&str>) -> AppResult<Box<dyn RemoteFs>> {
+    offline::ensure_online(url)?;
+    let password = password.filter(|p| !p.is_empty());
+    let remote: Box<dyn RemoteFs> = match url.scheme() {
+        "sftp" => Box::new(Sftp::connect(url, password)?),
+        _ => Box::new(WebDav::connect(url, password)?),
+    };
+    Ok(remote)
+}
+
+/// `url` pointing at `path` instead, for the entries of a listing.
+fn url_for(url: &Url, path: &str) -> String {
+    let mut url = url.clone();
+    url.set_path(path);
+    let _ = url.set_password(None);
+    url.to_string()
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteListingEntry {
+    #[serde(flatten)]
+    entry: RemoteEntry,
+    /// Location to pass back to the remote commands; carries no password.
+    url: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteDirListing {
+    url: String,
+    path: String,
+    /// Folders first, then files, each by name.
+    entries: Vec<RemoteListingEntry>,
+    /// Whether the folder held more than `MAX_LIST_ENTRIES` entries.
+    truncated: bool,
+}
+
+fn list_dir(raw: &str, password: Option<&str>) -> AppResult<RemoteDirListing> {
+    let (url, path) = parse_location(raw)?;
+    let remote = connect(&url, password)?;
+    let mut entries = remote.list(&path)?;
+    access_log::record(&url, None, None);
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    let truncated = entries.len() > MAX_LIST_ENTRIES;
+    entries.truncate(MAX_LIST_ENTRIES);
+    Ok(RemoteDirListing {
+        url: url_for(&url, &path),
+        entries: entries
+            .into_iter()
+            .map(|entry| RemoteListingEntry {
+                url: url_for(&url, &entry.path),
+                entry,
+            })
+            .collect(),
+        path,
+        truncated,
+    })
+}
+
+fn peek_file(
+    raw: &str,
+    password: Option<&str>,
+    offset: u64,
+    limits: PreviewLimits,
+) -> AppResult<FieldPreview> {
+    let (url, path) = parse_location(raw)?;
+    let remote = connect(&url, password)?;
+    let size = remote.size(&path)?;
+    let data = remote.read_range(&path, offset, limits.window(PEEK_BYTES) as u64)?;
+    access_log::record(&url, None, Some(data.len() as u64));
+    let (preview_text, text_length) = limits.text(&data).unzip();
+    let is_binary = preview_text.is_none();
+    let mut preview = FieldPreview {
+        preview_text,
+        text_length,
+        hex_snippet: limits.hex(&data),
+        guessed_ext: extension_of(&path).or_else(|| magic::sniff_ext(&data)),
+        is_binary,
+        size,
+        metadata: metadata::extract(&data),
+        plugin: None,
+        kind: PreviewKind::Binary,
+        compression: None,
+    };
+    decoder_plugins::apply(&data, &mut preview);
+    preview.kind = PreviewKind::detect(&data, &preview);
+    Ok(preview)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteFetchedFile {
+    /// Local copy, for the shard and archive inspectors.
+    path: String,
+    size: u64,
+    /// Whether an earlier copy of the same size was reused.
+    cached: bool,
+}
+
+fn cache_dir() -> PathBuf {
+    std::env::temp_dir()
+        .join("dataset-inspector")
+        .join("remote-fs")
+}
+
+/// Where a remote file is copied: under the cache, by scheme, host and remote path.
+fn cache_path(url: &Url, path: &str) -> PathBuf {
+    let mut dest = cache_dir().join(format!(
+        "{}-{}-{}",
+        url.scheme(),
+        url.host_str().unwrap_or("local"),
+        url.port().unwrap_or(0)
+    ));
+    for segment in path
+        .split('/')
+        .filter(|s| !s.is_empty() && *s != "." && *s != "..")
+    {
+        dest.push(segment);
+    }
+    dest
+}
+
+fn fetch_file(
+    app: &AppHandle,
+    job_id: &str,
+    raw: &str,
+    password: Option<&str>,
+) -> AppResult<RemoteFetchedFile> {
+    let (url, path) = parse_location(raw)?;
+    let remote = connect(&url, password)?;
+    let size = remote.size(&path)?;
+    let dest = cache_path(&url, &path);
+    if fs::metadata(&dest).is_ok_and(|meta| meta.len() == size) {
+        return Ok(RemoteFetchedFile {
+            path: dest.display().to_string(),
+            size,
+            cached: true,
+        });
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let label = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let part = part_path(&dest);
+    let job = app.state::<JobRegistry>().start(Some(part.clone()));
+    let mut reader = remote.open(&path)?;
+    let mut file = File::create(&part)?;
+    let mut buf = vec![0u8; COPY_BUFFER_BYTES];
+    let mut copied = 0u64;
+    let mut next_report = 0u64;
+    loop {
+        job.ensure_running()?;
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buf[..read])?;
+        copied += read as u64;
+        if copied >= next_report {
+            emit_progress(app, job_id, &label, copied, Some(size));
+            next_report = copied + PROGRESS_STEP_BYTES;
+        }
+    }
+    file.flush()?;
+    drop(file);
+    access_log::record(&url, None, Some(copied));
+    if copied != size {
+        fs::remove_file(&part)?;
+        return Err(AppError::Remote(format!(
+            "incomplete copy of {label}: got {copied} of {size} bytes"
+        )));
+    }
+    emit_progress(app, job_id, &label, copied, Some(size));
+    fs::rename(&part, &dest)?;
+    Ok(RemoteFetchedFile {
+        path: dest.display().to_string(),
+        size,
+        cached: false,
+    })
+}
+
+/// Lists a folder on a WebDAV share or SFTP server.
+#[tauri::command]
+pub async fn remote_list_dir(url: String, password: Option<String>) -> AppResult<RemoteDirListing> {
+    spawn_blocking(move || list_dir(&url, password.as_deref()))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+/// Previews the bytes of a remote file from `offset`, reading only that window.
+#[tauri::command]
+pub async fn remote_peek_file(
+    url: String,
+    password: Option<String>,
+    offset: Option<u64>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
+) -> AppResult<FieldPreview> {
+    let limits = PreviewLimits::new(text_chars, hex_bytes);
+    spawn_blocking(move || peek_file(&url, password.as_deref(), offset.unwrap_or(0), limits))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+/// Copies a remote file into the local cache and returns its path, so shards and archives on
+/// WebDAV or SFTP open in the same inspectors as local ones. Progress is reported as
+/// `download://progress` events under `job_id`.
+#[tauri::command]
+pub async fn remote_fetch_file(
+    app: AppHandle,
+    url: String,
+    password: Option<String>,
+    job_id: String,
+) -> AppResult<RemoteFetchedFile> {
+    spawn_blocking(move || fetch_file(&app, &job_id, &url, password.as_deref()))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
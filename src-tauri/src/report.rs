@@ -0,0 +1,603 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use tauri::{async_runtime::spawn_blocking, AppHandle, State};
+
+use crate::app_error::{AppError, AppResult};
+use crate::ipc_types::{FieldPreview, IndexSummary};
+use crate::litdata::{self, ChunkCache};
+use crate::mosaicml;
+use crate::preview_limits::PreviewLimits;
+use crate::read_only;
+use crate::webdataset::{self, LocalDatasetDetectResponse, WdsScanCache};
+
+const DEFAULT_SAMPLE_COUNT: u32 = 5;
+const MAX_SAMPLE_COUNT: u32 = 50;
+const MAX_FIELDS_PER_SAMPLE: usize = 8;
+const PREVIEW_CHARS: usize = 200;
+const MAX_ISSUES_LISTED: usize = 200;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Html,
+    Markdown,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportOptions {
+    sample_count: Option<u32>,
+    include_validation: Option<bool>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportResult {
+    path: String,
+    bytes: u64,
+    samples_previewed: u32,
+    issues: u32,
+}
+
+struct Table {
+    title: String,
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+#[derive(Default)]
+struct Report {
+    title: String,
+    overview: Vec<(&'static str, String)>,
+    tables: Vec<Table>,
+    previews: Vec<Vec<String>>,
+    issues: Vec<(String, String)>,
+    notes: Vec<String>,
+}
+
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// UTC timestamp without pulling in a date crate (civil-from-days, proleptic Gregorian).
+fn utc_now() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        rem / 3600,
+        (rem % 3600) / 60
+    )
+}
+
+fn preview_cell(preview: &FieldPreview) -> String {
+    match &preview.preview_text {
+        Some(text) => {
+            let mut out: String = text.chars().take(PREVIEW_CHARS).collect();
+            if text.chars().count() > PREVIEW_CHARS {
+                out.push('…');
+            }
+            out
+        }
+        None => format!("0x{}", preview.hex_snippet),
+    }
+}
+
+fn preview_row(location: String, field: String, preview: &FieldPreview) -> Vec<String> {
+    vec![
+        location,
+        field,
+        human_bytes(preview.size),
+        preview.guessed_ext.clone().unwrap_or_default(),
+        preview_cell(preview),
+    ]
+}
+
+fn index_tables(report: &mut Report, summary: &IndexSummary, unit: &'static str) {
+    let total_bytes = summary.chunks.iter().map(|c| c.chunk_bytes).sum::<u64>();
+    let total_items = summary
+        .chunks
+        .iter()
+        .map(|c| u64::from(c.chunk_size))
+        .sum::<u64>();
+    let missing = summary.chunks.iter().filter(|c| !c.exists).count();
+    report.overview.push(("Index", summary.index_path.clone()));
+    report
+        .overview
+        .push(("Fields", summary.data_format.join(", ")));
+    if let Some(compression) = &summary.compression {
+        report.overview.push(("Compression", compression.clone()));
+    }
+    report
+        .overview
+        .push((unit, summary.chunks.len().to_string()));
+    report.overview.push(("Samples", total_items.to_string()));
+    report.overview.push(("Size", human_bytes(total_bytes)));
+    if missing > 0 {
+        report.overview.push(("Missing files", missing.to_string()));
+    }
+    report.tables.push(Table {
+        title: unit.to_string(),
+        headers: vec!["File", "Samples", "Size", "Present"],
+        rows: summary
+            .chunks
+            .iter()
+            .map(|c| {
+                vec![
+                    c.filename.clone(),
+                    c.chunk_size.to_string(),
+                    human_bytes(c.chunk_bytes),
+                    if c.exists { "yes" } else { "no" }.to_string(),
+                ]
+            })
+            .collect(),
+    });
+}
+
+/// Pulls per-file issues out of a serialized validation report; `list_key` names the
+/// array of per-file results.
+fn validation_issues(report: &mut Report, value: serde_json::Value, list_key: &str) {
+    let ok = value.get(format!("{list_key}Ok")).and_then(|v| v.as_u64());
+    let total = value
+        .get(format!("{list_key}Total"))
+        .and_then(|v| v.as_u64());
+    if let (Some(ok), Some(total)) = (ok, total) {
+        report
+            .overview
+            .push(("Validation", format!("{ok} of {total} files passed")));
+    }
+    let Some(items) = value.get(list_key).and_then(|v| v.as_array()) else {
+        return;
+    };
+    for item in items {
+        let filename = item
+            .get("filename")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let issues = item.get("issues").and_then(|v| v.as_array());
+        for issue in issues.into_iter().flatten() {
+            if let Some(issue) = issue.as_str() {
+                report
+                    .issues
+                    .push((filename.to_string(), issue.to_string()));
+            }
+        }
+    }
+}
+
+fn litdata_report(
+    report: &mut Report,
+    app: &AppHandle,
+    cache: &ChunkCache,
+    index_path: PathBuf,
+    sample_count: u32,
+    validate: bool,
+) -> AppResult<()> {
+    let summary = litdata::load_index_sync(index_path.clone())?;
+    report.title = format!("LitData dataset: {}", summary.root_dir);
+    index_tables(report, &summary, "Chunks");
+
+    let index_str = index_path.display().to_string();
+    if let Some(chunk) = summary.chunks.iter().find(|c| c.exists) {
+        let items =
+            litdata::list_chunk_items_sync(index_path.clone(), chunk.filename.clone(), cache)?;
+        for item in items.iter().take(sample_count as usize) {
+            for field in item.fields.iter().take(MAX_FIELDS_PER_SAMPLE) {
+                let location = format!("{} #{}", chunk.filename, item.item_index);
+                let name = summary
+                    .data_format
+                    .get(field.field_index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("field {}", field.field_index));
+                match litdata::preview_field(
+                    &index_str,
+                    &chunk.filename,
+                    item.item_index,
+                    field.field_index,
+                    0,
+                    PreviewLimits::default(),
+                    cache,
+                ) {
+                    Ok(preview) => report.previews.push(preview_row(location, name, &preview)),
+                    Err(e) => report.notes.push(format!("{location} {name}: {e}")),
+                }
+            }
+        }
+    }
+
+    if validate {
+        let validation = litdata::validate_index_sync(app, index_path)?;
+        let value = serde_json::to_value(&validation)
+            .map_err(|e| AppError::Task(format!("serializing validation failed: {e}")))?;
+        validation_issues(report, value, "chunks");
+    }
+    Ok(())
+}
+
+fn mds_report(
+    report: &mut Report,
+    app: &AppHandle,
+    index_path: PathBuf,
+    sample_count: u32,
+    validate: bool,
+) -> AppResult<()> {
+    let summary = mosaicml::mosaicml_load_index_sync(index_path.clone())?;
+    report.title = format!("MosaicML MDS dataset: {}", summary.root_dir);
+    index_tables(report, &summary, "Shards");
+
+    if let Some(shard) = summary.chunks.iter().find(|c| c.exists) {
+        let items =
+            mosaicml::mosaicml_list_samples_sync(index_path.clone(), shard.filename.clone())?;
+        for item in items.iter().take(sample_count as usize) {
+            for field in item.fields.iter().take(MAX_FIELDS_PER_SAMPLE) {
+                let location = format!("{} #{}", shard.filename, item.item_index);
+                let name = summary
+                    .data_format
+                    .get(field.field_index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("field {}", field.field_index));
+                match mosaicml::mosaicml_peek_field_sync(
+                    index_path.clone(),
+                    shard.filename.clone(),
+                    item.item_index,
+                    field.field_index,
+                    0,
+                    PreviewLimits::default(),
+                ) {
+                    Ok(preview) => report.previews.push(preview_row(location, name, &preview)),
+                    Err(e) => report.notes.push(format!("{location} {name}: {e}")),
+                }
+            }
+        }
+    }
+
+    if validate {
+        let validation = mosaicml::mosaicml_validate_sync(app, index_path, false)?;
+        let value = serde_json::to_value(&validation)
+            .map_err(|e| AppError::Task(format!("serializing validation failed: {e}")))?;
+        validation_issues(report, value, "shards");
+    }
+    Ok(())
+}
+
+fn wds_report(
+    report: &mut Report,
+    app: &AppHandle,
+    cache: &WdsScanCache,
+    dir_path: PathBuf,
+    sample_count: u32,
+    validate: bool,
+) -> AppResult<()> {
+    let summary = webdataset::wds_load_dir_sync(dir_path.clone())?;
+    report.title = format!("WebDataset: {}", summary.dir_path);
+    let total_bytes = summary.shards.iter().map(|s| s.bytes).sum::<u64>();
+    report
+        .overview
+        .push(("Directory", summary.dir_path.clone()));
+    report
+        .overview
+        .push(("Shards", summary.shards.len().to_string()));
+    report.overview.push(("Size", human_bytes(total_bytes)));
+    report.tables.push(Table {
+        title: "Shards".into(),
+        headers: vec!["File", "Size"],
+        rows: summary
+            .shards
+            .iter()
+            .map(|s| vec![s.filename.clone(), human_bytes(s.bytes)])
+            .collect(),
+    });
+
+    let Some(first) = summary.shards.iter().find(|s| s.exists) else {
+        return Ok(());
+    };
+    let listing = webdataset::wds_list_samples_sync(
+        app,
+        dir_path.clone(),
+        first.filename.clone(),
+        Some(0),
+        Some(sample_count),
+        Some(false),
+        cache,
+    )?;
+    for sample in &listing.samples {
+        for field in sample.fields.iter().take(MAX_FIELDS_PER_SAMPLE) {
+            let location = format!("{} {}", first.filename, sample.key);
+            match webdataset::wds_peek_member_sync(
+                app,
+                dir_path.clone(),
+                first.filename.clone(),
+                field.member_path.clone(),
+                0,
+                PreviewLimits::default(),
+                cache,
+            ) {
+                Ok(preview) => {
+                    report
+                        .previews
+                        .push(preview_row(location, field.name.clone(), &preview))
+                }
+                Err(e) => report.notes.push(format!("{location} {}: {e}", field.name)),
+            }
+        }
+    }
+
+    if validate {
+        let lint = webdataset::wds_lint_sync(dir_path, first.filename.clone(), None)?;
+        report.overview.push((
+            "Lint",
+            format!(
+                "{} samples scanned in {}, {} issues",
+                lint.samples_scanned,
+                first.filename,
+                lint.issue_counts.values().sum::<u64>()
+            ),
+        ));
+        for issue in lint.issues {
+            report
+                .issues
+                .push((format!("{} {}", first.filename, issue.key), issue.message));
+        }
+    }
+    Ok(())
+}
+
+fn md_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+fn md_table(out: &mut String, headers: &[&str], rows: &[Vec<String>]) {
+    let _ = writeln!(out, "| {} |", headers.join(" | "));
+    let _ = writeln!(out, "|{}", " --- |".repeat(headers.len()));
+    for row in rows {
+        let cells = row.iter().map(|c| md_cell(c)).collect::<Vec<_>>();
+        let _ = writeln!(out, "| {} |", cells.join(" | "));
+    }
+    out.push('\n');
+}
+
+fn render_markdown(report: &Report, generated: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", md_cell(&report.title));
+    let _ = writeln!(out, "_Generated by Dataset Inspector on {generated}._\n");
+    let _ = writeln!(out, "## Overview\n");
+    for (key, value) in &report.overview {
+        let _ = writeln!(out, "- **{key}:** {}", md_cell(value));
+    }
+    out.push('\n');
+    for table in &report.tables {
+        let _ = writeln!(out, "## {}\n", table.title);
+        md_table(&mut out, &table.headers, &table.rows);
+    }
+    if !report.previews.is_empty() {
+        let _ = writeln!(out, "## Sampled previews\n");
+        md_table(
+            &mut out,
+            &["Sample", "Field", "Size", "Type", "Preview"],
+            &report.previews,
+        );
+    }
+    if !report.issues.is_empty() {
+        let _ = writeln!(out, "## Validation issues\n");
+        for (file, issue) in report.issues.iter().take(MAX_ISSUES_LISTED) {
+            let _ = writeln!(out, "- `{}`: {}", file.replace('`', "'"), md_cell(issue));
+        }
+        if report.issues.len() > MAX_ISSUES_LISTED {
+            let _ = writeln!(
+                out,
+                "- …and {} more",
+                report.issues.len() - MAX_ISSUES_LISTED
+            );
+        }
+        out.push('\n');
+    }
+    if !report.notes.is_empty() {
+        let _ = writeln!(out, "## Notes\n");
+        for note in &report.notes {
+            let _ = writeln!(out, "- {}", md_cell(note));
+        }
+    }
+    out
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_table(out: &mut String, headers: &[&str], rows: &[Vec<String>]) {
+    out.push_str("<table><thead><tr>");
+    for h in headers {
+        let _ = write!(out, "<th>{}</th>", html_escape(h));
+    }
+    out.push_str("</tr></thead><tbody>");
+    for row in rows {
+        out.push_str("<tr>");
+        for cell in row {
+            let _ = write!(out, "<td>{}</td>", html_escape(cell));
+        }
+        out.push_str("</tr>");
+    }
+    out.push_str("</tbody></table>\n");
+}
+
+fn render_html(report: &Report, generated: &str) -> String {
+    let mut out = String::new();
+    let title = html_escape(&report.title);
+    let _ = write!(
+        out,
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title>\n<style>\
+         body{{font-family:system-ui,sans-serif;margin:2rem auto;max-width:1100px;color:#222}}\
+         table{{border-collapse:collapse;width:100%;margin-bottom:1.5rem;font-size:0.9rem}}\
+         th,td{{border:1px solid #ddd;padding:4px 8px;text-align:left;vertical-align:top}}\
+         th{{background:#f5f5f5}}td{{word-break:break-all}}\
+         dl{{display:grid;grid-template-columns:max-content 1fr;gap:4px 16px}}dt{{font-weight:600}}\
+         </style></head><body>\n<h1>{title}</h1>\n<p><em>Generated by Dataset Inspector on {}.</em></p>\n",
+        html_escape(generated)
+    );
+    out.push_str("<h2>Overview</h2><dl>");
+    for (key, value) in &report.overview {
+        let _ = write!(
+            out,
+            "<dt>{}</dt><dd>{}</dd>",
+            html_escape(key),
+            html_escape(value)
+        );
+    }
+    out.push_str("</dl>\n");
+    for table in &report.tables {
+        let _ = writeln!(out, "<h2>{}</h2>", html_escape(&table.title));
+        html_table(&mut out, &table.headers, &table.rows);
+    }
+    if !report.previews.is_empty() {
+        out.push_str("<h2>Sampled previews</h2>\n");
+        html_table(
+            &mut out,
+            &["Sample", "Field", "Size", "Type", "Preview"],
+            &report.previews,
+        );
+    }
+    if !report.issues.is_empty() {
+        out.push_str("<h2>Validation issues</h2>\n");
+        let rows = report
+            .issues
+            .iter()
+            .take(MAX_ISSUES_LISTED)
+            .map(|(file, issue)| vec![file.clone(), issue.clone()])
+            .collect::<Vec<_>>();
+        html_table(&mut out, &["File", "Issue"], &rows);
+        if report.issues.len() > MAX_ISSUES_LISTED {
+            let _ = writeln!(
+                out,
+                "<p>…and {} more</p>",
+                report.issues.len() - MAX_ISSUES_LISTED
+            );
+        }
+    }
+    if !report.notes.is_empty() {
+        out.push_str("<h2>Notes</h2><ul>");
+        for note in &report.notes {
+            let _ = write!(out, "<li>{}</li>", html_escape(note));
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</body></html>\n");
+    out
+}
+
+fn generate_report_sync(
+    app: &AppHandle,
+    chunk_cache: &ChunkCache,
+    wds_cache: &WdsScanCache,
+    path: &Path,
+    dest: &Path,
+    format: ReportFormat,
+    options: ReportOptions,
+) -> AppResult<ReportResult> {
+    let sample_count = options
+        .sample_count
+        .unwrap_or(DEFAULT_SAMPLE_COUNT)
+        .min(MAX_SAMPLE_COUNT);
+    let validate = options.include_validation.unwrap_or(true);
+    let mut report = Report::default();
+    match webdataset::detect_local_dataset_sync(path.to_path_buf())? {
+        LocalDatasetDetectResponse::LitdataIndex { index_path } => litdata_report(
+            &mut report,
+            app,
+            chunk_cache,
+            PathBuf::from(index_path),
+            sample_count,
+            validate,
+        )?,
+        LocalDatasetDetectResponse::MdsIndex { index_path } => mds_report(
+            &mut report,
+            app,
+            PathBuf::from(index_path),
+            sample_count,
+            validate,
+        )?,
+        LocalDatasetDetectResponse::WebdatasetDir { dir_path } => wds_report(
+            &mut report,
+            app,
+            wds_cache,
+            PathBuf::from(dir_path),
+            sample_count,
+            validate,
+        )?,
+    }
+
+    let generated = utc_now();
+    let body = match format {
+        ReportFormat::Html => render_html(&report, &generated),
+        ReportFormat::Markdown => render_markdown(&report, &generated),
+    };
+    std::fs::write(dest, &body)?;
+    Ok(ReportResult {
+        path: dest.display().to_string(),
+        bytes: body.len() as u64,
+        samples_previewed: report.previews.len().min(u32::MAX as usize) as u32,
+        issues: report.issues.len().min(u32::MAX as usize) as u32,
+    })
+}
+
+/// Writes a standalone HTML or Markdown report for a local dataset: overview, per-file table,
+/// a few sampled previews and (optionally) validation results.
+#[tauri::command]
+pub async fn generate_report(
+    app: AppHandle,
+    chunk_cache: State<'_, ChunkCache>,
+    wds_cache: State<'_, WdsScanCache>,
+    path: String,
+    format: ReportFormat,
+    dest_path: String,
+    options: Option<ReportOptions>,
+) -> AppResult<ReportResult> {
+    let path = PathBuf::from(path.trim());
+    let dest = PathBuf::from(dest_path.trim());
+    if dest.as_os_str().is_empty() {
+        return Err(AppError::Invalid("report destination is empty".into()));
+    }
+    read_only::ensure_writable(&dest, "dataset report")?;
+    let chunk_cache = (*chunk_cache).clone();
+    let wds_cache = (*wds_cache).clone();
+    spawn_blocking(move || {
+        generate_report_sync(
+            &app,
+            &chunk_cache,
+            &wds_cache,
+            &path,
+            &dest,
+            format,
+            options.unwrap_or_default(),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
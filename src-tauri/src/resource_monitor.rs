@@ -0,0 +1,174 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::app_error::{AppError, AppResult};
+use crate::litdata::ChunkCache;
+use crate::media_stream::MediaStore;
+use crate::settings;
+use crate::webdataset::WdsScanCache;
+use crate::zenodo::ZenodoTarScanCache;
+
+const MEMORY_CAP_KEY: &str = "memory_cap_mb";
+const DEFAULT_MEMORY_CAP_MB: u64 = 2048;
+const MIN_MEMORY_CAP_MB: u64 = 256;
+const MAX_MEMORY_CAP_MB: u64 = 64 * 1024;
+/// Share of the cap past which a warning is emitted.
+const WARN_PERCENT: u64 = 80;
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Emitted when cache memory crosses the warning level, and whenever caches were trimmed to the cap.
+pub const MEMORY_WARNING_EVENT: &str = "resources://memory-warning";
+
+static CAP_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_MEMORY_CAP_MB * 1024 * 1024);
+/// Set while usage is above the warning level, so crossing it warns once.
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+/// An in-memory cache whose size counts against the global cap.
+pub trait MemoryCache {
+    /// Approximate bytes held, and the number of entries.
+    fn usage(&self) -> (usize, usize);
+    /// Drops entries, least recently used first where the cache knows, until at least `bytes`
+    /// were freed or it is empty. Returns the bytes freed.
+    fn shrink_by(&self, bytes: usize) -> usize;
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheUsage {
+    name: &'static str,
+    bytes: u64,
+    entries: u64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryUsage {
+    caches: Vec<CacheUsage>,
+    total_bytes: u64,
+    cap_bytes: u64,
+    warn_bytes: u64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryWarning {
+    usage: MemoryUsage,
+    /// Bytes dropped from caches to get back under the cap; zero for an early warning.
+    trimmed_bytes: u64,
+}
+
+fn cap_bytes() -> u64 {
+    CAP_BYTES.load(Ordering::Relaxed)
+}
+
+fn warn_bytes(cap: u64) -> u64 {
+    cap / 100 * WARN_PERCENT
+}
+
+/// The caches in the order they are trimmed: media a preview can fetch again first, listings
+/// that take a rescan last.
+fn with_caches<T>(app: &AppHandle, f: impl FnOnce(&[(&'static str, &dyn MemoryCache)]) -> T) -> T {
+    let media = app.state::<MediaStore>();
+    let chunks = app.state::<ChunkCache>();
+    let wds = app.state::<WdsScanCache>();
+    let tar = app.state::<ZenodoTarScanCache>();
+    let caches: [(&'static str, &dyn MemoryCache); 4] = [
+        ("media", media.inner()),
+        ("litdataChunks", chunks.inner()),
+        ("webdatasetScans", wds.inner()),
+        ("tarScans", tar.inner()),
+    ];
+    f(&caches)
+}
+
+fn measure(caches: &[(&'static str, &dyn MemoryCache)]) -> MemoryUsage {
+    let caches = caches
+        .iter()
+        .map(|(name, cache)| {
+            let (bytes, entries) = cache.usage();
+            CacheUsage {
+                name: *name,
+                bytes: bytes as u64,
+                entries: entries as u64,
+            }
+        })
+        .collect::<Vec<_>>();
+    let cap = cap_bytes();
+    MemoryUsage {
+        total_bytes: caches.iter().map(|c| c.bytes).sum(),
+        caches,
+        cap_bytes: cap,
+        warn_bytes: warn_bytes(cap),
+    }
+}
+
+/// Trims the caches back under the cap and warns when usage is near or over it.
+fn enforce(app: &AppHandle) -> MemoryUsage {
+    let (usage, trimmed) = with_caches(app, |caches| {
+        let usage = measure(caches);
+        let mut excess = usage.total_bytes.saturating_sub(usage.cap_bytes) as usize;
+        if excess == 0 {
+            return (usage, 0);
+        }
+        let mut trimmed = 0usize;
+        for (_, cache) in caches {
+            if excess == 0 {
+                break;
+            }
+            let freed = cache.shrink_by(excess);
+            trimmed += freed;
+            excess = excess.saturating_sub(freed);
+        }
+        (measure(caches), trimmed)
+    });
+    let near_cap = usage.total_bytes >= usage.warn_bytes;
+    let newly_near = near_cap && !WARNED.swap(true, Ordering::Relaxed);
+    if !near_cap {
+        WARNED.store(false, Ordering::Relaxed);
+    }
+    if trimmed > 0 || newly_near {
+        let _ = app.emit(
+            MEMORY_WARNING_EVENT,
+            MemoryWarning {
+                usage: usage.clone(),
+                trimmed_bytes: trimmed as u64,
+            },
+        );
+    }
+    usage
+}
+
+/// Loads the persisted cap and starts checking cache memory in the background. Called once at
+/// startup.
+pub fn init(app: &AppHandle) {
+    if let Ok(Some(cap_mb)) = settings::read_setting::<u64>(app, MEMORY_CAP_KEY) {
+        let cap_mb = cap_mb.clamp(MIN_MEMORY_CAP_MB, MAX_MEMORY_CAP_MB);
+        CAP_BYTES.store(cap_mb * 1024 * 1024, Ordering::Relaxed);
+    }
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        enforce(&app);
+    });
+}
+
+/// Memory held by the inspector's caches, per cache, with the cap and warning level.
+#[tauri::command]
+pub async fn get_memory_usage(app: AppHandle) -> AppResult<MemoryUsage> {
+    Ok(with_caches(&app, measure))
+}
+
+/// Sets the global cache cap in MiB and trims the caches to it right away.
+#[tauri::command]
+pub async fn set_memory_cap(app: AppHandle, cap_mb: u64) -> AppResult<MemoryUsage> {
+    if !(MIN_MEMORY_CAP_MB..=MAX_MEMORY_CAP_MB).contains(&cap_mb) {
+        return Err(AppError::Invalid(format!(
+            "memory cap must be between {MIN_MEMORY_CAP_MB} and {MAX_MEMORY_CAP_MB} MiB"
+        )));
+    }
+    settings::write_setting(&app, MEMORY_CAP_KEY, &cap_mb)?;
+    CAP_BYTES.store(cap_mb * 1024 * 1024, Ordering::Relaxed);
+    Ok(enforce(&app))
+}
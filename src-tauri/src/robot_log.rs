@@ -0,0 +1,298 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{borrow::Cow, collections::HashMap, fs::File, io::Read, ops::ControlFlow, path::Path};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::{
+    app_error::{AppError, AppResult},
+    mcap,
+    ros_msg::{self, MsgSchema, WireFormat},
+    rosbag,
+};
+
+const DEFAULT_SAMPLES_PER_TOPIC: usize = 3;
+const MAX_SAMPLES_PER_TOPIC: usize = 20;
+/// Message bytes read while sampling before giving up on topics that have not shown up yet.
+const MAX_SAMPLED_BYTES: u64 = 1024 * 1024 * 1024;
+/// Largest chunk inflated in one go.
+pub const MAX_CHUNK_BYTES: u64 = 512 * 1024 * 1024;
+/// Schema text returned per topic.
+const MAX_SCHEMA_CHARS: usize = 16 * 1024;
+const HEX_PREVIEW_BYTES: usize = 32;
+
+/// One connection (ROS bag) or channel (MCAP).
+pub struct LogChannel {
+    pub id: u32,
+    pub topic: String,
+    /// `sensor_msgs/Image`, `sensor_msgs/msg/Image`, ...
+    pub message_type: String,
+    /// `ros1`, `cdr`, `json`, `protobuf`, ...
+    pub message_encoding: String,
+    /// `ros1msg`, `ros2msg`, `jsonschema`, ...
+    pub schema_encoding: Option<String>,
+    pub schema: Option<String>,
+    pub message_count: u64,
+}
+
+/// A chunk of messages and where its record starts.
+pub struct LogChunk {
+    pub offset: u64,
+    pub start_ns: u64,
+    pub end_ns: u64,
+}
+
+/// What a log's index says, before reading any message.
+pub struct LogIndex {
+    /// `rosbag` or `mcap`.
+    pub format: &'static str,
+    /// MCAP profile (`ros1`, `ros2`, ...).
+    pub profile: Option<String>,
+    pub channels: Vec<LogChannel>,
+    /// Empty when the file has no chunk index and has to be read front to back.
+    pub chunks: Vec<LogChunk>,
+    pub compression: Vec<String>,
+    pub start_ns: Option<u64>,
+    pub end_ns: Option<u64>,
+}
+
+pub struct LogMessage<'a> {
+    pub channel: u32,
+    pub log_time_ns: u64,
+    pub data: &'a [u8],
+}
+
+pub fn open(path: &Path) -> AppResult<LogIndex> {
+    let mut magic = [0u8; 13];
+    let read = File::open(path)?.read(&mut magic)?;
+    let magic = &magic[..read];
+    if magic.starts_with(mcap::MAGIC) {
+        mcap::open(path)
+    } else if magic.starts_with(rosbag::MAGIC) {
+        rosbag::open(path)
+    } else {
+        Err(AppError::Invalid(
+            "not a ROS 1 bag (v2.0) or MCAP file".into(),
+        ))
+    }
+}
+
+/// Calls `f` for every message in file order, until it breaks.
+pub fn for_each_message(
+    path: &Path,
+    index: &LogIndex,
+    f: impl FnMut(LogMessage) -> ControlFlow<()>,
+) -> AppResult<()> {
+    match index.format {
+        "mcap" => mcap::for_each_message(path, index, f),
+        _ => rosbag::for_each_message(path, index, f),
+    }
+}
+
+/// Undoes a chunk's compression (`none`/empty, `bz2`, `lz4` or `zstd`).
+pub fn inflate<'a>(compression: &str, data: &'a [u8], size: u64) -> AppResult<Cow<'a, [u8]>> {
+    if size > MAX_CHUNK_BYTES {
+        return Err(AppError::Invalid(format!(
+            "chunk of {size} bytes is too large to read"
+        )));
+    }
+    let reader: Box<dyn Read + '_> = match compression {
+        "" | "none" => return Ok(Cow::Borrowed(data)),
+        "bz2" => Box::new(bzip2::read::BzDecoder::new(data)),
+        "lz4" => Box::new(lz4_flex::frame::FrameDecoder::new(data)),
+        "zstd" => Box::new(zstd::stream::read::Decoder::new(data)?),
+        other => return Err(AppError::UnsupportedCompression(other.to_string())),
+    };
+    let mut out = Vec::with_capacity(size as usize);
+    reader.take(size).read_to_end(&mut out)?;
+    Ok(Cow::Owned(out))
+}
+
+/// Decodes messages to JSON with each channel's schema, parsed once.
+#[derive(Default)]
+pub struct MessageDecoder {
+    schemas: HashMap<u32, Result<MsgSchema, String>>,
+}
+
+impl MessageDecoder {
+    fn schema(&mut self, channel: &LogChannel) -> Result<&MsgSchema, String> {
+        self.schemas
+            .entry(channel.id)
+            .or_insert_with(|| {
+                let text = channel
+                    .schema
+                    .as_deref()
+                    .ok_or_else(|| "channel has no schema".to_string())?;
+                MsgSchema::parse(&channel.message_type, text).map_err(|e| e.to_string())
+            })
+            .as_ref()
+            .map_err(String::clone)
+    }
+
+    pub fn decode(&mut self, channel: &LogChannel, data: &[u8]) -> Value {
+        let format = match channel.message_encoding.as_str() {
+            "ros1" => WireFormat::Ros1,
+            "cdr" => WireFormat::Cdr,
+            "json" => {
+                return serde_json::from_slice(data).unwrap_or_else(
+                    |e| json!({ "error": format!("invalid JSON: {e}"), "size": data.len() }),
+                )
+            }
+            other => {
+                let head = &data[..data.len().min(HEX_PREVIEW_BYTES)];
+                return json!({ "encoding": other, "size": data.len(), "hex": hex::encode(head) });
+            }
+        };
+        self.schema(channel)
+            .and_then(|schema| ros_msg::decode(schema, data, format).map_err(|e| e.to_string()))
+            .unwrap_or_else(|error| json!({ "error": error, "size": data.len() }))
+    }
+
+    /// Decodes a ROS message and returns the raw bytes of its top-level byte array `field`.
+    pub fn decode_with_blob<'d>(
+        &mut self,
+        channel: &LogChannel,
+        data: &'d [u8],
+        field: &str,
+    ) -> AppResult<(Value, Option<&'d [u8]>)> {
+        let format = match channel.message_encoding.as_str() {
+            "ros1" => WireFormat::Ros1,
+            "cdr" => WireFormat::Cdr,
+            other => {
+                return Err(AppError::Invalid(format!(
+                    "{other} messages cannot be unpacked"
+                )))
+            }
+        };
+        let schema = self.schema(channel).map_err(AppError::Invalid)?;
+        ros_msg::decode_with_blob(schema, data, format, Some(field))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RobotLogSample {
+    log_time_ns: u64,
+    size: usize,
+    data: Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RobotLogTopic {
+    topic: String,
+    message_type: String,
+    message_encoding: String,
+    schema_encoding: Option<String>,
+    /// Cut to `MAX_SCHEMA_CHARS`.
+    schema: Option<String>,
+    message_count: u64,
+    samples: Vec<RobotLogSample>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RobotLogSummary {
+    path: String,
+    format: &'static str,
+    profile: Option<String>,
+    start_time_ns: Option<u64>,
+    end_time_ns: Option<u64>,
+    message_count: u64,
+    chunk_count: usize,
+    compression: Vec<String>,
+    topics: Vec<RobotLogTopic>,
+}
+
+fn inspect(path: &Path, samples_per_topic: usize) -> AppResult<RobotLogSummary> {
+    let index = open(path)?;
+
+    // Connections sharing a topic (one per publisher in ROS bags) are listed once.
+    let mut topics: Vec<RobotLogTopic> = Vec::new();
+    let mut topic_of = HashMap::new();
+    for channel in &index.channels {
+        let slot = match topics.iter().position(|t| t.topic == channel.topic) {
+            Some(slot) => slot,
+            None => {
+                topics.push(RobotLogTopic {
+                    topic: channel.topic.clone(),
+                    message_type: channel.message_type.clone(),
+                    message_encoding: channel.message_encoding.clone(),
+                    schema_encoding: channel.schema_encoding.clone(),
+                    schema: channel
+                        .schema
+                        .as_ref()
+                        .map(|s| s.chars().take(MAX_SCHEMA_CHARS).collect()),
+                    message_count: 0,
+                    samples: Vec::new(),
+                });
+                topics.len() - 1
+            }
+        };
+        topics[slot].message_count += channel.message_count;
+        topic_of.insert(channel.id, slot);
+    }
+
+    if samples_per_topic > 0 {
+        let channels = index
+            .channels
+            .iter()
+            .map(|c| (c.id, c))
+            .collect::<HashMap<_, _>>();
+        let mut decoder = MessageDecoder::default();
+        let mut pending = topics.iter().filter(|t| t.message_count > 0).count();
+        let mut budget = MAX_SAMPLED_BYTES;
+        for_each_message(path, &index, |message| {
+            let (Some(slot), Some(channel)) = (
+                topic_of.get(&message.channel),
+                channels.get(&message.channel),
+            ) else {
+                return ControlFlow::Continue(());
+            };
+            let samples = &mut topics[*slot].samples;
+            if samples.len() < samples_per_topic {
+                samples.push(RobotLogSample {
+                    log_time_ns: message.log_time_ns,
+                    size: message.data.len(),
+                    data: decoder.decode(channel, message.data),
+                });
+                if samples.len() == samples_per_topic {
+                    pending = pending.saturating_sub(1);
+                }
+            }
+            budget = budget.saturating_sub(message.data.len() as u64);
+            if pending == 0 || budget == 0 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })?;
+    }
+
+    topics.sort_by(|a, b| a.topic.cmp(&b.topic));
+    Ok(RobotLogSummary {
+        path: path.display().to_string(),
+        format: index.format,
+        profile: index.profile,
+        start_time_ns: index.start_ns,
+        end_time_ns: index.end_ns,
+        message_count: topics.iter().map(|t| t.message_count).sum(),
+        chunk_count: index.chunks.len(),
+        compression: index.compression,
+        topics,
+    })
+}
+
+/// Lists a ROS 1 bag's or MCAP file's topics with message counts, types, schemas and the time
+/// range covered, and decodes the first few messages of each topic to JSON.
+#[tauri::command]
+pub async fn inspect_robot_log(
+    path: String,
+    samples_per_topic: Option<usize>,
+) -> AppResult<RobotLogSummary> {
+    let samples = samples_per_topic
+        .unwrap_or(DEFAULT_SAMPLES_PER_TOPIC)
+        .min(MAX_SAMPLES_PER_TOPIC);
+    spawn_blocking(move || inspect(Path::new(path.trim()), samples))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
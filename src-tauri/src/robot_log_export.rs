@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Cursor;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::read_only;
+use crate::robot_log::{self, MessageDecoder};
+
+/// Files written per export; the report says when the range held more.
+const MAX_EXPORTED_MESSAGES: u32 = 100_000;
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum RobotLogExportFormat {
+    /// Serialized message bytes as stored in the log.
+    Raw,
+    /// Decoded fields, with long arrays summarized as in the preview.
+    Json,
+    /// `sensor_msgs/CompressedImage` payloads as-is, `sensor_msgs/Image` frames as PNG.
+    Image,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RobotLogExportReport {
+    path: String,
+    count: u32,
+    /// Messages in range that could not be written in the chosen format.
+    skipped: u32,
+    /// Whether the export stopped at `MAX_EXPORTED_MESSAGES`.
+    truncated: bool,
+}
+
+fn file_stem(topic: &str) -> String {
+    let stem = topic
+        .trim_matches('/')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if stem.is_empty() {
+        "topic".into()
+    } else {
+        stem
+    }
+}
+
+/// Packs `height` rows of `row_bytes` out of a buffer with `step` bytes per row.
+fn pack_rows(data: &[u8], height: usize, step: usize, row_bytes: usize) -> Option<Vec<u8>> {
+    if step < row_bytes || data.len() < step.checked_mul(height.checked_sub(1)?)? + row_bytes {
+        return None;
+    }
+    let mut out = Vec::with_capacity(row_bytes * height);
+    for row in 0..height {
+        out.extend_from_slice(&data[row * step..row * step + row_bytes]);
+    }
+    Some(out)
+}
+
+/// Encodes a raw `sensor_msgs/Image` frame as PNG, for the common 8-bit color and 8/16-bit
+/// mono encodings.
+fn image_png(fields: &Value, data: &[u8]) -> Option<Vec<u8>> {
+    let width = u32::try_from(fields.get("width")?.as_u64()?).ok()?;
+    let height = u32::try_from(fields.get("height")?.as_u64()?).ok()?;
+    let step = usize::try_from(fields.get("step")?.as_u64()?).ok()?;
+    let big_endian = fields
+        .get("is_bigendian")
+        .and_then(Value::as_u64)
+        .is_some_and(|b| b != 0);
+    let encoding = fields.get("encoding")?.as_str()?;
+    let (channels, bytes_per_channel) = match encoding {
+        "rgb8" | "bgr8" | "8UC3" => (3, 1),
+        "rgba8" | "bgra8" | "8UC4" => (4, 1),
+        "mono8" | "8UC1" => (1, 1),
+        "mono16" | "16UC1" => (1, 2),
+        _ => return None,
+    };
+    let mut pixels = pack_rows(
+        data,
+        height as usize,
+        step,
+        (width as usize).checked_mul(channels * bytes_per_channel)?,
+    )?;
+    if encoding.starts_with("bgr") {
+        for px in pixels.chunks_exact_mut(channels) {
+            px.swap(0, 2);
+        }
+    }
+    let img = match (channels, bytes_per_channel) {
+        (3, _) => image::DynamicImage::ImageRgb8(image::RgbImage::from_raw(width, height, pixels)?),
+        (4, _) => {
+            image::DynamicImage::ImageRgba8(image::RgbaImage::from_raw(width, height, pixels)?)
+        }
+        (_, 1) => {
+            image::DynamicImage::ImageLuma8(image::GrayImage::from_raw(width, height, pixels)?)
+        }
+        _ => {
+            let samples = pixels
+                .chunks_exact(2)
+                .map(|b| {
+                    if big_endian {
+                        u16::from_be_bytes([b[0], b[1]])
+                    } else {
+                        u16::from_le_bytes([b[0], b[1]])
+                    }
+                })
+                .collect();
+            image::DynamicImage::ImageLuma16(image::ImageBuffer::from_raw(width, height, samples)?)
+        }
+    };
+    let mut out = Vec::new();
+    img.write_to(&mut Cursor::new(&mut out), image::ImageFormat::Png)
+        .ok()?;
+    Some(out)
+}
+
+/// The image file for an image message: its bytes and extension.
+fn image_file(
+    decoder: &mut MessageDecoder,
+    channel: &robot_log::LogChannel,
+    data: &[u8],
+) -> Option<(Vec<u8>, &'static str)> {
+    let (fields, blob) = decoder.decode_with_blob(channel, data, "data").ok()?;
+    let blob = blob?;
+    if channel.message_type.ends_with("/CompressedImage") {
+        // `compressedDepth` payloads start with a private header, go unrecognized and are
+        // skipped.
+        let ext = infer::get(blob)?.extension();
+        return Some((blob.to_vec(), ext));
+    }
+    if channel.message_type.ends_with("/Image") {
+        return image_png(&fields, blob).map(|png| (png, "png"));
+    }
+    None
+}
+
+fn export(
+    path: &Path,
+    topic: &str,
+    start_ns: Option<u64>,
+    end_ns: Option<u64>,
+    format: RobotLogExportFormat,
+    dest: &Path,
+) -> AppResult<RobotLogExportReport> {
+    let mut index = robot_log::open(path)?;
+    let channels = index
+        .channels
+        .iter()
+        .filter(|c| c.topic == topic)
+        .map(|c| c.id)
+        .collect::<Vec<_>>();
+    if channels.is_empty() {
+        return Err(AppError::Missing(format!(
+            "topic {topic} is not in this log"
+        )));
+    }
+    let start_ns = start_ns.unwrap_or(0);
+    let end_ns = end_ns.unwrap_or(u64::MAX);
+    index
+        .chunks
+        .retain(|c| c.end_ns >= start_ns && c.start_ns <= end_ns);
+    read_only::ensure_writable(dest, "robot log export")?;
+    std::fs::create_dir_all(dest)?;
+
+    let stem = file_stem(topic);
+    let mut decoder = MessageDecoder::default();
+    let mut count = 0u32;
+    let mut skipped = 0u32;
+    let mut truncated = false;
+    let mut error = None;
+    robot_log::for_each_message(path, &index, |message| {
+        if !channels.contains(&message.channel)
+            || !(start_ns..=end_ns).contains(&message.log_time_ns)
+        {
+            return ControlFlow::Continue(());
+        }
+        if count == MAX_EXPORTED_MESSAGES {
+            truncated = true;
+            return ControlFlow::Break(());
+        }
+        let Some(channel) = index.channels.iter().find(|c| c.id == message.channel) else {
+            return ControlFlow::Continue(());
+        };
+        let file = match format {
+            RobotLogExportFormat::Raw => Some((message.data.to_vec(), "bin")),
+            RobotLogExportFormat::Json => {
+                serde_json::to_vec_pretty(&decoder.decode(channel, message.data))
+                    .ok()
+                    .map(|json| (json, "json"))
+            }
+            RobotLogExportFormat::Image => image_file(&mut decoder, channel, message.data),
+        };
+        let Some((bytes, ext)) = file else {
+            skipped += 1;
+            return ControlFlow::Continue(());
+        };
+        let name = format!("{stem}_{count:06}_{}.{ext}", message.log_time_ns);
+        if let Err(e) = std::fs::write(dest.join(name), bytes) {
+            error = Some(e);
+            return ControlFlow::Break(());
+        }
+        count += 1;
+        ControlFlow::Continue(())
+    })?;
+    if let Some(e) = error {
+        return Err(e.into());
+    }
+
+    Ok(RobotLogExportReport {
+        path: dest.display().to_string(),
+        count,
+        skipped,
+        truncated,
+    })
+}
+
+/// Writes each message of `topic` logged between `start_time_ns` and `end_time_ns` (inclusive,
+/// both optional) to its own file in `dest_dir`, named by topic, sequence and log time.
+#[tauri::command]
+pub async fn export_robot_log_topic(
+    path: String,
+    topic: String,
+    start_time_ns: Option<u64>,
+    end_time_ns: Option<u64>,
+    format: RobotLogExportFormat,
+    dest_dir: String,
+) -> AppResult<RobotLogExportReport> {
+    let dest = dest_dir.trim();
+    if dest.is_empty() {
+        return Err(AppError::Invalid("destination folder is empty".into()));
+    }
+    let dest = PathBuf::from(dest);
+    spawn_blocking(move || {
+        export(
+            Path::new(path.trim()),
+            topic.trim(),
+            start_time_ns,
+            end_time_ns,
+            format,
+            &dest,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
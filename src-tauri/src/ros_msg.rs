@@ -0,0 +1,407 @@
+use serde_json::{Map, Value};
+use std::{collections::HashMap, ops::Range};
+
+use crate::app_error::{AppError, AppResult};
+
+/// Array items kept per decoded array; the rest are decoded past but summarized.
+const MAX_ARRAY_ITEMS: usize = 32;
+/// Byte arrays (`uint8[]`, image and point cloud payloads) longer than this are replaced by
+/// their length.
+const MAX_INLINE_BYTES: usize = 64;
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, Copy)]
+enum Prim {
+    Bool,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    Str,
+    WStr,
+    /// ROS 1 `time` (`uint32` sec, nsec).
+    Time,
+    /// ROS 1 `duration` (`int32` sec, nsec).
+    Duration,
+}
+
+impl Prim {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "bool" => Prim::Bool,
+            "int8" => Prim::I8,
+            "uint8" | "byte" | "char" => Prim::U8,
+            "int16" => Prim::I16,
+            "uint16" => Prim::U16,
+            "int32" => Prim::I32,
+            "uint32" => Prim::U32,
+            "int64" => Prim::I64,
+            "uint64" => Prim::U64,
+            "float32" => Prim::F32,
+            "float64" => Prim::F64,
+            "string" => Prim::Str,
+            "wstring" => Prim::WStr,
+            "time" => Prim::Time,
+            "duration" => Prim::Duration,
+            _ => return None,
+        })
+    }
+
+    /// Fixed encoded size, for skipping long arrays; `None` for strings.
+    fn size(self) -> Option<usize> {
+        match self {
+            Prim::Bool | Prim::I8 | Prim::U8 => Some(1),
+            Prim::I16 | Prim::U16 => Some(2),
+            Prim::I32 | Prim::U32 | Prim::F32 => Some(4),
+            Prim::I64 | Prim::U64 | Prim::F64 | Prim::Time | Prim::Duration => Some(8),
+            Prim::Str | Prim::WStr => None,
+        }
+    }
+}
+
+enum FieldType {
+    Prim(Prim),
+    /// A nested message, by normalized `pkg/Type` name.
+    Msg(String),
+}
+
+enum ArrayKind {
+    Scalar,
+    Fixed(usize),
+    /// Unbounded or bounded (`[<=N]`) sequence, prefixed by its length.
+    Dynamic,
+}
+
+struct MsgField {
+    name: String,
+    ty: FieldType,
+    array: ArrayKind,
+}
+
+/// A parsed `.msg` definition with the definitions it depends on, as embedded in ROS 1 bag
+/// connection headers and `ros1msg`/`ros2msg` MCAP schemas.
+pub struct MsgSchema {
+    root: String,
+    defs: HashMap<String, Vec<MsgField>>,
+}
+
+/// `pkg/msg/Type` (ROS 2) and `pkg/Type` (ROS 1) name the same thing.
+fn normalize(name: &str) -> String {
+    name.trim().replacen("/msg/", "/", 1)
+}
+
+fn package_of(name: &str) -> &str {
+    name.split_once('/').map_or("", |(pkg, _)| pkg)
+}
+
+impl MsgSchema {
+    /// Parses the concatenated definition text of message type `root_type`: the root's fields,
+    /// then one `MSG: pkg/Type` section per dependency, separated by `===` lines.
+    pub fn parse(root_type: &str, text: &str) -> AppResult<Self> {
+        let root = normalize(root_type);
+        let mut sections = vec![(root.clone(), Vec::new())];
+        for line in text.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("===") {
+                continue;
+            }
+            if let Some(name) = trimmed.strip_prefix("MSG:") {
+                sections.push((normalize(name), Vec::new()));
+                continue;
+            }
+            if let Some((_, lines)) = sections.last_mut() {
+                lines.push(trimmed);
+            }
+        }
+
+        let names = sections
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        let mut defs = HashMap::new();
+        for (name, lines) in sections {
+            let pkg = package_of(&name).to_string();
+            let fields = lines
+                .into_iter()
+                .filter_map(|line| parse_field(line, &pkg, &names).transpose())
+                .collect::<AppResult<Vec<_>>>()?;
+            defs.insert(name, fields);
+        }
+        Ok(Self { root, defs })
+    }
+}
+
+/// One field line, or `None` for blanks, comments and constants.
+fn parse_field(line: &str, pkg: &str, names: &[String]) -> AppResult<Option<MsgField>> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    let mut tokens = line.split_whitespace();
+    let (Some(ty), Some(name)) = (tokens.next(), tokens.next()) else {
+        return Ok(None);
+    };
+    // Constants (`int32 FOO=1`, `string BAR = "x"`); ROS 2 default values need no `=`.
+    if line[ty.len()..].contains('=') {
+        return Ok(None);
+    }
+    let (base, array) = match ty.strip_suffix(']').and_then(|t| t.rsplit_once('[')) {
+        Some((base, bound)) if bound.is_empty() || bound.starts_with("<=") => {
+            (base, ArrayKind::Dynamic)
+        }
+        Some((base, bound)) => {
+            let len = bound
+                .parse()
+                .map_err(|_| AppError::Invalid(format!("bad array bound in `{line}`")))?;
+            (base, ArrayKind::Fixed(len))
+        }
+        None => (ty, ArrayKind::Scalar),
+    };
+    // Bounded strings: `string<=10`.
+    let base = base.split("<=").next().unwrap_or(base);
+    let ty = match Prim::parse(base) {
+        Some(prim) => FieldType::Prim(prim),
+        None => FieldType::Msg(resolve(base, pkg, names)),
+    };
+    Ok(Some(MsgField {
+        name: name.to_string(),
+        ty,
+        array,
+    }))
+}
+
+fn resolve(base: &str, pkg: &str, names: &[String]) -> String {
+    if base.contains('/') {
+        return normalize(base);
+    }
+    if base == "Header" {
+        return "std_msgs/Header".into();
+    }
+    let local = format!("{pkg}/{base}");
+    if names.contains(&local) {
+        return local;
+    }
+    names
+        .iter()
+        .find(|n| n.rsplit('/').next() == Some(base))
+        .cloned()
+        .unwrap_or(local)
+}
+
+/// How message bytes are laid out.
+#[derive(Clone, Copy)]
+pub enum WireFormat {
+    /// ROS 1 serialization: little-endian, packed, `uint32` length prefixes.
+    Ros1,
+    /// ROS 2 CDR: a 4-byte encapsulation header, then naturally aligned fields.
+    Cdr,
+}
+
+struct Decoder<'a> {
+    schema: &'a MsgSchema,
+    data: &'a [u8],
+    pos: usize,
+    /// Offset alignment is measured from (after the CDR encapsulation header).
+    origin: usize,
+    cdr: bool,
+    little_endian: bool,
+    /// Top-level byte array whose raw bytes the caller wants back.
+    blob_field: Option<&'a str>,
+    /// Set while decoding `blob_field`.
+    capturing: bool,
+    blob: Option<Range<usize>>,
+}
+
+impl Decoder<'_> {
+    fn align(&mut self, size: usize) {
+        if self.cdr && size > 1 {
+            let offset = self.pos - self.origin;
+            self.pos += (size - offset % size) % size;
+        }
+    }
+
+    fn take(&mut self, len: usize) -> AppResult<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| AppError::Invalid("message ends early for its schema".into()))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> AppResult<[u8; N]> {
+        self.align(N);
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        if !self.little_endian {
+            out.reverse();
+        }
+        Ok(out)
+    }
+
+    fn u32(&mut self) -> AppResult<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    /// A sequence or string length, checked against the bytes left.
+    fn length(&mut self) -> AppResult<usize> {
+        let len = self.u32()? as usize;
+        if len > self.data.len().saturating_sub(self.pos) {
+            return Err(AppError::Invalid(format!(
+                "length {len} runs past the end of the message"
+            )));
+        }
+        Ok(len)
+    }
+
+    fn prim(&mut self, prim: Prim) -> AppResult<Value> {
+        Ok(match prim {
+            Prim::Bool => Value::from(self.take(1)?[0] != 0),
+            Prim::I8 => Value::from(i8::from_le_bytes(self.array()?)),
+            Prim::U8 => Value::from(self.take(1)?[0]),
+            Prim::I16 => Value::from(i16::from_le_bytes(self.array()?)),
+            Prim::U16 => Value::from(u16::from_le_bytes(self.array()?)),
+            Prim::I32 => Value::from(i32::from_le_bytes(self.array()?)),
+            Prim::U32 => Value::from(self.u32()?),
+            Prim::I64 => Value::from(i64::from_le_bytes(self.array()?)),
+            Prim::U64 => Value::from(u64::from_le_bytes(self.array()?)),
+            Prim::F32 => Value::from(f32::from_le_bytes(self.array()?)),
+            Prim::F64 => Value::from(f64::from_le_bytes(self.array()?)),
+            Prim::Str => {
+                let len = self.length()?;
+                let bytes = self.take(len)?;
+                // CDR counts the terminating NUL.
+                let bytes = if self.cdr {
+                    bytes.strip_suffix(&[0]).unwrap_or(bytes)
+                } else {
+                    bytes
+                };
+                Value::from(String::from_utf8_lossy(bytes).into_owned())
+            }
+            Prim::WStr => {
+                let len = self.length()?;
+                let unit = if self.cdr { 2 } else { 1 };
+                let bytes = self.take(len.saturating_mul(unit))?;
+                Value::from(format!("<wstring, {} bytes>", bytes.len()))
+            }
+            Prim::Time => {
+                let sec = self.u32()?;
+                let nsec = self.u32()?;
+                serde_json::json!({ "sec": sec, "nsec": nsec })
+            }
+            Prim::Duration => {
+                let sec = i32::from_le_bytes(self.array()?);
+                let nsec = i32::from_le_bytes(self.array()?);
+                serde_json::json!({ "sec": sec, "nsec": nsec })
+            }
+        })
+    }
+
+    fn single(&mut self, ty: &FieldType, depth: usize) -> AppResult<Value> {
+        match ty {
+            FieldType::Prim(prim) => self.prim(*prim),
+            FieldType::Msg(name) => self.message(name, depth + 1),
+        }
+    }
+
+    fn field(&mut self, field: &MsgField, depth: usize) -> AppResult<Value> {
+        let count = match field.array {
+            ArrayKind::Scalar => return self.single(&field.ty, depth),
+            ArrayKind::Fixed(len) => len,
+            ArrayKind::Dynamic => self.length()?,
+        };
+        if let FieldType::Prim(prim @ (Prim::U8 | Prim::I8)) = field.ty {
+            let start = self.pos;
+            let value = if count > MAX_INLINE_BYTES {
+                self.take(count)?;
+                Value::from(format!("<{count} bytes>"))
+            } else {
+                (0..count)
+                    .map(|_| self.prim(prim))
+                    .collect::<AppResult<Vec<_>>>()
+                    .map(Value::Array)?
+            };
+            if self.capturing {
+                self.blob = Some(start..self.pos);
+            }
+            return Ok(value);
+        }
+        let mut items = Vec::with_capacity(count.min(MAX_ARRAY_ITEMS + 1));
+        for idx in 0..count {
+            if idx == MAX_ARRAY_ITEMS {
+                if let FieldType::Prim(prim) = field.ty {
+                    if let Some(size) = prim.size() {
+                        self.align(size);
+                        self.take((count - idx).saturating_mul(size))?;
+                        break;
+                    }
+                }
+            }
+            let value = self.single(&field.ty, depth)?;
+            if idx < MAX_ARRAY_ITEMS {
+                items.push(value);
+            }
+        }
+        if count > MAX_ARRAY_ITEMS {
+            items.push(Value::from(format!("<{} more>", count - MAX_ARRAY_ITEMS)));
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn message(&mut self, name: &str, depth: usize) -> AppResult<Value> {
+        if depth > MAX_DEPTH {
+            return Err(AppError::Invalid("message nesting is too deep".into()));
+        }
+        let schema = self.schema;
+        let fields = schema
+            .defs
+            .get(name)
+            .ok_or_else(|| AppError::Invalid(format!("schema has no definition for {name}")))?;
+        let mut out = Map::new();
+        for field in fields {
+            self.capturing = depth == 0 && self.blob_field == Some(field.name.as_str());
+            out.insert(field.name.clone(), self.field(field, depth)?);
+        }
+        Ok(Value::Object(out))
+    }
+}
+
+/// Decodes one serialized message into JSON, with long arrays summarized.
+pub fn decode(schema: &MsgSchema, data: &[u8], format: WireFormat) -> AppResult<Value> {
+    decode_with_blob(schema, data, format, None).map(|(value, _)| value)
+}
+
+/// Like [`decode`], also returning the raw bytes of the top-level byte array `blob_field` (an
+/// image's `data`), which the JSON only summarizes.
+pub fn decode_with_blob<'d>(
+    schema: &MsgSchema,
+    data: &'d [u8],
+    format: WireFormat,
+    blob_field: Option<&str>,
+) -> AppResult<(Value, Option<&'d [u8]>)> {
+    let mut decoder = Decoder {
+        schema,
+        data,
+        pos: 0,
+        origin: 0,
+        cdr: false,
+        little_endian: true,
+        blob_field,
+        capturing: false,
+        blob: None,
+    };
+    if let WireFormat::Cdr = format {
+        // Encapsulation kind: 0x0000 CDR_BE, 0x0001 CDR_LE (and their parameter-list forms).
+        let header = decoder.take(4)?;
+        decoder.little_endian = header[1] & 1 == 1;
+        decoder.cdr = true;
+        decoder.origin = 4;
+    }
+    let value = decoder.message(&schema.root, 0)?;
+    Ok((value, decoder.blob.map(|range| &data[range])))
+}
@@ -0,0 +1,304 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    ops::ControlFlow,
+    path::Path,
+};
+
+use crate::{
+    app_error::{AppError, AppResult},
+    robot_log::{self, LogChannel, LogChunk, LogIndex, LogMessage, MAX_CHUNK_BYTES},
+};
+
+pub const MAGIC: &[u8] = b"#ROSBAG V2.0\n";
+
+const OP_MSG_DATA: u8 = 0x02;
+const OP_BAG_HEADER: u8 = 0x03;
+const OP_CHUNK: u8 = 0x05;
+const OP_CHUNK_INFO: u8 = 0x06;
+const OP_CONNECTION: u8 = 0x07;
+/// Record headers are small, but connection headers carry full message definitions.
+const MAX_HEADER_BYTES: u32 = 16 * 1024 * 1024;
+
+fn split_u32(bytes: &[u8]) -> AppResult<(usize, &[u8])> {
+    if bytes.len() < 4 {
+        return Err(AppError::Invalid("bag record ends early".into()));
+    }
+    let (len, rest) = bytes.split_at(4);
+    let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+    if len > rest.len() {
+        return Err(AppError::Invalid("bag record ends early".into()));
+    }
+    Ok((len, rest))
+}
+
+/// Splits one `header_len, header, data_len, data` record off the front of `bytes`.
+fn split_record(bytes: &[u8]) -> AppResult<(&[u8], &[u8], &[u8])> {
+    let (len, rest) = split_u32(bytes)?;
+    let (header, rest) = rest.split_at(len);
+    let (len, rest) = split_u32(rest)?;
+    let (data, rest) = rest.split_at(len);
+    Ok((header, data, rest))
+}
+
+/// The `name=value` fields of a record header or connection header.
+struct Fields<'a>(Vec<(&'a [u8], &'a [u8])>);
+
+impl<'a> Fields<'a> {
+    fn parse(mut bytes: &'a [u8]) -> AppResult<Self> {
+        let mut fields = Vec::new();
+        while !bytes.is_empty() {
+            let (len, rest) = split_u32(bytes)?;
+            let (field, rest) = rest.split_at(len);
+            let eq = field
+                .iter()
+                .position(|b| *b == b'=')
+                .ok_or_else(|| AppError::Invalid("bag header field has no `=`".into()))?;
+            fields.push((&field[..eq], &field[eq + 1..]));
+            bytes = rest;
+        }
+        Ok(Self(fields))
+    }
+
+    fn get(&self, name: &str) -> Option<&'a [u8]> {
+        self.0
+            .iter()
+            .find(|(key, _)| *key == name.as_bytes())
+            .map(|(_, value)| *value)
+    }
+
+    fn fixed<const N: usize>(&self, name: &str) -> AppResult<[u8; N]> {
+        self.get(name)
+            .and_then(|value| value.try_into().ok())
+            .ok_or_else(|| AppError::Invalid(format!("bag record has no valid `{name}` field")))
+    }
+
+    fn op(&self) -> AppResult<u8> {
+        self.fixed::<1>("op").map(|[op]| op)
+    }
+
+    fn u32(&self, name: &str) -> AppResult<u32> {
+        self.fixed(name).map(u32::from_le_bytes)
+    }
+
+    fn u64(&self, name: &str) -> AppResult<u64> {
+        self.fixed(name).map(u64::from_le_bytes)
+    }
+
+    /// A ROS `time` (`uint32` seconds, `uint32` nanoseconds) in nanoseconds.
+    fn time(&self, name: &str) -> AppResult<u64> {
+        let raw = self.fixed::<8>(name)?;
+        let sec = u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]);
+        let nsec = u32::from_le_bytes([raw[4], raw[5], raw[6], raw[7]]);
+        Ok(u64::from(sec) * 1_000_000_000 + u64::from(nsec))
+    }
+
+    fn string(&self, name: &str) -> Option<String> {
+        self.get(name)
+            .map(|value| String::from_utf8_lossy(value).into_owned())
+    }
+}
+
+struct BagFile {
+    reader: BufReader<File>,
+    pos: u64,
+    len: u64,
+}
+
+impl BagFile {
+    fn open(path: &Path) -> AppResult<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+        let mut magic = [0u8; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(AppError::Invalid("not a ROS 1 bag (v2.0)".into()));
+        }
+        Ok(Self {
+            reader,
+            pos: MAGIC.len() as u64,
+            len,
+        })
+    }
+
+    fn seek(&mut self, pos: u64) -> AppResult<()> {
+        self.reader.seek(SeekFrom::Start(pos))?;
+        self.pos = pos;
+        Ok(())
+    }
+
+    fn u32(&mut self) -> AppResult<u32> {
+        let mut buf = [0u8; 4];
+        self.reader.read_exact(&mut buf)?;
+        self.pos += 4;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn data(&mut self, len: u32) -> AppResult<Vec<u8>> {
+        if u64::from(len) > MAX_CHUNK_BYTES || u64::from(len) > self.len.saturating_sub(self.pos) {
+            return Err(AppError::Invalid(format!(
+                "bag record of {len} bytes at byte {} is too large or truncated",
+                self.pos
+            )));
+        }
+        let mut buf = vec![0u8; len as usize];
+        self.reader.read_exact(&mut buf)?;
+        self.pos += u64::from(len);
+        Ok(buf)
+    }
+
+    fn skip(&mut self, len: u32) -> AppResult<()> {
+        self.reader.seek_relative(i64::from(len))?;
+        self.pos += u64::from(len);
+        Ok(())
+    }
+
+    /// The next record's header bytes and data length, or `None` at the end of the file.
+    fn header(&mut self) -> AppResult<Option<(Vec<u8>, u32)>> {
+        if self.pos >= self.len {
+            return Ok(None);
+        }
+        let len = self.u32()?;
+        if len > MAX_HEADER_BYTES {
+            return Err(AppError::Invalid(format!(
+                "bag record header of {len} bytes at byte {}",
+                self.pos
+            )));
+        }
+        let header = self.data(len)?;
+        let data_len = self.u32()?;
+        Ok(Some((header, data_len)))
+    }
+}
+
+fn connection(fields: &Fields, data: &[u8]) -> AppResult<LogChannel> {
+    let conn = Fields::parse(data)?;
+    Ok(LogChannel {
+        id: fields.u32("conn")?,
+        topic: fields
+            .string("topic")
+            .or_else(|| conn.string("topic"))
+            .unwrap_or_default(),
+        message_type: conn.string("type").unwrap_or_default(),
+        message_encoding: "ros1".into(),
+        schema_encoding: Some("ros1msg".into()),
+        schema: conn.string("message_definition"),
+        message_count: 0,
+    })
+}
+
+/// Reads the connection and chunk tables from the index section at the end of the bag.
+pub fn open(path: &Path) -> AppResult<LogIndex> {
+    let mut bag = BagFile::open(path)?;
+    let (header, _) = bag
+        .header()?
+        .ok_or_else(|| AppError::Invalid("bag has no header record".into()))?;
+    let fields = Fields::parse(&header)?;
+    if fields.op()? != OP_BAG_HEADER {
+        return Err(AppError::Invalid(
+            "bag does not start with a bag header record".into(),
+        ));
+    }
+    let index_pos = fields.u64("index_pos")?;
+    if index_pos == 0 || index_pos >= bag.len {
+        return Err(AppError::Invalid(
+            "bag has no index (it was not closed cleanly; `rosbag reindex` rebuilds it)".into(),
+        ));
+    }
+
+    bag.seek(index_pos)?;
+    let mut channels = Vec::new();
+    let mut chunks = Vec::new();
+    let mut counts = HashMap::new();
+    while let Some((header, data_len)) = bag.header()? {
+        let fields = Fields::parse(&header)?;
+        match fields.op()? {
+            OP_CONNECTION => {
+                let data = bag.data(data_len)?;
+                channels.push(connection(&fields, &data)?);
+            }
+            OP_CHUNK_INFO => {
+                let data = bag.data(data_len)?;
+                for pair in data.chunks_exact(8) {
+                    let conn = u32::from_le_bytes([pair[0], pair[1], pair[2], pair[3]]);
+                    let count = u32::from_le_bytes([pair[4], pair[5], pair[6], pair[7]]);
+                    *counts.entry(conn).or_insert(0u64) += u64::from(count);
+                }
+                chunks.push(LogChunk {
+                    offset: fields.u64("chunk_pos")?,
+                    start_ns: fields.time("start_time")?,
+                    end_ns: fields.time("end_time")?,
+                });
+            }
+            _ => bag.skip(data_len)?,
+        }
+    }
+    for channel in &mut channels {
+        channel.message_count = counts.get(&channel.id).copied().unwrap_or(0);
+    }
+    chunks.sort_by_key(|c| c.offset);
+
+    let mut compression = BTreeSet::new();
+    for chunk in &chunks {
+        bag.seek(chunk.offset)?;
+        if let Some((header, _)) = bag.header()? {
+            compression.extend(Fields::parse(&header)?.string("compression"));
+        }
+    }
+
+    Ok(LogIndex {
+        format: "rosbag",
+        profile: None,
+        channels,
+        compression: compression.into_iter().collect(),
+        start_ns: chunks.iter().map(|c| c.start_ns).min(),
+        end_ns: chunks.iter().map(|c| c.end_ns).max(),
+        chunks,
+    })
+}
+
+/// Reads `index.chunks` in order, inflating each and passing its messages to `f`.
+pub fn for_each_message(
+    path: &Path,
+    index: &LogIndex,
+    mut f: impl FnMut(LogMessage) -> ControlFlow<()>,
+) -> AppResult<()> {
+    let mut bag = BagFile::open(path)?;
+    for chunk in &index.chunks {
+        bag.seek(chunk.offset)?;
+        let (header, data_len) = bag
+            .header()?
+            .ok_or_else(|| AppError::Invalid(format!("no chunk at byte {}", chunk.offset)))?;
+        let fields = Fields::parse(&header)?;
+        if fields.op()? != OP_CHUNK {
+            return Err(AppError::Invalid(format!(
+                "index points at a non-chunk record at byte {}",
+                chunk.offset
+            )));
+        }
+        let compression = fields.string("compression").unwrap_or_default();
+        let size = fields.u32("size")?;
+        let data = bag.data(data_len)?;
+        let records = robot_log::inflate(&compression, &data, u64::from(size))?;
+        let mut rest: &[u8] = &records;
+        while !rest.is_empty() {
+            let (header, data, next) = split_record(rest)?;
+            rest = next;
+            let fields = Fields::parse(header)?;
+            if fields.op()? != OP_MSG_DATA {
+                continue;
+            }
+            let message = LogMessage {
+                channel: fields.u32("conn")?,
+                log_time_ns: fields.time("time")?,
+                data,
+            };
+            if f(message).is_break() {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use url::{form_urlencoded, Url};
+
+use crate::app_error::{AppError, AppResult};
+
+const LOCATOR_PREFIX: &str = "dataset-inspector://sample/";
+
+/// One sample (and optionally one field of it) in any of the supported dataset formats.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SampleLocation {
+    #[serde(rename_all = "camelCase")]
+    Litdata {
+        index_path: String,
+        chunk_filename: String,
+        item_index: u32,
+        field_index: Option<usize>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Mds {
+        index_path: String,
+        shard_filename: String,
+        item_index: u32,
+        field_index: Option<usize>,
+    },
+    /// `sample_key` is the member path up to the first dot of its base name (`__key__`).
+    #[serde(rename_all = "camelCase")]
+    Webdataset {
+        dir_path: String,
+        shard_filename: String,
+        sample_key: String,
+        field: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Huggingface {
+        dataset: String,
+        config: Option<String>,
+        split: String,
+        row_index: usize,
+        field_name: Option<String>,
+    },
+}
+
+impl SampleLocation {
+    /// Provider, source path or dataset id, shard (split for Hugging Face), sample, and field.
+    fn parts(&self) -> (&'static str, &str, &str, String, Option<String>) {
+        match self {
+            SampleLocation::Litdata {
+                index_path,
+                chunk_filename,
+                item_index,
+                field_index,
+            } => (
+                "litdata",
+                index_path.as_str(),
+                chunk_filename.as_str(),
+                item_index.to_string(),
+                field_index.map(|f| f.to_string()),
+            ),
+            SampleLocation::Mds {
+                index_path,
+                shard_filename,
+                item_index,
+                field_index,
+            } => (
+                "mds",
+                index_path.as_str(),
+                shard_filename.as_str(),
+                item_index.to_string(),
+                field_index.map(|f| f.to_string()),
+            ),
+            SampleLocation::Webdataset {
+                dir_path,
+                shard_filename,
+                sample_key,
+                field,
+            } => (
+                "webdataset",
+                dir_path.as_str(),
+                shard_filename.as_str(),
+                sample_key.clone(),
+                field.clone(),
+            ),
+            SampleLocation::Huggingface {
+                dataset,
+                split,
+                row_index,
+                field_name,
+                ..
+            } => (
+                "huggingface",
+                dataset.as_str(),
+                split.as_str(),
+                row_index.to_string(),
+                field_name.clone(),
+            ),
+        }
+    }
+
+    /// `dataset-inspector://sample/<provider>?source=…&shard=…&sample=…[&field=…][&config=…]`.
+    fn to_locator(&self) -> String {
+        let (provider, source, shard, sample, field) = self.parts();
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        query
+            .append_pair("source", source.trim())
+            .append_pair("shard", shard.trim())
+            .append_pair("sample", sample.trim());
+        if let Some(field) = field.as_deref().map(str::trim).filter(|f| !f.is_empty()) {
+            query.append_pair("field", field);
+        }
+        if let SampleLocation::Huggingface {
+            config: Some(config),
+            ..
+        } = self
+        {
+            query.append_pair("config", config.trim());
+        }
+        format!("{LOCATOR_PREFIX}{provider}?{}", query.finish())
+    }
+
+    fn from_locator(locator: &str) -> AppResult<Self> {
+        let trimmed = locator.trim();
+        let provider = trimmed
+            .strip_prefix(LOCATOR_PREFIX)
+            .and_then(|rest| rest.split('?').next())
+            .ok_or_else(|| AppError::Invalid("Not a sample locator.".into()))?;
+        let url = Url::parse(trimmed)
+            .map_err(|_| AppError::Invalid("Malformed sample locator.".into()))?;
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.into_owned())
+                .filter(|v| !v.is_empty())
+        };
+        let required = |name: &str| {
+            param(name)
+                .ok_or_else(|| AppError::Invalid(format!("Sample locator is missing {name}.")))
+        };
+        let number = |name: &str| {
+            required(name)?
+                .parse::<usize>()
+                .map_err(|_| AppError::Invalid(format!("Sample locator has an invalid {name}.")))
+        };
+        let index = |name: &str| {
+            u32::try_from(number(name)?)
+                .map_err(|_| AppError::Invalid(format!("Sample locator has an invalid {name}.")))
+        };
+        let field_index = || param("field").map(|_| number("field")).transpose();
+
+        match provider {
+            "litdata" => Ok(SampleLocation::Litdata {
+                index_path: required("source")?,
+                chunk_filename: required("shard")?,
+                item_index: index("sample")?,
+                field_index: field_index()?,
+            }),
+            "mds" => Ok(SampleLocation::Mds {
+                index_path: required("source")?,
+                shard_filename: required("shard")?,
+                item_index: index("sample")?,
+                field_index: field_index()?,
+            }),
+            "webdataset" => Ok(SampleLocation::Webdataset {
+                dir_path: required("source")?,
+                shard_filename: required("shard")?,
+                sample_key: required("sample")?,
+                field: param("field"),
+            }),
+            "huggingface" => Ok(SampleLocation::Huggingface {
+                dataset: required("source")?,
+                config: param("config"),
+                split: required("shard")?,
+                row_index: number("sample")?,
+                field_name: param("field"),
+            }),
+            other => Err(AppError::Invalid(format!(
+                "Unknown sample locator provider: {other}."
+            ))),
+        }
+    }
+}
+
+/// Canonical string for sharing a sample with teammates; `parse_sample_locator` reverses it.
+#[tauri::command]
+pub async fn sample_locator(location: SampleLocation) -> AppResult<String> {
+    Ok(location.to_locator())
+}
+
+#[tauri::command]
+pub async fn parse_sample_locator(locator: String) -> AppResult<SampleLocation> {
+    SampleLocation::from_locator(&locator)
+}
@@ -0,0 +1,28 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::app_error::{AppError, AppResult};
+use crate::ipc_types::SavedLeafResponse;
+use crate::read_only;
+
+/// Writes decoded leaf bytes to the path chosen in the save dialog, replacing any existing file.
+pub fn write_to(destination_path: &str, data: &[u8], ext: String) -> AppResult<SavedLeafResponse> {
+    let dest = destination_path.trim();
+    if dest.is_empty() {
+        return Err(AppError::Invalid("destination path is empty".into()));
+    }
+    let dest = PathBuf::from(dest);
+    read_only::ensure_writable(&dest, "save as")?;
+    if dest.is_dir() {
+        return Err(AppError::Invalid(format!(
+            "destination is a directory: {}",
+            dest.display()
+        )));
+    }
+    fs::write(&dest, data)?;
+    Ok(SavedLeafResponse {
+        path: dest.display().to_string(),
+        size: data.len() as u64,
+        ext,
+    })
+}
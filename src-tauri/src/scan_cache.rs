@@ -0,0 +1,264 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::app_error::{AppError, AppResult};
+use crate::webdataset::WdsScanCache;
+use crate::zenodo::ZenodoTarScanCache;
+
+/// Memory budget for each scan cache. Least recently used entries are dropped beyond it.
+pub const SCAN_CACHE_BUDGET_BYTES: usize = 512 * 1024 * 1024;
+
+/// Emitted when a cached scan was dropped because its source changed on disk.
+pub const SOURCE_CHANGED_EVENT: &str = "scan://source-changed";
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceChangedEvent {
+    pub source: String,
+}
+
+pub fn emit_source_changed(app: &AppHandle, source: &str) {
+    let _ = app.emit(
+        SOURCE_CHANGED_EVENT,
+        SourceChangedEvent {
+            source: source.to_string(),
+        },
+    );
+}
+
+/// Size and modification time of a local source; a scan is reused only while both still match.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SourceFingerprint {
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+impl SourceFingerprint {
+    pub fn of(path: &Path) -> std::io::Result<Self> {
+        let meta = std::fs::metadata(path)?;
+        Ok(Self {
+            size: meta.len(),
+            modified: meta.modified().ok(),
+        })
+    }
+}
+
+/// Rough heap usage of a cached scan state, used to enforce the budget.
+pub trait ScanFootprint {
+    fn approx_bytes(&self) -> usize;
+}
+
+/// A cached scan state with its footprint as of the last time it was unlocked, so the cache can
+/// be measured while a long scan holds the lock.
+pub struct ScanSlot<S> {
+    state: Mutex<S>,
+    bytes: AtomicUsize,
+}
+
+/// Access to a locked scan state; records the state's footprint when released.
+pub struct StateGuard<'a, S: ScanFootprint> {
+    guard: MutexGuard<'a, S>,
+    bytes: &'a AtomicUsize,
+}
+
+impl<S: ScanFootprint> ScanSlot<S> {
+    fn new(state: S) -> Self {
+        let bytes = AtomicUsize::new(state.approx_bytes());
+        Self {
+            state: Mutex::new(state),
+            bytes,
+        }
+    }
+
+    /// Locks the state. One poisoned by a panicking scan is replaced on the next lookup, so the
+    /// error asks for the scan to be run again.
+    pub fn lock(&self) -> AppResult<StateGuard<'_, S>> {
+        let guard = self.state.lock().map_err(|_| {
+            AppError::Interrupted("an earlier scan of this source crashed; run it again".into())
+        })?;
+        Ok(StateGuard {
+            guard,
+            bytes: &self.bytes,
+        })
+    }
+
+    fn footprint(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    fn is_locked(&self) -> bool {
+        matches!(self.state.try_lock(), Err(TryLockError::WouldBlock))
+    }
+}
+
+impl<S: ScanFootprint> Deref for StateGuard<'_, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.guard
+    }
+}
+
+impl<S: ScanFootprint> DerefMut for StateGuard<'_, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.guard
+    }
+}
+
+impl<S: ScanFootprint> Drop for StateGuard<'_, S> {
+    fn drop(&mut self) {
+        self.bytes
+            .store(self.guard.approx_bytes(), Ordering::Relaxed);
+    }
+}
+
+struct LruEntry<S> {
+    state: Arc<ScanSlot<S>>,
+    fingerprint: Option<SourceFingerprint>,
+    last_used: u64,
+}
+
+/// Scan states keyed by source, evicted least-recently-used first once their combined
+/// footprint exceeds the budget. States locked by a running scan are never dropped, and states
+/// still referenced by a finished command stay alive until that command returns.
+pub struct LruScanMap<S> {
+    entries: HashMap<String, LruEntry<S>>,
+    tick: u64,
+    budget: usize,
+}
+
+impl<S> Default for LruScanMap<S> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            tick: 0,
+            budget: SCAN_CACHE_BUDGET_BYTES,
+        }
+    }
+}
+
+impl<S: ScanFootprint> LruScanMap<S> {
+    /// Returns the cached state for `key`, creating it when missing, when `fingerprint` no longer
+    /// matches the one it was created with, or when a scan panicked holding it. The flag reports
+    /// whether a stale entry was replaced.
+    pub fn get_or_try_insert(
+        &mut self,
+        key: &str,
+        fingerprint: Option<SourceFingerprint>,
+        create: impl FnOnce() -> AppResult<S>,
+    ) -> AppResult<(Arc<ScanSlot<S>>, bool)> {
+        self.tick += 1;
+        let tick = self.tick;
+        let stale = self.entries.get(key).is_some_and(|entry| {
+            entry.fingerprint != fingerprint || entry.state.state.is_poisoned()
+        });
+        if stale {
+            self.entries.remove(key);
+        }
+        let state = match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = tick;
+                entry.state.clone()
+            }
+            None => {
+                let state = Arc::new(ScanSlot::new(create()?));
+                self.entries.insert(
+                    key.to_string(),
+                    LruEntry {
+                        state: state.clone(),
+                        fingerprint,
+                        last_used: tick,
+                    },
+                );
+                state
+            }
+        };
+        self.evict(key);
+        Ok((state, stale))
+    }
+
+    fn evict(&mut self, keep: &str) {
+        let total = self.approx_bytes();
+        if total > self.budget {
+            self.drop_least_recent(total - self.budget, Some(keep));
+        }
+    }
+
+    /// Sizes of the cached states, as of their last unlock, with when each was last used.
+    fn measure(&self) -> Vec<(String, u64, usize)> {
+        self.entries
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.last_used, entry.state.footprint()))
+            .collect()
+    }
+
+    fn approx_bytes(&self) -> usize {
+        self.measure().iter().map(|(_, _, bytes)| *bytes).sum()
+    }
+
+    /// Combined footprint of the cached states, and how many there are.
+    pub fn usage(&self) -> (usize, usize) {
+        (self.approx_bytes(), self.entries.len())
+    }
+
+    /// Drops least recently used states until at least `bytes` were freed or only locked ones are
+    /// left. Returns the footprint dropped.
+    pub fn shrink_by(&mut self, bytes: usize) -> usize {
+        self.drop_least_recent(bytes, None)
+    }
+
+    fn drop_least_recent(&mut self, bytes: usize, keep: Option<&str>) -> usize {
+        let mut sizes = self.measure();
+        sizes.sort_by_key(|(_, last_used, _)| *last_used);
+        let mut freed = 0usize;
+        for (key, _, size) in sizes {
+            if freed >= bytes {
+                break;
+            }
+            if keep == Some(key.as_str())
+                || self.entries.get(&key).is_some_and(|e| e.state.is_locked())
+            {
+                continue;
+            }
+            self.entries.remove(&key);
+            freed += size;
+        }
+        freed
+    }
+
+    /// Drops the entry for `key`, or every entry when `key` is `None`. Returns how many were removed.
+    pub fn invalidate(&mut self, key: Option<&str>) -> usize {
+        match key {
+            Some(key) => usize::from(self.entries.remove(key).is_some()),
+            None => {
+                let removed = self.entries.len();
+                self.entries.clear();
+                removed
+            }
+        }
+    }
+}
+
+/// Locks a cached scan state; see [`ScanSlot::lock`].
+pub fn lock_state<S: ScanFootprint>(state: &ScanSlot<S>) -> AppResult<StateGuard<'_, S>> {
+    state.lock()
+}
+
+/// Forgets cached WebDataset shard listings and remote TAR scans, including TAR scan progress saved
+/// on disk. `source` is a shard path or a Zenodo content URL; without it both caches are cleared.
+#[tauri::command]
+pub async fn invalidate_scan_cache(
+    source: Option<String>,
+    wds_cache: State<'_, WdsScanCache>,
+    tar_cache: State<'_, ZenodoTarScanCache>,
+) -> AppResult<u32> {
+    let source = source.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let removed = wds_cache.invalidate(source)? + tar_cache.invalidate(source)?;
+    Ok(removed.min(u32::MAX as usize) as u32)
+}
@@ -0,0 +1,72 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Oldest checkpoints are removed beyond this many.
+const MAX_CHECKPOINTS: usize = 64;
+
+fn checkpoint_dir() -> PathBuf {
+    std::env::temp_dir()
+        .join("dataset-inspector")
+        .join("scan-checkpoints")
+}
+
+fn checkpoint_path(source: &str) -> PathBuf {
+    let digest = Sha256::digest(source.trim().as_bytes());
+    checkpoint_dir().join(format!("{}.json", hex::encode(digest)))
+}
+
+/// Progress saved by an earlier session for `source`, if any.
+pub fn load<T: DeserializeOwned>(source: &str) -> Option<T> {
+    let bytes = fs::read(checkpoint_path(source)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Best effort: a checkpoint that cannot be written only means the next session starts over.
+pub fn store<T: Serialize>(source: &str, value: &T) {
+    let Ok(json) = serde_json::to_vec(value) else {
+        return;
+    };
+    let dir = checkpoint_dir();
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    let path = checkpoint_path(source);
+    let tmp = path.with_extension("tmp");
+    if fs::write(&tmp, json).is_ok() && fs::rename(&tmp, &path).is_ok() {
+        prune(&dir);
+    }
+}
+
+/// Deletes the checkpoint for `source`, or all of them when `source` is `None`.
+pub fn remove(source: Option<&str>) {
+    match source {
+        Some(source) => {
+            let _ = fs::remove_file(checkpoint_path(source));
+        }
+        None => {
+            let _ = fs::remove_dir_all(checkpoint_dir());
+        }
+    }
+}
+
+fn prune(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files = entries
+        .flatten()
+        .filter(|e| e.path().extension().and_then(|x| x.to_str()) == Some("json"))
+        .map(|e| (e.metadata().and_then(|m| m.modified()).ok(), e.path()))
+        .collect::<Vec<_>>();
+    if files.len() <= MAX_CHECKPOINTS {
+        return;
+    }
+    files.sort_by_key(|(modified, _)| *modified);
+    let excess = files.len() - MAX_CHECKPOINTS;
+    for (_, path) in files.into_iter().take(excess) {
+        let _ = fs::remove_file(path);
+    }
+}
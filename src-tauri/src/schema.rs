@@ -0,0 +1,413 @@
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::{litdata, mosaicml, parquet, webdataset};
+
+const WDS_SCHEMA_SAMPLES: u32 = 1_000;
+/// Samples read from each shard when a whole WebDataset directory is compared.
+const WDS_ROOT_SAMPLES_PER_SHARD: u32 = 200;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaField {
+    name: String,
+    dtype: Option<String>,
+    /// For WebDataset: fraction of scanned samples containing this field.
+    coverage: Option<f64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSnapshot {
+    kind: String,
+    source: String,
+    fields: Vec<SchemaField>,
+    /// Shards the fields were collected from; the schema is their union.
+    shard_count: u32,
+    /// Shards lacking some of the union's fields or typing one differently.
+    divergent_shards: Vec<DivergentShard>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DivergentShard {
+    shard: String,
+    missing: Vec<String>,
+    /// `left_dtype` is the type in the union, `right_dtype` the one this shard uses.
+    retyped: Vec<SchemaFieldChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaFieldChange {
+    name: String,
+    left_dtype: Option<String>,
+    right_dtype: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaDiffResponse {
+    left: SchemaSnapshot,
+    right: SchemaSnapshot,
+    added: Vec<SchemaField>,
+    removed: Vec<SchemaField>,
+    retyped: Vec<SchemaFieldChange>,
+    identical: bool,
+}
+
+fn file_name_lower(path: &Path) -> String {
+    path.file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+fn parquet_files_in_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok().map(|e2| e2.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|f| f.to_str())
+                .map(parquet::looks_like_parquet)
+                .unwrap_or(false)
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+fn single_snapshot(kind: &str, source: String, fields: Vec<SchemaField>) -> SchemaSnapshot {
+    SchemaSnapshot {
+        kind: kind.into(),
+        source,
+        fields,
+        shard_count: 1,
+        divergent_shards: Vec::new(),
+    }
+}
+
+/// Merges per-shard field lists into one schema. Fields keep the order and type of the shard
+/// they first appear in; shards missing a field or typing it differently are listed.
+fn union_snapshot(
+    kind: &str,
+    source: String,
+    shards: Vec<(String, Vec<SchemaField>)>,
+) -> SchemaSnapshot {
+    let mut fields: Vec<SchemaField> = Vec::new();
+    let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+    for (_, shard_fields) in &shards {
+        for field in shard_fields {
+            if !positions.contains_key(&field.name) {
+                positions.insert(field.name.clone(), fields.len());
+                fields.push(field.clone());
+            }
+        }
+    }
+    let divergent_shards = shards
+        .iter()
+        .filter_map(|(shard, shard_fields)| {
+            let by_name: BTreeMap<&str, &SchemaField> =
+                shard_fields.iter().map(|f| (f.name.as_str(), f)).collect();
+            let missing: Vec<String> = fields
+                .iter()
+                .filter(|f| !by_name.contains_key(f.name.as_str()))
+                .map(|f| f.name.clone())
+                .collect();
+            let retyped: Vec<SchemaFieldChange> = fields
+                .iter()
+                .filter_map(|f| {
+                    let own = by_name.get(f.name.as_str())?;
+                    if own.dtype == f.dtype {
+                        return None;
+                    }
+                    Some(SchemaFieldChange {
+                        name: f.name.clone(),
+                        left_dtype: f.dtype.clone(),
+                        right_dtype: own.dtype.clone(),
+                    })
+                })
+                .collect();
+            if missing.is_empty() && retyped.is_empty() {
+                return None;
+            }
+            Some(DivergentShard {
+                shard: shard.clone(),
+                missing,
+                retyped,
+            })
+        })
+        .collect();
+    SchemaSnapshot {
+        kind: kind.into(),
+        source,
+        fields,
+        shard_count: shards.len() as u32,
+        divergent_shards,
+    }
+}
+
+fn shard_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|f| f.to_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn mds_fields(columns: Vec<(String, String)>) -> Vec<SchemaField> {
+    columns
+        .into_iter()
+        .map(|(name, encoding)| SchemaField {
+            name,
+            dtype: Some(encoding),
+            coverage: None,
+        })
+        .collect()
+}
+
+fn mds_snapshot(index_path: &Path, shard_filename: &str) -> AppResult<SchemaSnapshot> {
+    let (resolved, columns) = mosaicml::mds_shard_columns(index_path, shard_filename)?;
+    Ok(single_snapshot(
+        "mds",
+        format!("{resolved} · {shard_filename}"),
+        mds_fields(columns),
+    ))
+}
+
+fn mds_root_snapshot(index_path: &Path) -> AppResult<SchemaSnapshot> {
+    let (resolved, shards) = mosaicml::mds_columns_by_shard(index_path)?;
+    let shards = shards
+        .into_iter()
+        .map(|(name, columns)| (name, mds_fields(columns)))
+        .collect();
+    Ok(union_snapshot("mds", resolved, shards))
+}
+
+fn litdata_snapshot(path: &Path) -> AppResult<SchemaSnapshot> {
+    let (source, data_format) = litdata::litdata_data_format(path)?;
+    let fields = data_format
+        .into_iter()
+        .enumerate()
+        .map(|(idx, fmt)| SchemaField {
+            name: format!("field_{idx}"),
+            dtype: Some(fmt),
+            coverage: None,
+        })
+        .collect();
+    Ok(single_snapshot("litdata", source, fields))
+}
+
+fn wds_fields(counts: BTreeMap<String, u32>, scanned: u32) -> Vec<SchemaField> {
+    let denom = scanned.max(1) as f64;
+    counts
+        .into_iter()
+        .map(|(name, count)| SchemaField {
+            name,
+            dtype: None,
+            coverage: Some(count as f64 / denom),
+        })
+        .collect()
+}
+
+fn wds_snapshot(shard_path: &Path) -> AppResult<SchemaSnapshot> {
+    let (counts, scanned) = webdataset::wds_shard_field_counts(shard_path, WDS_SCHEMA_SAMPLES)?;
+    Ok(single_snapshot(
+        "webdataset",
+        shard_path.display().to_string(),
+        wds_fields(counts, scanned),
+    ))
+}
+
+/// Union over every shard in `dir`; coverage is over all samples read, not per shard.
+fn wds_root_snapshot(dir: &Path, shards: &[PathBuf]) -> AppResult<SchemaSnapshot> {
+    let mut total_counts: BTreeMap<String, u32> = BTreeMap::new();
+    let mut total_scanned = 0u32;
+    let mut per_shard = Vec::with_capacity(shards.len());
+    for shard in shards {
+        let (counts, scanned) =
+            webdataset::wds_shard_field_counts(shard, WDS_ROOT_SAMPLES_PER_SHARD)?;
+        for (name, count) in &counts {
+            *total_counts.entry(name.clone()).or_insert(0) += count;
+        }
+        total_scanned += scanned;
+        per_shard.push((shard_name(shard), wds_fields(counts, scanned)));
+    }
+    let mut snapshot = union_snapshot("webdataset", dir.display().to_string(), per_shard);
+    snapshot.fields = wds_fields(total_counts, total_scanned);
+    Ok(snapshot)
+}
+
+fn parquet_fields(path: &Path) -> AppResult<Vec<SchemaField>> {
+    let meta = parquet::read_parquet_meta(path)?;
+    Ok(meta
+        .columns
+        .iter()
+        .map(|c| SchemaField {
+            name: c.path.clone(),
+            dtype: Some(format!("{} {}", c.repetition, c.type_label())),
+            coverage: None,
+        })
+        .collect())
+}
+
+fn parquet_snapshot(path: &Path) -> AppResult<SchemaSnapshot> {
+    Ok(single_snapshot(
+        "parquet",
+        path.display().to_string(),
+        parquet_fields(path)?,
+    ))
+}
+
+/// Union over every Parquet file's footer in `dir`.
+fn parquet_root_snapshot(dir: &Path, files: &[PathBuf]) -> AppResult<SchemaSnapshot> {
+    let per_file = files
+        .iter()
+        .map(|file| Ok((shard_name(file), parquet_fields(file)?)))
+        .collect::<AppResult<Vec<_>>>()?;
+    Ok(union_snapshot(
+        "parquet",
+        dir.display().to_string(),
+        per_file,
+    ))
+}
+
+fn snapshot_for_path(path: &Path) -> AppResult<SchemaSnapshot> {
+    let trimmed = path.to_string_lossy().trim().to_string();
+    if trimmed.is_empty() {
+        return Err(AppError::Invalid("path is empty".into()));
+    }
+
+    if path.is_file() {
+        let name = file_name_lower(path);
+        if parquet::looks_like_parquet(&name) {
+            return parquet_snapshot(path);
+        }
+        if webdataset::looks_like_wds_shard(&name) {
+            return wds_snapshot(path);
+        }
+        if webdataset::looks_like_mds_shard(&name) {
+            let dir = path.parent().unwrap_or_else(|| Path::new("."));
+            let shard = path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+            return mds_snapshot(dir, shard);
+        }
+        if name.contains("index.json") && mosaicml::detect_mds_index_path(path).is_some() {
+            return mds_root_snapshot(path);
+        }
+        return litdata_snapshot(path);
+    }
+
+    if path.is_dir() {
+        if let Some(index) = webdataset::find_litdata_index_in_dir(path)? {
+            if mosaicml::detect_mds_index_path(&index).is_some() {
+                return mds_root_snapshot(&index);
+            }
+            return litdata_snapshot(&index);
+        }
+        let shards = webdataset::wds_shards_in_dir(path);
+        if !shards.is_empty() {
+            return wds_root_snapshot(path, &shards);
+        }
+        let files = parquet_files_in_dir(path);
+        if !files.is_empty() {
+            return parquet_root_snapshot(path, &files);
+        }
+        return Err(AppError::Missing(format!(
+            "no LitData, MDS, WebDataset, or Parquet data found in {}",
+            path.display()
+        )));
+    }
+
+    Err(AppError::Missing(format!(
+        "path does not exist: {}",
+        path.display()
+    )))
+}
+
+fn diff_snapshots(left: SchemaSnapshot, right: SchemaSnapshot) -> SchemaDiffResponse {
+    let left_map: BTreeMap<&str, &SchemaField> =
+        left.fields.iter().map(|f| (f.name.as_str(), f)).collect();
+    let right_map: BTreeMap<&str, &SchemaField> =
+        right.fields.iter().map(|f| (f.name.as_str(), f)).collect();
+
+    let added: Vec<SchemaField> = right
+        .fields
+        .iter()
+        .filter(|f| !left_map.contains_key(f.name.as_str()))
+        .cloned()
+        .collect();
+    let removed: Vec<SchemaField> = left
+        .fields
+        .iter()
+        .filter(|f| !right_map.contains_key(f.name.as_str()))
+        .cloned()
+        .collect();
+    let retyped: Vec<SchemaFieldChange> = left
+        .fields
+        .iter()
+        .filter_map(|l| {
+            let r = right_map.get(l.name.as_str())?;
+            if l.dtype == r.dtype {
+                return None;
+            }
+            Some(SchemaFieldChange {
+                name: l.name.clone(),
+                left_dtype: l.dtype.clone(),
+                right_dtype: r.dtype.clone(),
+            })
+        })
+        .collect();
+
+    let identical =
+        left.kind == right.kind && added.is_empty() && removed.is_empty() && retyped.is_empty();
+    SchemaDiffResponse {
+        left,
+        right,
+        added,
+        removed,
+        retyped,
+        identical,
+    }
+}
+
+#[tauri::command]
+pub async fn diff_schemas(left_path: String, right_path: String) -> AppResult<SchemaDiffResponse> {
+    spawn_blocking(move || {
+        let left = snapshot_for_path(&PathBuf::from(left_path.trim()))?;
+        let right = snapshot_for_path(&PathBuf::from(right_path.trim()))?;
+        Ok(diff_snapshots(left, right))
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+/// Diffs declared fields (e.g. from `croissant.json`) against the schema observed at
+/// `observed_path`. Declared types don't share a vocabulary with on-disk encodings, so only field
+/// presence is reported.
+pub fn diff_declared_fields(
+    kind: &str,
+    source: &str,
+    fields: Vec<(String, Option<String>)>,
+    observed_path: &Path,
+) -> AppResult<SchemaDiffResponse> {
+    let fields = fields
+        .into_iter()
+        .map(|(name, dtype)| SchemaField {
+            name,
+            dtype,
+            coverage: None,
+        })
+        .collect();
+    let declared = single_snapshot(kind, source.to_string(), fields);
+    let observed = snapshot_for_path(observed_path)?;
+    let mut diff = diff_snapshots(declared, observed);
+    diff.retyped.clear();
+    diff.identical = diff.added.is_empty() && diff.removed.is_empty();
+    Ok(diff)
+}
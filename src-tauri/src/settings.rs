@@ -0,0 +1,32 @@
+use serde::{de::DeserializeOwned, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::app_error::{AppError, AppResult};
+
+/// Same store file the frontend uses for its preferences.
+pub const STORE_FILE: &str = "dataset-inspector.bin";
+
+pub fn read_setting<T: DeserializeOwned>(app: &AppHandle, key: &str) -> AppResult<Option<T>> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| AppError::Task(format!("settings store unavailable: {e}")))?;
+    let Some(value) = store.get(key) else {
+        return Ok(None);
+    };
+    serde_json::from_value(value)
+        .map(Some)
+        .map_err(|e| AppError::Invalid(format!("setting {key} is malformed: {e}")))
+}
+
+pub fn write_setting<T: Serialize>(app: &AppHandle, key: &str, value: &T) -> AppResult<()> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| AppError::Task(format!("settings store unavailable: {e}")))?;
+    let json = serde_json::to_value(value)
+        .map_err(|e| AppError::Invalid(format!("setting {key} is not serializable: {e}")))?;
+    store.set(key, json);
+    store
+        .save()
+        .map_err(|e| AppError::Task(format!("saving settings failed: {e}")))
+}
@@ -0,0 +1,166 @@
+use ssh2::{CheckResult, KnownHostFileKind, Session};
+use std::io::{Read, Seek, SeekFrom};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::messages::{self, Message};
+use crate::remote_fs::{RemoteEntry, RemoteFs};
+
+const DEFAULT_PORT: u16 = 22;
+const CONNECT_TIMEOUT_SECS: u64 = 15;
+const SESSION_TIMEOUT_MS: u32 = 60_000;
+/// Private keys tried in `~/.ssh` when the SSH agent has none that work.
+const KEY_FILES: &[&str] = &["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/// An SFTP server, authenticated with a password, the SSH agent or a key in `~/.ssh`. The host
+/// key has to be in `~/.ssh/known_hosts` already.
+pub struct Sftp {
+    sftp: ssh2::Sftp,
+    // Kept alive for as long as the SFTP channel is used.
+    _session: Session,
+}
+
+fn ssh_error(err: ssh2::Error) -> AppError {
+    AppError::Remote(messages::text(Message::SftpFailed, &[("error", &err)]))
+}
+
+fn ssh_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".ssh"))
+}
+
+fn check_host_key(session: &Session, host: &str, port: u16) -> AppResult<()> {
+    let mut known = session.known_hosts().map_err(ssh_error)?;
+    if let Some(file) = ssh_dir().map(|dir| dir.join("known_hosts")) {
+        if file.is_file() {
+            known
+                .read_file(&file, KnownHostFileKind::OpenSSH)
+                .map_err(ssh_error)?;
+        }
+    }
+    let (key, _) = session
+        .host_key()
+        .ok_or_else(|| AppError::Remote(messages::text(Message::NoHostKey, &[("host", &host)])))?;
+    match known.check_port(host, port, key) {
+        CheckResult::Match => Ok(()),
+        CheckResult::NotFound => Err(AppError::Remote(messages::text(
+            Message::HostKeyUnknown,
+            &[("host", &host)],
+        ))),
+        CheckResult::Mismatch => Err(AppError::Remote(messages::text(
+            Message::HostKeyMismatch,
+            &[("host", &host)],
+        ))),
+        CheckResult::Failure => Err(AppError::Remote(messages::text(
+            Message::HostKeyCheckFailed,
+            &[("host", &host)],
+        ))),
+    }
+}
+
+fn authenticate(session: &Session, user: &str, password: Option<&str>) -> AppResult<()> {
+    if let Some(password) = password {
+        session.userauth_password(user, password).map_err(|e| {
+            AppError::Remote(messages::text(
+                Message::PasswordLoginFailed,
+                &[("user", &user), ("error", &e)],
+            ))
+        })?;
+        return Ok(());
+    }
+    if session.userauth_agent(user).is_ok() && session.authenticated() {
+        return Ok(());
+    }
+    let keys = ssh_dir()
+        .into_iter()
+        .flat_map(|dir| KEY_FILES.iter().map(move |name| dir.join(name)))
+        .filter(|key| key.is_file());
+    for key in keys {
+        if session.userauth_pubkey_file(user, None, &key, None).is_ok() && session.authenticated() {
+            return Ok(());
+        }
+    }
+    Err(AppError::Remote(messages::text(
+        Message::KeyLoginFailed,
+        &[("user", &user)],
+    )))
+}
+
+impl Sftp {
+    /// `url` is `sftp://[user@]host[:port]/...`; the user defaults to the local one.
+    pub fn connect(url: &Url, password: Option<&str>) -> AppResult<Self> {
+        let host = url.host_str().ok_or_else(|| {
+            AppError::Invalid(messages::text(Message::UrlHasNoHost, &[("url", &url)]))
+        })?;
+        let port = url.port().unwrap_or(DEFAULT_PORT);
+        let user = match url.username() {
+            "" => std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .map_err(|_| {
+                    AppError::Invalid(messages::text(Message::UrlHasNoUser, &[("url", &url)]))
+                })?,
+            user => user.to_string(),
+        };
+        let addr = (host, port).to_socket_addrs()?.next().ok_or_else(|| {
+            AppError::Remote(messages::text(Message::CouldNotResolve, &[("host", &host)]))
+        })?;
+        let tcp = TcpStream::connect_timeout(&addr, Duration::from_secs(CONNECT_TIMEOUT_SECS))?;
+        let mut session = Session::new().map_err(ssh_error)?;
+        session.set_tcp_stream(tcp);
+        session.set_timeout(SESSION_TIMEOUT_MS);
+        session.handshake().map_err(ssh_error)?;
+        check_host_key(&session, host, port)?;
+        let password = password.or_else(|| url.password());
+        authenticate(&session, &user, password)?;
+        let sftp = session.sftp().map_err(ssh_error)?;
+        Ok(Self {
+            sftp,
+            _session: session,
+        })
+    }
+}
+
+impl RemoteFs for Sftp {
+    fn list(&self, path: &str) -> AppResult<Vec<RemoteEntry>> {
+        let entries = self.sftp.readdir(Path::new(path)).map_err(ssh_error)?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|(entry, stat)| {
+                let name = entry.file_name()?.to_string_lossy().into_owned();
+                let is_dir = stat.is_dir();
+                Some(RemoteEntry {
+                    name,
+                    path: entry.to_string_lossy().into_owned(),
+                    is_dir,
+                    size: (!is_dir).then_some(stat.size).flatten(),
+                    modified: stat.mtime,
+                })
+            })
+            .collect())
+    }
+
+    fn size(&self, path: &str) -> AppResult<u64> {
+        let stat = self.sftp.stat(Path::new(path)).map_err(ssh_error)?;
+        stat.size.ok_or_else(|| {
+            AppError::Remote(messages::text(Message::SizeNotReported, &[("path", &path)]))
+        })
+    }
+
+    fn read_range(&self, path: &str, start: u64, len: u64) -> AppResult<Vec<u8>> {
+        let mut file = self.sftp.open(Path::new(path)).map_err(ssh_error)?;
+        file.seek(SeekFrom::Start(start))?;
+        let mut out = Vec::new();
+        file.take(len).read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    fn open(&self, path: &str) -> AppResult<Box<dyn Read + '_>> {
+        Ok(Box::new(
+            self.sftp.open(Path::new(path)).map_err(ssh_error)?,
+        ))
+    }
+}
@@ -0,0 +1,142 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::ipc_types::IndexSummary;
+use crate::litdata;
+use crate::mosaicml;
+use crate::webdataset::{self, LocalDatasetDetectResponse};
+
+const DEFAULT_THRESHOLD_PCT: f64 = 50.0;
+
+/// Per-shard sizes as parallel arrays (index `i` is the same shard in each), to draw a
+/// histogram from without one object per shard.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShardBalance {
+    /// `litdata`, `mds` or `webdataset`.
+    kind: &'static str,
+    names: Vec<String>,
+    bytes: Vec<u64>,
+    /// Samples per shard, from the index. WebDataset shards have no index, so this is `None`.
+    samples: Option<Vec<u32>>,
+    median_bytes: f64,
+    median_samples: Option<f64>,
+    threshold_pct: f64,
+    /// Shards whose size is more than `threshold_pct` away from the median.
+    bytes_outliers: Vec<usize>,
+    /// Shards whose sample count is more than `threshold_pct` away from the median.
+    samples_outliers: Vec<usize>,
+    /// Shards listed in the index but not on disk.
+    missing: Vec<usize>,
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => (sorted[mid - 1] + sorted[mid]) / 2.0,
+        _ => sorted[mid],
+    }
+}
+
+/// The median of `values` and the indices of those more than `threshold_pct` away from it.
+fn outliers(values: &[f64], threshold_pct: f64) -> (f64, Vec<usize>) {
+    let median = median(values);
+    if median == 0.0 {
+        return (median, Vec::new());
+    }
+    let flagged = values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| ((*v - median).abs() / median) * 100.0 > threshold_pct)
+        .map(|(i, _)| i)
+        .collect();
+    (median, flagged)
+}
+
+fn missing(exists: impl Iterator<Item = bool>) -> Vec<usize> {
+    exists
+        .enumerate()
+        .filter(|(_, exists)| !exists)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn from_index(kind: &'static str, summary: IndexSummary, threshold_pct: f64) -> ShardBalance {
+    let chunks = summary.chunks;
+    let bytes: Vec<u64> = chunks.iter().map(|c| c.chunk_bytes).collect();
+    let samples: Vec<u32> = chunks.iter().map(|c| c.chunk_size).collect();
+    let (median_bytes, bytes_outliers) = outliers(
+        &bytes.iter().map(|&b| b as f64).collect::<Vec<_>>(),
+        threshold_pct,
+    );
+    let (median_samples, samples_outliers) = outliers(
+        &samples.iter().map(|&s| f64::from(s)).collect::<Vec<_>>(),
+        threshold_pct,
+    );
+    ShardBalance {
+        kind,
+        missing: missing(chunks.iter().map(|c| c.exists)),
+        names: chunks.into_iter().map(|c| c.filename).collect(),
+        bytes,
+        samples: Some(samples),
+        median_bytes,
+        median_samples: Some(median_samples),
+        threshold_pct,
+        bytes_outliers,
+        samples_outliers,
+    }
+}
+
+fn shard_balance_sync(path: PathBuf, threshold_pct: f64) -> AppResult<ShardBalance> {
+    match webdataset::detect_local_dataset_sync(path)? {
+        LocalDatasetDetectResponse::LitdataIndex { index_path } => {
+            let summary = litdata::load_index_sync(PathBuf::from(index_path))?;
+            Ok(from_index("litdata", summary, threshold_pct))
+        }
+        LocalDatasetDetectResponse::MdsIndex { index_path } => {
+            let summary = mosaicml::mosaicml_load_index_sync(PathBuf::from(index_path))?;
+            Ok(from_index("mds", summary, threshold_pct))
+        }
+        LocalDatasetDetectResponse::WebdatasetDir { dir_path } => {
+            let shards = webdataset::wds_load_dir_sync(PathBuf::from(dir_path))?.shards;
+            let bytes: Vec<u64> = shards.iter().map(|s| s.bytes).collect();
+            let (median_bytes, bytes_outliers) = outliers(
+                &bytes.iter().map(|&b| b as f64).collect::<Vec<_>>(),
+                threshold_pct,
+            );
+            Ok(ShardBalance {
+                kind: "webdataset",
+                missing: missing(shards.iter().map(|s| s.exists)),
+                names: shards.into_iter().map(|s| s.filename).collect(),
+                bytes,
+                samples: None,
+                median_bytes,
+                median_samples: None,
+                threshold_pct,
+                bytes_outliers,
+                samples_outliers: Vec::new(),
+            })
+        }
+    }
+}
+
+/// Sizes and sample counts of every chunk or shard of a LitData or MDS index (sizes only for a
+/// WebDataset directory), flagging those more than `threshold_pct` percent (default 50) away
+/// from the median.
+#[tauri::command]
+pub async fn shard_balance(path: String, threshold_pct: Option<f64>) -> AppResult<ShardBalance> {
+    let threshold_pct = threshold_pct.unwrap_or(DEFAULT_THRESHOLD_PCT);
+    if !threshold_pct.is_finite() || threshold_pct <= 0.0 {
+        return Err(AppError::Invalid(
+            "threshold must be a positive percentage".into(),
+        ));
+    }
+    spawn_blocking(move || shard_balance_sync(PathBuf::from(path.trim()), threshold_pct))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,192 @@
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::huggingface;
+use crate::litdata;
+use crate::mosaicml;
+use crate::sample_location::SampleLocation;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonSnippet {
+    code: String,
+    /// Package to `pip install` for the snippet to run.
+    package: &'static str,
+}
+
+/// JSON string literals are valid Python string literals, escapes included.
+fn py_str(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".into())
+}
+
+fn litdata_snippet(
+    index_path: &str,
+    chunk_filename: &str,
+    item_index: u32,
+    field_index: Option<usize>,
+) -> AppResult<String> {
+    let (root_dir, global, formats) =
+        litdata::streaming_position(Path::new(index_path), chunk_filename, item_index)?;
+    let mut code = String::new();
+    let _ = writeln!(code, "from litdata import StreamingDataset");
+    if field_index.is_some() {
+        let _ = writeln!(code, "from torch.utils._pytree import tree_flatten");
+    }
+    let _ = writeln!(
+        code,
+        "\nds = StreamingDataset({})",
+        py_str(&root_dir.display().to_string())
+    );
+    let _ = writeln!(
+        code,
+        "sample = ds[{global}]  # item {item_index} of {chunk_filename}"
+    );
+    if let Some(field) = field_index {
+        // `data_format` lists the leaves of the flattened sample in order.
+        let format = formats
+            .get(field)
+            .ok_or_else(|| AppError::Invalid("field index out of range".into()))?;
+        let _ = writeln!(code, "value = tree_flatten(sample)[0][{field}]  # {format}");
+    }
+    Ok(code)
+}
+
+fn mds_snippet(
+    index_path: &str,
+    shard_filename: &str,
+    item_index: u32,
+    field_index: Option<usize>,
+) -> AppResult<String> {
+    let (root_dir, global, columns) =
+        mosaicml::streaming_position(Path::new(index_path), shard_filename, item_index)?;
+    let mut code = String::new();
+    let _ = writeln!(code, "from streaming import StreamingDataset\n");
+    let _ = writeln!(
+        code,
+        "ds = StreamingDataset(local={}, shuffle=False)",
+        py_str(&root_dir.display().to_string())
+    );
+    let _ = writeln!(
+        code,
+        "sample = ds[{global}]  # sample {item_index} of {shard_filename}"
+    );
+    if let Some(field) = field_index {
+        let name = columns
+            .get(field)
+            .ok_or_else(|| AppError::Invalid("field index out of range".into()))?;
+        let _ = writeln!(code, "value = sample[{}]", py_str(name));
+    }
+    Ok(code)
+}
+
+fn webdataset_snippet(
+    dir_path: &str,
+    shard_filename: &str,
+    sample_key: &str,
+    field: Option<&str>,
+) -> String {
+    let shard = PathBuf::from(dir_path).join(shard_filename.trim());
+    let mut code = String::new();
+    let _ = writeln!(code, "import webdataset as wds\n");
+    let _ = writeln!(
+        code,
+        "ds = wds.WebDataset({}, shardshuffle=False).select(",
+        py_str(&shard.display().to_string())
+    );
+    let _ = writeln!(
+        code,
+        "    lambda s: s[\"__key__\"] == {}\n)",
+        py_str(sample_key.trim())
+    );
+    let _ = writeln!(code, "sample = next(iter(ds))");
+    if let Some(field) = field.map(str::trim).filter(|f| !f.is_empty()) {
+        let _ = writeln!(code, "value = sample[{}]  # raw bytes", py_str(field));
+    }
+    code
+}
+
+fn huggingface_snippet(
+    dataset: &str,
+    config: Option<&str>,
+    split: &str,
+    row_index: usize,
+    field_name: Option<&str>,
+) -> AppResult<String> {
+    let mut args = py_str(&huggingface::extract_repo_id(dataset)?);
+    if let Some(config) = config.map(str::trim).filter(|c| !c.is_empty()) {
+        let _ = write!(args, ", {}", py_str(config));
+    }
+    let slice = format!("{}[{row_index}:{}]", split.trim(), row_index + 1);
+    let mut code = String::new();
+    let _ = writeln!(code, "from datasets import load_dataset\n");
+    let _ = writeln!(code, "ds = load_dataset({args}, split={})", py_str(&slice));
+    let _ = writeln!(
+        code,
+        "sample = ds[0]  # row {row_index} of {}",
+        split.trim()
+    );
+    if let Some(field) = field_name.map(str::trim).filter(|f| !f.is_empty()) {
+        let _ = writeln!(code, "value = sample[{}]", py_str(field));
+    }
+    Ok(code)
+}
+
+fn python_snippet_sync(location: SampleLocation) -> AppResult<PythonSnippet> {
+    match location {
+        SampleLocation::Litdata {
+            index_path,
+            chunk_filename,
+            item_index,
+            field_index,
+        } => Ok(PythonSnippet {
+            code: litdata_snippet(&index_path, &chunk_filename, item_index, field_index)?,
+            package: "litdata",
+        }),
+        SampleLocation::Mds {
+            index_path,
+            shard_filename,
+            item_index,
+            field_index,
+        } => Ok(PythonSnippet {
+            code: mds_snippet(&index_path, &shard_filename, item_index, field_index)?,
+            package: "mosaicml-streaming",
+        }),
+        SampleLocation::Webdataset {
+            dir_path,
+            shard_filename,
+            sample_key,
+            field,
+        } => Ok(PythonSnippet {
+            code: webdataset_snippet(&dir_path, &shard_filename, &sample_key, field.as_deref()),
+            package: "webdataset",
+        }),
+        SampleLocation::Huggingface {
+            dataset,
+            config,
+            split,
+            row_index,
+            field_name,
+        } => Ok(PythonSnippet {
+            code: huggingface_snippet(
+                &dataset,
+                config.as_deref(),
+                &split,
+                row_index,
+                field_name.as_deref(),
+            )?,
+            package: "datasets",
+        }),
+    }
+}
+
+/// Python code that loads the given sample with the format's own library, for moving findings
+/// into training code.
+#[tauri::command]
+pub async fn python_snippet(location: SampleLocation) -> AppResult<PythonSnippet> {
+    spawn_blocking(move || python_snippet_sync(location))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,246 @@
+use datafusion::arrow::json::writer::JsonArray;
+use datafusion::arrow::json::WriterBuilder;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::DataFusionError;
+use datafusion::prelude::{CsvReadOptions, NdJsonReadOptions, ParquetReadOptions, SessionContext};
+use datafusion::sql::planner::SQLOptions;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+use crate::app_error::{AppError, AppResult};
+
+const QUERY_PAGE_DEFAULT_ROWS: usize = 50;
+const QUERY_PAGE_MAX_ROWS: usize = 1000;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuerySource {
+    /// A Parquet, CSV, TSV or JSON Lines file, or a folder of them.
+    path: String,
+    /// Table name in the SQL; defaults to the file or folder name.
+    name: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryColumn {
+    name: String,
+    /// Arrow type, e.g. `Int64`, `Utf8`, `Timestamp(Microsecond, None)`.
+    data_type: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResult {
+    /// Tables the query could refer to, as registered.
+    tables: Vec<String>,
+    columns: Vec<QueryColumn>,
+    /// Result rows `offset..offset + rows.len()`, one object per row.
+    rows: Vec<Value>,
+    offset: usize,
+    /// Whether the result has no rows past these.
+    done: bool,
+}
+
+#[derive(Clone, Copy)]
+enum SourceFormat {
+    Parquet,
+    Csv,
+    Tsv,
+    Jsonl,
+}
+
+impl SourceFormat {
+    fn from_name(name: &str) -> Option<(Self, &'static str)> {
+        let name = name.to_ascii_lowercase();
+        let ext = name.rsplit_once('.')?.1;
+        Some(match ext {
+            "parquet" => (Self::Parquet, ".parquet"),
+            "pq" => (Self::Parquet, ".pq"),
+            "csv" => (Self::Csv, ".csv"),
+            "tsv" => (Self::Tsv, ".tsv"),
+            "jsonl" => (Self::Jsonl, ".jsonl"),
+            "ndjson" => (Self::Jsonl, ".ndjson"),
+            _ => return None,
+        })
+    }
+}
+
+/// The format of a file, or of the first recognized file in a folder, with the extension
+/// every file read for the table must have.
+fn source_format(path: &Path) -> AppResult<(SourceFormat, &'static str)> {
+    if !path.is_dir() {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        return SourceFormat::from_name(&name).ok_or_else(|| {
+            AppError::Invalid(format!(
+                "{name} is not a .parquet, .csv, .tsv or .jsonl file"
+            ))
+        });
+    }
+    let mut names = std::fs::read_dir(path)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| !name.starts_with('.') && !name.starts_with('_'))
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+        .iter()
+        .find_map(|name| SourceFormat::from_name(name))
+        .ok_or_else(|| {
+            AppError::Missing(format!(
+                "no .parquet, .csv, .tsv or .jsonl files in {}",
+                path.display()
+            ))
+        })
+}
+
+/// A lowercase SQL identifier from a file name: `Train-00001.parquet` becomes `train_00001`.
+fn table_name(path: &Path) -> String {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut name = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name.insert(0, 't');
+    }
+    name
+}
+
+fn query_error(err: DataFusionError) -> AppError {
+    AppError::Invalid(format!("query failed: {err}"))
+}
+
+async fn register(ctx: &SessionContext, source: &QuerySource) -> AppResult<String> {
+    let path = Path::new(source.path.trim());
+    if !path.exists() {
+        return Err(AppError::Missing(format!(
+            "{} does not exist",
+            path.display()
+        )));
+    }
+    let (format, ext) = source_format(path)?;
+    let name = match source.name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => table_name(path),
+    };
+    let location = path.to_string_lossy();
+    let registered = match format {
+        SourceFormat::Parquet => {
+            let options = ParquetReadOptions {
+                file_extension: ext,
+                ..Default::default()
+            };
+            ctx.register_parquet(&name, &location, options).await
+        }
+        SourceFormat::Csv | SourceFormat::Tsv => {
+            let delimiter = if matches!(format, SourceFormat::Tsv) {
+                b'\t'
+            } else {
+                b','
+            };
+            let options = CsvReadOptions::new()
+                .delimiter(delimiter)
+                .file_extension(ext);
+            ctx.register_csv(&name, &location, options).await
+        }
+        SourceFormat::Jsonl => {
+            let options = NdJsonReadOptions::default().file_extension(ext);
+            ctx.register_json(&name, &location, options).await
+        }
+    };
+    registered.map_err(query_error)?;
+    Ok(name)
+}
+
+/// Record batches as one JSON object per row, with nulls kept.
+pub fn batches_json(batches: &[RecordBatch]) -> AppResult<Vec<Value>> {
+    let mut writer = WriterBuilder::new()
+        .with_explicit_nulls(true)
+        .build::<_, JsonArray>(Vec::new());
+    for batch in batches {
+        writer.write(batch).map_err(|e| query_error(e.into()))?;
+    }
+    writer.finish().map_err(|e| query_error(e.into()))?;
+    let json = writer.into_inner();
+    if json.is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_slice(&json)
+        .map_err(|e| AppError::Invalid(format!("query result is not valid JSON: {e}")))
+}
+
+/// Runs a read-only SQL query over local Parquet, CSV, TSV and JSON Lines sources, each
+/// registered as a table, and returns one page of the result. Files are scanned in place, so
+/// the query runs again for every page.
+#[tauri::command]
+pub async fn run_query(
+    sql: String,
+    sources: Vec<QuerySource>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> AppResult<QueryResult> {
+    let sql = sql.trim();
+    if sql.is_empty() {
+        return Err(AppError::Invalid("query is empty".into()));
+    }
+    let offset = offset.unwrap_or(0);
+    let limit = limit
+        .unwrap_or(QUERY_PAGE_DEFAULT_ROWS)
+        .clamp(1, QUERY_PAGE_MAX_ROWS);
+
+    let ctx = SessionContext::new();
+    let mut tables = Vec::with_capacity(sources.len());
+    for source in &sources {
+        let name = register(&ctx, source).await?;
+        if tables.contains(&name) {
+            return Err(AppError::Invalid(format!("two sources are named {name}")));
+        }
+        tables.push(name);
+    }
+
+    // No DDL, DML or `SET`: the console reads the sources and never writes files.
+    let options = SQLOptions::new()
+        .with_allow_ddl(false)
+        .with_allow_dml(false)
+        .with_allow_statements(false);
+    let frame = ctx
+        .sql_with_options(sql, options)
+        .await
+        .map_err(query_error)?;
+    let columns = frame
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| QueryColumn {
+            name: field.name().clone(),
+            data_type: field.data_type().to_string(),
+        })
+        .collect();
+    // One row past the page says whether there is another.
+    let batches = frame
+        .limit(offset, Some(limit + 1))
+        .map_err(query_error)?
+        .collect()
+        .await
+        .map_err(query_error)?;
+
+    let mut rows = batches_json(&batches)?;
+    let done = rows.len() <= limit;
+    rows.truncate(limit);
+
+    Ok(QueryResult {
+        tables,
+        columns,
+        rows,
+        offset,
+        done,
+    })
+}
@@ -0,0 +1,44 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// Runs `task` on the current thread. When it panics, `reset` drops the cached state it may have
+/// left half-updated and the panic comes back as an `Interrupted` error, so running the task
+/// again starts from a clean scan. `what` names the task in the error.
+pub fn run_caught<T>(
+    what: &str,
+    reset: impl FnOnce(),
+    task: impl FnOnce() -> AppResult<T>,
+) -> AppResult<T> {
+    match panic::catch_unwind(AssertUnwindSafe(task)) {
+        Ok(result) => result,
+        Err(payload) => {
+            reset();
+            Err(AppError::Interrupted(format!(
+                "{what} crashed ({}); its cached scan was reset, run it again",
+                panic_message(payload.as_ref())
+            )))
+        }
+    }
+}
+
+/// `run_caught` on the blocking pool, for long scans started by a command.
+pub async fn run_blocking<T: Send + 'static>(
+    what: &'static str,
+    reset: impl FnOnce() + Send + 'static,
+    task: impl FnOnce() -> AppResult<T> + Send + 'static,
+) -> AppResult<T> {
+    spawn_blocking(move || run_caught(what, reset, task))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
@@ -0,0 +1,44 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app_error::{AppError, AppResult};
+
+/// Links followed before a chain counts as a loop, as with `MAXSYMLINKS` on Linux.
+const MAX_HOPS: usize = 40;
+
+/// `\\?\C:\data` as `C:\data`; junction targets come back in the verbatim form.
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    if !cfg!(windows) {
+        return path;
+    }
+    match path.to_str().and_then(|p| p.strip_prefix(r"\\?\")) {
+        Some(rest) if !rest.starts_with("UNC\\") => PathBuf::from(rest),
+        _ => path,
+    }
+}
+
+/// Where `path` ends up after following symlinks (and, on Windows, junctions) one link at a
+/// time, or `None` when it is not a link. The target need not exist. A chain that comes back to
+/// a link it already went through is reported rather than followed.
+pub fn resolve_link(path: &Path) -> AppResult<Option<PathBuf>> {
+    let mut current = path.to_path_buf();
+    let mut seen = HashSet::new();
+    loop {
+        let is_link = fs::symlink_metadata(&current).is_ok_and(|m| m.file_type().is_symlink());
+        if !is_link {
+            return Ok((current != path).then_some(current));
+        }
+        if !seen.insert(current.clone()) || seen.len() > MAX_HOPS {
+            return Err(AppError::Invalid(format!(
+                "{} is a symlink loop",
+                path.display()
+            )));
+        }
+        let target = strip_verbatim(fs::read_link(&current)?);
+        current = match current.parent() {
+            Some(parent) if target.is_relative() => parent.join(target),
+            _ => target,
+        };
+    }
+}
@@ -0,0 +1,288 @@
+use serde::Serialize;
+
+use crate::app_error::{AppError, AppResult};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabularColumn {
+    name: String,
+    /// ARFF attribute type (`numeric`, `nominal`, `string`, `date`, ...), or for CSV the type
+    /// inferred from the previewed values (`integer`, `float`, `string`).
+    dtype: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabularPreview {
+    /// `csv`, `tsv` or `arff`.
+    format: &'static str,
+    /// The ARFF `@relation` name.
+    relation: Option<String>,
+    columns: Vec<TabularColumn>,
+    /// Cell values as written in the file; `None` for missing values (`?` in ARFF, empty in CSV).
+    rows: Vec<Vec<Option<String>>>,
+    /// Whether the file has rows past the previewed ones.
+    truncated: bool,
+}
+
+/// Parses the first `max_rows` rows of a CSV, TSV or ARFF file from `data`, its leading bytes.
+/// `complete` says whether `data` is the whole file; if not, the last (possibly cut) line is
+/// dropped.
+pub fn preview(
+    name: &str,
+    data: &[u8],
+    complete: bool,
+    max_rows: usize,
+) -> AppResult<TabularPreview> {
+    if data.starts_with(b"PK\x03\x04") || data.starts_with(&[0x1F, 0x8B]) {
+        return Err(AppError::Invalid(format!(
+            "{name} is an archive; download it to preview its tables."
+        )));
+    }
+    let mut text = String::from_utf8_lossy(data).into_owned();
+    if !complete {
+        let end = text.rfind('\n').map_or(0, |idx| idx + 1);
+        text.truncate(end);
+    }
+    if is_arff(name, &text) {
+        Ok(arff_preview(&text, complete, max_rows))
+    } else {
+        Ok(delimited_preview(name, &text, complete, max_rows))
+    }
+}
+
+fn is_arff(name: &str, text: &str) -> bool {
+    if name.to_ascii_lowercase().ends_with(".arff") {
+        return true;
+    }
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('%'))
+        .is_some_and(|line| line.to_ascii_lowercase().starts_with("@relation"))
+}
+
+fn delimited_preview(name: &str, text: &str, complete: bool, max_rows: usize) -> TabularPreview {
+    let first_line = text.lines().next().unwrap_or_default();
+    let delim = if name.to_ascii_lowercase().ends_with(".tsv") {
+        '\t'
+    } else {
+        [',', '\t', ';', '|']
+            .into_iter()
+            .max_by_key(|d| first_line.matches(*d).count())
+            .filter(|d| first_line.contains(*d))
+            .unwrap_or(',')
+    };
+    let mut records = parse_delimited(text, delim).into_iter();
+    let header = records.next().unwrap_or_default();
+    let mut rows = records
+        .by_ref()
+        .take(max_rows)
+        .map(|record| {
+            record
+                .into_iter()
+                .map(|value| (!value.is_empty()).then_some(value))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let truncated = records.next().is_some() || !complete;
+    let width = rows
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+        .max(header.len());
+    for row in &mut rows {
+        row.resize(width, None);
+    }
+    let columns = (0..width)
+        .map(|idx| TabularColumn {
+            name: header
+                .get(idx)
+                .cloned()
+                .unwrap_or_else(|| format!("column_{idx}")),
+            dtype: infer_dtype(rows.iter().filter_map(|row| row[idx].as_deref())).into(),
+        })
+        .collect();
+    TabularPreview {
+        format: if delim == '\t' { "tsv" } else { "csv" },
+        relation: None,
+        columns,
+        rows,
+        truncated,
+    }
+}
+
+/// Splits delimited text into records, honouring double-quoted fields (with `""` escapes and
+/// embedded newlines) and skipping blank lines.
+fn parse_delimited(text: &str, delim: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                field.push('"');
+                chars.next();
+            } else {
+                in_quotes = false;
+            }
+        } else if c == '"' && field.is_empty() {
+            in_quotes = true;
+        } else if c == delim {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' {
+            record.push(std::mem::take(&mut field));
+            records.push(std::mem::take(&mut record));
+        } else if c != '\r' {
+            field.push(c);
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|r| !(r.len() == 1 && r[0].trim().is_empty()));
+    records
+}
+
+fn infer_dtype<'a>(values: impl Iterator<Item = &'a str>) -> &'static str {
+    let mut dtype = None;
+    for value in values.map(str::trim) {
+        let this = if value.parse::<i64>().is_ok() {
+            "integer"
+        } else if value.parse::<f64>().is_ok() {
+            "float"
+        } else {
+            return "string";
+        };
+        if dtype != Some("float") {
+            dtype = Some(this);
+        }
+    }
+    dtype.unwrap_or("string")
+}
+
+fn arff_preview(text: &str, complete: bool, max_rows: usize) -> TabularPreview {
+    let mut relation = None;
+    let mut columns = Vec::new();
+    let mut lines = text.lines().map(str::trim);
+    for line in lines.by_ref() {
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match keyword.to_ascii_lowercase().as_str() {
+            "@relation" => relation = Some(unquote(rest.trim()).0),
+            "@attribute" => {
+                let (name, ty) = unquote(rest.trim());
+                columns.push(TabularColumn {
+                    name,
+                    dtype: arff_dtype(ty.trim()),
+                });
+            }
+            "@data" => break,
+            _ => {}
+        }
+    }
+
+    let mut data = lines.filter(|line| !line.is_empty() && !line.starts_with('%'));
+    let mut rows = data
+        .by_ref()
+        .take(max_rows)
+        .map(|line| arff_row(line, columns.len()))
+        .collect::<Vec<_>>();
+    let truncated = data.next().is_some() || !complete;
+    for row in &mut rows {
+        row.resize(columns.len(), None);
+    }
+    TabularPreview {
+        format: "arff",
+        relation,
+        columns,
+        rows,
+        truncated,
+    }
+}
+
+/// The attribute type's name; nominal types (`{a,b,c}`) are reported as `nominal`.
+fn arff_dtype(ty: &str) -> String {
+    if ty.starts_with('{') {
+        return "nominal".into();
+    }
+    ty.split_whitespace()
+        .next()
+        .unwrap_or("unknown")
+        .to_ascii_lowercase()
+}
+
+/// Splits a leading (optionally quoted) token off `text`, returning it and the remainder.
+fn unquote(text: &str) -> (String, &str) {
+    let Some(quote) = text.chars().next().filter(|c| *c == '\'' || *c == '"') else {
+        let (token, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        return (token.to_string(), rest);
+    };
+    let mut token = String::new();
+    let mut chars = text.char_indices().skip(1);
+    while let Some((idx, c)) = chars.next() {
+        if c == '\\' {
+            if let Some((_, escaped)) = chars.next() {
+                token.push(escaped);
+            }
+        } else if c == quote {
+            return (token, &text[idx + 1..]);
+        } else {
+            token.push(c);
+        }
+    }
+    (token, "")
+}
+
+/// One ARFF data line, dense (`1,'a b',?`) or sparse (`{0 1, 3 'x'}`, where absent values are 0).
+fn arff_row(line: &str, width: usize) -> Vec<Option<String>> {
+    if let Some(sparse) = line.strip_prefix('{') {
+        let mut row = vec![Some("0".to_string()); width];
+        for entry in arff_values(sparse.trim_end_matches('}')) {
+            let Some(entry) = entry else { continue };
+            let (idx, value) = entry.split_once(' ').unwrap_or((entry.as_str(), ""));
+            if let Some(cell) = idx.parse::<usize>().ok().and_then(|idx| row.get_mut(idx)) {
+                let value = unquote(value.trim()).0;
+                *cell = (value != "?").then_some(value);
+            }
+        }
+        return row;
+    }
+    arff_values(line)
+}
+
+/// Comma-separated ARFF values, unquoted, with `?` as missing.
+fn arff_values(line: &str) -> Vec<Option<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quote = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) if c == '\\' => field.extend(chars.next()),
+            Some(_) => field.push(c),
+            None if (c == '\'' || c == '"') && field.trim().is_empty() => {
+                field.clear();
+                quote = Some(c);
+            }
+            None if c == ',' => fields.push(std::mem::take(&mut field)),
+            None => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+        .into_iter()
+        .map(|value| {
+            let value = value.trim().to_string();
+            (value != "?").then_some(value)
+        })
+        .collect()
+}
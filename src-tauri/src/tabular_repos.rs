@@ -0,0 +1,428 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, State};
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::downloads;
+use crate::http_cache;
+use crate::read_only;
+use crate::tabular::{self, TabularPreview};
+
+const OPENML_API_BASE: &str = "https://www.openml.org/api/v1/json/";
+const UCI_API_BASE: &str = "https://archive.ics.uci.edu/api/";
+const USER_AGENT: &str = "dataset-inspector/0.6.0 (tauri)";
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Leading bytes of a data file fetched for the table preview.
+const PREVIEW_BYTES: u64 = 256 * 1024;
+const DEFAULT_ROWS: usize = 25;
+const MAX_ROWS: usize = 100;
+
+/// HTTP clients for the OpenML and UCI ML repository APIs.
+pub struct TabularRepoClient {
+    http: reqwest::Client,
+    /// No overall timeout: full-file downloads can take much longer than API calls.
+    download_http: reqwest::Client,
+}
+
+impl Default for TabularRepoClient {
+    fn default() -> Self {
+        let http = reqwest::Client::builder()
+            .http1_only()
+            .user_agent(USER_AGENT)
+            .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        let download_http = reqwest::Client::builder()
+            .http1_only()
+            .user_agent(USER_AGENT)
+            .connect_timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            http,
+            download_http,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenmlDataResponse {
+    data_set_description: OpenmlDescription,
+}
+
+/// OpenML sends every scalar in the description as a string.
+#[derive(Deserialize)]
+struct OpenmlDescription {
+    id: String,
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    format: Option<String>,
+    licence: Option<String>,
+    default_target_attribute: Option<String>,
+    url: String,
+    parquet_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UciDataResponse {
+    status: u16,
+    message: Option<String>,
+    data: Option<UciDataset>,
+}
+
+#[derive(Deserialize)]
+struct UciDataset {
+    uci_id: u64,
+    name: String,
+    #[serde(rename = "abstract")]
+    summary: Option<String>,
+    repository_url: Option<String>,
+    data_url: Option<String>,
+    num_instances: Option<u64>,
+    num_features: Option<u64>,
+    target_col: Option<Vec<String>>,
+    #[serde(default)]
+    variables: Vec<UciVariable>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UciVariable {
+    name: String,
+    role: Option<String>,
+    #[serde(rename(deserialize = "type"))]
+    kind: Option<String>,
+    description: Option<String>,
+}
+
+/// A downloadable data file of a repository dataset.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoDataFile {
+    /// `arff`, `parquet` or `csv`.
+    format: String,
+    file_name: String,
+    url: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoDatasetSummary {
+    /// `openml` or `uci`.
+    source: &'static str,
+    id: u64,
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    license: Option<String>,
+    /// Target column(s) declared by the repository.
+    target: Vec<String>,
+    page_url: String,
+    num_instances: Option<u64>,
+    num_features: Option<u64>,
+    /// UCI variable descriptions; empty for OpenML, whose ARFF header carries the types.
+    variables: Vec<UciVariable>,
+    files: Vec<RepoDataFile>,
+    /// First rows of the primary data file; `None` when the repository offers no data file.
+    preview: Option<TabularPreview>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoDownloadReport {
+    path: String,
+    resumed: bool,
+}
+
+/// Accepts a bare id, `<prefix>:<id>`, or a dataset page URL whose path or `id` query holds it.
+fn extract_id(input: &str, prefix: &str, host: &str) -> AppResult<u64> {
+    let trimmed = input.trim();
+    let bare = trimmed
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .unwrap_or(trimmed);
+    if let Ok(id) = bare.trim().parse::<u64>() {
+        return Ok(id);
+    }
+    if let Ok(url) = Url::parse(trimmed) {
+        if url
+            .host_str()
+            .is_some_and(|h| h == host || h.ends_with(&format!(".{host}")))
+        {
+            let from_query = url
+                .query_pairs()
+                .find(|(key, _)| key == "id")
+                .and_then(|(_, value)| value.parse().ok());
+            let from_path = url
+                .path_segments()
+                .and_then(|mut segments| segments.find_map(|s| s.parse().ok()));
+            if let Some(id) = from_query.or(from_path) {
+                return Ok(id);
+            }
+        }
+    }
+    Err(AppError::Invalid(format!(
+        "Unsupported input. Provide a dataset id, {prefix}:<id>, or a {host} dataset URL."
+    )))
+}
+
+fn file_name_of(url: &Url) -> String {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("data")
+        .to_string()
+}
+
+fn parse_https(raw: &str) -> Option<Url> {
+    Url::parse(raw).ok().filter(|url| url.scheme() == "https")
+}
+
+/// Fetches the first `PREVIEW_BYTES` of `url` and parses them as a table.
+async fn preview_file(http: &reqwest::Client, url: &Url, rows: usize) -> AppResult<TabularPreview> {
+    let range = format!("bytes=0-{}", PREVIEW_BYTES - 1);
+    let res = http_cache::get(http, url, Some(&range), None).await?;
+    if !res.status.is_success() {
+        return Err(AppError::Remote(format!("HTTP {} from {url}", res.status)));
+    }
+    let total = res
+        .content_range
+        .as_deref()
+        .and_then(|v| v.rsplit('/').next()?.parse::<u64>().ok());
+    let mut data = res.body;
+    let complete = match total {
+        Some(total) => data.len() as u64 >= total,
+        None => data.len() as u64 <= PREVIEW_BYTES,
+    };
+    data.truncate(PREVIEW_BYTES as usize);
+    tabular::preview(&file_name_of(url), &data, complete, rows)
+}
+
+async fn get_api_json<T: serde::de::DeserializeOwned>(
+    http: &reqwest::Client,
+    url: Url,
+) -> AppResult<T> {
+    let res = http_cache::get(http, &url, None, None).await?;
+    let value: serde_json::Value = serde_json::from_slice(&res.body)
+        .map_err(|e| AppError::Remote(format!("invalid JSON from {url}: {e}")))?;
+    // OpenML: `{"error": {"code": "111", "message": "Unknown dataset"}}`.
+    if let Some(message) = value.pointer("/error/message").and_then(|v| v.as_str()) {
+        return Err(AppError::Invalid(format!("{message}.")));
+    }
+    if !res.status.is_success() {
+        return Err(AppError::Remote(format!("HTTP {} from {url}", res.status)));
+    }
+    serde_json::from_value(value).map_err(|e| AppError::Remote(format!("parse failed: {e}")))
+}
+
+async fn openml_description(http: &reqwest::Client, id: u64) -> AppResult<OpenmlDescription> {
+    let url = Url::parse(OPENML_API_BASE)
+        .and_then(|base| base.join(&format!("data/{id}")))
+        .map_err(|e| AppError::Remote(format!("invalid OpenML API url: {e}")))?;
+    let resp: OpenmlDataResponse = get_api_json(http, url).await?;
+    Ok(resp.data_set_description)
+}
+
+fn openml_files(desc: &OpenmlDescription) -> Vec<RepoDataFile> {
+    let format = desc
+        .format
+        .as_deref()
+        .unwrap_or("arff")
+        .to_ascii_lowercase();
+    [
+        Some((format, desc.url.as_str())),
+        desc.parquet_url
+            .as_deref()
+            .map(|u| ("parquet".to_string(), u)),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|(format, raw)| {
+        let url = parse_https(raw)?;
+        Some(RepoDataFile {
+            format,
+            file_name: file_name_of(&url),
+            url: url.to_string(),
+        })
+    })
+    .collect()
+}
+
+/// Looks up an OpenML dataset by id and previews the first rows of its ARFF file.
+#[tauri::command]
+pub async fn openml_dataset(
+    client: State<'_, TabularRepoClient>,
+    input: String,
+    rows: Option<usize>,
+) -> AppResult<RepoDatasetSummary> {
+    let id = extract_id(&input, "openml", "openml.org")?;
+    let rows = rows.unwrap_or(DEFAULT_ROWS).clamp(1, MAX_ROWS);
+    let desc = openml_description(&client.http, id).await?;
+    let files = openml_files(&desc);
+    let preview = match files.iter().find(|f| f.format != "parquet") {
+        Some(file) => {
+            let url = Url::parse(&file.url)
+                .map_err(|e| AppError::Remote(format!("invalid data url: {e}")))?;
+            Some(preview_file(&client.http, &url, rows).await?)
+        }
+        None => None,
+    };
+    Ok(RepoDatasetSummary {
+        source: "openml",
+        id: desc.id.parse().unwrap_or(id),
+        name: desc.name,
+        version: desc.version,
+        description: desc.description,
+        license: desc.licence,
+        target: desc
+            .default_target_attribute
+            .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default(),
+        page_url: format!("https://www.openml.org/search?type=data&id={id}"),
+        num_instances: None,
+        num_features: None,
+        variables: Vec::new(),
+        files,
+        preview,
+    })
+}
+
+async fn uci_dataset_info(http: &reqwest::Client, id: u64) -> AppResult<UciDataset> {
+    let mut url = Url::parse(UCI_API_BASE)
+        .and_then(|base| base.join("dataset"))
+        .map_err(|e| AppError::Remote(format!("invalid UCI API url: {e}")))?;
+    url.query_pairs_mut().append_pair("id", &id.to_string());
+    let resp: UciDataResponse = get_api_json(http, url).await?;
+    match resp.data {
+        Some(data) if resp.status == 200 => Ok(data),
+        _ => Err(AppError::Missing(format!(
+            "UCI dataset {id}: {}",
+            resp.message
+                .unwrap_or_else(|| format!("status {}", resp.status))
+        ))),
+    }
+}
+
+fn uci_files(info: &UciDataset) -> Vec<RepoDataFile> {
+    info.data_url
+        .as_deref()
+        .and_then(parse_https)
+        .map(|url| {
+            let file_name = file_name_of(&url);
+            let format = file_name
+                .rsplit_once('.')
+                .map_or("csv", |(_, ext)| ext)
+                .to_ascii_lowercase();
+            RepoDataFile {
+                format,
+                file_name,
+                url: url.to_string(),
+            }
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Looks up a UCI ML repository dataset by id and previews the first rows of its data file.
+#[tauri::command]
+pub async fn uci_dataset(
+    client: State<'_, TabularRepoClient>,
+    input: String,
+    rows: Option<usize>,
+) -> AppResult<RepoDatasetSummary> {
+    let id = extract_id(&input, "uci", "archive.ics.uci.edu")?;
+    let rows = rows.unwrap_or(DEFAULT_ROWS).clamp(1, MAX_ROWS);
+    let info = uci_dataset_info(&client.http, id).await?;
+    let files = uci_files(&info);
+    let preview = match files.first() {
+        Some(file) => {
+            let url = Url::parse(&file.url)
+                .map_err(|e| AppError::Remote(format!("invalid data url: {e}")))?;
+            Some(preview_file(&client.http, &url, rows).await?)
+        }
+        None => None,
+    };
+    Ok(RepoDatasetSummary {
+        source: "uci",
+        id: info.uci_id,
+        page_url: info
+            .repository_url
+            .unwrap_or_else(|| format!("https://archive.ics.uci.edu/dataset/{id}")),
+        name: info.name,
+        version: None,
+        description: info.summary,
+        license: None,
+        target: info.target_col.unwrap_or_default(),
+        num_instances: info.num_instances,
+        num_features: info.num_features,
+        variables: info.variables,
+        files,
+        preview,
+    })
+}
+
+async fn download_repo_file(
+    app: &AppHandle,
+    client: &TabularRepoClient,
+    job_id: &str,
+    file: &RepoDataFile,
+    dest_dir: &str,
+) -> AppResult<RepoDownloadReport> {
+    let dest_dir = PathBuf::from(dest_dir.trim());
+    if dest_dir.as_os_str().is_empty() {
+        return Err(AppError::Invalid("Choose a destination folder.".into()));
+    }
+    read_only::ensure_writable(&dest_dir, "repository file download")?;
+    std::fs::create_dir_all(&dest_dir)?;
+    let url =
+        Url::parse(&file.url).map_err(|e| AppError::Remote(format!("invalid data url: {e}")))?;
+    let dest = dest_dir.join(&file.file_name);
+    let resumed =
+        downloads::download_to_file(&client.download_http, app, job_id, url, &dest, None).await?;
+    Ok(RepoDownloadReport {
+        path: dest.display().to_string(),
+        resumed,
+    })
+}
+
+/// Downloads an OpenML dataset's data file (`arff` by default, or `parquet`) into `dest_dir`.
+#[tauri::command]
+pub async fn openml_download(
+    app: AppHandle,
+    client: State<'_, TabularRepoClient>,
+    input: String,
+    format: Option<String>,
+    dest_dir: String,
+) -> AppResult<RepoDownloadReport> {
+    let id = extract_id(&input, "openml", "openml.org")?;
+    let desc = openml_description(&client.http, id).await?;
+    let files = openml_files(&desc);
+    let wanted = format
+        .map(|f| f.trim().to_ascii_lowercase())
+        .filter(|f| !f.is_empty());
+    let file = match wanted {
+        Some(wanted) => files.iter().find(|f| f.format == wanted),
+        None => files.first(),
+    }
+    .ok_or_else(|| AppError::Missing(format!("OpenML dataset {id} has no such data file.")))?;
+    download_repo_file(&app, &client, &format!("openml-{id}"), file, &dest_dir).await
+}
+
+/// Downloads a UCI ML repository dataset's data file into `dest_dir`.
+#[tauri::command]
+pub async fn uci_download(
+    app: AppHandle,
+    client: State<'_, TabularRepoClient>,
+    input: String,
+    dest_dir: String,
+) -> AppResult<RepoDownloadReport> {
+    let id = extract_id(&input, "uci", "archive.ics.uci.edu")?;
+    let info = uci_dataset_info(&client.http, id).await?;
+    let files = uci_files(&info);
+    let file = files
+        .first()
+        .ok_or_else(|| AppError::Missing(format!("UCI dataset {id} has no data file.")))?;
+    download_repo_file(&app, &client, &format!("uci-{id}"), file, &dest_dir).await
+}
@@ -0,0 +1,63 @@
+use hex::encode as hex_encode;
+use serde::Serialize;
+
+/// Window size when the caller does not ask for one, and the most a tail peek will read.
+const TAIL_DEFAULT_BYTES: u64 = 64 * 1024;
+const TAIL_MAX_BYTES: u64 = 1024 * 1024;
+const TAIL_TEXT_CHARS: usize = 8 * 1024;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TailPreview {
+    /// Last characters of the window, starting at a line boundary when the window does not begin
+    /// at the start of the file.
+    preview_text: Option<String>,
+    /// First bytes of the window as hex.
+    hex_snippet: String,
+    is_binary: bool,
+    /// Offset of the first byte in the window.
+    window_start: u64,
+    size: u64,
+}
+
+/// Clamps a requested tail length in bytes.
+pub fn tail_len(requested: Option<u32>) -> u64 {
+    requested
+        .map(u64::from)
+        .unwrap_or(TAIL_DEFAULT_BYTES)
+        .clamp(1, TAIL_MAX_BYTES)
+}
+
+fn tail_text(window: &[u8], starts_mid_file: bool) -> Option<String> {
+    let mut data = window;
+    if starts_mid_file {
+        // The window likely opens mid-line (and possibly mid-character); start at the next line.
+        data = match data.iter().position(|b| *b == b'\n') {
+            Some(pos) => &data[pos + 1..],
+            None => {
+                let skip = data.iter().take_while(|b| (**b & 0xC0) == 0x80).count();
+                &data[skip..]
+            }
+        };
+    }
+    let text = std::str::from_utf8(data).ok()?;
+    let chars = text.chars().count();
+    Some(
+        text.chars()
+            .skip(chars.saturating_sub(TAIL_TEXT_CHARS))
+            .collect(),
+    )
+}
+
+impl TailPreview {
+    pub fn new(window: &[u8], window_start: u64, size: u64) -> Self {
+        let preview_text = tail_text(window, window_start > 0);
+        Self {
+            is_binary: preview_text.is_none(),
+            preview_text,
+            hex_snippet: hex_encode(window.iter().take(48).copied().collect::<Vec<u8>>()),
+            window_start,
+            size,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::app_error::AppResult;
+
+/// Kept under Windows' 260-character `MAX_PATH`, which many of the apps opening these files
+/// still honour.
+const MAX_PATH_LEN: usize = 250;
+/// Most filesystems cap a single name at 255 bytes.
+const MAX_NAME_BYTES: usize = 255;
+
+/// Tells apart the partial files of writes running at the same time.
+static NEXT_PARTIAL: AtomicU64 = AtomicU64::new(0);
+
+fn short_hash(input: &str) -> String {
+    hex::encode(&Sha256::digest(input.as_bytes())[..4])
+}
+
+/// Letters and digits in any script, and `-_.+`. Path separators, characters Windows reserves,
+/// whitespace, control characters and invisible formatting marks are not kept.
+fn keep(c: char) -> bool {
+    if c.is_ascii() {
+        return c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+');
+    }
+    !c.is_whitespace() && !c.is_control() && !matches!(c, '\u{2000}'..='\u{206F}' | '\u{FEFF}')
+}
+
+/// `input` as part of a temp file name, with every character not kept replaced by `_`. When
+/// anything was replaced, a short hash of `input` goes before the extension so names differing
+/// only in replaced characters do not overwrite each other's files.
+pub fn sanitize(input: &str) -> String {
+    let cleaned: String = input
+        .chars()
+        .map(|c| if keep(c) { c } else { '_' })
+        .collect();
+    if cleaned == input {
+        return cleaned;
+    }
+    let hash = short_hash(input);
+    match cleaned.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{stem}-{hash}.{ext}"),
+        _ => format!("{cleaned}-{hash}"),
+    }
+}
+
+/// `dir/stem.ext`, with `stem` cut short (and told apart by its hash) where the name would pass
+/// the filesystem's limit or the whole path Windows' `MAX_PATH`.
+pub fn temp_file(dir: &Path, stem: &str, ext: &str) -> PathBuf {
+    // Byte lengths: never fewer than the UTF-16 units Windows counts.
+    let budget = MAX_PATH_LEN
+        .saturating_sub(dir.as_os_str().len() + 1)
+        .min(MAX_NAME_BYTES)
+        .saturating_sub(ext.len() + 1);
+    if stem.len() <= budget {
+        return dir.join(format!("{stem}.{ext}"));
+    }
+    let hash = short_hash(stem);
+    let mut end = budget.saturating_sub(hash.len() + 1);
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    dir.join(format!("{}-{hash}.{ext}", &stem[..end]))
+}
+
+/// Writes `data` to `stem.ext` in a subdirectory of `dir` named after a hash of the bytes, so
+/// different members whose names come out the same never overwrite each other's files, and
+/// opening the same bytes again reuses the file already there. Returns the path of the file.
+pub fn write(dir: &Path, stem: &str, ext: &str, data: &[u8]) -> AppResult<PathBuf> {
+    let dir = dir.join(hex::encode(&Sha256::digest(data)[..8]));
+    fs::create_dir_all(&dir)?;
+    let path = temp_file(&dir, stem, ext);
+    if fs::metadata(&path).is_ok_and(|m| m.len() == data.len() as u64) {
+        return Ok(path);
+    }
+    // Written aside and renamed into place, so a concurrent open never sees a partial file.
+    let partial = path.with_extension(format!(
+        "{ext}.{}-{}.part",
+        std::process::id(),
+        NEXT_PARTIAL.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&partial, data)?;
+    fs::rename(&partial, &path)?;
+    Ok(path)
+}
@@ -0,0 +1,176 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+use url::Url;
+
+use crate::access_log;
+use crate::app_error::{AppError, AppResult};
+use crate::http_date::parse_http_date;
+
+/// Emitted each time a throttled request is about to be retried.
+const THROTTLED_EVENT: &str = "http://throttled";
+const MAX_RETRIES: u32 = 5;
+/// Longer `Retry-After` waits are not sat out; the request fails and says when to try again.
+const MAX_WAIT: Duration = Duration::from_secs(120);
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+/// Hosts that asked to be left alone, until when. Requests to them wait instead of piling on.
+static PAUSED: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThrottledEvent {
+    host: String,
+    url: String,
+    status: u16,
+    /// Retry about to be made, from 1.
+    attempt: u32,
+    retry_in_secs: u64,
+}
+
+/// Keeps the handle used to report throttling to the UI. Called once at startup.
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+}
+
+fn host_of(url: &Url) -> String {
+    url.host_str().unwrap_or_default().to_ascii_lowercase()
+}
+
+fn paused() -> std::sync::MutexGuard<'static, HashMap<String, Instant>> {
+    PAUSED
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// `Retry-After` as delay-seconds or an HTTP date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = parse_http_date(value)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(at.saturating_sub(now)))
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+fn is_throttled(status: StatusCode, headers: &HeaderMap) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::SERVICE_UNAVAILABLE && headers.contains_key(RETRY_AFTER))
+}
+
+/// Time left before `url`'s host may be asked again.
+fn remaining(url: &Url) -> Option<Duration> {
+    let mut paused = paused();
+    let host = host_of(url);
+    let left = paused
+        .get(&host)
+        .and_then(|until| until.checked_duration_since(Instant::now()));
+    if left.is_none() {
+        paused.remove(&host);
+    }
+    left
+}
+
+/// For a throttled response (429, or 503 with `Retry-After`), pauses the host before retry
+/// number `attempt + 1` for the server's `Retry-After`, or a doubling backoff without one, and
+/// tells the UI. An error once retries run out. `None` when the response is not throttled.
+fn throttled(
+    url: &Url,
+    status: StatusCode,
+    headers: &HeaderMap,
+    attempt: u32,
+) -> Option<AppResult<()>> {
+    if !is_throttled(status, headers) {
+        return None;
+    }
+    let wait = retry_after(headers).unwrap_or_else(|| Duration::from_secs(1 << attempt.min(6)));
+    if attempt >= MAX_RETRIES || wait > MAX_WAIT {
+        return Some(Err(AppError::Remote(format!(
+            "{} is rate limiting requests (HTTP {}); try again in {} s",
+            host_of(url),
+            status.as_u16(),
+            wait.as_secs().max(1)
+        ))));
+    }
+    let host = host_of(url);
+    let until = Instant::now() + wait;
+    paused()
+        .entry(host.clone())
+        .and_modify(|t| *t = (*t).max(until))
+        .or_insert(until);
+    if let Some(app) = APP.get() {
+        let _ = app.emit(
+            THROTTLED_EVENT,
+            ThrottledEvent {
+                host,
+                url: url.to_string(),
+                status: status.as_u16(),
+                attempt: attempt + 1,
+                retry_in_secs: wait.as_secs(),
+            },
+        );
+    }
+    Some(Ok(()))
+}
+
+/// Sends the request `build` makes, waiting out throttling: a paused host is waited for first,
+/// and 429s are retried after `Retry-After`. `what` names the request in errors.
+pub async fn send(
+    url: &Url,
+    what: &str,
+    build: impl Fn() -> reqwest::RequestBuilder,
+) -> AppResult<reqwest::Response> {
+    let mut attempt = 0;
+    loop {
+        if let Some(wait) = remaining(url) {
+            tokio::time::sleep(wait).await;
+        }
+        let res = build()
+            .send()
+            .await
+            .map_err(|e| AppError::Remote(format!("{what} failed: {e}")))?;
+        access_log::record(url, Some(res.status().as_u16()), res.content_length());
+        match throttled(url, res.status(), res.headers(), attempt) {
+            None => return Ok(res),
+            // The host is paused now; the next round waits it out.
+            Some(paused) => paused?,
+        }
+        attempt += 1;
+    }
+}
+
+/// `send` for callers on a blocking thread.
+pub fn send_blocking(
+    url: &Url,
+    what: &str,
+    build: impl Fn() -> reqwest::blocking::RequestBuilder,
+) -> AppResult<reqwest::blocking::Response> {
+    let mut attempt = 0;
+    loop {
+        if let Some(wait) = remaining(url) {
+            std::thread::sleep(wait);
+        }
+        let res = build()
+            .send()
+            .map_err(|e| AppError::Remote(format!("{what} failed: {e}")))?;
+        access_log::record(url, Some(res.status().as_u16()), res.content_length());
+        match throttled(url, res.status(), res.headers(), attempt) {
+            None => return Ok(res),
+            Some(paused) => paused?,
+        }
+        attempt += 1;
+    }
+}
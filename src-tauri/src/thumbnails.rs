@@ -0,0 +1,86 @@
+use base64::Engine;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use rayon::ThreadPool;
+use std::io::Cursor;
+use std::sync::mpsc::Receiver;
+use std::sync::OnceLock;
+
+use crate::ipc_types::Thumbnail;
+
+pub const DEFAULT_THUMBNAIL_EDGE: u32 = 256;
+pub const MAX_THUMBNAIL_EDGE: u32 = 1024;
+/// Encoded images waiting for a decoder. Bounds memory while the reader runs ahead of decoding.
+pub const THUMBNAIL_QUEUE_DEPTH: usize = 16;
+/// Source images larger than this are reported instead of decoded.
+pub const MAX_THUMBNAIL_SOURCE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// One image handed from a reader to the decode pool; `index` is its position in the request.
+pub struct ThumbnailSource {
+    pub index: usize,
+    pub name: String,
+    pub bytes: Result<Vec<u8>, String>,
+}
+
+static POOL: OnceLock<Option<ThreadPool>> = OnceLock::new();
+
+fn pool() -> Option<&'static ThreadPool> {
+    POOL.get_or_init(|| {
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("thumbnail-{i}"))
+            .build()
+            .ok()
+    })
+    .as_ref()
+}
+
+fn render(source: ThumbnailSource, max_edge: u32) -> (usize, Thumbnail) {
+    let mut thumb = Thumbnail {
+        name: source.name,
+        base64: None,
+        width: 0,
+        height: 0,
+        error: None,
+    };
+    let encoded = source.bytes.and_then(|bytes| {
+        let img = image::load_from_memory(&bytes).map_err(|e| format!("decode failed: {e}"))?;
+        let small = img.thumbnail(max_edge, max_edge);
+        let rgb = image::DynamicImage::ImageRgb8(small.to_rgb8());
+        let mut out = Vec::new();
+        rgb.write_to(&mut Cursor::new(&mut out), image::ImageFormat::Jpeg)
+            .map_err(|e| format!("encode failed: {e}"))?;
+        Ok((out, rgb.width(), rgb.height()))
+    });
+    match encoded {
+        Ok((out, width, height)) => {
+            thumb.base64 = Some(base64::engine::general_purpose::STANDARD.encode(out));
+            thumb.width = width;
+            thumb.height = height;
+        }
+        Err(err) => thumb.error = Some(err),
+    }
+    (source.index, thumb)
+}
+
+/// Decodes everything the reader sends on the shared thumbnail pool and returns JPEG thumbnails
+/// in request order. Falls back to the calling thread if the pool could not be started.
+pub fn render_all(rx: Receiver<ThumbnailSource>, max_edge: u32) -> Vec<(usize, Thumbnail)> {
+    let max_edge = max_edge.clamp(16, MAX_THUMBNAIL_EDGE);
+    let mut out: Vec<(usize, Thumbnail)> = match pool() {
+        Some(pool) => pool.install(|| {
+            rx.into_iter()
+                .par_bridge()
+                .map(|source| render(source, max_edge))
+                .collect()
+        }),
+        None => rx
+            .into_iter()
+            .map(|source| render(source, max_edge))
+            .collect(),
+    };
+    out.sort_by_key(|(index, _)| *index);
+    out
+}
@@ -0,0 +1,421 @@
+use flate2::read::DeflateDecoder;
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+use tauri::async_runtime::spawn_blocking;
+
+use crate::app_error::{AppError, AppResult};
+use crate::messages::{self, Message};
+use crate::pickle::{self, Node, Pickle};
+use crate::zip_format::{
+    find_zip_eocd, parse_central_directory_entries, read_u16_le, read_u32_le, read_u64_le,
+    ZipEntryIndex,
+};
+
+/// End of central directory record, its longest comment and the ZIP64 locator before it.
+const ZIP_TAIL_BYTES: u64 = 22 + 65_535 + 20;
+const MAX_CENTRAL_DIR_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_PICKLE_BYTES: u64 = 256 * 1024 * 1024;
+const MAX_TENSORS: usize = 50_000;
+const MAX_SCALARS: usize = 500;
+/// Nesting followed when walking the unpickled object; also stops reference cycles.
+const MAX_DEPTH: usize = 64;
+/// Nodes visited in one walk. The memo lets a pickle reuse a list many times over, so depth
+/// alone still allows exponentially many steps.
+const MAX_VISITS: usize = 1_000_000;
+/// The pickle magic number `torch.save` wrote before the ZIP format (PyTorch < 1.6).
+const LEGACY_MAGIC_PREFIX: &[u8] = &[0x80, 0x02, 0x8A, 0x0A, 0x6C, 0xFC, 0x9C, 0x46, 0xF9, 0x20];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TorchTensor {
+    /// Dotted path from the top-level object, e.g. `state_dict.encoder.layer.0.weight`.
+    name: String,
+    dtype: String,
+    shape: Vec<u64>,
+    /// `None` for storage types whose element size is unknown.
+    byte_size: Option<u64>,
+    storage_key: String,
+    storage_offset: u64,
+    device: Option<String>,
+}
+
+/// A plain value stored next to the tensors (epoch counters, hyperparameters, ...).
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TorchScalar {
+    name: String,
+    value: serde_json::Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TorchArchiveSummary {
+    path: String,
+    /// The top-level folder all entries live in (`archive`, or the file stem).
+    archive_name: String,
+    format_version: Option<String>,
+    byte_order: Option<String>,
+    entries: usize,
+    storage_count: usize,
+    /// Uncompressed size of all tensor storages in the archive.
+    storage_bytes: u64,
+    tensor_count: usize,
+    tensors: Vec<TorchTensor>,
+    scalars: Vec<TorchScalar>,
+    /// Whether `tensors` or `scalars` were cut at their limits, or the walk hit its visit budget.
+    truncated: bool,
+}
+
+fn read_at(file: &mut File, offset: u64, len: u64) -> AppResult<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = Vec::new();
+    file.take(len).read_to_end(&mut buf)?;
+    if (buf.len() as u64) < len {
+        return Err(AppError::Invalid(messages::text(
+            Message::UnexpectedEndOfFile,
+            &[],
+        )));
+    }
+    Ok(buf)
+}
+
+fn zip_entries(file: &mut File, size: u64) -> AppResult<Vec<ZipEntryIndex>> {
+    let tail_start = size.saturating_sub(ZIP_TAIL_BYTES);
+    let tail = read_at(file, tail_start, size - tail_start)?;
+    let eocd = find_zip_eocd(&tail)
+        .ok_or_else(|| AppError::Invalid(messages::text(Message::NotTorchZip, &[])))?;
+    let mut entries = u64::from(read_u16_le(&tail, eocd + 10)?);
+    let mut cd_size = u64::from(read_u32_le(&tail, eocd + 12)?);
+    let mut cd_offset = u64::from(read_u32_le(&tail, eocd + 16)?);
+    if entries == 0xFFFF || cd_size == 0xFFFF_FFFF || cd_offset == 0xFFFF_FFFF {
+        let locator = eocd
+            .checked_sub(20)
+            .filter(|at| read_u32_le(&tail, *at).ok() == Some(0x0706_4b50))
+            .ok_or_else(|| AppError::Invalid(messages::text(Message::MissingZip64Locator, &[])))?;
+        let zip64_eocd = read_at(file, read_u64_le(&tail, locator + 8)?, 56)?;
+        if read_u32_le(&zip64_eocd, 0)? != 0x0606_4b50 {
+            return Err(AppError::Invalid(messages::text(
+                Message::MissingZip64Eocd,
+                &[],
+            )));
+        }
+        entries = read_u64_le(&zip64_eocd, 32)?;
+        cd_size = read_u64_le(&zip64_eocd, 40)?;
+        cd_offset = read_u64_le(&zip64_eocd, 48)?;
+    }
+    if cd_size > MAX_CENTRAL_DIR_BYTES {
+        return Err(AppError::Invalid(messages::text(
+            Message::CentralDirTooLarge,
+            &[("size", &cd_size)],
+        )));
+    }
+    let central_dir = read_at(file, cd_offset, cd_size)?;
+    parse_central_directory_entries(&central_dir, entries, &[0])
+}
+
+fn read_entry(file: &mut File, entry: &ZipEntryIndex, limit: u64) -> AppResult<Vec<u8>> {
+    if entry.uncompressed_size > limit {
+        return Err(AppError::Invalid(messages::text(
+            Message::EntryTooLargeToRead,
+            &[("name", &entry.name), ("size", &entry.uncompressed_size)],
+        )));
+    }
+    let header = read_at(file, entry.local_header_offset, 30)?;
+    if read_u32_le(&header, 0)? != 0x0403_4b50 {
+        return Err(AppError::Invalid(messages::text(
+            Message::BadLocalHeader,
+            &[("name", &entry.name)],
+        )));
+    }
+    let name_len = u64::from(read_u16_le(&header, 26)?);
+    let extra_len = u64::from(read_u16_le(&header, 28)?);
+    let data_start = entry.local_header_offset + 30 + name_len + extra_len;
+    let raw = read_at(file, data_start, entry.compressed_size)?;
+    match entry.method {
+        0 => Ok(raw),
+        8 => {
+            let mut out = Vec::new();
+            DeflateDecoder::new(raw.as_slice())
+                .take(limit)
+                .read_to_end(&mut out)?;
+            Ok(out)
+        }
+        method => Err(AppError::UnsupportedCompression(messages::text(
+            Message::UnsupportedZipMethod,
+            &[("method", &method), ("name", &entry.name)],
+        ))),
+    }
+}
+
+/// Element type and size of a legacy typed storage class (`torch.FloatStorage`, ...).
+fn storage_dtype(class: &str) -> (String, Option<u64>) {
+    let (dtype, size) = match class {
+        "FloatStorage" => ("float32", 4),
+        "DoubleStorage" => ("float64", 8),
+        "HalfStorage" => ("float16", 2),
+        "BFloat16Storage" => ("bfloat16", 2),
+        "LongStorage" => ("int64", 8),
+        "IntStorage" => ("int32", 4),
+        "ShortStorage" => ("int16", 2),
+        "CharStorage" => ("int8", 1),
+        "ByteStorage" | "UntypedStorage" => ("uint8", 1),
+        "BoolStorage" => ("bool", 1),
+        "ComplexFloatStorage" => ("complex64", 8),
+        "ComplexDoubleStorage" => ("complex128", 16),
+        "QInt8Storage" => ("qint8", 1),
+        "QUInt8Storage" => ("quint8", 1),
+        "QInt32Storage" => ("qint32", 4),
+        "Float8_e5m2Storage" => ("float8_e5m2", 1),
+        "Float8_e4m3fnStorage" => ("float8_e4m3fn", 1),
+        other => {
+            let name = other.strip_suffix("Storage").unwrap_or(other);
+            return (name.to_ascii_lowercase(), None);
+        }
+    };
+    (dtype.to_string(), Some(size))
+}
+
+struct Walker<'a> {
+    pickle: &'a Pickle,
+    tensors: Vec<TorchTensor>,
+    tensor_count: usize,
+    scalars: Vec<TorchScalar>,
+    truncated: bool,
+    visits: usize,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, id: usize, path: &str, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        if self.visits >= MAX_VISITS {
+            self.truncated = true;
+            return;
+        }
+        self.visits += 1;
+        let pickle = self.pickle;
+        match pickle.node(id) {
+            Node::Dict(pairs) => self.walk_pairs(pairs, path, depth),
+            Node::List(items) | Node::Tuple(items) | Node::Set(items) => {
+                for (idx, item) in items.iter().enumerate() {
+                    self.walk(*item, &join(path, &idx.to_string()), depth + 1);
+                }
+            }
+            Node::Call {
+                callable,
+                args,
+                state,
+                items,
+                list,
+            } => {
+                if let Some(tensor) = self.tensor(*callable, *args) {
+                    self.tensor_count += 1;
+                    if self.tensors.len() < MAX_TENSORS {
+                        self.tensors.push(TorchTensor {
+                            name: path.to_string(),
+                            ..tensor
+                        });
+                    } else {
+                        self.truncated = true;
+                    }
+                    return;
+                }
+                // An object: walk what was stored into it rather than its constructor arguments.
+                self.walk_pairs(items, path, depth);
+                for (idx, item) in list.iter().enumerate() {
+                    self.walk(*item, &join(path, &idx.to_string()), depth + 1);
+                }
+                if let Some(state) = state {
+                    match pickle.node(*state) {
+                        // `(dict_state, slots_state)`
+                        Node::Tuple(parts) => {
+                            for part in parts {
+                                self.walk(*part, path, depth + 1);
+                            }
+                        }
+                        _ => self.walk(*state, path, depth + 1),
+                    }
+                }
+            }
+            Node::None => self.scalar(path, serde_json::Value::Null),
+            Node::Bool(v) => self.scalar(path, (*v).into()),
+            Node::Int(v) => self.scalar(path, (*v).into()),
+            Node::BigInt(bytes) => {
+                self.scalar(path, format!("<{}-byte integer>", bytes.len()).into())
+            }
+            Node::Float(v) => self.scalar(path, (*v).into()),
+            Node::Str(v) => self.scalar(path, v.as_str().into()),
+            Node::Bytes(v) => self.scalar(path, format!("<{} bytes>", v.len()).into()),
+            Node::Global { module, name } => self.scalar(path, format!("{module}.{name}").into()),
+            Node::PersistentId(_) => {}
+        }
+    }
+
+    fn walk_pairs(&mut self, pairs: &[(usize, usize)], path: &str, depth: usize) {
+        for (key, value) in pairs {
+            let key = match self.pickle.node(*key) {
+                Node::Str(s) => s.clone(),
+                Node::Int(v) => v.to_string(),
+                _ => "?".to_string(),
+            };
+            self.walk(*value, &join(path, &key), depth + 1);
+        }
+    }
+
+    fn scalar(&mut self, path: &str, value: serde_json::Value) {
+        if path.is_empty() {
+            return;
+        }
+        if self.scalars.len() < MAX_SCALARS {
+            self.scalars.push(TorchScalar {
+                name: path.to_string(),
+                value,
+            });
+        } else {
+            self.truncated = true;
+        }
+    }
+
+    /// The tensor a `torch._utils._rebuild_*` call would build, without its name.
+    fn tensor(&self, callable: usize, args: usize) -> Option<TorchTensor> {
+        let pickle = self.pickle;
+        let (module, name) = pickle.global(callable)?;
+        let args = pickle.items(args)?;
+        match (module, name) {
+            ("torch._utils", "_rebuild_parameter" | "_rebuild_parameter_with_state") => {
+                let Node::Call { callable, args, .. } = pickle.node(*args.first()?) else {
+                    return None;
+                };
+                self.tensor(*callable, *args)
+            }
+            // `_rebuild_from_type_v2(func, new_type, args, state)` for tensor subclasses.
+            ("torch._tensor", "_rebuild_from_type_v2") => {
+                self.tensor(*args.first()?, *args.get(2)?)
+            }
+            ("torch._utils", name)
+                if name.starts_with("_rebuild_tensor") || name == "_rebuild_qtensor" =>
+            {
+                // `(storage, storage_offset, size, stride, ...)`
+                let Node::PersistentId(pid) = pickle.node(*args.first()?) else {
+                    return None;
+                };
+                // `('storage', storage_type, key, location, numel)`
+                let pid = pickle.items(*pid)?;
+                let (_, class) = pickle.global(*pid.get(1)?)?;
+                let (dtype, item_size) = storage_dtype(class);
+                let shape = pickle
+                    .items(*args.get(2)?)?
+                    .iter()
+                    .map(|dim| pickle.as_int(*dim).and_then(|d| u64::try_from(d).ok()))
+                    .collect::<Option<Vec<_>>>()?;
+                // Crafted shapes can overflow; the size is then left unknown.
+                let numel = shape.iter().try_fold(1u64, |n, dim| n.checked_mul(*dim));
+                Some(TorchTensor {
+                    name: String::new(),
+                    dtype,
+                    byte_size: item_size
+                        .zip(numel)
+                        .and_then(|(size, numel)| size.checked_mul(numel)),
+                    shape,
+                    storage_key: pickle.as_str(*pid.get(2)?)?.to_string(),
+                    storage_offset: pickle
+                        .as_int(*args.get(1)?)
+                        .and_then(|v| u64::try_from(v).ok())
+                        .unwrap_or(0),
+                    device: pid
+                        .get(3)
+                        .and_then(|id| pickle.as_str(*id))
+                        .map(str::to_string),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn inspect(path: &Path) -> AppResult<TorchArchiveSummary> {
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    let head = read_at(&mut file, 0, size.min(LEGACY_MAGIC_PREFIX.len() as u64))?;
+    if head == LEGACY_MAGIC_PREFIX {
+        return Err(AppError::Invalid(messages::text(
+            Message::LegacyTorchSave,
+            &[],
+        )));
+    }
+    let entries = zip_entries(&mut file, size)?;
+    let pkl = entries
+        .iter()
+        .filter(|e| e.name == "data.pkl" || e.name.ends_with("/data.pkl"))
+        .min_by_key(|e| e.name.len())
+        .ok_or_else(|| AppError::Missing(messages::text(Message::NoDataPkl, &[])))?;
+    let prefix = pkl
+        .name
+        .strip_suffix("data.pkl")
+        .unwrap_or_default()
+        .to_string();
+    let small_entry = |file: &mut File, name: &str| -> Option<String> {
+        let entry = entries
+            .iter()
+            .find(|e| e.name == format!("{prefix}{name}"))?;
+        let bytes = read_entry(file, entry, 1024).ok()?;
+        Some(String::from_utf8_lossy(&bytes).trim().to_string())
+    };
+    let format_version = small_entry(&mut file, "version");
+    let byte_order = small_entry(&mut file, "byteorder");
+
+    let data_prefix = format!("{prefix}data/");
+    let storages = entries
+        .iter()
+        .filter(|e| e.name.starts_with(&data_prefix) && e.name.len() > data_prefix.len())
+        .collect::<Vec<_>>();
+
+    let pickle = pickle::parse(&read_entry(&mut file, pkl, MAX_PICKLE_BYTES)?)?;
+    let mut walker = Walker {
+        pickle: &pickle,
+        tensors: Vec::new(),
+        tensor_count: 0,
+        scalars: Vec::new(),
+        truncated: false,
+        visits: 0,
+    };
+    walker.walk(pickle.root(), "", 0);
+
+    Ok(TorchArchiveSummary {
+        path: path.display().to_string(),
+        archive_name: prefix.trim_end_matches('/').to_string(),
+        format_version,
+        byte_order,
+        entries: entries.len(),
+        storage_count: storages.len(),
+        storage_bytes: storages
+            .iter()
+            .fold(0u64, |sum, e| sum.saturating_add(e.uncompressed_size)),
+        tensor_count: walker.tensor_count,
+        tensors: walker.tensors,
+        scalars: walker.scalars,
+        truncated: walker.truncated,
+    })
+}
+
+/// Lists the tensors in a PyTorch `.pt`/`.pth` file (the ZIP format `torch.save` has written
+/// since 1.6) by decoding its `data.pkl` without running any of it.
+#[tauri::command]
+pub async fn inspect_torch_file(path: String) -> AppResult<TorchArchiveSummary> {
+    spawn_blocking(move || inspect(Path::new(path.trim())))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
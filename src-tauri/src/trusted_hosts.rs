@@ -0,0 +1,81 @@
+use std::sync::RwLock;
+use tauri::AppHandle;
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::settings;
+
+const TRUSTED_HOSTS_KEY: &str = "trusted_repository_hosts";
+
+/// Self-hosted InvenioRDM instances the user has allowed alongside zenodo.org.
+static TRUSTED: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// True if `host` is a trusted host or one of its subdomains.
+pub fn is_trusted(host: &str) -> bool {
+    let host = host.trim().to_ascii_lowercase();
+    let Ok(trusted) = TRUSTED.read() else {
+        return false;
+    };
+    trusted
+        .iter()
+        .any(|t| host == *t || host.ends_with(&format!(".{t}")))
+}
+
+/// Accepts a bare host ("data.example.edu") or a URL and returns its lowercase host.
+fn normalize_host(input: &str) -> AppResult<String> {
+    let trimmed = input.trim();
+    let candidate = if trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        format!("https://{trimmed}")
+    };
+    Url::parse(&candidate)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_ascii_lowercase()))
+        .filter(|h| !h.is_empty())
+        .ok_or_else(|| AppError::Invalid(format!("Not a valid host name: {trimmed}.")))
+}
+
+fn normalize_all(hosts: Vec<String>) -> AppResult<Vec<String>> {
+    let mut out = Vec::new();
+    for host in hosts.iter().filter(|h| !h.trim().is_empty()) {
+        let host = normalize_host(host)?;
+        if !out.contains(&host) {
+            out.push(host);
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+fn store(hosts: Vec<String>) {
+    if let Ok(mut trusted) = TRUSTED.write() {
+        *trusted = hosts;
+    }
+}
+
+/// Loads the persisted host list. Called once at startup.
+pub fn load_settings(app: &AppHandle) {
+    if let Ok(Some(hosts)) = settings::read_setting::<Vec<String>>(app, TRUSTED_HOSTS_KEY) {
+        if let Ok(hosts) = normalize_all(hosts) {
+            store(hosts);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_trusted_repository_hosts() -> AppResult<Vec<String>> {
+    Ok(TRUSTED.read().map(|t| t.clone()).unwrap_or_default())
+}
+
+/// Replaces the trusted host list and returns it normalized (lowercase hosts, no duplicates).
+#[tauri::command]
+pub async fn set_trusted_repository_hosts(
+    app: AppHandle,
+    hosts: Vec<String>,
+) -> AppResult<Vec<String>> {
+    let hosts = normalize_all(hosts)?;
+    settings::write_setting(&app, TRUSTED_HOSTS_KEY, &hosts)?;
+    store(hosts.clone());
+    Ok(hosts)
+}
@@ -0,0 +1,162 @@
+//! Stable/beta update channels on top of the Tauri updater plugin. Every update downloads the
+//! full signed bundle named in the channel manifest; delta (binary diff) updates are out of scope,
+//! since the plugin has no patch format and the release workflow publishes no diffs to apply.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_updater::{Update, UpdaterExt};
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::messages::{self, Message};
+use crate::offline;
+use crate::settings;
+
+const UPDATE_CHANNEL_KEY: &str = "update_channel";
+const PROGRESS_EVENT: &str = "updater://progress";
+
+const STABLE_ENDPOINT: &str =
+    "https://github.com/binbinsh/dataset-inspector/releases/latest/download/latest.json";
+/// Manifest attached to the rolling `beta` pre-release.
+const BETA_ENDPOINT: &str =
+    "https://github.com/binbinsh/dataset-inspector/releases/download/beta/latest.json";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    fn endpoint(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => STABLE_ENDPOINT,
+            UpdateChannel::Beta => BETA_ENDPOINT,
+        }
+    }
+}
+
+/// The update found by the last check, kept for `apply_update`.
+#[derive(Default)]
+pub struct PendingUpdate(Mutex<Option<Update>>);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableUpdate {
+    version: String,
+    current_version: String,
+    date: Option<String>,
+    /// Release notes.
+    body: Option<String>,
+    channel: UpdateChannel,
+}
+
+/// Shaped like the updater plugin's `DownloadEvent`, so the UI handles both the same way.
+#[derive(Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+enum UpdateProgress {
+    #[serde(rename_all = "camelCase")]
+    Started {
+        content_length: Option<u64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Progress {
+        chunk_length: usize,
+    },
+    Finished,
+}
+
+fn updater_error(e: tauri_plugin_updater::Error) -> AppError {
+    AppError::Remote(messages::text(Message::UpdateFailed, &[("error", &e)]))
+}
+
+fn read_channel(app: &AppHandle) -> UpdateChannel {
+    settings::read_setting(app, UPDATE_CHANNEL_KEY)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn get_update_channel(app: AppHandle) -> AppResult<UpdateChannel> {
+    Ok(read_channel(&app))
+}
+
+#[tauri::command]
+pub async fn set_update_channel(app: AppHandle, channel: UpdateChannel) -> AppResult<()> {
+    settings::write_setting(&app, UPDATE_CHANNEL_KEY, &channel)
+}
+
+/// Asks the selected channel's manifest for a newer version. `None` when up to date.
+#[tauri::command]
+pub async fn check_update(
+    app: AppHandle,
+    pending: State<'_, PendingUpdate>,
+) -> AppResult<Option<AvailableUpdate>> {
+    let channel = read_channel(&app);
+    let endpoint = Url::parse(channel.endpoint()).map_err(|e| {
+        AppError::Invalid(messages::text(
+            Message::InvalidUpdateEndpoint,
+            &[("error", &e)],
+        ))
+    })?;
+    offline::ensure_online(&endpoint)?;
+    let update = app
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .map_err(updater_error)?
+        .build()
+        .map_err(updater_error)?
+        .check()
+        .await
+        .map_err(updater_error)?;
+    let available = update.as_ref().map(|update| AvailableUpdate {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        date: update.date.map(|date| date.to_string()),
+        body: update.body.clone(),
+        channel,
+    });
+    *pending
+        .0
+        .lock()
+        .map_err(|_| AppError::Task("update lock poisoned".into()))? = update;
+    Ok(available)
+}
+
+/// Downloads and installs the update the last `check_update` found, reporting progress as
+/// `updater://progress` events. The app has to be restarted afterwards.
+#[tauri::command]
+pub async fn apply_update(app: AppHandle, pending: State<'_, PendingUpdate>) -> AppResult<()> {
+    let update = {
+        let mut slot = pending
+            .0
+            .lock()
+            .map_err(|_| AppError::Task("update lock poisoned".into()))?;
+        // Checked before taking it, so an offline attempt keeps the update for later.
+        if let Some(update) = slot.as_ref() {
+            offline::ensure_online(&update.download_url)?;
+        }
+        slot.take()
+            .ok_or_else(|| AppError::Invalid(messages::text(Message::NoPendingUpdate, &[])))?
+    };
+    let mut started = false;
+    update
+        .download_and_install(
+            |chunk_length, content_length| {
+                if !started {
+                    started = true;
+                    let _ = app.emit(PROGRESS_EVENT, UpdateProgress::Started { content_length });
+                }
+                let _ = app.emit(PROGRESS_EVENT, UpdateProgress::Progress { chunk_length });
+            },
+            || {
+                let _ = app.emit(PROGRESS_EVENT, UpdateProgress::Finished);
+            },
+        )
+        .await
+        .map_err(updater_error)
+}
@@ -0,0 +1,370 @@
+use arrow_flight::error::FlightError;
+use arrow_flight::{FlightClient, FlightInfo};
+use bytes::Bytes;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use duckdb::{AccessMode, Config, Connection};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use std::time::Duration;
+use tauri::async_runtime::spawn_blocking;
+use tonic::transport::{Channel, ClientTlsConfig};
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::offline;
+use crate::sql_query::batches_json;
+
+const DEFAULT_ROWS: u32 = 25;
+const MAX_ROWS: u32 = 100;
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum WarehouseKind {
+    /// A DuckDB database file, opened read-only.
+    Duckdb,
+    /// An Arrow Flight endpoint (`grpc://host:port` or `grpc+tls://host:port`).
+    Flight,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarehouseColumn {
+    name: String,
+    /// DuckDB SQL type or Arrow type.
+    data_type: String,
+    nullable: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarehouseTable {
+    /// `schema.table` (just `table` in DuckDB's `main` schema), or a Flight descriptor's path
+    /// joined with `/` (its command when it has no path).
+    name: String,
+    columns: Vec<WarehouseColumn>,
+    /// DuckDB's estimate for tables (`None` for views), or the row count a Flight server reports.
+    num_rows: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarehouseListing {
+    location: String,
+    tables: Vec<WarehouseTable>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarehousePage {
+    table: String,
+    columns: Vec<WarehouseColumn>,
+    offset: usize,
+    rows: Vec<Value>,
+    /// Exact for DuckDB; for Flight, what the server reports, if anything.
+    num_rows_total: Option<u64>,
+}
+
+fn duckdb_error(err: duckdb::Error) -> AppError {
+    AppError::Invalid(format!("duckdb query failed: {err}"))
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn duckdb_open(path: &Path) -> AppResult<Connection> {
+    if !path.is_file() {
+        return Err(AppError::Missing(format!(
+            "{} does not exist",
+            path.display()
+        )));
+    }
+    let config = Config::default()
+        .access_mode(AccessMode::ReadOnly)
+        .map_err(duckdb_error)?;
+    Connection::open_with_flags(path, config).map_err(duckdb_error)
+}
+
+/// Tables and views of the database with their schema names.
+fn duckdb_tables(conn: &Connection) -> AppResult<Vec<(String, String, WarehouseTable)>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT c.table_schema, c.table_name, c.column_name, c.data_type, c.is_nullable, \
+             t.estimated_size \
+             FROM information_schema.columns c \
+             LEFT JOIN duckdb_tables() t \
+             ON t.database_name = c.table_catalog AND t.schema_name = c.table_schema \
+             AND t.table_name = c.table_name \
+             WHERE c.table_catalog = current_database() \
+             ORDER BY c.table_schema, c.table_name, c.ordinal_position",
+        )
+        .map_err(duckdb_error)?;
+    let mut rows = stmt.query([]).map_err(duckdb_error)?;
+    let mut tables: Vec<(String, String, WarehouseTable)> = Vec::new();
+    while let Some(row) = rows.next().map_err(duckdb_error)? {
+        let schema: String = row.get(0).map_err(duckdb_error)?;
+        let table: String = row.get(1).map_err(duckdb_error)?;
+        let column = WarehouseColumn {
+            name: row.get(2).map_err(duckdb_error)?,
+            data_type: row.get(3).map_err(duckdb_error)?,
+            nullable: row.get::<_, String>(4).map_err(duckdb_error)? == "YES",
+        };
+        match tables.last_mut() {
+            Some((s, t, entry)) if *s == schema && *t == table => entry.columns.push(column),
+            _ => {
+                let num_rows = row
+                    .get::<_, Option<i64>>(5)
+                    .map_err(duckdb_error)?
+                    .map(|n| n.max(0) as u64);
+                let name = if schema == "main" {
+                    table.clone()
+                } else {
+                    format!("{schema}.{table}")
+                };
+                tables.push((
+                    schema,
+                    table,
+                    WarehouseTable {
+                        name,
+                        columns: vec![column],
+                        num_rows,
+                    },
+                ));
+            }
+        }
+    }
+    Ok(tables)
+}
+
+fn duckdb_listing(path: &Path) -> AppResult<WarehouseListing> {
+    let conn = duckdb_open(path)?;
+    Ok(WarehouseListing {
+        location: path.display().to_string(),
+        tables: duckdb_tables(&conn)?
+            .into_iter()
+            .map(|(_, _, table)| table)
+            .collect(),
+    })
+}
+
+fn duckdb_page(path: &Path, name: &str, offset: usize, length: usize) -> AppResult<WarehousePage> {
+    let conn = duckdb_open(path)?;
+    let (schema, table, entry) = duckdb_tables(&conn)?
+        .into_iter()
+        .find(|(_, _, t)| t.name == name)
+        .ok_or_else(|| AppError::Missing(format!("table {name} is not in this database")))?;
+    let target = format!("{}.{}", quote_ident(&schema), quote_ident(&table));
+    let total: i64 = conn
+        .query_row(&format!("SELECT count(*) FROM {target}"), [], |row| {
+            row.get(0)
+        })
+        .map_err(duckdb_error)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT * FROM {target} LIMIT {length} OFFSET {offset}"
+        ))
+        .map_err(duckdb_error)?;
+    let batches = stmt
+        .query_arrow([])
+        .map_err(duckdb_error)?
+        .collect::<Vec<_>>();
+    Ok(WarehousePage {
+        table: entry.name,
+        columns: entry.columns,
+        offset,
+        rows: batches_json(&batches)?,
+        num_rows_total: Some(total.max(0) as u64),
+    })
+}
+
+fn flight_error(err: FlightError) -> AppError {
+    AppError::Remote(format!("flight request failed: {err}"))
+}
+
+async fn flight_connect(location: &str, token: Option<&str>) -> AppResult<FlightClient> {
+    let (uri, tls) = match location.split_once("://") {
+        Some(("grpc" | "http", rest)) => (format!("http://{rest}"), false),
+        Some(("grpc+tls" | "https", rest)) => (format!("https://{rest}"), true),
+        _ => {
+            return Err(AppError::Invalid(format!(
+                "flight endpoint must start with grpc:// or grpc+tls://, got {location}"
+            )))
+        }
+    };
+    let url = Url::parse(location)
+        .map_err(|e| AppError::Invalid(format!("invalid flight endpoint {location}: {e}")))?;
+    offline::ensure_online(&url)?;
+    let mut endpoint = Channel::from_shared(uri)
+        .map_err(|e| AppError::Invalid(format!("invalid flight endpoint {location}: {e}")))?
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS));
+    if tls {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_webpki_roots())
+            .map_err(|e| AppError::Invalid(format!("flight TLS setup failed: {e}")))?;
+    }
+    let channel = endpoint
+        .connect()
+        .await
+        .map_err(|e| AppError::Remote(format!("could not connect to {location}: {e}")))?;
+    let mut client = FlightClient::new(channel);
+    if let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) {
+        client
+            .add_header("authorization", &format!("Bearer {token}"))
+            .map_err(flight_error)?;
+    }
+    Ok(client)
+}
+
+fn flight_name(info: &FlightInfo) -> String {
+    match &info.flight_descriptor {
+        Some(d) if !d.path.is_empty() => d.path.join("/"),
+        Some(d) => String::from_utf8_lossy(&d.cmd).into_owned(),
+        None => String::new(),
+    }
+}
+
+fn schema_columns(schema: &Schema) -> Vec<WarehouseColumn> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| WarehouseColumn {
+            name: field.name().clone(),
+            data_type: field.data_type().to_string(),
+            nullable: field.is_nullable(),
+        })
+        .collect()
+}
+
+fn flight_table(info: FlightInfo) -> WarehouseTable {
+    let name = flight_name(&info);
+    let num_rows = u64::try_from(info.total_records).ok();
+    let columns = info
+        .try_decode_schema()
+        .map(|schema| schema_columns(&schema))
+        .unwrap_or_default();
+    WarehouseTable {
+        name,
+        columns,
+        num_rows,
+    }
+}
+
+async fn list_flights(client: &mut FlightClient) -> AppResult<Vec<FlightInfo>> {
+    client
+        .list_flights(Bytes::new())
+        .await
+        .map_err(flight_error)?
+        .try_collect()
+        .await
+        .map_err(flight_error)
+}
+
+async fn flight_listing(location: &str, token: Option<&str>) -> AppResult<WarehouseListing> {
+    let mut client = flight_connect(location, token).await?;
+    let tables = list_flights(&mut client)
+        .await?
+        .into_iter()
+        .map(flight_table)
+        .collect();
+    Ok(WarehouseListing {
+        location: location.to_string(),
+        tables,
+    })
+}
+
+/// Streams the flight's endpoints in order, keeping rows `offset..offset + length`. Endpoints
+/// are fetched from the same server, whatever other locations they list.
+async fn flight_page(
+    location: &str,
+    token: Option<&str>,
+    name: &str,
+    offset: usize,
+    length: usize,
+) -> AppResult<WarehousePage> {
+    let mut client = flight_connect(location, token).await?;
+    let mut info = list_flights(&mut client)
+        .await?
+        .into_iter()
+        .find(|info| flight_name(info) == name)
+        .ok_or_else(|| AppError::Missing(format!("flight {name} is not listed by {location}")))?;
+    let endpoints = std::mem::take(&mut info.endpoint);
+    let table = flight_table(info);
+
+    let mut skip = offset;
+    let mut remaining = length;
+    let mut batches: Vec<RecordBatch> = Vec::new();
+    'endpoints: for ticket in endpoints.into_iter().filter_map(|e| e.ticket) {
+        let mut stream = client.do_get(ticket).await.map_err(flight_error)?;
+        while let Some(batch) = stream.try_next().await.map_err(flight_error)? {
+            let rows = batch.num_rows();
+            if skip >= rows {
+                skip -= rows;
+                continue;
+            }
+            let take = (rows - skip).min(remaining);
+            batches.push(batch.slice(skip, take));
+            skip = 0;
+            remaining -= take;
+            if remaining == 0 {
+                break 'endpoints;
+            }
+        }
+    }
+    let columns = match (table.columns.is_empty(), batches.first()) {
+        (true, Some(batch)) => schema_columns(&batch.schema()),
+        _ => table.columns,
+    };
+    Ok(WarehousePage {
+        table: table.name,
+        columns,
+        offset,
+        rows: batches_json(&batches)?,
+        num_rows_total: table.num_rows,
+    })
+}
+
+/// Lists the tables and views of a DuckDB file, or the flights an Arrow Flight server offers,
+/// with their columns and row counts.
+#[tauri::command]
+pub async fn warehouse_tables(
+    kind: WarehouseKind,
+    location: String,
+    token: Option<String>,
+) -> AppResult<WarehouseListing> {
+    let location = location.trim().to_string();
+    match kind {
+        WarehouseKind::Duckdb => spawn_blocking(move || duckdb_listing(Path::new(&location)))
+            .await
+            .map_err(|e| AppError::Task(e.to_string()))?,
+        WarehouseKind::Flight => flight_listing(&location, token.as_deref()).await,
+    }
+}
+
+/// Rows `offset..offset + length` of a table listed by `warehouse_tables`.
+#[tauri::command]
+pub async fn warehouse_page(
+    kind: WarehouseKind,
+    location: String,
+    table: String,
+    offset: Option<u32>,
+    length: Option<u32>,
+    token: Option<String>,
+) -> AppResult<WarehousePage> {
+    let location = location.trim().to_string();
+    let offset = offset.unwrap_or(0) as usize;
+    let length = length.unwrap_or(DEFAULT_ROWS).clamp(1, MAX_ROWS) as usize;
+    match kind {
+        WarehouseKind::Duckdb => {
+            spawn_blocking(move || duckdb_page(Path::new(&location), &table, offset, length))
+                .await
+                .map_err(|e| AppError::Task(e.to_string()))?
+        }
+        WarehouseKind::Flight => {
+            flight_page(&location, token.as_deref(), &table, offset, length).await
+        }
+    }
+}
@@ -1,55 +1,127 @@
-use hex::encode as hex_encode;
 use serde::Serialize;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fs::{self, File},
-    io::{self, Read},
+    io::{self, BufRead, BufReader, Read},
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, PoisonError},
 };
-use tauri::async_runtime::spawn_blocking;
+use tauri::{async_runtime::spawn_blocking, AppHandle, Emitter};
 
 use crate::app_error::{AppError, AppResult};
 use crate::audio;
-use crate::ipc_types::{FieldPreview, OpenLeafResponse, PreparedFileResponse};
+use crate::dataset_profile;
+use crate::decode_profile::{self, DecodeOverride};
+use crate::decoder_plugins;
+use crate::decompress;
+use crate::embeddings::{self, EmbeddingProjection, ProjectionMethod};
+use crate::gzip_index::{self, GzipIndex};
+use crate::image_flags;
+use crate::ipc_types::{
+    FieldPreview, OpenLeafResponse, PreparedFileResponse, SavedLeafResponse, Thumbnail,
+};
+use crate::magic;
+use crate::messages::{self, Message};
+use crate::metadata;
 use crate::mosaicml;
+use crate::multipart;
+use crate::network_fs;
 use crate::open_with;
+use crate::path_glob;
+use crate::pii_scan::{self, PiiMatch};
+use crate::preview_kind::PreviewKind;
+use crate::preview_limits::PreviewLimits;
+use crate::quarantine;
+use crate::resource_monitor::MemoryCache;
+use crate::save_leaf;
+use crate::scan_cache::{self, LruScanMap, ScanFootprint, ScanSlot, SourceFingerprint};
+use crate::settings;
+use crate::supervisor;
+use crate::symlinks;
+use crate::tail_preview::{self, TailPreview};
+use crate::temp_names::{self, sanitize};
+use crate::thumbnails::{self, ThumbnailSource};
 
 const PREVIEW_BYTES: usize = 16 * 1024;
-const PREVIEW_TEXT_CHARS: usize = 8 * 1024;
 const MAX_LISTED_SAMPLES: usize = 5000;
 const MAX_OPEN_BYTES: u64 = 256 * 1024 * 1024;
 const MAX_TAR_META_BYTES: u64 = 1024 * 1024;
-
-fn preview_utf8_text(data: &[u8]) -> Option<String> {
-    let raw = match std::str::from_utf8(data) {
-        Ok(text) => text,
-        Err(err) if err.error_len().is_none() => {
-            std::str::from_utf8(&data[..err.valid_up_to()]).ok()?
-        }
-        Err(_) => return None,
-    };
-    Some(raw.chars().take(PREVIEW_TEXT_CHARS).collect())
-}
+const MAX_LINT_ISSUES: usize = 500;
+/// Text members are only scanned up to this many bytes.
+const MAX_PII_SCAN_BYTES: u64 = 1024 * 1024;
+const MAX_THUMBNAILS_PER_REQUEST: usize = 512;
+/// Members listed in a shard layout before it is truncated.
+const MAX_LAYOUT_MEMBERS: usize = 100_000;
+const PREFETCH_DEPTH_KEY: &str = "wds_prefetch_depth";
+const DEFAULT_PREFETCH_DEPTH: u32 = 2;
+const MAX_PREFETCH_DEPTH: u32 = 32;
+const PEEK_PAGE_DEFAULT_SAMPLES: u32 = 20;
+const PEEK_PAGE_MAX_SAMPLES: u32 = 100;
+/// Upper bound on preview bytes kept per shard, shared by peeks and prefetches.
+const MAX_CACHED_PREVIEW_BYTES: usize = 16 * 1024 * 1024;
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+const LZ4_FRAME_MAGIC: [u8; 4] = [0x04, 0x22, 0x4D, 0x18];
 
 #[derive(Clone, Default)]
 pub struct WdsScanCache {
-    inner: Arc<Mutex<HashMap<String, Arc<Mutex<ShardScanState>>>>>,
+    inner: Arc<Mutex<LruScanMap<ShardScanState>>>,
 }
 
 impl WdsScanCache {
-    fn get_or_create(&self, shard_path: &Path) -> AppResult<Arc<Mutex<ShardScanState>>> {
+    fn get_or_create(
+        &self,
+        app: &AppHandle,
+        shard_path: &Path,
+    ) -> AppResult<Arc<ScanSlot<ShardScanState>>> {
         let key = shard_path.display().to_string();
+        let fingerprint = SourceFingerprint::of(shard_path)?;
         let mut guard = self
             .inner
             .lock()
-            .map_err(|_| AppError::Task("wds scan cache lock poisoned".into()))?;
-        if let Some(existing) = guard.get(&key) {
-            return Ok(existing.clone());
+            // The map stays consistent even if a scan panicked while holding it.
+            .unwrap_or_else(PoisonError::into_inner);
+        let (state, replaced) = guard.get_or_try_insert(&key, Some(fingerprint), || {
+            ShardScanState::new(shard_path.to_path_buf())
+        })?;
+        if replaced {
+            scan_cache::emit_source_changed(app, &key);
+        }
+        Ok(state)
+    }
+
+    pub fn invalidate(&self, shard_path: Option<&str>) -> AppResult<usize> {
+        let mut guard = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(guard.invalidate(shard_path))
+    }
+
+    /// Drops the scan of `shard_filename` in `dir_path`, for a scan that panicked.
+    fn resetter(&self, dir_path: &str, shard_filename: &str) -> impl FnOnce() + Send + 'static {
+        let cache = self.clone();
+        let dir_path = PathBuf::from(dir_path);
+        let shard_filename = shard_filename.trim().to_string();
+        move || {
+            if let Ok(dir) = shard_dir(&dir_path) {
+                let key = dir.join(&shard_filename).display().to_string();
+                let _ = cache.invalidate(Some(&key));
+            }
         }
-        let created = Arc::new(Mutex::new(ShardScanState::new(shard_path.to_path_buf())?));
-        guard.insert(key, created.clone());
-        Ok(created)
+    }
+}
+
+impl MemoryCache for WdsScanCache {
+    fn usage(&self) -> (usize, usize) {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .usage()
+    }
+
+    fn shrink_by(&self, bytes: usize) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .shrink_by(bytes)
     }
 }
 
@@ -62,17 +134,35 @@ struct ShardScanState {
     current_fields: Vec<WdsFieldInfo>,
     current_bytes: u64,
     current_sample_index: u32,
+    previews: HashMap<String, CachedPreview>,
+    preview_bytes: usize,
+    prefetch_in_flight: bool,
+    listing_bytes: usize,
+    /// Restart points recorded while scanning a plain `.tar.gz` shard.
+    gzip_index: Option<Arc<Mutex<GzipIndex>>>,
+}
+
+struct CachedPreview {
+    data: Vec<u8>,
+    size: u64,
 }
 
 struct TarStream<R: Read> {
     reader: R,
     pending_longname: Option<String>,
     pending_pax_path: Option<String>,
+    /// Bytes of the tar stream consumed so far.
+    offset: u64,
+    /// Where the current entry's long-name or PAX headers started.
+    chain_start: Option<u64>,
 }
 
 struct TarFileMeta {
     path: String,
+    raw_path: String,
     size: u64,
+    /// Offset of the entry's first header block, including any long-name or PAX headers.
+    header_offset: u64,
 }
 
 impl<R: Read> TarStream<R> {
@@ -81,36 +171,48 @@ impl<R: Read> TarStream<R> {
             reader,
             pending_longname: None,
             pending_pax_path: None,
+            offset: 0,
+            chain_start: None,
         }
     }
 
     fn next_file(&mut self) -> io::Result<Option<TarFileMeta>> {
         loop {
+            let header_start = self.offset;
             let Some(header) = read_tar_header_block(&mut self.reader)? else {
                 return Ok(None);
             };
+            self.offset += 512;
             if header.iter().all(|b| *b == 0) {
                 // tar EOF marker: two consecutive 512-byte zero blocks.
+                let next_start = self.offset;
                 let Some(next) = read_tar_header_block(&mut self.reader)? else {
                     return Ok(None);
                 };
+                self.offset += 512;
                 if next.iter().all(|b| *b == 0) {
                     return Ok(None);
                 }
-                if let Some(item) = self.process_header(next)? {
+                if let Some(item) = self.process_header(next, next_start)? {
                     return Ok(Some(item));
                 }
                 continue;
             }
-            if let Some(item) = self.process_header(header)? {
+            if let Some(item) = self.process_header(header, header_start)? {
                 return Ok(Some(item));
             }
         }
     }
 
-    fn process_header(&mut self, header: [u8; 512]) -> io::Result<Option<TarFileMeta>> {
+    fn process_header(
+        &mut self,
+        header: [u8; 512],
+        header_start: u64,
+    ) -> io::Result<Option<TarFileMeta>> {
         let size = parse_tar_size(&header).unwrap_or(0);
         let typeflag = header[156];
+        let chain_start = *self.chain_start.get_or_insert(header_start);
+        self.offset += padded_tar_size(size);
 
         // GNU long name (next entry path stored in the data section).
         if typeflag == b'L' {
@@ -151,6 +253,7 @@ impl<R: Read> TarStream<R> {
             path = pax_path;
         }
         let normalized = normalize_member_path_str(&path);
+        self.chain_start = None;
 
         // Skip entry data so the stream is positioned at the next header.
         skip_tar_data(&mut self.reader, size)?;
@@ -164,7 +267,9 @@ impl<R: Read> TarStream<R> {
 
         Ok(Some(TarFileMeta {
             path: normalized,
+            raw_path: path,
             size,
+            header_offset: chain_start,
         }))
     }
 }
@@ -195,6 +300,10 @@ fn skip_tar_data<R: Read>(reader: &mut R, size: u64) -> io::Result<()> {
     skip_tar_padding(reader, size)
 }
 
+fn padded_tar_size(size: u64) -> u64 {
+    size + (512 - (size % 512)) % 512
+}
+
 fn skip_tar_padding<R: Read>(reader: &mut R, size: u64) -> io::Result<()> {
     let pad = (512 - (size % 512)) % 512;
     if pad == 0 {
@@ -272,7 +381,12 @@ fn parse_ustar_path(header: &[u8; 512]) -> String {
 
 impl ShardScanState {
     fn new(shard_path: PathBuf) -> AppResult<Self> {
-        let reader = open_shard_reader(&shard_path)?;
+        let (reader, gzip_index) = if is_plain_gzip_shard(&shard_path) {
+            let (reader, index) = gzip_index::indexing_reader(&shard_path)?;
+            (reader, Some(index))
+        } else {
+            (open_shard_reader(&shard_path)?, None)
+        };
         Ok(Self {
             shard_path,
             tar: TarStream::new(reader),
@@ -282,9 +396,46 @@ impl ShardScanState {
             current_fields: Vec::new(),
             current_bytes: 0,
             current_sample_index: 0,
+            previews: HashMap::new(),
+            preview_bytes: 0,
+            prefetch_in_flight: false,
+            listing_bytes: 0,
+            gzip_index,
         })
     }
 
+    fn cache_preview(&mut self, member_path: String, data: Vec<u8>, size: u64) {
+        if self.previews.contains_key(&member_path)
+            || self.preview_bytes + data.len() > MAX_CACHED_PREVIEW_BYTES
+        {
+            return;
+        }
+        self.preview_bytes += data.len();
+        self.previews
+            .insert(member_path, CachedPreview { data, size });
+    }
+
+    /// Uncached members of the `depth` samples following the one that contains `member_path`.
+    fn prefetch_targets(&mut self, member_path: &str, depth: u32) -> AppResult<Vec<String>> {
+        let Some(index) = self.samples.iter().position(|sample| {
+            sample
+                .fields
+                .iter()
+                .any(|f| normalize_member_path_str(&f.member_path) == member_path)
+        }) else {
+            return Ok(Vec::new());
+        };
+        let end = index + 1 + depth as usize;
+        self.ensure_scanned(end.min(u32::MAX as usize) as u32, false)?;
+        let end = end.min(self.samples.len());
+        Ok(self.samples[(index + 1).min(end)..end]
+            .iter()
+            .flat_map(|sample| sample.fields.iter())
+            .map(|f| normalize_member_path_str(&f.member_path))
+            .filter(|path| !self.previews.contains_key(path))
+            .collect())
+    }
+
     fn ensure_scanned(&mut self, target_count: u32, compute_total: bool) -> AppResult<()> {
         if self.done {
             return Ok(());
@@ -294,6 +445,8 @@ impl ShardScanState {
             return Ok(());
         }
         let mut stopped_early = false;
+        let listed_before = self.samples.len();
+        let mut indexed_bytes = 0;
 
         while !self.done {
             let next = self
@@ -308,6 +461,10 @@ impl ShardScanState {
             let member_path = next.path;
             let (key, field_name) = split_sample_key(&member_path);
             let size = next.size;
+            if let Some(mut index) = self.gzip_index.as_ref().and_then(|i| i.lock().ok()) {
+                indexed_bytes += member_path.len() + std::mem::size_of::<(String, u64)>();
+                index.record_header(member_path.clone(), next.header_offset);
+            }
 
             if self.current_key.as_deref() != Some(&key) {
                 flush_sample_parts(
@@ -342,10 +499,31 @@ impl ShardScanState {
                 &mut self.samples,
             );
         }
+        self.listing_bytes += indexed_bytes
+            + self.samples[listed_before..]
+                .iter()
+                .map(sample_footprint)
+                .sum::<usize>();
         Ok(())
     }
 }
 
+impl ScanFootprint for ShardScanState {
+    fn approx_bytes(&self) -> usize {
+        self.listing_bytes + self.preview_bytes
+    }
+}
+
+fn sample_footprint(sample: &WdsSampleInfo) -> usize {
+    std::mem::size_of::<WdsSampleInfo>()
+        + sample.key.len()
+        + sample
+            .fields
+            .iter()
+            .map(|f| std::mem::size_of::<WdsFieldInfo>() + f.name.len() + f.member_path.len())
+            .sum::<usize>()
+}
+
 fn flush_sample_parts(
     key: Option<String>,
     current_fields: &mut Vec<WdsFieldInfo>,
@@ -383,12 +561,18 @@ pub struct WdsShardSummary {
     pub path: String,
     pub bytes: u64,
     pub exists: bool,
+    /// Where the shard really lives, when `path` is a symlink or junction.
+    pub link_target: Option<String>,
+    /// Why the link could not be followed (a loop), in which case `exists` is false.
+    pub link_error: Option<String>,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WdsDirSummary {
     pub dir_path: String,
+    /// Where the directory really lives, when `dir_path` is a symlink or junction.
+    pub resolved_dir_path: Option<String>,
     pub shards: Vec<WdsShardSummary>,
 }
 
@@ -446,12 +630,27 @@ pub async fn detect_local_dataset(path: String) -> AppResult<LocalDatasetDetectR
         .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn detect_local_dataset_sync(path: PathBuf) -> AppResult<LocalDatasetDetectResponse> {
+pub fn detect_local_dataset_sync(path: PathBuf) -> AppResult<LocalDatasetDetectResponse> {
     let trimmed = path.to_string_lossy().trim().to_string();
     if trimmed.is_empty() {
         return Err(AppError::Invalid("path is empty".into()));
     }
 
+    if shard_glob(&path).is_some() {
+        let has_shards = path_glob::expand(&trimmed)?.iter().any(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(looks_like_wds_shard)
+        });
+        if !has_shards {
+            return Err(AppError::Missing(format!(
+                "no WebDataset shard matches {trimmed}"
+            )));
+        }
+        // `wds_load_dir` expands the pattern again into its shard list.
+        return Ok(LocalDatasetDetectResponse::WebdatasetDir { dir_path: trimmed });
+    }
+
     if path.is_file() {
         let filename = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
         if looks_like_wds_shard(filename) {
@@ -489,7 +688,7 @@ fn detect_local_dataset_sync(path: PathBuf) -> AppResult<LocalDatasetDetectRespo
     }
 
     if path.is_dir() {
-        if let Some(index) = find_litdata_index_in_dir(&path) {
+        if let Some(index) = find_litdata_index_in_dir(&path)? {
             if let Some(index_path) = mosaicml::detect_mds_index_path(&index) {
                 return Ok(LocalDatasetDetectResponse::MdsIndex { index_path });
             }
@@ -514,7 +713,7 @@ fn detect_local_dataset_sync(path: PathBuf) -> AppResult<LocalDatasetDetectRespo
     )))
 }
 
-fn looks_like_litdata_file(filename: &str) -> bool {
+pub fn looks_like_litdata_file(filename: &str) -> bool {
     let name = filename.to_lowercase();
     if name.contains("index.json") {
         return true;
@@ -528,8 +727,10 @@ fn looks_like_litdata_file(filename: &str) -> bool {
     false
 }
 
-fn find_litdata_index_in_dir(dir: &Path) -> Option<PathBuf> {
-    let candidates = [
+/// Of a directory's file names (sorted), the LitData (or MDS) index: a conventional name
+/// first, then any `*.index.json*`.
+pub fn litdata_index_name(names: &[String]) -> Option<&str> {
+    const CANDIDATES: &[&str] = &[
         "index.json",
         "index.json.zstd",
         "index.json.zst",
@@ -537,24 +738,69 @@ fn find_litdata_index_in_dir(dir: &Path) -> Option<PathBuf> {
         "0.index.json.zstd",
         "0.index.json.zst",
     ];
-    for name in candidates {
-        let p = dir.join(name);
-        if p.exists() {
-            return Some(p);
-        }
-    }
-    let mut globbed: Vec<PathBuf> = std::fs::read_dir(dir)
-        .ok()?
+    CANDIDATES
+        .iter()
+        .find(|c| names.iter().any(|n| n == *c))
+        .copied()
+        .or_else(|| {
+            names
+                .iter()
+                .find(|n| n.ends_with(".index.json") || n.contains(".index.json."))
+                .map(String::as_str)
+        })
+}
+
+pub fn find_litdata_index_in_dir(dir: &Path) -> AppResult<Option<PathBuf>> {
+    let dir = dir.to_path_buf();
+    let what = format!("looking for an index in {}", dir.display());
+    network_fs::with_timeout(&what, network_fs::LISTING_TIMEOUT, move || {
+        // One directory read rather than a stat per candidate name, which on network shares
+        // costs a round trip each.
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(None);
+        };
+        let mut names = entries
+            .filter_map(|e| e.ok()?.file_name().into_string().ok())
+            .collect::<Vec<_>>();
+        names.sort();
+        Ok(litdata_index_name(&names).map(|n| dir.join(n)))
+    })
+}
+
+/// WebDataset shards directly inside `dir`, sorted by path.
+pub fn wds_shards_in_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut shards: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
         .filter_map(|e| e.ok().map(|e2| e2.path()))
         .filter(|p| {
             p.file_name()
                 .and_then(|f| f.to_str())
-                .map(|name| name.ends_with(".index.json") || name.contains(".index.json."))
+                .map(looks_like_wds_shard)
                 .unwrap_or(false)
         })
         .collect();
-    globbed.sort();
-    globbed.into_iter().next()
+    shards.sort();
+    shards
+}
+
+/// Field names (member extensions) seen in the first `max_samples` samples of a shard,
+/// with the number of samples each one appears in.
+pub fn wds_shard_field_counts(
+    shard_path: &Path,
+    max_samples: u32,
+) -> AppResult<(BTreeMap<String, u32>, u32)> {
+    let mut state = ShardScanState::new(shard_path.to_path_buf())?;
+    state.ensure_scanned(max_samples, false)?;
+    let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+    let scanned = state.samples.len().min(max_samples as usize);
+    for sample in state.samples.iter().take(scanned) {
+        let names: BTreeSet<&str> = sample.fields.iter().map(|f| f.name.as_str()).collect();
+        for name in names {
+            *counts.entry(name.to_string()).or_insert(0) += 1;
+        }
+    }
+    Ok((counts, scanned as u32))
 }
 
 fn has_wds_shards_in_dir(dir: &Path) -> bool {
@@ -571,37 +817,80 @@ fn has_wds_shards_in_dir(dir: &Path) -> bool {
         })
 }
 
-fn looks_like_wds_shard(filename: &str) -> bool {
+/// Split archives are listed by their first volume, which stands for the combined shard.
+pub fn looks_like_wds_shard(filename: &str) -> bool {
     let name = filename.to_lowercase();
     name.ends_with(".tar")
         || name.ends_with(".tar.gz")
         || name.ends_with(".tgz")
         || name.ends_with(".tar.zst")
         || name.ends_with(".tar.zstd")
+        || name.ends_with(".tar.lz4")
+        || multipart::is_first_volume(&name)
 }
 
-fn looks_like_mds_shard(filename: &str) -> bool {
+fn shard_bytes(path: &Path) -> u64 {
+    let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    if multipart::is_first_volume(name) {
+        return multipart::local_volume_paths(path)
+            .iter()
+            .filter_map(|p| p.metadata().ok())
+            .map(|m| m.len())
+            .sum();
+    }
+    path.metadata().map(|m| m.len()).unwrap_or(0)
+}
+
+/// Size and link target of a shard. Symlinked shards are followed, so `bytes` is the target's.
+fn shard_summary(path: &Path) -> WdsShardSummary {
+    let (link_target, link_error) = match symlinks::resolve_link(path) {
+        Ok(target) => (target.map(|t| t.display().to_string()), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    WdsShardSummary {
+        filename: path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        path: path.display().to_string(),
+        bytes: shard_bytes(path),
+        exists: link_error.is_none() && path.exists(),
+        link_target,
+        link_error,
+    }
+}
+
+pub fn looks_like_mds_shard(filename: &str) -> bool {
     let name = filename.to_lowercase();
     name.ends_with(".mds") || name.ends_with(".mds.zst") || name.ends_with(".mds.zstd")
 }
 
 #[tauri::command]
 pub async fn wds_load_dir(dir_path: String) -> AppResult<WdsDirSummary> {
-    spawn_blocking(move || wds_load_dir_sync(PathBuf::from(dir_path)))
-        .await
-        .map_err(|e| AppError::Task(e.to_string()))?
+    spawn_blocking(move || {
+        let summary = wds_load_dir_sync(PathBuf::from(dir_path))?;
+        // Later member reads name the dataset by `dir_path` of the summary. Profile file errors
+        // are reported by `get_dataset_profile`, not here.
+        let _ = dataset_profile::load(&summary.dir_path);
+        Ok(summary)
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn wds_load_dir_sync(dir_path: PathBuf) -> AppResult<WdsDirSummary> {
+pub fn wds_load_dir_sync(dir_path: PathBuf) -> AppResult<WdsDirSummary> {
     let (dir, shards) = resolve_shard_dir_and_list(&dir_path)?;
+    let resolved = symlinks::resolve_link(&dir)?;
     Ok(WdsDirSummary {
         dir_path: dir.display().to_string(),
+        resolved_dir_path: resolved.map(|p| p.display().to_string()),
         shards,
     })
 }
 
 #[tauri::command]
 pub async fn wds_list_samples(
+    app: AppHandle,
     dir_path: String,
     shard_filename: String,
     offset: Option<u32>,
@@ -610,8 +899,10 @@ pub async fn wds_list_samples(
     cache: tauri::State<'_, WdsScanCache>,
 ) -> AppResult<WdsSampleListResponse> {
     let cache_handle = (*cache).clone();
-    spawn_blocking(move || {
+    let reset = cache_handle.resetter(&dir_path, &shard_filename);
+    supervisor::run_blocking("the shard scan", reset, move || {
         wds_list_samples_sync(
+            &app,
             PathBuf::from(dir_path),
             shard_filename,
             offset,
@@ -621,10 +912,10 @@ pub async fn wds_list_samples(
         )
     })
     .await
-    .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn wds_list_samples_sync(
+pub fn wds_list_samples_sync(
+    app: &AppHandle,
     dir_path: PathBuf,
     shard_filename: String,
     offset: Option<u32>,
@@ -632,7 +923,7 @@ fn wds_list_samples_sync(
     compute_total: Option<bool>,
     cache: &WdsScanCache,
 ) -> AppResult<WdsSampleListResponse> {
-    let (dir, _) = resolve_shard_dir_and_list(&dir_path)?;
+    let dir = shard_dir(&dir_path)?;
     let shard_filename = shard_filename.trim().to_string();
     if shard_filename.is_empty() {
         return Err(AppError::Invalid("shard filename is empty".into()));
@@ -662,10 +953,8 @@ fn wds_list_samples_sync(
     let length = length.unwrap_or(200).max(1).min(MAX_LISTED_SAMPLES as u32);
     let compute_total = compute_total.unwrap_or(false);
 
-    let state = cache.get_or_create(&shard_path)?;
-    let mut guard = state
-        .lock()
-        .map_err(|_| AppError::Task("wds shard scan lock poisoned".into()))?;
+    let state = cache.get_or_create(app, &shard_path)?;
+    let mut guard = scan_cache::lock_state(&state)?;
     if guard.shard_path != shard_path {
         return Err(AppError::Task("wds shard scan cache mismatch".into()));
     }
@@ -694,212 +983,1490 @@ fn wds_list_samples_sync(
     })
 }
 
-#[tauri::command]
-pub async fn wds_peek_member(
-    dir_path: String,
-    shard_filename: String,
-    member_path: String,
-) -> AppResult<FieldPreview> {
-    spawn_blocking(move || {
-        wds_peek_member_sync(PathBuf::from(dir_path), shard_filename, member_path)
-    })
-    .await
-    .map_err(|e| AppError::Task(e.to_string()))?
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsLintIssue {
+    pub kind: String,
+    pub key: String,
+    pub member_path: Option<String>,
+    pub message: String,
 }
 
-fn wds_peek_member_sync(
-    dir_path: PathBuf,
-    shard_filename: String,
-    member_path: String,
-) -> AppResult<FieldPreview> {
-    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
-    let member_path = member_path.trim().to_string();
-    if member_path.is_empty() {
-        return Err(AppError::Invalid("member path is empty".into()));
-    }
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsLintReport {
+    pub shard_path: String,
+    pub members_scanned: u64,
+    pub samples_scanned: u64,
+    pub complete: bool,
+    /// Extensions present in at least half of the scanned samples; samples lacking one are flagged.
+    pub expected_fields: Vec<String>,
+    pub issue_counts: BTreeMap<String, u64>,
+    pub issues: Vec<WdsLintIssue>,
+}
 
-    let reader = open_shard_reader(&shard_path)?;
-    let mut archive = tar::Archive::new(reader);
-    let normalized = normalize_member_path_str(&member_path);
+struct LintCollector {
+    counts: BTreeMap<String, u64>,
+    issues: Vec<WdsLintIssue>,
+}
 
-    for entry in archive.entries()? {
-        let entry = entry?;
-        if entry.header().entry_type().is_dir() {
-            continue;
-        }
-        let current = normalize_member_path(&entry.path()?);
-        if current != normalized {
-            continue;
+impl LintCollector {
+    fn push(&mut self, kind: &str, key: &str, member_path: Option<&str>, message: String) {
+        *self.counts.entry(kind.to_string()).or_insert(0) += 1;
+        if self.issues.len() < MAX_LINT_ISSUES {
+            self.issues.push(WdsLintIssue {
+                kind: kind.to_string(),
+                key: key.to_string(),
+                member_path: member_path.map(|p| p.to_string()),
+                message,
+            });
         }
-        let size = entry.size();
-        let mut buf = Vec::new();
-        entry.take(PREVIEW_BYTES as u64).read_to_end(&mut buf)?;
-
-        let preview_text = preview_utf8_text(&buf);
-        let is_binary = preview_text.is_none();
-        let guessed_ext = guess_ext_from_member(&normalized, &buf);
-        let hex_snippet = hex_encode(buf.iter().take(48).copied().collect::<Vec<u8>>());
-        return Ok(FieldPreview {
-            preview_text,
-            hex_snippet,
-            guessed_ext,
-            is_binary,
-            size: size.min(u32::MAX as u64) as u32,
-        });
     }
+}
 
-    Err(AppError::Missing(format!(
-        "member not found in shard: {member_path}"
-    )))
+fn non_normalized_reason(raw_path: &str, normalized: &str) -> Option<&'static str> {
+    if raw_path.contains('\\') {
+        return Some("uses backslash separators");
+    }
+    if raw_path.starts_with('/') {
+        return Some("is an absolute path");
+    }
+    if raw_path.split('/').any(|seg| seg == "..") {
+        return Some("contains a parent-directory segment");
+    }
+    if normalized
+        .split('/')
+        .any(|seg| seg.is_empty() || seg == ".")
+    {
+        return Some("contains empty or '.' segments");
+    }
+    if raw_path != raw_path.trim() {
+        return Some("has leading or trailing whitespace");
+    }
+    let base = normalized.rsplit('/').next().unwrap_or(normalized);
+    if let Some((_, ext)) = base.split_once('.') {
+        if ext.chars().any(|c| c.is_ascii_uppercase()) {
+            return Some("has an upper-case extension");
+        }
+    }
+    None
 }
 
 #[tauri::command]
-pub async fn wds_open_member(
+pub async fn wds_lint(
     dir_path: String,
     shard_filename: String,
-    member_path: String,
-    opener_app_path: Option<String>,
-) -> AppResult<OpenLeafResponse> {
-    spawn_blocking(move || {
-        wds_open_member_sync(
-            PathBuf::from(dir_path),
-            shard_filename,
-            member_path,
-            opener_app_path.as_deref(),
-        )
-    })
-    .await
-    .map_err(|e| AppError::Task(e.to_string()))?
+    max_members: Option<u64>,
+) -> AppResult<WdsLintReport> {
+    spawn_blocking(move || wds_lint_sync(PathBuf::from(dir_path), shard_filename, max_members))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn wds_open_member_sync(
+pub fn wds_lint_sync(
     dir_path: PathBuf,
     shard_filename: String,
-    member_path: String,
-    opener_app_path: Option<&str>,
-) -> AppResult<OpenLeafResponse> {
+    max_members: Option<u64>,
+) -> AppResult<WdsLintReport> {
     let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
-    let member_path = member_path.trim().to_string();
-    if member_path.is_empty() {
-        return Err(AppError::Invalid("member path is empty".into()));
-    }
-    let normalized = normalize_member_path_str(&member_path);
-    let (data, size) = read_member_bytes(&shard_path, &normalized, None)?;
-    if size > MAX_OPEN_BYTES {
-        return Err(AppError::Invalid(format!(
-            "member too large to open ({} bytes)",
-            size
-        )));
-    }
-    let guessed_ext = guess_ext_from_member(&normalized, &data).unwrap_or_else(|| "bin".into());
+    let mut tar = TarStream::new(open_shard_reader(&shard_path)?);
+    let mut lint = LintCollector {
+        counts: BTreeMap::new(),
+        issues: Vec::new(),
+    };
 
-    let temp_dir = std::env::temp_dir().join("dataset-inspector");
-    fs::create_dir_all(&temp_dir)?;
-    let base_name = format!("{}-{}", sanitize(&shard_filename), sanitize(&normalized));
-    let mut out = temp_dir.join(format!("{base_name}.{guessed_ext}"));
-    fs::write(&out, &data)?;
+    let mut finished_keys: HashMap<String, u64> = HashMap::new();
+    let mut samples: Vec<(String, BTreeSet<String>)> = Vec::new();
+    let mut current: Option<(String, BTreeSet<String>)> = None;
+    let mut members_scanned = 0u64;
+    let mut complete = true;
 
-    // Default `.sph` support: decode to a WAV and open that.
-    let mut ext = guessed_ext;
-    if ext == "sph" {
-        let wav_out = temp_dir.join(format!("{base_name}.wav"));
-        match audio::write_sph_as_wav_with_fallback(&data, &out, &wav_out) {
-            Ok(()) => {
-                out = wav_out;
-                ext = "wav".into();
+    loop {
+        if max_members.is_some_and(|max| members_scanned >= max) {
+            complete = false;
+            break;
+        }
+        let next = tar
+            .next_file()
+            .map_err(|e| AppError::Task(format!("wds tar scan failed: {e}")))?;
+        let Some(next) = next else {
+            break;
+        };
+        members_scanned += 1;
+        let (key, field_name) = split_sample_key(&next.path);
+
+        if current.as_ref().map(|(k, _)| k.as_str()) != Some(key.as_str()) {
+            if let Some(done) = current.take() {
+                finished_keys.insert(done.0.clone(), samples.len() as u64);
+                samples.push(done);
             }
-            Err(err) => {
-                let base = format!("{} ({} bytes)", out.display(), size);
-                return Ok(OpenLeafResponse {
-                    path: out.display().to_string(),
-                    size: size.min(u32::MAX as u64) as u32,
-                    ext,
-                    opened: false,
-                    needs_opener: true,
-                    message: format!(
-                        "{base} · sph decode failed: {err} · choose an app to open it"
+            if let Some(prev_sample) = finished_keys.get(&key) {
+                lint.push(
+                    "non-contiguous",
+                    &key,
+                    Some(&next.path),
+                    format!(
+                        "members of this key also appear in sample {prev_sample}; readers will split it into separate samples"
                     ),
-                });
+                );
             }
+            current = Some((key.clone(), BTreeSet::new()));
         }
-    }
 
-    let mut opened = false;
-    let mut open_error = None::<String>;
-    if let Some(app_path) = opener_app_path {
-        match open_with::open_with_app_detached(&out, app_path) {
-            Ok(()) => opened = true,
-            Err(err) => open_error = Some(err),
+        if let Some((_, fields)) = current.as_mut() {
+            if !fields.insert(field_name.clone()) {
+                lint.push(
+                    "duplicate-member",
+                    &key,
+                    Some(&next.path),
+                    format!("field '{field_name}' appears more than once in this sample"),
+                );
+            }
         }
-    } else {
-        match open::that_detached(&out) {
-            Ok(()) => opened = true,
-            Err(err) => open_error = Some(err.to_string()),
+        if next.size == 0 {
+            lint.push(
+                "zero-byte",
+                &key,
+                Some(&next.path),
+                "member is empty".into(),
+            );
+        }
+        if let Some(reason) = non_normalized_reason(&next.raw_path, &next.path) {
+            lint.push(
+                "non-normalized-path",
+                &key,
+                Some(&next.raw_path),
+                format!("member path {reason}"),
+            );
         }
     }
+    // When `max_members` cut the scan short, the last sample's remaining members are unread;
+    // counting it would report companions it does have as missing.
+    if let Some(done) = current.take().filter(|_| complete) {
+        samples.push(done);
+    }
 
-    let needs_opener = !opened;
-    let message = if opened {
-        format!("Opened {} ({})", out.display(), size)
-    } else {
-        let detail = open_error.unwrap_or_else(|| "unknown error".into());
-        format!("Could not open {} · {detail}", out.display())
-    };
+    let mut field_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for (_, fields) in &samples {
+        for field in fields {
+            *field_counts.entry(field.as_str()).or_insert(0) += 1;
+        }
+    }
+    let expected_fields: Vec<String> = field_counts
+        .iter()
+        .filter(|(_, count)| **count * 2 >= samples.len())
+        .map(|(name, _)| name.to_string())
+        .collect();
+    for (key, fields) in &samples {
+        let missing: Vec<&str> = expected_fields
+            .iter()
+            .filter(|f| !fields.contains(*f))
+            .map(|f| f.as_str())
+            .collect();
+        if !missing.is_empty() {
+            lint.push(
+                "missing-companion",
+                key,
+                None,
+                format!("sample is missing .{}", missing.join(", .")),
+            );
+        }
+    }
 
-    Ok(OpenLeafResponse {
-        path: out.display().to_string(),
-        size: size.min(u32::MAX as u64) as u32,
-        ext,
-        opened,
-        needs_opener,
-        message,
+    Ok(WdsLintReport {
+        shard_path: shard_path.display().to_string(),
+        members_scanned,
+        samples_scanned: samples.len() as u64,
+        complete,
+        expected_fields,
+        issue_counts: lint.counts,
+        issues: lint.issues,
     })
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsImageFlag {
+    pub kind: String,
+    pub key: String,
+    pub member_path: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsImageFlagReport {
+    pub shard_path: String,
+    pub images_scanned: u64,
+    /// Images over the thumbnail source size limit, left unchecked.
+    pub images_skipped: u64,
+    pub complete: bool,
+    pub flag_counts: BTreeMap<String, u64>,
+    /// Review list, in shard order.
+    pub flags: Vec<WdsImageFlag>,
+}
+
+/// Flags blank (all-black, all-white, single-color), tiny, extremely elongated, and undecodable
+/// images in a shard for manual review.
 #[tauri::command]
-pub async fn wds_prepare_audio_preview(
+pub async fn wds_image_flags(
     dir_path: String,
     shard_filename: String,
-    member_path: String,
-) -> AppResult<PreparedFileResponse> {
+    max_images: Option<u64>,
+    min_edge: Option<u32>,
+    max_aspect_ratio: Option<f64>,
+) -> AppResult<WdsImageFlagReport> {
+    let thresholds = image_flags::Thresholds {
+        min_edge: min_edge.unwrap_or(image_flags::DEFAULT_MIN_EDGE),
+        max_aspect_ratio: max_aspect_ratio
+            .filter(|r| *r >= 1.0)
+            .unwrap_or(image_flags::DEFAULT_MAX_ASPECT_RATIO),
+    };
     spawn_blocking(move || {
-        wds_prepare_audio_preview_sync(PathBuf::from(dir_path), shard_filename, member_path)
+        wds_image_flags_sync(
+            PathBuf::from(dir_path),
+            shard_filename,
+            max_images,
+            &thresholds,
+        )
     })
     .await
     .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-fn wds_prepare_audio_preview_sync(
+fn wds_image_flags_sync(
     dir_path: PathBuf,
     shard_filename: String,
-    member_path: String,
-) -> AppResult<PreparedFileResponse> {
+    max_images: Option<u64>,
+    thresholds: &image_flags::Thresholds,
+) -> AppResult<WdsImageFlagReport> {
     let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
-    let member_path = member_path.trim().to_string();
-    if member_path.is_empty() {
-        return Err(AppError::Invalid("member path is empty".into()));
+    let reader = open_shard_reader(&shard_path)?;
+    let mut archive = tar::Archive::new(reader);
+    let mut report = WdsImageFlagReport {
+        shard_path: shard_path.display().to_string(),
+        images_scanned: 0,
+        images_skipped: 0,
+        complete: true,
+        flag_counts: BTreeMap::new(),
+        flags: Vec::new(),
+    };
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let member_path = normalize_member_path(&entry.path()?);
+        if !image_flags::is_image_name(&member_path) {
+            continue;
+        }
+        if max_images.is_some_and(|max| report.images_scanned >= max) {
+            report.complete = false;
+            break;
+        }
+        if entry.size() > thumbnails::MAX_THUMBNAIL_SOURCE_BYTES {
+            report.images_skipped += 1;
+            continue;
+        }
+        let mut bytes = Vec::new();
+        entry.take(MAX_OPEN_BYTES).read_to_end(&mut bytes)?;
+        report.images_scanned += 1;
+
+        let (dims, findings) = image_flags::inspect(&bytes, thresholds);
+        let (key, _) = split_sample_key(&member_path);
+        for finding in findings {
+            *report
+                .flag_counts
+                .entry(finding.kind.to_string())
+                .or_insert(0) += 1;
+            if report.flags.len() < MAX_LINT_ISSUES {
+                report.flags.push(WdsImageFlag {
+                    kind: finding.kind.to_string(),
+                    key: key.clone(),
+                    member_path: member_path.clone(),
+                    width: dims.map(|d| d.0),
+                    height: dims.map(|d| d.1),
+                    message: finding.message,
+                });
+            }
+        }
     }
-    let normalized = normalize_member_path_str(&member_path);
-    let (data, size) = read_member_bytes(&shard_path, &normalized, None)?;
-    if size > MAX_OPEN_BYTES {
-        return Err(AppError::Invalid(format!(
-            "member too large to preview ({} bytes)",
-            size
-        )));
+    Ok(report)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsLayoutMember {
+    pub member_path: String,
+    /// Offset of the first header block, including any long-name or PAX headers.
+    pub header_offset: u64,
+    pub data_offset: u64,
+    pub size: u64,
+    /// End of the member's data after padding to the 512-byte block boundary.
+    pub end_offset: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsLayoutSample {
+    pub key: String,
+    pub start_offset: u64,
+    pub end_offset: u64,
+    pub members: Vec<WdsLayoutMember>,
+}
+
+/// Byte-offset map of a shard's decompressed tar stream. Samples are consecutive runs of members
+/// sharing a key, so a key split across the shard shows up more than once.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsShardLayout {
+    pub shard_path: String,
+    /// Size of the shard file on disk (the first volume for multi-part shards).
+    pub file_size: u64,
+    /// Length of the decompressed tar stream, including the end-of-archive blocks and any
+    /// trailing record padding. Only known when the whole shard was mapped.
+    pub archive_size: Option<u64>,
+    /// Where the last listed member ends.
+    pub members_end: u64,
+    pub header_bytes: u64,
+    pub data_bytes: u64,
+    pub padding_bytes: u64,
+    pub member_count: usize,
+    pub complete: bool,
+    pub samples: Vec<WdsLayoutSample>,
+}
+
+/// Maps where every sample and member of a shard sits in its tar stream, for a layout strip.
+#[tauri::command]
+pub async fn wds_shard_layout(
+    dir_path: String,
+    shard_filename: String,
+) -> AppResult<WdsShardLayout> {
+    spawn_blocking(move || wds_shard_layout_sync(PathBuf::from(dir_path), shard_filename))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn wds_shard_layout_sync(dir_path: PathBuf, shard_filename: String) -> AppResult<WdsShardLayout> {
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    let mut tar = TarStream::new(open_shard_reader(&shard_path)?);
+    let mut layout = WdsShardLayout {
+        shard_path: shard_path.display().to_string(),
+        file_size: fs::metadata(&shard_path)?.len(),
+        archive_size: None,
+        members_end: 0,
+        header_bytes: 0,
+        data_bytes: 0,
+        padding_bytes: 0,
+        member_count: 0,
+        complete: true,
+        samples: Vec::new(),
+    };
+    loop {
+        let next = tar
+            .next_file()
+            .map_err(|e| AppError::Task(format!("wds tar scan failed: {e}")))?;
+        let Some(next) = next else {
+            break;
+        };
+        if layout.member_count >= MAX_LAYOUT_MEMBERS {
+            layout.complete = false;
+            break;
+        }
+        let end_offset = tar.offset;
+        let data_offset = end_offset - padded_tar_size(next.size);
+        layout.header_bytes += data_offset - next.header_offset;
+        layout.data_bytes += next.size;
+        layout.padding_bytes += padded_tar_size(next.size) - next.size;
+        layout.members_end = end_offset;
+        layout.member_count += 1;
+
+        let (key, _) = split_sample_key(&next.path);
+        let member = WdsLayoutMember {
+            member_path: next.path,
+            header_offset: next.header_offset,
+            data_offset,
+            size: next.size,
+            end_offset,
+        };
+        match layout.samples.last_mut() {
+            Some(sample) if sample.key == key => {
+                sample.end_offset = end_offset;
+                sample.members.push(member);
+            }
+            _ => layout.samples.push(WdsLayoutSample {
+                key,
+                start_offset: member.header_offset,
+                end_offset,
+                members: vec![member],
+            }),
+        }
+    }
+    if layout.complete {
+        let trailing = io::copy(&mut tar.reader, &mut io::sink())?;
+        layout.archive_size = Some(tar.offset + trailing);
+    }
+    Ok(layout)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsPiiMember {
+    pub key: String,
+    pub member_path: String,
+    /// All matches in the member, including ones beyond the `matches` list.
+    pub match_count: usize,
+    pub matches: Vec<PiiMatch>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsPiiReport {
+    pub shard_path: String,
+    pub text_members_scanned: u64,
+    pub complete: bool,
+    pub match_counts: BTreeMap<String, u64>,
+    /// Text members with at least one match, in shard order.
+    pub members: Vec<WdsPiiMember>,
+}
+
+/// Scans the shard's text members for emails, phone numbers, card-like numbers, profanity, and
+/// the user's custom patterns.
+#[tauri::command]
+pub async fn wds_pii_scan(
+    dir_path: String,
+    shard_filename: String,
+    max_members: Option<u64>,
+) -> AppResult<WdsPiiReport> {
+    spawn_blocking(move || wds_pii_scan_sync(PathBuf::from(dir_path), shard_filename, max_members))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn wds_pii_scan_sync(
+    dir_path: PathBuf,
+    shard_filename: String,
+    max_members: Option<u64>,
+) -> AppResult<WdsPiiReport> {
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    let scanner = pii_scan::Scanner::new()?;
+    let reader = open_shard_reader(&shard_path)?;
+    let mut archive = tar::Archive::new(reader);
+    let mut report = WdsPiiReport {
+        shard_path: shard_path.display().to_string(),
+        text_members_scanned: 0,
+        complete: true,
+        match_counts: BTreeMap::new(),
+        members: Vec::new(),
+    };
+    let mut members_seen = 0u64;
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        if max_members.is_some_and(|max| members_seen >= max) {
+            report.complete = false;
+            break;
+        }
+        members_seen += 1;
+        let member_path = normalize_member_path(&entry.path()?);
+        let mut bytes = Vec::new();
+        entry.take(MAX_PII_SCAN_BYTES).read_to_end(&mut bytes)?;
+        // Anything that is not clean UTF-8 is treated as binary and skipped.
+        let Ok(text) = std::str::from_utf8(&bytes) else {
+            continue;
+        };
+        if text.contains('\0') {
+            continue;
+        }
+        report.text_members_scanned += 1;
+
+        let (matches, match_count) = scanner.scan(text);
+        if match_count == 0 {
+            continue;
+        }
+        for m in &matches {
+            *report.match_counts.entry(m.kind.clone()).or_insert(0) += 1;
+        }
+        if report.members.len() < MAX_LINT_ISSUES {
+            let (key, _) = split_sample_key(&member_path);
+            report.members.push(WdsPiiMember {
+                key,
+                member_path,
+                match_count,
+                matches,
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// 2D PCA or UMAP projection of a `.npy` float vector field over the first `max_samples`
+/// samples of a shard.
+#[tauri::command]
+pub async fn wds_embedding_projection(
+    dir_path: String,
+    shard_filename: String,
+    field: String,
+    max_samples: Option<usize>,
+    method: Option<ProjectionMethod>,
+) -> AppResult<EmbeddingProjection> {
+    let method = method.unwrap_or_default();
+    spawn_blocking(move || {
+        wds_embedding_projection_sync(
+            PathBuf::from(dir_path),
+            shard_filename,
+            &field,
+            max_samples
+                .unwrap_or(embeddings::DEFAULT_PROJECTION_SAMPLES)
+                .clamp(2, method.max_samples()),
+            method,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn wds_embedding_projection_sync(
+    dir_path: PathBuf,
+    shard_filename: String,
+    field: &str,
+    max_samples: usize,
+    method: ProjectionMethod,
+) -> AppResult<EmbeddingProjection> {
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    let field = field.trim().trim_start_matches('.').to_lowercase();
+    if field.is_empty() {
+        return Err(AppError::Invalid("missing field".into()));
+    }
+    let reader = open_shard_reader(&shard_path)?;
+    let mut archive = tar::Archive::new(reader);
+    let mut vectors = Vec::new();
+    let mut skipped = 0;
+    for entry in archive.entries()? {
+        if vectors.len() + skipped >= max_samples {
+            break;
+        }
+        let entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let (key, field_name) = split_sample_key(&normalize_member_path(&entry.path()?));
+        if field_name != field {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        entry.take(MAX_OPEN_BYTES).read_to_end(&mut bytes)?;
+        match embeddings::npy_values(&bytes) {
+            Some(values) => vectors.push((key, values)),
+            None => skipped += 1,
+        }
+    }
+    embeddings::project_2d(vectors, skipped, method)
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsSamplePageEvent {
+    pub request_id: String,
+    pub offset: u32,
+    pub samples: Vec<WdsSampleInfo>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsStreamSummary {
+    pub request_id: String,
+    pub emitted: u32,
+    pub num_samples_total: Option<u32>,
+    pub partial: bool,
+}
+
+/// Streams a shard listing as `wds://samples-page` events, one page per scan step, so the
+/// UI can render rows while the tar is still being read. Returns once `max_samples` rows
+/// have been emitted or the shard is exhausted.
+#[tauri::command]
+pub async fn wds_stream_samples(
+    app: AppHandle,
+    dir_path: String,
+    shard_filename: String,
+    request_id: String,
+    page_size: Option<u32>,
+    max_samples: Option<u32>,
+    cache: tauri::State<'_, WdsScanCache>,
+) -> AppResult<WdsStreamSummary> {
+    let cache_handle = (*cache).clone();
+    let reset = cache_handle.resetter(&dir_path, &shard_filename);
+    supervisor::run_blocking("the shard scan", reset, move || {
+        wds_stream_samples_sync(
+            &app,
+            PathBuf::from(dir_path),
+            shard_filename,
+            request_id,
+            page_size,
+            max_samples,
+            &cache_handle,
+        )
+    })
+    .await
+}
+
+fn wds_stream_samples_sync(
+    app: &AppHandle,
+    dir_path: PathBuf,
+    shard_filename: String,
+    request_id: String,
+    page_size: Option<u32>,
+    max_samples: Option<u32>,
+    cache: &WdsScanCache,
+) -> AppResult<WdsStreamSummary> {
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    let page_size = page_size.unwrap_or(200).clamp(1, MAX_LISTED_SAMPLES as u32);
+    let max_samples = max_samples.unwrap_or(u32::MAX);
+
+    let state = cache.get_or_create(app, &shard_path)?;
+    let mut emitted = 0u32;
+    loop {
+        // Lock per page so other commands on the same shard can interleave with the stream.
+        let (page, done, total) = {
+            let mut guard = scan_cache::lock_state(&state)?;
+            let target = emitted.saturating_add(page_size).min(max_samples);
+            guard.ensure_scanned(target, false)?;
+            let start = (emitted as usize).min(guard.samples.len());
+            let end = (target as usize).min(guard.samples.len());
+            let total = guard.done.then_some(guard.current_sample_index);
+            (guard.samples[start..end].to_vec(), guard.done, total)
+        };
+        let count = page.len() as u32;
+        if count > 0 {
+            let _ = app.emit(
+                "wds://samples-page",
+                WdsSamplePageEvent {
+                    request_id: request_id.clone(),
+                    offset: emitted,
+                    samples: page,
+                },
+            );
+            emitted += count;
+        }
+        if emitted >= max_samples || (done && count == 0) || (done && total == Some(emitted)) {
+            return Ok(WdsStreamSummary {
+                request_id,
+                emitted,
+                num_samples_total: total,
+                partial: !done,
+            });
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn wds_peek_member(
+    app: AppHandle,
+    dir_path: String,
+    shard_filename: String,
+    member_path: String,
+    offset: Option<u64>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
+    cache: tauri::State<'_, WdsScanCache>,
+) -> AppResult<FieldPreview> {
+    let cache_handle = (*cache).clone();
+    let limits = PreviewLimits::new(text_chars, hex_bytes);
+    spawn_blocking(move || {
+        wds_peek_member_sync(
+            &app,
+            PathBuf::from(dir_path),
+            shard_filename,
+            member_path,
+            offset.unwrap_or(0),
+            limits,
+            &cache_handle,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+/// Previews `offset..offset + PREVIEW_BYTES` of a member, or a longer window when `limits` ask for
+/// more text. Only head previews (offset 0) are cached and trigger prefetching.
+pub fn wds_peek_member_sync(
+    app: &AppHandle,
+    dir_path: PathBuf,
+    shard_filename: String,
+    member_path: String,
+    offset: u64,
+    limits: PreviewLimits,
+    cache: &WdsScanCache,
+) -> AppResult<FieldPreview> {
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    let member_path = member_path.trim().to_string();
+    if member_path.is_empty() {
+        return Err(AppError::Invalid("member path is empty".into()));
+    }
+    let normalized = normalize_member_path_str(&member_path);
+    let window = limits.window(PREVIEW_BYTES);
+    if offset > 0 {
+        let (buf, size) = read_member_bytes(&shard_path, &member_path, offset, Some(window))?;
+        return Ok(field_preview_from_bytes(
+            &dir_path,
+            &normalized,
+            &buf,
+            size,
+            limits,
+        ));
+    }
+
+    let state = cache.get_or_create(app, &shard_path)?;
+    let cached = {
+        let guard = scan_cache::lock_state(&state)?;
+        guard
+            .previews
+            .get(&normalized)
+            // A head cached for a shorter window cannot fill a longer request.
+            .filter(|p| p.data.len() as u64 >= p.size.min(window as u64))
+            .map(|p| field_preview_from_bytes(&dir_path, &normalized, &p.data, p.size, limits))
+    };
+    let preview = match cached {
+        Some(preview) => preview,
+        None => {
+            let (buf, size) = read_member_bytes(&shard_path, &member_path, 0, Some(window))?;
+            let preview = field_preview_from_bytes(&dir_path, &normalized, &buf, size, limits);
+            if let Ok(mut guard) = state.lock() {
+                guard.cache_preview(normalized.clone(), buf, size);
+            }
+            preview
+        }
+    };
+
+    let depth = settings::read_setting(app, PREFETCH_DEPTH_KEY)
+        .ok()
+        .flatten()
+        .unwrap_or(DEFAULT_PREFETCH_DEPTH)
+        .min(MAX_PREFETCH_DEPTH);
+    if depth > 0 {
+        spawn_prefetch(state, normalized, depth);
+    }
+    Ok(preview)
+}
+
+fn field_preview_from_bytes(
+    dir_path: &Path,
+    normalized: &str,
+    buf: &[u8],
+    size: u64,
+    limits: PreviewLimits,
+) -> FieldPreview {
+    // `transcript.json.gz` previews as the JSON inside it.
+    let unwrapped = decompress::unwrap(normalized, buf, limits.window(PREVIEW_BYTES));
+    let (name, data) = match &unwrapped {
+        Some(inner) => (inner.inner_name.as_str(), inner.data.as_slice()),
+        None => (normalized, buf),
+    };
+    let (preview_text, text_length) = limits.text(data).unzip();
+    let is_binary = preview_text.is_none();
+    let guessed_ext = guess_ext_from_member(name, data);
+    let mut preview = FieldPreview {
+        preview_text,
+        text_length,
+        hex_snippet: limits.hex(data),
+        guessed_ext,
+        is_binary,
+        size,
+        metadata: metadata::extract(data),
+        plugin: None,
+        kind: PreviewKind::Binary,
+        compression: unwrapped.as_ref().map(|inner| inner.codec),
+    };
+    if let Some(rule) = decode_override(dir_path, name) {
+        rule.apply_to_preview(data, &mut preview);
+    }
+    decoder_plugins::apply(data, &mut preview);
+    preview.kind = PreviewKind::detect(data, &preview);
+    preview
+}
+
+/// The user's decode override for a member, keyed by field suffix or extension.
+fn decode_override(dir_path: &Path, normalized: &str) -> Option<DecodeOverride> {
+    decode_profile::lookup(
+        &dir_path.to_string_lossy(),
+        &decode_profile::name_keys(normalized),
+    )
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsFieldPreview {
+    name: String,
+    member_path: String,
+    preview: FieldPreview,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsSamplePreview {
+    sample_index: u32,
+    key: String,
+    fields: Vec<WdsFieldPreview>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsPeekPageResponse {
+    offset: u32,
+    num_samples_total: Option<u32>,
+    partial: bool,
+    samples: Vec<WdsSamplePreview>,
+}
+
+/// Head previews of every member of `offset..offset + length`. Previews that are not cached yet
+/// are read in one pass over the shard and cached for later peeks.
+#[tauri::command]
+pub async fn wds_peek_page(
+    app: AppHandle,
+    dir_path: String,
+    shard_filename: String,
+    offset: Option<u32>,
+    length: Option<u32>,
+    cache: tauri::State<'_, WdsScanCache>,
+) -> AppResult<WdsPeekPageResponse> {
+    let cache_handle = (*cache).clone();
+    spawn_blocking(move || {
+        wds_peek_page_sync(
+            &app,
+            PathBuf::from(dir_path),
+            shard_filename,
+            offset.unwrap_or(0),
+            length.unwrap_or(PEEK_PAGE_DEFAULT_SAMPLES),
+            &cache_handle,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn wds_peek_page_sync(
+    app: &AppHandle,
+    dir_path: PathBuf,
+    shard_filename: String,
+    offset: u32,
+    length: u32,
+    cache: &WdsScanCache,
+) -> AppResult<WdsPeekPageResponse> {
+    let page = wds_list_samples_sync(
+        app,
+        dir_path.clone(),
+        shard_filename.clone(),
+        Some(offset),
+        Some(length.clamp(1, PEEK_PAGE_MAX_SAMPLES)),
+        Some(false),
+        cache,
+    )?;
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    let state = cache.get_or_create(app, &shard_path)?;
+
+    let mut previews = HashMap::new();
+    let mut missing = BTreeSet::new();
+    {
+        let guard = scan_cache::lock_state(&state)?;
+        for field in page.samples.iter().flat_map(|s| &s.fields) {
+            let normalized = normalize_member_path_str(&field.member_path);
+            match guard.previews.get(&normalized) {
+                Some(p) => {
+                    let preview = field_preview_from_bytes(
+                        &dir_path,
+                        &normalized,
+                        &p.data,
+                        p.size,
+                        PreviewLimits::default(),
+                    );
+                    previews.insert(normalized, preview);
+                }
+                None => {
+                    missing.insert(normalized);
+                }
+            }
+        }
+    }
+    if !missing.is_empty() {
+        let fetched = read_members_bytes(&shard_path, missing, Some(PREVIEW_BYTES))?;
+        let mut guard = state.lock().ok();
+        for (path, data, size) in fetched {
+            let preview =
+                field_preview_from_bytes(&dir_path, &path, &data, size, PreviewLimits::default());
+            previews.insert(path.clone(), preview);
+            if let Some(guard) = guard.as_mut() {
+                guard.cache_preview(path, data, size);
+            }
+        }
+    }
+
+    let samples = page
+        .samples
+        .into_iter()
+        .map(|sample| WdsSamplePreview {
+            sample_index: sample.sample_index,
+            key: sample.key,
+            fields: sample
+                .fields
+                .into_iter()
+                .filter_map(|field| {
+                    let preview =
+                        previews.remove(&normalize_member_path_str(&field.member_path))?;
+                    Some(WdsFieldPreview {
+                        name: field.name,
+                        member_path: field.member_path,
+                        preview,
+                    })
+                })
+                .collect(),
+        })
+        .collect();
+    Ok(WdsPeekPageResponse {
+        offset,
+        num_samples_total: page.num_samples_total,
+        partial: page.partial,
+        samples,
+    })
+}
+
+/// Warms the preview cache with the samples after `member_path` in a single pass over the shard,
+/// so paging forward serves peeks from memory. At most one prefetch runs per shard.
+fn spawn_prefetch(state: Arc<ScanSlot<ShardScanState>>, member_path: String, depth: u32) {
+    spawn_blocking(move || {
+        let (shard_path, targets) = {
+            let Ok(mut guard) = state.lock() else {
+                return;
+            };
+            if guard.prefetch_in_flight || guard.preview_bytes >= MAX_CACHED_PREVIEW_BYTES {
+                return;
+            }
+            let Ok(targets) = guard.prefetch_targets(&member_path, depth) else {
+                return;
+            };
+            if targets.is_empty() {
+                return;
+            }
+            guard.prefetch_in_flight = true;
+            (guard.shard_path.clone(), targets)
+        };
+
+        let fetched = read_members_bytes(
+            &shard_path,
+            targets.into_iter().collect(),
+            Some(PREVIEW_BYTES),
+        );
+        if let Ok(mut guard) = state.lock() {
+            guard.prefetch_in_flight = false;
+            for (path, data, size) in fetched.unwrap_or_default() {
+                guard.cache_preview(path, data, size);
+            }
+        }
+    });
+}
+
+/// Reads up to `limit` bytes (whole members, capped at `MAX_OPEN_BYTES`, when `None`) of every
+/// wanted member in one pass over the shard, stopping once all were found. Each result carries the
+/// member's full size.
+fn read_members_bytes(
+    shard_path: &Path,
+    mut wanted: BTreeSet<String>,
+    limit: Option<usize>,
+) -> AppResult<Vec<(String, Vec<u8>, u64)>> {
+    let limit = limit.map_or(MAX_OPEN_BYTES, |l| l as u64);
+    let mut out = Vec::new();
+    let members = wanted.iter().map(String::as_str).collect::<Vec<_>>();
+    let reader = open_shard_reader_near(shard_path, &members)?;
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        if wanted.is_empty() {
+            break;
+        }
+        let entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let current = normalize_member_path(&entry.path()?);
+        if !wanted.remove(&current) {
+            continue;
+        }
+        let size = entry.size();
+        let mut buf = Vec::new();
+        entry.take(limit).read_to_end(&mut buf)?;
+        out.push((current, buf, size));
+    }
+    Ok(out)
+}
+
+#[tauri::command]
+pub async fn wds_thumbnails(
+    dir_path: String,
+    shard_filename: String,
+    member_paths: Vec<String>,
+    max_edge: Option<u32>,
+) -> AppResult<Vec<Thumbnail>> {
+    spawn_blocking(move || {
+        wds_thumbnails_sync(
+            PathBuf::from(dir_path),
+            shard_filename,
+            member_paths,
+            max_edge.unwrap_or(thumbnails::DEFAULT_THUMBNAIL_EDGE),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn wds_thumbnails_sync(
+    dir_path: PathBuf,
+    shard_filename: String,
+    member_paths: Vec<String>,
+    max_edge: u32,
+) -> AppResult<Vec<Thumbnail>> {
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    if member_paths.len() > MAX_THUMBNAILS_PER_REQUEST {
+        return Err(AppError::Invalid(format!(
+            "too many thumbnails requested at once (max {MAX_THUMBNAILS_PER_REQUEST})"
+        )));
+    }
+    // Repeated paths are read once, under their first index, and copied to the rest below.
+    let mut wanted = HashMap::new();
+    let first_index = member_paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            *wanted
+                .entry(normalize_member_path_str(path.trim()))
+                .or_insert(index)
+        })
+        .collect::<Vec<_>>();
+
+    // One pass over the shard feeds a bounded queue; the decode pool drains it concurrently.
+    let (tx, rx) = std::sync::mpsc::sync_channel(thumbnails::THUMBNAIL_QUEUE_DEPTH);
+    let (rendered, read_result) = std::thread::scope(|scope| {
+        let reader = scope.spawn(move || send_thumbnail_sources(&shard_path, wanted, tx));
+        let rendered = thumbnails::render_all(rx, max_edge);
+        (rendered, reader.join())
+    });
+    read_result.map_err(|_| AppError::Task("thumbnail reader panicked".into()))??;
+
+    let mut slots = member_paths
+        .iter()
+        .map(|path| Thumbnail {
+            name: path.clone(),
+            base64: None,
+            width: 0,
+            height: 0,
+            error: Some("member not found in shard".into()),
+        })
+        .collect::<Vec<_>>();
+    for (index, thumb) in rendered {
+        if let Some(slot) = slots.get_mut(index) {
+            *slot = thumb;
+        }
+    }
+    for (index, first) in first_index.into_iter().enumerate() {
+        if first != index {
+            slots[index] = slots[first].clone();
+        }
+    }
+    Ok(slots)
+}
+
+fn send_thumbnail_sources(
+    shard_path: &Path,
+    mut wanted: HashMap<String, usize>,
+    tx: std::sync::mpsc::SyncSender<ThumbnailSource>,
+) -> AppResult<()> {
+    let reader = open_shard_reader(shard_path)?;
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        if wanted.is_empty() {
+            break;
+        }
+        let entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let current = normalize_member_path(&entry.path()?);
+        let Some(index) = wanted.remove(&current) else {
+            continue;
+        };
+        let size = entry.size();
+        let bytes = if size > thumbnails::MAX_THUMBNAIL_SOURCE_BYTES {
+            Err(format!("image is too large to thumbnail ({size} bytes)"))
+        } else {
+            let mut buf = Vec::with_capacity(size as usize);
+            entry.take(size).read_to_end(&mut buf)?;
+            Ok(buf)
+        };
+        let source = ThumbnailSource {
+            index,
+            name: current,
+            bytes,
+        };
+        if tx.send(source).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_wds_prefetch_depth(app: AppHandle) -> AppResult<u32> {
+    Ok(settings::read_setting(&app, PREFETCH_DEPTH_KEY)?
+        .unwrap_or(DEFAULT_PREFETCH_DEPTH)
+        .min(MAX_PREFETCH_DEPTH))
+}
+
+#[tauri::command]
+pub async fn set_wds_prefetch_depth(app: AppHandle, depth: u32) -> AppResult<()> {
+    if depth > MAX_PREFETCH_DEPTH {
+        return Err(AppError::Invalid(format!(
+            "prefetch depth must be at most {MAX_PREFETCH_DEPTH}"
+        )));
+    }
+    settings::write_setting(&app, PREFETCH_DEPTH_KEY, &depth)
+}
+
+/// A member's bytes after any decode override, its size, and the extension to write it as.
+pub fn decoded_member(
+    dir_path: &Path,
+    shard_filename: &str,
+    member_path: &str,
+) -> AppResult<(Vec<u8>, u64, String)> {
+    let shard_path = resolve_shard_path(dir_path, shard_filename)?;
+    let member_path = member_path.trim();
+    if member_path.is_empty() {
+        return Err(AppError::Invalid("member path is empty".into()));
+    }
+    let normalized = normalize_member_path_str(member_path);
+    let (data, size) = read_member_bytes(&shard_path, &normalized, 0, None)?;
+    if size > MAX_OPEN_BYTES {
+        return Err(AppError::Invalid(format!(
+            "member too large to open ({} bytes)",
+            size
+        )));
     }
-    let guessed_ext = guess_ext_from_member(&normalized, &data).unwrap_or_else(|| "bin".into());
+    let (data, ext) = match decode_override(dir_path, &normalized) {
+        Some(rule) => rule.decode_for_open(data),
+        None => {
+            let ext = guess_ext_from_member(&normalized, &data).unwrap_or_else(|| "bin".into());
+            (data, ext)
+        }
+    };
+    Ok((data, size, ext))
+}
+
+/// Writes a member to `destination_path` instead of opening it from the temp dir.
+#[tauri::command]
+pub async fn wds_save_member_as(
+    dir_path: String,
+    shard_filename: String,
+    member_path: String,
+    destination_path: String,
+) -> AppResult<SavedLeafResponse> {
+    spawn_blocking(move || {
+        let (data, _size, ext) =
+            decoded_member(Path::new(&dir_path), &shard_filename, &member_path)?;
+        save_leaf::write_to(&destination_path, &data, ext)
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn wds_open_member(
+    app: AppHandle,
+    dir_path: String,
+    shard_filename: String,
+    member_path: String,
+    opener_app_path: Option<String>,
+) -> AppResult<OpenLeafResponse> {
+    spawn_blocking(move || {
+        wds_open_member_sync(
+            &app,
+            PathBuf::from(dir_path),
+            shard_filename,
+            member_path,
+            opener_app_path.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn wds_open_member_sync(
+    app: &AppHandle,
+    dir_path: PathBuf,
+    shard_filename: String,
+    member_path: String,
+    opener_app_path: Option<&str>,
+) -> AppResult<OpenLeafResponse> {
+    let normalized = normalize_member_path_str(member_path.trim());
+    let (data, size, guessed_ext) = decoded_member(&dir_path, &shard_filename, &member_path)?;
 
     let temp_dir = std::env::temp_dir().join("dataset-inspector");
     fs::create_dir_all(&temp_dir)?;
     let base_name = format!("{}-{}", sanitize(&shard_filename), sanitize(&normalized));
+    let mut out = temp_names::write(&temp_dir, &base_name, &guessed_ext, &data)?;
+
+    // Default `.sph` support: decode to a WAV and open that.
+    let mut ext = guessed_ext;
+    if ext == "sph" {
+        let wav_out = out.with_extension("wav");
+        match audio::write_sph_as_wav_with_fallback(&data, &out, &wav_out) {
+            Ok(()) => {
+                out = wav_out;
+                ext = "wav".into();
+            }
+            Err(err) => {
+                let base = messages::text(
+                    Message::FileWithSize,
+                    &[("path", &out.display()), ("size", &size)],
+                );
+                return Ok(OpenLeafResponse {
+                    path: out.display().to_string(),
+                    size,
+                    ext,
+                    opened: false,
+                    needs_opener: true,
+                    message: base + &messages::text(Message::SphDecodeFailed, &[("error", &err)]),
+                });
+            }
+        }
+    }
+
+    let open_result = open_with::open_extracted(app, &out, opener_app_path);
+    let opened = open_result.is_ok();
+    let needs_opener = !opened;
+    let message = match open_result {
+        Ok(()) => messages::text(
+            Message::OpenedFile,
+            &[("path", &out.display()), ("size", &size)],
+        ),
+        Err(detail) => messages::text(
+            Message::CouldNotOpen,
+            &[("path", &out.display()), ("error", &detail)],
+        ),
+    };
+
+    Ok(OpenLeafResponse {
+        path: out.display().to_string(),
+        size,
+        ext,
+        opened,
+        needs_opener,
+        message,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WdsOpenedSample {
+    /// Folder the sample's members were extracted into.
+    pub path: String,
+    /// Member file names inside `path`, in shard order.
+    pub files: Vec<String>,
+    pub total_bytes: u64,
+    pub opened: bool,
+    pub message: String,
+}
+
+/// Extracts every member of sample `key` into a temp folder named after the key and opens the
+/// folder, so a multimodal sample can be inspected as a whole.
+#[tauri::command]
+pub async fn wds_open_sample(
+    app: AppHandle,
+    dir_path: String,
+    shard_filename: String,
+    key: String,
+) -> AppResult<WdsOpenedSample> {
+    spawn_blocking(move || wds_open_sample_sync(&app, PathBuf::from(dir_path), shard_filename, key))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn wds_open_sample_sync(
+    app: &AppHandle,
+    dir_path: PathBuf,
+    shard_filename: String,
+    key: String,
+) -> AppResult<WdsOpenedSample> {
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    let key = normalize_member_path_str(&key);
+    if key.is_empty() {
+        return Err(AppError::Invalid("sample key is empty".into()));
+    }
+
+    let out_dir = std::env::temp_dir().join("dataset-inspector").join(format!(
+        "{}-{}",
+        sanitize(&shard_filename),
+        sanitize(&key)
+    ));
+    if out_dir.exists() {
+        fs::remove_dir_all(&out_dir)?;
+    }
+    fs::create_dir_all(&out_dir)?;
+
+    let mut files = Vec::new();
+    let mut total_bytes = 0u64;
+    let mut archive = tar::Archive::new(open_shard_reader(&shard_path)?);
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let member_path = normalize_member_path(&entry.path()?);
+        if split_sample_key(&member_path).0 != key {
+            // Members of a sample are contiguous; stop once past them.
+            if !files.is_empty() {
+                break;
+            }
+            continue;
+        }
+        let size = entry.size();
+        if size > MAX_OPEN_BYTES {
+            return Err(AppError::Invalid(format!(
+                "member {member_path} too large to open ({size} bytes)"
+            )));
+        }
+        let name = member_path
+            .rsplit_once('/')
+            .map_or(member_path.as_str(), |(_, base)| base)
+            .to_string();
+        let out = out_dir.join(&name);
+        let mut data = Vec::new();
+        entry.take(MAX_OPEN_BYTES).read_to_end(&mut data)?;
+        fs::write(&out, &data)?;
+        quarantine::prepare_temp_file(app, &out);
+        total_bytes += size;
+        files.push(name);
+    }
+    if files.is_empty() {
+        let _ = fs::remove_dir(&out_dir);
+        return Err(AppError::Missing(format!(
+            "sample {key} in {shard_filename}"
+        )));
+    }
+
+    let (opened, message) = match open::that_detached(&out_dir) {
+        Ok(()) => (
+            true,
+            messages::text(
+                Message::OpenedFolder,
+                &[
+                    ("path", &out_dir.display()),
+                    ("count", &files.len()),
+                    ("size", &total_bytes),
+                ],
+            ),
+        ),
+        Err(err) => (
+            false,
+            messages::text(
+                Message::CouldNotOpen,
+                &[("path", &out_dir.display()), ("error", &err)],
+            ),
+        ),
+    };
+    Ok(WdsOpenedSample {
+        path: out_dir.display().to_string(),
+        files,
+        total_bytes,
+        opened,
+        message,
+    })
+}
+
+#[tauri::command]
+pub async fn wds_prepare_audio_preview(
+    dir_path: String,
+    shard_filename: String,
+    member_path: String,
+) -> AppResult<PreparedFileResponse> {
+    spawn_blocking(move || {
+        wds_prepare_audio_preview_sync(PathBuf::from(dir_path), shard_filename, member_path)
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn wds_prepare_audio_preview_sync(
+    dir_path: PathBuf,
+    shard_filename: String,
+    member_path: String,
+) -> AppResult<PreparedFileResponse> {
+    let shard_path = resolve_shard_path(&dir_path, &shard_filename)?;
+    let member_path = member_path.trim().to_string();
+    if member_path.is_empty() {
+        return Err(AppError::Invalid("member path is empty".into()));
+    }
+    let normalized = normalize_member_path_str(&member_path);
+    let (data, size) = read_member_bytes(&shard_path, &normalized, 0, None)?;
+    write_audio_preview(&shard_filename, &normalized, &data, size)
+}
+
+/// Prepares several audio members for playback with one pass over the shard. Results follow the
+/// order of `member_paths`.
+#[tauri::command]
+pub async fn wds_prepare_audio_previews(
+    dir_path: String,
+    shard_filename: String,
+    member_paths: Vec<String>,
+) -> AppResult<Vec<PreparedFileResponse>> {
+    spawn_blocking(move || {
+        let shard_path = resolve_shard_path(&PathBuf::from(dir_path), &shard_filename)?;
+        let normalized = member_paths
+            .iter()
+            .map(|p| normalize_member_path_str(p.trim()))
+            .collect::<Vec<_>>();
+        let mut fetched =
+            read_members_bytes(&shard_path, normalized.iter().cloned().collect(), None)?
+                .into_iter()
+                .map(|(path, data, size)| (path, (data, size)))
+                .collect::<HashMap<_, _>>();
+        normalized
+            .iter()
+            .map(|path| {
+                let (data, size) = fetched
+                    .remove(path)
+                    .ok_or_else(|| AppError::Missing(format!("member not found: {path}")))?;
+                write_audio_preview(&shard_filename, path, &data, size)
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn write_audio_preview(
+    shard_filename: &str,
+    normalized: &str,
+    data: &[u8],
+    size: u64,
+) -> AppResult<PreparedFileResponse> {
+    if size > MAX_OPEN_BYTES {
+        return Err(AppError::Invalid(format!(
+            "member too large to preview ({} bytes)",
+            size
+        )));
+    }
+    let guessed_ext = guess_ext_from_member(normalized, data).unwrap_or_else(|| "bin".into());
+
+    let temp_dir = std::env::temp_dir().join("dataset-inspector");
+    fs::create_dir_all(&temp_dir)?;
+    let base_name = format!("{}-{}", sanitize(shard_filename), sanitize(normalized));
 
-    let mut out = temp_dir.join(format!("{base_name}.{guessed_ext}"));
-    fs::write(&out, &data)?;
+    let mut out = temp_names::write(&temp_dir, &base_name, &guessed_ext, data)?;
 
     let mut ext = guessed_ext;
     if ext == "sph" {
-        let wav_out = temp_dir.join(format!("{base_name}.wav"));
-        audio::write_sph_as_wav_with_fallback(&data, &out, &wav_out)
+        let wav_out = out.with_extension("wav");
+        audio::write_sph_as_wav_with_fallback(data, &out, &wav_out)
             .map_err(|e| AppError::Invalid(format!("sph decode failed: {e}")))?;
         out = wav_out;
         ext = "wav".into();
@@ -907,12 +2474,33 @@ fn wds_prepare_audio_preview_sync(
 
     Ok(PreparedFileResponse {
         path: out.display().to_string(),
-        size: size.min(u32::MAX as u64) as u32,
+        size,
         ext,
     })
 }
 
-fn resolve_shard_dir_and_list(dir_path: &Path) -> AppResult<(PathBuf, Vec<WdsShardSummary>)> {
+/// A glob pattern in place of a shard directory, unless a path with that literal name exists.
+fn shard_glob(dir_path: &Path) -> Option<&str> {
+    dir_path
+        .to_str()
+        .filter(|p| path_glob::is_glob(p) && !dir_path.exists())
+}
+
+/// The shard directory `dir_path` names (itself, a shard's parent, or the directory a glob
+/// pattern's matches are under), without listing it.
+fn check_shard_dir(dir_path: &Path) -> AppResult<PathBuf> {
+    if let Some(pattern) = shard_glob(dir_path) {
+        let base = path_glob::base_dir(pattern);
+        if !base.is_dir() {
+            return Err(AppError::Missing(format!(
+                "directory does not exist: {}",
+                base.display()
+            )));
+        }
+        return Ok(base);
+    }
+    // Surfaces a symlink loop as such rather than as a missing directory.
+    symlinks::resolve_link(dir_path)?;
     if dir_path.is_file() {
         let filename = dir_path.file_name().and_then(|s| s.to_str()).unwrap_or("");
         if !looks_like_wds_shard(filename) {
@@ -920,22 +2508,11 @@ fn resolve_shard_dir_and_list(dir_path: &Path) -> AppResult<(PathBuf, Vec<WdsSha
                 "file is not a supported WebDataset shard".into(),
             ));
         }
-        let dir = dir_path
+        return Ok(dir_path
             .parent()
             .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| PathBuf::from("."));
-        let meta = fs::metadata(dir_path)?;
-        return Ok((
-            dir,
-            vec![WdsShardSummary {
-                filename: filename.to_string(),
-                path: dir_path.display().to_string(),
-                bytes: meta.len(),
-                exists: true,
-            }],
-        ));
+            .unwrap_or_else(|| PathBuf::from(".")));
     }
-
     if !dir_path.exists() {
         return Err(AppError::Missing(format!(
             "directory does not exist: {}",
@@ -945,31 +2522,65 @@ fn resolve_shard_dir_and_list(dir_path: &Path) -> AppResult<(PathBuf, Vec<WdsSha
     if !dir_path.is_dir() {
         return Err(AppError::Invalid("path is not a directory".into()));
     }
+    Ok(dir_path.to_path_buf())
+}
 
-    let mut shards: Vec<WdsShardSummary> = fs::read_dir(dir_path)?
-        .filter_map(|e| e.ok())
-        .filter_map(|e| {
-            let p = e.path();
-            let filename = p.file_name()?.to_str()?.to_string();
-            if !looks_like_wds_shard(&filename) {
-                return None;
-            }
-            let bytes = p.metadata().ok().map(|m| m.len()).unwrap_or(0);
-            Some(WdsShardSummary {
-                filename,
-                path: p.display().to_string(),
-                bytes,
-                exists: p.exists(),
+/// `check_shard_dir` under a timeout, for callers that need the directory but not its shards.
+fn shard_dir(dir_path: &Path) -> AppResult<PathBuf> {
+    let dir_path = dir_path.to_path_buf();
+    let what = format!("opening {}", dir_path.display());
+    network_fs::with_timeout(&what, network_fs::PROBE_TIMEOUT, move || {
+        check_shard_dir(&dir_path)
+    })
+}
+
+fn list_shard_dir(dir_path: &Path) -> AppResult<(PathBuf, Vec<WdsShardSummary>)> {
+    let dir = check_shard_dir(dir_path)?;
+    if dir_path.is_file() {
+        return Ok((dir, vec![shard_summary(dir_path)]));
+    }
+    if let Some(pattern) = shard_glob(dir_path) {
+        // Shards from several directories are named by their path under `dir`, which every
+        // command taking a directory and a shard filename joins back the same way.
+        let paths: Vec<PathBuf> = path_glob::expand(pattern)?
+            .into_iter()
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(looks_like_wds_shard)
             })
-        })
+            .collect();
+        let mut shards = network_fs::stat_all(&paths, network_fs::is_slow(&dir), shard_summary);
+        for (shard, path) in shards.iter_mut().zip(&paths) {
+            shard.filename = path_glob::relative_name(path, &dir);
+        }
+        return Ok((dir, shards));
+    }
+
+    let paths: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_str().is_some_and(looks_like_wds_shard))
+        .map(|e| e.path())
         .collect();
+    // Sizes come from a stat per shard; on network shares they are issued in parallel.
+    let mut shards = network_fs::stat_all(&paths, network_fs::is_slow(&dir), shard_summary);
     shards.sort_by(|a, b| a.filename.cmp(&b.filename));
 
-    Ok((dir_path.to_path_buf(), shards))
+    Ok((dir, shards))
+}
+
+/// Lists the shards of a directory (or of a single shard file), giving up after
+/// `LISTING_TIMEOUT` so a hung network mount surfaces as an error rather than a stalled view.
+fn resolve_shard_dir_and_list(dir_path: &Path) -> AppResult<(PathBuf, Vec<WdsShardSummary>)> {
+    let dir_path = dir_path.to_path_buf();
+    let what = format!("listing {}", dir_path.display());
+    network_fs::with_timeout(&what, network_fs::LISTING_TIMEOUT, move || {
+        list_shard_dir(&dir_path)
+    })
 }
 
 fn resolve_shard_path(dir_path: &Path, shard_filename: &str) -> AppResult<PathBuf> {
-    let (dir, _) = resolve_shard_dir_and_list(dir_path)?;
+    let dir = shard_dir(dir_path)?;
     let shard_filename = shard_filename.trim();
     if shard_filename.is_empty() {
         return Err(AppError::Invalid("shard filename is empty".into()));
@@ -980,6 +2591,7 @@ fn resolve_shard_path(dir_path: &Path, shard_filename: &str) -> AppResult<PathBu
         ));
     }
     let shard_path = dir.join(shard_filename);
+    symlinks::resolve_link(&shard_path)?;
     if !shard_path.exists() {
         return Err(AppError::Missing(format!(
             "shard does not exist: {}",
@@ -989,22 +2601,83 @@ fn resolve_shard_path(dir_path: &Path, shard_filename: &str) -> AppResult<PathBu
     Ok(shard_path)
 }
 
+/// Opens a shard as a plain TAR stream. Compression is told by the first bytes rather than the
+/// name, so a gzip, zstd or lz4 shard named `.tar` (or the other way round) still opens.
 fn open_shard_reader(shard_path: &Path) -> AppResult<Box<dyn Read + Send>> {
-    let file = File::open(shard_path)?;
+    let mut file: Box<dyn Read + Send> = Box::new(File::open(shard_path)?);
     let filename = shard_path
         .file_name()
         .and_then(|s| s.to_str())
         .unwrap_or("")
         .to_lowercase();
+    if multipart::volume_base(&filename).is_some() {
+        let dir = shard_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+        let first_name = shard_path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        file = Box::new(multipart::VolumeReader::new(
+            file,
+            &first_name,
+            Box::new(move |name: &str| {
+                let path = dir.join(name);
+                if !path.is_file() {
+                    return Ok(None);
+                }
+                Ok(Some(Box::new(File::open(path)?) as Box<dyn Read + Send>))
+            }),
+        ));
+    }
+    decompress_shard(file)
+}
 
-    if filename.ends_with(".tar.gz") || filename.ends_with(".tgz") {
-        return Ok(Box::new(flate2::read::GzDecoder::new(file)));
+fn decompress_shard(file: Box<dyn Read + Send>) -> AppResult<Box<dyn Read + Send>> {
+    let mut reader = BufReader::new(file);
+    let head = reader.fill_buf()?;
+    let (is_gzip, is_zstd, is_lz4) = (
+        head.starts_with(&GZIP_MAGIC),
+        head.starts_with(&ZSTD_MAGIC),
+        head.starts_with(&LZ4_FRAME_MAGIC),
+    );
+    if is_gzip {
+        return Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader)));
     }
-    if filename.ends_with(".tar.zst") || filename.ends_with(".tar.zstd") {
-        let decoder = zstd::stream::read::Decoder::new(file)?;
+    if is_zstd {
+        let decoder = zstd::stream::read::Decoder::with_buffer(reader)?;
         return Ok(Box::new(decoder));
     }
-    Ok(Box::new(file))
+    if is_lz4 {
+        return Ok(Box::new(lz4_flex::frame::FrameDecoder::new(reader)));
+    }
+    Ok(Box::new(reader))
+}
+
+/// True for single-file gzip shards, the ones a gzip index is kept for, whatever their name.
+fn is_plain_gzip_shard(shard_path: &Path) -> bool {
+    let filename = shard_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let mut head = [0u8; GZIP_MAGIC.len()];
+    multipart::volume_base(&filename).is_none()
+        && File::open(shard_path).is_ok_and(|mut f| f.read_exact(&mut head).is_ok())
+        && head == GZIP_MAGIC
+}
+
+/// Like `open_shard_reader`, but for indexed `.tar.gz` shards starts decompressing at the gzip
+/// member nearest to the earliest of `members` (normalized paths).
+fn open_shard_reader_near(shard_path: &Path, members: &[&str]) -> AppResult<Box<dyn Read + Send>> {
+    if !members.is_empty() && is_plain_gzip_shard(shard_path) {
+        if let Some(reader) = gzip_index::open_near(shard_path, members)? {
+            return Ok(reader);
+        }
+    }
+    open_shard_reader(shard_path)
 }
 
 fn normalize_member_path(path: &Path) -> String {
@@ -1050,45 +2723,71 @@ fn guess_ext_from_member(member_path: &str, data: &[u8]) -> Option<String> {
     if ext.is_some() {
         return ext;
     }
-    detect_magic_ext(data).or_else(|| infer::get(data).map(|t| t.extension().to_string()))
+    magic::sniff_ext(data)
 }
 
-fn detect_magic_ext(data: &[u8]) -> Option<String> {
-    if audio::is_sphere_file(data) {
-        return Some("sph".into());
-    }
-    if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE" {
-        return Some("wav".into());
-    }
-    if data.len() >= 3 && &data[0..3] == b"ID3" {
-        return Some("mp3".into());
-    }
-    if data.len() >= 2 && data[0] == 0xFF && (data[1] & 0xE0) == 0xE0 {
-        return Some("mp3".into());
-    }
-    if data.len() >= 4 && &data[0..4] == b"fLaC" {
-        return Some("flac".into());
+/// The last `len` bytes of a member, with the offset they start at and the member size.
+fn read_member_tail(
+    shard_path: &Path,
+    member_path: &str,
+    len: u64,
+) -> AppResult<(Vec<u8>, u64, u64)> {
+    let normalized = normalize_member_path_str(member_path);
+    let reader = open_shard_reader_near(shard_path, &[&normalized])?;
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        if normalize_member_path(&entry.path()?) != normalized {
+            continue;
+        }
+        let size = entry.size();
+        let start = size.saturating_sub(len);
+        std::io::copy(&mut (&mut entry).take(start), &mut std::io::sink())?;
+        let mut buf = Vec::new();
+        entry.read_to_end(&mut buf)?;
+        return Ok((buf, start, size));
     }
-    None
+    Err(AppError::Missing(format!(
+        "member not found in shard: {member_path}"
+    )))
 }
 
-fn sanitize(input: &str) -> String {
-    input
-        .chars()
-        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
-        .collect()
+/// Last `length` bytes of a shard member (64 KiB by default).
+#[tauri::command]
+pub async fn wds_peek_member_tail(
+    dir_path: String,
+    shard_filename: String,
+    member_path: String,
+    length: Option<u32>,
+) -> AppResult<TailPreview> {
+    spawn_blocking(move || {
+        let shard_path = resolve_shard_path(Path::new(&dir_path), &shard_filename)?;
+        let member_path = member_path.trim();
+        if member_path.is_empty() {
+            return Err(AppError::Invalid("member path is empty".into()));
+        }
+        let (buf, start, size) =
+            read_member_tail(&shard_path, member_path, tail_preview::tail_len(length))?;
+        Ok(TailPreview::new(&buf, start, size))
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
 }
 
 fn read_member_bytes(
     shard_path: &Path,
     member_path: &str,
+    skip: u64,
     limit: Option<usize>,
 ) -> AppResult<(Vec<u8>, u64)> {
-    let reader = open_shard_reader(shard_path)?;
-    let mut archive = tar::Archive::new(reader);
     let normalized = normalize_member_path_str(member_path);
+    let reader = open_shard_reader_near(shard_path, &[&normalized])?;
+    let mut archive = tar::Archive::new(reader);
     for entry in archive.entries()? {
-        let entry = entry?;
+        let mut entry = entry?;
         if entry.header().entry_type().is_dir() {
             continue;
         }
@@ -1097,6 +2796,10 @@ fn read_member_bytes(
             continue;
         }
         let size = entry.size();
+        let skip = skip.min(size);
+        if skip > 0 {
+            std::io::copy(&mut (&mut entry).take(skip), &mut std::io::sink())?;
+        }
         let read_limit = limit.map(|v| v as u64).unwrap_or(size);
         let mut buf = Vec::new();
         entry.take(read_limit).read_to_end(&mut buf)?;
@@ -0,0 +1,220 @@
+use regex::Regex;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::{header, Method, StatusCode};
+use std::io::Read;
+use std::sync::OnceLock;
+use std::time::Duration;
+use url::Url;
+
+use crate::app_error::{AppError, AppResult};
+use crate::http_date::parse_http_date;
+use crate::messages::{self, Message};
+use crate::remote_fs::{percent_decode, RemoteEntry, RemoteFs};
+
+const USER_AGENT: &str = "dataset-inspector/2.0.0 (tauri)";
+const CONNECT_TIMEOUT_SECS: u64 = 15;
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/><d:getcontentlength/><d:getlastmodified/></d:prop></d:propfind>"#;
+
+/// A WebDAV share (Nextcloud, ownCloud, Apache `mod_dav`, ...), with basic auth when a user is
+/// given.
+pub struct WebDav {
+    http: Client,
+    base: Url,
+    user: Option<(String, Option<String>)>,
+}
+
+fn dav_error(err: reqwest::Error) -> AppError {
+    AppError::Remote(messages::text(Message::WebdavFailed, &[("error", &err)]))
+}
+
+/// Patterns for the parts of a PROPFIND multistatus response that are read, matching elements
+/// whatever namespace prefix the server uses.
+struct Patterns {
+    response: Regex,
+    href: Regex,
+    length: Regex,
+    modified: Regex,
+    collection: Regex,
+}
+
+fn element_pattern(name: &str) -> Regex {
+    Regex::new(&format!(
+        r"(?s)<(?:[\w.-]+:)?{name}\b[^>]*>(.*?)</(?:[\w.-]+:)?{name}>"
+    ))
+    .expect("valid regex")
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        response: element_pattern("response"),
+        href: element_pattern("href"),
+        length: element_pattern("getcontentlength"),
+        modified: element_pattern("getlastmodified"),
+        collection: Regex::new(r"<(?:[\w.-]+:)?collection\b").expect("valid regex"),
+    })
+}
+
+fn element<'a>(pattern: &Regex, xml: &'a str) -> Option<&'a str> {
+    pattern
+        .captures(xml)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().trim())
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+impl WebDav {
+    /// `url` is the share's `http(s)://` address; a user name in it is sent with `password`.
+    pub fn connect(url: &Url, password: Option<&str>) -> AppResult<Self> {
+        let http = Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+            .build()
+            .map_err(dav_error)?;
+        let user = (!url.username().is_empty()).then(|| {
+            (
+                percent_decode(url.username()),
+                password
+                    .map(str::to_string)
+                    .or_else(|| url.password().map(percent_decode)),
+            )
+        });
+        let mut base = url.clone();
+        let _ = base.set_username("");
+        let _ = base.set_password(None);
+        base.set_query(None);
+        base.set_fragment(None);
+        Ok(Self { http, base, user })
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let mut url = self.base.clone();
+        url.set_path(path);
+        let req = self.http.request(method, url);
+        match &self.user {
+            Some((user, password)) => req.basic_auth(user, password.as_deref()),
+            None => req,
+        }
+    }
+
+    fn send(&self, req: RequestBuilder, path: &str) -> AppResult<Response> {
+        check(req.send().map_err(dav_error)?, path)
+    }
+}
+
+fn check(res: Response, path: &str) -> AppResult<Response> {
+    match res.status() {
+        status if status.is_success() => Ok(res),
+        StatusCode::NOT_FOUND => Err(AppError::Missing(messages::text(
+            Message::NotOnServer,
+            &[("path", &path)],
+        ))),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(AppError::Remote(messages::text(
+            Message::AccessRefused,
+            &[("path", &path)],
+        ))),
+        status => Err(AppError::Remote(messages::text(
+            Message::WebdavHttpStatus,
+            &[("status", &status), ("path", &path)],
+        ))),
+    }
+}
+
+impl RemoteFs for WebDav {
+    fn list(&self, path: &str) -> AppResult<Vec<RemoteEntry>> {
+        let dir = if path.ends_with('/') {
+            path.to_string()
+        } else {
+            format!("{path}/")
+        };
+        let req = self
+            .request(Method::from_bytes(b"PROPFIND").expect("valid method"), &dir)
+            .header("Depth", "1")
+            .header(header::CONTENT_TYPE, "application/xml; charset=utf-8")
+            .body(PROPFIND_BODY);
+        let xml = self.send(req, &dir)?.text().map_err(dav_error)?;
+        let mut entries = Vec::new();
+        let patterns = patterns();
+        for response in patterns
+            .response
+            .captures_iter(&xml)
+            .filter_map(|c| c.get(1))
+        {
+            let response = response.as_str();
+            let Some(href) = element(&patterns.href, response) else {
+                continue;
+            };
+            let href = unescape_xml(href);
+            let href_path = match Url::parse(&href) {
+                Ok(url) => url.path().to_string(),
+                Err(_) => href,
+            };
+            let entry_path = percent_decode(&href_path);
+            if entry_path.trim_end_matches('/') == dir.trim_end_matches('/') {
+                continue;
+            }
+            let is_dir = patterns.collection.is_match(response);
+            let name = entry_path
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            entries.push(RemoteEntry {
+                name,
+                path: entry_path.trim_end_matches('/').to_string(),
+                is_dir,
+                size: (!is_dir)
+                    .then(|| element(&patterns.length, response)?.parse().ok())
+                    .flatten(),
+                modified: element(&patterns.modified, response).and_then(parse_http_date),
+            });
+        }
+        Ok(entries)
+    }
+
+    fn size(&self, path: &str) -> AppResult<u64> {
+        let res = self.send(self.request(Method::HEAD, path), path)?;
+        res.headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| {
+                AppError::Remote(messages::text(Message::SizeNotReported, &[("path", &path)]))
+            })
+    }
+
+    fn read_range(&self, path: &str, start: u64, len: u64) -> AppResult<Vec<u8>> {
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        let end = start.saturating_add(len - 1);
+        let req = self
+            .request(Method::GET, path)
+            .header(header::RANGE, format!("bytes={start}-{end}"));
+        let res = req.send().map_err(dav_error)?;
+        if res.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(Vec::new());
+        }
+        let mut res = check(res, path)?;
+        if res.status() != StatusCode::PARTIAL_CONTENT {
+            // Servers without range support send the whole file; skip to `start`.
+            std::io::copy(&mut res.by_ref().take(start), &mut std::io::sink())?;
+        }
+        let mut out = Vec::new();
+        res.take(len).read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    fn open(&self, path: &str) -> AppResult<Box<dyn Read + '_>> {
+        Ok(Box::new(self.send(self.request(Method::GET, path), path)?))
+    }
+}
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{async_runtime::spawn_blocking, AppHandle};
+
+use crate::app_error::{AppError, AppResult};
+use crate::network_fs;
+use crate::settings;
+use crate::webdataset::{detect_local_dataset_sync, LocalDatasetDetectResponse};
+
+const WORKSPACES_KEY: &str = "workspaces";
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRoot {
+    /// A dataset directory, index file or shard, as dropped on the window.
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    /// Seconds since the Unix epoch; set by `open_workspace_root`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_opened: Option<u64>,
+}
+
+/// A named set of dataset roots, persisted in the settings store.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    name: String,
+    roots: Vec<WorkspaceRoot>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRootStatus {
+    #[serde(flatten)]
+    root: WorkspaceRoot,
+    exists: bool,
+    /// Seconds since the Unix epoch.
+    modified: Option<u64>,
+    /// What the root opens as, as `detect_local_dataset` reports it.
+    dataset: Option<LocalDatasetDetectResponse>,
+    /// Why the root is unavailable or not recognized.
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceStatus {
+    name: String,
+    roots: Vec<WorkspaceRootStatus>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn load_workspaces(app: &AppHandle) -> AppResult<Vec<Workspace>> {
+    Ok(settings::read_setting(app, WORKSPACES_KEY)?.unwrap_or_default())
+}
+
+fn find<'a>(workspaces: &'a mut [Workspace], name: &str) -> AppResult<&'a mut Workspace> {
+    let name = name.trim();
+    workspaces
+        .iter_mut()
+        .find(|w| w.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| AppError::Missing(format!("no workspace named {name}")))
+}
+
+/// Trims the name and paths, drops empty and repeated roots, and keeps when each root was last
+/// opened from `previous`.
+fn normalize(workspace: Workspace, previous: Option<&Workspace>) -> AppResult<Workspace> {
+    let name = workspace.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Invalid("workspace name is empty".into()));
+    }
+    let mut roots: Vec<WorkspaceRoot> = Vec::new();
+    for root in workspace.roots {
+        let path = root.path.trim().to_string();
+        if path.is_empty() || roots.iter().any(|r| r.path == path) {
+            continue;
+        }
+        let last_opened = previous
+            .and_then(|w| w.roots.iter().find(|r| r.path == path))
+            .and_then(|r| r.last_opened);
+        roots.push(WorkspaceRoot {
+            label: root
+                .label
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty()),
+            last_opened,
+            path,
+        });
+    }
+    Ok(Workspace { name, roots })
+}
+
+/// Modification time of the root, and what it opens as.
+type Check = (Option<u64>, AppResult<LocalDatasetDetectResponse>);
+
+fn check_root(path: PathBuf) -> Check {
+    let modified = std::fs::metadata(&path)
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
+    let dataset = match modified {
+        None => Err(AppError::Missing(format!(
+            "{} is not reachable",
+            path.display()
+        ))),
+        Some(_) => detect_local_dataset_sync(path),
+    };
+    (modified, dataset)
+}
+
+/// `check_root`, given up on after `PROBE_TIMEOUT` when the root sits on a network mount that
+/// does not answer.
+fn probe(path: &Path) -> Check {
+    let owned = path.to_path_buf();
+    let what = format!("checking {}", path.display());
+    network_fs::with_timeout(&what, network_fs::PROBE_TIMEOUT, move || {
+        Ok(check_root(owned))
+    })
+    .unwrap_or_else(|e| (None, Err(e)))
+}
+
+fn root_status(root: WorkspaceRoot, (modified, dataset): Check) -> WorkspaceRootStatus {
+    let (dataset, error) = match dataset {
+        Ok(dataset) => (Some(dataset), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    WorkspaceRootStatus {
+        root,
+        exists: modified.is_some(),
+        modified,
+        dataset,
+        error,
+    }
+}
+
+#[tauri::command]
+pub async fn list_workspaces(app: AppHandle) -> AppResult<Vec<Workspace>> {
+    load_workspaces(&app)
+}
+
+/// Creates or replaces the workspace with the same name (ignoring case) and returns all of them.
+#[tauri::command]
+pub async fn save_workspace(app: AppHandle, workspace: Workspace) -> AppResult<Vec<Workspace>> {
+    let mut workspaces = load_workspaces(&app)?;
+    let existing = workspaces
+        .iter()
+        .position(|w| w.name.eq_ignore_ascii_case(workspace.name.trim()));
+    let workspace = normalize(workspace, existing.map(|i| &workspaces[i]))?;
+    match existing {
+        Some(i) => workspaces[i] = workspace,
+        None => workspaces.push(workspace),
+    }
+    workspaces.sort_by_key(|w| w.name.to_lowercase());
+    settings::write_setting(&app, WORKSPACES_KEY, &workspaces)?;
+    Ok(workspaces)
+}
+
+#[tauri::command]
+pub async fn delete_workspace(app: AppHandle, name: String) -> AppResult<Vec<Workspace>> {
+    let mut workspaces = load_workspaces(&app)?;
+    workspaces.retain(|w| !w.name.eq_ignore_ascii_case(name.trim()));
+    settings::write_setting(&app, WORKSPACES_KEY, &workspaces)?;
+    Ok(workspaces)
+}
+
+/// Checks every root of a workspace (in parallel, each with a timeout) and reports whether it is
+/// still there and what it opens as.
+#[tauri::command]
+pub async fn refresh_workspace(app: AppHandle, name: String) -> AppResult<WorkspaceStatus> {
+    let mut workspaces = load_workspaces(&app)?;
+    let workspace = find(&mut workspaces, &name)?.clone();
+    spawn_blocking(move || {
+        let paths: Vec<PathBuf> = workspace
+            .roots
+            .iter()
+            .map(|r| PathBuf::from(&r.path))
+            .collect();
+        let checks = network_fs::stat_all(&paths, true, probe);
+        Ok(WorkspaceStatus {
+            name: workspace.name,
+            roots: workspace
+                .roots
+                .into_iter()
+                .zip(checks)
+                .map(|(root, check)| root_status(root, check))
+                .collect(),
+        })
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+/// Resolves a workspace root for opening and records when it was opened. Fails when the root is
+/// gone or not a recognized dataset.
+#[tauri::command]
+pub async fn open_workspace_root(
+    app: AppHandle,
+    name: String,
+    path: String,
+) -> AppResult<WorkspaceRootStatus> {
+    let mut workspaces = load_workspaces(&app)?;
+    let workspace = find(&mut workspaces, &name)?;
+    let root = workspace
+        .roots
+        .iter_mut()
+        .find(|r| r.path == path.trim())
+        .ok_or_else(|| {
+            AppError::Missing(format!(
+                "{} is not in workspace {}",
+                path.trim(),
+                name.trim()
+            ))
+        })?;
+    let target = PathBuf::from(&root.path);
+    let (modified, dataset) = spawn_blocking(move || probe(&target))
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?;
+    let dataset = dataset?;
+    root.last_opened = Some(now_secs());
+    let status = root_status(root.clone(), (modified, Ok(dataset)));
+    settings::write_setting(&app, WORKSPACES_KEY, &workspaces)?;
+    Ok(status)
+}
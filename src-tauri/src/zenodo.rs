@@ -1,20 +1,68 @@
 use base64::Engine;
-use hex::encode as hex_encode;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
-use std::{collections::HashMap, io::Read};
-use tauri::State;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+};
+use tauri::{AppHandle, Emitter, State};
 use url::Url;
 
 use crate::app_error::{AppError, AppResult};
-use crate::ipc_types::{FieldPreview, InlineMediaResponse, OpenLeafResponse};
+use crate::archive_listing::{
+    self, ArchiveEntry, ArchiveListQuery, ArchiveTreeNode, ArchiveTypeCount,
+};
+use crate::checksum::{self, StreamingHasher};
+use crate::croissant::{self, DeclaredMetadata};
+use crate::dataset_docs::{self, DatasetDocsResponse};
+use crate::decode_profile::{self, DecodeOverride};
+use crate::decoder_plugins;
+use crate::decompress;
+use crate::downloads;
+use crate::http_cache;
+use crate::http_limit;
+use crate::ipc_types::{
+    FieldPreview, InlineMediaResponse, OpenLeafResponse, SavedLeafResponse, StreamedMediaResponse,
+};
+use crate::jobs::{self, JobRegistry};
+use crate::magic;
+use crate::media_stream::MediaStore;
+use crate::messages::{self, Message};
+use crate::metadata;
+use crate::multipart;
+use crate::offline;
 use crate::open_with;
+use crate::preview_kind::PreviewKind;
+use crate::preview_limits::PreviewLimits;
+use crate::read_only;
+use crate::resource_monitor::MemoryCache;
+use crate::save_leaf;
+use crate::scan_cache::{self, LruScanMap, ScanFootprint, ScanSlot};
+use crate::scan_checkpoint;
+use crate::supervisor;
+use crate::tail_preview::{self, TailPreview};
+use crate::temp_names::{self, sanitize};
+use crate::throttle;
+use crate::trusted_hosts;
+use crate::zip_format::{
+    find_zip_eocd, parse_central_directory_entries, read_u16_le, read_u32_le, read_u64_le,
+    ZipEntryIndex,
+};
 
 const USER_AGENT: &str = "dataset-inspector/1.2.0 (tauri)";
 const REQUEST_TIMEOUT_SECS: u64 = 30;
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
 const PEEK_BYTES: usize = 64 * 1024;
-const PREVIEW_TEXT_CHARS: usize = 8 * 1024;
+/// Leading bytes of a streamed archive checked for an HTML page before parsing.
+const HTML_SNIFF_BYTES: u64 = 512;
 const MAX_INLINE_DOWNLOAD_BYTES: u64 = 50 * 1024 * 1024;
+/// Transfers from this size on are flagged for confirmation unless the caller sets its own.
+const DEFAULT_TRANSFER_WARN_BYTES: u64 = 1024 * 1024 * 1024;
 const ZIP_TAIL_INITIAL_BYTES: u64 = 1024 * 1024;
 const ZIP_TAIL_MAX_BYTES: u64 = 8 * 1024 * 1024;
 const ZIP_MAX_CENTRAL_DIR_BYTES: u64 = 64 * 1024 * 1024;
@@ -24,43 +72,52 @@ const TAR_MAX_ENTRIES: usize = 250_000;
 const TAR_INLINE_MEDIA_MAX_BYTES: u64 = 128 * 1024 * 1024;
 const TAR_DEFAULT_PAGE_SIZE: u32 = 25;
 const TAR_MAX_PAGE_SIZE: u32 = 200;
+const ZIP_DEFAULT_PAGE_SIZE: u32 = 100;
+const ZIP_MAX_PAGE_SIZE: u32 = 1000;
+const ARCHIVE_TREE_DEFAULT_DEPTH: u32 = 2;
 const MAX_TAR_META_BYTES: u64 = 1024 * 1024;
 const TAR_MEDIA_CACHE_ITEM_MAX_BYTES: u64 = 32 * 1024 * 1024;
 const TAR_MEDIA_CACHE_TOTAL_MAX_BYTES: u64 = 256 * 1024 * 1024;
-
-fn preview_utf8_text(data: &[u8]) -> Option<String> {
-    let raw = match std::str::from_utf8(data) {
-        Ok(text) => text,
-        Err(err) if err.error_len().is_none() => {
-            std::str::from_utf8(&data[..err.valid_up_to()]).ok()?
-        }
-        Err(_) => return None,
-    };
-    Some(raw.chars().take(PREVIEW_TEXT_CHARS).collect())
-}
+/// Scan progress is written to disk once this many new entries or this much time has accumulated.
+const TAR_CHECKPOINT_MIN_ENTRIES: usize = 1000;
+const TAR_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+/// Entries a background scan reads per lock; page requests get in between chunks.
+const TAR_BACKGROUND_CHUNK_ENTRIES: usize = 500;
+const TAR_SCAN_PROGRESS_EVENT: &str = "zenodo://tar-scan-progress";
+const RECORD_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+const RECORD_CACHE_MAX_ENTRIES: usize = 128;
+const GLOB_PEEK_DEFAULT_ENTRIES: usize = 20;
+const GLOB_PEEK_MAX_ENTRIES: usize = 100;
+
+static BLOCKING_HTTP: OnceLock<reqwest::blocking::Client> = OnceLock::new();
 
 #[derive(Clone)]
 pub struct ZenodoClient {
     http: reqwest::Client,
+    /// No overall timeout: full-file downloads can take much longer than API calls.
+    download_http: reqwest::Client,
+    records: RecordCache,
 }
 
+/// Record JSON keyed by API URL, so hopping between files of one record doesn't refetch it.
+type RecordCache = Arc<Mutex<HashMap<String, (Instant, Arc<serde_json::Value>)>>>;
+
 #[derive(Clone, Default)]
 pub struct ZenodoZipIndexCache(Arc<Mutex<HashMap<String, Arc<ZipIndex>>>>);
 
 #[derive(Clone)]
 struct ZipIndex {
+    /// The files making up the logical archive: just the `.zip` itself, or `.z01`, `.z02`, …
+    /// followed by the `.zip` for a split archive. Entry offsets are into their concatenation.
+    segments: Vec<ZipSegment>,
     entries: Vec<ZipEntryIndex>,
 }
 
 #[derive(Clone)]
-struct ZipEntryIndex {
-    name: String,
-    method: u16,
-    flags: u16,
-    compressed_size: u64,
-    uncompressed_size: u64,
-    local_header_offset: u64,
-    is_dir: bool,
+struct ZipSegment {
+    url: Url,
+    start: u64,
+    size: u64,
 }
 
 #[derive(Serialize)]
@@ -73,7 +130,33 @@ pub struct ZenodoZipEntrySummary {
     is_dir: bool,
 }
 
-#[derive(Clone, Serialize)]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoZipEntryListResponse {
+    offset: u32,
+    length: u32,
+    entries: Vec<ZenodoZipEntrySummary>,
+    /// Rows matching the query, before paging.
+    num_matching: u32,
+    num_entries_total: u32,
+}
+
+impl ArchiveEntry for ZipEntryIndex {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn size(&self) -> u64 {
+        self.uncompressed_size
+    }
+    fn stored_size(&self) -> u64 {
+        self.compressed_size
+    }
+    fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZenodoTarEntrySummary {
     name: String,
@@ -81,6 +164,18 @@ pub struct ZenodoTarEntrySummary {
     is_dir: bool,
 }
 
+impl ArchiveEntry for ZenodoTarEntrySummary {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn size(&self) -> u64 {
+        self.size
+    }
+    fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZenodoTarEntryListResponse {
@@ -91,9 +186,55 @@ pub struct ZenodoTarEntryListResponse {
     num_entries_total: Option<u32>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoArchiveTreeResponse {
+    root: ArchiveTreeNode,
+    /// TAR only: the tree covers the entries scanned so far, not the whole archive.
+    partial: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveTypeSummaryResponse {
+    types: Vec<ArchiveTypeCount>,
+    num_files: u64,
+    total_size: u64,
+    /// TAR only: the counts cover the entries scanned so far, not the whole archive.
+    partial: bool,
+}
+
+impl ArchiveTypeSummaryResponse {
+    fn new(types: Vec<ArchiveTypeCount>, partial: bool) -> Self {
+        Self {
+            num_files: types.iter().map(|t| t.count()).sum(),
+            total_size: types.iter().map(|t| t.size()).sum(),
+            types,
+            partial,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoTarScanProgress {
+    content_url: String,
+    entries_scanned: u32,
+    /// Uncompressed TAR bytes read so far.
+    bytes_scanned: u64,
+    done: bool,
+    cancelled: bool,
+    error: Option<String>,
+    /// Whether the archive matched its Zenodo checksum, once the scan has read all of it. `None`
+    /// while scanning, or when the scan resumed partway or has no checksum to compare against.
+    checksum_verified: Option<bool>,
+}
+
 #[derive(Clone, Default)]
 pub struct ZenodoTarScanCache {
-    inner: Arc<Mutex<HashMap<String, Arc<Mutex<ZenodoTarScanState>>>>>,
+    inner: Arc<Mutex<LruScanMap<ZenodoTarScanState>>>,
+    /// Cancel flags of running background scans, by content URL.
+    background: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
 }
 
 impl ZenodoTarScanCache {
@@ -101,63 +242,308 @@ impl ZenodoTarScanCache {
         &self,
         content_url: &str,
         filename: &str,
-    ) -> AppResult<Arc<Mutex<ZenodoTarScanState>>> {
+    ) -> AppResult<Arc<ScanSlot<ZenodoTarScanState>>> {
         let key = content_url.trim().to_string();
         if key.is_empty() {
-            return Err(AppError::Invalid("Missing content URL.".into()));
+            return Err(AppError::Invalid(messages::text(
+                Message::MissingContentUrl,
+                &[],
+            )));
         }
         let filename = filename.trim().to_string();
         if filename.is_empty() {
-            return Err(AppError::Invalid("Missing filename.".into()));
+            return Err(AppError::Invalid(messages::text(
+                Message::MissingFilename,
+                &[],
+            )));
         }
         if !looks_like_tar(&filename) {
-            return Err(AppError::Invalid(
-                "Selected file is not a supported TAR archive.".into(),
-            ));
+            return Err(AppError::Invalid(messages::text(
+                Message::NotTarArchive,
+                &[],
+            )));
         }
 
         let mut guard = self
             .inner
             .lock()
-            .map_err(|_| AppError::Task("tar scan cache lock poisoned".into()))?;
-        if let Some(existing) = guard.get(&key) {
-            return Ok(existing.clone());
+            // The map stays consistent even if a scan panicked while holding it.
+            .unwrap_or_else(PoisonError::into_inner);
+        let (state, _) = guard.get_or_try_insert(&key, None, || {
+            let url = Url::parse(&key)
+                .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
+            if !allowed_content_url(&url) {
+                return Err(AppError::Invalid(messages::text(
+                    Message::BlockedContentUrl,
+                    &[],
+                )));
+            }
+            Ok(ZenodoTarScanState::new(key.clone(), url, filename))
+        })?;
+        Ok(state)
+    }
+
+    pub fn invalidate(&self, content_url: Option<&str>) -> AppResult<usize> {
+        let mut guard = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let removed = guard.invalidate(content_url);
+        scan_checkpoint::remove(content_url);
+        Ok(removed)
+    }
+
+    /// Drops the scan of `content_url` and its checkpoint, for a scan that panicked.
+    fn resetter(&self, content_url: &str) -> impl FnOnce() + Send + 'static {
+        let cache = self.clone();
+        let key = content_url.trim().to_string();
+        move || {
+            let _ = cache.invalidate(Some(&key));
         }
+    }
+}
 
-        let url = Url::parse(&key)
-            .map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
-        if !allowed_content_url(&url) {
-            return Err(AppError::Invalid("Blocked content URL.".into()));
+impl MemoryCache for ZenodoTarScanCache {
+    fn usage(&self) -> (usize, usize) {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .usage()
+    }
+
+    fn shrink_by(&self, bytes: usize) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .shrink_by(bytes)
+    }
+}
+
+/// Where a remote TAR scan can pick up again: the next header's offset in the TAR data, and a
+/// point in the remote file the data can be decoded from (a gzip member start, or the header
+/// itself for uncompressed archives).
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TarResumePoint {
+    tar_offset: u64,
+    remote_offset: u64,
+    decoded_offset: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TarScanCheckpoint<'a> {
+    source: String,
+    entries: Cow<'a, [ZenodoTarEntrySummary]>,
+    done: bool,
+    resume: TarResumePoint,
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TarCompression {
+    Plain,
+    Gzip,
+    /// zstd and split archives: resuming re-reads from the start and skips what was scanned.
+    Other,
+}
+
+impl TarCompression {
+    fn of(filename: &str) -> Self {
+        let name = filename.trim().to_ascii_lowercase();
+        if multipart::volume_base(&name).is_some() {
+            return Self::Other;
+        }
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Self::Gzip
+        } else if name.ends_with(".tar") {
+            Self::Plain
+        } else {
+            Self::Other
         }
+    }
+}
+
+/// Shared with the readers under a scan's `TarStream` so the scan knows where it stands.
+#[derive(Default)]
+struct TarStreamPosition {
+    /// TAR bytes handed to the parser so far.
+    decoded: AtomicU64,
+    /// Start of the gzip member being decoded, in the remote file and in the TAR data.
+    member_remote: AtomicU64,
+    member_decoded: AtomicU64,
+}
 
-        let created = Arc::new(Mutex::new(ZenodoTarScanState::new(url, filename)?));
-        guard.insert(key, created.clone());
-        Ok(created)
+impl TarStreamPosition {
+    fn resume_point(&self, compression: TarCompression) -> TarResumePoint {
+        let tar_offset = self.decoded.load(Ordering::Relaxed);
+        match compression {
+            TarCompression::Plain => TarResumePoint {
+                tar_offset,
+                remote_offset: tar_offset,
+                decoded_offset: tar_offset,
+            },
+            TarCompression::Gzip => TarResumePoint {
+                tar_offset,
+                remote_offset: self.member_remote.load(Ordering::Relaxed),
+                decoded_offset: self.member_decoded.load(Ordering::Relaxed),
+            },
+            TarCompression::Other => TarResumePoint {
+                tar_offset,
+                ..TarResumePoint::default()
+            },
+        }
     }
 }
 
+/// Digest of the remote bytes a scan has read, with its algorithm. Taken out when the scan ends.
+type SharedDigest = Arc<Mutex<Option<(String, StreamingHasher)>>>;
+
 struct ZenodoTarScanState {
-    tar: TarStream<Box<dyn Read + Send>>,
+    source: String,
+    url: Url,
+    filename: String,
+    /// Opened on first use, at `resume` when continuing a checkpointed scan.
+    tar: Option<TarStream<Box<dyn Read + Send>>>,
+    resume: TarResumePoint,
+    position: Arc<TarStreamPosition>,
+    saved: (usize, bool),
+    saved_at: Instant,
     done: bool,
+    /// Set while a scan that started at the first byte of a single-file archive is running.
+    hashing: Option<SharedDigest>,
+    /// `algorithm:hex` of the whole archive, once such a scan has finished.
+    digest: Option<String>,
+    /// The checksum Zenodo lists for the file, when a caller passed it along.
+    expected_checksum: Option<String>,
     entries: Vec<ZenodoTarEntrySummary>,
     previews: HashMap<String, FieldPreview>,
     media_cache: HashMap<String, CachedMedia>,
     media_lru: std::collections::VecDeque<String>,
     media_total: u64,
+    listing_bytes: usize,
 }
 
 impl ZenodoTarScanState {
-    fn new(url: Url, filename: String) -> AppResult<Self> {
-        let reader = open_remote_tar_reader(url, &filename)?;
-        Ok(Self {
-            tar: TarStream::new(reader),
-            done: false,
-            entries: Vec::new(),
+    fn new(source: String, url: Url, filename: String) -> Self {
+        let checkpoint = scan_checkpoint::load::<TarScanCheckpoint>(&source)
+            .filter(|c| c.source == source)
+            .unwrap_or(TarScanCheckpoint {
+                source: source.clone(),
+                entries: Cow::Owned(Vec::new()),
+                done: false,
+                resume: TarResumePoint::default(),
+                digest: None,
+            });
+        let entries = checkpoint.entries.into_owned();
+        let listing_bytes = entries
+            .iter()
+            .map(|e| std::mem::size_of::<ZenodoTarEntrySummary>() + e.name.len())
+            .sum();
+        Self {
+            source,
+            url,
+            filename,
+            tar: None,
+            resume: checkpoint.resume,
+            position: Arc::new(TarStreamPosition::default()),
+            saved: (entries.len(), checkpoint.done),
+            saved_at: Instant::now(),
+            done: checkpoint.done,
+            hashing: None,
+            digest: checkpoint.digest,
+            expected_checksum: None,
+            entries,
             previews: HashMap::new(),
             media_cache: HashMap::new(),
             media_lru: std::collections::VecDeque::new(),
             media_total: 0,
-        })
+            listing_bytes,
+        }
+    }
+
+    fn progress(&self, content_url: &str) -> ZenodoTarScanProgress {
+        ZenodoTarScanProgress {
+            content_url: content_url.to_string(),
+            entries_scanned: self.entries.len().min(u32::MAX as usize) as u32,
+            bytes_scanned: self.position.decoded.load(Ordering::Relaxed),
+            done: self.done,
+            cancelled: false,
+            error: None,
+            checksum_verified: self.checksum_verified(),
+        }
+    }
+
+    fn checksum_verified(&self) -> Option<bool> {
+        let (algorithm, actual) = checksum::split_checksum(self.digest.as_deref()?)?;
+        let (expected_algorithm, expected) =
+            checksum::split_checksum(self.expected_checksum.as_deref()?)?;
+        (algorithm == expected_algorithm).then(|| actual.eq_ignore_ascii_case(&expected))
+    }
+
+    /// Starts hashing a scan about to open at `resume`. Earlier bytes are never read again once a
+    /// scan resumes past them, and split archives are checksummed per volume, so those are skipped.
+    fn start_digest(&mut self) {
+        let whole_file = self.resume.remote_offset == 0
+            && self.digest.is_none()
+            && multipart::volume_base(&self.filename.trim().to_ascii_lowercase()).is_none();
+        self.hashing = whole_file.then(|| {
+            // Zenodo lists md5 checksums; hash with that until told otherwise.
+            let algorithm = self
+                .expected_checksum
+                .as_deref()
+                .and_then(checksum::split_checksum)
+                .map(|(algorithm, _)| algorithm)
+                .filter(|algorithm| StreamingHasher::new(algorithm).is_some())
+                .unwrap_or_else(|| "md5".into());
+            let hasher = StreamingHasher::new(&algorithm);
+            Arc::new(Mutex::new(hasher.map(|h| (algorithm, h))))
+        });
+    }
+
+    /// Reads what follows the end-of-archive blocks so the digest covers the whole file, then
+    /// finalizes it.
+    fn finish_digest(&mut self) {
+        let Some(hashing) = self.hashing.take() else {
+            return;
+        };
+        if let Some(tar) = self.tar.as_mut() {
+            if std::io::copy(&mut tar.reader, &mut std::io::sink()).is_err() {
+                return;
+            }
+        }
+        let finished = hashing.lock().ok().and_then(|mut digest| digest.take());
+        self.digest =
+            finished.map(|(algorithm, hasher)| format!("{algorithm}:{}", hasher.finalize_hex()));
+    }
+
+    fn save_checkpoint_if_due(&mut self) {
+        let current = (self.entries.len(), self.done);
+        if current == self.saved {
+            return;
+        }
+        let due = self.done
+            || current.0.saturating_sub(self.saved.0) >= TAR_CHECKPOINT_MIN_ENTRIES
+            || self.saved_at.elapsed() >= TAR_CHECKPOINT_INTERVAL;
+        if !due {
+            return;
+        }
+        let resume = if self.tar.is_some() {
+            self.position
+                .resume_point(TarCompression::of(&self.filename))
+        } else {
+            self.resume
+        };
+        scan_checkpoint::store(
+            &self.source,
+            &TarScanCheckpoint {
+                source: self.source.clone(),
+                entries: Cow::Borrowed(&self.entries),
+                done: self.done,
+                resume,
+                digest: self.digest.clone(),
+            },
+        );
+        self.saved = current;
+        self.saved_at = Instant::now();
     }
 
     fn ensure_scanned_for_page(
@@ -169,69 +555,171 @@ impl ZenodoTarScanState {
         while !self.done && self.entries.len() < target {
             let idx = self.entries.len();
             let capture = idx >= capture_start && idx < capture_end;
-            let next = self
-                .tar
-                .next_file_with_bytes(|meta| {
-                    if !capture || meta.is_dir {
-                        return None;
-                    }
-                    let ext = ext_from_filename(&meta.path).unwrap_or_default();
-                    let is_media = matches!(
-                        ext.as_str(),
-                        "mp4" | "wav" | "mp3" | "flac" | "m4a" | "ogg" | "opus" | "aac"
-                    );
-                    if is_media && meta.size > 0 && meta.size <= TAR_MEDIA_CACHE_ITEM_MAX_BYTES {
-                        return Some(meta.size);
-                    }
-                    Some(PEEK_BYTES as u64)
-                })
-                .map_err(|e| AppError::Invalid(format!("tar parse failed: {e}")))?;
-            let Some((meta, maybe_bytes)) = next else {
-                self.done = true;
+            self.scan_next(|_| capture)?;
+        }
+        self.save_checkpoint_if_due();
+        Ok(())
+    }
+
+    /// Index of the first file whose path satisfies `matches`, scanning further into the archive
+    /// until one turns up, the archive ends or `cancel` is set. The match's preview is captured on
+    /// the way.
+    fn find_entry(
+        &mut self,
+        matches: impl Fn(&str) -> bool,
+        cancel: &AtomicBool,
+    ) -> AppResult<Option<usize>> {
+        if let Some(idx) = self
+            .entries
+            .iter()
+            .position(|e| !e.is_dir && matches(&e.name))
+        {
+            return Ok(Some(idx));
+        }
+        let mut found = None;
+        while !self.done {
+            jobs::ensure_running(cancel)?;
+            if !self.scan_next(&matches)? {
                 break;
-            };
+            }
+            let idx = self.entries.len() - 1;
+            let entry = &self.entries[idx];
+            if !entry.is_dir && matches(&entry.name) {
+                found = Some(idx);
+                break;
+            }
+        }
+        self.save_checkpoint_if_due();
+        Ok(found)
+    }
 
-            let summary = ZenodoTarEntrySummary {
-                name: meta.path.clone(),
-                size: meta.size,
-                is_dir: meta.is_dir,
-            };
-            self.entries.push(summary);
-            if self.entries.len() >= TAR_MAX_ENTRIES {
-                return Err(AppError::Invalid(
-                    "TAR contains too many entries to list.".into(),
-                ));
+    /// Indices of the first `limit` files whose paths satisfy `matches`, scanning further into the
+    /// archive as needed until `cancel` is set. Previews of matches found on the way are captured.
+    fn find_entries(
+        &mut self,
+        matches: impl Fn(&str) -> bool,
+        limit: usize,
+        cancel: &AtomicBool,
+    ) -> AppResult<Vec<usize>> {
+        let mut found = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.is_dir && matches(&e.name))
+            .map(|(idx, _)| idx)
+            .take(limit)
+            .collect::<Vec<_>>();
+        while found.len() < limit && !self.done {
+            jobs::ensure_running(cancel)?;
+            if !self.scan_next(&matches)? {
+                break;
+            }
+            let idx = self.entries.len() - 1;
+            let entry = &self.entries[idx];
+            if !entry.is_dir && matches(&entry.name) {
+                found.push(idx);
             }
+        }
+        self.save_checkpoint_if_due();
+        Ok(found)
+    }
 
-            if let Some(bytes) = maybe_bytes {
-                if !meta.is_dir {
-                    let preview_bytes = bytes.iter().take(PEEK_BYTES).copied().collect::<Vec<u8>>();
-                    let preview_text = preview_utf8_text(&preview_bytes);
-                    let guessed_ext = ext_from_filename(&meta.path)
-                        .or_else(|| infer::get(&preview_bytes).map(|t| t.extension().to_string()));
-                    let hex_snippet =
-                        hex_encode(preview_bytes.iter().take(48).copied().collect::<Vec<u8>>());
-                    let is_binary = preview_text.is_none();
-                    let preview = FieldPreview {
-                        preview_text,
-                        hex_snippet,
-                        guessed_ext,
-                        is_binary,
-                        size: meta.size.min(u32::MAX as u64) as u32,
-                    };
-                    self.previews.insert(meta.path.clone(), preview);
-
-                    if bytes.len() as u64 == meta.size
-                        && meta.size <= TAR_MEDIA_CACHE_ITEM_MAX_BYTES
-                    {
-                        let ext = ext_from_filename(&meta.path).unwrap_or_else(|| "bin".into());
-                        let mime = mime_for_ext(&ext).to_string();
-                        self.cache_media(meta.path, ext, mime, bytes)?;
-                    }
+    /// Reads the next entry, keeping its preview (and, for small media, its bytes) when `capture`
+    /// accepts its path. Returns false once the archive has ended.
+    fn scan_next(&mut self, capture: impl Fn(&str) -> bool) -> AppResult<bool> {
+        if self.done {
+            return Ok(false);
+        }
+        if self.tar.is_none() {
+            self.start_digest();
+            let reader = open_tar_scan_reader(
+                self.url.clone(),
+                &self.filename,
+                self.resume,
+                self.position.clone(),
+                self.hashing.clone(),
+            )?;
+            self.tar = Some(TarStream::new(reader));
+        }
+        let Some(tar) = self.tar.as_mut() else {
+            return Ok(false);
+        };
+        let next = tar
+            .next_file_with_bytes(|meta| {
+                if meta.is_dir || !capture(&meta.path) {
+                    return None;
+                }
+                let ext = ext_from_filename(&meta.path).unwrap_or_default();
+                let is_media = matches!(
+                    ext.as_str(),
+                    "mp4" | "wav" | "mp3" | "flac" | "m4a" | "ogg" | "opus" | "aac"
+                );
+                if is_media && meta.size > 0 && meta.size <= TAR_MEDIA_CACHE_ITEM_MAX_BYTES {
+                    return Some(meta.size);
+                }
+                Some(PEEK_BYTES as u64)
+            })
+            .map_err(|e| AppError::Invalid(format!("tar parse failed: {e}")))?;
+        let Some((meta, maybe_bytes)) = next else {
+            self.finish_digest();
+            self.done = true;
+            self.tar = None;
+            return Ok(false);
+        };
+
+        let summary = ZenodoTarEntrySummary {
+            name: meta.path.clone(),
+            size: meta.size,
+            is_dir: meta.is_dir,
+        };
+        self.listing_bytes += std::mem::size_of::<ZenodoTarEntrySummary>() + summary.name.len();
+        self.entries.push(summary);
+        if self.entries.len() >= TAR_MAX_ENTRIES {
+            return Err(AppError::Invalid(messages::text(
+                Message::TooManyTarEntries,
+                &[],
+            )));
+        }
+
+        if let Some(bytes) = maybe_bytes {
+            if !meta.is_dir {
+                let unwrapped = decompress::unwrap(&meta.path, &bytes, PEEK_BYTES);
+                let (name, preview_bytes) = match &unwrapped {
+                    Some(inner) => (inner.inner_name.as_str(), inner.data.as_slice()),
+                    None => (meta.path.as_str(), &bytes[..bytes.len().min(PEEK_BYTES)]),
+                };
+                let limits = PreviewLimits::default();
+                let (preview_text, text_length) = limits.text(preview_bytes).unzip();
+                let guessed_ext =
+                    ext_from_filename(name).or_else(|| magic::sniff_ext(preview_bytes));
+                let is_binary = preview_text.is_none();
+                let mut preview = FieldPreview {
+                    preview_text,
+                    text_length,
+                    hex_snippet: limits.hex(preview_bytes),
+                    guessed_ext,
+                    is_binary,
+                    size: meta.size,
+                    metadata: metadata::extract(preview_bytes),
+                    plugin: None,
+                    kind: PreviewKind::Binary,
+                    compression: unwrapped.as_ref().map(|inner| inner.codec),
+                };
+                preview.kind = PreviewKind::detect(preview_bytes, &preview);
+                self.listing_bytes += std::mem::size_of::<FieldPreview>()
+                    + meta.path.len()
+                    + preview.preview_text.as_ref().map_or(0, String::len)
+                    + preview.hex_snippet.len();
+                self.previews.insert(meta.path.clone(), preview);
+
+                if bytes.len() as u64 == meta.size && meta.size <= TAR_MEDIA_CACHE_ITEM_MAX_BYTES {
+                    let ext = ext_from_filename(&meta.path).unwrap_or_else(|| "bin".into());
+                    let mime = mime_for_ext(&ext).to_string();
+                    self.cache_media(meta.path, ext, mime, bytes)?;
                 }
             }
         }
-        Ok(())
+        Ok(true)
     }
 
     fn cached_preview(&self, name: &str) -> Option<FieldPreview> {
@@ -285,6 +773,12 @@ impl ZenodoTarScanState {
     }
 }
 
+impl ScanFootprint for ZenodoTarScanState {
+    fn approx_bytes(&self) -> usize {
+        self.listing_bytes + self.media_total as usize
+    }
+}
+
 #[derive(Clone)]
 struct CachedMedia {
     bytes: Vec<u8>,
@@ -534,13 +1028,26 @@ impl Default for ZenodoClient {
             .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
-        Self { http }
+        let download_http = reqwest::Client::builder()
+            .http1_only()
+            .user_agent(USER_AGENT)
+            .connect_timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            http,
+            download_http,
+            records: RecordCache::default(),
+        }
     }
 }
 
 #[derive(Deserialize)]
 struct ZenodoRecordResponse {
     id: u64,
+    /// Zenodo serializes the concept id as a string.
+    conceptrecid: Option<serde_json::Value>,
+    conceptdoi: Option<String>,
     doi: Option<String>,
     doi_url: Option<String>,
     metadata: ZenodoMetadata,
@@ -555,6 +1062,7 @@ struct ZenodoMetadata {
     version: Option<String>,
     access_right: Option<String>,
     creators: Option<Vec<ZenodoCreator>>,
+    license: Option<serde_json::Value>,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -595,10 +1103,39 @@ pub struct ZenodoFileSummary {
     content_url: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoDownloadedFile {
+    key: String,
+    path: String,
+    size: u64,
+    /// "downloaded", "skipped" (already present and verified) or "failed".
+    status: String,
+    resumed: bool,
+    checksum_verified: Option<bool>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoRecordDownloadReport {
+    record_id: u64,
+    dest_dir: String,
+    files: Vec<ZenodoDownloadedFile>,
+    ok: bool,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZenodoRecordSummary {
     record_id: u64,
+    /// The id that was asked for; differs from `record_id` when a concept id resolved to the
+    /// latest version.
+    requested_record_id: u64,
+    concept_record_id: Option<u64>,
+    concept_doi: Option<String>,
+    /// Record lives on sandbox.zenodo.org: a test upload, not a published dataset.
+    sandbox: bool,
     title: String,
     doi: Option<String>,
     doi_url: Option<String>,
@@ -610,9 +1147,34 @@ pub struct ZenodoRecordSummary {
     files: Vec<ZenodoFileSummary>,
 }
 
+/// Zenodo's test instance, where dataset authors trial uploads before publishing.
+const SANDBOX_HOST: &str = "sandbox.zenodo.org";
+/// Versions fetched per request when listing a concept's versions.
+const VERSIONS_PAGE_SIZE: u64 = 100;
+/// Stops a misbehaving server from paging forever.
+const MAX_VERSION_PAGES: u64 = 100;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoRecordVersion {
+    record_id: u64,
+    version: Option<String>,
+    publication_date: Option<String>,
+    doi: Option<String>,
+    is_latest: bool,
+}
+
+fn json_u64(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_u64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
 fn is_allowed_zenodo_host(host: &str) -> bool {
     let host = host.trim().to_ascii_lowercase();
-    host == "zenodo.org" || host.ends_with(".zenodo.org")
+    host == "zenodo.org" || host.ends_with(".zenodo.org") || trusted_hosts::is_trusted(&host)
 }
 
 fn validate_zenodo_url(url: &Url) -> bool {
@@ -639,31 +1201,55 @@ fn extract_record_id_from_url(url: &Url) -> Option<u64> {
     None
 }
 
+/// Accepts Zenodo DOIs ("10.5281/zenodo.123", "doi:…", "https://doi.org/…"); sandbox DOIs use
+/// the 10.5072 test prefix.
+fn parse_zenodo_doi(input: &str) -> Option<(Url, u64)> {
+    let lower = input.trim().to_ascii_lowercase();
+    let doi = [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ]
+    .iter()
+    .find_map(|prefix| lower.strip_prefix(prefix))
+    .unwrap_or(&lower);
+    let (prefix, suffix) = doi.split_once("/zenodo.")?;
+    let host = match prefix {
+        "10.5281" => "zenodo.org",
+        "10.5072" => SANDBOX_HOST,
+        _ => return None,
+    };
+    let record_id = suffix.parse::<u64>().ok()?;
+    let url = Url::parse(&format!("https://{host}/records/{record_id}")).ok()?;
+    Some((url, record_id))
+}
+
 fn extract_record_id(input: &str) -> AppResult<(Url, u64)> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
-        return Err(AppError::Invalid(
-            "Provide a Zenodo record URL like https://zenodo.org/records/<id>.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::ProvideRecordUrl,
+            &[],
+        )));
     }
 
-    let url = Url::parse(trimmed).map_err(|_| {
-        AppError::Invalid(
-            "Unsupported input. Provide a Zenodo record URL like https://zenodo.org/records/<id>."
-                .into(),
-        )
-    })?;
+    if let Some(found) = parse_zenodo_doi(trimmed) {
+        return Ok(found);
+    }
+
+    let url = Url::parse(trimmed)
+        .map_err(|_| AppError::Invalid(messages::text(Message::UnsupportedRecordInput, &[])))?;
     if !validate_zenodo_url(&url) {
-        return Err(AppError::Invalid(
-            "Unsupported Zenodo host or scheme.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::UnsupportedRepositoryHost,
+            &[],
+        )));
     }
 
-    let record_id = extract_record_id_from_url(&url).ok_or_else(|| {
-        AppError::Invalid(
-            "Unsupported Zenodo URL. Expected https://zenodo.org/records/<id>.".into(),
-        )
-    })?;
+    let record_id = extract_record_id_from_url(&url)
+        .ok_or_else(|| AppError::Invalid(messages::text(Message::UnsupportedRecordUrl, &[])))?;
 
     Ok((url, record_id))
 }
@@ -714,21 +1300,62 @@ async fn get_json<T: serde::de::DeserializeOwned>(
     client: &reqwest::Client,
     url: Url,
 ) -> AppResult<T> {
-    let res = client
-        .get(url.clone())
-        .send()
-        .await
-        .map_err(|e| AppError::Remote(format!("request failed: {e}")))?;
-    let status = res.status();
-    let text = res
-        .text()
-        .await
-        .map_err(|e| AppError::Remote(format!("read response failed: {e}")))?;
-    if !status.is_success() {
-        return Err(AppError::Remote(format!("HTTP {status} from {url}")));
+    let res = http_cache::get(client, &url, None, None).await?;
+    if !res.status.is_success() {
+        return Err(AppError::Remote(messages::text(
+            Message::HttpStatusFrom,
+            &[("status", &res.status), ("url", &url)],
+        )));
+    }
+    serde_json::from_slice(&res.body).map_err(|e| {
+        AppError::Remote(messages::text(
+            Message::InvalidJsonFrom,
+            &[("url", &url), ("error", &e)],
+        ))
+    })
+}
+
+impl ZenodoClient {
+    /// Fetches record JSON, reusing a copy younger than `RECORD_CACHE_TTL` unless `force_refresh`.
+    async fn fetch_record(
+        &self,
+        api_url: Url,
+        force_refresh: bool,
+    ) -> AppResult<ZenodoRecordResponse> {
+        let key = api_url.to_string();
+        let cached = if force_refresh {
+            None
+        } else {
+            self.records.lock().ok().and_then(|map| {
+                map.get(&key)
+                    .filter(|(fetched, _)| fetched.elapsed() < RECORD_CACHE_TTL)
+                    .map(|(_, value)| value.clone())
+            })
+        };
+        let value = match cached {
+            Some(value) => value,
+            None => {
+                let value: Arc<serde_json::Value> = Arc::new(get_json(&self.http, api_url).await?);
+                if let Ok(mut map) = self.records.lock() {
+                    map.retain(|_, (fetched, _)| fetched.elapsed() < RECORD_CACHE_TTL);
+                    if map.len() >= RECORD_CACHE_MAX_ENTRIES {
+                        if let Some(oldest) = map
+                            .iter()
+                            .min_by_key(|(_, (fetched, _))| *fetched)
+                            .map(|(k, _)| k.clone())
+                        {
+                            map.remove(&oldest);
+                        }
+                    }
+                    map.insert(key, (Instant::now(), value.clone()));
+                }
+                value
+            }
+        };
+        serde_json::from_value((*value).clone()).map_err(|e| {
+            AppError::Remote(messages::text(Message::InvalidRecordJson, &[("error", &e)]))
+        })
     }
-    serde_json::from_str(&text)
-        .map_err(|e| AppError::Remote(format!("invalid JSON from {url}: {e}")))
 }
 
 fn ext_from_filename(name: &str) -> Option<String> {
@@ -743,6 +1370,11 @@ fn ext_from_filename(name: &str) -> Option<String> {
     }
 }
 
+/// The user's decode override for a file or archive entry, keyed by field suffix or extension.
+fn decode_override(content_url: &str, name: &str) -> Option<DecodeOverride> {
+    decode_profile::lookup(content_url, &decode_profile::name_keys(name))
+}
+
 fn looks_like_tar(filename: &str) -> bool {
     let name = filename.trim().to_ascii_lowercase();
     name.ends_with(".tar")
@@ -750,6 +1382,18 @@ fn looks_like_tar(filename: &str) -> bool {
         || name.ends_with(".tgz")
         || name.ends_with(".tar.zst")
         || name.ends_with(".tar.zstd")
+        || multipart::is_first_volume(&name)
+}
+
+/// Content URL of a sibling file in the same record (`…/files/<key>/content`).
+fn sibling_content_url(url: &Url, key: &str) -> Option<Url> {
+    let mut sibling = url.clone();
+    {
+        let mut segments = sibling.path_segments_mut().ok()?;
+        segments.pop_if_empty().pop().pop();
+        segments.push(key).push("content");
+    }
+    allowed_content_url(&sibling).then_some(sibling)
 }
 
 fn mime_for_ext(ext: &str) -> &'static str {
@@ -792,9 +1436,10 @@ fn inflate_deflate_with_limit(compressed: &[u8], limit: u64) -> AppResult<Vec<u8
         }
         out.extend_from_slice(&buf[..n]);
         if out.len() as u64 > limit {
-            return Err(AppError::Invalid(
-                "ZIP entry expanded beyond the limit.".into(),
-            ));
+            return Err(AppError::Invalid(messages::text(
+                Message::ZipEntryTooLarge,
+                &[],
+            )));
         }
     }
     Ok(out)
@@ -814,31 +1459,20 @@ async fn range_request(
     start: u64,
     end_inclusive: u64,
 ) -> AppResult<(Vec<u8>, Option<u64>)> {
-    let res = client
-        .get(url.clone())
-        .header(
-            reqwest::header::RANGE,
-            format!("bytes={start}-{end_inclusive}"),
-        )
-        .send()
-        .await
-        .map_err(|e| AppError::Remote(format!("request failed: {e}")))?;
-
-    let status = res.status();
+    let range = format!("bytes={start}-{end_inclusive}");
+    let res = http_cache::get(client, &url, Some(&range), None).await?;
+    let status = res.status;
     if !(status.is_success() || status == reqwest::StatusCode::PARTIAL_CONTENT) {
-        return Err(AppError::Remote(format!("HTTP {status} from {url}")));
+        return Err(AppError::Remote(messages::text(
+            Message::HttpStatusFrom,
+            &[("status", &status), ("url", &url)],
+        )));
     }
-
     let total_size = res
-        .headers()
-        .get(reqwest::header::CONTENT_RANGE)
-        .and_then(|v| v.to_str().ok())
+        .content_range
+        .as_deref()
         .and_then(parse_content_range_total);
-    let bytes = res
-        .bytes()
-        .await
-        .map_err(|e| AppError::Remote(format!("read response failed: {e}")))?;
-    Ok((bytes.to_vec(), total_size))
+    Ok((res.body, total_size))
 }
 
 fn parse_content_range(value: &str) -> Option<(u64, u64, u64)> {
@@ -859,118 +1493,321 @@ async fn suffix_range_request(
     suffix_len: u64,
 ) -> AppResult<(Vec<u8>, u64, u64)> {
     let suffix_len = suffix_len.max(1);
-    let res = client
-        .get(url.clone())
-        .header(reqwest::header::RANGE, format!("bytes=-{suffix_len}"))
-        .send()
-        .await
-        .map_err(|e| AppError::Remote(format!("request failed: {e}")))?;
-
-    let status = res.status();
+    let range = format!("bytes=-{suffix_len}");
+    let res = http_cache::get(client, &url, Some(&range), None).await?;
+    let status = res.status;
     if !(status.is_success() || status == reqwest::StatusCode::PARTIAL_CONTENT) {
-        return Err(AppError::Remote(format!("HTTP {status} from {url}")));
+        return Err(AppError::Remote(messages::text(
+            Message::HttpStatusFrom,
+            &[("status", &status), ("url", &url)],
+        )));
     }
 
-    let (start, _end, total) = res
-        .headers()
-        .get(reqwest::header::CONTENT_RANGE)
-        .and_then(|v| v.to_str().ok())
-        .and_then(parse_content_range)
-        .ok_or_else(|| AppError::Remote(format!("Missing Content-Range from {url}")))?;
+    let Some((start, _end, total)) = res.content_range.as_deref().and_then(parse_content_range)
+    else {
+        if magic::looks_like_html(&res.body) {
+            return Err(html_instead_of_file(&url));
+        }
+        return Err(AppError::Remote(format!(
+            "Missing Content-Range from {url}"
+        )));
+    };
+    Ok((res.body, start, total))
+}
 
-    let bytes = res
-        .bytes()
-        .await
-        .map_err(|e| AppError::Remote(format!("read response failed: {e}")))?;
-    Ok((bytes.to_vec(), start, total))
+/// Captive portals and sign-in redirects answer with an HTML page and a 200, which the archive
+/// parsers would otherwise reject with a confusing format error.
+fn html_instead_of_file(url: &Url) -> AppError {
+    AppError::Remote(messages::text(
+        Message::HtmlInsteadOfFile,
+        &[("host", &url.host_str().unwrap_or_default())],
+    ))
 }
 
-fn sanitize(input: &str) -> String {
-    input
-        .chars()
-        .map(|c| {
-            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+') {
-                c
-            } else {
-                '_'
-            }
-        })
-        .collect()
+/// Fails when a response body starts with an HTML page; otherwise hands back a reader that still
+/// yields every byte.
+fn reject_html(mut reader: Box<dyn Read + Send>, url: &Url) -> AppResult<Box<dyn Read + Send>> {
+    let mut head = Vec::with_capacity(HTML_SNIFF_BYTES as usize);
+    (&mut reader)
+        .take(HTML_SNIFF_BYTES)
+        .read_to_end(&mut head)?;
+    if magic::looks_like_html(&head) {
+        return Err(html_instead_of_file(url));
+    }
+    Ok(Box::new(std::io::Cursor::new(head).chain(reader)))
 }
 
-fn open_remote_tar_reader(url: Url, filename_hint: &str) -> AppResult<Box<dyn Read + Send>> {
+/// The blocking client shared by every remote stream, so archive volumes and repeated scans of
+/// the same host reuse pooled connections. HTTP/2 is used where the server offers it over TLS,
+/// and keepalive probes stop idle middleboxes from dropping a stream the decoder is slow to drain.
+fn blocking_http_client() -> AppResult<reqwest::blocking::Client> {
+    if let Some(client) = BLOCKING_HTTP.get() {
+        return Ok(client.clone());
+    }
     let client = reqwest::blocking::Client::builder()
         .user_agent(USER_AGENT)
         .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .connect_timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .http2_adaptive_window(true)
+        .http2_keep_alive_interval(TCP_KEEPALIVE)
+        .http2_keep_alive_while_idle(true)
         .build()
         .map_err(|e| AppError::Task(format!("Failed to build HTTP client: {e}")))?;
-    let res = client
-        .get(url.clone())
-        .send()
-        .map_err(|e| AppError::Remote(format!("request failed: {e}")))?;
+    Ok(BLOCKING_HTTP.get_or_init(|| client).clone())
+}
+
+/// Streams a remote file from byte `start`. Servers that ignore the Range header are read from the
+/// beginning and the skipped prefix discarded.
+fn open_remote_from(url: &Url, start: u64) -> AppResult<Box<dyn Read + Send>> {
+    let client = blocking_http_client()?;
+    offline::ensure_online(url)?;
+    let permit = http_limit::acquire_blocking(url)?;
+    let build = || {
+        let req = client.get(url.clone());
+        if start > 0 {
+            req.header(reqwest::header::RANGE, format!("bytes={start}-"))
+        } else {
+            req
+        }
+    };
+    let res = throttle::send_blocking(url, "request", build)?;
+    drop(permit);
     let status = res.status();
     if !status.is_success() {
-        return Err(AppError::Remote(format!("HTTP {status} from {url}")));
+        return Err(AppError::Remote(messages::text(
+            Message::HttpStatusFrom,
+            &[("status", &status), ("url", &url)],
+        )));
     }
+    let mut reader = reject_html(Box::new(res), url)?;
+    if start > 0 && status != reqwest::StatusCode::PARTIAL_CONTENT {
+        std::io::copy(&mut (&mut reader).take(start), &mut std::io::sink())?;
+    }
+    Ok(reader)
+}
 
-    let name = filename_hint.trim().to_ascii_lowercase();
-    let base: Box<dyn Read + Send> = Box::new(res);
-    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
-        return Ok(Box::new(flate2::read::GzDecoder::new(base)));
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n as u64;
+        Ok(n)
     }
-    if name.ends_with(".tar.zst") || name.ends_with(".tar.zstd") {
-        let decoder = zstd::stream::read::Decoder::new(base)?;
-        return Ok(Box::new(decoder));
+}
+
+type CountedBufReader = std::io::BufReader<CountingReader<Box<dyn Read + Send>>>;
+
+/// Decodes concatenated gzip members and records where each starts, so a scan can later resume
+/// from the member it was in with a Range request.
+struct GzipMembers {
+    decoder: Option<
+        flate2::bufread::GzDecoder<std::io::BufReader<CountingReader<Box<dyn Read + Send>>>>,
+    >,
+    remote_start: u64,
+    decoded: u64,
+    position: Arc<TarStreamPosition>,
+}
+
+impl GzipMembers {
+    fn new(
+        raw: Box<dyn Read + Send>,
+        remote_start: u64,
+        decoded: u64,
+        position: Arc<TarStreamPosition>,
+    ) -> Self {
+        position
+            .member_remote
+            .store(remote_start, Ordering::Relaxed);
+        position.member_decoded.store(decoded, Ordering::Relaxed);
+        let inner = std::io::BufReader::new(CountingReader {
+            inner: raw,
+            count: 0,
+        });
+        Self {
+            decoder: Some(flate2::bufread::GzDecoder::new(inner)),
+            remote_start,
+            decoded,
+            position,
+        }
     }
-    Ok(base)
 }
 
-fn read_u16_le(input: &[u8], offset: usize) -> AppResult<u16> {
-    let slice = input
-        .get(offset..offset + 2)
-        .ok_or_else(|| AppError::Invalid("Unexpected EOF while parsing ZIP.".into()))?;
-    Ok(u16::from_le_bytes([slice[0], slice[1]]))
+impl Read for GzipMembers {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::io::BufRead;
+        loop {
+            let Some(mut decoder) = self.decoder.take() else {
+                return Ok(0);
+            };
+            let n = decoder.read(buf)?;
+            if n > 0 || buf.is_empty() {
+                self.decoded += n as u64;
+                self.decoder = Some(decoder);
+                return Ok(n);
+            }
+            let mut inner = decoder.into_inner();
+            if inner.fill_buf()?.is_empty() {
+                return Ok(0);
+            }
+            let consumed = inner.get_ref().count - inner.buffer().len() as u64;
+            self.position
+                .member_remote
+                .store(self.remote_start + consumed, Ordering::Relaxed);
+            self.position
+                .member_decoded
+                .store(self.decoded, Ordering::Relaxed);
+            self.decoder = Some(flate2::bufread::GzDecoder::new(inner));
+        }
+    }
+}
+
+/// Feeds the remote bytes of a scan to its digest as they are read.
+struct DigestReader {
+    inner: Box<dyn Read + Send>,
+    digest: SharedDigest,
+}
+
+impl Read for DigestReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if let Ok(mut digest) = self.digest.lock() {
+            if let Some((_, hasher)) = digest.as_mut() {
+                hasher.update(&buf[..n]);
+            }
+        }
+        Ok(n)
+    }
 }
 
-fn read_u32_le(input: &[u8], offset: usize) -> AppResult<u32> {
-    let slice = input
-        .get(offset..offset + 4)
-        .ok_or_else(|| AppError::Invalid("Unexpected EOF while parsing ZIP.".into()))?;
-    Ok(u32::from_le_bytes([slice[0], slice[1], slice[2], slice[3]]))
+fn with_digest(reader: Box<dyn Read + Send>, digest: Option<SharedDigest>) -> Box<dyn Read + Send> {
+    match digest {
+        Some(digest) => Box::new(DigestReader {
+            inner: reader,
+            digest,
+        }),
+        None => reader,
+    }
 }
 
-fn read_u64_le(input: &[u8], offset: usize) -> AppResult<u64> {
-    let slice = input
-        .get(offset..offset + 8)
-        .ok_or_else(|| AppError::Invalid("Unexpected EOF while parsing ZIP.".into()))?;
-    Ok(u64::from_le_bytes([
-        slice[0], slice[1], slice[2], slice[3], slice[4], slice[5], slice[6], slice[7],
-    ]))
+struct PositionReader {
+    inner: Box<dyn Read + Send>,
+    position: Arc<TarStreamPosition>,
 }
 
-fn find_zip_eocd(buf: &[u8]) -> Option<usize> {
-    // EOCD can be at most 65535 + 22 bytes from the end of the file.
-    const EOCD_SIG: [u8; 4] = [0x50, 0x4b, 0x05, 0x06];
-    if buf.len() < 22 {
-        return None;
+impl Read for PositionReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position.decoded.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
     }
-    let start = buf.len().saturating_sub(22 + 65535);
-    for i in (start..=buf.len().saturating_sub(22)).rev() {
-        if buf.get(i..i + 4) != Some(&EOCD_SIG) {
-            continue;
-        }
-        let comment_len = u16::from_le_bytes([buf[i + 20], buf[i + 21]]) as usize;
-        if i + 22 + comment_len == buf.len() {
-            return Some(i);
+}
+
+/// Opens the TAR data of a scan at `resume.tar_offset`, fetching only from `resume.remote_offset`
+/// where the compression allows it. The fetched bytes, before decompression, go to `digest`.
+fn open_tar_scan_reader(
+    url: Url,
+    filename: &str,
+    resume: TarResumePoint,
+    position: Arc<TarStreamPosition>,
+    digest: Option<SharedDigest>,
+) -> AppResult<Box<dyn Read + Send>> {
+    let (inner, start): (Box<dyn Read + Send>, u64) = match TarCompression::of(filename) {
+        TarCompression::Plain => (
+            with_digest(open_remote_from(&url, resume.remote_offset)?, digest),
+            resume.remote_offset,
+        ),
+        TarCompression::Gzip => {
+            let raw = with_digest(open_remote_from(&url, resume.remote_offset)?, digest);
+            let members = GzipMembers::new(
+                raw,
+                resume.remote_offset,
+                resume.decoded_offset,
+                position.clone(),
+            );
+            (Box::new(members), resume.decoded_offset)
         }
+        TarCompression::Other => (open_remote_tar_reader(url, filename, digest)?, 0),
+    };
+    position.decoded.store(start, Ordering::Relaxed);
+    let mut reader = PositionReader { inner, position };
+    let skip = resume.tar_offset.saturating_sub(start);
+    if skip > 0 {
+        std::io::copy(&mut (&mut reader).take(skip), &mut std::io::sink())?;
     }
-    None
+    Ok(Box::new(reader))
+}
+
+fn open_remote_tar_reader(
+    url: Url,
+    filename_hint: &str,
+    digest: Option<SharedDigest>,
+) -> AppResult<Box<dyn Read + Send>> {
+    let client = blocking_http_client()?;
+    offline::ensure_online(&url)?;
+    let permit = http_limit::acquire_blocking(&url)?;
+    let res = throttle::send_blocking(&url, "request", || client.get(url.clone()))?;
+    drop(permit);
+    let status = res.status();
+    if !status.is_success() {
+        return Err(AppError::Remote(messages::text(
+            Message::HttpStatusFrom,
+            &[("status", &status), ("url", &url)],
+        )));
+    }
+
+    let mut name = filename_hint.trim().to_ascii_lowercase();
+    let body = reject_html(Box::new(res), &url)?;
+    let mut base = with_digest(body, digest);
+    if let Some(combined) = multipart::volume_base(&name).map(|b| b.to_string()) {
+        // Later volumes are fetched one at a time as the scan reaches them; a missing next
+        // volume ends the archive.
+        base = Box::new(multipart::VolumeReader::new(
+            base,
+            filename_hint.trim(),
+            Box::new(move |key: &str| {
+                let Some(next) = sibling_content_url(&url, key) else {
+                    return Ok(None);
+                };
+                offline::ensure_online(&next).map_err(std::io::Error::other)?;
+                let permit = http_limit::acquire_blocking(&next).map_err(std::io::Error::other)?;
+                let res = throttle::send_blocking(&next, "request", || client.get(next.clone()))
+                    .map_err(std::io::Error::other)?;
+                drop(permit);
+                if res.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                let res = res.error_for_status().map_err(std::io::Error::other)?;
+                Ok(Some(Box::new(res) as Box<dyn Read + Send>))
+            }),
+        ));
+        name = combined;
+    }
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        return Ok(Box::new(flate2::read::GzDecoder::new(base)));
+    }
+    if name.ends_with(".tar.zst") || name.ends_with(".tar.zstd") {
+        let decoder = zstd::stream::read::Decoder::new(base)?;
+        return Ok(Box::new(decoder));
+    }
+    Ok(base)
 }
 
 struct ZipCentralDirectory {
     total_entries: u64,
     central_dir_size: u64,
+    /// Relative to the start of `central_dir_disk`.
     central_dir_offset: u64,
+    /// Number of the last segment; 0 unless the archive is split.
+    last_disk: u32,
+    central_dir_disk: u32,
+    /// Size of the file the EOCD was read from (the last segment).
+    last_segment_size: u64,
 }
 
 async fn read_zip_central_directory_info(
@@ -978,7 +1815,7 @@ async fn read_zip_central_directory_info(
     url: Url,
 ) -> AppResult<ZipCentralDirectory> {
     let mut tail_len = ZIP_TAIL_INITIAL_BYTES;
-    let (tail, tail_start, _total_size, eocd_rel) = loop {
+    let (tail, tail_start, total_size, eocd_rel) = loop {
         let (tail, tail_start, total_size) =
             suffix_range_request(client, url.clone(), tail_len).await?;
         let eocd_rel = find_zip_eocd(&tail)
@@ -986,6 +1823,9 @@ async fn read_zip_central_directory_info(
         if let Ok(eocd_rel) = eocd_rel {
             break (tail, tail_start, total_size, eocd_rel);
         }
+        if magic::looks_like_html(&tail) {
+            return Err(html_instead_of_file(&url));
+        }
         if tail_len >= ZIP_TAIL_MAX_BYTES {
             return Err(AppError::Invalid(
                 "Unable to locate ZIP EOCD (tail too small).".into(),
@@ -1000,6 +1840,8 @@ async fn read_zip_central_directory_info(
         return Err(AppError::Invalid("Invalid ZIP EOCD signature.".into()));
     }
 
+    let last_disk_u16 = read_u16_le(&tail, eocd_rel + 4)?;
+    let central_dir_disk_u16 = read_u16_le(&tail, eocd_rel + 6)?;
     let entries_u16 = read_u16_le(&tail, eocd_rel + 10)? as u64;
     let central_dir_size_u32 = read_u32_le(&tail, eocd_rel + 12)? as u64;
     let central_dir_offset_u32 = read_u32_le(&tail, eocd_rel + 16)? as u64;
@@ -1013,6 +1855,9 @@ async fn read_zip_central_directory_info(
             total_entries: entries_u16,
             central_dir_size: central_dir_size_u32,
             central_dir_offset: central_dir_offset_u32,
+            last_disk: last_disk_u16 as u32,
+            central_dir_disk: central_dir_disk_u16 as u32,
+            last_segment_size: total_size,
         });
     }
 
@@ -1036,6 +1881,8 @@ async fn read_zip_central_directory_info(
     if zip64_eocd.len() < 56 || read_u32_le(&zip64_eocd, 0)? != 0x0606_4b50 {
         return Err(AppError::Invalid("Missing ZIP64 EOCD record.".into()));
     }
+    let last_disk = read_u32_le(&zip64_eocd, 16)?;
+    let central_dir_disk = read_u32_le(&zip64_eocd, 20)?;
     let total_entries = read_u64_le(&zip64_eocd, 32)?;
     let central_dir_size = read_u64_le(&zip64_eocd, 40)?;
     let central_dir_offset = read_u64_le(&zip64_eocd, 48)?;
@@ -1044,114 +1891,25 @@ async fn read_zip_central_directory_info(
         total_entries,
         central_dir_size,
         central_dir_offset,
+        last_disk,
+        central_dir_disk,
+        last_segment_size: total_size,
     })
 }
 
-fn parse_zip64_extra(
-    extra: &[u8],
-    need_uncompressed: bool,
-    need_compressed: bool,
-    need_local_offset: bool,
-) -> AppResult<(Option<u64>, Option<u64>, Option<u64>)> {
-    let mut pos = 0usize;
-    while pos + 4 <= extra.len() {
-        let header_id = u16::from_le_bytes([extra[pos], extra[pos + 1]]);
-        let data_size = u16::from_le_bytes([extra[pos + 2], extra[pos + 3]]) as usize;
-        pos += 4;
-        if pos + data_size > extra.len() {
-            break;
-        }
-        if header_id == 0x0001 {
-            let mut cursor = pos;
-            let mut uncompressed = None;
-            let mut compressed = None;
-            let mut local_offset = None;
-            if need_uncompressed {
-                uncompressed = Some(read_u64_le(extra, cursor)?);
-                cursor += 8;
-            }
-            if need_compressed {
-                compressed = Some(read_u64_le(extra, cursor)?);
-                cursor += 8;
-            }
-            if need_local_offset {
-                local_offset = Some(read_u64_le(extra, cursor)?);
-            }
-            return Ok((uncompressed, compressed, local_offset));
-        }
-        pos += data_size;
-    }
-    Ok((None, None, None))
-}
-
-fn parse_central_directory_entries(
-    buf: &[u8],
-    max_entries_hint: u64,
-) -> AppResult<Vec<ZipEntryIndex>> {
-    let mut entries = Vec::new();
-    let mut pos = 0usize;
-    while pos + 46 <= buf.len() {
-        let sig = read_u32_le(buf, pos)?;
-        if sig != 0x0201_4b50 {
-            break;
-        }
-        let flags = read_u16_le(buf, pos + 8)?;
-        let method = read_u16_le(buf, pos + 10)?;
-        let crc32 = read_u32_le(buf, pos + 16)?;
-        let compressed_size_u32 = read_u32_le(buf, pos + 20)?;
-        let uncompressed_size_u32 = read_u32_le(buf, pos + 24)?;
-        let name_len = read_u16_le(buf, pos + 28)? as usize;
-        let extra_len = read_u16_le(buf, pos + 30)? as usize;
-        let comment_len = read_u16_le(buf, pos + 32)? as usize;
-        let local_header_offset_u32 = read_u32_le(buf, pos + 42)?;
-        let header_end = pos
-            .checked_add(46)
-            .ok_or_else(|| AppError::Invalid("ZIP header overflow.".into()))?;
-        let name_start = header_end;
-        let name_end = name_start + name_len;
-        let extra_start = name_end;
-        let extra_end = extra_start + extra_len;
-        let comment_end = extra_end + comment_len;
-        let name_bytes = buf
-            .get(name_start..name_end)
-            .ok_or_else(|| AppError::Invalid("Malformed ZIP central directory entry.".into()))?;
-        let extra_bytes = buf.get(extra_start..extra_end).unwrap_or(&[]);
-        let name = String::from_utf8(name_bytes.to_vec())
-            .unwrap_or_else(|_| String::from_utf8_lossy(name_bytes).to_string());
-        let is_dir = name.ends_with('/');
-
-        let need_zip64_uncompressed = uncompressed_size_u32 == 0xFFFF_FFFF;
-        let need_zip64_compressed = compressed_size_u32 == 0xFFFF_FFFF;
-        let need_zip64_local_offset = local_header_offset_u32 == 0xFFFF_FFFF;
-        let (zip64_uncompressed, zip64_compressed, zip64_local_offset) = parse_zip64_extra(
-            extra_bytes,
-            need_zip64_uncompressed,
-            need_zip64_compressed,
-            need_zip64_local_offset,
-        )?;
-
-        let compressed_size = zip64_compressed.unwrap_or(compressed_size_u32 as u64);
-        let uncompressed_size = zip64_uncompressed.unwrap_or(uncompressed_size_u32 as u64);
-        let local_header_offset = zip64_local_offset.unwrap_or(local_header_offset_u32 as u64);
-        let _ = crc32;
-
-        entries.push(ZipEntryIndex {
-            name,
-            method,
-            flags,
-            compressed_size,
-            uncompressed_size,
-            local_header_offset,
-            is_dir,
-        });
-
-        if max_entries_hint > 0 && entries.len() as u64 >= max_entries_hint {
-            // Keep parsing safe even if EOCD entry count is wrong.
-            // We'll still break once we hit buffer end or invalid signature.
-        }
-        pos = comment_end;
+/// Content URL of segment `disk` of a split archive whose last segment is at `url`
+/// (`…/files/data.zip/content` → `…/files/data.z01/content`).
+fn split_zip_segment_url(url: &Url, disk: u32) -> Option<Url> {
+    let path = url.path();
+    let prefix = path.strip_suffix("/content")?;
+    let (files, key) = prefix.rsplit_once('/')?;
+    let stem = key.get(..key.len().checked_sub(4)?)?;
+    if !key[stem.len()..].eq_ignore_ascii_case(".zip") {
+        return None;
     }
-    Ok(entries)
+    let mut out = url.clone();
+    out.set_path(&format!("{files}/{stem}.z{:02}/content", disk + 1));
+    allowed_content_url(&out).then_some(out)
 }
 
 async fn build_zip_index(client: &reqwest::Client, url: Url) -> AppResult<ZipIndex> {
@@ -1161,14 +1919,79 @@ async fn build_zip_index(client: &reqwest::Client, url: Url) -> AppResult<ZipInd
             "ZIP central directory is too large to parse.".into(),
         ));
     }
-    let end = cd
-        .central_dir_offset
+
+    let mut segments = Vec::new();
+    let mut start = 0u64;
+    for disk in 0..cd.last_disk {
+        let segment_url = split_zip_segment_url(&url, disk)
+            .ok_or_else(|| AppError::Invalid(messages::text(Message::SplitZipNaming, &[])))?;
+        let (_head, size) = range_request(client, segment_url.clone(), 0, 0)
+            .await
+            .map_err(|e| {
+                AppError::Missing(messages::text(
+                    Message::SplitZipSegmentUnavailable,
+                    &[("segment", &(disk + 1)), ("error", &e)],
+                ))
+            })?;
+        let size = size.ok_or_else(|| {
+            AppError::Remote(messages::text(
+                Message::SplitZipSegmentNoSize,
+                &[("segment", &(disk + 1))],
+            ))
+        })?;
+        segments.push(ZipSegment {
+            url: segment_url,
+            start,
+            size,
+        });
+        start += size;
+    }
+    segments.push(ZipSegment {
+        url,
+        start,
+        size: cd.last_segment_size,
+    });
+    let disk_starts: Vec<u64> = segments.iter().map(|s| s.start).collect();
+    let central_dir_start = disk_starts
+        .get(cd.central_dir_disk as usize)
+        .copied()
+        .ok_or_else(|| AppError::Invalid("ZIP central directory segment is missing.".into()))?
+        .checked_add(cd.central_dir_offset)
+        .ok_or_else(|| AppError::Invalid("ZIP central directory range overflow.".into()))?;
+
+    let mut index = ZipIndex {
+        segments,
+        entries: Vec::new(),
+    };
+    let end = central_dir_start
         .checked_add(cd.central_dir_size)
         .and_then(|v| v.checked_sub(1))
         .ok_or_else(|| AppError::Invalid("ZIP central directory range overflow.".into()))?;
-    let (buf, _total) = range_request(client, url, cd.central_dir_offset, end).await?;
-    let entries = parse_central_directory_entries(&buf, cd.total_entries)?;
-    Ok(ZipIndex { entries })
+    let (buf, _total) = zip_range(client, &index, central_dir_start, end).await?;
+    index.entries = parse_central_directory_entries(&buf, cd.total_entries, &disk_starts)?;
+    Ok(index)
+}
+
+/// Range request against the logical archive, stitching together reads that cross segments.
+async fn zip_range(
+    client: &reqwest::Client,
+    index: &ZipIndex,
+    start: u64,
+    end_inclusive: u64,
+) -> AppResult<(Vec<u8>, Option<u64>)> {
+    let total = index.segments.iter().map(|s| s.size).sum::<u64>();
+    let mut out = Vec::new();
+    for segment in &index.segments {
+        let segment_end = segment.start + segment.size;
+        if segment.size == 0 || end_inclusive < segment.start || start >= segment_end {
+            continue;
+        }
+        let from = start.max(segment.start) - segment.start;
+        let to = end_inclusive.min(segment_end - 1) - segment.start;
+        let (data, _) = range_request(client, segment.url.clone(), from, to).await?;
+        out.extend_from_slice(&data);
+    }
+    Ok((out, Some(total)))
 }
 
 fn looks_like_zip(filename: &str) -> bool {
@@ -1184,7 +2007,10 @@ async fn get_zip_index(
 ) -> AppResult<Arc<ZipIndex>> {
     let trimmed = content_url.trim();
     if trimmed.is_empty() {
-        return Err(AppError::Invalid("Missing content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingContentUrl,
+            &[],
+        )));
     }
     {
         let guard = cache
@@ -1196,10 +2022,13 @@ async fn get_zip_index(
         }
     }
 
-    let url =
-        Url::parse(trimmed).map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
+    let url = Url::parse(trimmed)
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
     if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
     }
 
     let index = Arc::new(build_zip_index(client, url).await?);
@@ -1214,23 +2043,45 @@ async fn get_zip_index(
 fn find_zip_entry<'a>(index: &'a ZipIndex, entry_name: &str) -> AppResult<&'a ZipEntryIndex> {
     let name = entry_name.trim();
     if name.is_empty() {
-        return Err(AppError::Invalid("Missing ZIP entry name.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingZipEntryName,
+            &[],
+        )));
     }
     index
         .entries
         .iter()
         .find(|e| e.name == name)
-        .ok_or_else(|| AppError::Missing(format!("Entry '{name}' not found in ZIP.")))
+        .ok_or_else(|| {
+            AppError::Missing(messages::text(
+                Message::ZipEntryNotFound,
+                &[("name", &name)],
+            ))
+        })
 }
 
 #[tauri::command]
 pub async fn zenodo_record_summary(
     client: State<'_, ZenodoClient>,
     input: String,
+    force_refresh: Option<bool>,
 ) -> AppResult<ZenodoRecordSummary> {
     let (base_url, record_id) = extract_record_id(&input)?;
     let api_url = api_record_url(&base_url, record_id)?;
-    let record: ZenodoRecordResponse = get_json(&client.http, api_url).await?;
+    let force_refresh = force_refresh.unwrap_or(false);
+    // Zenodo redirects a concept id to its newest version; other InvenioRDM hosts may only expose
+    // that through `versions/latest`.
+    let record = match client.fetch_record(api_url.clone(), force_refresh).await {
+        Ok(record) => record,
+        Err(err) => {
+            let mut latest_url = api_url;
+            latest_url.set_path(&format!("/api/records/{record_id}/versions/latest"));
+            client
+                .fetch_record(latest_url, force_refresh)
+                .await
+                .map_err(|_| err)?
+        }
+    };
 
     let creators = record.metadata.creators.unwrap_or_default();
     let record_url = record
@@ -1256,6 +2107,10 @@ pub async fn zenodo_record_summary(
 
     Ok(ZenodoRecordSummary {
         record_id: record.id,
+        requested_record_id: record_id,
+        concept_record_id: record.conceptrecid.as_ref().and_then(json_u64),
+        concept_doi: record.conceptdoi,
+        sandbox: base_url.host_str() == Some(SANDBOX_HOST),
         title: record.metadata.title,
         doi: record.doi,
         doi_url: record.doi_url,
@@ -1268,60 +2123,373 @@ pub async fn zenodo_record_summary(
     })
 }
 
+/// Lists every version of the record's concept, newest first, so an older version can be picked
+/// instead of the latest one a concept id resolves to.
+#[tauri::command]
+pub async fn zenodo_record_versions(
+    client: State<'_, ZenodoClient>,
+    input: String,
+) -> AppResult<Vec<ZenodoRecordVersion>> {
+    let (base_url, record_id) = extract_record_id(&input)?;
+    let mut url = api_record_url(&base_url, record_id)?;
+    url.set_path(&format!("/api/records/{record_id}/versions"));
+
+    let mut versions: Vec<ZenodoRecordVersion> = Vec::new();
+    for page in 1..=MAX_VERSION_PAGES {
+        let mut page_url = url.clone();
+        page_url
+            .query_pairs_mut()
+            .append_pair("size", &VERSIONS_PAGE_SIZE.to_string())
+            .append_pair("page", &page.to_string())
+            .append_pair("all_versions", "true");
+        let value: serde_json::Value = get_json(&client.http, page_url).await?;
+        let hits = value
+            .pointer("/hits/hits")
+            .and_then(|h| h.as_array())
+            .cloned()
+            .unwrap_or_default();
+        // InvenioRDM reports the total as a number; older Zenodo as `{"value": n}`.
+        let total = value
+            .pointer("/hits/total")
+            .and_then(|t| json_u64(t).or_else(|| t.get("value").and_then(json_u64)));
+        versions.extend(hits.iter().filter_map(|hit| {
+            let text = |ptr: &str| hit.pointer(ptr).and_then(|v| v.as_str()).map(String::from);
+            Some(ZenodoRecordVersion {
+                record_id: hit.get("id").and_then(json_u64)?,
+                version: text("/metadata/version"),
+                publication_date: text("/metadata/publication_date"),
+                doi: text("/doi").or_else(|| text("/pids/doi/identifier")),
+                is_latest: false,
+            })
+        }));
+        let last_page = (hits.len() as u64) < VERSIONS_PAGE_SIZE
+            || total.is_some_and(|total| page * VERSIONS_PAGE_SIZE >= total);
+        if last_page {
+            break;
+        }
+    }
+    versions.sort_by(|a, b| b.record_id.cmp(&a.record_id));
+    if let Some(latest) = versions.first_mut() {
+        latest.is_latest = true;
+    }
+    Ok(versions)
+}
+
+/// Fetches and parses any `croissant.json` / `dataset_infos.json` attached to a record, so the
+/// declared features, licenses and record counts can be checked before downloading the data.
+#[tauri::command]
+pub async fn zenodo_declared_metadata(
+    client: State<'_, ZenodoClient>,
+    input: String,
+) -> AppResult<Vec<DeclaredMetadata>> {
+    let (base_url, record_id) = extract_record_id(&input)?;
+    let api_url = api_record_url(&base_url, record_id)?;
+    let record = client.fetch_record(api_url, false).await?;
+
+    let mut out = Vec::new();
+    for f in record.files.unwrap_or_default() {
+        if !croissant::is_declared_metadata_file(&f.key) {
+            continue;
+        }
+        if f.size == 0 || f.size > croissant::MAX_DECLARED_METADATA_BYTES {
+            continue;
+        }
+        let Ok(url) = Url::parse(&f.links.content) else {
+            continue;
+        };
+        if !allowed_content_url(&url) {
+            continue;
+        }
+        let (data, _) = range_request(&client.http, url, 0, f.size - 1).await?;
+        out.push(croissant::parse_declared_metadata(&f.key, &data)?);
+    }
+    Ok(out)
+}
+
+/// Returns LICENSE, README and DATASHEET files attached to a record, plus the license the record
+/// metadata declares.
+#[tauri::command]
+pub async fn zenodo_record_docs(
+    client: State<'_, ZenodoClient>,
+    input: String,
+) -> AppResult<DatasetDocsResponse> {
+    let (base_url, record_id) = extract_record_id(&input)?;
+    let api_url = api_record_url(&base_url, record_id)?;
+    let record = client.fetch_record(api_url.clone(), false).await?;
+    let declared_license = record.metadata.license.as_ref().and_then(|v| match v {
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => other
+            .get("id")
+            .or_else(|| other.get("title"))
+            .and_then(|id| id.as_str())
+            .map(|s| s.to_string()),
+    });
+
+    let mut documents = Vec::new();
+    for f in record.files.unwrap_or_default() {
+        let Some(kind) = dataset_docs::doc_kind(&f.key) else {
+            continue;
+        };
+        let Ok(url) = Url::parse(&f.links.content) else {
+            continue;
+        };
+        if !allowed_content_url(&url) {
+            continue;
+        }
+        let data = if f.size == 0 {
+            Vec::new()
+        } else {
+            let end = f.size.min(dataset_docs::MAX_DOC_BYTES) - 1;
+            range_request(&client.http, url, 0, end).await?.0
+        };
+        documents.push(dataset_docs::document_from_bytes(
+            kind,
+            f.key,
+            f.size,
+            &data,
+            Some(f.links.content),
+        ));
+    }
+    Ok(DatasetDocsResponse::new(
+        api_url.to_string(),
+        declared_license,
+        documents,
+    ))
+}
+
+fn local_filename_for_key(key: &str) -> AppResult<String> {
+    let name: String = key
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if name.is_empty() || name == "." || name == ".." {
+        return Err(AppError::Invalid(messages::text(
+            Message::UnsafeFileName,
+            &[("key", &key)],
+        )));
+    }
+    Ok(name)
+}
+
+async fn download_record_file(
+    app: &AppHandle,
+    http: &reqwest::Client,
+    job_id: &str,
+    dest_dir: &std::path::Path,
+    file: &ZenodoFileEntry,
+    report: &mut ZenodoDownloadedFile,
+) -> AppResult<()> {
+    let url = Url::parse(&file.links.content)
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
+    if !allowed_content_url(&url) {
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
+    }
+    let dest = dest_dir.join(local_filename_for_key(&file.key)?);
+    report.path = dest.display().to_string();
+
+    if std::fs::metadata(&dest).is_ok_and(|m| m.len() == file.size) {
+        let verified = match file.checksum.clone() {
+            Some(expected) => downloads::verify_checksum(dest.clone(), expected).await?,
+            None => None,
+        };
+        if verified != Some(false) {
+            report.status = "skipped".into();
+            report.checksum_verified = verified;
+            return Ok(());
+        }
+    }
+
+    report.resumed =
+        downloads::download_to_file(http, app, job_id, url, &dest, Some(file.size)).await?;
+    let verified = match file.checksum.clone() {
+        Some(expected) => downloads::verify_checksum(dest.clone(), expected).await?,
+        None => None,
+    };
+    report.checksum_verified = verified;
+    if verified == Some(false) {
+        let _ = std::fs::remove_file(&dest);
+        return Err(AppError::Remote(messages::text(
+            Message::ChecksumMismatch,
+            &[("file", &file.key)],
+        )));
+    }
+    report.status = "downloaded".into();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn zenodo_download_record(
+    app: AppHandle,
+    client: State<'_, ZenodoClient>,
+    input: String,
+    dest_dir: String,
+) -> AppResult<ZenodoRecordDownloadReport> {
+    let (base_url, record_id) = extract_record_id(&input)?;
+    let dest_dir = PathBuf::from(dest_dir.trim());
+    if dest_dir.as_os_str().is_empty() {
+        return Err(AppError::Invalid(messages::text(
+            Message::ChooseDestinationFolder,
+            &[],
+        )));
+    }
+    read_only::ensure_writable(&dest_dir, "record download")?;
+    std::fs::create_dir_all(&dest_dir)?;
+
+    let api_url = api_record_url(&base_url, record_id)?;
+    let record = client.fetch_record(api_url, false).await?;
+    let job_id = format!("zenodo-{record_id}");
+
+    let mut files = Vec::new();
+    for f in record.files.unwrap_or_default() {
+        let mut report = ZenodoDownloadedFile {
+            key: f.key.clone(),
+            path: String::new(),
+            size: f.size,
+            status: "failed".into(),
+            resumed: false,
+            checksum_verified: None,
+            error: None,
+        };
+        if let Err(err) = download_record_file(
+            &app,
+            &client.download_http,
+            &job_id,
+            &dest_dir,
+            &f,
+            &mut report,
+        )
+        .await
+        {
+            report.status = "failed".into();
+            report.error = Some(err.to_string());
+        }
+        files.push(report);
+    }
+
+    let ok = files.iter().all(|f| f.error.is_none());
+    Ok(ZenodoRecordDownloadReport {
+        record_id: record.id,
+        dest_dir: dest_dir.display().to_string(),
+        files,
+        ok,
+    })
+}
+
 #[tauri::command]
 pub async fn zenodo_peek_file(
     client: State<'_, ZenodoClient>,
     content_url: String,
+    offset: Option<u64>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
 ) -> AppResult<FieldPreview> {
+    let limits = PreviewLimits::new(text_chars, hex_bytes);
     let trimmed = content_url.trim();
-    let url =
-        Url::parse(trimmed).map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
+    let url = Url::parse(trimmed)
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
     if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
     }
 
-    let end = (PEEK_BYTES as u64).saturating_sub(1);
-    let (data, total_size) = range_request(&client.http, url.clone(), 0, end).await?;
-    let preview_text = preview_utf8_text(&data);
+    let start = offset.unwrap_or(0);
+    let end = start.saturating_add((limits.window(PEEK_BYTES) as u64).saturating_sub(1));
+    let (data, total_size) = range_request(&client.http, url.clone(), start, end).await?;
 
-    let guessed_ext = url
+    let file_name = url
         .path_segments()
         .and_then(|segments| {
             let segs = segments.filter(|s| !s.is_empty()).collect::<Vec<_>>();
             let idx = segs.iter().position(|s| *s == "files")?;
-            ext_from_filename(segs.get(idx + 1).copied().unwrap_or(""))
+            segs.get(idx + 1).map(|s| s.to_string())
         })
-        .or_else(|| infer::get(&data).map(|t| t.extension().to_string()));
-
-    let hex_snippet = hex_encode(data.iter().take(48).copied().collect::<Vec<u8>>());
-    let size_u32 = total_size.unwrap_or(0).min(u32::MAX as u64) as u32;
+        .unwrap_or_default();
+    let size = total_size.unwrap_or(0);
 
-    let is_binary = preview_text.is_none();
-    Ok(FieldPreview {
-        preview_text,
-        hex_snippet,
-        guessed_ext,
-        is_binary,
-        size: size_u32,
+    // Metadata parsing and decoder plugins are CPU-bound; keep them off the async runtime.
+    tauri::async_runtime::spawn_blocking(move || {
+        let (preview_text, text_length) = limits.text(&data).unzip();
+        let guessed_ext = ext_from_filename(&file_name).or_else(|| magic::sniff_ext(&data));
+        let is_binary = preview_text.is_none();
+        let mut preview = FieldPreview {
+            preview_text,
+            text_length,
+            hex_snippet: limits.hex(&data),
+            guessed_ext,
+            is_binary,
+            size,
+            metadata: metadata::extract(&data),
+            plugin: None,
+            kind: PreviewKind::Binary,
+            compression: None,
+        };
+        if let Some(rule) = decode_override(url.as_str(), &file_name) {
+            rule.apply_to_preview(&data, &mut preview);
+        }
+        decoder_plugins::apply(&data, &mut preview);
+        preview.kind = PreviewKind::detect(&data, &preview);
+        preview
     })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))
+}
+
+/// Last `length` bytes of a Zenodo file (64 KiB by default), for logs and JSONL dumps that are
+/// checked at the end.
+#[tauri::command]
+pub async fn zenodo_peek_tail(
+    client: State<'_, ZenodoClient>,
+    content_url: String,
+    length: Option<u32>,
+) -> AppResult<TailPreview> {
+    let url = Url::parse(content_url.trim())
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
+    if !allowed_content_url(&url) {
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
+    }
+    let (data, start, total) =
+        suffix_range_request(&client.http, url, tail_preview::tail_len(length)).await?;
+    Ok(TailPreview::new(&data, start, total))
 }
 
 #[tauri::command]
 pub async fn zenodo_open_file(
+    app: AppHandle,
     client: State<'_, ZenodoClient>,
     content_url: String,
     filename: String,
     opener_app_path: Option<String>,
 ) -> AppResult<OpenLeafResponse> {
     let trimmed = content_url.trim();
-    let url =
-        Url::parse(trimmed).map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
+    let url = Url::parse(trimmed)
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
     if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
     }
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
 
     let (_head, total_size) = range_request(&client.http, url.clone(), 0, 0).await?;
@@ -1338,15 +2506,15 @@ pub async fn zenodo_open_file(
 
     if total_size == 0 || total_size > MAX_INLINE_DOWNLOAD_BYTES {
         let opened = open::that_detached(trimmed).is_ok();
-        let size_u32 = total_size.min(u32::MAX as u64) as u32;
+        let size = total_size;
         let message = if opened {
-            format!("Opened download URL ({size_u32} bytes) in your browser.")
+            messages::text(Message::OpenedInBrowser, &[("size", &size)])
         } else {
-            "Unable to open download URL.".into()
+            messages::text(Message::CouldNotOpenUrl, &[])
         };
         return Ok(OpenLeafResponse {
             path: trimmed.to_string(),
-            size: size_u32,
+            size,
             ext,
             opened,
             needs_opener: false,
@@ -1354,24 +2522,6 @@ pub async fn zenodo_open_file(
         });
     }
 
-    let res = client
-        .http
-        .get(url.clone())
-        .send()
-        .await
-        .map_err(|e| AppError::Remote(format!("download failed: {e}")))?;
-    let status = res.status();
-    if !status.is_success() {
-        return Err(AppError::Remote(format!(
-            "download HTTP {status} from {url}"
-        )));
-    }
-    let bytes = res
-        .bytes()
-        .await
-        .map_err(|e| AppError::Remote(format!("download read failed: {e}")))?;
-    let size_u32 = (bytes.len() as u64).min(u32::MAX as u64) as u32;
-
     let record_id = record_id_from_content_url(&url).unwrap_or_else(|| "unknown".into());
     let temp_dir = std::env::temp_dir()
         .join("dataset-inspector")
@@ -1383,41 +2533,54 @@ pub async fn zenodo_open_file(
         .rsplit_once('.')
         .map(|(s, _)| s)
         .unwrap_or(&sanitized);
-    let out = temp_dir.join(format!(
-        "{}-r{}-{}.{}",
+    let base = format!(
+        "{}-r{}-{}",
         sanitize(url.host_str().unwrap_or("zenodo")),
         sanitize(&record_id),
-        stem,
-        ext
-    ));
-    std::fs::write(&out, &bytes)?;
-
-    let mut opened = false;
-    let mut open_error = None::<String>;
-    if let Some(app_path) = opener_app_path.as_deref() {
-        match open_with::open_with_app_detached(&out, app_path) {
-            Ok(()) => opened = true,
-            Err(err) => open_error = Some(err),
-        }
-    }
-    if !opened {
-        if let Err(err) = open::that_detached(&out) {
-            open_error = Some(err.to_string());
-        } else {
-            opened = true;
-        }
-    }
+        stem
+    );
+    let out = temp_names::temp_file(&temp_dir, &base, &ext);
+
+    let size = if offline::is_enabled() {
+        // A previous open left the file in the temp dir; reuse it instead of downloading.
+        let meta = std::fs::metadata(&out).map_err(|_| offline::not_cached(&url))?;
+        meta.len()
+    } else {
+        let permit = http_limit::acquire(&url).await?;
+        let res = throttle::send(&url, "download", || client.http.get(url.clone())).await?;
+        drop(permit);
+        let status = res.status();
+        if !status.is_success() {
+            return Err(AppError::Remote(messages::text(
+                Message::DownloadHttpStatus,
+                &[("status", &status), ("url", &url)],
+            )));
+        }
+        let bytes = res.bytes().await.map_err(|e| {
+            AppError::Remote(messages::text(
+                Message::DownloadReadFailed,
+                &[("error", &e)],
+            ))
+        })?;
+        std::fs::write(&out, &bytes)?;
+        bytes.len() as u64
+    };
+
+    let opened = open_with::open_extracted(&app, &out, opener_app_path.as_deref()).is_ok();
 
-    let base = format!("{} ({} bytes)", out.display(), size_u32);
+    let base = messages::text(
+        Message::FileWithSize,
+        &[("path", &out.display()), ("size", &size)],
+    );
     let mut message = base;
-    let needs_opener = !opened && open_error.is_some();
+    let needs_opener = !opened;
     if needs_opener {
-        message.push_str(" · no default app found, choose an app to open it");
+        message.push_str(&messages::text(Message::NoDefaultApp, &[]));
     }
 
     Ok(OpenLeafResponse {
         path: out.display().to_string(),
-        size: size_u32,
+        size,
         ext,
         opened,
         needs_opener,
@@ -1434,12 +2597,16 @@ pub async fn zenodo_zip_list_entries(
 ) -> AppResult<Vec<ZenodoZipEntrySummary>> {
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
     if !looks_like_zip(&filename) {
-        return Err(AppError::Invalid(
-            "Selected file is not a ZIP archive.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::NotZipArchive,
+            &[],
+        )));
     }
     let index = get_zip_index(&client.http, &cache, &content_url).await?;
     Ok(index
@@ -1456,6 +2623,71 @@ pub async fn zenodo_zip_list_entries(
         .collect())
 }
 
+/// Paged ZIP listing with optional prefix/glob/extension filters, sorting, and a tree mode that
+/// lists one directory level at a time. Folded directories report cumulative sizes.
+#[tauri::command]
+pub async fn zenodo_zip_list_entries_paged(
+    client: State<'_, ZenodoClient>,
+    cache: State<'_, ZenodoZipIndexCache>,
+    content_url: String,
+    filename: String,
+    offset: Option<u32>,
+    length: Option<u32>,
+    query: Option<ArchiveListQuery>,
+) -> AppResult<ZenodoZipEntryListResponse> {
+    let filename = filename.trim().to_string();
+    if filename.is_empty() {
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
+    }
+    if !looks_like_zip(&filename) {
+        return Err(AppError::Invalid(messages::text(
+            Message::NotZipArchive,
+            &[],
+        )));
+    }
+    let offset = offset.unwrap_or(0);
+    let length = length
+        .unwrap_or(ZIP_DEFAULT_PAGE_SIZE)
+        .clamp(1, ZIP_MAX_PAGE_SIZE);
+    let index = get_zip_index(&client.http, &cache, &content_url).await?;
+    let query = query.unwrap_or_default();
+    let items = archive_listing::list(&index.entries, &query);
+
+    let entries = items
+        .iter()
+        .skip(offset as usize)
+        .take(length as usize)
+        .map(
+            |item| match item.source.and_then(|i| index.entries.get(i)) {
+                Some(e) => ZenodoZipEntrySummary {
+                    name: e.name.clone(),
+                    method: e.method,
+                    compressed_size: e.compressed_size,
+                    uncompressed_size: e.uncompressed_size,
+                    is_dir: e.is_dir,
+                },
+                None => ZenodoZipEntrySummary {
+                    name: item.name.clone(),
+                    method: 0,
+                    compressed_size: item.stored_size,
+                    uncompressed_size: item.size,
+                    is_dir: item.is_dir,
+                },
+            },
+        )
+        .collect();
+    Ok(ZenodoZipEntryListResponse {
+        offset,
+        length,
+        entries,
+        num_matching: items.len().min(u32::MAX as usize) as u32,
+        num_entries_total: index.entries.len().min(u32::MAX as usize) as u32,
+    })
+}
+
 fn local_header_data_offset(local_header: &[u8]) -> AppResult<u64> {
     if local_header.len() < 30 || read_u32_le(local_header, 0)? != 0x0403_4b50 {
         return Err(AppError::Invalid("Invalid ZIP local header.".into()));
@@ -1465,22 +2697,30 @@ fn local_header_data_offset(local_header: &[u8]) -> AppResult<u64> {
     Ok(30 + name_len + extra_len)
 }
 
+/// Up to `window` bytes of an entry's uncompressed data starting at `skip`. Deflated entries are
+/// inflated from the start, so windows deep into large entries hit the compressed-fetch cap.
 async fn read_zip_entry_preview_bytes(
     client: &reqwest::Client,
-    url: Url,
+    index: &ZipIndex,
     entry: &ZipEntryIndex,
+    skip: u64,
+    window: usize,
 ) -> AppResult<Vec<u8>> {
     if entry.is_dir {
-        return Err(AppError::Invalid("ZIP entry is a directory.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::ZipEntryIsDirectory,
+            &[],
+        )));
     }
     if entry.flags & 1 == 1 {
-        return Err(AppError::Invalid(
-            "Encrypted ZIP entries are not supported.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::EncryptedZipEntry,
+            &[],
+        )));
     }
-    let (local_header, _total) = range_request(
+    let (local_header, _total) = zip_range(
         client,
-        url.clone(),
+        index,
         entry.local_header_offset,
         entry.local_header_offset + 64,
     )
@@ -1491,7 +2731,7 @@ async fn read_zip_entry_preview_bytes(
         .checked_add(data_offset)
         .ok_or_else(|| AppError::Invalid("ZIP offset overflow.".into()))?;
 
-    if entry.compressed_size == 0 {
+    if entry.compressed_size == 0 || skip >= entry.uncompressed_size {
         return Ok(Vec::new());
     }
 
@@ -1499,18 +2739,21 @@ async fn read_zip_entry_preview_bytes(
         let end = data_start
             .checked_add(entry.compressed_size.saturating_sub(1))
             .ok_or_else(|| AppError::Invalid("ZIP range overflow.".into()))?;
-        let want_end = data_start
-            .checked_add((PEEK_BYTES as u64).saturating_sub(1))
+        let window_start = data_start
+            .checked_add(skip)
+            .ok_or_else(|| AppError::Invalid("ZIP range overflow.".into()))?;
+        let want_end = window_start
+            .checked_add((window as u64).saturating_sub(1))
             .ok_or_else(|| AppError::Invalid("ZIP range overflow.".into()))?
             .min(end);
-        let (data, _total) = range_request(client, url, data_start, want_end).await?;
+        let (data, _total) = zip_range(client, index, window_start, want_end).await?;
         return Ok(data);
     }
 
     if entry.method != 8 {
-        return Err(AppError::Invalid(format!(
-            "Unsupported ZIP compression method: {}",
-            entry.method
+        return Err(AppError::Invalid(messages::text(
+            Message::UnsupportedZipCompression,
+            &[("method", &entry.method)],
         )));
     }
 
@@ -1520,18 +2763,16 @@ async fn read_zip_entry_preview_bytes(
     let mut fetched: u64 = 0;
     let mut chunk_start = data_start;
     let mut remaining = entry.compressed_size;
+    let mut to_skip = skip;
 
-    while remaining > 0
-        && (output.len() as u64) < PEEK_BYTES as u64
-        && fetched < ZIP_PREVIEW_MAX_COMPRESSED_BYTES
-    {
+    while remaining > 0 && output.len() < window && fetched < ZIP_PREVIEW_MAX_COMPRESSED_BYTES {
         let chunk_len = remaining
             .min(256 * 1024)
             .min(ZIP_PREVIEW_MAX_COMPRESSED_BYTES - fetched);
         let chunk_end = chunk_start
             .checked_add(chunk_len.saturating_sub(1))
             .ok_or_else(|| AppError::Invalid("ZIP range overflow.".into()))?;
-        let (chunk, _total) = range_request(client, url.clone(), chunk_start, chunk_end).await?;
+        let (chunk, _total) = zip_range(client, index, chunk_start, chunk_end).await?;
         if chunk.is_empty() {
             break;
         }
@@ -1540,7 +2781,7 @@ async fn read_zip_entry_preview_bytes(
         remaining = remaining.saturating_sub(chunk.len() as u64);
 
         let mut input = chunk.as_slice();
-        while !input.is_empty() && output.len() < PEEK_BYTES {
+        while !input.is_empty() && output.len() < window {
             let mut buf = [0u8; 8192];
             let before_in = decompressor.total_in();
             let before_out = decompressor.total_out();
@@ -1549,9 +2790,11 @@ async fn read_zip_entry_preview_bytes(
                 .map_err(|e| AppError::Invalid(format!("ZIP inflate failed: {e}")))?;
             let consumed = (decompressor.total_in() - before_in) as usize;
             let produced = (decompressor.total_out() - before_out) as usize;
-            if produced > 0 {
-                let take = (PEEK_BYTES - output.len()).min(produced);
-                output.extend_from_slice(&buf[..take]);
+            let skipped = to_skip.min(produced as u64) as usize;
+            to_skip -= skipped as u64;
+            if produced > skipped {
+                let take = (window - output.len()).min(produced - skipped);
+                output.extend_from_slice(&buf[skipped..skipped + take]);
             }
             if consumed == 0 && produced == 0 {
                 break;
@@ -1563,6 +2806,12 @@ async fn read_zip_entry_preview_bytes(
         }
     }
 
+    if to_skip > 0 && remaining > 0 {
+        return Err(AppError::Invalid(messages::text(
+            Message::PreviewOffsetTooFar,
+            &[],
+        )));
+    }
     Ok(output)
 }
 
@@ -1573,80 +2822,211 @@ pub async fn zenodo_zip_peek_entry(
     content_url: String,
     filename: String,
     entry_name: String,
+    offset: Option<u64>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
 ) -> AppResult<FieldPreview> {
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
     if !looks_like_zip(&filename) {
-        return Err(AppError::Invalid(
-            "Selected file is not a ZIP archive.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::NotZipArchive,
+            &[],
+        )));
     }
     let index = get_zip_index(&client.http, &cache, &content_url).await?;
     let entry = find_zip_entry(index.as_ref(), &entry_name)?;
-    let url = Url::parse(content_url.trim())
-        .map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
-    if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
-    }
+    zip_entry_preview(
+        &client.http,
+        &index,
+        entry,
+        &content_url,
+        offset.unwrap_or(0),
+        PreviewLimits::new(text_chars, hex_bytes),
+    )
+    .await
+}
 
-    let data = read_zip_entry_preview_bytes(&client.http, url, entry).await?;
-    let preview_text = preview_utf8_text(&data);
-    let guessed_ext = ext_from_filename(&entry.name)
-        .or_else(|| infer::get(&data).map(|t| t.extension().to_string()));
-    let hex_snippet = hex_encode(data.iter().take(48).copied().collect::<Vec<u8>>());
-    let size_u32 = entry.uncompressed_size.min(u32::MAX as u64) as u32;
+/// Preview of a ZIP entry's window starting at `offset`, after any decode override.
+async fn zip_entry_preview(
+    http: &reqwest::Client,
+    index: &ZipIndex,
+    entry: &ZipEntryIndex,
+    content_url: &str,
+    offset: u64,
+    limits: PreviewLimits,
+) -> AppResult<FieldPreview> {
+    let window = limits.window(PEEK_BYTES);
+    let raw = read_zip_entry_preview_bytes(http, index, entry, offset, window).await?;
+    let entry_name = entry.name.clone();
+    let size = entry.uncompressed_size;
+    let content_url = content_url.to_string();
+    // Decompression, metadata parsing and decoder plugins are CPU-bound; keep them off the
+    // async runtime.
+    tauri::async_runtime::spawn_blocking(move || {
+        let unwrapped = decompress::unwrap(&entry_name, &raw, window);
+        let (name, data) = match &unwrapped {
+            Some(inner) => (inner.inner_name.as_str(), inner.data.as_slice()),
+            None => (entry_name.as_str(), raw.as_slice()),
+        };
+        let (preview_text, text_length) = limits.text(data).unzip();
+        let guessed_ext = ext_from_filename(name).or_else(|| magic::sniff_ext(data));
 
-    let is_binary = preview_text.is_none();
-    Ok(FieldPreview {
-        preview_text,
-        hex_snippet,
-        guessed_ext,
-        is_binary,
-        size: size_u32,
+        let is_binary = preview_text.is_none();
+        let mut preview = FieldPreview {
+            preview_text,
+            text_length,
+            hex_snippet: limits.hex(data),
+            guessed_ext,
+            is_binary,
+            size,
+            metadata: metadata::extract(data),
+            plugin: None,
+            kind: PreviewKind::Binary,
+            compression: unwrapped.as_ref().map(|inner| inner.codec),
+        };
+        if let Some(rule) = decode_override(&content_url, name) {
+            rule.apply_to_preview(data, &mut preview);
+        }
+        decoder_plugins::apply(data, &mut preview);
+        preview.kind = PreviewKind::detect(data, &preview);
+        preview
     })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoGlobPeekEntry {
+    name: String,
+    size: u64,
+    preview: FieldPreview,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoGlobPeekResponse {
+    entries: Vec<ZenodoGlobPeekEntry>,
+    /// More entries may match than were previewed.
+    limit_reached: bool,
 }
 
+fn glob_peek_limit(max_entries: Option<usize>) -> usize {
+    max_entries
+        .unwrap_or(GLOB_PEEK_DEFAULT_ENTRIES)
+        .clamp(1, GLOB_PEEK_MAX_ENTRIES)
+}
+
+/// Previews every ZIP entry matching `glob` (up to `max_entries`), for scanning sidecar files
+/// scattered across an archive in one call.
 #[tauri::command]
-pub async fn zenodo_zip_open_entry(
+pub async fn zenodo_zip_peek_glob(
     client: State<'_, ZenodoClient>,
     cache: State<'_, ZenodoZipIndexCache>,
     content_url: String,
     filename: String,
-    entry_name: String,
-    opener_app_path: Option<String>,
-) -> AppResult<OpenLeafResponse> {
+    glob: String,
+    max_entries: Option<usize>,
+) -> AppResult<ZenodoGlobPeekResponse> {
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
     if !looks_like_zip(&filename) {
-        return Err(AppError::Invalid(
-            "Selected file is not a ZIP archive.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::NotZipArchive,
+            &[],
+        )));
+    }
+    let glob = glob.trim().to_string();
+    if glob.is_empty() {
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingGlobPattern,
+            &[],
+        )));
     }
+    let limit = glob_peek_limit(max_entries);
     let index = get_zip_index(&client.http, &cache, &content_url).await?;
-    let entry = find_zip_entry(index.as_ref(), &entry_name)?.clone();
+    let mut matching = index
+        .entries
+        .iter()
+        .filter(|e| !e.is_dir && archive_listing::glob_match(&glob, &e.name));
+    let mut entries = Vec::new();
+    for entry in matching.by_ref().take(limit) {
+        let preview = zip_entry_preview(
+            &client.http,
+            &index,
+            entry,
+            &content_url,
+            0,
+            PreviewLimits::default(),
+        )
+        .await?;
+        entries.push(ZenodoGlobPeekEntry {
+            name: entry.name.clone(),
+            size: entry.uncompressed_size,
+            preview,
+        });
+    }
+    Ok(ZenodoGlobPeekResponse {
+        entries,
+        limit_reached: matching.next().is_some(),
+    })
+}
+
+/// A ZIP entry's bytes after any decode override, the extension to write it as, and the entry's
+/// full name.
+async fn zip_entry_decoded(
+    http: &reqwest::Client,
+    cache: &ZenodoZipIndexCache,
+    content_url: &str,
+    filename: &str,
+    entry_name: &str,
+) -> AppResult<(Vec<u8>, String, String)> {
+    let filename = filename.trim().to_string();
+    if filename.is_empty() {
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
+    }
+    if !looks_like_zip(&filename) {
+        return Err(AppError::Invalid(messages::text(
+            Message::NotZipArchive,
+            &[],
+        )));
+    }
+    let index = get_zip_index(http, cache, content_url).await?;
+    let entry = find_zip_entry(index.as_ref(), entry_name)?.clone();
     if entry.is_dir {
-        return Err(AppError::Invalid("ZIP entry is a directory.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::ZipEntryIsDirectory,
+            &[],
+        )));
     }
     if entry.uncompressed_size > MAX_INLINE_DOWNLOAD_BYTES
         || entry.compressed_size > MAX_INLINE_DOWNLOAD_BYTES
     {
-        return Err(AppError::Invalid(
-            "ZIP entry is too large to extract locally.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::ZipEntryTooLargeToExtract,
+            &[],
+        )));
     }
     let url = Url::parse(content_url.trim())
-        .map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
-    if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
-    }
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
 
-    let (local_header, _total) = range_request(
-        &client.http,
-        url.clone(),
+    let (local_header, _total) = zip_range(
+        http,
+        &index,
         entry.local_header_offset,
         entry.local_header_offset + 64,
     )
@@ -1660,68 +3040,81 @@ pub async fn zenodo_zip_open_entry(
     let end = data_start
         .checked_add(entry.compressed_size.saturating_sub(1))
         .ok_or_else(|| AppError::Invalid("ZIP range overflow.".into()))?;
-    let (compressed, _total) = range_request(&client.http, url.clone(), data_start, end).await?;
+    let (compressed, _total) = zip_range(http, &index, data_start, end).await?;
 
     let bytes: Vec<u8> = if entry.method == 0 {
         compressed
     } else if entry.method == 8 {
         inflate_deflate_with_limit(&compressed, MAX_INLINE_DOWNLOAD_BYTES)?
     } else {
-        return Err(AppError::Invalid(format!(
-            "Unsupported ZIP compression method: {}",
-            entry.method
+        return Err(AppError::Invalid(messages::text(
+            Message::UnsupportedZipCompression,
+            &[("method", &entry.method)],
         )));
     };
 
+    let (bytes, ext) = match decode_override(url.as_str(), &entry.name) {
+        Some(rule) => rule.decode_for_open(bytes),
+        None => (
+            bytes,
+            ext_from_filename(&entry.name).unwrap_or_else(|| "bin".into()),
+        ),
+    };
+    Ok((bytes, ext, entry.name))
+}
+
+#[tauri::command]
+pub async fn zenodo_zip_open_entry(
+    app: AppHandle,
+    client: State<'_, ZenodoClient>,
+    cache: State<'_, ZenodoZipIndexCache>,
+    content_url: String,
+    filename: String,
+    entry_name: String,
+    opener_app_path: Option<String>,
+) -> AppResult<OpenLeafResponse> {
+    let (bytes, ext, entry_name) =
+        zip_entry_decoded(&client.http, &cache, &content_url, &filename, &entry_name).await?;
+    let filename = filename.trim();
+    let url = Url::parse(content_url.trim())
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
+
     let record_id = record_id_from_content_url(&url).unwrap_or_else(|| "unknown".into());
     let temp_dir = std::env::temp_dir()
         .join("dataset-inspector")
         .join("zenodo");
     std::fs::create_dir_all(&temp_dir)?;
 
-    let ext = ext_from_filename(&entry.name).unwrap_or_else(|| "bin".into());
     let base = format!(
         "{}-r{}-{}",
         sanitize(url.host_str().unwrap_or("zenodo")),
         sanitize(&record_id),
-        sanitize(&filename)
+        sanitize(filename)
     );
-    let entry_filename = entry.name.split('/').last().unwrap_or(entry.name.as_str());
+    let entry_filename = entry_name.split('/').last().unwrap_or(entry_name.as_str());
     let entry_stem_raw = entry_filename
         .rsplit_once('.')
         .map(|(s, _)| s)
         .unwrap_or(entry_filename);
     let entry_stem = sanitize(entry_stem_raw);
-    let out_path = temp_dir.join(format!("{base}-{entry_stem}.{ext}"));
-    std::fs::write(&out_path, &bytes)?;
+    let out_path = temp_names::write(&temp_dir, &format!("{base}-{entry_stem}"), &ext, &bytes)?;
 
-    let mut opened = false;
-    let mut open_error = None::<String>;
-    if let Some(app_path) = opener_app_path.as_deref() {
-        match open_with::open_with_app_detached(&out_path, app_path) {
-            Ok(()) => opened = true,
-            Err(err) => open_error = Some(err),
-        }
-    }
-    if !opened {
-        if let Err(err) = open::that_detached(&out_path) {
-            open_error = Some(err.to_string());
-        } else {
-            opened = true;
-        }
-    }
+    let opened = open_with::open_extracted(&app, &out_path, opener_app_path.as_deref()).is_ok();
 
-    let size_u32 = (bytes.len() as u64).min(u32::MAX as u64) as u32;
-    let base_msg = format!("{} ({} bytes)", out_path.display(), size_u32);
+    let size = bytes.len() as u64;
+    let base_msg = messages::text(
+        Message::FileWithSize,
+        &[("path", &out_path.display()), ("size", &size)],
+    );
     let mut message = base_msg;
-    let needs_opener = !opened && open_error.is_some();
+    let needs_opener = !opened;
     if needs_opener {
-        message.push_str(" · no default app found, choose an app to open it");
+        message.push_str(&messages::text(Message::NoDefaultApp, &[]));
     }
 
     Ok(OpenLeafResponse {
         path: out_path.display().to_string(),
-        size: size_u32,
+        size,
         ext,
         opened,
         needs_opener,
@@ -1729,27 +3122,48 @@ pub async fn zenodo_zip_open_entry(
     })
 }
 
+/// Writes a ZIP entry to `destination_path` instead of opening it from the temp dir.
 #[tauri::command]
-pub async fn zenodo_zip_inline_entry_media(
+pub async fn zenodo_zip_save_entry_as(
     client: State<'_, ZenodoClient>,
     cache: State<'_, ZenodoZipIndexCache>,
     content_url: String,
     filename: String,
     entry_name: String,
-) -> AppResult<InlineMediaResponse> {
+    destination_path: String,
+) -> AppResult<SavedLeafResponse> {
+    let (bytes, ext, _entry_name) =
+        zip_entry_decoded(&client.http, &cache, &content_url, &filename, &entry_name).await?;
+    save_leaf::write_to(&destination_path, &bytes, ext)
+}
+
+async fn zip_entry_media_bytes(
+    client: &ZenodoClient,
+    cache: &ZenodoZipIndexCache,
+    content_url: &str,
+    filename: &str,
+    entry_name: &str,
+) -> AppResult<(Vec<u8>, String)> {
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
     if !looks_like_zip(&filename) {
-        return Err(AppError::Invalid(
-            "Selected file is not a ZIP archive.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::NotZipArchive,
+            &[],
+        )));
     }
-    let index = get_zip_index(&client.http, &cache, &content_url).await?;
-    let entry = find_zip_entry(index.as_ref(), &entry_name)?.clone();
+    let index = get_zip_index(&client.http, cache, content_url).await?;
+    let entry = find_zip_entry(index.as_ref(), entry_name)?.clone();
     if entry.is_dir {
-        return Err(AppError::Invalid("ZIP entry is a directory.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::ZipEntryIsDirectory,
+            &[],
+        )));
     }
     if entry.uncompressed_size > ZIP_INLINE_MEDIA_MAX_BYTES {
         return Err(AppError::Invalid(
@@ -1757,20 +3171,15 @@ pub async fn zenodo_zip_inline_entry_media(
         ));
     }
     if entry.flags & 0x1 != 0 {
-        return Err(AppError::Invalid(
-            "Encrypted ZIP entries are not supported.".into(),
-        ));
-    }
-
-    let url = Url::parse(content_url.trim())
-        .map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
-    if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::EncryptedZipEntry,
+            &[],
+        )));
     }
 
-    let (local_header, _total) = range_request(
+    let (local_header, _total) = zip_range(
         &client.http,
-        url.clone(),
+        &index,
         entry.local_header_offset,
         entry.local_header_offset + 64,
     )
@@ -1784,34 +3193,72 @@ pub async fn zenodo_zip_inline_entry_media(
     let end = data_start
         .checked_add(entry.compressed_size.saturating_sub(1))
         .ok_or_else(|| AppError::Invalid("ZIP range overflow.".into()))?;
-    let (compressed, _total) = range_request(&client.http, url.clone(), data_start, end).await?;
+    let (compressed, _total) = zip_range(&client.http, &index, data_start, end).await?;
 
     let bytes: Vec<u8> = if entry.method == 0 {
         compressed
     } else if entry.method == 8 {
         inflate_deflate_with_limit(&compressed, ZIP_INLINE_MEDIA_MAX_BYTES)?
     } else {
-        return Err(AppError::Invalid(format!(
-            "Unsupported ZIP compression method: {}",
-            entry.method
+        return Err(AppError::Invalid(messages::text(
+            Message::UnsupportedZipCompression,
+            &[("method", &entry.method)],
         )));
     };
 
     let ext = ext_from_filename(&entry.name).unwrap_or_else(|| "bin".into());
+    Ok((bytes, ext))
+}
+
+#[tauri::command]
+pub async fn zenodo_zip_inline_entry_media(
+    client: State<'_, ZenodoClient>,
+    cache: State<'_, ZenodoZipIndexCache>,
+    content_url: String,
+    filename: String,
+    entry_name: String,
+) -> AppResult<InlineMediaResponse> {
+    let (bytes, ext) =
+        zip_entry_media_bytes(&client, &cache, &content_url, &filename, &entry_name).await?;
     let mime = mime_for_ext(&ext).to_string();
     let base64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
     Ok(InlineMediaResponse {
         base64,
         mime,
-        size: (bytes.len() as u64).min(u32::MAX as u64) as u32,
+        size: bytes.len() as u64,
+        ext,
+    })
+}
+
+/// Like [`zenodo_zip_inline_entry_media`], but serves the bytes over the media protocol.
+#[tauri::command]
+pub async fn zenodo_zip_stream_entry_media(
+    client: State<'_, ZenodoClient>,
+    cache: State<'_, ZenodoZipIndexCache>,
+    media: State<'_, MediaStore>,
+    content_url: String,
+    filename: String,
+    entry_name: String,
+) -> AppResult<StreamedMediaResponse> {
+    let (bytes, ext) =
+        zip_entry_media_bytes(&client, &cache, &content_url, &filename, &entry_name).await?;
+    let mime = mime_for_ext(&ext).to_string();
+    let size = bytes.len() as u64;
+    let token = media.register(bytes, mime.clone())?;
+    Ok(StreamedMediaResponse {
+        token,
+        mime,
+        size,
         ext,
     })
 }
 
+/// Reads up to `read_at_most` bytes of a member, starting `skip` bytes into it.
 fn read_tar_member_with_limit(
     url: Url,
     filename_hint: String,
     member_name: String,
+    skip: u64,
     read_at_most: u64,
     hard_limit: Option<u64>,
 ) -> AppResult<(Vec<u8>, u64)> {
@@ -1820,10 +3267,10 @@ fn read_tar_member_with_limit(
         return Err(AppError::Invalid("Missing TAR entry name.".into()));
     }
 
-    let reader = open_remote_tar_reader(url, &filename_hint)?;
+    let reader = open_remote_tar_reader(url, &filename_hint, None)?;
     let mut archive = tar::Archive::new(reader);
     for entry in archive.entries()? {
-        let entry = entry?;
+        let mut entry = entry?;
         if entry.header().entry_type().is_dir() {
             continue;
         }
@@ -1841,8 +3288,12 @@ fn read_tar_member_with_limit(
             }
         }
 
+        let skip = skip.min(size);
+        if skip > 0 {
+            std::io::copy(&mut (&mut entry).take(skip), &mut std::io::sink())?;
+        }
         let mut buf = Vec::new();
-        let cap = read_at_most.min(size);
+        let cap = read_at_most.min(size - skip);
         entry.take(cap).read_to_end(&mut buf)?;
         return Ok((buf, size));
     }
@@ -1862,19 +3313,26 @@ pub async fn zenodo_tar_list_entries_paged(
 ) -> AppResult<ZenodoTarEntryListResponse> {
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
     if !looks_like_tar(&filename) {
-        return Err(AppError::Invalid(
-            "Selected file is not a supported TAR archive.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::NotTarArchive,
+            &[],
+        )));
     }
 
     let trimmed = content_url.trim();
-    let url =
-        Url::parse(trimmed).map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
+    let url = Url::parse(trimmed)
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
     if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
     }
 
     let offset = offset.unwrap_or(0);
@@ -1884,10 +3342,9 @@ pub async fn zenodo_tar_list_entries_paged(
         .min(TAR_MAX_PAGE_SIZE);
 
     let state = cache.get_or_create(&content_url, &filename)?;
-    tauri::async_runtime::spawn_blocking(move || {
-        let mut guard = state
-            .lock()
-            .map_err(|_| AppError::Task("tar scan lock poisoned".into()))?;
+    let reset = cache.resetter(&content_url);
+    supervisor::run_blocking("the TAR scan", reset, move || {
+        let mut guard = scan_cache::lock_state(&state)?;
         let start = offset as usize;
         let end = start.saturating_add(length as usize);
         guard.ensure_scanned_for_page(end, start, end)?;
@@ -1915,37 +3372,255 @@ pub async fn zenodo_tar_list_entries_paged(
         })
     })
     .await
+}
+
+fn archive_tree_root<E: ArchiveEntry>(
+    entries: &[E],
+    directory: &str,
+    depth: u32,
+) -> AppResult<ArchiveTreeNode> {
+    archive_listing::build_tree(entries, directory, depth)
+        .ok_or_else(|| AppError::Missing(format!("Directory '{directory}' not found in archive.")))
+}
+
+/// Directory tree with per-directory file/subdirectory counts and cumulative sizes for a ZIP or
+/// TAR. ZIP trees are complete; TAR trees cover what the incremental scan has reached.
+#[tauri::command]
+pub async fn zenodo_archive_tree(
+    client: State<'_, ZenodoClient>,
+    zip_cache: State<'_, ZenodoZipIndexCache>,
+    tar_cache: State<'_, ZenodoTarScanCache>,
+    content_url: String,
+    filename: String,
+    directory: Option<String>,
+    depth: Option<u32>,
+) -> AppResult<ZenodoArchiveTreeResponse> {
+    let filename = filename.trim().to_string();
+    if filename.is_empty() {
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
+    }
+    let directory = directory.unwrap_or_default();
+    let depth = depth.unwrap_or(ARCHIVE_TREE_DEFAULT_DEPTH);
+
+    if looks_like_zip(&filename) {
+        let index = get_zip_index(&client.http, &zip_cache, &content_url).await?;
+        let root = archive_tree_root(&index.entries, &directory, depth)?;
+        return Ok(ZenodoArchiveTreeResponse {
+            root,
+            partial: false,
+        });
+    }
+    if !looks_like_tar(&filename) {
+        return Err(AppError::Invalid(
+            "Selected file is not a ZIP or TAR archive.".into(),
+        ));
+    }
+
+    let state = tar_cache.get_or_create(&content_url, &filename)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let guard = scan_cache::lock_state(&state)?;
+        let root = archive_tree_root(&guard.entries, &directory, depth)?;
+        Ok(ZenodoArchiveTreeResponse {
+            root,
+            partial: !guard.done,
+        })
+    })
+    .await
     .map_err(|e| AppError::Task(e.to_string()))?
 }
 
+/// Counts and total bytes per file extension for a ZIP or TAR, to show what an archive holds
+/// before browsing it.
+#[tauri::command]
+pub async fn archive_type_summary(
+    client: State<'_, ZenodoClient>,
+    zip_cache: State<'_, ZenodoZipIndexCache>,
+    tar_cache: State<'_, ZenodoTarScanCache>,
+    content_url: String,
+    filename: String,
+) -> AppResult<ArchiveTypeSummaryResponse> {
+    let filename = filename.trim().to_string();
+    if filename.is_empty() {
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
+    }
+    if looks_like_zip(&filename) {
+        let index = get_zip_index(&client.http, &zip_cache, &content_url).await?;
+        let types = archive_listing::type_summary(&index.entries);
+        return Ok(ArchiveTypeSummaryResponse::new(types, false));
+    }
+    if !looks_like_tar(&filename) {
+        return Err(AppError::Invalid(
+            "Selected file is not a ZIP or TAR archive.".into(),
+        ));
+    }
+
+    let state = tar_cache.get_or_create(&content_url, &filename)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let guard = scan_cache::lock_state(&state)?;
+        let types = archive_listing::type_summary(&guard.entries);
+        Ok(ArchiveTypeSummaryResponse::new(types, !guard.done))
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+fn run_background_tar_scan(
+    app: &AppHandle,
+    state: &ScanSlot<ZenodoTarScanState>,
+    content_url: &str,
+    cancel: &AtomicBool,
+) -> AppResult<ZenodoTarScanProgress> {
+    loop {
+        let progress = {
+            let mut guard = scan_cache::lock_state(state)?;
+            let target = guard.entries.len() + TAR_BACKGROUND_CHUNK_ENTRIES;
+            guard.ensure_scanned_for_page(target, 0, 0)?;
+            guard.progress(content_url)
+        };
+        if progress.done {
+            return Ok(progress);
+        }
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(ZenodoTarScanProgress {
+                cancelled: true,
+                ..progress
+            });
+        }
+        let _ = app.emit(TAR_SCAN_PROGRESS_EVENT, progress);
+    }
+}
+
+/// Keeps scanning a remote TAR to the end on a background thread so the total entry count becomes
+/// known and later pages are served from memory. Progress is emitted as
+/// `zenodo://tar-scan-progress`; the scan uses one request slot of the host like any other read.
+/// With the file's Zenodo `checksum`, the archive is hashed as it streams by and the final
+/// progress reports whether it matched, so a corrupt upload shows up without downloading it.
+/// Returns false when a background scan of this archive is already running.
+#[tauri::command]
+pub async fn zenodo_tar_scan_to_end(
+    app: AppHandle,
+    cache: State<'_, ZenodoTarScanCache>,
+    registry: State<'_, JobRegistry>,
+    content_url: String,
+    filename: String,
+    checksum: Option<String>,
+) -> AppResult<bool> {
+    let content_url = content_url.trim().to_string();
+    let state = cache.get_or_create(&content_url, &filename)?;
+    if let Some(checksum) = checksum.filter(|c| !c.trim().is_empty()) {
+        scan_cache::lock_state(&state)?.expected_checksum = Some(checksum.trim().to_string());
+    }
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut jobs = cache
+            .background
+            .lock()
+            .map_err(|_| AppError::Task("tar scan job lock poisoned".into()))?;
+        if jobs.contains_key(&content_url) {
+            return Ok(false);
+        }
+        jobs.insert(content_url.clone(), cancel.clone());
+    }
+
+    let jobs = cache.background.clone();
+    let reset = cache.resetter(&content_url);
+    let job = registry.track(cancel.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        let last = supervisor::run_caught("the TAR scan", reset, || {
+            run_background_tar_scan(&app, &state, &content_url, &cancel)
+        })
+        .unwrap_or_else(|e| ZenodoTarScanProgress {
+            content_url: content_url.clone(),
+            entries_scanned: 0,
+            bytes_scanned: 0,
+            done: false,
+            cancelled: false,
+            error: Some(e.to_string()),
+            checksum_verified: None,
+        });
+        if let Ok(mut jobs) = jobs.lock() {
+            jobs.remove(&content_url);
+        }
+        // Unregistered only once the scan has stopped.
+        drop(job);
+        let _ = app.emit(TAR_SCAN_PROGRESS_EVENT, last);
+    });
+    Ok(true)
+}
+
+/// Stops a background scan after its current chunk. The entries read so far stay cached.
+#[tauri::command]
+pub async fn zenodo_tar_cancel_scan(
+    cache: State<'_, ZenodoTarScanCache>,
+    content_url: String,
+) -> AppResult<bool> {
+    let jobs = cache
+        .background
+        .lock()
+        .map_err(|_| AppError::Task("tar scan job lock poisoned".into()))?;
+    let Some(cancel) = jobs.get(content_url.trim()) else {
+        return Ok(false);
+    };
+    cancel.store(true, Ordering::Relaxed);
+    Ok(true)
+}
+
 #[tauri::command]
 pub async fn zenodo_tar_peek_entry(
     cache: State<'_, ZenodoTarScanCache>,
     content_url: String,
     filename: String,
     entry_name: String,
+    offset: Option<u64>,
+    text_chars: Option<usize>,
+    hex_bytes: Option<usize>,
 ) -> AppResult<FieldPreview> {
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
     if !looks_like_tar(&filename) {
-        return Err(AppError::Invalid(
-            "Selected file is not a supported TAR archive.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::NotTarArchive,
+            &[],
+        )));
     }
 
     let trimmed = content_url.trim();
-    let url =
-        Url::parse(trimmed).map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
+    let url = Url::parse(trimmed)
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
     if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
     }
     let entry_name = entry_name.trim().to_string();
     if entry_name.is_empty() {
         return Err(AppError::Invalid("Missing TAR entry name.".into()));
     }
 
+    // Scans only keep previews of member heads, decoded the default way at the default limits.
+    let offset = offset.unwrap_or(0);
+    let limits = PreviewLimits::new(text_chars, hex_bytes);
+    let custom_decode = decode_override(url.as_str(), &entry_name).is_some()
+        || ext_from_filename(&entry_name).is_some_and(|ext| decoder_plugins::handles(&ext));
+    if offset > 0 || custom_decode || limits != PreviewLimits::default() {
+        return tauri::async_runtime::spawn_blocking(move || {
+            peek_remote_tar_member(url, filename, entry_name, offset, limits)
+        })
+        .await
+        .map_err(|e| AppError::Task(e.to_string()))?;
+    }
     if let Ok(state) = cache.get_or_create(&content_url, &filename) {
         let wanted = normalize_member_path_str(&entry_name);
         if let Ok(guard) = state.lock() {
@@ -1956,61 +3631,465 @@ pub async fn zenodo_tar_peek_entry(
     }
 
     tauri::async_runtime::spawn_blocking(move || {
-        let (data, size) =
-            read_tar_member_with_limit(url, filename, entry_name.clone(), PEEK_BYTES as u64, None)?;
-        let preview_text = preview_utf8_text(&data);
-        let guessed_ext = ext_from_filename(&entry_name)
-            .or_else(|| infer::get(&data).map(|t| t.extension().to_string()));
-        let hex_snippet = hex_encode(data.iter().take(48).copied().collect::<Vec<u8>>());
-        let is_binary = preview_text.is_none();
-        Ok(FieldPreview {
-            preview_text,
-            hex_snippet,
-            guessed_ext,
-            is_binary,
-            size: size.min(u32::MAX as u64) as u32,
-        })
+        peek_remote_tar_member(url, filename, entry_name, 0, limits)
     })
     .await
     .map_err(|e| AppError::Task(e.to_string()))?
 }
 
+fn peek_remote_tar_member(
+    url: Url,
+    filename: String,
+    entry_name: String,
+    skip: u64,
+    limits: PreviewLimits,
+) -> AppResult<FieldPreview> {
+    let (data, size) = read_tar_member_with_limit(
+        url.clone(),
+        filename,
+        entry_name.clone(),
+        skip,
+        limits.window(PEEK_BYTES) as u64,
+        None,
+    )?;
+    Ok(tar_member_preview(&url, &entry_name, &data, size, limits))
+}
+
+fn tar_member_preview(
+    url: &Url,
+    entry_name: &str,
+    data: &[u8],
+    size: u64,
+    limits: PreviewLimits,
+) -> FieldPreview {
+    let unwrapped = decompress::unwrap(entry_name, data, limits.window(PEEK_BYTES));
+    let (entry_name, data) = match &unwrapped {
+        Some(inner) => (inner.inner_name.as_str(), inner.data.as_slice()),
+        None => (entry_name, data),
+    };
+    let (preview_text, text_length) = limits.text(data).unzip();
+    let guessed_ext = ext_from_filename(entry_name).or_else(|| magic::sniff_ext(data));
+    let is_binary = preview_text.is_none();
+    let mut preview = FieldPreview {
+        preview_text,
+        text_length,
+        hex_snippet: limits.hex(data),
+        guessed_ext,
+        is_binary,
+        size,
+        metadata: metadata::extract(data),
+        plugin: None,
+        kind: PreviewKind::Binary,
+        compression: unwrapped.as_ref().map(|inner| inner.codec),
+    };
+    if let Some(rule) = decode_override(url.as_str(), entry_name) {
+        rule.apply_to_preview(data, &mut preview);
+    }
+    decoder_plugins::apply(data, &mut preview);
+    preview.kind = PreviewKind::detect(data, &preview);
+    preview
+}
+
+/// What a transfer estimate is for: a preview window or the whole file (or TAR member).
+#[derive(Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferOperation {
+    Peek,
+    Open,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoTransferEstimate {
+    file_size: Option<u64>,
+    /// Bytes expected to be downloaded.
+    bytes: u64,
+    /// Whether `bytes` is only an estimate: an upper bound before a TAR scan reaches the member,
+    /// or scaled by the compression ratio of a compressed TAR.
+    approximate: bool,
+    /// Uncompressed TAR bytes before the member, which have to be streamed past to reach it.
+    tar_offset: Option<u64>,
+    /// Whether `bytes` reaches `warn_bytes`, so the UI should ask before going ahead.
+    needs_confirmation: bool,
+}
+
+/// Bytes a TAR takes for `entry`: its header block and its data padded to whole blocks.
+fn tar_record_bytes(entry: &ZenodoTarEntrySummary) -> u64 {
+    512 + entry.size.div_ceil(512) * 512
+}
+
+/// `(bytes, approximate, tar_offset)` for reading a member of a remote TAR, from what the scan
+/// of that TAR has listed so far. Members are always streamed from the start of the archive.
+fn tar_transfer_estimate(
+    state: &ZenodoTarScanState,
+    entry_name: &str,
+    operation: TransferOperation,
+    file_size: Option<u64>,
+) -> AppResult<(u64, bool, Option<u64>)> {
+    let wanted = normalize_member_path_str(entry_name);
+    if operation == TransferOperation::Peek && state.cached_preview(&wanted).is_some() {
+        return Ok((0, false, None));
+    }
+    let Some(index) = state
+        .entries
+        .iter()
+        .position(|e| normalize_member_path_str(&e.name) == wanted)
+    else {
+        if state.done {
+            return Err(AppError::Missing(format!(
+                "Entry '{wanted}' not found in TAR."
+            )));
+        }
+        // Not reached by the scan yet: anywhere up to the whole archive.
+        return Ok((file_size.unwrap_or(0), true, None));
+    };
+    let tar_offset: u64 = state.entries[..index].iter().map(tar_record_bytes).sum();
+    let member = &state.entries[index];
+    let read = match operation {
+        TransferOperation::Peek => member.size.min(PEEK_BYTES as u64),
+        TransferOperation::Open => member.size,
+    };
+    let tar_bytes = tar_offset + 512 + read;
+    let bytes = match (TarCompression::of(&state.filename), file_size) {
+        (TarCompression::Plain, _) | (_, None) => tar_bytes,
+        (_, Some(size)) if state.done => {
+            // The whole TAR is listed, so the compression ratio is known.
+            let tar_total: u64 = state.entries.iter().map(tar_record_bytes).sum::<u64>() + 1024;
+            (tar_bytes as f64 * size as f64 / tar_total as f64).ceil() as u64
+        }
+        (_, Some(size)) => tar_bytes.min(size),
+    };
+    let approximate = TarCompression::of(&state.filename) != TarCompression::Plain;
+    Ok((bytes, approximate, Some(tar_offset)))
+}
+
+/// Estimates how many bytes previewing or opening a Zenodo file, or a member of a TAR on Zenodo
+/// (`entry_name`), will download, so large transfers can be confirmed first. `warn_bytes`
+/// (1 GiB by default) sets `needsConfirmation`.
 #[tauri::command]
-pub async fn zenodo_tar_open_entry(
+pub async fn zenodo_transfer_estimate(
+    client: State<'_, ZenodoClient>,
+    cache: State<'_, ZenodoTarScanCache>,
     content_url: String,
     filename: String,
-    entry_name: String,
-    opener_app_path: Option<String>,
-) -> AppResult<OpenLeafResponse> {
+    operation: TransferOperation,
+    entry_name: Option<String>,
+    warn_bytes: Option<u64>,
+) -> AppResult<ZenodoTransferEstimate> {
+    let url = Url::parse(content_url.trim())
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
+    if !allowed_content_url(&url) {
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
+    }
+    let (_head, file_size) = range_request(&client.http, url, 0, 0).await?;
+    let entry_name = entry_name
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty());
+    let (bytes, approximate, tar_offset) = match entry_name {
+        Some(entry_name) => {
+            let state = cache.get_or_create(&content_url, &filename)?;
+            let guard = scan_cache::lock_state(&state)?;
+            tar_transfer_estimate(&guard, &entry_name, operation, file_size)?
+        }
+        None => {
+            let size = file_size.unwrap_or(0);
+            let bytes = match operation {
+                TransferOperation::Peek => size.min(PEEK_BYTES as u64),
+                TransferOperation::Open => size,
+            };
+            (bytes, file_size.is_none(), None)
+        }
+    };
+    Ok(ZenodoTransferEstimate {
+        file_size,
+        bytes,
+        approximate,
+        tar_offset,
+        needs_confirmation: bytes >= warn_bytes.unwrap_or(DEFAULT_TRANSFER_WARN_BYTES),
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZenodoTarFindResponse {
+    /// Position in the entry listing, for jumping to the right page.
+    index: Option<u32>,
+    entry: Option<ZenodoTarEntrySummary>,
+    preview: Option<FieldPreview>,
+    entries_scanned: u32,
+    done: bool,
+}
+
+/// Finds the first file matching `query` in a remote TAR, scanning further when it is not among
+/// the entries read so far. A query with `*` or `?` is a glob, anything else an exact path; either
+/// is compared with the file name alone when it contains no `/`.
+#[tauri::command]
+pub async fn zenodo_tar_find_entry(
+    cache: State<'_, ZenodoTarScanCache>,
+    registry: State<'_, JobRegistry>,
+    content_url: String,
+    filename: String,
+    query: String,
+) -> AppResult<ZenodoTarFindResponse> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Err(AppError::Invalid("Missing entry name or pattern.".into()));
+    }
+    let url = Url::parse(content_url.trim())
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
+    let state = cache.get_or_create(&content_url, &filename)?;
+    let reset = cache.resetter(&content_url);
+    let job = registry.start(None);
+    let filename = filename.trim().to_string();
+
+    supervisor::run_blocking("the TAR scan", reset, move || {
+        let is_glob = query.contains(['*', '?']);
+        let exact = normalize_member_path_str(&query);
+        let matches = |name: &str| {
+            if is_glob {
+                archive_listing::glob_match(&query, name)
+            } else if exact.contains('/') {
+                name == exact
+            } else {
+                name.rsplit('/').next() == Some(exact.as_str())
+            }
+        };
+
+        let mut guard = scan_cache::lock_state(&state)?;
+        let index = guard.find_entry(matches, job.cancel_flag())?;
+        let entry = index.map(|i| guard.entries[i].clone());
+        let cached = entry.as_ref().and_then(|e| guard.cached_preview(&e.name));
+        let entries_scanned = guard.entries.len().min(u32::MAX as usize) as u32;
+        let done = guard.done;
+        drop(guard);
+
+        // Entries found among earlier pages were read without a preview.
+        let preview = match (cached, &entry) {
+            (Some(preview), _) => Some(preview),
+            (None, Some(e)) => Some(peek_remote_tar_member(
+                url,
+                filename,
+                e.name.clone(),
+                0,
+                PreviewLimits::default(),
+            )?),
+            (None, None) => None,
+        };
+        Ok(ZenodoTarFindResponse {
+            index: index.map(|i| i as u32),
+            entry,
+            preview,
+            entries_scanned,
+            done,
+        })
+    })
+    .await
+}
+
+/// Previews every TAR entry matching `glob` (up to `max_entries`), scanning further into the
+/// archive when fewer matches are among the entries read so far.
+#[tauri::command]
+pub async fn zenodo_tar_peek_glob(
+    cache: State<'_, ZenodoTarScanCache>,
+    registry: State<'_, JobRegistry>,
+    content_url: String,
+    filename: String,
+    glob: String,
+    max_entries: Option<usize>,
+) -> AppResult<ZenodoGlobPeekResponse> {
+    let glob = glob.trim().to_string();
+    if glob.is_empty() {
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingGlobPattern,
+            &[],
+        )));
+    }
+    let url = Url::parse(content_url.trim())
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
+    let state = cache.get_or_create(&content_url, &filename)?;
+    let reset = cache.resetter(&content_url);
+    let job = registry.start(None);
+    let filename = filename.trim().to_string();
+    let limit = glob_peek_limit(max_entries);
+
+    supervisor::run_blocking("the TAR scan", reset, move || {
+        let mut guard = scan_cache::lock_state(&state)?;
+        // One extra match tells whether the limit cut the list short.
+        let found = guard.find_entries(
+            |name| archive_listing::glob_match(&glob, name),
+            limit + 1,
+            job.cancel_flag(),
+        )?;
+        let limit_reached = found.len() > limit;
+        let matched = found
+            .into_iter()
+            .take(limit)
+            .map(|i| {
+                let entry = guard.entries[i].clone();
+                let cached = guard.cached_preview(&entry.name);
+                (entry, cached)
+            })
+            .collect::<Vec<_>>();
+        drop(guard);
+
+        // Entries found among earlier pages were read without a preview; fetch their heads in
+        // one pass over the archive.
+        let wanted = matched
+            .iter()
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(entry, _)| normalize_member_path_str(&entry.name))
+            .collect::<HashSet<_>>();
+        let mut heads = read_tar_member_heads(&url, &filename, wanted)?;
+        let entries = matched
+            .into_iter()
+            .filter_map(|(entry, cached)| {
+                let preview = match cached {
+                    Some(preview) => preview,
+                    None => {
+                        let (data, size) = heads.remove(&normalize_member_path_str(&entry.name))?;
+                        tar_member_preview(&url, &entry.name, &data, size, PreviewLimits::default())
+                    }
+                };
+                Some(ZenodoGlobPeekEntry {
+                    name: entry.name,
+                    size: entry.size,
+                    preview,
+                })
+            })
+            .collect();
+        Ok(ZenodoGlobPeekResponse {
+            entries,
+            limit_reached,
+        })
+    })
+    .await
+}
+
+/// The first `PEEK_BYTES` and the size of each `wanted` member (normalized paths), read in one
+/// pass over the archive that stops once all were found.
+fn read_tar_member_heads(
+    url: &Url,
+    filename: &str,
+    mut wanted: HashSet<String>,
+) -> AppResult<HashMap<String, (Vec<u8>, u64)>> {
+    let mut out = HashMap::new();
+    if wanted.is_empty() {
+        return Ok(out);
+    }
+    let reader = open_remote_tar_reader(url.clone(), filename, None)?;
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let current = normalize_member_path_str(&entry.path()?.to_string_lossy());
+        if !wanted.remove(&current) {
+            continue;
+        }
+        let size = entry.size();
+        let mut buf = Vec::new();
+        entry.take(PEEK_BYTES as u64).read_to_end(&mut buf)?;
+        out.insert(current, (buf, size));
+        if wanted.is_empty() {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+/// Validated content URL, archive file name, and entry name for a TAR entry request.
+fn tar_entry_request(
+    content_url: &str,
+    filename: &str,
+    entry_name: &str,
+) -> AppResult<(Url, String, String)> {
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
     if !looks_like_tar(&filename) {
-        return Err(AppError::Invalid(
-            "Selected file is not a supported TAR archive.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::NotTarArchive,
+            &[],
+        )));
     }
 
     let trimmed = content_url.trim();
-    let url =
-        Url::parse(trimmed).map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
+    let url = Url::parse(trimmed)
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
     if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
     }
     let entry_name = entry_name.trim().to_string();
     if entry_name.is_empty() {
         return Err(AppError::Invalid("Missing TAR entry name.".into()));
     }
+    Ok((url, filename, entry_name))
+}
 
+/// A TAR entry's bytes after any decode override, its size, and the extension to write it as.
+fn tar_entry_decoded(
+    url: &Url,
+    filename: &str,
+    entry_name: &str,
+) -> AppResult<(Vec<u8>, u64, String)> {
+    let (bytes, size) = read_tar_member_with_limit(
+        url.clone(),
+        filename.to_string(),
+        entry_name.to_string(),
+        0,
+        MAX_INLINE_DOWNLOAD_BYTES,
+        Some(MAX_INLINE_DOWNLOAD_BYTES),
+    )?;
+    let (bytes, ext) = match decode_override(url.as_str(), entry_name) {
+        Some(rule) => rule.decode_for_open(bytes),
+        None => (
+            bytes,
+            ext_from_filename(entry_name).unwrap_or_else(|| "bin".into()),
+        ),
+    };
+    Ok((bytes, size, ext))
+}
+
+/// Writes a TAR entry to `destination_path` instead of opening it from the temp dir.
+#[tauri::command]
+pub async fn zenodo_tar_save_entry_as(
+    content_url: String,
+    filename: String,
+    entry_name: String,
+    destination_path: String,
+) -> AppResult<SavedLeafResponse> {
+    let (url, filename, entry_name) = tar_entry_request(&content_url, &filename, &entry_name)?;
     tauri::async_runtime::spawn_blocking(move || {
-        let (bytes, size) = read_tar_member_with_limit(
-            url.clone(),
-            filename.clone(),
-            entry_name.clone(),
-            MAX_INLINE_DOWNLOAD_BYTES,
-            Some(MAX_INLINE_DOWNLOAD_BYTES),
-        )?;
+        let (bytes, _size, ext) = tar_entry_decoded(&url, &filename, &entry_name)?;
+        save_leaf::write_to(&destination_path, &bytes, ext)
+    })
+    .await
+    .map_err(|e| AppError::Task(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn zenodo_tar_open_entry(
+    app: AppHandle,
+    content_url: String,
+    filename: String,
+    entry_name: String,
+    opener_app_path: Option<String>,
+) -> AppResult<OpenLeafResponse> {
+    let (url, filename, entry_name) = tar_entry_request(&content_url, &filename, &entry_name)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (bytes, size, ext) = tar_entry_decoded(&url, &filename, &entry_name)?;
 
         let record_id = record_id_from_content_url(&url).unwrap_or_else(|| "unknown".into());
         let temp_dir = std::env::temp_dir()
@@ -2018,7 +4097,6 @@ pub async fn zenodo_tar_open_entry(
             .join("zenodo");
         std::fs::create_dir_all(&temp_dir)?;
 
-        let ext = ext_from_filename(&entry_name).unwrap_or_else(|| "bin".into());
         let base = format!(
             "{}-r{}-{}",
             sanitize(url.host_str().unwrap_or("zenodo")),
@@ -2031,36 +4109,23 @@ pub async fn zenodo_tar_open_entry(
             .map(|(s, _)| s)
             .unwrap_or(entry_filename);
         let entry_stem = sanitize(entry_stem_raw);
-        let out_path = temp_dir.join(format!("{base}-{entry_stem}.{ext}"));
-        std::fs::write(&out_path, &bytes)?;
-
-        let mut opened = false;
-        let mut open_error = None::<String>;
-        if let Some(app_path) = opener_app_path.as_deref() {
-            match open_with::open_with_app_detached(&out_path, app_path) {
-                Ok(()) => opened = true,
-                Err(err) => open_error = Some(err),
-            }
-        }
-        if !opened {
-            if let Err(err) = open::that_detached(&out_path) {
-                open_error = Some(err.to_string());
-            } else {
-                opened = true;
-            }
-        }
+        let out_path = temp_names::write(&temp_dir, &format!("{base}-{entry_stem}"), &ext, &bytes)?;
 
-        let size_u32 = size.min(u32::MAX as u64) as u32;
-        let base_msg = format!("{} ({} bytes)", out_path.display(), size_u32);
+        let opened = open_with::open_extracted(&app, &out_path, opener_app_path.as_deref()).is_ok();
+
+        let base_msg = messages::text(
+            Message::FileWithSize,
+            &[("path", &out_path.display()), ("size", &size)],
+        );
         let mut message = base_msg;
-        let needs_opener = !opened && open_error.is_some();
+        let needs_opener = !opened;
         if needs_opener {
-            message.push_str(" · no default app found, choose an app to open it");
+            message.push_str(&messages::text(Message::NoDefaultApp, &[]));
         }
 
         Ok(OpenLeafResponse {
             path: out_path.display().to_string(),
-            size: size_u32,
+            size,
             ext,
             opened,
             needs_opener,
@@ -2071,67 +4136,102 @@ pub async fn zenodo_tar_open_entry(
     .map_err(|e| AppError::Task(e.to_string()))?
 }
 
-#[tauri::command]
-pub async fn zenodo_tar_inline_entry_media(
-    cache: State<'_, ZenodoTarScanCache>,
-    content_url: String,
-    filename: String,
-    entry_name: String,
-) -> AppResult<InlineMediaResponse> {
+/// Entry bytes plus `(ext, mime)`, served from the scan state's media cache when possible.
+async fn tar_entry_media_bytes(
+    cache: &ZenodoTarScanCache,
+    content_url: &str,
+    filename: &str,
+    entry_name: &str,
+) -> AppResult<(Vec<u8>, String, String)> {
     let filename = filename.trim().to_string();
     if filename.is_empty() {
-        return Err(AppError::Invalid("Missing filename.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::MissingFilename,
+            &[],
+        )));
     }
     if !looks_like_tar(&filename) {
-        return Err(AppError::Invalid(
-            "Selected file is not a supported TAR archive.".into(),
-        ));
+        return Err(AppError::Invalid(messages::text(
+            Message::NotTarArchive,
+            &[],
+        )));
     }
 
     let trimmed = content_url.trim();
-    let url =
-        Url::parse(trimmed).map_err(|_| AppError::Invalid("Invalid Zenodo content URL.".into()))?;
+    let url = Url::parse(trimmed)
+        .map_err(|_| AppError::Invalid(messages::text(Message::InvalidContentUrl, &[])))?;
     if !allowed_content_url(&url) {
-        return Err(AppError::Invalid("Blocked content URL.".into()));
+        return Err(AppError::Invalid(messages::text(
+            Message::BlockedContentUrl,
+            &[],
+        )));
     }
     let entry_name = entry_name.trim().to_string();
     if entry_name.is_empty() {
         return Err(AppError::Invalid("Missing TAR entry name.".into()));
     }
 
-    if let Ok(state) = cache.get_or_create(&content_url, &filename) {
+    if let Ok(state) = cache.get_or_create(content_url, &filename) {
         let wanted = normalize_member_path_str(&entry_name);
         if let Ok(mut guard) = state.lock() {
             if let Some(hit) = guard.cached_media(&wanted) {
-                let base64 = base64::engine::general_purpose::STANDARD.encode(&hit.bytes);
-                return Ok(InlineMediaResponse {
-                    base64,
-                    mime: hit.mime,
-                    size: (hit.bytes.len() as u64).min(u32::MAX as u64) as u32,
-                    ext: hit.ext,
-                });
+                return Ok((hit.bytes, hit.ext, hit.mime));
             }
         }
     }
 
     tauri::async_runtime::spawn_blocking(move || {
-        let (bytes, size) = read_tar_member_with_limit(
+        let (bytes, _size) = read_tar_member_with_limit(
             url,
             filename,
             entry_name.clone(),
+            0,
             TAR_INLINE_MEDIA_MAX_BYTES,
             Some(TAR_INLINE_MEDIA_MAX_BYTES),
         )?;
         let ext = ext_from_filename(&entry_name).unwrap_or_else(|| "bin".into());
         let mime = mime_for_ext(&ext).to_string();
-        let base64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
-        Ok(InlineMediaResponse {
-            base64,
-            mime,
-            size: size.min(u32::MAX as u64) as u32,
-            ext,
-        })
+        Ok((bytes, ext, mime))
     })
     .await
     .map_err(|e| AppError::Task(e.to_string()))?
 }
+
+#[tauri::command]
+pub async fn zenodo_tar_inline_entry_media(
+    cache: State<'_, ZenodoTarScanCache>,
+    content_url: String,
+    filename: String,
+    entry_name: String,
+) -> AppResult<InlineMediaResponse> {
+    let (bytes, ext, mime) =
+        tar_entry_media_bytes(&cache, &content_url, &filename, &entry_name).await?;
+    let base64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
+    Ok(InlineMediaResponse {
+        base64,
+        mime,
+        size: bytes.len() as u64,
+        ext,
+    })
+}
+
+/// Like [`zenodo_tar_inline_entry_media`], but serves the bytes over the media protocol.
+#[tauri::command]
+pub async fn zenodo_tar_stream_entry_media(
+    cache: State<'_, ZenodoTarScanCache>,
+    media: State<'_, MediaStore>,
+    content_url: String,
+    filename: String,
+    entry_name: String,
+) -> AppResult<StreamedMediaResponse> {
+    let (bytes, ext, mime) =
+        tar_entry_media_bytes(&cache, &content_url, &filename, &entry_name).await?;
+    let size = bytes.len() as u64;
+    let token = media.register(bytes, mime.clone())?;
+    Ok(StreamedMediaResponse {
+        token,
+        mime,
+        size,
+        ext,
+    })
+}
@@ -0,0 +1,182 @@
+use crate::app_error::{AppError, AppResult};
+
+/// One central directory record, with ZIP64 sizes and split-archive offsets already resolved.
+#[derive(Clone)]
+pub struct ZipEntryIndex {
+    pub name: String,
+    pub method: u16,
+    pub flags: u16,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    pub local_header_offset: u64,
+    pub is_dir: bool,
+}
+
+pub fn read_u16_le(input: &[u8], offset: usize) -> AppResult<u16> {
+    let slice = input
+        .get(offset..offset + 2)
+        .ok_or_else(|| AppError::Invalid("Unexpected EOF while parsing ZIP.".into()))?;
+    Ok(u16::from_le_bytes([slice[0], slice[1]]))
+}
+
+pub fn read_u32_le(input: &[u8], offset: usize) -> AppResult<u32> {
+    let slice = input
+        .get(offset..offset + 4)
+        .ok_or_else(|| AppError::Invalid("Unexpected EOF while parsing ZIP.".into()))?;
+    Ok(u32::from_le_bytes([slice[0], slice[1], slice[2], slice[3]]))
+}
+
+pub fn read_u64_le(input: &[u8], offset: usize) -> AppResult<u64> {
+    let slice = input
+        .get(offset..offset + 8)
+        .ok_or_else(|| AppError::Invalid("Unexpected EOF while parsing ZIP.".into()))?;
+    Ok(u64::from_le_bytes([
+        slice[0], slice[1], slice[2], slice[3], slice[4], slice[5], slice[6], slice[7],
+    ]))
+}
+
+pub fn find_zip_eocd(buf: &[u8]) -> Option<usize> {
+    // EOCD can be at most 65535 + 22 bytes from the end of the file.
+    const EOCD_SIG: [u8; 4] = [0x50, 0x4b, 0x05, 0x06];
+    if buf.len() < 22 {
+        return None;
+    }
+    let start = buf.len().saturating_sub(22 + 65535);
+    for i in (start..=buf.len().saturating_sub(22)).rev() {
+        if buf.get(i..i + 4) != Some(&EOCD_SIG) {
+            continue;
+        }
+        let comment_len = u16::from_le_bytes([buf[i + 20], buf[i + 21]]) as usize;
+        if i + 22 + comment_len == buf.len() {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn parse_zip64_extra(
+    extra: &[u8],
+    need_uncompressed: bool,
+    need_compressed: bool,
+    need_local_offset: bool,
+    need_disk: bool,
+) -> AppResult<(Option<u64>, Option<u64>, Option<u64>, Option<u32>)> {
+    let mut pos = 0usize;
+    while pos + 4 <= extra.len() {
+        let header_id = u16::from_le_bytes([extra[pos], extra[pos + 1]]);
+        let data_size = u16::from_le_bytes([extra[pos + 2], extra[pos + 3]]) as usize;
+        pos += 4;
+        if pos + data_size > extra.len() {
+            break;
+        }
+        if header_id == 0x0001 {
+            let mut cursor = pos;
+            let mut uncompressed = None;
+            let mut compressed = None;
+            let mut local_offset = None;
+            if need_uncompressed {
+                uncompressed = Some(read_u64_le(extra, cursor)?);
+                cursor += 8;
+            }
+            if need_compressed {
+                compressed = Some(read_u64_le(extra, cursor)?);
+                cursor += 8;
+            }
+            if need_local_offset {
+                local_offset = Some(read_u64_le(extra, cursor)?);
+                cursor += 8;
+            }
+            let disk = if need_disk {
+                Some(read_u32_le(extra, cursor)?)
+            } else {
+                None
+            };
+            return Ok((uncompressed, compressed, local_offset, disk));
+        }
+        pos += data_size;
+    }
+    Ok((None, None, None, None))
+}
+
+/// `disk_starts[n]` is where segment `n` begins in the logical archive; local header offsets are
+/// stored relative to their segment and translated here.
+pub fn parse_central_directory_entries(
+    buf: &[u8],
+    max_entries_hint: u64,
+    disk_starts: &[u64],
+) -> AppResult<Vec<ZipEntryIndex>> {
+    let mut entries = Vec::new();
+    let mut pos = 0usize;
+    while pos + 46 <= buf.len() {
+        let sig = read_u32_le(buf, pos)?;
+        if sig != 0x0201_4b50 {
+            break;
+        }
+        let flags = read_u16_le(buf, pos + 8)?;
+        let method = read_u16_le(buf, pos + 10)?;
+        let crc32 = read_u32_le(buf, pos + 16)?;
+        let compressed_size_u32 = read_u32_le(buf, pos + 20)?;
+        let uncompressed_size_u32 = read_u32_le(buf, pos + 24)?;
+        let name_len = read_u16_le(buf, pos + 28)? as usize;
+        let extra_len = read_u16_le(buf, pos + 30)? as usize;
+        let comment_len = read_u16_le(buf, pos + 32)? as usize;
+        let disk_u16 = read_u16_le(buf, pos + 34)?;
+        let local_header_offset_u32 = read_u32_le(buf, pos + 42)?;
+        let header_end = pos
+            .checked_add(46)
+            .ok_or_else(|| AppError::Invalid("ZIP header overflow.".into()))?;
+        let name_start = header_end;
+        let name_end = name_start + name_len;
+        let extra_start = name_end;
+        let extra_end = extra_start + extra_len;
+        let comment_end = extra_end + comment_len;
+        let name_bytes = buf
+            .get(name_start..name_end)
+            .ok_or_else(|| AppError::Invalid("Malformed ZIP central directory entry.".into()))?;
+        let extra_bytes = buf.get(extra_start..extra_end).unwrap_or(&[]);
+        let name = String::from_utf8(name_bytes.to_vec())
+            .unwrap_or_else(|_| String::from_utf8_lossy(name_bytes).to_string());
+        let is_dir = name.ends_with('/');
+
+        let need_zip64_uncompressed = uncompressed_size_u32 == 0xFFFF_FFFF;
+        let need_zip64_compressed = compressed_size_u32 == 0xFFFF_FFFF;
+        let need_zip64_local_offset = local_header_offset_u32 == 0xFFFF_FFFF;
+        let (zip64_uncompressed, zip64_compressed, zip64_local_offset, zip64_disk) =
+            parse_zip64_extra(
+                extra_bytes,
+                need_zip64_uncompressed,
+                need_zip64_compressed,
+                need_zip64_local_offset,
+                disk_u16 == 0xFFFF,
+            )?;
+
+        let compressed_size = zip64_compressed.unwrap_or(compressed_size_u32 as u64);
+        let uncompressed_size = zip64_uncompressed.unwrap_or(uncompressed_size_u32 as u64);
+        let disk = zip64_disk.unwrap_or(disk_u16 as u32) as usize;
+        let disk_start = disk_starts.get(disk).copied().ok_or_else(|| {
+            AppError::Invalid(format!("ZIP entry refers to missing segment {}.", disk + 1))
+        })?;
+        let local_header_offset = zip64_local_offset
+            .unwrap_or(local_header_offset_u32 as u64)
+            .checked_add(disk_start)
+            .ok_or_else(|| AppError::Invalid("ZIP offset overflow.".into()))?;
+        let _ = crc32;
+
+        entries.push(ZipEntryIndex {
+            name,
+            method,
+            flags,
+            compressed_size,
+            uncompressed_size,
+            local_header_offset,
+            is_dir,
+        });
+
+        if max_entries_hint > 0 && entries.len() as u64 >= max_entries_hint {
+            // Keep parsing safe even if EOCD entry count is wrong.
+            // We'll still break once we hit buffer end or invalid signature.
+        }
+        pos = comment_end;
+    }
+    Ok(entries)
+}
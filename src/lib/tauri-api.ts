@@ -1,9 +1,10 @@
 import { convertFileSrc, invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { dirname } from "@tauri-apps/api/path";
 import { open as openDialog } from "@tauri-apps/plugin-dialog";
 import { relaunch } from "@tauri-apps/plugin-process";
 import { Store } from "@tauri-apps/plugin-store";
-import { check, type DownloadEvent, type Update } from "@tauri-apps/plugin-updater";
+import type { DownloadEvent } from "@tauri-apps/plugin-updater";
 
 export type ChunkSummary = {
   filename: string;
@@ -36,12 +37,54 @@ export type ItemMeta = {
   fields: FieldMeta[];
 };
 
+export type MetadataTag = {
+  key: string;
+  value: string;
+};
+
+/** How to render a preview, detected from the previewed bytes. */
+export type PreviewKind =
+  | { type: "text" }
+  | { type: "json" }
+  | { type: "image"; width: number; height: number }
+  /** Duration and sample rate are only known for WAV, FLAC and SPHERE. */
+  | { type: "audio"; durationSecs: number | null; sampleRate: number | null }
+  | { type: "tensor"; shape: number[]; dtype: string }
+  | { type: "binary" };
+
 export type FieldPreview = {
   previewText?: string | null;
+  /** Characters of text in the previewed window before `previewText` was cut to length. */
+  textLength?: number;
   hexSnippet: string;
   guessedExt?: string | null;
   isBinary: boolean;
   size: number;
+  kind: PreviewKind;
+  /** Compression layer removed before previewing, e.g. for `transcript.json.gz`. */
+  compression?: "gzip" | "zstd" | "bzip2";
+  metadata?: MetadataTag[];
+  /** Set when a decoder plugin handled the guessed extension. */
+  plugin?: PluginPreview;
+};
+
+/** Optional preview sizes for the peek commands; the backend clamps both. */
+export type PreviewLimits = {
+  /** Characters of `previewText` (8192 by default). */
+  textChars?: number;
+  /** Bytes shown in `hexSnippet` (48 by default). */
+  hexBytes?: number;
+};
+
+const previewLimitArgs = (limits: PreviewLimits) => ({
+  textChars: limits.textChars ?? null,
+  hexBytes: limits.hexBytes ?? null,
+});
+
+export type PluginPreview = {
+  name: string;
+  image?: { mime: string; base64: string };
+  error?: string;
 };
 
 export type OpenLeafResponse = {
@@ -53,6 +96,12 @@ export type OpenLeafResponse = {
   message: string;
 };
 
+export type SavedLeafResponse = {
+  path: string;
+  size: number;
+  ext: string;
+};
+
 export type PreparedFileResponse = {
   path: string;
   size: number;
@@ -66,6 +115,18 @@ export type InlineMediaResponse = {
   ext: string;
 };
 
+export type StreamedMediaResponse = {
+  token: string;
+  mime: string;
+  size: number;
+  ext: string;
+};
+
+/** URL the webview can load (with Range requests) for media registered by a stream command. */
+export function streamedMediaUrl(result: StreamedMediaResponse): string {
+  return convertFileSrc(result.token, "dsi-media");
+}
+
 export type PickResult = { kind: "index"; indexPath: string };
 
 export type LocalDatasetDetectResponse =
@@ -78,10 +139,13 @@ export type WdsShardSummary = {
   path: string;
   bytes: number;
   exists: boolean;
+  linkTarget: string | null;
+  linkError: string | null;
 };
 
 export type WdsDirSummary = {
   dirPath: string;
+  resolvedDirPath: string | null;
   shards: WdsShardSummary[];
 };
 
@@ -106,6 +170,210 @@ export type WdsSampleListResponse = {
   samples: WdsSampleInfo[];
 };
 
+export type WdsFieldPreview = {
+  name: string;
+  memberPath: string;
+  preview: FieldPreview;
+};
+
+export type WdsPeekPageResponse = {
+  offset: number;
+  numSamplesTotal?: number | null;
+  partial: boolean;
+  samples: { sampleIndex: number; key: string; fields: WdsFieldPreview[] }[];
+};
+
+export type MdsPeekPageResponse = {
+  offset: number;
+  numSamplesTotal: number;
+  /** `fields` holds one preview per column, in column order. */
+  samples: { itemIndex: number; fields: FieldPreview[] }[];
+};
+
+export type LitdataItemJson = {
+  itemIndex: number;
+  /** Nested per the index's `data_spec` when possible, otherwise one entry per field. */
+  item: unknown;
+  binaryFields: number[];
+};
+
+export type MdsSampleJson = {
+  itemIndex: number;
+  /** Binary columns hold an `{ encoding, size, ext }` summary; see `binaryColumns`. */
+  sample: Record<string, unknown>;
+  binaryColumns: string[];
+};
+
+export type WdsSamplePageEvent = {
+  requestId: string;
+  offset: number;
+  samples: WdsSampleInfo[];
+};
+
+export type WdsStreamSummary = {
+  requestId: string;
+  emitted: number;
+  numSamplesTotal: number | null;
+  partial: boolean;
+};
+
+export const WDS_SAMPLES_PAGE_EVENT = "wds://samples-page";
+
+export type SourceChangedEvent = {
+  source: string;
+};
+
+export const SOURCE_CHANGED_EVENT = "scan://source-changed";
+
+export type WatchedFileInfo = {
+  filename: string;
+  bytes: number;
+};
+
+export type DirChangedEvent = {
+  dirPath: string;
+  added: WatchedFileInfo[];
+  changed: WatchedFileInfo[];
+  removed: string[];
+};
+
+export const DIR_CHANGED_EVENT = "watch://dir-changed";
+
+export type Thumbnail = {
+  name: string;
+  base64: string | null;
+  width: number;
+  height: number;
+  error: string | null;
+};
+
+export type WdsLintIssueKind =
+  | "non-contiguous"
+  | "duplicate-member"
+  | "zero-byte"
+  | "non-normalized-path"
+  | "missing-companion";
+
+export type WdsLintIssue = {
+  kind: WdsLintIssueKind;
+  key: string;
+  memberPath: string | null;
+  message: string;
+};
+
+export type WdsLintReport = {
+  shardPath: string;
+  membersScanned: number;
+  samplesScanned: number;
+  complete: boolean;
+  expectedFields: string[];
+  issueCounts: Partial<Record<WdsLintIssueKind, number>>;
+  issues: WdsLintIssue[];
+};
+
+export type WdsImageFlagKind =
+  | "undecodable"
+  | "tiny"
+  | "extreme-aspect-ratio"
+  | "all-black"
+  | "all-white"
+  | "solid-color";
+
+export type WdsImageFlag = {
+  kind: WdsImageFlagKind;
+  key: string;
+  memberPath: string;
+  width: number | null;
+  height: number | null;
+  message: string;
+};
+
+export type WdsImageFlagReport = {
+  shardPath: string;
+  imagesScanned: number;
+  imagesSkipped: number;
+  complete: boolean;
+  flagCounts: Partial<Record<WdsImageFlagKind, number>>;
+  flags: WdsImageFlag[];
+};
+
+/** "email", "phone", "credit-card", "profanity", or a custom pattern name. */
+export type PiiMatch = {
+  kind: string;
+  redacted: string;
+  offset: number;
+};
+
+export type WdsOpenedSample = {
+  path: string;
+  files: string[];
+  totalBytes: number;
+  opened: boolean;
+  message: string;
+};
+
+export type WdsLayoutMember = {
+  memberPath: string;
+  headerOffset: number;
+  dataOffset: number;
+  size: number;
+  endOffset: number;
+};
+
+export type WdsLayoutSample = {
+  key: string;
+  startOffset: number;
+  endOffset: number;
+  members: WdsLayoutMember[];
+};
+
+export type WdsShardLayout = {
+  shardPath: string;
+  fileSize: number;
+  archiveSize: number | null;
+  membersEnd: number;
+  headerBytes: number;
+  dataBytes: number;
+  paddingBytes: number;
+  memberCount: number;
+  complete: boolean;
+  samples: WdsLayoutSample[];
+};
+
+export type WdsPiiMember = {
+  key: string;
+  memberPath: string;
+  matchCount: number;
+  matches: PiiMatch[];
+};
+
+export type WdsPiiReport = {
+  shardPath: string;
+  textMembersScanned: number;
+  complete: boolean;
+  matchCounts: Record<string, number>;
+  members: WdsPiiMember[];
+};
+
+export type EmbeddingPoint = {
+  /** Sample key (WebDataset) or "shard:item" (MDS). */
+  id: string;
+  x: number;
+  y: number;
+};
+
+/** 2D PCA projection of a float vector field over a sampled subset. */
+export type ProjectionMethod = "pca" | "umap";
+
+export type EmbeddingProjection = {
+  dims: number;
+  method: ProjectionMethod;
+  /** Share of the total variance along each axis; PCA only. */
+  explainedVariance?: [number, number] | null;
+  points: EmbeddingPoint[];
+  skipped: number;
+};
+
 export type HfConfigSummary = {
   config: string;
   splits: string[];
@@ -123,11 +391,30 @@ export type HfDatasetPreview = {
   split: string;
   configs: HfConfigSummary[];
   offset: number;
+  /** Rows per page actually used, after clamping the requested length to `maxLength`. */
   length: number;
+  maxLength: number;
+  /** `parquet` when rows were read from the repo's Parquet files (private or gated datasets). */
+  source: "datasets-server" | "parquet";
   numRowsTotal: number;
   partial: boolean;
   features: HfFeature[];
   rows: unknown[];
+  /** Arrays in `rows` cut to their first 16 items; fetch the whole value with `hfFieldValue`. */
+  truncated: HfTruncatedValue[];
+};
+
+/** A large list (Sequence, Array2D, ...) in a preview row, summarized instead of sent whole. */
+export type HfTruncatedValue = {
+  /** Index into `rows`. */
+  row: number;
+  field: string;
+  /** JSON pointer from the field's value to the cut array; empty for the value itself. */
+  pointer: string;
+  length: number;
+  /** Length of each nesting level when the array is rectangular. */
+  shape?: number[] | null;
+  dtype: string;
 };
 
 export type ZenodoCreator = {
@@ -145,6 +432,10 @@ export type ZenodoFileSummary = {
 
 export type ZenodoRecordSummary = {
   recordId: number;
+  requestedRecordId: number;
+  conceptRecordId: number | null;
+  conceptDoi: string | null;
+  sandbox: boolean;
   title: string;
   doi?: string | null;
   doiUrl?: string | null;
@@ -156,155 +447,1190 @@ export type ZenodoRecordSummary = {
   files: ZenodoFileSummary[];
 };
 
-export type ZenodoZipEntrySummary = {
+export type ZenodoDownloadedFile = {
+  key: string;
+  path: string;
+  size: number;
+  status: "downloaded" | "skipped" | "failed";
+  resumed: boolean;
+  checksumVerified: boolean | null;
+  error: string | null;
+};
+
+export type ZenodoRecordDownloadReport = {
+  recordId: number;
+  destDir: string;
+  files: ZenodoDownloadedFile[];
+  ok: boolean;
+};
+
+export type TabularColumn = {
   name: string;
-  method: number;
-  compressedSize: number;
-  uncompressedSize: number;
-  isDir: boolean;
+  /** ARFF attribute type, or for CSV the type inferred from the previewed values. */
+  dtype: string;
 };
 
-export type ZenodoTarEntrySummary = {
+export type TabularPreview = {
+  format: "csv" | "tsv" | "arff";
+  relation: string | null;
+  columns: TabularColumn[];
+  /** `null` cells are missing values. */
+  rows: (string | null)[][];
+  truncated: boolean;
+};
+
+export type UciVariable = {
   name: string;
-  size: number;
-  isDir: boolean;
+  role: string | null;
+  kind: string | null;
+  description: string | null;
 };
 
-export type ZenodoTarEntryListResponse = {
-  offset: number;
-  length: number;
-  entries: ZenodoTarEntrySummary[];
-  partial: boolean;
-  numEntriesTotal?: number | null;
+export type RepoDataFile = {
+  format: string;
+  fileName: string;
+  url: string;
 };
 
-const STORE_NAME = "dataset-inspector.bin";
-const STORE_LAST_INDEX = "last_index";
-const STORE_OPENERS_BY_EXT = "openers_by_ext";
-const STORE_HF_TOKEN = "hf_token";
+export type RepoDatasetSummary = {
+  source: "openml" | "uci";
+  id: number;
+  name: string;
+  version: string | null;
+  description: string | null;
+  license: string | null;
+  target: string[];
+  pageUrl: string;
+  numInstances: number | null;
+  numFeatures: number | null;
+  variables: UciVariable[];
+  files: RepoDataFile[];
+  preview: TabularPreview | null;
+};
 
-let storeInstance: Store | null = null;
+export type RepoDownloadReport = {
+  path: string;
+  resumed: boolean;
+};
 
-export const isTauri = () => {
-  if (typeof window === "undefined") return false;
-  const w = window as Window & {
-    __TAURI_INTERNALS__?: unknown;
-    __TAURI__?: unknown;
-    __TAURI_METADATA__?: unknown;
-    __TAURI_IPC__?: unknown;
-  };
-  const ua = String(w.navigator?.userAgent || "").toLowerCase();
-  return Boolean(
-    w.__TAURI_INTERNALS__ ||
-      w.__TAURI__ ||
-      w.__TAURI_METADATA__ ||
-      w.__TAURI_IPC__ ||
-      ua.includes("tauri") ||
-      w.location?.protocol === "tauri:" ||
-      (typeof w.location?.href === "string" && w.location.href.startsWith("tauri://"))
-  );
+export type TorchTensor = {
+  /** Dotted path from the top-level object, e.g. `state_dict.encoder.layer.0.weight`. */
+  name: string;
+  dtype: string;
+  shape: number[];
+  byteSize: number | null;
+  storageKey: string;
+  storageOffset: number;
+  device: string | null;
 };
 
-async function requireTauri(task: string) {
-  if (!isTauri()) {
-    throw new Error(`${task} requires the Tauri runtime.`);
-  }
-}
+export type TorchArchiveSummary = {
+  path: string;
+  archiveName: string;
+  formatVersion: string | null;
+  byteOrder: string | null;
+  entries: number;
+  storageCount: number;
+  storageBytes: number;
+  tensorCount: number;
+  tensors: TorchTensor[];
+  /** Plain values stored next to the tensors (epochs, hyperparameters, ...). */
+  scalars: { name: string; value: unknown }[];
+  truncated: boolean;
+};
 
-export async function checkAppUpdate(): Promise<Update | null> {
-  if (!isTauri()) return null;
-  return check();
-}
+export type OnnxValueInfo = {
+  name: string;
+  elemType: string | null;
+  /** Each dimension's size, symbolic name, or `?`; `null` when undeclared. */
+  shape: string[] | null;
+};
 
-export async function downloadAndInstallAppUpdate(params: {
-  update: Update;
-  onProgress?: (event: DownloadEvent) => void;
-}): Promise<void> {
-  await requireTauri("Installing update");
-  await params.update.downloadAndInstall(params.onProgress);
-  await params.update.close();
-}
+export type OnnxSummary = {
+  path: string;
+  irVersion: number | null;
+  producerName: string | null;
+  producerVersion: string | null;
+  domain: string | null;
+  modelVersion: number | null;
+  docString: string | null;
+  opsets: { domain: string; version: number }[];
+  graphName: string | null;
+  inputs: OnnxValueInfo[];
+  outputs: OnnxValueInfo[];
+  nodeCount: number;
+  opCounts: { opType: string; count: number }[];
+  initializerCount: number;
+  parameterCount: number;
+  externalData: boolean;
+  metadata: Record<string, string>;
+};
 
-export async function relaunchApp(): Promise<void> {
-  await requireTauri("Restarting app");
-  await relaunch();
-}
+export type GgufMetadataEntry = {
+  key: string;
+  /** `uint32`, `string`, `array[string]`, ... */
+  valueType: string;
+  /** Arrays are cut to their first 16 items; `length` has the full count. */
+  value: unknown;
+  length: number | null;
+};
 
-async function getStore(): Promise<Store> {
-  if (storeInstance) return storeInstance;
-  await requireTauri("Reading preferences");
-  storeInstance = await Store.load(STORE_NAME);
-  return storeInstance;
-}
+export type GgufTensor = {
+  name: string;
+  /** Innermost dimension first, as GGUF stores them. */
+  dims: number[];
+  ggmlType: string;
+  offset: number;
+  byteSize: number | null;
+};
 
-export async function saveLastIndex(indexPath: string) {
-  if (!isTauri()) return;
-  const store = await getStore();
-  await store.set(STORE_LAST_INDEX, indexPath);
-  await store.save();
-}
+export type GgufSummary = {
+  path: string;
+  version: number;
+  architecture: string | null;
+  name: string | null;
+  metadata: GgufMetadataEntry[];
+  tensors: GgufTensor[];
+  parameterCount: number;
+  dataOffset: number;
+  alignment: number;
+};
 
-export async function readLastIndex(): Promise<string | null> {
-  if (!isTauri()) return null;
-  const store = await getStore();
-  return (await store.get<string>(STORE_LAST_INDEX)) ?? null;
-}
+export type RobotLogSample = {
+  logTimeNs: number;
+  size: number;
+  data: unknown;
+};
 
-type OpenersByExt = Record<string, string>;
+export type RobotLogTopic = {
+  topic: string;
+  messageType: string;
+  messageEncoding: string;
+  schemaEncoding: string | null;
+  schema: string | null;
+  messageCount: number;
+  samples: RobotLogSample[];
+};
 
-export async function readPreferredOpenerForExt(ext: string): Promise<string | null> {
-  if (!isTauri()) return null;
-  const normalized = ext.trim().replace(/^\./, "").toLowerCase();
-  if (!normalized) return null;
-  const store = await getStore();
-  const map = (await store.get<OpenersByExt>(STORE_OPENERS_BY_EXT)) ?? {};
-  return map[normalized] ?? null;
-}
+export type RobotLogSummary = {
+  path: string;
+  format: "rosbag" | "mcap";
+  profile: string | null;
+  startTimeNs: number | null;
+  endTimeNs: number | null;
+  messageCount: number;
+  chunkCount: number;
+  compression: string[];
+  topics: RobotLogTopic[];
+};
 
-export async function savePreferredOpenerForExt(ext: string, appPath: string) {
-  if (!isTauri()) return;
-  const normalized = ext.trim().replace(/^\./, "").toLowerCase();
-  const trimmedPath = appPath.trim();
-  if (!normalized || !trimmedPath) return;
-  const store = await getStore();
-  const map = (await store.get<OpenersByExt>(STORE_OPENERS_BY_EXT)) ?? {};
-  map[normalized] = trimmedPath;
-  await store.set(STORE_OPENERS_BY_EXT, map);
-  await store.save();
-}
+export type RobotLogExportFormat = "raw" | "json" | "image";
 
-export async function readHfToken(): Promise<string | null> {
-  if (!isTauri()) return null;
-  const store = await getStore();
-  const value = (await store.get<string>(STORE_HF_TOKEN)) ?? null;
-  const trimmed = String(value ?? "").trim();
-  return trimmed ? trimmed : null;
-}
+export type RobotLogExportReport = {
+  path: string;
+  count: number;
+  skipped: number;
+  truncated: boolean;
+};
 
-export async function saveHfToken(token: string) {
-  if (!isTauri()) return;
-  const trimmed = token.trim();
-  const store = await getStore();
-  await store.set(STORE_HF_TOKEN, trimmed);
-  await store.save();
-}
+export type ArchiveRecordHeader = {
+  name: string;
+  value: string;
+};
 
-export async function clearHfToken() {
-  if (!isTauri()) return;
-  const store = await getStore();
-  await store.delete(STORE_HF_TOKEN);
-  await store.save();
-}
+export type ArchiveRecord = {
+  index: number;
+  byteOffset: number;
+  headers: ArchiveRecordHeader[];
+  httpStatus: string | null;
+  httpHeaders: ArchiveRecordHeader[];
+  body: FieldPreview;
+};
 
-export async function chooseOpenerApp(): Promise<string | null> {
-  await requireTauri("Choosing an application");
-  const ua = typeof navigator === "undefined" ? "" : String(navigator.userAgent || "");
-  const isMac = /Macintosh|Mac OS X/i.test(ua);
-  const picked = await openDialog({
-    title: isMac ? "Choose an application (.app)" : "Choose an application",
-    multiple: false,
-    ...(isMac
-      ? {
+export type ArchivePage = {
+  format: "mbox" | "warc";
+  compression: "gzip" | "zstd" | null;
+  offset: number;
+  records: ArchiveRecord[];
+  done: boolean;
+};
+
+export type ParquetQueryResult = {
+  offset: number;
+  rows: Record<string, unknown>[];
+  matched: number;
+  files: number;
+  filesScanned: number;
+  rowGroupsScanned: number;
+  rowGroupsPruned: number;
+  rowsPruned: number;
+  rowsTested: number;
+  scanComplete: boolean;
+  timedOut: boolean;
+};
+
+export type QuerySource = {
+  path: string;
+  name?: string | null;
+};
+
+export type QueryColumn = {
+  name: string;
+  dataType: string;
+};
+
+export type QueryResult = {
+  tables: string[];
+  columns: QueryColumn[];
+  rows: Record<string, unknown>[];
+  offset: number;
+  done: boolean;
+};
+
+export type WarehouseKind = "duckdb" | "flight";
+
+export type WarehouseColumn = {
+  name: string;
+  dataType: string;
+  nullable: boolean;
+};
+
+export type WarehouseTable = {
+  name: string;
+  columns: WarehouseColumn[];
+  numRows: number | null;
+};
+
+export type WarehouseListing = {
+  location: string;
+  tables: WarehouseTable[];
+};
+
+export type WarehousePage = {
+  table: string;
+  columns: WarehouseColumn[];
+  offset: number;
+  rows: Record<string, unknown>[];
+  numRowsTotal: number | null;
+};
+
+export type RemoteEntry = {
+  name: string;
+  /** Absolute path on the server. */
+  path: string;
+  isDir: boolean;
+  size: number | null;
+  /** Seconds since the Unix epoch. */
+  modified: number | null;
+  /** Location to pass back to the remote commands; carries no password. */
+  url: string;
+};
+
+export type RemoteDirListing = {
+  url: string;
+  path: string;
+  entries: RemoteEntry[];
+  truncated: boolean;
+};
+
+export type RemoteFetchedFile = {
+  path: string;
+  size: number;
+  cached: boolean;
+};
+
+export type DatasetRootKind = "litdata" | "mds" | "webdataset" | "parquet";
+
+export type DatasetRoot = {
+  path: string;
+  dir: string;
+  relativeDir: string;
+  depth: number;
+  files: number;
+};
+
+export type DatasetRootGroup = {
+  kind: DatasetRootKind;
+  roots: DatasetRoot[];
+};
+
+export type DatasetTreeScan = {
+  root: string;
+  maxDepth: number;
+  groups: DatasetRootGroup[];
+  dirsScanned: number;
+  skipped: string[];
+  skippedCount: number;
+  truncated: boolean;
+};
+
+export type WorkspaceRoot = {
+  path: string;
+  label?: string;
+  lastOpened?: number;
+};
+
+export type Workspace = {
+  name: string;
+  roots: WorkspaceRoot[];
+};
+
+export type WorkspaceRootStatus = WorkspaceRoot & {
+  exists: boolean;
+  modified: number | null;
+  dataset: LocalDatasetDetectResponse | null;
+  error: string | null;
+};
+
+export type WorkspaceStatus = {
+  name: string;
+  roots: WorkspaceRootStatus[];
+};
+
+export type ShardBalance = {
+  kind: "litdata" | "mds" | "webdataset";
+  names: string[];
+  bytes: number[];
+  samples: number[] | null;
+  medianBytes: number;
+  medianSamples: number | null;
+  thresholdPct: number;
+  bytesOutliers: number[];
+  samplesOutliers: number[];
+  missing: number[];
+};
+
+export type ZenodoTransferEstimate = {
+  fileSize: number | null;
+  bytes: number;
+  approximate: boolean;
+  tarOffset: number | null;
+  needsConfirmation: boolean;
+};
+
+export type OpenManyFailure = {
+  /** Position of the leaf in the request. */
+  index: number;
+  error: string;
+};
+
+export type OpenedSession = {
+  path: string;
+  /** File names inside `path`, in request order; failed leaves are missing. */
+  files: string[];
+  totalBytes: number;
+  failed: OpenManyFailure[];
+  opened: boolean;
+  message: string;
+};
+
+/** Local automation endpoint: scripts POST camelCase arguments to `${url}/invoke/<command>`. */
+export type AutomationEndpoint = {
+  enabled: boolean;
+  url: string | null;
+  token: string | null;
+  commands: string[];
+};
+
+export type UpdateChannel = "stable" | "beta";
+
+export type AvailableUpdate = {
+  version: string;
+  currentVersion: string;
+  date: string | null;
+  body: string | null;
+  channel: UpdateChannel;
+};
+
+export type CacheUsage = {
+  name: "media" | "litdataChunks" | "webdatasetScans" | "tarScans";
+  bytes: number;
+  entries: number;
+};
+
+export type MemoryUsage = {
+  caches: CacheUsage[];
+  totalBytes: number;
+  capBytes: number;
+  warnBytes: number;
+};
+
+export type MemoryWarning = {
+  usage: MemoryUsage;
+  /** Bytes dropped from caches to get back under the cap; 0 for an early warning. */
+  trimmedBytes: number;
+};
+
+export type DownloadProgress = {
+  jobId: string;
+  file: string;
+  downloaded: number;
+  total: number | null;
+};
+
+export const DOWNLOAD_PROGRESS_EVENT = "download://progress";
+
+/** A server answered 429 (or 503 with Retry-After); the request is retried after `retryInSecs`. */
+export type HttpThrottled = {
+  host: string;
+  url: string;
+  status: number;
+  attempt: number;
+  retryInSecs: number;
+};
+
+export const HTTP_THROTTLED_EVENT = "http://throttled";
+
+export type TailPreview = {
+  /** Ends at the last byte; starts at a line boundary when the window begins mid-file. */
+  previewText: string | null;
+  hexSnippet: string;
+  isBinary: boolean;
+  windowStart: number;
+  size: number;
+};
+
+export type ZenodoTarScanProgress = {
+  contentUrl: string;
+  entriesScanned: number;
+  bytesScanned: number;
+  done: boolean;
+  cancelled: boolean;
+  error: string | null;
+  /** Whether the archive matched its Zenodo checksum; null until the scan ends or when it cannot be checked. */
+  checksumVerified: boolean | null;
+};
+
+export const TAR_SCAN_PROGRESS_EVENT = "zenodo://tar-scan-progress";
+
+export type ZenodoGlobPeekEntry = {
+  name: string;
+  size: number;
+  preview: FieldPreview;
+};
+
+export type ZenodoGlobPeekResponse = {
+  entries: ZenodoGlobPeekEntry[];
+  /** More entries may match than were previewed. */
+  limitReached: boolean;
+};
+
+export type ZenodoTarFindResponse = {
+  /** Position in the entry listing, for jumping to the right page. */
+  index: number | null;
+  entry: ZenodoTarEntrySummary | null;
+  preview: FieldPreview | null;
+  entriesScanned: number;
+  done: boolean;
+};
+
+export type ZenodoZipEntrySummary = {
+  name: string;
+  method: number;
+  compressedSize: number;
+  uncompressedSize: number;
+  isDir: boolean;
+};
+
+export type ArchiveSort = "name" | "size";
+
+export type ArchiveListQuery = {
+  prefix?: string;
+  glob?: string;
+  extensions?: string[];
+  /** Tree mode: immediate children of this directory ("" for the root). */
+  directory?: string;
+  sort?: ArchiveSort;
+  descending?: boolean;
+};
+
+export type ZenodoZipEntryListResponse = {
+  offset: number;
+  length: number;
+  entries: ZenodoZipEntrySummary[];
+  numMatching: number;
+  numEntriesTotal: number;
+};
+
+export type ArchiveTreeNode = {
+  name: string;
+  /** Full path with a trailing slash, "" for the archive root. */
+  path: string;
+  numFiles: number;
+  numDirs: number;
+  size: number;
+  storedSize: number;
+  children: ArchiveTreeNode[];
+  /** Subdirectories exist but were left out by the depth limit. */
+  collapsed: boolean;
+};
+
+export type ZenodoArchiveTreeResponse = {
+  root: ArchiveTreeNode;
+  partial: boolean;
+};
+
+export type ArchiveTypeCount = {
+  /** Lowercased extension without the dot; null for files without one. */
+  extension: string | null;
+  count: number;
+  size: number;
+  storedSize: number;
+};
+
+export type ArchiveTypeSummaryResponse = {
+  types: ArchiveTypeCount[];
+  numFiles: number;
+  totalSize: number;
+  partial: boolean;
+};
+
+export type ZenodoTarEntrySummary = {
+  name: string;
+  size: number;
+  isDir: boolean;
+};
+
+export type ZenodoTarEntryListResponse = {
+  offset: number;
+  length: number;
+  entries: ZenodoTarEntrySummary[];
+  partial: boolean;
+  numEntriesTotal?: number | null;
+};
+
+export type SchemaField = {
+  name: string;
+  dtype?: string | null;
+  coverage?: number | null;
+};
+
+export type SchemaSnapshot = {
+  kind: "mds" | "litdata" | "webdataset" | "parquet";
+  source: string;
+  fields: SchemaField[];
+  shardCount: number;
+  divergentShards: DivergentShard[];
+};
+
+export type DivergentShard = {
+  shard: string;
+  missing: string[];
+  retyped: SchemaFieldChange[];
+};
+
+export type SchemaFieldChange = {
+  name: string;
+  leftDtype?: string | null;
+  rightDtype?: string | null;
+};
+
+export type SchemaDiffResponse = {
+  left: SchemaSnapshot;
+  right: SchemaSnapshot;
+  added: SchemaField[];
+  removed: SchemaField[];
+  retyped: SchemaFieldChange[];
+  identical: boolean;
+};
+
+export type MdsHashCheck = {
+  algorithm: string;
+  target: "raw" | "zip";
+  expected: string;
+  actual: string | null;
+  matches: boolean | null;
+  note: string | null;
+};
+
+export type MdsShardValidation = {
+  filename: string;
+  exists: boolean;
+  expectedSamples: number;
+  headerSamples: number | null;
+  expectedBytes: number;
+  actualBytes: number | null;
+  issues: string[];
+  hashChecks: MdsHashCheck[];
+  ok: boolean;
+};
+
+export type MdsValidationReport = {
+  indexPath: string;
+  shardsTotal: number;
+  shardsOk: number;
+  shards: MdsShardValidation[];
+};
+
+export type MdsValidateProgress = {
+  indexPath: string;
+  completed: number;
+  shardsTotal: number;
+  filename: string;
+};
+
+export const MDS_VALIDATE_PROGRESS_EVENT = "mds://validate-progress";
+
+export type ChunkValidation = {
+  filename: string;
+  exists: boolean;
+  expectedItems: number;
+  headerItems: number | null;
+  expectedBytes: number;
+  actualBytes: number | null;
+  sampledItem: number | null;
+  issues: string[];
+  ok: boolean;
+};
+
+export type LitdataValidationReport = {
+  indexPath: string;
+  chunksTotal: number;
+  chunksOk: number;
+  chunks: ChunkValidation[];
+};
+
+export type LitdataValidateProgress = {
+  indexPath: string;
+  completed: number;
+  chunksTotal: number;
+  filename: string;
+};
+
+export const LITDATA_VALIDATE_PROGRESS_EVENT = "litdata://validate-progress";
+
+export type OpenerTemplate = {
+  id: string;
+  name: string;
+  /** Command line with {path}, {dir}, {name}, {stem} or {ext} placeholders. */
+  command: string;
+  /** Extensions this tool applies to; empty means any file. */
+  extensions: string[];
+};
+
+export type InstalledApp = {
+  name: string;
+  /** Pass back as `openerAppPath` / `appPath`. */
+  path: string;
+  /** Icon file path, or a theme icon name on Linux. */
+  icon: string | null;
+  isDefault: boolean;
+};
+
+/** macOS handling of the quarantine attribute on files extracted for opening. */
+export type QuarantineMode = "leave" | "set" | "strip";
+
+export type Annotation = {
+  id: number;
+  source: string;
+  shard: string;
+  sample: string;
+  tag: string;
+  note: string | null;
+  createdAt: number;
+  updatedAt: number;
+};
+
+export type AnnotationExportFormat = "csv" | "json";
+
+export type ListingExportFormat = "csv" | "jsonl";
+
+/** One row of the (filtered) sample table; `metadata` holds derived columns. */
+export type ListingRow = {
+  key: string;
+  fields: { name: string; size: number }[];
+  metadata?: Record<string, unknown>;
+};
+
+export type ListingExportReport = {
+  path: string;
+  count: number;
+};
+
+export type AnnotationExportReport = {
+  path: string;
+  count: number;
+};
+
+/** One remote request made by the app. `at` is in seconds since the Unix epoch. */
+export type AccessLogEntry = {
+  id: number;
+  at: number;
+  scheme: string;
+  host: string;
+  path: string;
+  status: number | null;
+  bytes: number | null;
+};
+
+export type AccessLogExportFormat = "csv" | "json";
+
+export type AccessLogExportReport = {
+  path: string;
+  count: number;
+};
+
+export type ReportFormat = "html" | "markdown";
+
+export type ReportResult = {
+  path: string;
+  bytes: number;
+  samplesPreviewed: number;
+  issues: number;
+};
+
+/** One sample, and optionally one field of it, in any supported dataset format. */
+export type SampleLocation =
+  | {
+      kind: "litdata";
+      indexPath: string;
+      chunkFilename: string;
+      itemIndex: number;
+      fieldIndex?: number | null;
+    }
+  | {
+      kind: "mds";
+      indexPath: string;
+      shardFilename: string;
+      itemIndex: number;
+      fieldIndex?: number | null;
+    }
+  | {
+      kind: "webdataset";
+      dirPath: string;
+      shardFilename: string;
+      sampleKey: string;
+      field?: string | null;
+    }
+  | {
+      kind: "huggingface";
+      dataset: string;
+      config?: string | null;
+      split: string;
+      rowIndex: number;
+      fieldName?: string | null;
+    };
+
+export type PythonSnippet = {
+  code: string;
+  package: string;
+};
+
+export type DeclaredField = {
+  name: string;
+  dataType: string | null;
+  description: string | null;
+};
+
+export type DeclaredSplit = {
+  name: string;
+  numRecords: number | null;
+  numBytes: number | null;
+};
+
+export type DeclaredRecordSet = {
+  name: string;
+  numRecords: number | null;
+  splits: DeclaredSplit[];
+  fields: DeclaredField[];
+};
+
+export type DeclaredFile = {
+  name: string;
+  contentUrl: string | null;
+  contentSize: string | null;
+  encodingFormat: string | null;
+};
+
+export type DeclaredMetadata = {
+  source: string;
+  format: "croissant" | "dataset_infos";
+  name: string | null;
+  description: string | null;
+  licenses: string[];
+  recordSets: DeclaredRecordSet[];
+  files: DeclaredFile[];
+};
+
+export type DeclaredSchemaComparison = {
+  declared: DeclaredMetadata;
+  recordSet: string;
+  diff: SchemaDiffResponse;
+};
+
+export type DatasetDocument = {
+  kind: "license" | "readme" | "datasheet";
+  name: string;
+  size: number;
+  text: string;
+  truncated: boolean;
+  url: string | null;
+};
+
+export type DatasetDocsResponse = {
+  source: string;
+  declaredLicense: string | null;
+  documents: DatasetDocument[];
+};
+
+export type ZenodoRecordVersion = {
+  recordId: number;
+  version: string | null;
+  publicationDate: string | null;
+  doi: string | null;
+  isLatest: boolean;
+};
+
+const STORE_NAME = "dataset-inspector.bin";
+const STORE_LAST_INDEX = "last_index";
+const STORE_HF_TOKEN = "hf_token";
+
+let storeInstance: Store | null = null;
+
+export const isTauri = () => {
+  if (typeof window === "undefined") return false;
+  const w = window as Window & {
+    __TAURI_INTERNALS__?: unknown;
+    __TAURI__?: unknown;
+    __TAURI_METADATA__?: unknown;
+    __TAURI_IPC__?: unknown;
+  };
+  const ua = String(w.navigator?.userAgent || "").toLowerCase();
+  return Boolean(
+    w.__TAURI_INTERNALS__ ||
+      w.__TAURI__ ||
+      w.__TAURI_METADATA__ ||
+      w.__TAURI_IPC__ ||
+      ua.includes("tauri") ||
+      w.location?.protocol === "tauri:" ||
+      (typeof w.location?.href === "string" && w.location.href.startsWith("tauri://"))
+  );
+};
+
+async function requireTauri(task: string) {
+  if (!isTauri()) {
+    throw new Error(`${task} requires the Tauri runtime.`);
+  }
+}
+
+export async function readUpdateChannel(): Promise<UpdateChannel> {
+  if (!isTauri()) return "stable";
+  return invoke<UpdateChannel>("get_update_channel");
+}
+
+export async function saveUpdateChannel(channel: UpdateChannel) {
+  if (!isTauri()) return;
+  await invoke<void>("set_update_channel", { channel });
+}
+
+/** Checks the selected update channel; `null` when up to date. */
+export async function checkAppUpdate(): Promise<AvailableUpdate | null> {
+  if (!isTauri()) return null;
+  return invoke<AvailableUpdate | null>("check_update");
+}
+
+/** Installs the update found by the last `checkAppUpdate`. */
+export async function downloadAndInstallAppUpdate(params: {
+  update: AvailableUpdate;
+  onProgress?: (event: DownloadEvent) => void;
+}): Promise<void> {
+  await requireTauri("Installing update");
+  const onProgress = params.onProgress;
+  const unlisten = onProgress
+    ? await listen<DownloadEvent>("updater://progress", (event) => onProgress(event.payload))
+    : null;
+  try {
+    await invoke<void>("apply_update");
+  } finally {
+    unlisten?.();
+  }
+}
+
+/** Application menu items the UI acts on; each arrives as an `app://<command>` event. */
+export type MenuCommand = "open" | "reload-source" | "copy-preview" | "check-updates";
+
+export async function onMenuCommand(command: MenuCommand, handler: () => void): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  return listen(`app://${command}`, () => handler());
+}
+
+export async function readMemoryUsage(): Promise<MemoryUsage> {
+  await requireTauri("Reading cache memory usage");
+  return invoke<MemoryUsage>("get_memory_usage");
+}
+
+export async function saveMemoryCap(capMb: number): Promise<MemoryUsage> {
+  await requireTauri("Setting the cache memory cap");
+  return invoke<MemoryUsage>("set_memory_cap", { capMb: Math.round(capMb) });
+}
+
+export async function onMemoryWarning(handler: (warning: MemoryWarning) => void): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  return listen<MemoryWarning>("resources://memory-warning", (event) => handler(event.payload));
+}
+
+export async function relaunchApp(): Promise<void> {
+  await requireTauri("Restarting app");
+  await relaunch();
+}
+
+async function getStore(): Promise<Store> {
+  if (storeInstance) return storeInstance;
+  await requireTauri("Reading preferences");
+  storeInstance = await Store.load(STORE_NAME);
+  return storeInstance;
+}
+
+export async function saveLastIndex(indexPath: string) {
+  if (!isTauri()) return;
+  const store = await getStore();
+  await store.set(STORE_LAST_INDEX, indexPath);
+  await store.save();
+}
+
+export async function readLastIndex(): Promise<string | null> {
+  if (!isTauri()) return null;
+  const store = await getStore();
+  return (await store.get<string>(STORE_LAST_INDEX)) ?? null;
+}
+
+export async function readPreferredOpenerForExt(ext: string): Promise<string | null> {
+  if (!isTauri()) return null;
+  const normalized = ext.trim().replace(/^\./, "").toLowerCase();
+  if (!normalized) return null;
+  return invoke<string | null>("get_default_opener", { ext: normalized });
+}
+
+export async function savePreferredOpenerForExt(ext: string, appPath: string | null) {
+  if (!isTauri()) return;
+  const normalized = ext.trim().replace(/^\./, "").toLowerCase();
+  if (!normalized) return;
+  const trimmedPath = (appPath ?? "").trim();
+  await invoke<void>("set_default_opener", { ext: normalized, appPath: trimmedPath || null });
+}
+
+export async function readQuarantineMode(): Promise<QuarantineMode> {
+  if (!isTauri()) return "leave";
+  return invoke<QuarantineMode>("get_quarantine_mode");
+}
+
+export async function saveQuarantineMode(mode: QuarantineMode) {
+  if (!isTauri()) return;
+  await invoke<void>("set_quarantine_mode", { mode });
+}
+
+export async function readMaxRequestsPerHost(): Promise<number> {
+  if (!isTauri()) return 4;
+  return invoke<number>("get_max_requests_per_host");
+}
+
+export async function saveMaxRequestsPerHost(limit: number) {
+  if (!isTauri()) return;
+  await invoke<void>("set_max_requests_per_host", { limit: Math.round(limit) });
+}
+
+export async function readOfflineMode(): Promise<boolean> {
+  if (!isTauri()) return false;
+  return invoke<boolean>("get_offline_mode");
+}
+
+export async function saveOfflineMode(enabled: boolean) {
+  if (!isTauri()) return;
+  await invoke<void>("set_offline_mode", { enabled });
+}
+
+export async function readReadOnlyMode(): Promise<boolean> {
+  if (!isTauri()) return false;
+  return invoke<boolean>("get_read_only_mode");
+}
+
+export async function saveReadOnlyMode(enabled: boolean) {
+  if (!isTauri()) return;
+  await invoke<void>("set_read_only_mode", { enabled });
+}
+
+export type ReadOnlyDryRunEntry = {
+  at: number;
+  what: string;
+  path: string;
+};
+
+/** Writes read-only mode stopped, oldest first. */
+export async function readReadOnlyDryRun(): Promise<ReadOnlyDryRunEntry[]> {
+  if (!isTauri()) return [];
+  return invoke<ReadOnlyDryRunEntry[]>("get_read_only_dry_run");
+}
+
+export async function clearReadOnlyDryRun() {
+  if (!isTauri()) return;
+  await invoke<void>("clear_read_only_dry_run");
+}
+
+export async function readAutomationEndpoint(): Promise<AutomationEndpoint | null> {
+  if (!isTauri()) return null;
+  return invoke<AutomationEndpoint>("get_automation_endpoint");
+}
+
+export async function saveAutomationEndpoint(params: {
+  enabled: boolean;
+  port?: number | null;
+  rotateToken?: boolean | null;
+}): Promise<AutomationEndpoint> {
+  await requireTauri("Changing the automation endpoint");
+  return invoke<AutomationEndpoint>("set_automation_endpoint", {
+    enabled: params.enabled,
+    port: params.port ?? null,
+    rotateToken: params.rotateToken ?? null,
+  });
+}
+
+/** Languages backend status messages are available in, as BCP 47 tags. */
+export async function listMessageLanguages(): Promise<string[]> {
+  if (!isTauri()) return ["en"];
+  return invoke<string[]>("list_message_languages");
+}
+
+export async function readMessageLanguage(): Promise<string> {
+  if (!isTauri()) return "en";
+  return invoke<string>("get_message_language");
+}
+
+/** Switches backend messages to the closest supported language; returns the one picked. */
+export async function saveMessageLanguage(language: string): Promise<string> {
+  if (!isTauri()) return "en";
+  return invoke<string>("set_message_language", { language: language.trim() });
+}
+
+export async function readTrustedRepositoryHosts(): Promise<string[]> {
+  if (!isTauri()) return [];
+  return invoke<string[]>("get_trusted_repository_hosts");
+}
+
+export async function saveTrustedRepositoryHosts(hosts: string[]): Promise<string[]> {
+  if (!isTauri()) return hosts;
+  return invoke<string[]>("set_trusted_repository_hosts", { hosts });
+}
+
+/** A regex scanned for by the PII scan in addition to the built-in patterns. */
+export type PiiPattern = {
+  name: string;
+  pattern: string;
+};
+
+export async function readPiiPatterns(): Promise<PiiPattern[]> {
+  if (!isTauri()) return [];
+  return invoke<PiiPattern[]>("get_pii_patterns");
+}
+
+export async function savePiiPatterns(patterns: PiiPattern[]): Promise<PiiPattern[]> {
+  if (!isTauri()) return patterns;
+  return invoke<PiiPattern[]>("set_pii_patterns", { patterns });
+}
+
+/**
+ * How to decode a field instead of the guessed type: "text", "json", a little-endian dtype
+ * ("int8" … "uint64", "float32", "float64") with an optional shape, or any extension.
+ */
+export type DecodeOverride = {
+  decodeAs: string;
+  shape?: number[];
+};
+
+/**
+ * Overrides for one dataset keyed by field index, column name, format, or extension. The dataset
+ * is the index path, WebDataset directory, or content URL it is opened from.
+ */
+export type DecodeProfile = Record<string, DecodeOverride>;
+
+export async function readDecodeProfile(dataset: string): Promise<DecodeProfile> {
+  if (!isTauri()) return {};
+  return invoke<DecodeProfile>("get_decode_profile", { dataset: dataset.trim() });
+}
+
+export async function saveDecodeProfile(
+  dataset: string,
+  profile: DecodeProfile,
+): Promise<DecodeProfile> {
+  if (!isTauri()) return profile;
+  return invoke<DecodeProfile>("set_decode_profile", { dataset: dataset.trim(), profile });
+}
+
+/** Settings from a `.dataset-inspector.toml` at the dataset root. */
+export type DatasetProfile = {
+  /** The profile file. */
+  path: string;
+  /** Display names keyed by field index, column name, or WebDataset field suffix. */
+  rename: Record<string, string>;
+  /** Overrides the user sets in the app take precedence over these. */
+  decode: DecodeProfile;
+  defaultShard: string | null;
+  hiddenFields: string[];
+};
+
+/** The dataset's profile file, or null when it has none. Rejects when the file is invalid. */
+export async function getDatasetProfile(path: string): Promise<DatasetProfile | null> {
+  if (!isTauri()) return null;
+  const trimmed = path.trim();
+  if (!trimmed) throw new Error("Missing dataset path.");
+  return invoke<DatasetProfile | null>("get_dataset_profile", { path: trimmed });
+}
+
+export type DecoderPluginInfo = {
+  name: string;
+  path: string;
+  extensions: string[];
+  description?: string | null;
+  error?: string | null;
+};
+
+/** WASM modules in `dir`; see `decoder_plugins.rs` for the `alloc`/`decode` ABI. */
+export type DecoderPluginList = {
+  dir: string;
+  plugins: DecoderPluginInfo[];
+};
+
+export async function listDecoderPlugins(): Promise<DecoderPluginList> {
+  await requireTauri("Listing decoder plugins");
+  return invoke<DecoderPluginList>("list_decoder_plugins");
+}
+
+export async function reloadDecoderPlugins(): Promise<DecoderPluginList> {
+  await requireTauri("Reloading decoder plugins");
+  return invoke<DecoderPluginList>("reload_decoder_plugins");
+}
+
+export async function readHfToken(): Promise<string | null> {
+  if (!isTauri()) return null;
+  const store = await getStore();
+  const value = (await store.get<string>(STORE_HF_TOKEN)) ?? null;
+  const trimmed = String(value ?? "").trim();
+  return trimmed ? trimmed : null;
+}
+
+export async function saveHfToken(token: string) {
+  if (!isTauri()) return;
+  const trimmed = token.trim();
+  const store = await getStore();
+  await store.set(STORE_HF_TOKEN, trimmed);
+  await store.save();
+}
+
+export async function clearHfToken() {
+  if (!isTauri()) return;
+  const store = await getStore();
+  await store.delete(STORE_HF_TOKEN);
+  await store.save();
+}
+
+export async function chooseOpenerApp(): Promise<string | null> {
+  await requireTauri("Choosing an application");
+  const ua = typeof navigator === "undefined" ? "" : String(navigator.userAgent || "");
+  const isMac = /Macintosh|Mac OS X/i.test(ua);
+  const picked = await openDialog({
+    title: isMac ? "Choose an application (.app)" : "Choose an application",
+    multiple: false,
+    ...(isMac
+      ? {
           filters: [{ name: "Applications", extensions: ["app"] }],
           defaultPath: "/Applications",
         }
@@ -357,6 +1683,13 @@ export async function loadIndex(indexPath: string): Promise<IndexSummary> {
   return invoke<IndexSummary>("load_index", { indexPath: trimmed });
 }
 
+export async function validateIndex(indexPath: string): Promise<LitdataValidationReport> {
+  await requireTauri("Validating LitData chunks");
+  const trimmed = indexPath.trim();
+  if (!trimmed) throw new Error("Provide an index.json path to validate.");
+  return invoke<LitdataValidationReport>("validate_index", { indexPath: trimmed });
+}
+
 export async function loadChunkList(paths: string[]): Promise<IndexSummary> {
   await requireTauri("Loading chunks");
   if (!paths.length) throw new Error("Select at least one chunk file to load.");
@@ -380,9 +1713,49 @@ export async function mosaicmlPeekField(params: {
   shardFilename: string;
   itemIndex: number;
   fieldIndex: number;
-}): Promise<FieldPreview> {
+  /** Byte offset of the preview window inside the field. */
+  offset?: number;
+} & PreviewLimits): Promise<FieldPreview> {
   await requireTauri("Previewing MosaicML MDS field");
-  return invoke<FieldPreview>("mosaicml_peek_field", params);
+  return invoke<FieldPreview>("mosaicml_peek_field", {
+    ...params,
+    offset: params.offset ?? null,
+    ...previewLimitArgs(params),
+  });
+}
+
+export async function mosaicmlPeekPage(params: {
+  indexPath: string;
+  shardFilename: string;
+  offset?: number;
+  length?: number;
+}): Promise<MdsPeekPageResponse> {
+  await requireTauri("Previewing MosaicML MDS samples");
+  return invoke<MdsPeekPageResponse>("mosaicml_peek_page", {
+    ...params,
+    offset: params.offset ?? null,
+    length: params.length ?? null,
+  });
+}
+
+export async function mosaicmlGetSampleJson(params: {
+  indexPath: string;
+  shardFilename: string;
+  itemIndex: number;
+}): Promise<MdsSampleJson> {
+  await requireTauri("Decoding a MosaicML MDS sample");
+  return invoke<MdsSampleJson>("mosaicml_get_sample_json", params);
+}
+
+/** Evenly samples a float vector column across the dataset and projects it to 2D with PCA. */
+export async function mosaicmlEmbeddingProjection(params: {
+  indexPath: string;
+  fieldIndex: number;
+  maxSamples?: number | null;
+  method?: ProjectionMethod | null;
+}): Promise<EmbeddingProjection> {
+  await requireTauri("Projecting MosaicML MDS embeddings");
+  return invoke<EmbeddingProjection>("mosaicml_embedding_projection", params);
 }
 
 export async function mosaicmlOpenLeaf(params: {
@@ -396,6 +1769,19 @@ export async function mosaicmlOpenLeaf(params: {
   return invoke<OpenLeafResponse>("mosaicml_open_leaf", params);
 }
 
+export async function mosaicmlSaveLeafAs(params: {
+  indexPath: string;
+  shardFilename: string;
+  itemIndex: number;
+  fieldIndex: number;
+  destinationPath: string;
+}): Promise<SavedLeafResponse> {
+  await requireTauri("Saving MosaicML MDS field");
+  const destinationPath = params.destinationPath.trim();
+  if (!destinationPath) throw new Error("Choose where to save the file.");
+  return invoke<SavedLeafResponse>("mosaicml_save_leaf_as", { ...params, destinationPath });
+}
+
 export async function mosaicmlPrepareAudioPreview(params: {
   indexPath: string;
   shardFilename: string;
@@ -406,6 +1792,16 @@ export async function mosaicmlPrepareAudioPreview(params: {
   return invoke<PreparedFileResponse>("mosaicml_prepare_audio_preview", params);
 }
 
+export async function mosaicmlValidate(params: {
+  indexPath: string;
+  verifyHashes?: boolean;
+}): Promise<MdsValidationReport> {
+  await requireTauri("Validating MosaicML MDS shards");
+  const indexPath = params.indexPath.trim();
+  if (!indexPath) throw new Error("Provide an MDS index.json path or directory.");
+  return invoke<MdsValidationReport>("mosaicml_validate", { indexPath, verifyHashes: params.verifyHashes ?? false });
+}
+
 export async function listChunkItems(params: { indexPath: string; chunkFilename: string }): Promise<ItemMeta[]> {
   await requireTauri("Reading chunk");
   return invoke<ItemMeta[]>("list_chunk_items", params);
@@ -416,9 +1812,24 @@ export async function peekField(params: {
   chunkFilename: string;
   itemIndex: number;
   fieldIndex: number;
-}): Promise<FieldPreview> {
+  /** Byte offset of the preview window inside the field. */
+  offset?: number;
+} & PreviewLimits): Promise<FieldPreview> {
   await requireTauri("Previewing data");
-  return invoke<FieldPreview>("peek_field", params);
+  return invoke<FieldPreview>("peek_field", {
+    ...params,
+    offset: params.offset ?? null,
+    ...previewLimitArgs(params),
+  });
+}
+
+export async function getItemJson(params: {
+  indexPath: string;
+  chunkFilename: string;
+  itemIndex: number;
+}): Promise<LitdataItemJson> {
+  await requireTauri("Decoding an item");
+  return invoke<LitdataItemJson>("get_item_json", params);
 }
 
 export async function openLeaf(params: {
@@ -432,6 +1843,19 @@ export async function openLeaf(params: {
   return invoke<OpenLeafResponse>("open_leaf", params);
 }
 
+export async function saveLeafAs(params: {
+  indexPath: string;
+  chunkFilename: string;
+  itemIndex: number;
+  fieldIndex: number;
+  destinationPath: string;
+}): Promise<SavedLeafResponse> {
+  await requireTauri("Saving field");
+  const destinationPath = params.destinationPath.trim();
+  if (!destinationPath) throw new Error("Choose where to save the file.");
+  return invoke<SavedLeafResponse>("save_leaf_as", { ...params, destinationPath });
+}
+
 export async function prepareAudioPreview(params: {
   indexPath: string;
   chunkFilename: string;
@@ -460,13 +1884,152 @@ export async function wdsListSamples(params: {
   return invoke<WdsSampleListResponse>("wds_list_samples", params);
 }
 
+/**
+ * Streams a shard listing: pages arrive as `wds://samples-page` events tagged with `requestId`,
+ * and the promise resolves once the stream finishes.
+ */
+export async function wdsStreamSamples(params: {
+  dirPath: string;
+  shardFilename: string;
+  requestId: string;
+  pageSize?: number;
+  maxSamples?: number | null;
+}): Promise<WdsStreamSummary> {
+  await requireTauri("Listing WebDataset samples");
+  return invoke<WdsStreamSummary>("wds_stream_samples", {
+    dirPath: params.dirPath,
+    shardFilename: params.shardFilename,
+    requestId: params.requestId,
+    pageSize: params.pageSize ?? null,
+    maxSamples: params.maxSamples ?? null,
+  });
+}
+
+/** Last `length` bytes of a shard member (64 KiB by default). */
+export async function wdsPeekMemberTail(params: {
+  dirPath: string;
+  shardFilename: string;
+  memberPath: string;
+  length?: number;
+}): Promise<TailPreview> {
+  await requireTauri("Previewing WebDataset member");
+  return invoke<TailPreview>("wds_peek_member_tail", { ...params, length: params.length ?? null });
+}
+
 export async function wdsPeekMember(params: {
   dirPath: string;
   shardFilename: string;
   memberPath: string;
-}): Promise<FieldPreview> {
+  /** Byte offset of the preview window inside the member. */
+  offset?: number;
+} & PreviewLimits): Promise<FieldPreview> {
   await requireTauri("Previewing WebDataset member");
-  return invoke<FieldPreview>("wds_peek_member", params);
+  return invoke<FieldPreview>("wds_peek_member", {
+    ...params,
+    offset: params.offset ?? null,
+    ...previewLimitArgs(params),
+  });
+}
+
+export async function wdsPeekPage(params: {
+  dirPath: string;
+  shardFilename: string;
+  offset?: number;
+  length?: number;
+}): Promise<WdsPeekPageResponse> {
+  await requireTauri("Previewing WebDataset samples");
+  return invoke<WdsPeekPageResponse>("wds_peek_page", {
+    ...params,
+    offset: params.offset ?? null,
+    length: params.length ?? null,
+  });
+}
+
+export async function readWdsPrefetchDepth(): Promise<number> {
+  if (!isTauri()) return 2;
+  return invoke<number>("get_wds_prefetch_depth");
+}
+
+export async function saveWdsPrefetchDepth(depth: number) {
+  if (!isTauri()) return;
+  await invoke<void>("set_wds_prefetch_depth", { depth: Math.max(0, Math.round(depth)) });
+}
+
+export async function watchDatasetDir(dirPath: string): Promise<string> {
+  await requireTauri("Watching dataset directory");
+  const trimmed = dirPath.trim();
+  if (!trimmed) throw new Error("Provide a directory to watch.");
+  return invoke<string>("watch_dataset_dir", { dirPath: trimmed });
+}
+
+export async function unwatchDatasetDir(dirPath: string): Promise<boolean> {
+  if (!isTauri()) return false;
+  return invoke<boolean>("unwatch_dataset_dir", { dirPath: dirPath.trim() });
+}
+
+export async function invalidateScanCache(source?: string | null): Promise<number> {
+  if (!isTauri()) return 0;
+  const trimmed = String(source ?? "").trim();
+  return invoke<number>("invalidate_scan_cache", { source: trimmed || null });
+}
+
+export async function wdsThumbnails(params: {
+  dirPath: string;
+  shardFilename: string;
+  memberPaths: string[];
+  maxEdge?: number | null;
+}): Promise<Thumbnail[]> {
+  await requireTauri("Generating WebDataset thumbnails");
+  if (!params.memberPaths.length) return [];
+  return invoke<Thumbnail[]>("wds_thumbnails", params);
+}
+
+export async function wdsLint(params: {
+  dirPath: string;
+  shardFilename: string;
+  maxMembers?: number | null;
+}): Promise<WdsLintReport> {
+  await requireTauri("Linting WebDataset shard");
+  return invoke<WdsLintReport>("wds_lint", params);
+}
+
+/** Review list of blank, tiny, elongated and undecodable images in a shard. */
+export async function wdsImageFlags(params: {
+  dirPath: string;
+  shardFilename: string;
+  maxImages?: number | null;
+  minEdge?: number | null;
+  maxAspectRatio?: number | null;
+}): Promise<WdsImageFlagReport> {
+  await requireTauri("Flagging WebDataset images");
+  return invoke<WdsImageFlagReport>("wds_image_flags", params);
+}
+
+/** Text members of a shard that look like they contain PII or profanity. */
+export async function wdsPiiScan(params: {
+  dirPath: string;
+  shardFilename: string;
+  maxMembers?: number | null;
+}): Promise<WdsPiiReport> {
+  await requireTauri("Scanning WebDataset text for PII");
+  return invoke<WdsPiiReport>("wds_pii_scan", params);
+}
+
+/** Byte offsets of every sample and member in a shard's tar stream. */
+export async function wdsShardLayout(params: { dirPath: string; shardFilename: string }): Promise<WdsShardLayout> {
+  await requireTauri("Mapping WebDataset shard layout");
+  return invoke<WdsShardLayout>("wds_shard_layout", params);
+}
+
+export async function wdsEmbeddingProjection(params: {
+  dirPath: string;
+  shardFilename: string;
+  field: string;
+  maxSamples?: number | null;
+  method?: ProjectionMethod | null;
+}): Promise<EmbeddingProjection> {
+  await requireTauri("Projecting WebDataset embeddings");
+  return invoke<EmbeddingProjection>("wds_embedding_projection", params);
 }
 
 export async function wdsOpenMember(params: {
@@ -479,6 +2042,28 @@ export async function wdsOpenMember(params: {
   return invoke<OpenLeafResponse>("wds_open_member", params);
 }
 
+/** Extracts every member of a sample into a temp folder and opens the folder. */
+export async function wdsOpenSample(params: {
+  dirPath: string;
+  shardFilename: string;
+  key: string;
+}): Promise<WdsOpenedSample> {
+  await requireTauri("Opening WebDataset sample");
+  return invoke<WdsOpenedSample>("wds_open_sample", params);
+}
+
+export async function wdsSaveMemberAs(params: {
+  dirPath: string;
+  shardFilename: string;
+  memberPath: string;
+  destinationPath: string;
+}): Promise<SavedLeafResponse> {
+  await requireTauri("Saving WebDataset member");
+  const destinationPath = params.destinationPath.trim();
+  if (!destinationPath) throw new Error("Choose where to save the file.");
+  return invoke<SavedLeafResponse>("wds_save_member_as", { ...params, destinationPath });
+}
+
 export async function wdsPrepareAudioPreview(params: {
   dirPath: string;
   shardFilename: string;
@@ -488,6 +2073,16 @@ export async function wdsPrepareAudioPreview(params: {
   return invoke<PreparedFileResponse>("wds_prepare_audio_preview", params);
 }
 
+/** Prepares several audio members with one pass over the shard; results follow `memberPaths`. */
+export async function wdsPrepareAudioPreviews(params: {
+  dirPath: string;
+  shardFilename: string;
+  memberPaths: string[];
+}): Promise<PreparedFileResponse[]> {
+  await requireTauri("Preparing WebDataset audio previews");
+  return invoke<PreparedFileResponse[]>("wds_prepare_audio_previews", params);
+}
+
 export function toFileSrc(path: string): string {
   if (!isTauri()) return path;
   return convertFileSrc(path);
@@ -502,6 +2097,46 @@ export async function openPathWithApp(params: { path: string; appPath: string })
   return invoke<string>("open_path_with_app", { path, appPath });
 }
 
+/** Windows only: shows the system "Open with" chooser for a file. */
+export async function openWithSystemPicker(path: string): Promise<string> {
+  await requireTauri("Opening with app");
+  const trimmed = path.trim();
+  if (!trimmed) throw new Error("Missing file path to open.");
+  return invoke<string>("open_with_system_picker", { path: trimmed });
+}
+
+export async function listInstalledApps(ext: string): Promise<InstalledApp[]> {
+  await requireTauri("Listing installed applications");
+  const normalized = ext.trim().replace(/^\./, "").toLowerCase();
+  if (!normalized) return [];
+  return invoke<InstalledApp[]>("list_installed_apps", { ext: normalized });
+}
+
+export async function listRegisteredOpeners(ext?: string | null): Promise<OpenerTemplate[]> {
+  await requireTauri("Listing external tools");
+  const normalized = (ext ?? "").trim().replace(/^\./, "").toLowerCase();
+  return invoke<OpenerTemplate[]>("list_registered_openers", { ext: normalized || null });
+}
+
+export async function saveOpenerTemplate(template: OpenerTemplate): Promise<OpenerTemplate[]> {
+  await requireTauri("Saving external tool");
+  if (!template.name.trim()) throw new Error("Give the tool a name.");
+  if (!template.command.trim()) throw new Error("Provide a command line for the tool.");
+  return invoke<OpenerTemplate[]>("save_opener_template", { template });
+}
+
+export async function deleteOpenerTemplate(id: string): Promise<OpenerTemplate[]> {
+  await requireTauri("Removing external tool");
+  return invoke<OpenerTemplate[]>("delete_opener_template", { id });
+}
+
+export async function runOpenerTemplate(params: { id: string; path: string }): Promise<string> {
+  await requireTauri("Running external tool");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing file path to open.");
+  return invoke<string>("run_opener_template", { id: params.id, path });
+}
+
 export async function hfDatasetPreview(params: {
   input: string;
   config?: string;
@@ -523,6 +2158,66 @@ export async function hfDatasetPreview(params: {
   });
 }
 
+/** A single row, for jumping to a position without loading the pages around it. */
+export type HfRow = {
+  rowIndex: number;
+  numRowsTotal: number;
+  row: unknown;
+  /** Arrays in `row` that were cut; their `row` is always 0. */
+  truncated: HfTruncatedValue[];
+};
+
+export async function hfGetRow(params: {
+  input: string;
+  config: string;
+  split: string;
+  rowIndex: number;
+  token?: string | null;
+}): Promise<HfRow> {
+  await requireTauri("Loading Hugging Face row");
+  const input = params.input.trim();
+  const config = params.config.trim();
+  const split = params.split.trim();
+  if (!input) throw new Error("Missing dataset input.");
+  if (!config) throw new Error("Missing config.");
+  if (!split) throw new Error("Missing split.");
+  return invoke<HfRow>("hf_get_row", {
+    input,
+    config,
+    split,
+    rowIndex: params.rowIndex,
+    token: params.token ?? null,
+  });
+}
+
+/** The whole value of a field that `hfDatasetPreview` cut short. */
+export async function hfFieldValue(params: {
+  input: string;
+  config: string;
+  split: string;
+  rowIndex: number;
+  fieldName: string;
+  token?: string | null;
+}): Promise<unknown> {
+  await requireTauri("Loading Hugging Face field");
+  const input = params.input.trim();
+  const config = params.config.trim();
+  const split = params.split.trim();
+  const fieldName = params.fieldName.trim();
+  if (!input) throw new Error("Missing dataset input.");
+  if (!config) throw new Error("Missing config.");
+  if (!split) throw new Error("Missing split.");
+  if (!fieldName) throw new Error("Missing field name.");
+  return invoke<unknown>("hf_field_value", {
+    input,
+    config,
+    split,
+    rowIndex: params.rowIndex,
+    fieldName,
+    token: params.token ?? null,
+  });
+}
+
 export async function hfOpenField(params: {
   input: string;
   config: string;
@@ -552,18 +2247,113 @@ export async function hfOpenField(params: {
   });
 }
 
-export async function zenodoRecordSummary(params: { input: string }): Promise<ZenodoRecordSummary> {
+export async function hfSaveFieldAs(params: {
+  input: string;
+  config: string;
+  split: string;
+  rowIndex: number;
+  fieldName: string;
+  destinationPath: string;
+  token?: string | null;
+}): Promise<SavedLeafResponse> {
+  await requireTauri("Saving Hugging Face field");
+  const input = params.input.trim();
+  const config = params.config.trim();
+  const split = params.split.trim();
+  const fieldName = params.fieldName.trim();
+  const destinationPath = params.destinationPath.trim();
+  if (!input) throw new Error("Missing dataset input.");
+  if (!config) throw new Error("Missing config.");
+  if (!split) throw new Error("Missing split.");
+  if (!fieldName) throw new Error("Missing field name.");
+  if (!destinationPath) throw new Error("Choose where to save the file.");
+  return invoke<SavedLeafResponse>("hf_save_field_as", {
+    input,
+    config,
+    split,
+    rowIndex: params.rowIndex,
+    fieldName,
+    destinationPath,
+    token: params.token ?? null,
+  });
+}
+
+export async function zenodoRecordSummary(params: {
+  input: string;
+  forceRefresh?: boolean;
+}): Promise<ZenodoRecordSummary> {
   await requireTauri("Loading Zenodo record");
   const input = params.input.trim();
   if (!input) throw new Error("Provide a Zenodo record URL like https://zenodo.org/records/<id>.");
-  return invoke<ZenodoRecordSummary>("zenodo_record_summary", { input });
+  return invoke<ZenodoRecordSummary>("zenodo_record_summary", {
+    input,
+    forceRefresh: params.forceRefresh ?? false,
+  });
+}
+
+export async function zenodoRecordVersions(params: { input: string }): Promise<ZenodoRecordVersion[]> {
+  await requireTauri("Listing Zenodo record versions");
+  const input = params.input.trim();
+  if (!input) throw new Error("Provide a Zenodo record URL like https://zenodo.org/records/<id>.");
+  return invoke<ZenodoRecordVersion[]>("zenodo_record_versions", { input });
+}
+
+export async function zenodoDownloadRecord(params: { input: string; destDir: string }): Promise<ZenodoRecordDownloadReport> {
+  await requireTauri("Downloading Zenodo record");
+  const input = params.input.trim();
+  const destDir = params.destDir.trim();
+  if (!input) throw new Error("Provide a Zenodo record URL like https://zenodo.org/records/<id>.");
+  if (!destDir) throw new Error("Choose a destination folder.");
+  return invoke<ZenodoRecordDownloadReport>("zenodo_download_record", { input, destDir });
+}
+
+export async function openmlDataset(params: { input: string; rows?: number }): Promise<RepoDatasetSummary> {
+  await requireTauri("Loading OpenML dataset");
+  const input = params.input.trim();
+  if (!input) throw new Error("Provide an OpenML dataset id or URL.");
+  return invoke<RepoDatasetSummary>("openml_dataset", { input, rows: params.rows ?? null });
+}
+
+export async function openmlDownload(params: {
+  input: string;
+  format?: "arff" | "parquet";
+  destDir: string;
+}): Promise<RepoDownloadReport> {
+  await requireTauri("Downloading OpenML dataset");
+  const input = params.input.trim();
+  const destDir = params.destDir.trim();
+  if (!input) throw new Error("Provide an OpenML dataset id or URL.");
+  if (!destDir) throw new Error("Choose a destination folder.");
+  return invoke<RepoDownloadReport>("openml_download", { input, format: params.format ?? null, destDir });
+}
+
+export async function uciDataset(params: { input: string; rows?: number }): Promise<RepoDatasetSummary> {
+  await requireTauri("Loading UCI dataset");
+  const input = params.input.trim();
+  if (!input) throw new Error("Provide a UCI ML repository dataset id or URL.");
+  return invoke<RepoDatasetSummary>("uci_dataset", { input, rows: params.rows ?? null });
 }
 
-export async function zenodoPeekFile(params: { contentUrl: string }): Promise<FieldPreview> {
+export async function uciDownload(params: { input: string; destDir: string }): Promise<RepoDownloadReport> {
+  await requireTauri("Downloading UCI dataset");
+  const input = params.input.trim();
+  const destDir = params.destDir.trim();
+  if (!input) throw new Error("Provide a UCI ML repository dataset id or URL.");
+  if (!destDir) throw new Error("Choose a destination folder.");
+  return invoke<RepoDownloadReport>("uci_download", { input, destDir });
+}
+
+export async function zenodoPeekFile(
+  params: { contentUrl: string; offset?: number } & PreviewLimits,
+): Promise<FieldPreview> {
   await requireTauri("Previewing Zenodo file");
   const contentUrl = params.contentUrl.trim();
   if (!contentUrl) throw new Error("Missing Zenodo content URL.");
-  return invoke<FieldPreview>("zenodo_peek_file", { contentUrl });
+  return invoke<FieldPreview>("zenodo_peek_file", {
+    contentUrl,
+    offset: params.offset ?? null,
+    ...previewLimitArgs(params),
+  });
 }
 
 export async function zenodoOpenFile(params: {
@@ -576,58 +2366,172 @@ export async function zenodoOpenFile(params: {
   const filename = params.filename.trim();
   if (!contentUrl) throw new Error("Missing Zenodo content URL.");
   if (!filename) throw new Error("Missing filename.");
-  return invoke<OpenLeafResponse>("zenodo_open_file", {
+  return invoke<OpenLeafResponse>("zenodo_open_file", {
+    contentUrl,
+    filename,
+    openerAppPath: params.openerAppPath ?? null,
+  });
+}
+
+export async function zenodoZipListEntries(params: {
+  contentUrl: string;
+  filename: string;
+}): Promise<ZenodoZipEntrySummary[]> {
+  await requireTauri("Listing ZIP entries");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  return invoke<ZenodoZipEntrySummary[]>("zenodo_zip_list_entries", { contentUrl, filename });
+}
+
+export async function zenodoZipListEntriesPaged(params: {
+  contentUrl: string;
+  filename: string;
+  offset?: number;
+  length?: number;
+  query?: ArchiveListQuery;
+}): Promise<ZenodoZipEntryListResponse> {
+  await requireTauri("Listing ZIP entries");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  const offset = typeof params.offset === "number" && Number.isFinite(params.offset) ? (params.offset | 0) : 0;
+  const length = typeof params.length === "number" && Number.isFinite(params.length) ? (params.length | 0) : 100;
+  return invoke<ZenodoZipEntryListResponse>("zenodo_zip_list_entries_paged", {
+    contentUrl,
+    filename,
+    offset,
+    length,
+    query: params.query ?? null,
+  });
+}
+
+export async function zenodoArchiveTree(params: {
+  contentUrl: string;
+  filename: string;
+  directory?: string;
+  depth?: number;
+}): Promise<ZenodoArchiveTreeResponse> {
+  await requireTauri("Building the archive tree");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  return invoke<ZenodoArchiveTreeResponse>("zenodo_archive_tree", {
+    contentUrl,
+    filename,
+    directory: params.directory ?? null,
+    depth: params.depth ?? null,
+  });
+}
+
+export async function archiveTypeSummary(params: {
+  contentUrl: string;
+  filename: string;
+}): Promise<ArchiveTypeSummaryResponse> {
+  await requireTauri("Summarizing archive contents");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  return invoke<ArchiveTypeSummaryResponse>("archive_type_summary", { contentUrl, filename });
+}
+
+/** Last `length` bytes of a Zenodo file (64 KiB by default). */
+export async function zenodoPeekTail(params: { contentUrl: string; length?: number }): Promise<TailPreview> {
+  await requireTauri("Previewing Zenodo file");
+  const contentUrl = params.contentUrl.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  return invoke<TailPreview>("zenodo_peek_tail", { contentUrl, length: params.length ?? null });
+}
+
+export async function zenodoZipPeekEntry(params: {
+  contentUrl: string;
+  filename: string;
+  entryName: string;
+  offset?: number;
+} & PreviewLimits): Promise<FieldPreview> {
+  await requireTauri("Previewing ZIP entry");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  const entryName = params.entryName.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  if (!entryName) throw new Error("Missing ZIP entry name.");
+  return invoke<FieldPreview>("zenodo_zip_peek_entry", {
     contentUrl,
     filename,
-    openerAppPath: params.openerAppPath ?? null,
+    entryName,
+    offset: params.offset ?? null,
+    ...previewLimitArgs(params),
   });
 }
 
-export async function zenodoZipListEntries(params: {
+/** Previews every ZIP entry matching a glob such as `metadata/*.json`. */
+export async function zenodoZipPeekGlob(params: {
   contentUrl: string;
   filename: string;
-}): Promise<ZenodoZipEntrySummary[]> {
-  await requireTauri("Listing ZIP entries");
+  glob: string;
+  maxEntries?: number;
+}): Promise<ZenodoGlobPeekResponse> {
+  await requireTauri("Previewing ZIP entries");
   const contentUrl = params.contentUrl.trim();
   const filename = params.filename.trim();
+  const glob = params.glob.trim();
   if (!contentUrl) throw new Error("Missing Zenodo content URL.");
   if (!filename) throw new Error("Missing filename.");
-  return invoke<ZenodoZipEntrySummary[]>("zenodo_zip_list_entries", { contentUrl, filename });
+  if (!glob) throw new Error("Provide a glob pattern.");
+  return invoke<ZenodoGlobPeekResponse>("zenodo_zip_peek_glob", {
+    contentUrl,
+    filename,
+    glob,
+    maxEntries: params.maxEntries ?? null,
+  });
 }
 
-export async function zenodoZipPeekEntry(params: {
+export async function zenodoZipOpenEntry(params: {
   contentUrl: string;
   filename: string;
   entryName: string;
-}): Promise<FieldPreview> {
-  await requireTauri("Previewing ZIP entry");
+  openerAppPath?: string | null;
+}): Promise<OpenLeafResponse> {
+  await requireTauri("Opening ZIP entry");
   const contentUrl = params.contentUrl.trim();
   const filename = params.filename.trim();
   const entryName = params.entryName.trim();
   if (!contentUrl) throw new Error("Missing Zenodo content URL.");
   if (!filename) throw new Error("Missing filename.");
   if (!entryName) throw new Error("Missing ZIP entry name.");
-  return invoke<FieldPreview>("zenodo_zip_peek_entry", { contentUrl, filename, entryName });
+  return invoke<OpenLeafResponse>("zenodo_zip_open_entry", {
+    contentUrl,
+    filename,
+    entryName,
+    openerAppPath: params.openerAppPath ?? null,
+  });
 }
 
-export async function zenodoZipOpenEntry(params: {
+export async function zenodoZipSaveEntryAs(params: {
   contentUrl: string;
   filename: string;
   entryName: string;
-  openerAppPath?: string | null;
-}): Promise<OpenLeafResponse> {
-  await requireTauri("Opening ZIP entry");
+  destinationPath: string;
+}): Promise<SavedLeafResponse> {
+  await requireTauri("Saving ZIP entry");
   const contentUrl = params.contentUrl.trim();
   const filename = params.filename.trim();
   const entryName = params.entryName.trim();
+  const destinationPath = params.destinationPath.trim();
   if (!contentUrl) throw new Error("Missing Zenodo content URL.");
   if (!filename) throw new Error("Missing filename.");
   if (!entryName) throw new Error("Missing ZIP entry name.");
-  return invoke<OpenLeafResponse>("zenodo_zip_open_entry", {
+  if (!destinationPath) throw new Error("Choose where to save the file.");
+  return invoke<SavedLeafResponse>("zenodo_zip_save_entry_as", {
     contentUrl,
     filename,
     entryName,
-    openerAppPath: params.openerAppPath ?? null,
+    destinationPath,
   });
 }
 
@@ -646,6 +2550,21 @@ export async function zenodoZipInlineEntryMedia(params: {
   return invoke<InlineMediaResponse>("zenodo_zip_inline_entry_media", { contentUrl, filename, entryName });
 }
 
+export async function zenodoZipStreamEntryMedia(params: {
+  contentUrl: string;
+  filename: string;
+  entryName: string;
+}): Promise<StreamedMediaResponse> {
+  await requireTauri("Previewing ZIP media");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  const entryName = params.entryName.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  if (!entryName) throw new Error("Missing ZIP entry name.");
+  return invoke<StreamedMediaResponse>("zenodo_zip_stream_entry_media", { contentUrl, filename, entryName });
+}
+
 export async function zenodoTarListEntries(params: {
   contentUrl: string;
   filename: string;
@@ -662,11 +2581,78 @@ export async function zenodoTarListEntries(params: {
   return invoke<ZenodoTarEntryListResponse>("zenodo_tar_list_entries_paged", { contentUrl, filename, offset, length });
 }
 
+/** Finds the first file matching an exact path, a file name, or a glob (`*`, `?`, `**`). */
+export async function zenodoTarFindEntry(params: {
+  contentUrl: string;
+  filename: string;
+  query: string;
+}): Promise<ZenodoTarFindResponse> {
+  await requireTauri("Searching TAR entries");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  const query = params.query.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  if (!query) throw new Error("Provide an entry name or pattern.");
+  return invoke<ZenodoTarFindResponse>("zenodo_tar_find_entry", { contentUrl, filename, query });
+}
+
+/** Previews every TAR entry matching a glob such as `metadata/*.json`. */
+export async function zenodoTarPeekGlob(params: {
+  contentUrl: string;
+  filename: string;
+  glob: string;
+  maxEntries?: number;
+}): Promise<ZenodoGlobPeekResponse> {
+  await requireTauri("Previewing TAR entries");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  const glob = params.glob.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  if (!glob) throw new Error("Provide a glob pattern.");
+  return invoke<ZenodoGlobPeekResponse>("zenodo_tar_peek_glob", {
+    contentUrl,
+    filename,
+    glob,
+    maxEntries: params.maxEntries ?? null,
+  });
+}
+
+/**
+ * Scans the TAR to the end in the background; resolves to false if a scan is already running.
+ * Pass the file's Zenodo checksum to have the final progress event report whether it matched.
+ */
+export async function zenodoTarScanToEnd(params: {
+  contentUrl: string;
+  filename: string;
+  checksum?: string | null;
+}): Promise<boolean> {
+  await requireTauri("Scanning TAR archives");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  return invoke<boolean>("zenodo_tar_scan_to_end", {
+    contentUrl,
+    filename,
+    checksum: params.checksum?.trim() || null,
+  });
+}
+
+export async function zenodoTarCancelScan(contentUrl: string): Promise<boolean> {
+  await requireTauri("Cancelling TAR scans");
+  const trimmed = contentUrl.trim();
+  if (!trimmed) throw new Error("Missing Zenodo content URL.");
+  return invoke<boolean>("zenodo_tar_cancel_scan", { contentUrl: trimmed });
+}
+
 export async function zenodoTarPeekEntry(params: {
   contentUrl: string;
   filename: string;
   entryName: string;
-}): Promise<FieldPreview> {
+  offset?: number;
+} & PreviewLimits): Promise<FieldPreview> {
   await requireTauri("Previewing TAR entry");
   const contentUrl = params.contentUrl.trim();
   const filename = params.filename.trim();
@@ -674,7 +2660,13 @@ export async function zenodoTarPeekEntry(params: {
   if (!contentUrl) throw new Error("Missing Zenodo content URL.");
   if (!filename) throw new Error("Missing filename.");
   if (!entryName) throw new Error("Missing TAR entry name.");
-  return invoke<FieldPreview>("zenodo_tar_peek_entry", { contentUrl, filename, entryName });
+  return invoke<FieldPreview>("zenodo_tar_peek_entry", {
+    contentUrl,
+    filename,
+    entryName,
+    offset: params.offset ?? null,
+    ...previewLimitArgs(params),
+  });
 }
 
 export async function zenodoTarOpenEntry(params: {
@@ -698,6 +2690,29 @@ export async function zenodoTarOpenEntry(params: {
   });
 }
 
+export async function zenodoTarSaveEntryAs(params: {
+  contentUrl: string;
+  filename: string;
+  entryName: string;
+  destinationPath: string;
+}): Promise<SavedLeafResponse> {
+  await requireTauri("Saving TAR entry");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  const entryName = params.entryName.trim();
+  const destinationPath = params.destinationPath.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  if (!entryName) throw new Error("Missing TAR entry name.");
+  if (!destinationPath) throw new Error("Choose where to save the file.");
+  return invoke<SavedLeafResponse>("zenodo_tar_save_entry_as", {
+    contentUrl,
+    filename,
+    entryName,
+    destinationPath,
+  });
+}
+
 export async function zenodoTarInlineEntryMedia(params: {
   contentUrl: string;
   filename: string;
@@ -712,3 +2727,504 @@ export async function zenodoTarInlineEntryMedia(params: {
   if (!entryName) throw new Error("Missing TAR entry name.");
   return invoke<InlineMediaResponse>("zenodo_tar_inline_entry_media", { contentUrl, filename, entryName });
 }
+
+export async function zenodoTarStreamEntryMedia(params: {
+  contentUrl: string;
+  filename: string;
+  entryName: string;
+}): Promise<StreamedMediaResponse> {
+  await requireTauri("Previewing TAR media");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  const entryName = params.entryName.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  if (!entryName) throw new Error("Missing TAR entry name.");
+  return invoke<StreamedMediaResponse>("zenodo_tar_stream_entry_media", { contentUrl, filename, entryName });
+}
+
+export async function diffSchemas(params: { leftPath: string; rightPath: string }): Promise<SchemaDiffResponse> {
+  await requireTauri("Comparing schemas");
+  const leftPath = params.leftPath.trim();
+  const rightPath = params.rightPath.trim();
+  if (!leftPath || !rightPath) throw new Error("Provide two shard or dataset paths to compare.");
+  return invoke<SchemaDiffResponse>("diff_schemas", { leftPath, rightPath });
+}
+
+export async function inspectTorchFile(params: { path: string }): Promise<TorchArchiveSummary> {
+  await requireTauri("Inspecting PyTorch file");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing .pt/.pth file path.");
+  return invoke<TorchArchiveSummary>("inspect_torch_file", { path });
+}
+
+export async function inspectOnnxFile(params: { path: string }): Promise<OnnxSummary> {
+  await requireTauri("Inspecting ONNX model");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing .onnx file path.");
+  return invoke<OnnxSummary>("inspect_onnx_file", { path });
+}
+
+export async function inspectGgufFile(params: { path: string }): Promise<GgufSummary> {
+  await requireTauri("Inspecting GGUF file");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing .gguf file path.");
+  return invoke<GgufSummary>("inspect_gguf_file", { path });
+}
+
+export async function inspectRobotLog(params: {
+  path: string;
+  samplesPerTopic?: number;
+}): Promise<RobotLogSummary> {
+  await requireTauri("Inspecting robot log");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing .bag or .mcap file path.");
+  return invoke<RobotLogSummary>("inspect_robot_log", {
+    path,
+    samplesPerTopic: params.samplesPerTopic ?? null,
+  });
+}
+
+export async function exportRobotLogTopic(params: {
+  path: string;
+  topic: string;
+  format: RobotLogExportFormat;
+  destDir: string;
+  startTimeNs?: number | null;
+  endTimeNs?: number | null;
+}): Promise<RobotLogExportReport> {
+  await requireTauri("Exporting robot log topic");
+  const path = params.path.trim();
+  const topic = params.topic.trim();
+  const destDir = params.destDir.trim();
+  if (!path) throw new Error("Missing .bag or .mcap file path.");
+  if (!topic) throw new Error("Missing topic to export.");
+  if (!destDir) throw new Error("Choose a folder to export into.");
+  return invoke<RobotLogExportReport>("export_robot_log_topic", {
+    path,
+    topic,
+    startTimeNs: params.startTimeNs ?? null,
+    endTimeNs: params.endTimeNs ?? null,
+    format: params.format,
+    destDir,
+  });
+}
+
+export async function mboxPage(
+  params: { path: string; offset?: number; length?: number } & PreviewLimits,
+): Promise<ArchivePage> {
+  await requireTauri("Reading mbox messages");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing .mbox file path.");
+  return invoke<ArchivePage>("mbox_page", {
+    path,
+    offset: params.offset ?? null,
+    length: params.length ?? null,
+    ...previewLimitArgs(params),
+  });
+}
+
+export async function warcPage(
+  params: { path: string; offset?: number; length?: number } & PreviewLimits,
+): Promise<ArchivePage> {
+  await requireTauri("Reading WARC records");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing .warc file path.");
+  return invoke<ArchivePage>("warc_page", {
+    path,
+    offset: params.offset ?? null,
+    length: params.length ?? null,
+    ...previewLimitArgs(params),
+  });
+}
+
+export async function parquetQuery(params: {
+  path: string;
+  filter: string;
+  offset?: number;
+  limit?: number;
+}): Promise<ParquetQueryResult> {
+  await requireTauri("Querying Parquet");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing Parquet file or folder path.");
+  return invoke<ParquetQueryResult>("parquet_query", {
+    path,
+    filter: params.filter.trim(),
+    offset: params.offset ?? null,
+    limit: params.limit ?? null,
+  });
+}
+
+export async function runQuery(params: {
+  sql: string;
+  sources: QuerySource[];
+  offset?: number;
+  limit?: number;
+}): Promise<QueryResult> {
+  await requireTauri("Running SQL queries");
+  const sql = params.sql.trim();
+  if (!sql) throw new Error("Missing SQL query.");
+  return invoke<QueryResult>("run_query", {
+    sql,
+    sources: params.sources.map((source) => ({
+      path: source.path.trim(),
+      name: source.name?.trim() || null,
+    })),
+    offset: params.offset ?? null,
+    limit: params.limit ?? null,
+  });
+}
+
+export async function warehouseTables(params: {
+  kind: WarehouseKind;
+  location: string;
+  token?: string | null;
+}): Promise<WarehouseListing> {
+  await requireTauri("Listing warehouse tables");
+  const location = params.location.trim();
+  if (!location) throw new Error("Missing DuckDB file path or Flight endpoint.");
+  return invoke<WarehouseListing>("warehouse_tables", {
+    kind: params.kind,
+    location,
+    token: params.token?.trim() || null,
+  });
+}
+
+export async function warehousePage(params: {
+  kind: WarehouseKind;
+  location: string;
+  table: string;
+  offset?: number;
+  length?: number;
+  token?: string | null;
+}): Promise<WarehousePage> {
+  await requireTauri("Reading warehouse rows");
+  const location = params.location.trim();
+  if (!location) throw new Error("Missing DuckDB file path or Flight endpoint.");
+  if (!params.table) throw new Error("Missing table name.");
+  return invoke<WarehousePage>("warehouse_page", {
+    kind: params.kind,
+    location,
+    table: params.table,
+    offset: params.offset ?? null,
+    length: params.length ?? null,
+    token: params.token?.trim() || null,
+  });
+}
+
+export async function remoteListDir(params: {
+  url: string;
+  password?: string | null;
+}): Promise<RemoteDirListing> {
+  await requireTauri("Listing remote folder");
+  const url = params.url.trim();
+  if (!url) throw new Error("Missing sftp:// or WebDAV URL.");
+  return invoke<RemoteDirListing>("remote_list_dir", {
+    url,
+    password: params.password || null,
+  });
+}
+
+export async function remotePeekFile(
+  params: { url: string; password?: string | null; offset?: number } & PreviewLimits,
+): Promise<FieldPreview> {
+  await requireTauri("Previewing remote file");
+  const url = params.url.trim();
+  if (!url) throw new Error("Missing sftp:// or WebDAV URL.");
+  return invoke<FieldPreview>("remote_peek_file", {
+    url,
+    password: params.password || null,
+    offset: params.offset ?? null,
+    ...previewLimitArgs(params),
+  });
+}
+
+export async function remoteFetchFile(params: {
+  url: string;
+  password?: string | null;
+  jobId: string;
+}): Promise<RemoteFetchedFile> {
+  await requireTauri("Copying remote file");
+  const url = params.url.trim();
+  if (!url) throw new Error("Missing sftp:// or WebDAV URL.");
+  return invoke<RemoteFetchedFile>("remote_fetch_file", {
+    url,
+    password: params.password || null,
+    jobId: params.jobId,
+  });
+}
+
+export async function scanTree(params: {
+  path: string;
+  maxDepth?: number;
+}): Promise<DatasetTreeScan> {
+  await requireTauri("Scanning folder for datasets");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing folder path.");
+  return invoke<DatasetTreeScan>("scan_tree", {
+    path,
+    maxDepth: params.maxDepth ?? null,
+  });
+}
+
+export async function listWorkspaces(): Promise<Workspace[]> {
+  await requireTauri("Listing workspaces");
+  return invoke<Workspace[]>("list_workspaces");
+}
+
+export async function saveWorkspace(workspace: Workspace): Promise<Workspace[]> {
+  await requireTauri("Saving workspace");
+  if (!workspace.name.trim()) throw new Error("Give the workspace a name.");
+  return invoke<Workspace[]>("save_workspace", { workspace });
+}
+
+export async function deleteWorkspace(name: string): Promise<Workspace[]> {
+  await requireTauri("Removing workspace");
+  return invoke<Workspace[]>("delete_workspace", { name });
+}
+
+export async function refreshWorkspace(name: string): Promise<WorkspaceStatus> {
+  await requireTauri("Checking workspace");
+  return invoke<WorkspaceStatus>("refresh_workspace", { name });
+}
+
+export async function openWorkspaceRoot(params: {
+  name: string;
+  path: string;
+}): Promise<WorkspaceRootStatus> {
+  await requireTauri("Opening workspace dataset");
+  return invoke<WorkspaceRootStatus>("open_workspace_root", {
+    name: params.name,
+    path: params.path,
+  });
+}
+
+export async function shardBalance(params: {
+  path: string;
+  thresholdPct?: number;
+}): Promise<ShardBalance> {
+  await requireTauri("Measuring shard balance");
+  const path = params.path.trim();
+  if (!path) throw new Error("Missing index or shard directory path.");
+  return invoke<ShardBalance>("shard_balance", {
+    path,
+    thresholdPct: params.thresholdPct ?? null,
+  });
+}
+
+export async function zenodoTransferEstimate(params: {
+  contentUrl: string;
+  filename: string;
+  operation: "peek" | "open";
+  entryName?: string | null;
+  warnBytes?: number;
+}): Promise<ZenodoTransferEstimate> {
+  await requireTauri("Estimating download size");
+  const contentUrl = params.contentUrl.trim();
+  const filename = params.filename.trim();
+  if (!contentUrl) throw new Error("Missing Zenodo content URL.");
+  if (!filename) throw new Error("Missing filename.");
+  return invoke<ZenodoTransferEstimate>("zenodo_transfer_estimate", {
+    contentUrl,
+    filename,
+    operation: params.operation,
+    entryName: params.entryName?.trim() || null,
+    warnBytes: params.warnBytes ?? null,
+  });
+}
+
+/** Extracts the selected fields into one session folder and opens the folder once. */
+export async function openMany(params: { leaves: SampleLocation[]; token?: string | null }): Promise<OpenedSession> {
+  await requireTauri("Opening several fields");
+  if (params.leaves.length === 0) throw new Error("Select at least one field.");
+  return invoke<OpenedSession>("open_many", {
+    leaves: params.leaves,
+    token: params.token?.trim() || null,
+  });
+}
+
+export async function annotateSample(params: {
+  source: string;
+  shard: string;
+  sample: string;
+  tag: string;
+  note?: string | null;
+}): Promise<Annotation> {
+  await requireTauri("Annotating sample");
+  const tag = params.tag.trim();
+  if (!tag) throw new Error("Provide a tag for the annotation.");
+  return invoke<Annotation>("annotate_sample", { ...params, tag, note: params.note ?? null });
+}
+
+export async function removeAnnotation(id: number): Promise<boolean> {
+  await requireTauri("Removing annotation");
+  return invoke<boolean>("remove_annotation", { id });
+}
+
+export async function listAnnotations(params: {
+  source?: string | null;
+  shard?: string | null;
+  sample?: string | null;
+} = {}): Promise<Annotation[]> {
+  if (!isTauri()) return [];
+  return invoke<Annotation[]>("list_annotations", {
+    source: params.source ?? null,
+    shard: params.shard ?? null,
+    sample: params.sample ?? null,
+  });
+}
+
+export async function exportAnnotations(params: {
+  source?: string | null;
+  format: AnnotationExportFormat;
+  destPath: string;
+}): Promise<AnnotationExportReport> {
+  await requireTauri("Exporting annotations");
+  const destPath = params.destPath.trim();
+  if (!destPath) throw new Error("Choose where to save the annotations.");
+  return invoke<AnnotationExportReport>("export_annotations", {
+    source: params.source ?? null,
+    format: params.format,
+    destPath,
+  });
+}
+
+/** Writes the sample table as shown (after filtering) to CSV or JSONL for pandas and friends. */
+export async function exportListing(params: {
+  rows: ListingRow[];
+  format: ListingExportFormat;
+  destPath: string;
+}): Promise<ListingExportReport> {
+  await requireTauri("Exporting the sample listing");
+  const destPath = params.destPath.trim();
+  if (!destPath) throw new Error("Choose where to save the listing.");
+  return invoke<ListingExportReport>("export_listing", {
+    rows: params.rows.map((row) => ({ ...row, metadata: row.metadata ?? {} })),
+    format: params.format,
+    destPath,
+  });
+}
+
+/** The newest access log entries matching the filters, oldest first. Times are Unix seconds. */
+export async function queryAccessLog(params: {
+  host?: string | null;
+  since?: number | null;
+  until?: number | null;
+  limit?: number | null;
+} = {}): Promise<AccessLogEntry[]> {
+  if (!isTauri()) return [];
+  return invoke<AccessLogEntry[]>("query_access_log", {
+    host: params.host?.trim() || null,
+    since: params.since ?? null,
+    until: params.until ?? null,
+    limit: params.limit ?? null,
+  });
+}
+
+export async function exportAccessLog(params: {
+  host?: string | null;
+  since?: number | null;
+  until?: number | null;
+  format: AccessLogExportFormat;
+  destPath: string;
+}): Promise<AccessLogExportReport> {
+  await requireTauri("Exporting the access log");
+  const destPath = params.destPath.trim();
+  if (!destPath) throw new Error("Choose where to save the access log.");
+  return invoke<AccessLogExportReport>("export_access_log", {
+    host: params.host?.trim() || null,
+    since: params.since ?? null,
+    until: params.until ?? null,
+    format: params.format,
+    destPath,
+  });
+}
+
+export async function generateReport(params: {
+  path: string;
+  format: ReportFormat;
+  destPath: string;
+  sampleCount?: number | null;
+  includeValidation?: boolean | null;
+}): Promise<ReportResult> {
+  await requireTauri("Generating dataset report");
+  const path = params.path.trim();
+  const destPath = params.destPath.trim();
+  if (!path) throw new Error("Provide a dataset path to report on.");
+  if (!destPath) throw new Error("Choose where to save the report.");
+  return invoke<ReportResult>("generate_report", {
+    path,
+    format: params.format,
+    destPath,
+    options: {
+      sampleCount: params.sampleCount ?? null,
+      includeValidation: params.includeValidation ?? null,
+    },
+  });
+}
+
+export async function pythonSnippet(location: SampleLocation): Promise<PythonSnippet> {
+  await requireTauri("Generating Python snippet");
+  return invoke<PythonSnippet>("python_snippet", { location });
+}
+
+/** `dataset-inspector://sample/<kind>?source=…&shard=…&sample=…` for sharing with teammates. */
+export async function sampleLocator(location: SampleLocation): Promise<string> {
+  await requireTauri("Copying sample locator");
+  return invoke<string>("sample_locator", { location });
+}
+
+export async function parseSampleLocator(locator: string): Promise<SampleLocation> {
+  await requireTauri("Opening sample locator");
+  const trimmed = locator.trim();
+  if (!trimmed) throw new Error("Paste a sample locator.");
+  return invoke<SampleLocation>("parse_sample_locator", { locator: trimmed });
+}
+
+export async function readDeclaredMetadata(params: { path: string }): Promise<DeclaredMetadata[]> {
+  await requireTauri("Reading declared metadata");
+  const path = params.path.trim();
+  if (!path) throw new Error("Provide a dataset directory or croissant.json path.");
+  return invoke<DeclaredMetadata[]>("read_declared_metadata", { path });
+}
+
+export async function compareDeclaredSchema(params: {
+  path: string;
+  recordSet?: string | null;
+}): Promise<DeclaredSchemaComparison> {
+  await requireTauri("Comparing declared schema");
+  const path = params.path.trim();
+  if (!path) throw new Error("Provide a dataset directory or croissant.json path.");
+  return invoke<DeclaredSchemaComparison>("compare_declared_schema", {
+    path,
+    recordSet: params.recordSet?.trim() || null,
+  });
+}
+
+export async function zenodoDeclaredMetadata(params: { input: string }): Promise<DeclaredMetadata[]> {
+  await requireTauri("Reading Zenodo declared metadata");
+  const input = params.input.trim();
+  if (!input) throw new Error("Provide a Zenodo record URL like https://zenodo.org/records/<id>.");
+  return invoke<DeclaredMetadata[]>("zenodo_declared_metadata", { input });
+}
+
+export async function localDatasetDocs(params: { path: string }): Promise<DatasetDocsResponse> {
+  await requireTauri("Reading dataset documentation");
+  const path = params.path.trim();
+  if (!path) throw new Error("Provide a dataset directory.");
+  return invoke<DatasetDocsResponse>("local_dataset_docs", { path });
+}
+
+export async function zenodoRecordDocs(params: { input: string }): Promise<DatasetDocsResponse> {
+  await requireTauri("Reading Zenodo record documentation");
+  const input = params.input.trim();
+  if (!input) throw new Error("Provide a Zenodo record URL like https://zenodo.org/records/<id>.");
+  return invoke<DatasetDocsResponse>("zenodo_record_docs", { input });
+}
+
+export async function hfDatasetDocs(params: { input: string; token?: string | null }): Promise<DatasetDocsResponse> {
+  await requireTauri("Reading Hugging Face dataset card");
+  const input = params.input.trim();
+  if (!input) throw new Error("Provide a Hugging Face dataset URL or hf://datasets/... URI.");
+  return invoke<DatasetDocsResponse>("hf_dataset_docs", { input, token: params.token ?? null });
+}
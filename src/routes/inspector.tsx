@@ -52,6 +52,7 @@ import {
   readHfToken,
   readPreferredOpenerForExt,
   readLastIndex,
+  readTrustedRepositoryHosts,
   saveHfToken,
   saveLastIndex,
   savePreferredOpenerForExt,
@@ -64,12 +65,12 @@ import {
   zenodoOpenFile,
   zenodoPeekFile,
   zenodoRecordSummary,
-  zenodoTarInlineEntryMedia,
+  zenodoTarStreamEntryMedia,
   zenodoTarListEntries,
   zenodoTarOpenEntry,
   zenodoTarPeekEntry,
   zenodoZipListEntries,
-  zenodoZipInlineEntryMedia,
+  zenodoZipStreamEntryMedia,
   zenodoZipOpenEntry,
   zenodoZipPeekEntry,
   type HfConfigSummary,
@@ -80,7 +81,8 @@ import {
   type ItemMeta,
   type WdsDirSummary,
   type WdsSampleListResponse,
-  type InlineMediaResponse,
+  streamedMediaUrl,
+  type StreamedMediaResponse,
   type ZenodoFileSummary,
   type ZenodoRecordSummary,
   type ZenodoTarEntryListResponse,
@@ -200,13 +202,20 @@ const looksLikeHfInput = (value: string) => {
   return false;
 };
 
-const looksLikeZenodoInput = (value: string) => {
+// Mirrors the backend check: zenodo.org, its subdomains, and the user's trusted InvenioRDM hosts.
+const isRepositoryHost = (host: string, trustedHosts: string[]) =>
+  host === "zenodo.org" ||
+  host.endsWith(".zenodo.org") ||
+  trustedHosts.some((trusted) => host === trusted || host.endsWith(`.${trusted}`));
+
+const looksLikeZenodoInput = (value: string, trustedHosts: string[]) => {
   const v = value.trim();
   if (!v) return false;
+  if (/^(doi:|https?:\/\/(dx\.)?doi\.org\/)?10\.(5281|5072)\/zenodo\.[0-9]+$/i.test(v)) return true;
   try {
     const u = new URL(v);
     const host = u.hostname.toLowerCase();
-    if (!(host === "zenodo.org" || host.endsWith(".zenodo.org"))) return false;
+    if (!isRepositoryHost(host, trustedHosts)) return false;
     const segments = u.pathname.split("/").filter(Boolean);
     for (let i = 0; i < segments.length; i += 1) {
       if (segments[i] !== "records" && segments[i] !== "record") continue;
@@ -313,7 +322,9 @@ function looksLikeTarFilename(name: string) {
     n.endsWith(".tar.gz") ||
     n.endsWith(".tgz") ||
     n.endsWith(".tar.zst") ||
-    n.endsWith(".tar.zstd")
+    n.endsWith(".tar.zstd") ||
+    // First volume of a split archive; the backend reads the following volumes in order.
+    /\.(tar|tar\.gz|tgz|tar\.zst|tar\.zstd)\.(part)?(aa+|00+|001)$/.test(n)
   );
 }
 
@@ -367,6 +378,7 @@ export default function InspectorPage() {
   const tauri = useMemo(() => isTauri(), []);
 
   const [hfToken, setHfToken] = useState<string | null>(null);
+  const [trustedHosts, setTrustedHosts] = useState<string[]>([]);
   const hfTokenMasked = hfToken ? `…${hfToken.slice(-6)}` : null;
   const [hfTokenDialogOpen, setHfTokenDialogOpen] = useState(false);
   const [hfOffsetDraft, setHfOffsetDraft] = useState(String(hfOffset));
@@ -410,7 +422,7 @@ export default function InspectorPage() {
   const isZenodoMode = mode?.kind === "zenodo";
   const autodetectedHf = sourceKind === "auto" && looksLikeHfInput(sourceInput) && chunkSelection.length === 0;
   const autodetectedZenodo =
-    sourceKind === "auto" && looksLikeZenodoInput(sourceInput) && chunkSelection.length === 0;
+    sourceKind === "auto" && looksLikeZenodoInput(sourceInput, trustedHosts) && chunkSelection.length === 0;
 
   const latestSourceInputRef = useRef(sourceInput);
   useEffect(() => {
@@ -446,6 +458,13 @@ export default function InspectorPage() {
       .catch((err) => console.error("Unable to read HF token:", err));
   }, []);
 
+  useEffect(() => {
+    if (!isTauri()) return;
+    void readTrustedRepositoryHosts()
+      .then((hosts) => setTrustedHosts(hosts))
+      .catch((err) => console.error("Unable to read trusted hosts:", err));
+  }, []);
+
   useEffect(() => {
     if (!hfTokenDialogOpen) return;
     tokenForm.update({
@@ -568,7 +587,12 @@ export default function InspectorPage() {
   useEffect(() => {
     if (zenodoQuery.data) {
       const count = zenodoQuery.data.files.length;
-      setStatusMessage(`Loaded Zenodo record ${zenodoQuery.data.recordId} · ${count} file${count === 1 ? "" : "s"}.`);
+      const { recordId, requestedRecordId } = zenodoQuery.data;
+      const sandbox = zenodoQuery.data.sandbox ? " (sandbox)" : "";
+      const resolved = recordId !== requestedRecordId ? ` (latest version of ${requestedRecordId})` : "";
+      setStatusMessage(
+        `Loaded Zenodo record ${recordId}${resolved}${sandbox} · ${count} file${count === 1 ? "" : "s"}.`,
+      );
     }
   }, [setStatusMessage, zenodoQuery.data]);
 
@@ -975,7 +999,7 @@ export default function InspectorPage() {
     mutationFn: async () => {
       if (!selectedZenodoFile) throw new Error("Select a Zenodo ZIP file.");
       if (!selectedZenodoEntry || selectedZenodoEntry.isDir) throw new Error("Select a ZIP entry.");
-      return zenodoZipInlineEntryMedia({
+      return zenodoZipStreamEntryMedia({
         contentUrl: selectedZenodoFile.contentUrl,
         filename: selectedZenodoFile.key,
         entryName: selectedZenodoEntry.name,
@@ -987,7 +1011,7 @@ export default function InspectorPage() {
     mutationFn: async () => {
       if (!selectedZenodoFile) throw new Error("Select a Zenodo TAR file.");
       if (!selectedZenodoEntry || (selectedZenodoEntry as ZenodoTarEntrySummary).isDir) throw new Error("Select a TAR entry.");
-      return zenodoTarInlineEntryMedia({
+      return zenodoTarStreamEntryMedia({
         contentUrl: selectedZenodoFile.contentUrl,
         filename: selectedZenodoFile.key,
         entryName: selectedZenodoEntry.name,
@@ -995,11 +1019,9 @@ export default function InspectorPage() {
     },
   });
 
-  const inlineMediaToObjectUrl = async (result: InlineMediaResponse) => {
+  const inlineMediaToObjectUrl = async (result: StreamedMediaResponse) => {
     const mime = result.mime || "application/octet-stream";
-    const dataUrl = `data:${mime};base64,${result.base64}`;
-    const blob = await (await fetch(dataUrl)).blob();
-    return { src: URL.createObjectURL(blob), mime, ext: result.ext };
+    return { src: streamedMediaUrl(result), mime, ext: result.ext };
   };
 
   const loadZenodoZipInlineMedia = async () => {
@@ -1320,7 +1342,7 @@ export default function InspectorPage() {
     const trimmed = sourceInput.trim();
     if (!trimmed) return;
 
-    if (sourceKind === "zenodo" || (sourceKind === "auto" && looksLikeZenodoInput(trimmed))) {
+    if (sourceKind === "zenodo" || (sourceKind === "auto" && looksLikeZenodoInput(trimmed, trustedHosts))) {
       triggerLoad("zenodo", trimmed);
       return;
     }